@@ -1,28 +1,272 @@
+/// Prefix of the symbols and type names exported to C, can be overridden by setting
+/// `BDK_FFI_SYMBOL_PREFIX`
+#[cfg(feature = "c")]
+const DEFAULT_SYMBOL_PREFIX: &str = "bdk_";
+
 #[cfg(feature = "c")]
 fn c_build_rs() {
     println!("cargo:rerun-if-changed=derive/");
     println!("cargo:rerun-if-changed=src/");
 
     use std::env;
+    use std::fs;
+    use std::path::PathBuf;
 
-    use cbindgen::{Builder, Language};
+    use cbindgen::{Builder, Config, Language};
 
-    // TODO: the directory can be read-only, use OUT_DIR
     let crate_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let crate_name = env::var("CARGO_PKG_NAME").unwrap();
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+
+    // `bdk-ffi` -> `bdk_ffi.h`, `BDK_FFI_H`, `BDK_FFI_VERSION`, ...
+    let lib_name = crate_name.replace('-', "_");
+    let macro_prefix = lib_name.to_uppercase();
+
+    // Optional directory the header is copied to, for consumers that don't read our metadata
+    let header_dir_var = format!("{}_HEADER_DIR", macro_prefix);
+    println!("cargo:rerun-if-env-changed={}", header_dir_var);
+    // Same for the Swift source, which is written by the `expose_*` macros
+    #[cfg(feature = "swift")]
+    println!("cargo:rerun-if-env-changed={}_SWIFT_DIR", macro_prefix);
+    // And for the Go sources
+    #[cfg(feature = "go")]
+    println!("cargo:rerun-if-env-changed={}_GO_DIR", macro_prefix);
+    // And for the C# source
+    #[cfg(feature = "csharp")]
+    println!("cargo:rerun-if-env-changed={}_CSHARP_DIR", macro_prefix);
+    // And for the Dart library
+    #[cfg(feature = "dart")]
+    println!("cargo:rerun-if-env-changed={}_DART_DIR", macro_prefix);
+
+    // Read by the `expose_*` macros when naming the exported functions, while cbindgen applies
+    // the same prefix to the type names
+    let prefix_var = format!("{}_SYMBOL_PREFIX", macro_prefix);
+    println!("cargo:rerun-if-env-changed={}", prefix_var);
+    let symbol_prefix = env::var(&prefix_var).unwrap_or_else(|_| DEFAULT_SYMBOL_PREFIX.into());
+    println!("cargo:rustc-env=EXPOSE_C_PREFIX={}", symbol_prefix);
+
+    let major = env::var("CARGO_PKG_VERSION_MAJOR").unwrap();
+    let minor = env::var("CARGO_PKG_VERSION_MINOR").unwrap();
+
+    // `ffi_abi_hash()` and `ffi_version()` are part of the runtime, `FFI_ABI_HASH` is appended
+    // once the header is generated. The check macros are only expanded by the caller, after the
+    // declarations
+    let version_defines = format!(
+        "\n#define {prefix}_VERSION \"{version}\"\n\
+         #define {prefix}_VERSION_MAJOR {major}\n\
+         #define {prefix}_VERSION_MINOR {minor}\n\
+         #define {prefix}_VERSION_PATCH {patch}\n\
+         \n\
+         #define {prefix}_ABI_COMPATIBLE() ({symbols}ffi_abi_hash() == {symbols}FFI_ABI_HASH)\n\
+         #define {prefix}_CHECK_ABI() do {{ \\\n\
+         \x20   if (!{prefix}_ABI_COMPATIBLE()) {{ \\\n\
+         \x20       fprintf(stderr, \"{crate_name}: the ABI of the loaded library (version %s) doesn't match the header (version %s)\\n\", \\\n\
+         \x20               {symbols}ffi_version(), {prefix}_VERSION); \\\n\
+         \x20       abort(); \\\n\
+         \x20   }} \\\n\
+         }} while (0)",
+        prefix = macro_prefix,
+        symbols = symbol_prefix,
+        crate_name = crate_name,
+        version = env::var("CARGO_PKG_VERSION").unwrap(),
+        major = major,
+        minor = minor,
+        patch = env::var("CARGO_PKG_VERSION_PATCH").unwrap(),
+    );
+
+    // Version the SONAME, so that the dynamic loader refuses a library with a different ABI
+    // instead of letting callers misinterpret it. Before 1.0 every minor version can break it
+    let target_os = env::var("CARGO_CFG_TARGET_OS").unwrap();
+    if target_os == "linux" || target_os == "android" {
+        let soversion = match major.as_str() {
+            "0" => format!("0.{}", minor),
+            _ => major.clone(),
+        };
+        let soname = format!("lib{}.so.{}", lib_name, soversion);
+        println!("cargo:rustc-cdylib-link-arg=-Wl,-soname,{}", soname);
+
+        // Programs linked with `-lbdk_ffi` load the library by its SONAME, so link that name to
+        // the library in the target directory, like `ldconfig` does where it's installed.
+        // `OUT_DIR` is `<target dir>/build/<pkg>/out`
+        if let Some(target_dir) = out_dir.ancestors().nth(3) {
+            let link = target_dir.join(&soname);
+            let _ = fs::remove_file(&link);
+            std::os::unix::fs::symlink(format!("lib{}.so", lib_name), &link)
+                .expect("Unable to link the library to its SONAME");
+        }
+    }
+
+    let mut config = Config {
+        // Wrap the declarations in `extern "C"` when included from C++
+        cpp_compat: true,
+        // For `fprintf` in `{prefix}_CHECK_ABI()`
+        sys_includes: vec!["stdio.h".into()],
+        // The getters of `shared` fields are the only ones that don't return a borrowed pointer
+        header: Some(format!(
+            "/*\n \
+             * Opaque structs returned by value are owned by the caller, and freed with\n \
+             * `{symbols}<type>_destroy`, or `{symbols}<type>_release` for `shared` structs.\n \
+             * Getters return pointers borrowed from the parent struct, except for the `shared`\n \
+             * structs: those are returned with a new reference, given back with\n \
+             * `{symbols}<type>_release`.\n \
+             */",
+            symbols = symbol_prefix,
+        )),
+        ..Default::default()
+    };
+    config.export.prefix = Some(symbol_prefix.clone());
 
-    Builder::new()
+    // Returned by the runtime's `ffi_abi_hash()`. Written before generating the bindings, because
+    // cbindgen expands the crate with this build script, and only known after
+    let abi_hash_file = out_dir.join("abi_hash.rs");
+    fs::write(&abi_hash_file, "0").expect("Unable to write the ABI hash");
+
+    let header = out_dir.join(format!("{}.h", lib_name));
+    let mut bindings = Builder::new()
+        .with_config(config)
         .with_crate(crate_dir)
         .with_language(Language::C)
-        .with_parse_expand(&["bdk-ffi"])
+        .with_include_guard(format!("{}_H", macro_prefix))
+        .with_parse_expand(&[crate_name.as_str()])
         .with_parse_expand_features(&["c"])
         .generate()
-        .expect("Unable to generate bindings")
-        .write_to_file("c/bindings.h");
+        .expect("Unable to generate bindings");
+
+    // Hash every declaration of the header, the runtime's included, so it changes whenever the
+    // ABI does and only then
+    let mut declarations = vec![];
+    bindings.write(&mut declarations);
+    let hash = abi_hash(&String::from_utf8(declarations).unwrap());
+    fs::write(&abi_hash_file, format!("{:#018x}", hash)).expect("Unable to write the ABI hash");
+
+    bindings.config.after_includes = Some(format!(
+        "{}\n\n#define {}FFI_ABI_HASH {:#018x}ULL",
+        version_defines, symbol_prefix, hash
+    ));
+    bindings.write_to_file(&header);
+
+    // Exposed to crates that depend on us as `DEP_BDK_FFI_HEADER` and `DEP_BDK_FFI_INCLUDE`
+    println!("cargo:header={}", header.display());
+    println!("cargo:include={}", out_dir.display());
+
+    if let Some(dir) = env::var_os(&header_dir_var) {
+        let dir = PathBuf::from(dir);
+        fs::create_dir_all(&dir).expect("Unable to create the header directory");
+        fs::copy(&header, dir.join(header.file_name().unwrap()))
+            .expect("Unable to copy the header");
+    }
+}
+
+/// 64-bit FNV-1a of the declarations in `header`, which unlike `DefaultHasher` is stable across
+/// compilers. Comments and blank lines are skipped, so documenting the API doesn't change the hash
+#[cfg(feature = "c")]
+fn abi_hash(header: &str) -> u64 {
+    let mut code = String::with_capacity(header.len());
+    let mut rest = header;
+    while let Some(start) = rest.find("/*") {
+        code.push_str(&rest[..start]);
+        rest = rest[start..].find("*/").map_or("", |end| &rest[start + end + 2..]);
+    }
+    code.push_str(rest);
+
+    let lines = code
+        .lines()
+        .map(|line| line.split("//").next().unwrap().trim())
+        .filter(|line| !line.is_empty());
+
+    let mut hash = 0xcbf2_9ce4_8422_2325u64;
+    for byte in lines.flat_map(|line| line.bytes().chain(Some(b'\n'))) {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+
+    hash
+}
+
+/// Package of the generated Kotlin and Java sources, can be overridden by setting
+/// `BDK_FFI_JAVA_PACKAGE`
+#[cfg(feature = "jni")]
+const DEFAULT_JAVA_PACKAGE: &str = "org.bitcoindevkit";
+
+#[cfg(feature = "jni")]
+fn jni_build_rs() {
+    use std::env;
+
+    let crate_name = env::var("CARGO_PKG_NAME").unwrap();
+    let macro_prefix = crate_name.replace('-', "_").to_uppercase();
+
+    // Optional directory the sources are copied to by the `expose_mod` macro
+    println!("cargo:rerun-if-env-changed={}_JNI_DIR", macro_prefix);
+
+    // Read by the `expose_*` macros when naming the `Java_*` entry points, which must match the
+    // package of the generated classes
+    let package_var = format!("{}_JAVA_PACKAGE", macro_prefix);
+    println!("cargo:rerun-if-env-changed={}", package_var);
+    let package = env::var(&package_var).unwrap_or_else(|_| DEFAULT_JAVA_PACKAGE.into());
+    println!("cargo:rustc-env=EXPOSE_JNI_PACKAGE={}", package);
+}
+
+#[cfg(feature = "python")]
+fn python_build_rs() {
+    use std::env;
+    use std::process::Command;
+
+    // The extension module is loaded by the interpreter, while the tests embed it and have to be
+    // linked to `libpython`. Same interpreter as pyo3
+    println!("cargo:rerun-if-env-changed=PYO3_PYTHON");
+    let python = env::var("PYO3_PYTHON").unwrap_or_else(|_| "python3".into());
+    let output = Command::new(&python)
+        .args([
+            "-c",
+            "import sysconfig; print(sysconfig.get_config_var('LIBDIR')); \
+             print(sysconfig.get_config_var('LDVERSION'))",
+        ])
+        .output();
+    let config = match output {
+        Ok(output) if output.status.success() => {
+            String::from_utf8_lossy(&output.stdout).into_owned()
+        }
+        // The extension module itself doesn't need it
+        _ => return,
+    };
+    let mut lines = config.lines();
+    if let (Some(lib_dir), Some(version)) = (lines.next(), lines.next()) {
+        println!("cargo:rustc-link-arg-tests=-L{}", lib_dir);
+        println!("cargo:rustc-link-arg-tests=-Wl,-rpath,{}", lib_dir);
+        println!("cargo:rustc-link-arg-tests=-lpython{}", version);
+    }
+}
+
+#[cfg(feature = "node")]
+fn node_build_rs() {
+    use std::env;
+
+    let crate_name = env::var("CARGO_PKG_NAME").unwrap();
+    let macro_prefix = crate_name.replace('-', "_").to_uppercase();
+
+    // Optional directory `index.d.ts` is copied to by the `expose_mod` macro
+    println!("cargo:rerun-if-env-changed={}_NODE_DIR", macro_prefix);
+
+    // The N-API symbols are resolved when the addon is loaded by Node
+    napi_build::setup();
 }
 
 fn main() {
     println!("cargo:rerun-if-changed=build.rs");
 
+    // Directory the `expose_mod` macro copies the API description to
+    #[cfg(feature = "idl")]
+    println!(
+        "cargo:rerun-if-env-changed={}_IDL_DIR",
+        std::env::var("CARGO_PKG_NAME").unwrap().replace('-', "_").to_uppercase()
+    );
+
     #[cfg(feature = "c")]
     c_build_rs();
+    #[cfg(feature = "python")]
+    python_build_rs();
+    #[cfg(feature = "jni")]
+    jni_build_rs();
+    #[cfg(feature = "node")]
+    node_build_rs();
 }
@@ -1,24 +1,87 @@
-#include <assert.h>
 #include <stdio.h>
 
-#include "bindings.h"
+#include "bdk_ffi.h"
+
+static void log_record(void *user_data, int32_t level, const char *target, const char *message) {
+    (void) user_data;
+    fprintf(stderr, "[%d] %s: %s\n", level, target, message);
+}
 
 int main() {
-    Inner i = { .val = 10 };
+    // Abort if the loaded library doesn't match the header we were built against
+    BDK_FFI_CHECK_ABI();
+
+    bdk_install_logger(log_record, NULL, bdk_LOG_LEVEL_INFO);
+
+    // Transparent structs are declared in the header, and can be accessed directly
+    bdk_Inner i = { .val = 10 };
+
+    bdk_Outer *o = NULL;
+    bdk_outer_new(&i, 42, &o);
+
+    printf("%u\n", bdk_outer_get_inner(o)->val);
+    bdk_outer_get_inner(o)->val *= 5;
+    printf("%u\n", bdk_outer_get_inner(o)->val);
+
+    bdk_Inner i2 = { .val = 1000 };
+    bdk_outer_set_inner(o, &i2);
+
+    printf("%u\n", bdk_outer_get_inner(o)->val);
+
+    printf("%u\n", bdk_outer_get_value(o));
+
+    bdk_outer_destroy(o);
+
+    bdk_Address *a = NULL;
+    int32_t rc = bdk_address_new("32qQDeCM67ZyLT1EYvv9HHTG9bXHLVK1gf", &a);
+    if (rc != 0) {
+        char *error = bdk_last_error_message();
+        fprintf(stderr, "Invalid address: %s\n", error);
+        bdk_string_free(error);
+        return 1;
+    }
+
+    // Strings returned by the library are owned by the caller
+    char *address = bdk_address_to_string(a);
+    printf("%s\n", address);
+    bdk_string_free(address);
+
+    // Borrowed from the address, which must outlive it
+    char *hex = bdk_script_to_hex(bdk_address_get_script(a));
+    printf("%s\n", hex);
+    bdk_string_free(hex);
 
-    Outer *o = NULL;
-    outer_new(&i, 42, &o);
+    // Rejected by the setter's validation, which leaves the address unchanged
+    bdk_Script *empty = NULL;
+    bdk_script_new_empty(&empty);
+    if (bdk_address_set_script(a, empty) != 0) {
+        char *error = bdk_last_error_message();
+        printf("rejected: %s\n", error);
+        bdk_string_free(error);
+    }
+    bdk_script_destroy(empty);
 
-    printf("%u\n", outer_get_inner(o)->val);
-    outer_get_inner(o)->val *= 5;
-    printf("%u\n", outer_get_inner(o)->val);
+    bdk_address_destroy(a);
 
-    Inner i2 = { .val = 1000 };
-    outer_set_inner(o, &i2);
+    bdk_OutPoint *op = NULL;
+    rc = bdk_out_point_new_from_string(
+        "e567952fb6cc33857f392efa3a46c995a28f69cca4bb1b37e0204dab1ec7a389:1", &op);
+    if (rc != 0) {
+        char *error = bdk_last_error_message();
+        fprintf(stderr, "Invalid outpoint: %s\n", error);
+        bdk_string_free(error);
+        return 1;
+    }
 
-    printf("%u\n", outer_get_inner(o)->val);
+    // `Txid` is shared: the getter returns a new reference that outlives the outpoint
+    bdk_Txid *t = bdk_out_point_get_txid(op);
+    bdk_out_point_destroy(op);
 
-    printf("%u\n", outer_get_value(o));
+    bdk_Txid *t2 = bdk_txid_retain(t);
+    bdk_txid_release(t);
 
-    outer_destroy(o);
+    char *txid = bdk_txid_to_string(t2);
+    printf("%s\n", txid);
+    bdk_string_free(txid);
+    bdk_txid_release(t2);
 }
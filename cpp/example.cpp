@@ -0,0 +1,41 @@
+#include <cassert>
+#include <iostream>
+
+#include "bdk_ffi_bitcoin.hpp"
+
+using namespace bdk_ffi::bitcoin;
+
+int main() {
+    Address address("32qQDeCM67ZyLT1EYvv9HHTG9bXHLVK1gf");
+    std::cout << address.to_string() << std::endl;
+
+    // `Txid` is shared: the copy keeps it alive after the outpoint is destroyed
+    Txid txid = OutPoint::from_string(
+        "e567952fb6cc33857f392efa3a46c995a28f69cca4bb1b37e0204dab1ec7a389:1").get_txid();
+    Txid copy = txid;
+    std::cout << copy.to_string() << std::endl;
+
+    Script script = Script::from_hex("a9140c8e1071fea96ca41f2b6735ef63a882187d39e087");
+    std::cout << script.to_hex() << std::endl;
+
+    Script joined = script + Script::from_hex("51");
+    std::cout << joined.asm_() << std::endl;
+
+    try {
+        joined[1000];
+        assert(false);
+    } catch (const bdk_ffi::IndexError &e) {
+        std::cout << "caught " << e.what() << std::endl;
+    }
+
+    try {
+        Network network("not a network");
+        assert(false);
+    } catch (const bdk_ffi::Exception &e) {
+        std::cout << "caught " << e.what() << std::endl;
+    }
+
+    auto from_script = Address::from_script(script, Network::testnet());
+    assert(from_script.has_value());
+    std::cout << from_script->to_string() << std::endl;
+}
@@ -0,0 +1,59 @@
+using System;
+using Xunit;
+
+using BdkFfi;
+
+public class BdkFfiTests
+{
+    [Fact]
+    public void TestAddress()
+    {
+        using var address = new Address("32qQDeCM67ZyLT1EYvv9HHTG9bXHLVK1gf");
+        Assert.Equal("32qQDeCM67ZyLT1EYvv9HHTG9bXHLVK1gf", address.ToString());
+    }
+
+    [Fact]
+    public void TestSharedTxidOutlivesOutPoint()
+    {
+        var outPoint = OutPoint.FromString("e567952fb6cc33857f392efa3a46c995a28f69cca4bb1b37e0204dab1ec7a389:1");
+        // `Txid` is shared: the getter takes a new reference
+        using var txid = outPoint.Txid;
+        outPoint.Dispose();
+        Assert.Equal("e567952fb6cc33857f392efa3a46c995a28f69cca4bb1b37e0204dab1ec7a389", txid.ToString());
+    }
+
+    [Fact]
+    public void TestScriptFromBytes()
+    {
+        using var script = new Script(new byte[] { 0x51 });
+        Assert.Equal("51", script.ToHex());
+        Assert.Equal(script.ToHex(), Script.FromHex("51").ToHex());
+    }
+
+    [Fact]
+    public void TestOperators()
+    {
+        using var joined = Script.FromHex("51") + Script.FromHex("52");
+        Assert.Equal("5152", joined.ToHex());
+        Assert.Equal(0x52, joined[1]);
+        var error = Assert.Throws<IndexErrorException>(() => joined[1000]);
+        Assert.Equal(-1, error.Code);
+    }
+
+    [Fact]
+    public void TestErrors()
+    {
+        Assert.Throws<BitcoinErrorException>(() => new Network("not a network"));
+        Assert.IsAssignableFrom<BdkFfiException>(
+            Record.Exception(() => Script.FromHex("not hex")));
+    }
+
+    [Fact]
+    public void TestFromScript()
+    {
+        using var script = Script.FromHex("a9140c8e1071fea96ca41f2b6735ef63a882187d39e087");
+        using var testnet = Address.FromScript(script, Network.Testnet());
+        Assert.NotNull(testnet);
+        Assert.True(Network.Testnet().IsTestnet());
+    }
+}
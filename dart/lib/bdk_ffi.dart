@@ -0,0 +1,4 @@
+/// Bindings for bdk-ffi, over its C API
+library bdk_ffi;
+
+export 'src/bdk_ffi_bitcoin.dart';
@@ -0,0 +1,47 @@
+import 'package:test/test.dart';
+
+import 'package:bdk_ffi/bdk_ffi.dart';
+
+void main() {
+  test('address', () {
+    final address = Address('32qQDeCM67ZyLT1EYvv9HHTG9bXHLVK1gf');
+    expect(address.toString(), '32qQDeCM67ZyLT1EYvv9HHTG9bXHLVK1gf');
+  });
+
+  test('shared txid outlives the outpoint', () {
+    Txid txid() {
+      // `Txid` is shared: the getter takes a new reference
+      return OutPoint.fromString('e567952fb6cc33857f392efa3a46c995a28f69cca4bb1b37e0204dab1ec7a389:1').txid;
+    }
+
+    expect(txid().toString(), 'e567952fb6cc33857f392efa3a46c995a28f69cca4bb1b37e0204dab1ec7a389');
+  });
+
+  test('script from bytes', () {
+    final script = Script([0x51]);
+    expect(script.toHex(), '51');
+    expect(Script.fromHex('51').toHex(), script.toHex());
+  });
+
+  test('operators', () {
+    final joined = Script.fromHex('51') + Script.fromHex('52');
+    expect(joined.toHex(), '5152');
+    expect(joined[1], 0x52);
+    expect(
+      () => joined[1000],
+      throwsA(isA<IndexErrorException>().having((e) => e.code, 'code', -1)),
+    );
+  });
+
+  test('errors', () {
+    expect(() => Network('not a network'), throwsA(isA<BitcoinErrorException>()));
+    expect(() => Script.fromHex('not hex'), throwsA(isA<BdkFfiException>()));
+  });
+
+  test('fromScript', () {
+    final script = Script.fromHex('a9140c8e1071fea96ca41f2b6735ef63a882187d39e087');
+    expect(Address.fromScript(script, Network.testnet()), isNotNull);
+    expect(Address.fromScriptWithDefaults(script), isNotNull);
+    expect(Network.testnet().isTestnet(), isTrue);
+  });
+}
@@ -0,0 +1,955 @@
+//! Language-neutral description of the exposed API
+//!
+//! While a module is analyzed the exposed items are recorded as they were written, before the
+//! language rewrites them. When the root `#[expose_mod]` is done the description is serialized
+//! to JSON and written to `OUT_DIR` as `<crate>_<module>.json`, and to `<CRATE>_IDL_DIR` if set,
+//! so that external generators and API-diff tools don't have to parse the Rust sources.
+//!
+//! Types are kept as Rust source, along with the type every backend passes them as, whether the
+//! mapping is built in or declared with `#[expose_mapping]`. The description doesn't depend on the
+//! language being built.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+
+use proc_macro2::{Delimiter, Spacing, Span, TokenStream, TokenTree};
+use quote::ToTokens;
+use serde::Serialize;
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
+use syn::{
+    parse_quote, Attribute, Expr, Fields, FnArg, GenericArgument, Ident, ImplItem, ItemFn,
+    ItemImpl, ItemStruct, ItemTrait, PathArguments, Receiver, ReturnType, Signature, Token,
+    TraitItem, Type, TypeBareFn, TypePath, TypePtr, TypeReference, TypeSlice,
+};
+
+use crate::langs::common_mapping::is_our_opaque_type;
+use crate::langs::{lib_name, write_generated, LangError};
+use crate::types::*;
+
+/// Bumped on incompatible changes of the format
+const FORMAT_VERSION: u32 = 1;
+
+/// Keys of `#[expose_mapping]`, one for each backend. The languages built over the C API share
+/// the `c` mappings
+const BACKENDS: &[&str] = &["c", "jni", "node", "python", "ruby", "wasm"];
+
+#[derive(Debug, Serialize)]
+pub struct Api {
+    format_version: u32,
+    #[serde(rename = "crate")]
+    krate: String,
+    module: Module,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct Module {
+    name: String,
+    /// Path from the root module, including this one
+    path: Vec<String>,
+    /// Exported symbols are prefixed with the module path
+    module_path: bool,
+    /// Prefix of the exported symbols, after the crate-wide one and before the module path
+    #[serde(skip_serializing_if = "Option::is_none")]
+    prefix: Option<String>,
+    functions: Vec<Function>,
+    structs: Vec<Struct>,
+    traits: Vec<Trait>,
+    modules: Vec<Module>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Struct {
+    name: String,
+    opaque: bool,
+    /// Options of `#[expose_struct]` besides `"opaque"`, like `to_string` or `bytes`
+    options: Vec<&'static str>,
+    fields: Vec<Field>,
+    methods: Vec<Function>,
+    operators: Vec<OperatorDesc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Field {
+    name: String,
+    #[serde(rename = "type")]
+    ty: TypeDesc,
+    get: bool,
+    set: bool,
+    /// Function validating the new values given to the setter
+    #[serde(skip_serializing_if = "Option::is_none")]
+    validate: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FunctionKind {
+    /// Free function of a module
+    Function,
+    Method,
+    /// Associated function that doesn't take `self`
+    Static,
+    Constructor,
+    Destructor,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiverKind {
+    /// `&self`
+    Ref,
+    /// `&mut self`
+    Mut,
+    /// `self`
+    Value,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Function {
+    name: String,
+    kind: FunctionKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    receiver: Option<ReceiverKind>,
+    /// Name given with `#[constructor(name = "...")]`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    constructor_name: Option<String>,
+    /// Name of the method of the original trait, for the methods of exposed traits
+    #[serde(skip_serializing_if = "Option::is_none")]
+    original: Option<String>,
+    blocking: bool,
+    inputs: Vec<Argument>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    output: Option<TypeDesc>,
+    /// Error type, when the output is a `Result`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<TypeDesc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Argument {
+    name: String,
+    #[serde(rename = "type")]
+    ty: TypeDesc,
+    /// Expression given with `#[expose(default = "...")]`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    default: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Trait {
+    name: String,
+    /// Trait implemented by the native types that can be passed for this one
+    supertrait: Option<String>,
+    methods: Vec<Function>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OperatorDesc {
+    /// Method of the `std::ops` trait, like `add` or `index`
+    op: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rhs: Option<TypeDesc>,
+    output: TypeDesc,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<TypeDesc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TypeDesc {
+    rust: String,
+    /// Type passed by each backend, by `#[expose_mapping]` key. Backends that can't pass the type
+    /// have no entry
+    mappings: BTreeMap<&'static str, MappedType>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MappedType {
+    /// Type in the C header for `c`, Rust type taken or returned by the bindings otherwise
+    #[serde(rename = "type")]
+    ty: String,
+    /// Declared with `#[expose_mapping]`
+    #[serde(skip_serializing_if = "is_false")]
+    custom: bool,
+    /// Written through a pointer to `type`, passed as the last argument, instead of returned
+    #[serde(skip_serializing_if = "is_false")]
+    out_pointer: bool,
+}
+
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
+/// Where a type appears, which changes how some backends pass it
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Position {
+    Input,
+    /// Returned as is
+    Output,
+    /// Ok value of a returned `Result`
+    Ok,
+    /// Error of a returned `Result`
+    Error,
+    /// Read with the getter of a field
+    Field,
+}
+
+thread_local! {
+    /// Modules being analyzed, the innermost last
+    static MODULES: RefCell<Vec<Module>> = const { RefCell::new(Vec::new()) };
+    /// Methods of `#[expose_impl]` blocks, attached to their struct when the module is done
+    static METHODS: RefCell<Vec<(String, Function)>> = const { RefCell::new(Vec::new()) };
+    static OPERATORS: RefCell<Vec<(String, OperatorDesc)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Rendered token: idents, literals and lifetimes are words, the punctuation is joined into
+/// operators like `::` or `->`
+enum Atom {
+    Word(String),
+    Op(String, OpKind),
+    Group(Delimiter, String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum OpKind {
+    /// Followed by a space: `,`, `;` and `:`
+    Separator,
+    /// Surrounded by spaces, like `=`, `+` or `->`
+    Binary,
+    /// Applied to what follows, like `&`, `*` or `-`
+    Prefix,
+    /// `!` of a macro call
+    Bang,
+    /// Never followed by a space, like `::`, `.` or the angle brackets of generics
+    Tight,
+}
+
+/// Whether `atom` ends a value or a type, after which `&`, `*`, `-` and `!` are not prefixes
+fn ends_value(atom: &Atom) -> bool {
+    match atom {
+        Atom::Word(word) => !matches!(
+            word.as_str(),
+            "as" | "const" | "dyn" | "impl" | "in" | "let" | "mut" | "ref" | "return"
+        ),
+        Atom::Op(op, _) => op == ">" || op == "?",
+        Atom::Group(..) => true,
+    }
+}
+
+fn op_kind(op: &str, prev: Option<&Atom>) -> OpKind {
+    let after_value = matches!(prev, Some(prev) if ends_value(prev));
+    match op {
+        "&" | "&&" | "*" | "-" | "!" if !after_value => OpKind::Prefix,
+        "!" => OpKind::Bang,
+        "," | ";" | ":" => OpKind::Separator,
+        "::" | "." | ".." | "..=" | "?" | "<" | ">" | "#" => OpKind::Tight,
+        _ => OpKind::Binary,
+    }
+}
+
+/// Split `tokens` into atoms, flattening the invisible groups
+fn atoms(tokens: TokenStream) -> Vec<Atom> {
+    fn push_op(atoms: &mut Vec<Atom>, op: &mut String) {
+        // `>>` closes two generics
+        let ops = match op.chars().all(|c| c == '>') {
+            true => op.chars().map(String::from).collect(),
+            false => vec![op.clone()],
+        };
+        for op in ops.into_iter().filter(|op| !op.is_empty()) {
+            let kind = op_kind(&op, atoms.last());
+            atoms.push(Atom::Op(op, kind));
+        }
+        op.clear();
+    }
+
+    let mut atoms = vec![];
+    let mut op = String::new();
+    let mut lifetime = false;
+    for token in tokens {
+        if let TokenTree::Punct(punct) = &token {
+            if punct.as_char() == '\'' {
+                push_op(&mut atoms, &mut op);
+                lifetime = true;
+            } else {
+                op.push(punct.as_char());
+                if punct.spacing() == Spacing::Alone {
+                    push_op(&mut atoms, &mut op);
+                }
+            }
+            continue;
+        }
+
+        push_op(&mut atoms, &mut op);
+        match token {
+            TokenTree::Ident(ident) if lifetime => atoms.push(Atom::Word(format!("'{}", ident))),
+            TokenTree::Ident(ident) => atoms.push(Atom::Word(ident.to_string())),
+            TokenTree::Literal(literal) => atoms.push(Atom::Word(literal.to_string())),
+            TokenTree::Group(group) if group.delimiter() == Delimiter::None => {
+                atoms.extend(self::atoms(group.stream()))
+            }
+            TokenTree::Group(group) => atoms.push(Atom::Group(
+                group.delimiter(),
+                render_tokens(group.stream()),
+            )),
+            TokenTree::Punct(_) => unreachable!(),
+        }
+        lifetime = false;
+    }
+    push_op(&mut atoms, &mut op);
+
+    atoms
+}
+
+/// Whether a space separates `prev` from `next`, as rustfmt would for types, patterns and short
+/// expressions
+fn spaced(prev: &Atom, next: &Atom) -> bool {
+    let after_separator = matches!(prev, Atom::Op(_, OpKind::Separator | OpKind::Binary));
+    match next {
+        Atom::Op(_, OpKind::Separator) | Atom::Op(_, OpKind::Bang) => false,
+        // Paths and generics only stand apart after a separator, like in `-> ::std::u8`
+        Atom::Op(_, OpKind::Tight) => {
+            after_separator && matches!(next, Atom::Op(op, _) if op == "::" || op == "<")
+        }
+        Atom::Op(_, OpKind::Binary) => true,
+        Atom::Op(_, OpKind::Prefix) => after_separator || matches!(prev, Atom::Word(_)),
+        Atom::Word(_) => {
+            after_separator
+                || matches!(prev, Atom::Word(_) | Atom::Group(..))
+                || matches!(prev, Atom::Op(op, _) if op == ">")
+        }
+        Atom::Group(Delimiter::Brace, _) => {
+            after_separator
+                || matches!(prev, Atom::Word(_))
+                || matches!(prev, Atom::Op(op, _) if op == ">")
+        }
+        Atom::Group(..) => after_separator,
+    }
+}
+
+fn render_tokens(tokens: TokenStream) -> String {
+    let atoms = atoms(tokens);
+    let mut source = String::new();
+    for (i, atom) in atoms.iter().enumerate() {
+        if i > 0 && spaced(&atoms[i - 1], atom) {
+            source.push(' ');
+        }
+        match atom {
+            Atom::Word(word) | Atom::Op(word, _) => source.push_str(word),
+            Atom::Group(Delimiter::Parenthesis, inner) => source.push_str(&format!("({})", inner)),
+            Atom::Group(Delimiter::Bracket, inner) => source.push_str(&format!("[{}]", inner)),
+            Atom::Group(_, inner) if inner.is_empty() => source.push_str("{}"),
+            Atom::Group(_, inner) => source.push_str(&format!("{{ {} }}", inner)),
+        }
+    }
+
+    source
+}
+
+/// Render tokens as Rust source, like `Vec<u8>`, `&'a str` or `fn(value: u32) -> u32`
+fn to_source<T: ToTokens>(tokens: &T) -> String {
+    render_tokens(tokens.to_token_stream())
+}
+
+/// Whether `module` or one of its submodules exposes a struct named `name`
+fn has_struct(module: &Module, name: &str) -> bool {
+    module.structs.iter().any(|s| s.name == name)
+        || module.modules.iter().any(|m| has_struct(m, name))
+}
+
+/// Whether `ty` is a struct exposed so far, including ours
+fn is_exposed(ty: &Type) -> bool {
+    if *ty == parse_quote!(Self) || is_our_opaque_type(ty) {
+        return true;
+    }
+
+    match ty {
+        Type::Path(TypePath { qself: None, path }) => match path.get_ident() {
+            Some(ident) => MODULES.with(|modules| {
+                let name = ident.to_string();
+                modules.borrow().iter().any(|m| has_struct(m, &name))
+            }),
+            None => false,
+        },
+        _ => false,
+    }
+}
+
+/// C spelling of the Rust type `ty` of the C API, the way cbindgen writes it in the header
+fn c_spelling(ty: &Type, self_name: Option<&str>) -> String {
+    let prefix = std::env::var("EXPOSE_C_PREFIX").unwrap_or_default();
+    match ty {
+        Type::Tuple(tuple) if tuple.elems.is_empty() => "void".into(),
+        Type::Ptr(TypePtr {
+            const_token, elem, ..
+        }) => c_pointer(&c_spelling(elem, self_name), const_token.is_some()),
+        Type::Reference(TypeReference {
+            mutability, elem, ..
+        }) => c_pointer(&c_spelling(elem, self_name), mutability.is_none()),
+        Type::Path(TypePath { qself: None, path }) => {
+            let last = path.segments.last().expect("Empty path");
+            let primitive = match last.ident.to_string().as_str() {
+                "bool" => "bool",
+                "i8" => "int8_t",
+                "u8" => "uint8_t",
+                "i16" => "int16_t",
+                "u16" => "uint16_t",
+                "i32" => "int32_t",
+                "u32" => "uint32_t",
+                "i64" => "int64_t",
+                "u64" => "uint64_t",
+                "isize" => "intptr_t",
+                "usize" => "uintptr_t",
+                "f32" => "float",
+                "f64" => "double",
+                "c_char" => "char",
+                "c_void" => "void",
+                "Self" => return format!("{}{}", prefix, self_name.unwrap_or("Self")),
+                "Arr" => return c_arr(&ty_args(ty)[0]),
+                _ => return format!("{}{}", prefix, last.ident),
+            };
+            primitive.into()
+        }
+        ty => to_source(ty),
+    }
+}
+
+fn c_pointer(pointee: &str, constant: bool) -> String {
+    match (pointee.ends_with('*'), constant) {
+        (true, _) => format!("{}*", pointee),
+        (false, true) => format!("const {} *", pointee),
+        (false, false) => format!("{} *", pointee),
+    }
+}
+
+/// cbindgen names the instances of `Arr<T>` after their element, like `Arr_u8`
+fn c_arr(elem: &Type) -> String {
+    let prefix = std::env::var("EXPOSE_C_PREFIX").unwrap_or_default();
+    format!("{}Arr_{}", prefix, to_source(elem))
+}
+
+/// Generic arguments of the last segment of `ty`
+fn ty_args(ty: &Type) -> Vec<Type> {
+    match ty {
+        Type::Path(TypePath { path, .. }) => match &path.segments.last().unwrap().arguments {
+            PathArguments::AngleBracketed(args) => args
+                .args
+                .iter()
+                .filter_map(|arg| match arg {
+                    GenericArgument::Type(ty) => Some(ty.clone()),
+                    _ => None,
+                })
+                .collect(),
+            _ => vec![],
+        },
+        _ => vec![],
+    }
+}
+
+/// C type of an argument, as converted by `C::convert_input()`
+fn c_input(ty: &Type, self_name: Option<&str>) -> String {
+    if let Some(target) = lookup_mapping("c", ty) {
+        return c_spelling(&target, self_name);
+    }
+
+    match ty {
+        _ if match_fixed_type(ty, parse_quote!(String)) => "const char *".into(),
+        _ if *ty == parse_quote!([u8; 32]) => "const uint8_t *".into(),
+        _ if match_generic_type(ty, parse_quote!(Vec)).is_some() => c_arr(&ty_args(ty)[0]),
+        Type::Reference(TypeReference { elem, .. }) => match elem.as_ref() {
+            // Borrowed from the caller for the duration of the call
+            Type::Slice(TypeSlice { elem, .. }) => c_arr(elem),
+            _ => c_spelling(ty, self_name),
+        },
+        _ if match_generic_type(ty, parse_quote!(Destroy)).is_some() => {
+            c_pointer(&c_input(&ty_args(ty)[0], self_name), false)
+        }
+        // Callbacks take their arguments like returned values
+        Type::BareFn(TypeBareFn { inputs, output, .. }) => {
+            let ret = match output {
+                ReturnType::Default => "void".into(),
+                ReturnType::Type(_, ty) => c_input(ty, self_name),
+            };
+            let args = inputs
+                .iter()
+                .map(|arg| {
+                    let ty = c_output(&arg.ty, self_name).0;
+                    match &arg.name {
+                        Some((name, _)) if ty.ends_with('*') => format!("{}{}", ty, name),
+                        Some((name, _)) => format!("{} {}", ty, name),
+                        None => ty,
+                    }
+                })
+                .collect::<Vec<_>>();
+            format!("{} (*)({})", ret, args.join(", "))
+        }
+        // Destructors take the struct by pointer
+        _ if *ty == parse_quote!(Self) => c_pointer(&c_spelling(ty, self_name), false),
+        _ => c_spelling(ty, self_name),
+    }
+}
+
+/// C type of a returned value, as converted by `C::convert_output()`, and whether it's written
+/// through an out pointer
+fn c_output(ty: &Type, self_name: Option<&str>) -> (String, bool) {
+    if let Some(target) = lookup_mapping("c", ty) {
+        return (c_spelling(&target, self_name), false);
+    }
+
+    if *ty == parse_quote!(Self) || is_our_opaque_type(ty) {
+        // Moved to the heap
+        (c_pointer(&c_spelling(ty, self_name), false), true)
+    } else if match_fixed_type(ty, parse_quote!(String)) {
+        // Freed with `string_free()`
+        ("char *".into(), false)
+    } else if *ty == parse_quote!(&[u8]) {
+        ("const uint8_t *".into(), false)
+    } else if *ty == parse_quote!(crate::common::ValidationError)
+        || *ty == parse_quote!(crate::common::IndexError)
+    {
+        ("int32_t".into(), false)
+    } else if match_generic_type(ty, parse_quote!(Vec)).is_some() {
+        // Freed with `bytes_free()` for `Vec<u8>`
+        (c_arr(&ty_args(ty)[0]), false)
+    } else if let Some(inner) = match_generic_type(ty, parse_quote!(Option)) {
+        // Null if `None`
+        (c_output(&inner[0], self_name).0, true)
+    } else {
+        (c_spelling(ty, self_name), false)
+    }
+}
+
+/// Fields that the C getters return by value, like `c::is_by_value()`
+fn c_by_value(ty: &Type) -> bool {
+    const PRIMITIVES: &[&str] = &[
+        "bool", "i8", "u8", "i16", "u16", "i32", "u32", "i64", "u64", "isize", "usize", "f32",
+        "f64",
+    ];
+    match ty {
+        Type::Ptr(_) => true,
+        Type::Path(TypePath { qself: None, path }) => path
+            .get_ident()
+            .into_iter()
+            .any(|ident| PRIMITIVES.iter().any(|name| ident == name)),
+        _ => false,
+    }
+}
+
+fn c_mapping(ty: &Type, position: Position, self_name: Option<&str>) -> MappedType {
+    let (c, out_pointer) = match position {
+        Position::Input => (c_input(ty, self_name), false),
+        Position::Field if c_by_value(ty) => (c_spelling(ty, self_name), false),
+        // Nested structs are returned by pointer, so that they can be modified in place
+        Position::Field => (c_pointer(&c_spelling(ty, self_name), false), false),
+        Position::Output | Position::Error => c_output(ty, self_name),
+        // The error code is returned instead
+        Position::Ok => match ty {
+            Type::Tuple(tuple) if tuple.elems.is_empty() => ("void".into(), false),
+            ty => (c_output(ty, self_name).0, true),
+        },
+    };
+
+    MappedType {
+        ty: c,
+        custom: false,
+        out_pointer,
+    }
+}
+
+/// Rust type taken or returned by the bindings of `backend` other than `c`, as converted by its
+/// `convert_input()` and `convert_output()`. `None` if the backend can't pass `ty`
+fn rust_mapping(backend: &str, ty: &Type, position: Position) -> Option<Type> {
+    if let Some(target) = lookup_mapping(backend, ty) {
+        return Some(target);
+    }
+
+    let input = position == Position::Input;
+    Some(match backend {
+        // Only the C API and Python take callbacks
+        "node" | "ruby" | "wasm" if matches!(ty, Type::BareFn(_)) => return None,
+        "python" if input => python_input(ty),
+        "node" if *ty == parse_quote!(Vec<u8>) => parse_quote!(::napi::bindgen_prelude::Buffer),
+        "node" if *ty == parse_quote!(usize) => parse_quote!(u32),
+        "node" if input && *ty == parse_quote!(u64) => {
+            parse_quote!(::napi::bindgen_prelude::BigInt)
+        }
+        "ruby" if *ty == parse_quote!(Vec<u8>) => parse_quote!(magnus::RString),
+        "ruby" if input => ruby_input(ty)?,
+        _ => ty.clone(),
+    })
+}
+
+fn python_input(ty: &Type) -> Type {
+    match ty {
+        Type::Reference(TypeReference {
+            elem, mutability, ..
+        }) if is_our_opaque_type(elem) => match mutability {
+            Some(_) => parse_quote!(pyo3::PyRefMut<#elem>),
+            None => parse_quote!(pyo3::PyRef<#elem>),
+        },
+        Type::BareFn(_) => parse_quote!(crate::python_callback::PyCb<'_>),
+        _ => match match_generic_type(ty, parse_quote!(Vec)).as_deref() {
+            Some([Type::Reference(TypeReference { elem, .. })]) => {
+                parse_quote!(Vec<pyo3::PyRef<#elem>>)
+            }
+            _ => ty.clone(),
+        },
+    }
+}
+
+fn ruby_input(ty: &Type) -> Option<Type> {
+    match ty {
+        // The guard can't be borrowed mutably
+        Type::Reference(TypeReference {
+            elem,
+            mutability: Some(_),
+            ..
+        }) if is_exposed(elem) => None,
+        Type::Reference(TypeReference { elem, .. }) if is_exposed(elem) => {
+            Some(parse_quote!(crate::langs::RbRef<#elem>))
+        }
+        ty if is_exposed(ty) => Some(parse_quote!(crate::langs::RbRef<#ty>)),
+        // Binary data is passed as a `String`
+        Type::Reference(TypeReference { elem, .. }) if **elem == parse_quote!([u8]) => {
+            Some(parse_quote!(magnus::RString))
+        }
+        ty if *ty == parse_quote!(&str) => Some(parse_quote!(String)),
+        ty => Some(ty.clone()),
+    }
+}
+
+/// Describe `ty` found at `position`, in a method of the struct `self_name` if any
+fn type_desc(ty: &Type, position: Position, self_name: Option<&str>) -> TypeDesc {
+    let mut mappings = BTreeMap::new();
+    for &backend in BACKENDS {
+        let mapped = match backend {
+            "c" => Some(c_mapping(ty, position, self_name)),
+            backend => rust_mapping(backend, ty, position).map(|target| MappedType {
+                ty: to_source(&target),
+                custom: false,
+                out_pointer: false,
+            }),
+        };
+        if let Some(mut mapped) = mapped {
+            mapped.custom = lookup_mapping(backend, ty).is_some();
+            mappings.insert(backend, mapped);
+        }
+    }
+
+    TypeDesc {
+        rust: to_source(ty),
+        mappings,
+    }
+}
+
+/// The output type, split from the error type when it's a `Result`
+fn output_desc(ty: &Type, self_name: Option<&str>) -> (TypeDesc, Option<TypeDesc>) {
+    match match_generic_type(ty, parse_quote!(Result)) {
+        Some(inner) if inner.len() == 2 => (
+            type_desc(&inner[0], Position::Ok, self_name),
+            Some(type_desc(&inner[1], Position::Error, self_name)),
+        ),
+        _ => (type_desc(ty, Position::Output, self_name), None),
+    }
+}
+
+fn has_attr(attrs: &[Attribute], name: &str) -> bool {
+    attrs.iter().any(|a| a.path.is_ident(name))
+}
+
+/// Describe a function, a method of the struct `self_name` if any
+fn describe_signature(
+    sig: &Signature,
+    attrs: &[Attribute],
+    kind: FunctionKind,
+    self_name: Option<&str>,
+) -> Result<Function, LangError> {
+    // The attributes are parsed again by the languages, which remove them
+    let mut attrs = attrs.to_vec();
+    let constructor = take_constructor(&mut attrs, sig)?;
+    let blocking = take_blocking(&mut attrs);
+    let kind = match (
+        constructor.is_some(),
+        has_attr(&attrs, "destructor"),
+        sig.receiver(),
+    ) {
+        (true, _, _) => FunctionKind::Constructor,
+        (_, true, _) => FunctionKind::Destructor,
+        (_, _, Some(_)) => FunctionKind::Method,
+        _ => kind,
+    };
+
+    let receiver = sig.receiver().map(|receiver| match receiver {
+        FnArg::Receiver(Receiver {
+            reference: Some(_),
+            mutability: Some(_),
+            ..
+        }) => ReceiverKind::Mut,
+        FnArg::Receiver(Receiver {
+            reference: Some(_), ..
+        }) => ReceiverKind::Ref,
+        _ => ReceiverKind::Value,
+    });
+
+    let inputs = sig
+        .inputs
+        .iter()
+        .filter_map(|input| match input {
+            FnArg::Typed(arg) => Some(arg),
+            FnArg::Receiver(_) => None,
+        })
+        .map(|arg| {
+            Ok(Argument {
+                name: to_source(&arg.pat),
+                ty: type_desc(&arg.ty, Position::Input, self_name),
+                default: arg_default(&arg.attrs)?.map(|expr: Expr| to_source(&expr)),
+            })
+        })
+        .collect::<Result<_, LangError>>()?;
+
+    let (output, error) = match &sig.output {
+        ReturnType::Default => (None, None),
+        ReturnType::Type(_, ty) => {
+            let (output, error) = output_desc(ty, self_name);
+            (Some(output), error)
+        }
+    };
+
+    Ok(Function {
+        name: sig.ident.to_string(),
+        kind,
+        receiver,
+        constructor_name: constructor
+            .and_then(|opts| opts.name)
+            .map(|name| name.to_string()),
+        original: None,
+        blocking,
+        inputs,
+        output,
+        error,
+    })
+}
+
+fn with_current<F: FnOnce(&mut Module)>(f: F) {
+    MODULES.with(|modules| {
+        if let Some(module) = modules.borrow_mut().last_mut() {
+            f(module);
+        }
+    });
+}
+
+fn self_ident(self_ty: &Type) -> Option<String> {
+    match self_ty {
+        Type::Path(TypePath { path, .. }) => path.segments.last().map(|s| s.ident.to_string()),
+        _ => None,
+    }
+}
+
+/// Start recording a module, `path` including its own ident
+pub fn enter_module(path: &[Ident], opts: &ExposeModOpts) {
+    let module = Module {
+        name: path.last().map(ToString::to_string).unwrap_or_default(),
+        path: path.iter().map(ToString::to_string).collect(),
+        module_path: opts.module_path,
+        prefix: opts.prefix.clone(),
+        ..Default::default()
+    };
+    MODULES.with(|modules| modules.borrow_mut().push(module));
+}
+
+/// Finish recording the current module. The root one is written out, a write error is
+/// reported at `span`
+pub fn exit_module(span: Span) -> Result<(), LangError> {
+    let mut module = match MODULES.with(|modules| modules.borrow_mut().pop()) {
+        Some(module) => module,
+        None => return Ok(()),
+    };
+
+    // The `impl` blocks are matched by name, whatever their position in the module
+    let methods = METHODS.with(|methods| std::mem::take(&mut *methods.borrow_mut()));
+    let operators = OPERATORS.with(|operators| std::mem::take(&mut *operators.borrow_mut()));
+    for (name, method) in methods {
+        if let Some(structure) = module.structs.iter_mut().find(|s| s.name == name) {
+            structure.methods.push(method);
+        }
+    }
+    for (name, operator) in operators {
+        if let Some(structure) = module.structs.iter_mut().find(|s| s.name == name) {
+            structure.operators.push(operator);
+        }
+    }
+
+    let module = MODULES.with(|modules| match modules.borrow_mut().last_mut() {
+        Some(parent) => {
+            parent.modules.push(module);
+            None
+        }
+        None => Some(module),
+    });
+    if let Some(module) = module {
+        let root = module.name.clone();
+        let api = Api {
+            format_version: FORMAT_VERSION,
+            krate: std::env::var("CARGO_PKG_NAME").unwrap_or_default(),
+            module,
+        };
+        let json = serde_json::to_string_pretty(&api).expect("Unable to serialize the API") + "\n";
+        let file_name = format!("{}_{}.json", lib_name(), root);
+        write_generated("IDL", None, &[(file_name.into(), json)], span)?;
+    }
+
+    Ok(())
+}
+
+/// Drop what was recorded, after an error
+pub fn clear() {
+    MODULES.with(|modules| modules.borrow_mut().clear());
+    METHODS.with(|methods| methods.borrow_mut().clear());
+    OPERATORS.with(|operators| operators.borrow_mut().clear());
+}
+
+pub fn record_fn(function: &ItemFn) -> Result<(), LangError> {
+    let function =
+        describe_signature(&function.sig, &function.attrs, FunctionKind::Function, None)?;
+    with_current(|module| module.functions.push(function));
+
+    Ok(())
+}
+
+pub fn record_struct(
+    structure: &ItemStruct,
+    opts: &Punctuated<ExposeStructOpts, Token![,]>,
+) -> Result<(), LangError> {
+    let options = opts
+        .iter()
+        .filter_map(|opt| match opt {
+            ExposeStructOpts::Shared => Some("shared"),
+            ExposeStructOpts::ToString => Some("to_string"),
+            ExposeStructOpts::ToDebug => Some("to_debug"),
+            ExposeStructOpts::Bytes => Some("bytes"),
+            _ => None,
+        })
+        .collect();
+
+    let name = structure.ident.to_string();
+    let mut fields = vec![];
+    if let Fields::Named(named) = &structure.fields {
+        for field in &named.named {
+            let attr = match field
+                .attrs
+                .iter()
+                .find(|a| a.path.is_ident("expose_struct"))
+            {
+                Some(attr) => attr,
+                None => continue,
+            };
+            let field_opts = attr
+                .parse_args_with(Punctuated::<ExposeStructOpts, Token![,]>::parse_terminated)
+                .map_err(LangError::ExposeStructAttrError)?;
+            let validate = field_opts.iter().find_map(|opt| match opt {
+                ExposeStructOpts::ValidatedSet(path) => Some(path.value()),
+                _ => None,
+            });
+
+            fields.push(Field {
+                name: field
+                    .ident
+                    .as_ref()
+                    .map(ToString::to_string)
+                    .unwrap_or_default(),
+                ty: type_desc(&field.ty, Position::Field, Some(&name)),
+                get: field_opts.iter().any(|opt| *opt == ExposeStructOpts::Get),
+                set: validate.is_some()
+                    || field_opts.iter().any(|opt| *opt == ExposeStructOpts::Set),
+                validate,
+            });
+        }
+    }
+
+    let structure = Struct {
+        name,
+        opaque: opts.iter().any(|opt| *opt == ExposeStructOpts::Opaque),
+        options,
+        fields,
+        methods: vec![],
+        operators: vec![],
+    };
+    with_current(|module| module.structs.push(structure));
+
+    Ok(())
+}
+
+pub fn record_impl(implementation: &ItemImpl) -> Result<(), LangError> {
+    let name = match self_ident(&implementation.self_ty) {
+        Some(name) => name,
+        None => return Ok(()),
+    };
+
+    for item in &implementation.items {
+        if let ImplItem::Method(method) = item {
+            let method = describe_signature(
+                &method.sig,
+                &method.attrs,
+                FunctionKind::Static,
+                Some(&name),
+            )?;
+            METHODS.with(|methods| methods.borrow_mut().push((name.clone(), method)));
+        }
+    }
+
+    Ok(())
+}
+
+pub fn record_operator(self_ty: &Type, operator: &ExposedOperator) {
+    let name = match self_ident(self_ty) {
+        Some(name) => name,
+        None => return,
+    };
+
+    let (output, error) = output_desc(&operator.output, Some(&name));
+    let operator = OperatorDesc {
+        op: operator.op.method().to_string(),
+        rhs: operator
+            .rhs
+            .as_ref()
+            .map(|rhs| type_desc(rhs, Position::Input, Some(&name))),
+        output,
+        error,
+    };
+    OPERATORS.with(|operators| operators.borrow_mut().push((name, operator)));
+}
+
+pub fn record_trait(tr: &ItemTrait) -> Result<(), LangError> {
+    let mut methods = vec![];
+    for item in &tr.items {
+        if let TraitItem::Method(method) = item {
+            let original = match method
+                .attrs
+                .iter()
+                .find(|a| a.path.is_ident("expose_trait"))
+            {
+                Some(attr) => attr
+                    .parse_args_with(
+                        Punctuated::<ExposeTraitOption, Token![,]>::parse_separated_nonempty,
+                    )
+                    .map_err(LangError::ExposeTraitAttrError)?
+                    .into_iter()
+                    .map(|ExposeTraitOption::Original(_, name)| name.value())
+                    .last(),
+                None => None,
+            };
+
+            let mut function =
+                describe_signature(&method.sig, &method.attrs, FunctionKind::Static, None)?;
+            function.original = original;
+            methods.push(function);
+        }
+    }
+
+    let tr = Trait {
+        name: tr.ident.to_string(),
+        supertrait: tr.supertraits.first().map(to_source),
+        methods,
+    };
+    with_current(|module| module.traits.push(tr));
+
+    Ok(())
+}
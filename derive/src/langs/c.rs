@@ -1,16 +1,17 @@
+use std::cell::RefCell;
 use std::collections::HashSet;
 use std::convert::{TryFrom, TryInto};
 use std::fmt;
 
 use proc_macro::TokenStream;
 use proc_macro2::{Span, TokenStream as TokenStream2};
-use quote::{format_ident, quote, ToTokens, TokenStreamExt};
+use quote::{format_ident, quote, quote_spanned, ToTokens, TokenStreamExt};
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
 use syn::{
     parse_quote, BareFnArg, Field, Fields, FieldsNamed, FnArg, Ident, ImplItem, ImplItemMethod,
-    Item, ItemFn, ItemStruct, ItemTrait, Pat, PatIdent, PatType, Token, TraitItem, TraitItemMethod,
-    TypeBareFn, TypePath, TypeReference,
+    Item, ItemConst, ItemFn, ItemStruct, ItemTrait, LitInt, Pat, PatIdent, PatType, Receiver, Token, TraitItem,
+    TraitItemMethod, TypeBareFn, TypePath, TypeReference, TypeSlice,
 };
 
 use super::*;
@@ -22,7 +23,18 @@ pub struct C;
 impl Lang for C {
     type Error = CError;
 
-    fn expose_fn(function: &mut ItemFn, _mod_path: &Vec<Ident>) -> Result<Ident, Self::Error> {
+    const NAME: &'static str = "c";
+
+    fn expose_fn(
+        function: &mut ItemFn,
+        mod_path: &Vec<Ident>,
+        extra: &mut Vec<Item>,
+    ) -> Result<Ident, Self::Error> {
+        if let Some(mut variant) = with_defaults_variant(function)? {
+            Self::expose_fn(&mut variant, mod_path, extra)?;
+            extra.push(variant.into());
+        }
+
         if let Some(pos) = function
             .attrs
             .iter()
@@ -52,9 +64,10 @@ impl Lang for C {
             }
         }
 
-        let ident = &function.sig.ident;
+        let ident = symbol_name(mod_path, &function.sig.ident);
 
-        let (mut args, input_conversion) = Self::convert_fn_args(function.sig.inputs.clone())?;
+        let (mut args, input_conversion) =
+            Self::convert_fn_args(function.sig.inputs.clone(), false)?;
 
         let ExpandedReturn {
             ret,
@@ -67,6 +80,7 @@ impl Lang for C {
         )?;
         args.extend(extra_args);
 
+
         let block = &function.block;
         let attrs = &function.attrs;
 
@@ -110,16 +124,118 @@ impl Lang for C {
             .iter()
             .find(|o| **o == ExposeStructOpts::Opaque)
             .is_some();
+        let is_shared = opts
+            .iter()
+            .find(|o| **o == ExposeStructOpts::Shared)
+            .is_some();
+
+        if is_shared && !is_opaque {
+            return Err(CError::SharedTransparentStruct(structure.ident.span()));
+        }
 
         if !is_opaque {
             structure.attrs.push(parse_quote!(#[repr(C)]));
+
+            let impl_repr_c: ItemImpl = parse_quote! {
+                impl crate::langs::ReprC for #ident {}
+            };
+            extra.push(impl_repr_c.into());
         }
         structure.vis = parse_quote!(pub);
 
+        if is_shared {
+            let retain_ident = symbol_name(
+                mod_path,
+                &format_ident!("{}_retain", ident.to_string().to_snake_case()),
+            );
+            let release_ident = symbol_name(
+                mod_path,
+                &format_ident!("{}_release", ident.to_string().to_snake_case()),
+            );
+
+            let impl_exposed_ptr: ItemImpl = parse_quote! {
+                impl crate::langs::ExposedPtr for #ident {
+                    type Kind = crate::langs::Shared;
+                }
+            };
+            extra.push(impl_exposed_ptr.into());
+
+            let retain_release: ItemImpl = parse_quote! {
+                impl #ident {
+                    #[no_mangle]
+                    pub unsafe extern "C" fn #retain_ident(ptr: *mut Self) -> *mut Self {
+                        crate::langs::Shared::retain(ptr)
+                    }
+
+                    #[no_mangle]
+                    pub unsafe extern "C" fn #release_ident(ptr: *mut Self) {
+                        crate::langs::Shared::release(ptr)
+                    }
+                }
+            };
+            extra.push(retain_release.into());
+        } else {
+            let impl_exposed_ptr: ItemImpl = parse_quote! {
+                impl crate::langs::ExposedPtr for #ident {
+                    type Kind = crate::langs::Boxed;
+                }
+            };
+            extra.push(impl_exposed_ptr.into());
+        }
+
+        if opts.iter().any(|o| *o == ExposeStructOpts::Bytes) {
+            // Exposed as `<type>_to_bytes` and `<type>_from_bytes`
+            let mut impl_block: ItemImpl = parse_quote! {
+                impl #ident {
+                    fn to_bytes(&self) -> Vec<u8> {
+                        crate::common::ByteContainer::as_bytes(self).to_vec()
+                    }
+
+                    fn from_bytes(bytes: Vec<u8>) -> Self {
+                        <Self as crate::common::ByteContainer>::from_bytes(bytes)
+                    }
+                }
+            };
+            Self::expose_impl(&mut impl_block, mod_path)?;
+            extra.push(impl_block.into());
+        }
+
         let impl_block =
             Self::generate_getters_setters(structure, is_opaque, mod_path)?;
         extra.push(impl_block.into());
 
+        if !is_opaque {
+            // cbindgen can't resolve `WrappedStructField::Store`, so spell out the box the opaque
+            // fields are stored in
+            for field in structure.fields.iter_mut() {
+                if let Type::Path(TypePath {
+                    qself: Some(qself),
+                    path,
+                }) = &field.ty
+                {
+                    if path.segments.last().map_or(false, |s| s.ident == "Store") {
+                        let ty = &qself.ty;
+                        field.ty = parse_quote!(Box<#ty>);
+                    }
+                }
+            }
+
+            // Every field is stored inline, or boxed if it's opaque, so it must have a C layout too
+            let field_checks = structure.fields.iter().map(|f| {
+                let ty = &f.ty;
+                quote_spanned! { ty.span() => crate::langs::assert_repr_c::<#ty>(); }
+            });
+            let assert_fields: ItemConst = parse_quote! {
+                const _: () = {
+                    #[allow(dead_code)]
+                    fn assert_fields() {
+                        #(#field_checks)*
+                    }
+                };
+            };
+            extra.push(assert_fields.into());
+        }
+
         Ok(ident)
     }
 
@@ -127,6 +243,10 @@ impl Lang for C {
         implementation: &mut ItemImpl,
         mod_path: &Vec<Ident>,
     ) -> Result<(), Self::Error> {
+        // `_with_defaults` variants of the methods
+        let mut variants = vec![];
+        let mut has_default_constructor = false;
+
         for item in &mut implementation.items {
             match item {
                 ImplItem::Method(ImplItemMethod {
@@ -142,6 +262,36 @@ impl Lang for C {
                         attrs: attrs.clone(),
                         block: Box::new(block.clone()),
                     };
+                    if let Some(FnArg::Receiver(Receiver {
+                        mutability: Some(_),
+                        ..
+                    })) = sig.inputs.first()
+                    {
+                        // `shared` structs can't be mutated through their pointer
+                        as_fn
+                            .block
+                            .stmts
+                            .insert(0, parse_quote!(crate::langs::assert_unique_ptr::<Self>();));
+                    }
+                    if let Some(ConstructorOpts { name }) =
+                        take_constructor(&mut as_fn.attrs, sig)?
+                    {
+                        // Constructors are all named `<type>_new[_<name>]`
+                        as_fn.sig.ident = match name {
+                            Some(name) => format_ident!("new_{}", name),
+                            None => {
+                                if std::mem::replace(&mut has_default_constructor, true) {
+                                    return Err(
+                                        LangError::MultipleDefaultConstructors(sig.ident.span()).into()
+                                    );
+                                }
+                                format_ident!("new")
+                            }
+                        };
+                        as_fn.attrs.push(parse_quote! {
+                            #[doc = " Ownership of the value written to `__ptr_out` is transferred to the caller"]
+                        });
+                    }
                     if let Type::Path(TypePath { path, .. }) = implementation.self_ty.as_ref() {
                         // Add the struct name as prefix
                         as_fn.sig.ident = format_ident!(
@@ -154,7 +304,7 @@ impl Lang for C {
                             as_fn.sig.ident
                         );
                     }
-                    Self::expose_fn(&mut as_fn, mod_path)?;
+                    Self::expose_fn(&mut as_fn, mod_path, &mut variants)?;
 
                     *sig = as_fn.sig;
                     *vis = as_fn.vis;
@@ -165,12 +315,30 @@ impl Lang for C {
             }
         }
 
+        implementation
+            .items
+            .extend(variants.into_iter().filter_map(|item| match item {
+                Item::Fn(ItemFn {
+                    attrs,
+                    vis,
+                    sig,
+                    block,
+                }) => Some(ImplItem::Method(ImplItemMethod {
+                    attrs,
+                    vis,
+                    defaultness: None,
+                    sig,
+                    block: *block,
+                })),
+                _ => None,
+            }));
+
         Ok(())
     }
 
     fn expose_trait(
         tr: &mut ItemTrait,
-        _mod_path: &Vec<Ident>,
+        mod_path: &Vec<Ident>,
         extra: &mut Vec<Item>,
     ) -> Result<Ident, Self::Error> {
         let ident = tr.ident.clone();
@@ -235,10 +403,14 @@ impl Lang for C {
         extra.push(trait_struct.into());
 
         // Define a constructor for our struct
-        let constructor_ident =
-            format_ident!("{}_new", trait_struct_ident.to_string().to_snake_case());
-        let destructor_ident =
-            format_ident!("{}_destroy", trait_struct_ident.to_string().to_snake_case());
+        let constructor_ident = symbol_name(
+            mod_path,
+            &format_ident!("{}_new", trait_struct_ident.to_string().to_snake_case()),
+        );
+        let destructor_ident = symbol_name(
+            mod_path,
+            &format_ident!("{}_destroy", trait_struct_ident.to_string().to_snake_case()),
+        );
         let (mut constructor_args, fields): (Vec<_>, Vec<_>) = callbacks
             .iter()
             .map(|(sig, bare_fn, conv, _)| {
@@ -375,6 +547,103 @@ impl Lang for C {
         Ok(ident)
     }
 
+    fn expose_getter(
+        field: &mut Field,
+        is_opaque: bool,
+        impl_block: &mut ItemImpl,
+    ) -> Result<(), Self::Error> {
+        if !is_opaque {
+            return Self::expose_inline_getter(field, impl_block);
+        }
+
+        let field_ty = &field.ty;
+        let getter_ty = Self::convert_getter_setter_ty(field.ty.clone())?.0;
+        let field_ident = field.ident.as_ref().expect("Missing field ident");
+        let getter_name = format_ident!("get_{}", field_ident);
+        let mut getter: ImplItemMethod = parse_quote! {
+            #[getter]
+            fn #getter_name(&mut self) -> #getter_ty {
+                use crate::common::WrappedStructField;
+                #field_ty::wrap_get(&mut self.#field_ident)
+            }
+        };
+        // See `PtrKind::get_stored`
+        if !is_by_value(field_ty) {
+            getter.attrs.push(parse_quote! {
+                #[doc = " Borrowed from `self`, except for `shared` structs: those are returned with a new"]
+            });
+            getter.attrs.push(parse_quote! {
+                #[doc = " reference, owned by the caller and given back with `<type>_release`"]
+            });
+        }
+        impl_block.items.push(getter.into());
+
+        Ok(())
+    }
+
+    fn expose_inline_getter(field: &mut Field, impl_block: &mut ItemImpl) -> Result<(), Self::Error> {
+        let field_ty = &field.ty;
+        let field_ident = field.ident.as_ref().expect("Missing field ident");
+        let getter_name = format_ident!("get_{}", field_ident);
+
+        // Return a pointer to nested structs, so that they can be modified in place
+        let getter: ImplItemMethod = if is_by_value(field_ty) {
+            parse_quote! {
+                #[getter]
+                fn #getter_name(&self) -> #field_ty {
+                    self.#field_ident
+                }
+            }
+        } else {
+            parse_quote! {
+                #[getter]
+                fn #getter_name(&mut self) -> *mut #field_ty {
+                    &mut self.#field_ident as *mut #field_ty
+                }
+            }
+        };
+        impl_block.items.push(getter.into());
+
+        Ok(())
+    }
+
+    fn expose_inline_setter(
+        field: &mut Field,
+        validate: Option<&Path>,
+        impl_block: &mut ItemImpl,
+    ) -> Result<(), Self::Error> {
+        let field_ty = &field.ty;
+        let field_ident = field.ident.as_ref().expect("Missing field ident");
+        let setter_name = format_ident!("set_{}", field_ident);
+
+        let setter_ty: Type = if is_by_value(field_ty) {
+            parse_quote!(#field_ty)
+        } else {
+            parse_quote!(&#field_ty)
+        };
+        let setter: ImplItemMethod = match validate {
+            None => parse_quote! {
+                #[setter]
+                fn #setter_name(&mut self, #field_ident: #setter_ty) {
+                    self.#field_ident = crate::mapping::MapFrom::map_from(#field_ident);
+                }
+            },
+            Some(validate) => parse_quote! {
+                #[setter]
+                fn #setter_name(&mut self, #field_ident: #setter_ty) -> Result<(), crate::common::ValidationError> {
+                    let #field_ident: #field_ty = crate::mapping::MapFrom::map_from(#field_ident);
+                    #validate(self, &#field_ident).map_err(crate::common::ValidationError::from_display)?;
+                    self.#field_ident = #field_ident;
+
+                    Ok(())
+                }
+            },
+        };
+        impl_block.items.push(setter.into());
+
+        Ok(())
+    }
+
     fn convert_getter_setter_ty(ty: Type) -> Result<(Type, Type), Self::Error> {
         for t in types_arr!(i8, u8, i16, u16, i32, u32, i64, u64) {
             if &ty == t {
@@ -385,31 +654,31 @@ impl Lang for C {
         Ok((parse_quote!(*mut #ty), parse_quote!(&#ty)))
     }
 
-    fn convert_input(ty: Type) -> Result<Input, Self::Error> {
-        match ty {
-            Type::Reference(TypeReference { ref elem, .. })
-                if elem.as_ref() == &parse_quote!(Inner) =>
-            {
-                let elem = elem.clone();
-
-                return Ok(Input::new_custom(
-                    parse_quote!(*mut #elem),
-                    vec![ty.clone()],
-                    move |_, ident| {
-                        let ts = quote! {
-                            {
-                                let #ident: #elem = #ident.clone();
-                                MapFrom::map_from(#ident)
-                            }
-                        };
-                        ts.into()
-                    },
-                ));
-            }
-            _ => {}
+    fn expose_operators(
+        self_ty: &Type,
+        operators: Vec<ExposedOperator>,
+        mod_path: &Vec<Ident>,
+        extra: &mut Vec<Item>,
+    ) -> Result<(), Self::Error> {
+        // Exposed as regular methods, named `<type>_<add|sub|mul|neg|index>`
+        let mut impl_block: ItemImpl = parse_quote! {
+            impl #self_ty {}
+        };
+        for operator in operators {
+            let rhs_by_value = operator.rhs.as_ref().is_some_and(is_by_value);
+            impl_block.items.push(operator.wrapper(rhs_by_value).into());
         }
 
-        if match_fixed_type(&ty, parse_quote!(String)) {
+        Self::expose_impl(&mut impl_block, mod_path)?;
+        extra.push(impl_block.into());
+
+        Ok(())
+    }
+
+    fn convert_input(ty: Type) -> Result<Input, Self::Error> {
+        if let Some(target) = Self::custom_mapping(&ty) {
+            Ok(Input::new_map_from(ty, vec![target]))
+        } else if match_fixed_type(&ty, parse_quote!(String)) {
             Ok(Input::new_map_from(
                 ty,
                 vec![parse_quote!(*const libc::c_char)],
@@ -433,6 +702,15 @@ impl Lang for C {
                 ty,
                 vec![parse_quote!(crate::langs::Arr<#sources>)],
             ))
+        } else if let Type::Reference(TypeReference { elem, .. }) = &ty {
+            match elem.as_ref() {
+                // Borrowed from the caller for the duration of the call
+                Type::Slice(TypeSlice { elem, .. }) => Ok(Input::new_map_from(
+                    ty.clone(),
+                    vec![parse_quote!(crate::langs::Arr<#elem>)],
+                )),
+                _ => Ok(Input::new_unchanged(ty)),
+            }
         } else if let Some(inner) = match_generic_type(&ty, parse_quote!(Destroy)) {
             let inner = inner
                 .into_iter()
@@ -507,6 +785,10 @@ impl Lang for C {
     }
 
     fn convert_output(output: Type) -> Result<Output, Self::Error> {
+        if let Some(target) = Self::custom_mapping(&output) {
+            return Ok(Output::new_map_to_single(output, target));
+        }
+
         // Return our opaque types by reference
         for t in &our_opaque_types!() {
             if t == &output {
@@ -523,7 +805,9 @@ impl Lang for C {
             ))
         } else if output == parse_quote!(&[u8]) {
             Ok(Output::new_map_to_single(output, parse_quote!(*const u8)))
-        } else if output == parse_quote!(BitcoinError) {
+        } else if output == parse_quote!(crate::common::ValidationError)
+            || output == parse_quote!(crate::common::IndexError)
+        {
             Ok(Output::new_map_to_single(output, parse_quote!(i32)))
         } else if let Some(inner) = match_generic_type(&output, parse_quote!(Vec)) {
             let inner = inner
@@ -536,12 +820,9 @@ impl Lang for C {
                 .into_iter()
                 .collect::<Punctuated<_, Comma>>(); // TODO: as_tuple() ?
 
-            Ok(Output::new_map_to_suffix(
+            Ok(Output::new_map_to_single(
                 output,
-                vec![
-                    (parse_quote!(*mut #targets), "arr".into()),
-                    (parse_quote!(usize), "len".into()),
-                ],
+                parse_quote!(crate::langs::Arr<#targets>),
             ))
         } else if let Some(inner) = match_generic_type(&output, parse_quote!(Option)) {
             let inner = inner
@@ -583,6 +864,80 @@ impl Lang for C {
     }
 }
 
+/// Name of an exported symbol: the crate-wide prefix set by the build script in `EXPOSE_C_PREFIX`,
+/// then the one of `#[expose_mod(prefix = "...")]`, the module path if `#[expose_mod(module_path)]`
+/// is set and finally `name`.
+///
+/// Only functions are named this way: cbindgen names the types with the crate-wide prefix alone,
+/// so they must be unique across the exposed modules
+pub(super) fn symbol_name(mod_path: &[Ident], name: &Ident) -> Ident {
+    let opts = mod_opts();
+    let mut symbol = std::env::var("EXPOSE_C_PREFIX").unwrap_or_default();
+    if let Some(prefix) = &opts.prefix {
+        symbol.push_str(prefix);
+    }
+    if opts.module_path {
+        for module in mod_path {
+            symbol.push_str(&module.to_string().to_snake_case());
+            symbol.push('_');
+        }
+    }
+    symbol.push_str(&name.to_string());
+
+    Ident::new(&symbol, name.span())
+}
+
+/// For functions with `#[expose(default = "...")]` arguments, build a `<name>_with_defaults`
+/// variant that doesn't take those arguments and uses the default values instead
+fn with_defaults_variant(function: &ItemFn) -> Result<Option<ItemFn>, CError> {
+    let mut variant = function.clone();
+    let mut defaults = vec![];
+
+    variant.sig.inputs = Punctuated::new();
+    for input in &function.sig.inputs {
+        match input {
+            FnArg::Typed(PatType { pat, ty, attrs, .. }) => match arg_default(attrs)? {
+                Some(default) => {
+                    let borrow = match ty.as_ref() {
+                        Type::Reference(TypeReference { mutability, .. }) => quote!(& #mutability),
+                        _ => quote!(),
+                    };
+                    defaults.push(quote! { let #pat: #ty = #borrow #default; });
+                }
+                None => variant.sig.inputs.push(input.clone()),
+            },
+            FnArg::Receiver(_) => variant.sig.inputs.push(input.clone()),
+        }
+    }
+
+    if defaults.is_empty() {
+        return Ok(None);
+    }
+
+    let ident = &function.sig.ident;
+    let doc = format!(" Same as `{}`, using the default values of the omitted arguments", ident);
+    variant.sig.ident = format_ident!("{}_with_defaults", ident);
+    variant.attrs.push(parse_quote!( #[doc = #doc] ));
+
+    let block = &function.block;
+    variant.block = parse_quote! {
+        {
+            #(#defaults)*
+            #block
+        }
+    };
+
+    Ok(Some(variant))
+}
+
+/// Types that are passed to and from C by value: primitives and raw pointers
+pub(super) fn is_by_value(ty: &Type) -> bool {
+    matches!(ty, Type::Ptr(_))
+        || types_arr!(bool, i8, u8, i16, u16, i32, u32, i64, u64, isize, usize, f32, f64)
+            .iter()
+            .any(|t| t == ty)
+}
+
 #[derive(Debug)]
 pub enum CError {
     Lang(LangError),
@@ -590,16 +945,47 @@ pub enum CError {
     UnnamedCallbackArguments(Span),
     DestructorReceiverArgument(Span),
     InvalidResult(Span),
+    SharedTransparentStruct(Span),
+    MissingDestructor(Span),
 }
 
 impl fmt::Display for CError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{:?}", self)
+        match self {
+            CError::Lang(e) => write!(f, "{}", e),
+            CError::UnnamedCallbackArguments(_) => {
+                write!(f, "Callback arguments must be named in C")
+            }
+            CError::DestructorReceiverArgument(_) => {
+                write!(f, "`destructor` functions can't take a `self` receiver")
+            }
+            CError::InvalidResult(_) => write!(f, "Invalid `Result` type"),
+            CError::SharedTransparentStruct(_) => {
+                write!(f, "Only opaque structs can be `shared`")
+            }
+            CError::MissingDestructor(_) => write!(
+                f,
+                "Opaque structs need a `#[destructor]` to be freed by the generated class"
+            ),
+        }
     }
 }
 
 impl std::error::Error for CError {}
 
+impl From<CError> for syn::Error {
+    fn from(e: CError) -> Self {
+        match e {
+            CError::Lang(e) => e.into(),
+            CError::UnnamedCallbackArguments(span)
+            | CError::DestructorReceiverArgument(span)
+            | CError::InvalidResult(span)
+            | CError::SharedTransparentStruct(span)
+            | CError::MissingDestructor(span) => syn::Error::new(span, e),
+        }
+    }
+}
+
 impl From<LangError> for CError {
     fn from(e: LangError) -> Self {
         CError::Lang(e)
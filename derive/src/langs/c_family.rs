@@ -0,0 +1,706 @@
+//! Shared side of the layers rendered over the C API: C++, Swift, Go, C# and Dart
+//!
+//! The Rust side of these backends is generated by the C backend, while the original signatures
+//! of the exposed items are recorded here. When the root `#[expose_mod]` is expanded the items
+//! recorded for it are handed to `CFamily::render`, and the rendered files are written next to
+//! the C header.
+
+use std::cell::RefCell;
+use std::fs;
+use std::path::PathBuf;
+
+use syn::punctuated::Punctuated;
+use syn::{
+    FnArg, Ident, ImplItem, ImplItemMethod, Item, ItemFn, ItemImpl, ItemMod, ItemStruct, ItemTrait,
+    Pat, PatIdent, PatType, ReturnType, Signature, Token, TraitItem, TraitItemMethod, Type,
+    TypePath,
+};
+
+use super::c::{is_by_value, symbol_name, CError, C};
+use super::*;
+use crate::types::*;
+
+/// A language rendered over the C API
+pub trait CFamily {
+    /// `<CRATE>_<OUTPUT>_DIR` is the optional directory the rendered files are copied to
+    const OUTPUT: &'static str;
+
+    /// One level of indentation
+    const INDENT: &'static str = "    ";
+
+    /// Whether methods can be overloaded. Otherwise the variant without the arguments that have
+    /// a default value is named `<name>_with_defaults`
+    const OVERLOADING: bool = true;
+
+    /// Whether named `#[constructor]`s that return `Self` are constructors too, instead of static
+    /// methods
+    const NAMED_CONSTRUCTORS: bool = false;
+
+    /// Whether the exposed traits can be implemented in the language, otherwise they are only
+    /// available through the C API
+    const CALLBACKS: bool = false;
+
+    /// Whether opaque structs must have a `#[destructor]`, because the wrapper class frees them
+    /// automatically
+    const REQUIRES_DESTRUCTOR: bool = false;
+
+    /// Render the items recorded for the root module `root` to files, named relative to the
+    /// output directories. Items whose types can't be rendered are skipped, with a comment in
+    /// their place
+    fn render(root: &Ident, items: Recorded) -> Vec<(PathBuf, String)>;
+
+    /// Files that are only rendered for some modules, removed when they are left over from a
+    /// previous build
+    fn optional_files(_root: &Ident) -> Vec<PathBuf> {
+        vec![]
+    }
+
+    /// Indent every non-empty line by `level`
+    fn indent(lines: Vec<String>, level: usize) -> String {
+        let prefix = Self::INDENT.repeat(level);
+        lines
+            .iter()
+            .flat_map(|l| l.split('\n'))
+            .map(|l| match l.is_empty() {
+                true => "\n".to_string(),
+                false => format!("{}{}\n", prefix, l),
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MethodKind {
+    /// `#[constructor]` that returns `Self` or `Result<Self, _>`. Unnamed unless the language has
+    /// `NAMED_CONSTRUCTORS`
+    Constructor,
+    Static,
+    Instance {
+        mutable: bool,
+    },
+    /// Implementation of an exposed operator
+    Operator(Operator),
+    /// Getter of an opaque struct field, named after the field
+    Getter,
+    /// Setter of an opaque struct field, named after the field. Takes the new `value`
+    Setter,
+}
+
+#[derive(Debug, Clone)]
+pub struct Method {
+    pub symbol: Ident,
+    pub name: String,
+    pub kind: MethodKind,
+    pub inputs: Vec<(Ident, Type)>,
+    pub output: Type,
+}
+
+#[derive(Debug)]
+pub struct Class {
+    pub mod_path: Vec<Ident>,
+    pub ident: Ident,
+    pub opaque: bool,
+    pub shared: bool,
+    pub destructor: Option<Ident>,
+    /// `<type>_retain`, only for `shared` structs
+    pub retain: Option<Ident>,
+    /// Fields of a transparent struct
+    pub fields: Vec<(Ident, Type)>,
+    pub methods: Vec<Method>,
+}
+
+/// A method of an exposed trait, called back from the C API
+#[cfg_attr(
+    not(any(feature = "go", feature = "csharp", feature = "dart")),
+    allow(dead_code)
+)]
+#[derive(Debug)]
+pub struct Callback {
+    /// Name of the method implemented in the language
+    pub name: String,
+    pub inputs: Vec<(Ident, Type)>,
+    pub output: Type,
+}
+
+impl Callback {
+    /// Only primitives and strings can cross the C API in this direction
+    pub fn supported(&self) -> bool {
+        self.inputs.iter().all(|(_, ty)| {
+            primitive_ident(ty).is_some() || match_fixed_type(ty, parse_quote!(String))
+        }) && (primitive_ident(&self.output).is_some() || self.output == parse_quote!(()))
+    }
+}
+
+#[cfg_attr(
+    not(any(feature = "go", feature = "csharp", feature = "dart")),
+    allow(dead_code)
+)]
+#[derive(Debug)]
+pub struct Trait {
+    pub mod_path: Vec<Ident>,
+    pub ident: Ident,
+    pub struct_ident: Ident,
+    /// Name of the struct in the C symbols, without the `_new` and `_destroy` suffixes
+    #[cfg_attr(not(feature = "go"), allow(dead_code))]
+    pub symbol: Ident,
+    /// `<struct>_new`, which takes the callbacks
+    pub constructor: Ident,
+    pub callbacks: Vec<Callback>,
+    pub supported: bool,
+}
+
+/// Everything recorded for a root module
+pub struct Recorded {
+    pub classes: Vec<Class>,
+    /// Free functions, with their module
+    pub functions: Vec<(Vec<Ident>, Method)>,
+    pub traits: Vec<Trait>,
+}
+
+impl Recorded {
+    /// Modules that have items, in the order they are first seen
+    #[cfg_attr(
+        not(any(feature = "cpp", feature = "swift", feature = "csharp")),
+        allow(dead_code)
+    )]
+    pub fn modules(&self) -> Vec<&[Ident]> {
+        let mut modules: Vec<&[Ident]> = vec![];
+        for path in self
+            .classes
+            .iter()
+            .map(|c| c.mod_path.as_slice())
+            .chain(self.functions.iter().map(|(path, _)| path.as_slice()))
+            .chain(self.traits.iter().map(|t| t.mod_path.as_slice()))
+        {
+            if !modules.contains(&path) {
+                modules.push(path);
+            }
+        }
+
+        modules
+    }
+}
+
+thread_local! {
+    /// Structs and their methods recorded while expanding the current root module
+    static CLASSES: RefCell<Vec<Class>> = const { RefCell::new(Vec::new()) };
+    /// Free functions recorded while expanding the current root module
+    static FUNCTIONS: RefCell<Vec<(Vec<Ident>, Method)>> = const { RefCell::new(Vec::new()) };
+    /// Traits recorded while expanding the current root module
+    static TRAITS: RefCell<Vec<Trait>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Take the items recorded for the root module `root`, leaving those of the other modules
+fn take_recorded(root: &Ident) -> Recorded {
+    fn take<T, F: Fn(&T) -> bool>(items: &RefCell<Vec<T>>, ours: F) -> Vec<T> {
+        let (ours, others) = items.borrow_mut().drain(..).partition(ours);
+        *items.borrow_mut() = others;
+        ours
+    }
+    let in_root = |path: &[Ident]| path.first() == Some(root);
+
+    Recorded {
+        classes: CLASSES.with(|classes| take(classes, |c| in_root(&c.mod_path))),
+        functions: FUNCTIONS.with(|functions| take(functions, |(path, _)| in_root(path))),
+        traits: TRAITS.with(|traits| take(traits, |t| in_root(&t.mod_path))),
+    }
+}
+
+fn with_class<F: FnOnce(&mut Class)>(ident: &Ident, f: F) {
+    CLASSES.with(|classes| {
+        let mut classes = classes.borrow_mut();
+        let pos = match classes.iter().position(|c| &c.ident == ident) {
+            Some(pos) => pos,
+            None => {
+                classes.push(Class {
+                    mod_path: vec![],
+                    ident: ident.clone(),
+                    opaque: false,
+                    shared: false,
+                    destructor: None,
+                    retain: None,
+                    fields: vec![],
+                    methods: vec![],
+                });
+                classes.len() - 1
+            }
+        };
+
+        f(&mut classes[pos]);
+    });
+}
+
+/// Name of the primitive `ty`, if it's one of those that cross the C API as they are
+pub fn primitive_ident(ty: &Type) -> Option<String> {
+    let ident = match ty {
+        Type::Path(TypePath { path, .. }) => path.get_ident()?.to_string(),
+        _ => return None,
+    };
+
+    match ident.as_str() {
+        "bool" | "i8" | "u8" | "i16" | "u16" | "i32" | "u32" | "i64" | "u64" | "isize"
+        | "usize" | "f32" | "f64" => Some(ident),
+        _ => None,
+    }
+}
+
+pub fn expose_fn<B: CFamily>(
+    function: &mut ItemFn,
+    mod_path: &Vec<Ident>,
+    extra: &mut Vec<Item>,
+) -> Result<Ident, CError> {
+    let original = function.sig.clone();
+    let symbol = C::expose_fn(function, mod_path, extra)?;
+
+    if !mod_path.is_empty() {
+        let methods = methods_from_sig::<B>(
+            &original,
+            &symbol,
+            original.ident.to_string(),
+            MethodKind::Static,
+        );
+        FUNCTIONS.with(|functions| {
+            let mut functions = functions.borrow_mut();
+            functions.extend(methods.into_iter().map(|m| (mod_path.clone(), m)));
+        });
+    }
+
+    Ok(symbol)
+}
+
+pub fn expose_mod<B: CFamily>(
+    module: &mut ItemMod,
+    mod_path: &Vec<Ident>,
+    sub_items: Vec<ModuleItem>,
+) -> Result<Ident, CError> {
+    let ident = C::expose_mod(module, mod_path, sub_items)?;
+
+    // Nested modules are expanded first, so everything has been recorded by now
+    if mod_path.len() == 1 {
+        let items = take_recorded(&ident);
+        if B::REQUIRES_DESTRUCTOR {
+            let missing = items
+                .classes
+                .iter()
+                .find(|class| class.opaque && class.destructor.is_none());
+            if let Some(class) = missing {
+                return Err(CError::MissingDestructor(class.ident.span()));
+            }
+        }
+
+        let files = B::render(&ident, items);
+        write_generated(B::OUTPUT, None, &files, ident.span())?;
+
+        for dir in output_dirs(B::OUTPUT, None) {
+            for file in B::optional_files(&ident) {
+                if files.iter().all(|(path, _)| path != &file) {
+                    let _ = fs::remove_file(dir.join(file));
+                }
+            }
+        }
+    }
+
+    Ok(ident)
+}
+
+pub fn expose_struct(
+    structure: &mut ItemStruct,
+    opts: Punctuated<ExposeStructOpts, Token![,]>,
+    mod_path: &Vec<Ident>,
+    extra: &mut Vec<Item>,
+) -> Result<Ident, CError> {
+    // Transparent structs holding opaque ones can't be copied by value, so they are wrapped like
+    // opaque structs
+    let opaque = opts.iter().any(|o| *o == ExposeStructOpts::Opaque)
+        || structure
+            .fields
+            .iter()
+            .any(|f| common_mapping::is_our_opaque_type(&f.ty));
+    let shared = opts.iter().any(|o| *o == ExposeStructOpts::Shared);
+    let bytes = opts.iter().any(|o| *o == ExposeStructOpts::Bytes);
+    let (accessors, fields) = if opaque {
+        (accessors(structure, mod_path), vec![])
+    } else {
+        let fields = structure
+            .fields
+            .iter()
+            .filter_map(|f| Some((f.ident.clone()?, f.ty.clone())))
+            .collect();
+        (vec![], fields)
+    };
+
+    let ident = C::expose_struct(structure, opts, mod_path, extra)?;
+    let snake_case = ident.to_string().to_snake_case();
+
+    with_class(&ident, |class| {
+        class.mod_path = mod_path.clone();
+        class.opaque = opaque;
+        class.shared = shared;
+        if shared {
+            class.retain = Some(symbol_name(
+                mod_path,
+                &format_ident!("{}_retain", snake_case),
+            ));
+            // Used when there's no `#[destructor]`
+            class.destructor.get_or_insert_with(|| {
+                symbol_name(mod_path, &format_ident!("{}_release", snake_case))
+            });
+        }
+        class.fields = fields;
+        class.methods.extend(accessors);
+        if bytes {
+            class.methods.extend(bytes_methods(&ident, mod_path));
+        }
+    });
+
+    Ok(ident)
+}
+
+pub fn expose_impl<B: CFamily>(
+    implementation: &mut ItemImpl,
+    mod_path: &Vec<Ident>,
+) -> Result<(), CError> {
+    expose_impl_with_operators::<B>(implementation, mod_path, &[])
+}
+
+pub fn expose_trait<B: CFamily>(
+    tr: &mut ItemTrait,
+    mod_path: &Vec<Ident>,
+    extra: &mut Vec<Item>,
+) -> Result<Ident, CError> {
+    if !B::CALLBACKS {
+        return C::expose_trait(tr, mod_path, extra);
+    }
+
+    // The `original` names are removed by the C backend
+    let callbacks = tr
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            TraitItem::Method(TraitItemMethod { attrs, sig, .. }) => {
+                Some(callback_from_sig(attrs, sig))
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    let ident = C::expose_trait(tr, mod_path, extra)?;
+
+    if !mod_path.is_empty() {
+        let struct_ident = format_ident!("{}Struct", ident);
+        let snake_case = struct_ident.to_string().to_snake_case();
+        let supported = callbacks.iter().all(|c| c.supported());
+        if supported {
+            with_class(&struct_ident, |class| {
+                class.mod_path = mod_path.clone();
+                class.opaque = true;
+                class.destructor = Some(symbol_name(
+                    mod_path,
+                    &format_ident!("{}_destroy", snake_case),
+                ));
+            });
+        }
+
+        TRAITS.with(|traits| {
+            traits.borrow_mut().push(Trait {
+                mod_path: mod_path.clone(),
+                ident: ident.clone(),
+                struct_ident,
+                symbol: symbol_name(mod_path, &format_ident!("{}", snake_case)),
+                constructor: symbol_name(mod_path, &format_ident!("{}_new", snake_case)),
+                callbacks,
+                supported,
+            })
+        });
+    }
+
+    Ok(ident)
+}
+
+pub fn expose_operators<B: CFamily>(
+    self_ty: &Type,
+    operators: Vec<ExposedOperator>,
+    mod_path: &Vec<Ident>,
+    extra: &mut Vec<Item>,
+) -> Result<(), CError> {
+    let mut impl_block: ItemImpl = parse_quote! {
+        impl #self_ty {}
+    };
+    let mut ops = vec![];
+    for operator in operators {
+        let rhs_by_value = operator.rhs.as_ref().is_some_and(is_by_value);
+        impl_block.items.push(operator.wrapper(rhs_by_value).into());
+        ops.push(operator.op);
+    }
+
+    expose_impl_with_operators::<B>(&mut impl_block, mod_path, &ops)?;
+    extra.push(impl_block.into());
+
+    Ok(())
+}
+
+/// Expose an `impl` block through the C backend and record its methods. The wrappers of the
+/// `operators` are recorded as such
+fn expose_impl_with_operators<B: CFamily>(
+    implementation: &mut ItemImpl,
+    mod_path: &Vec<Ident>,
+    operators: &[Operator],
+) -> Result<(), CError> {
+    let original = implementation.items.clone();
+    C::expose_impl(implementation, mod_path)?;
+
+    let class_ident = match implementation.self_ty.as_ref() {
+        Type::Path(TypePath { path, .. }) => match path.segments.last() {
+            Some(segment) => segment.ident.clone(),
+            None => return Ok(()),
+        },
+        _ => return Ok(()),
+    };
+
+    // The C backend only renames the methods in place, and appends the `_with_defaults` variants
+    let mut methods = vec![];
+    let mut destructor = None;
+    for (item, converted) in original.iter().zip(&implementation.items) {
+        let (method, symbol) = match (item, converted) {
+            (ImplItem::Method(method), ImplItem::Method(converted)) => {
+                (method, &converted.sig.ident)
+            }
+            _ => continue,
+        };
+        let ImplItemMethod { attrs, sig, .. } = method;
+
+        if attrs.iter().any(|a| a.path.is_ident("destructor")) {
+            destructor = Some(symbol.clone());
+            continue;
+        }
+
+        let receiver = match sig.inputs.first() {
+            Some(FnArg::Receiver(receiver)) => Some(receiver.mutability.is_some()),
+            _ => None,
+        };
+        let operator = operators.iter().find(|op| op.method() == sig.ident);
+        let (kind, name) = match take_constructor(&mut attrs.clone(), sig).ok().flatten() {
+            Some(ConstructorOpts { name: Some(name) })
+                if B::NAMED_CONSTRUCTORS && constructs_in_place(&sig.output) =>
+            {
+                (MethodKind::Constructor, name.to_string())
+            }
+            Some(ConstructorOpts { name: Some(name) }) => (MethodKind::Static, name.to_string()),
+            Some(ConstructorOpts { name: None }) if constructs_in_place(&sig.output) => {
+                (MethodKind::Constructor, String::new())
+            }
+            Some(ConstructorOpts { name: None }) => (MethodKind::Static, "create".to_string()),
+            None => (
+                match (operator, receiver) {
+                    (Some(op), _) => MethodKind::Operator(*op),
+                    (None, Some(mutable)) => MethodKind::Instance { mutable },
+                    (None, None) => MethodKind::Static,
+                },
+                sig.ident.to_string(),
+            ),
+        };
+
+        methods.extend(methods_from_sig::<B>(sig, symbol, name, kind));
+    }
+
+    with_class(&class_ident, |class| {
+        if destructor.is_some() {
+            class.destructor = destructor;
+        }
+        class.methods.extend(methods);
+    });
+
+    Ok(())
+}
+
+/// Whether a constructor can be a constructor of the language, which can't return `null`
+fn constructs_in_place(output: &ReturnType) -> bool {
+    match output {
+        ReturnType::Type(_, ty) => {
+            match_fixed_type(ty, parse_quote!(Self))
+                || matches!(match_generic_type(ty, parse_quote!(Result)), Some(inner) if match_fixed_type(&inner[0], parse_quote!(Self)))
+        }
+        ReturnType::Default => false,
+    }
+}
+
+/// Build the method for `sig`, followed by the variant without the arguments that have a
+/// default value, if any
+fn methods_from_sig<B: CFamily>(
+    sig: &Signature,
+    symbol: &Ident,
+    name: String,
+    kind: MethodKind,
+) -> Vec<Method> {
+    let mut inputs = vec![];
+    let mut required = vec![];
+    for (i, input) in sig.inputs.iter().enumerate() {
+        if let FnArg::Typed(PatType { pat, ty, attrs, .. }) = input {
+            let ident = match pat.as_ref() {
+                Pat::Ident(PatIdent { ident, .. }) => ident.clone(),
+                _ => format_ident!("arg{}", i),
+            };
+            if !matches!(arg_default(attrs), Ok(Some(_))) {
+                required.push((ident.clone(), (**ty).clone()));
+            }
+            inputs.push((ident, (**ty).clone()));
+        }
+    }
+    let output = match &sig.output {
+        ReturnType::Default => parse_quote!(()),
+        ReturnType::Type(_, ty) => (**ty).clone(),
+    };
+
+    let has_defaults = required.len() != inputs.len();
+    let method = Method {
+        symbol: symbol.clone(),
+        name,
+        kind,
+        inputs,
+        output,
+    };
+
+    let mut methods = vec![];
+    if has_defaults {
+        let name = match method.name.as_str() {
+            name if B::OVERLOADING => name.to_string(),
+            "" => "with_defaults".to_string(),
+            name => format!("{}_with_defaults", name),
+        };
+        methods.push(Method {
+            symbol: format_ident!("{}_with_defaults", symbol),
+            name,
+            inputs: required,
+            ..method.clone()
+        });
+    }
+    methods.insert(0, method);
+
+    methods
+}
+
+fn callback_from_sig(attrs: &[syn::Attribute], sig: &Signature) -> Callback {
+    let name = attrs
+        .iter()
+        .find(|a| a.path.is_ident("expose_trait"))
+        .and_then(|attr| {
+            attr.parse_args_with(
+                Punctuated::<ExposeTraitOption, Token![,]>::parse_separated_nonempty,
+            )
+            .ok()
+        })
+        .and_then(|opts| {
+            opts.iter()
+                .map(|opt| match opt {
+                    ExposeTraitOption::Original(_, name) => name.value(),
+                })
+                .next()
+        })
+        .unwrap_or_else(|| sig.ident.to_string());
+
+    let inputs = sig
+        .inputs
+        .iter()
+        .enumerate()
+        .filter_map(|(i, input)| match input {
+            FnArg::Typed(PatType { pat, ty, .. }) => {
+                let ident = match pat.as_ref() {
+                    Pat::Ident(PatIdent { ident, .. }) => ident.clone(),
+                    _ => format_ident!("arg{}", i),
+                };
+                Some((ident, (**ty).clone()))
+            }
+            FnArg::Receiver(_) => None,
+        })
+        .collect();
+    let output = match &sig.output {
+        ReturnType::Default => parse_quote!(()),
+        ReturnType::Type(_, ty) => (**ty).clone(),
+    };
+
+    Callback {
+        name,
+        inputs,
+        output,
+    }
+}
+
+/// `to_bytes` and `from_bytes`, generated by the C backend for the `bytes` structs
+fn bytes_methods(ident: &Ident, mod_path: &[Ident]) -> Vec<Method> {
+    let prefix = ident.to_string().to_snake_case();
+
+    vec![
+        Method {
+            symbol: symbol_name(mod_path, &format_ident!("{}_to_bytes", prefix)),
+            name: "to_bytes".to_string(),
+            kind: MethodKind::Instance { mutable: false },
+            inputs: vec![],
+            output: parse_quote!(Vec<u8>),
+        },
+        Method {
+            symbol: symbol_name(mod_path, &format_ident!("{}_from_bytes", prefix)),
+            name: "from_bytes".to_string(),
+            kind: MethodKind::Static,
+            inputs: vec![(format_ident!("bytes"), parse_quote!(Vec<u8>))],
+            output: parse_quote!(Self),
+        },
+    ]
+}
+
+/// Getters and setters of the fields of an opaque struct
+fn accessors(structure: &ItemStruct, mod_path: &[Ident]) -> Vec<Method> {
+    let prefix = structure.ident.to_string().to_snake_case();
+
+    let mut methods = vec![];
+    for field in &structure.fields {
+        let opts = match field
+            .attrs
+            .iter()
+            .find(|a| a.path.is_ident("expose_struct"))
+            .and_then(|attr| {
+                attr.parse_args_with(Punctuated::<ExposeStructOpts, Token![,]>::parse_terminated)
+                    .ok()
+            }) {
+            Some(opts) => opts,
+            None => continue,
+        };
+        let field_ident = field.ident.as_ref().expect("Missing field ident");
+        let ty = &field.ty;
+
+        if opts.iter().any(|o| *o == ExposeStructOpts::Get) {
+            methods.push(Method {
+                symbol: symbol_name(mod_path, &format_ident!("{}_get_{}", prefix, field_ident)),
+                name: field_ident.to_string(),
+                kind: MethodKind::Getter,
+                inputs: vec![],
+                output: ty.clone(),
+            });
+        }
+
+        let validate = opts
+            .iter()
+            .any(|o| matches!(o, ExposeStructOpts::ValidatedSet(_)));
+        if validate || opts.iter().any(|o| *o == ExposeStructOpts::Set) {
+            let setter_ty = if is_by_value(ty) {
+                ty.clone()
+            } else {
+                parse_quote!(&#ty)
+            };
+            let output = if validate {
+                parse_quote!(Result<(), crate::common::ValidationError>)
+            } else {
+                parse_quote!(())
+            };
+
+            methods.push(Method {
+                symbol: symbol_name(mod_path, &format_ident!("{}_set_{}", prefix, field_ident)),
+                name: field_ident.to_string(),
+                kind: MethodKind::Setter,
+                inputs: vec![(format_ident!("value"), setter_ty)],
+                output,
+            });
+        }
+    }
+
+    methods
+}
@@ -1,5 +1,10 @@
 use syn::{parse_quote, Type};
 
+// Not every backend uses the macros
+#[cfg_attr(
+    not(any(feature = "c", feature = "python", feature = "ruby")),
+    allow(unused_macros)
+)]
 macro_rules! our_opaque_types {
     () => {
         [
@@ -9,6 +14,7 @@ macro_rules! our_opaque_types {
             parse_quote!(Script),
             parse_quote!(Network),
             parse_quote!(Address),
+            parse_quote!(Txid),
             parse_quote!(OutPoint),
             parse_quote!(TxOut),
             parse_quote!(TxIn),
@@ -17,8 +23,17 @@ macro_rules! our_opaque_types {
     }
 }
 
+#[cfg_attr(
+    not(any(feature = "c", feature = "wasm", feature = "node")),
+    allow(unused_macros)
+)]
 macro_rules! types_arr {
     ($( $ty:ident ),*) => {
         &[ Type::Verbatim(Default::default()), $( parse_quote!( $ty ) ),* ]
     }
 }
+
+/// Whether `ty` is one of our opaque types, which the languages take and return by reference
+pub fn is_our_opaque_type(ty: &Type) -> bool {
+    our_opaque_types!().iter().any(|t| t == ty)
+}
@@ -0,0 +1,869 @@
+//! Header-only C++17 layer over the C API
+//!
+//! The Rust side is generated by the C backend, while the original signatures of the exposed
+//! items are recorded. When the root `#[expose_mod]` is expanded they are rendered to a header
+//! that wraps every opaque struct in an RAII class, turns error codes into typed exceptions and
+//! strings into `std::string`. The header is written to `OUT_DIR` as `<crate>_<module>.hpp`,
+//! next to the C header.
+
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use quote::ToTokens;
+use syn::punctuated::Punctuated;
+use syn::{
+    Ident, Item, ItemFn, ItemImpl, ItemMod, ItemStruct, ItemTrait, Path, Token, Type, TypePath,
+    TypeReference, TypeSlice,
+};
+
+use super::c::{CError, C};
+use super::c_family::{self, CFamily, Class, Method, MethodKind, Recorded};
+use super::*;
+use crate::types::*;
+
+#[derive(Debug)]
+pub struct Cpp;
+
+impl CFamily for Cpp {
+    const OUTPUT: &'static str = "HEADER";
+
+    const REQUIRES_DESTRUCTOR: bool = true;
+
+    fn render(root: &Ident, items: Recorded) -> Vec<(PathBuf, String)> {
+        let file_name = format!("{}_{}.hpp", lib_name(), root);
+        vec![(file_name.into(), render_header(root, items))]
+    }
+}
+
+impl Lang for Cpp {
+    type Error = CError;
+
+    // The Rust side is the C API, so it uses the same mappings
+    const NAME: &'static str = C::NAME;
+
+    fn expose_fn(
+        function: &mut ItemFn,
+        mod_path: &Vec<Ident>,
+        extra: &mut Vec<Item>,
+    ) -> Result<Ident, Self::Error> {
+        c_family::expose_fn::<Self>(function, mod_path, extra)
+    }
+
+    fn expose_mod(
+        module: &mut ItemMod,
+        mod_path: &Vec<Ident>,
+        sub_items: Vec<ModuleItem>,
+    ) -> Result<Ident, Self::Error> {
+        c_family::expose_mod::<Self>(module, mod_path, sub_items)
+    }
+
+    fn expose_struct(
+        structure: &mut ItemStruct,
+        opts: Punctuated<ExposeStructOpts, Token![,]>,
+        mod_path: &Vec<Ident>,
+        extra: &mut Vec<Item>,
+    ) -> Result<Ident, Self::Error> {
+        c_family::expose_struct(structure, opts, mod_path, extra)
+    }
+
+    fn expose_impl(
+        implementation: &mut ItemImpl,
+        mod_path: &Vec<Ident>,
+    ) -> Result<(), Self::Error> {
+        c_family::expose_impl::<Self>(implementation, mod_path)
+    }
+
+    fn expose_trait(
+        tr: &mut ItemTrait,
+        mod_path: &Vec<Ident>,
+        extra: &mut Vec<Item>,
+    ) -> Result<Ident, Self::Error> {
+        // Callbacks are only available through the C API
+        c_family::expose_trait::<Self>(tr, mod_path, extra)
+    }
+
+    fn expose_operators(
+        self_ty: &Type,
+        operators: Vec<ExposedOperator>,
+        mod_path: &Vec<Ident>,
+        extra: &mut Vec<Item>,
+    ) -> Result<(), Self::Error> {
+        c_family::expose_operators::<Self>(self_ty, operators, mod_path, extra)
+    }
+
+    fn expose_getter(
+        field: &mut Field,
+        is_opaque: bool,
+        impl_block: &mut ItemImpl,
+    ) -> Result<(), Self::Error> {
+        C::expose_getter(field, is_opaque, impl_block)
+    }
+
+    fn expose_inline_getter(
+        field: &mut Field,
+        impl_block: &mut ItemImpl,
+    ) -> Result<(), Self::Error> {
+        C::expose_inline_getter(field, impl_block)
+    }
+
+    fn expose_inline_setter(
+        field: &mut Field,
+        validate: Option<&Path>,
+        impl_block: &mut ItemImpl,
+    ) -> Result<(), Self::Error> {
+        C::expose_inline_setter(field, validate, impl_block)
+    }
+
+    fn convert_getter_setter_ty(ty: Type) -> Result<(Type, Type), Self::Error> {
+        C::convert_getter_setter_ty(ty)
+    }
+
+    fn convert_input(ty: Type) -> Result<Input, Self::Error> {
+        C::convert_input(ty)
+    }
+
+    fn convert_output(output: Type) -> Result<Output, Self::Error> {
+        C::convert_output(output)
+    }
+}
+
+/// Rust identifiers that are reserved in C++ get a trailing underscore
+fn escape_keyword<T: ToString>(ident: T) -> String {
+    const KEYWORDS: &[&str] = &[
+        "alignas",
+        "alignof",
+        "and",
+        "asm",
+        "auto",
+        "bool",
+        "case",
+        "catch",
+        "char",
+        "class",
+        "const_cast",
+        "default",
+        "delete",
+        "double",
+        "explicit",
+        "export",
+        "float",
+        "friend",
+        "goto",
+        "inline",
+        "int",
+        "long",
+        "namespace",
+        "new",
+        "noexcept",
+        "not",
+        "nullptr",
+        "operator",
+        "or",
+        "private",
+        "protected",
+        "public",
+        "register",
+        "short",
+        "signed",
+        "sizeof",
+        "switch",
+        "template",
+        "this",
+        "throw",
+        "try",
+        "typedef",
+        "typeid",
+        "typename",
+        "union",
+        "unsigned",
+        "using",
+        "virtual",
+        "void",
+        "volatile",
+        "xor",
+    ];
+
+    let ident = ident.to_string();
+    if KEYWORDS.contains(&ident.as_str()) {
+        ident + "_"
+    } else {
+        ident
+    }
+}
+
+/// Name of a method in C++: operators are named after the operator, accessors are `get_<field>`
+/// and `set_<field>`
+fn method_name(method: &Method) -> String {
+    match &method.kind {
+        MethodKind::Operator(op) => match op {
+            Operator::Add => "operator+",
+            Operator::Sub | Operator::Neg => "operator-",
+            Operator::Mul => "operator*",
+            Operator::Index => "operator[]",
+        }
+        .to_string(),
+        MethodKind::Getter => format!("get_{}", method.name),
+        MethodKind::Setter => format!("set_{}", method.name),
+        _ => escape_keyword(&method.name),
+    }
+}
+
+/// How a Rust type crosses the C API, from the point of view of the C++ layer
+#[derive(Debug, Clone)]
+enum CppType<'a> {
+    Unit,
+    Primitive(&'static str),
+    String,
+    /// Opaque struct, passed as a pointer and returned through an out pointer
+    Class(&'a Class),
+    /// Transparent struct, passed by value
+    Plain(&'a Class),
+    Optional(&'a Class),
+    /// `Vec` or slice of primitives as an argument, `Vec<u8>` as a return value. Crosses the C
+    /// API as an `Arr<T>`, with the C++ and the Rust names of the primitive
+    Vector(&'static str, String),
+    /// Error code, with the ok value returned through an out pointer
+    Result(Box<CppType<'a>>, String),
+}
+
+fn primitive(ty: &Type) -> Option<&'static str> {
+    Some(match c_family::primitive_ident(ty)?.as_str() {
+        "bool" => "bool",
+        "i8" => "int8_t",
+        "u8" => "uint8_t",
+        "i16" => "int16_t",
+        "u16" => "uint16_t",
+        "i32" => "int32_t",
+        "u32" => "uint32_t",
+        "i64" => "int64_t",
+        "u64" => "uint64_t",
+        "isize" => "intptr_t",
+        "usize" => "size_t",
+        "f32" => "float",
+        "f64" => "double",
+        _ => return None,
+    })
+}
+
+struct Context<'a> {
+    lib: String,
+    c_prefix: String,
+    classes: &'a [Class],
+    errors: RefCell<BTreeSet<String>>,
+}
+
+impl<'a> Context<'a> {
+    fn class(&self, ty: &Type, self_class: Option<&'a Class>) -> Option<&'a Class> {
+        if match_fixed_type(ty, parse_quote!(Self)) {
+            return self_class;
+        }
+        match ty {
+            Type::Path(TypePath { path, .. }) => {
+                let ident = &path.segments.last()?.ident;
+                self.classes.iter().find(|c| &c.ident == ident)
+            }
+            _ => None,
+        }
+    }
+
+    fn error(&self, ty: &Type) -> Option<String> {
+        let ident = match ty {
+            Type::Path(TypePath { path, .. }) => path.segments.last()?.ident.to_string(),
+            _ => return None,
+        };
+        if lookup_mapping(C::NAME, ty).is_none()
+            && ident != "ValidationError"
+            && ident != "IndexError"
+        {
+            return None;
+        }
+
+        self.errors.borrow_mut().insert(ident.clone());
+        Some(ident)
+    }
+
+    fn classify(&self, ty: &Type, self_class: Option<&'a Class>) -> Option<CppType<'a>> {
+        if let Some(target) = lookup_mapping(C::NAME, ty) {
+            return self.classify(&target, self_class);
+        }
+        if let Type::Tuple(tuple) = ty {
+            return if tuple.elems.is_empty() {
+                Some(CppType::Unit)
+            } else {
+                None
+            };
+        }
+        if let Some(name) = primitive(ty) {
+            return Some(CppType::Primitive(name));
+        }
+        if match_fixed_type(ty, parse_quote!(String)) {
+            return Some(CppType::String);
+        }
+        // Returned vectors are freed with `bytes_free()`
+        if ty == &parse_quote!(Vec<u8>) {
+            return Some(CppType::Vector("uint8_t", "u8".into()));
+        }
+        if let Some(inner) = match_generic_type(ty, parse_quote!(Option)) {
+            return match self.class(&inner[0], self_class) {
+                Some(class) if class.opaque => Some(CppType::Optional(class)),
+                _ => None,
+            };
+        }
+        if let Some(inner) = match_generic_type(ty, parse_quote!(Result)) {
+            if inner.len() != 2 {
+                return None;
+            }
+            let ok = match self.classify(&inner[0], self_class)? {
+                ok @ CppType::Unit
+                | ok @ CppType::Primitive(_)
+                | ok @ CppType::String
+                | ok @ CppType::Vector(..)
+                | ok @ CppType::Class(_) => ok,
+                _ => return None,
+            };
+            return Some(CppType::Result(Box::new(ok), self.error(&inner[1])?));
+        }
+
+        self.class(ty, self_class).map(|class| match class.opaque {
+            true => CppType::Class(class),
+            false => CppType::Plain(class),
+        })
+    }
+
+    fn classify_input(&self, ty: &Type, self_class: Option<&'a Class>) -> Option<CppType<'a>> {
+        if let Some(inner) = match_generic_type(ty, parse_quote!(Vec)) {
+            let name = primitive(&inner[0])?;
+            return Some(CppType::Vector(
+                name,
+                inner[0].to_token_stream().to_string(),
+            ));
+        }
+
+        match ty {
+            Type::Reference(TypeReference { elem, .. }) => match elem.as_ref() {
+                // Slices are passed like vectors
+                Type::Slice(TypeSlice { elem, .. }) => Some(CppType::Vector(
+                    primitive(elem)?,
+                    elem.to_token_stream().to_string(),
+                )),
+                // Opaque structs are only taken by reference
+                elem => match self.classify(elem, self_class)? {
+                    class @ CppType::Class(_) => Some(class),
+                    _ => None,
+                },
+            },
+            ty => match self.classify(ty, self_class)? {
+                ty @ CppType::Primitive(_) | ty @ CppType::String | ty @ CppType::Plain(_) => {
+                    Some(ty)
+                }
+                _ => None,
+            },
+        }
+    }
+
+    fn c_type(&self, class: &Class) -> String {
+        format!("::{}{}", self.c_prefix, class.ident)
+    }
+
+    /// Name of a class as seen from the namespace of `from`
+    fn class_name(&self, class: &Class, from: &[Ident]) -> String {
+        if class.mod_path == from {
+            class.ident.to_string()
+        } else {
+            format!("::{}::{}", self.namespace(&class.mod_path), class.ident)
+        }
+    }
+
+    fn namespace(&self, mod_path: &[Ident]) -> String {
+        std::iter::once(self.lib.clone())
+            .filter(|lib| !lib.is_empty())
+            .chain(mod_path.iter().map(|i| i.to_string()))
+            .collect::<Vec<_>>()
+            .join("::")
+    }
+
+    fn type_name(&self, ty: &CppType, from: &[Ident]) -> String {
+        match ty {
+            CppType::Unit => "void".into(),
+            CppType::Primitive(name) => name.to_string(),
+            CppType::String => "std::string".into(),
+            CppType::Class(class) | CppType::Plain(class) => self.class_name(class, from),
+            CppType::Optional(class) => format!("std::optional<{}>", self.class_name(class, from)),
+            CppType::Result(ok, _) => self.type_name(ok, from),
+            CppType::Vector(name, _) => format!("std::vector<{}>", name),
+        }
+    }
+
+    fn param(&self, ident: &str, ty: &CppType, from: &[Ident]) -> (String, String) {
+        match ty {
+            CppType::String => (
+                format!("const std::string &{}", ident),
+                format!("{}.c_str()", ident),
+            ),
+            CppType::Class(class) => (
+                format!("const {} &{}", self.class_name(class, from), ident),
+                format!("{}.as_raw()", ident),
+            ),
+            CppType::Vector(name, rust_name) => (
+                format!("const std::vector<{}> &{}", name, ident),
+                format!(
+                    "::{}Arr_{}{{{ident}.data(), {ident}.size()}}",
+                    self.c_prefix,
+                    rust_name,
+                    ident = ident
+                ),
+            ),
+            ty => (
+                format!("{} {}", self.type_name(ty, from), ident),
+                ident.to_string(),
+            ),
+        }
+    }
+
+    /// Declare the out pointer for a value returned by reference, and the expression that
+    /// converts it to the C++ type
+    fn out_value(&self, ty: &CppType, from: &[Ident]) -> Option<(String, String)> {
+        match ty {
+            CppType::Primitive(name) => Some((format!("{} out{{}};", name), "out".into())),
+            CppType::String => Some(("char *out = nullptr;".into(), "take_string(out)".into())),
+            CppType::Vector(..) => Some((
+                format!("::{}Arr_u8 out{{}};", self.c_prefix),
+                "take_bytes(out)".into(),
+            )),
+            CppType::Class(class) => Some((
+                format!("{} *out = nullptr;", self.c_type(class)),
+                format!("{}(out)", self.class_name(class, from)),
+            )),
+            _ => None,
+        }
+    }
+
+    fn body(
+        &self,
+        symbol: &Ident,
+        mut args: Vec<String>,
+        output: &CppType,
+        from: &[Ident],
+    ) -> Vec<String> {
+        let call = |args: &[String]| format!("::{}({})", symbol, args.join(", "));
+
+        match output {
+            CppType::Unit => vec![format!("{};", call(&args))],
+            CppType::Primitive(_) | CppType::Plain(_) => vec![format!("return {};", call(&args))],
+            CppType::String => vec![format!("return take_string({});", call(&args))],
+            CppType::Vector(..) => vec![format!("return take_bytes({});", call(&args))],
+            CppType::Class(_) | CppType::Optional(_) => {
+                let class = match output {
+                    CppType::Class(class) | CppType::Optional(class) => class,
+                    _ => unreachable!(),
+                };
+                args.push("&out".into());
+
+                let mut lines = vec![
+                    format!("{} *out = nullptr;", self.c_type(class)),
+                    format!("{};", call(&args)),
+                ];
+                if let CppType::Optional(_) = output {
+                    lines.push("if (!out) {".into());
+                    lines.push("    return std::nullopt;".into());
+                    lines.push("}".into());
+                }
+                lines.push(format!("return {}(out);", self.class_name(class, from)));
+
+                lines
+            }
+            CppType::Result(ok, error) => {
+                let out = self.out_value(ok, from);
+                let mut lines = vec![];
+                if let Some((decl, _)) = &out {
+                    lines.push(decl.clone());
+                    args.push("&out".into());
+                }
+                lines.push(format!("const auto code = {};", call(&args)));
+                lines.push("if (code != 0) {".into());
+                lines.push(format!("    throw {}(code);", error));
+                lines.push("}".into());
+                if let Some((_, value)) = out {
+                    lines.push(format!("return {};", value));
+                }
+
+                lines
+            }
+        }
+    }
+}
+
+/// A method ready to be rendered: its declaration inside the class and its definition
+struct RenderedMethod {
+    declaration: String,
+    definition: String,
+}
+
+fn render_method(
+    ctx: &Context,
+    class: Option<&Class>,
+    method: &Method,
+    from: &[Ident],
+) -> Option<RenderedMethod> {
+    let mut params = vec![];
+    let mut args = vec![];
+    if let MethodKind::Instance { .. }
+    | MethodKind::Operator(_)
+    | MethodKind::Getter
+    | MethodKind::Setter = method.kind
+    {
+        args.push("ptr_".to_string());
+    }
+    for (ident, ty) in &method.inputs {
+        let ident = escape_keyword(ident);
+        let (param, arg) = ctx.param(&ident, &ctx.classify_input(ty, class)?, from);
+        params.push(param);
+        args.push(arg);
+    }
+    let params = params.join(", ");
+
+    let output = ctx.classify(&method.output, class)?;
+    if method.kind == MethodKind::Getter {
+        match output {
+            // Other opaque fields are borrowed from the struct, so they can't be owned by a class
+            CppType::Class(class) if !class.shared => return None,
+            CppType::Primitive(name) if name == "bool" || name == "float" || name == "double" => {
+                return None
+            }
+            CppType::Primitive(_) | CppType::Class(_) => {}
+            _ => return None,
+        }
+    }
+
+    let indent = |lines| Cpp::indent(lines, 1);
+    let name = method_name(method);
+
+    if method.kind == MethodKind::Constructor {
+        let class = class?;
+        args.push("&ptr_".into());
+        let call = format!("::{}({})", method.symbol, args.join(", "));
+        let body = match output {
+            CppType::Result(_, error) => vec![
+                format!("const auto code = {};", call),
+                "if (code != 0) {".into(),
+                format!("    throw {}(code);", error),
+                "}".into(),
+            ],
+            _ => vec![format!("{};", call)],
+        };
+
+        return Some(RenderedMethod {
+            declaration: format!("explicit {}({});", class.ident, params),
+            definition: format!(
+                "inline {ident}::{ident}({}) : ptr_(nullptr) {{\n{}}}\n",
+                params,
+                indent(body),
+                ident = class.ident
+            ),
+        });
+    }
+
+    let ret = ctx.type_name(&output, from);
+    let body = match (&method.kind, &output) {
+        // Getters return the pointer directly, with a new reference for `shared` structs
+        (MethodKind::Getter, CppType::Class(class)) => indent(vec![format!(
+            "return {}(::{}(ptr_));",
+            ctx.class_name(class, from),
+            method.symbol
+        )]),
+        _ => indent(ctx.body(&method.symbol, args, &output, from)),
+    };
+    let constness = match method.kind {
+        MethodKind::Instance { mutable: false } | MethodKind::Operator(_) | MethodKind::Getter => {
+            " const"
+        }
+        _ => "",
+    };
+
+    Some(match class {
+        Some(class) => RenderedMethod {
+            declaration: format!(
+                "{}{} {}({}){};",
+                if method.kind == MethodKind::Static {
+                    "static "
+                } else {
+                    ""
+                },
+                ret,
+                name,
+                params,
+                constness
+            ),
+            definition: format!(
+                "inline {} {}::{}({}){} {{\n{}}}\n",
+                ret, class.ident, name, params, constness, body
+            ),
+        },
+        None => RenderedMethod {
+            declaration: String::new(),
+            definition: format!("inline {} {}({}) {{\n{}}}\n", ret, name, params, body),
+        },
+    })
+}
+
+fn render_class(ctx: &Context, class: &Class, out: &mut String, definitions: &mut String) {
+    let ident = &class.ident;
+    let c_type = ctx.c_type(class);
+
+    writeln!(out, "class {} {{", ident).unwrap();
+    writeln!(out, "public:").unwrap();
+    writeln!(
+        out,
+        "    /// Take the ownership of a pointer returned by the C API"
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "    explicit {}({} *ptr) noexcept : ptr_(ptr) {{}}",
+        ident, c_type
+    )
+    .unwrap();
+    // Opaque structs without a destructor are rejected by `expose_mod`
+    let destroy = class
+        .destructor
+        .as_ref()
+        .expect("Opaque struct without a destructor");
+    writeln!(
+        out,
+        "    ~{}() {{\n        if (ptr_) {{\n            ::{}(ptr_);\n        }}\n    }}",
+        ident, destroy
+    )
+    .unwrap();
+    writeln!(out).unwrap();
+    writeln!(
+        out,
+        "    {ident}({ident} &&other) noexcept : ptr_(std::exchange(other.ptr_, nullptr)) {{}}",
+        ident = ident
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "    {ident} &operator=({ident} &&other) noexcept {{\n        std::swap(ptr_, other.ptr_);\n        return *this;\n    }}",
+        ident = ident
+    )
+    .unwrap();
+    if let Some(retain) = &class.retain {
+        // Copies share the same value, by taking a new reference to it
+        writeln!(
+            out,
+            "    {ident}(const {ident} &other) noexcept : ptr_(other.ptr_ ? ::{retain}(other.ptr_) : nullptr) {{}}",
+            ident = ident,
+            retain = retain
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "    {ident} &operator=(const {ident} &other) noexcept {{\n        {ident} copy(other);\n        std::swap(ptr_, copy.ptr_);\n        return *this;\n    }}",
+            ident = ident
+        )
+        .unwrap();
+    } else {
+        writeln!(out, "    {ident}(const {ident} &) = delete;", ident = ident).unwrap();
+        writeln!(
+            out,
+            "    {ident} &operator=(const {ident} &) = delete;",
+            ident = ident
+        )
+        .unwrap();
+    }
+    writeln!(out).unwrap();
+    writeln!(
+        out,
+        "    /// The underlying pointer, still owned by this object"
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "    {} *as_raw() const noexcept {{\n        return ptr_;\n    }}",
+        c_type
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "    /// Give up the ownership of the underlying pointer"
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "    {} *into_raw() noexcept {{\n        return std::exchange(ptr_, nullptr);\n    }}",
+        c_type
+    )
+    .unwrap();
+
+    if !class.methods.is_empty() {
+        writeln!(out).unwrap();
+    }
+    for method in &class.methods {
+        match render_method(ctx, Some(class), method, &class.mod_path) {
+            Some(RenderedMethod {
+                declaration,
+                definition,
+            }) => {
+                writeln!(out, "    {}", declaration).unwrap();
+                writeln!(definitions, "{}", definition).unwrap();
+            }
+            None => writeln!(
+                out,
+                "    // `{}` is not available: unsupported argument or return types",
+                method_name(method)
+            )
+            .unwrap(),
+        }
+    }
+
+    writeln!(out).unwrap();
+    writeln!(out, "private:").unwrap();
+    writeln!(out, "    {} *ptr_;", c_type).unwrap();
+    writeln!(out, "}};").unwrap();
+}
+
+fn render_header(root: &Ident, items: Recorded) -> String {
+    let namespaces = items.modules();
+    let Recorded {
+        classes, functions, ..
+    } = &items;
+
+    let lib = lib_name();
+    let ctx = Context {
+        lib: lib.clone(),
+        c_prefix: std::env::var("EXPOSE_C_PREFIX").unwrap_or_default(),
+        classes,
+        errors: RefCell::new(BTreeSet::new()),
+    };
+
+    let mut declarations = String::new();
+    let mut definitions = String::new();
+    for namespace in &namespaces {
+        let mut decl = String::new();
+        let mut defs = String::new();
+        for class in classes.iter().filter(|c| c.mod_path == *namespace) {
+            if class.opaque {
+                render_class(&ctx, class, &mut decl, &mut defs);
+                writeln!(decl).unwrap();
+            }
+        }
+        for (_, function) in functions.iter().filter(|(path, _)| path == namespace) {
+            match render_method(&ctx, None, function, namespace) {
+                Some(RenderedMethod { definition, .. }) => {
+                    writeln!(defs, "{}", definition).unwrap()
+                }
+                None => writeln!(
+                    defs,
+                    "// `{}` is not available: unsupported argument or return types\n",
+                    method_name(function)
+                )
+                .unwrap(),
+            }
+        }
+
+        let ns = ctx.namespace(namespace);
+        if !decl.is_empty() {
+            writeln!(
+                declarations,
+                "namespace {} {{\n\n{}}} // namespace {}\n",
+                ns, decl, ns
+            )
+            .unwrap();
+        }
+        if !defs.is_empty() {
+            writeln!(
+                definitions,
+                "namespace {} {{\n\n{}}} // namespace {}\n",
+                ns, defs, ns
+            )
+            .unwrap();
+        }
+    }
+
+    let mut forward = String::new();
+    for namespace in &namespaces {
+        let mut decl = String::new();
+        for class in classes.iter().filter(|c| c.mod_path == *namespace) {
+            match class.opaque {
+                true => writeln!(decl, "class {};", class.ident).unwrap(),
+                false => writeln!(decl, "using {} = {};", class.ident, ctx.c_type(class)).unwrap(),
+            }
+        }
+        if !decl.is_empty() {
+            let ns = ctx.namespace(namespace);
+            writeln!(
+                forward,
+                "namespace {} {{\n\n{}\n}} // namespace {}\n",
+                ns, decl, ns
+            )
+            .unwrap();
+        }
+    }
+
+    let guard_prefix = lib.to_uppercase();
+    let mut errors = String::new();
+    for error in ctx.errors.borrow().iter() {
+        let guard = format!("{}_{}_DEFINED", guard_prefix, error.to_uppercase());
+        writeln!(
+            errors,
+            "#ifndef {guard}\n#define {guard}\nclass {error} : public Exception {{\npublic:\n    explicit {error}(int32_t code) : Exception(\"{error}\", code) {{}}\n}};\n#endif\n",
+            guard = guard,
+            error = error
+        )
+        .unwrap();
+    }
+    let helpers_guard = format!("{}_HELPERS_DEFINED", guard_prefix);
+    let helpers = format!(
+        "#ifndef {guard}\n#define {guard}\n\
+         /// Base class of the exceptions thrown when the C API returns an error code\n\
+         class Exception : public std::runtime_error {{\n\
+         public:\n    \
+         Exception(const char *name, int32_t code)\n        \
+         : std::runtime_error(std::string(name) + \" (code \" + std::to_string(code) + \")\"), code_(code) {{}}\n\n    \
+         int32_t code() const noexcept {{\n        return code_;\n    }}\n\n\
+         private:\n    int32_t code_;\n}};\n\n\
+         /// Copy a string returned by the C API, and free it\n\
+         inline std::string take_string(char *s) {{\n    \
+         std::string copy(s);\n    \
+         ::{c}string_free(s);\n    \
+         return copy;\n\
+         }}\n\n\
+         /// Copy a vector returned by the C API, and free it\n\
+         inline std::vector<uint8_t> take_bytes(::{c}Arr_u8 arr) {{\n    \
+         std::vector<uint8_t> copy(arr.ptr, arr.ptr + arr.len);\n    \
+         ::{c}bytes_free(arr);\n    \
+         return copy;\n\
+         }}\n#endif\n",
+        guard = helpers_guard,
+        c = ctx.c_prefix,
+    );
+    let crate_ns = ctx.namespace(&[]);
+    let errors = if crate_ns.is_empty() {
+        format!("{}\n{}", helpers, errors)
+    } else {
+        format!(
+            "namespace {ns} {{\n\n{}\n{}}} // namespace {ns}\n",
+            helpers,
+            errors,
+            ns = crate_ns
+        )
+    };
+
+    let guard = format!("{}_{}_HPP", guard_prefix, root.to_string().to_uppercase());
+    format!(
+        "// Generated by `#[expose_mod]`, do not edit\n\
+         #ifndef {guard}\n#define {guard}\n\n\
+         #include <cstdint>\n#include <optional>\n#include <stdexcept>\n#include <string>\n#include <utility>\n#include <vector>\n\n\
+         #include \"{lib}.h\"\n\n\
+         {errors}\n{forward}{declarations}{definitions}\
+         #endif // {guard}\n",
+        guard = guard,
+        lib = lib,
+        errors = errors,
+        forward = forward,
+        declarations = declarations,
+        definitions = definitions,
+    )
+}
@@ -0,0 +1,1247 @@
+//! C# bindings over the C API, through P/Invoke
+//!
+//! Like the Swift layer, the Rust side is generated by the C backend while the original
+//! signatures of the exposed items are recorded. When the root `#[expose_mod]` is expanded they
+//! are rendered to a C# source with the `DllImport` declarations of the C functions, and a
+//! `SafeHandle` subclass for every opaque struct that frees its pointer when it's disposed or
+//! finalized. Error codes are thrown as exceptions, and exposed traits are implemented by .NET
+//! objects called through `UnmanagedFunctionPointer` delegates. The source is written to
+//! `OUT_DIR` as `<crate>_<module>.cs`, in the namespace named after the crate.
+
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use syn::punctuated::Punctuated;
+use syn::{
+    Ident, Item, ItemFn, ItemImpl, ItemMod, ItemStruct, ItemTrait, Path, Token, Type, TypePath,
+    TypeReference, TypeSlice,
+};
+
+use super::c::{CError, C};
+use super::c_family::{self, CFamily, Class, Method, MethodKind, Recorded, Trait};
+use super::*;
+use crate::types::*;
+
+#[derive(Debug)]
+pub struct CSharp;
+
+impl CFamily for CSharp {
+    const OUTPUT: &'static str = "CSHARP";
+    const CALLBACKS: bool = true;
+
+    fn render(root: &Ident, items: Recorded) -> Vec<(PathBuf, String)> {
+        let file_name = format!("{}_{}.cs", lib_name(), root);
+        vec![(file_name.into(), render_source(items))]
+    }
+}
+
+impl Lang for CSharp {
+    type Error = CError;
+
+    // The Rust side is the C API, so it uses the same mappings
+    const NAME: &'static str = C::NAME;
+
+    fn expose_fn(
+        function: &mut ItemFn,
+        mod_path: &Vec<Ident>,
+        extra: &mut Vec<Item>,
+    ) -> Result<Ident, Self::Error> {
+        c_family::expose_fn::<Self>(function, mod_path, extra)
+    }
+
+    fn expose_mod(
+        module: &mut ItemMod,
+        mod_path: &Vec<Ident>,
+        sub_items: Vec<ModuleItem>,
+    ) -> Result<Ident, Self::Error> {
+        c_family::expose_mod::<Self>(module, mod_path, sub_items)
+    }
+
+    fn expose_struct(
+        structure: &mut ItemStruct,
+        opts: Punctuated<ExposeStructOpts, Token![,]>,
+        mod_path: &Vec<Ident>,
+        extra: &mut Vec<Item>,
+    ) -> Result<Ident, Self::Error> {
+        c_family::expose_struct(structure, opts, mod_path, extra)
+    }
+
+    fn expose_impl(
+        implementation: &mut ItemImpl,
+        mod_path: &Vec<Ident>,
+    ) -> Result<(), Self::Error> {
+        c_family::expose_impl::<Self>(implementation, mod_path)
+    }
+
+    fn expose_trait(
+        tr: &mut ItemTrait,
+        mod_path: &Vec<Ident>,
+        extra: &mut Vec<Item>,
+    ) -> Result<Ident, Self::Error> {
+        c_family::expose_trait::<Self>(tr, mod_path, extra)
+    }
+
+    fn expose_operators(
+        self_ty: &Type,
+        operators: Vec<ExposedOperator>,
+        mod_path: &Vec<Ident>,
+        extra: &mut Vec<Item>,
+    ) -> Result<(), Self::Error> {
+        c_family::expose_operators::<Self>(self_ty, operators, mod_path, extra)
+    }
+
+    fn expose_getter(
+        field: &mut Field,
+        is_opaque: bool,
+        impl_block: &mut ItemImpl,
+    ) -> Result<(), Self::Error> {
+        C::expose_getter(field, is_opaque, impl_block)
+    }
+
+    fn expose_inline_getter(
+        field: &mut Field,
+        impl_block: &mut ItemImpl,
+    ) -> Result<(), Self::Error> {
+        C::expose_inline_getter(field, impl_block)
+    }
+
+    fn expose_inline_setter(
+        field: &mut Field,
+        validate: Option<&Path>,
+        impl_block: &mut ItemImpl,
+    ) -> Result<(), Self::Error> {
+        C::expose_inline_setter(field, validate, impl_block)
+    }
+
+    fn convert_getter_setter_ty(ty: Type) -> Result<(Type, Type), Self::Error> {
+        C::convert_getter_setter_ty(ty)
+    }
+
+    fn convert_input(ty: Type) -> Result<Input, Self::Error> {
+        C::convert_input(ty)
+    }
+
+    fn convert_output(output: Type) -> Result<Output, Self::Error> {
+        C::convert_output(output)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CsOperator {
+    Binary(&'static str),
+    Unary(&'static str),
+    Indexer,
+}
+
+fn cs_operator(op: Operator) -> CsOperator {
+    match op {
+        Operator::Add => CsOperator::Binary("+"),
+        Operator::Sub => CsOperator::Binary("-"),
+        Operator::Mul => CsOperator::Binary("*"),
+        Operator::Neg => CsOperator::Unary("-"),
+        Operator::Index => CsOperator::Indexer,
+    }
+}
+
+/// C# keywords are escaped with `@`, the names used by the generated code are suffixed with an
+/// underscore
+fn param_name(ident: &Ident) -> String {
+    const KEYWORDS: &[&str] = &[
+        "abstract",
+        "as",
+        "base",
+        "bool",
+        "break",
+        "byte",
+        "case",
+        "catch",
+        "char",
+        "checked",
+        "class",
+        "const",
+        "continue",
+        "decimal",
+        "default",
+        "delegate",
+        "do",
+        "double",
+        "else",
+        "enum",
+        "event",
+        "explicit",
+        "extern",
+        "false",
+        "finally",
+        "fixed",
+        "float",
+        "for",
+        "foreach",
+        "goto",
+        "if",
+        "implicit",
+        "in",
+        "int",
+        "interface",
+        "internal",
+        "is",
+        "lock",
+        "long",
+        "namespace",
+        "new",
+        "null",
+        "object",
+        "operator",
+        "out",
+        "override",
+        "params",
+        "private",
+        "protected",
+        "public",
+        "readonly",
+        "ref",
+        "return",
+        "sbyte",
+        "sealed",
+        "short",
+        "sizeof",
+        "stackalloc",
+        "static",
+        "string",
+        "struct",
+        "switch",
+        "this",
+        "throw",
+        "true",
+        "try",
+        "typeof",
+        "uint",
+        "ulong",
+        "unchecked",
+        "unsafe",
+        "ushort",
+        "using",
+        "virtual",
+        "void",
+        "volatile",
+        "while",
+    ];
+    const RESERVED: &[&str] = &["code", "ptrOut", "self"];
+
+    let name = camel_case(&ident.to_string(), false);
+    if KEYWORDS.contains(&name.as_str()) {
+        format!("@{}", name)
+    } else if RESERVED.contains(&name.as_str()) {
+        format!("{}_", name)
+    } else {
+        name
+    }
+}
+
+/// How a Rust type crosses the C API, from the point of view of the C# layer
+#[derive(Clone)]
+enum CsType<'a> {
+    Unit,
+    /// C# name of the primitive
+    Primitive(&'static str),
+    /// Marshalled as UTF-8 when passed, copied and freed when returned
+    String,
+    /// Opaque struct, passed as a `SafeHandle` and returned through an out pointer
+    Class(&'a Class),
+    /// Transparent struct with primitive fields, passed by value
+    Plain(&'a Class),
+    Optional(&'a Class),
+    /// Array of primitives from a `Vec` or a slice, pinned and passed as an `Arr`. `byte[]` from a
+    /// `Vec<u8>` as a return value, copied from the returned `Arr`
+    Array(&'static str),
+    /// Error code, with the ok value returned through an out pointer
+    Result(Box<CsType<'a>>, String),
+}
+
+fn primitive(ty: &Type) -> Option<&'static str> {
+    Some(match c_family::primitive_ident(ty)?.as_str() {
+        "bool" => "bool",
+        "i8" => "sbyte",
+        "u8" => "byte",
+        "i16" => "short",
+        "u16" => "ushort",
+        "i32" => "int",
+        "u32" => "uint",
+        "i64" => "long",
+        "u64" => "ulong",
+        "isize" => "nint",
+        "usize" => "nuint",
+        "f32" => "float",
+        "f64" => "double",
+        _ => return None,
+    })
+}
+
+/// Rust `bool`s are a single byte, while .NET marshals them as a 4-byte `BOOL` by default
+fn marshal_as(cs: &str) -> &'static str {
+    match cs {
+        "bool" => "[MarshalAs(UnmanagedType.U1)] ",
+        "string" => "[MarshalAs(UnmanagedType.LPUTF8Str)] ",
+        _ => "",
+    }
+}
+
+struct Context<'a> {
+    /// Name of the base class of the exceptions
+    lib: String,
+    c_prefix: String,
+    classes: &'a [Class],
+    traits: &'a [Trait],
+    errors: RefCell<BTreeSet<String>>,
+    /// `DllImport` declarations of the functions called so far
+    natives: RefCell<Vec<(Ident, String)>>,
+}
+
+impl<'a> Context<'a> {
+    fn class(&self, ty: &Type, self_class: Option<&'a Class>) -> Option<&'a Class> {
+        if match_fixed_type(ty, parse_quote!(Self)) {
+            return self_class;
+        }
+        match ty {
+            Type::Path(TypePath { path, .. }) => {
+                let ident = &path.segments.last()?.ident;
+                self.classes.iter().find(|c| &c.ident == ident)
+            }
+            _ => None,
+        }
+    }
+
+    fn error(&self, ty: &Type) -> Option<String> {
+        let ident = match ty {
+            Type::Path(TypePath { path, .. }) => path.segments.last()?.ident.to_string(),
+            _ => return None,
+        };
+        if lookup_mapping(C::NAME, ty).is_none()
+            && ident != "ValidationError"
+            && ident != "IndexError"
+        {
+            return None;
+        }
+
+        self.errors.borrow_mut().insert(ident.clone());
+        Some(format!("{}Exception", ident))
+    }
+
+    fn classify(&self, ty: &Type, self_class: Option<&'a Class>) -> Option<CsType<'a>> {
+        if let Some(target) = lookup_mapping(C::NAME, ty) {
+            return self.classify(&target, self_class);
+        }
+        if let Type::Tuple(tuple) = ty {
+            return if tuple.elems.is_empty() {
+                Some(CsType::Unit)
+            } else {
+                None
+            };
+        }
+        if let Some(name) = primitive(ty) {
+            return Some(CsType::Primitive(name));
+        }
+        if match_fixed_type(ty, parse_quote!(String)) {
+            return Some(CsType::String);
+        }
+        // Returned vectors are freed with `bytes_free()`
+        if ty == &parse_quote!(Vec<u8>) {
+            return Some(CsType::Array("byte"));
+        }
+        if let Some(inner) = match_generic_type(ty, parse_quote!(Option)) {
+            return match self.class(&inner[0], self_class) {
+                Some(class) if class.opaque => Some(CsType::Optional(class)),
+                _ => None,
+            };
+        }
+        if let Some(inner) = match_generic_type(ty, parse_quote!(Result)) {
+            if inner.len() != 2 {
+                return None;
+            }
+            let ok = match self.classify(&inner[0], self_class)? {
+                ok @ CsType::Unit
+                | ok @ CsType::Primitive(_)
+                | ok @ CsType::String
+                | ok @ CsType::Array(_)
+                | ok @ CsType::Class(_) => ok,
+                _ => return None,
+            };
+            return Some(CsType::Result(Box::new(ok), self.error(&inner[1])?));
+        }
+
+        match self.class(ty, self_class)? {
+            class if class.opaque => Some(CsType::Class(class)),
+            class if class.fields.iter().all(|(_, ty)| primitive(ty).is_some()) => {
+                Some(CsType::Plain(class))
+            }
+            _ => None,
+        }
+    }
+
+    fn classify_input(&self, ty: &Type, self_class: Option<&'a Class>) -> Option<CsType<'a>> {
+        // `bool[]` is not blittable, so it can't be pinned
+        let array = |elem: &Type| match primitive(elem)? {
+            "bool" => None,
+            name => Some(CsType::Array(name)),
+        };
+        if let Some(inner) = match_generic_type(ty, parse_quote!(Vec)) {
+            return array(&inner[0]);
+        }
+
+        match ty {
+            Type::Reference(TypeReference { elem, .. }) => match elem.as_ref() {
+                // Slices are passed like vectors
+                Type::Slice(TypeSlice { elem, .. }) => array(elem),
+                // Opaque structs are only taken by reference
+                elem => match self.classify(elem, self_class)? {
+                    class @ CsType::Class(_) => Some(class),
+                    _ => None,
+                },
+            },
+            ty => match self.classify(ty, self_class)? {
+                ty @ CsType::Primitive(_) | ty @ CsType::String | ty @ CsType::Plain(_) => Some(ty),
+                _ => None,
+            },
+        }
+    }
+
+    /// Modules other than the root one become nested static classes
+    fn namespace(&self, mod_path: &[Ident]) -> Vec<String> {
+        mod_path
+            .iter()
+            .map(|m| camel_case(&m.to_string(), true))
+            .collect()
+    }
+
+    /// Name of a class as seen from anywhere in the namespace of the crate. Structs of the root
+    /// module are declared directly in the namespace
+    fn class_name(&self, class: &Class) -> String {
+        match class.mod_path.len() {
+            0 | 1 => class.ident.to_string(),
+            _ => format!(
+                "{}.{}",
+                self.namespace(&class.mod_path).join("."),
+                class.ident
+            ),
+        }
+    }
+
+    fn type_name(&self, ty: &CsType) -> String {
+        match ty {
+            CsType::Unit => "void".into(),
+            CsType::Primitive(name) => name.to_string(),
+            CsType::String => "string".into(),
+            CsType::Class(class) | CsType::Plain(class) => self.class_name(class),
+            CsType::Optional(class) => format!("{}?", self.class_name(class)),
+            CsType::Result(ok, _) => self.type_name(ok),
+            CsType::Array(name) => format!("{}[]", name),
+        }
+    }
+
+    /// A parameter of a `DllImport` declaration
+    fn native_param(&self, name: &str, ty: &CsType) -> String {
+        match ty {
+            CsType::Primitive(cs) => format!("{}{} {}", marshal_as(cs), cs, name),
+            CsType::String => format!("{}string {}", marshal_as("string"), name),
+            CsType::Class(class) | CsType::Plain(class) => {
+                format!("{} {}", self.class_name(class), name)
+            }
+            CsType::Array(_) => format!("Arr {}", name),
+            CsType::Unit | CsType::Optional(_) | CsType::Result(..) => {
+                unreachable!("Not taken as an argument")
+            }
+        }
+    }
+
+    /// The out parameter of a `DllImport` declaration, for a value returned by reference
+    fn native_out(&self, ty: &CsType) -> Option<String> {
+        match ty {
+            CsType::Primitive(cs) => Some(format!("{}out {} ptrOut", marshal_as(cs), cs)),
+            CsType::String | CsType::Class(_) | CsType::Optional(_) => {
+                Some("out IntPtr ptrOut".into())
+            }
+            CsType::Array(_) => Some("out Arr ptrOut".into()),
+            _ => None,
+        }
+    }
+
+    /// Record the `DllImport` declaration of `symbol`
+    fn native(&self, symbol: &Ident, mut params: Vec<String>, output: &CsType) {
+        let ret = match output {
+            CsType::Unit | CsType::Class(_) | CsType::Optional(_) => "void".to_string(),
+            CsType::Primitive(cs) => cs.to_string(),
+            CsType::String => "IntPtr".into(),
+            CsType::Plain(class) => self.class_name(class),
+            CsType::Result(..) => "int".into(),
+            CsType::Array(_) => "Arr".into(),
+        };
+        let out = match output {
+            CsType::Result(ok, _) => self.native_out(ok),
+            output => self.native_out(output).filter(|_| ret == "void"),
+        };
+        params.extend(out);
+
+        let ret_attr = match ret.as_str() {
+            "bool" => "[return: MarshalAs(UnmanagedType.U1)]\n",
+            _ => "",
+        };
+        let declaration = format!(
+            "[DllImport(Lib)]\n{}internal static extern {} {}({});",
+            ret_attr,
+            ret,
+            symbol,
+            params.join(", ")
+        );
+
+        let mut natives = self.natives.borrow_mut();
+        if !natives.iter().any(|(s, _)| s == symbol) {
+            natives.push((symbol.clone(), declaration));
+        }
+    }
+
+    /// Convert a value returned by the C API to its C# type
+    fn convert(&self, value: &str, ty: &CsType) -> String {
+        match ty {
+            CsType::String => format!("Native.TakeString({})", value),
+            CsType::Array(_) => format!("Native.TakeBytes({})", value),
+            CsType::Class(class) => format!("new {}({})", self.class_name(class), value),
+            CsType::Optional(class) => format!(
+                "{value} == IntPtr.Zero ? null : new {}({value})",
+                self.class_name(class),
+                value = value
+            ),
+            _ => value.to_string(),
+        }
+    }
+
+    /// Lines of the body that calls `symbol`, after the arguments have been prepared
+    fn body(&self, symbol: &Ident, mut args: Vec<String>, output: &CsType) -> Vec<String> {
+        let call = |args: &[String]| format!("Native.{}({})", symbol, args.join(", "));
+
+        match output {
+            CsType::Unit => vec![format!("{};", call(&args))],
+            CsType::Primitive(_) | CsType::String | CsType::Array(_) | CsType::Plain(_) => {
+                vec![format!("return {};", self.convert(&call(&args), output))]
+            }
+            CsType::Class(_) | CsType::Optional(_) => {
+                args.push("out var ptrOut".into());
+                vec![
+                    format!("{};", call(&args)),
+                    format!("return {};", self.convert("ptrOut", output)),
+                ]
+            }
+            CsType::Result(ok, exception) => {
+                let has_out = self.native_out(ok).is_some();
+                if has_out {
+                    args.push("out var ptrOut".into());
+                }
+                let mut lines = vec![
+                    format!("var code = {};", call(&args)),
+                    "if (code != 0)".into(),
+                    "{".into(),
+                    format!("    throw new {}(code);", exception),
+                    "}".into(),
+                ];
+                if has_out {
+                    lines.push(format!("return {};", self.convert("ptrOut", ok)));
+                }
+
+                lines
+            }
+        }
+    }
+}
+
+/// Parameters, native parameters, arguments and the lines that prepare them
+struct Params {
+    params: Vec<String>,
+    native: Vec<String>,
+    args: Vec<String>,
+    lines: Vec<String>,
+}
+
+fn params<'a>(
+    ctx: &Context<'a>,
+    class: Option<&'a Class>,
+    receiver: Option<&str>,
+    inputs: &[(Ident, Type)],
+) -> Option<Params> {
+    let mut params = Params {
+        params: vec![],
+        native: vec![],
+        args: vec![],
+        lines: vec![],
+    };
+    if let Some(receiver) = receiver {
+        params
+            .native
+            .push(format!("{} self", ctx.class_name(class?)));
+        params.args.push(receiver.to_string());
+    }
+    for (ident, ty) in inputs {
+        let ty = ctx.classify_input(ty, class)?;
+        let name = param_name(ident);
+        params
+            .params
+            .push(format!("{} {}", ctx.type_name(&ty), name));
+        params.native.push(ctx.native_param(&name, &ty));
+
+        match ty {
+            CsType::Array(_) => {
+                let pinned = format!("{}Pinned", camel_case(&ident.to_string(), false));
+                params
+                    .lines
+                    .push(format!("using var {} = new Pinned({});", pinned, name));
+                params.args.push(format!("{}.Arr", pinned));
+            }
+            _ => params.args.push(name),
+        }
+    }
+
+    Some(params)
+}
+
+/// Render a method of a class, or a function when `class` is `None`. `level` is the indentation
+/// of the declaration. Returns `None` if some types are not supported
+fn render_method(
+    ctx: &Context,
+    class: Option<&Class>,
+    method: &Method,
+    level: usize,
+) -> Option<String> {
+    let operator = match method.kind {
+        MethodKind::Operator(op) => Some(cs_operator(op)),
+        _ => None,
+    };
+    let receiver = match (&method.kind, operator) {
+        (_, Some(CsOperator::Binary(_))) => Some("lhs"),
+        (_, Some(CsOperator::Unary(_))) => Some("operand"),
+        (_, Some(CsOperator::Indexer)) | (MethodKind::Instance { .. }, _) => Some("this"),
+        (MethodKind::Constructor, _) | (MethodKind::Static, _) => None,
+        _ => unreachable!("Rendered as properties"),
+    };
+
+    let Params {
+        params,
+        native,
+        args,
+        mut lines,
+    } = params(ctx, class, receiver, &method.inputs)?;
+    let params = params.join(", ");
+
+    let output = ctx.classify(&method.output, class)?;
+    ctx.native(&method.symbol, native, &output);
+    let ret = ctx.type_name(&output);
+    let name = camel_case(&method.name, true);
+    let self_name = class.map(|class| ctx.class_name(class));
+
+    let decl = match (&method.kind, operator) {
+        (MethodKind::Constructor, _) => {
+            // The body returns the new object, the constructor sets its own handle instead
+            let mut body = ctx.body(&method.symbol, args, &output);
+            body.pop();
+            body.push("SetHandle(ptrOut);".into());
+            lines.extend(body);
+
+            let class = class?;
+            format!(
+                "public {}({}) : base(IntPtr.Zero, {})",
+                class.ident,
+                params,
+                class.destructor.is_some()
+            )
+        }
+        (_, Some(CsOperator::Binary(op))) => {
+            lines.extend(ctx.body(&method.symbol, args, &output));
+            format!(
+                "public static {} operator {}({} lhs, {})",
+                ret, op, self_name?, params
+            )
+        }
+        (_, Some(CsOperator::Unary(op))) => {
+            lines.extend(ctx.body(&method.symbol, args, &output));
+            format!(
+                "public static {} operator {}({} operand)",
+                ret, op, self_name?
+            )
+        }
+        (_, Some(CsOperator::Indexer)) => {
+            let body = ctx.body(&method.symbol, args, &output);
+            lines.extend(
+                std::iter::once("get".to_string())
+                    .chain(std::iter::once("{".to_string()))
+                    .chain(body.into_iter().map(|l| format!("    {}", l)))
+                    .chain(std::iter::once("}".to_string())),
+            );
+            format!("public {} this[{}]", ret, params)
+        }
+        // `to_string()` overrides `Object.ToString()`
+        (MethodKind::Instance { .. }, _) if is_to_string(method) => {
+            lines.extend(ctx.body(&method.symbol, args, &output));
+            "public override string ToString()".to_string()
+        }
+        (MethodKind::Instance { .. }, _) => {
+            lines.extend(ctx.body(&method.symbol, args, &output));
+            format!("public {} {}({})", ret, name, params)
+        }
+        (MethodKind::Static, _) => {
+            lines.extend(ctx.body(&method.symbol, args, &output));
+            format!("public static {} {}({})", ret, name, params)
+        }
+        _ => unreachable!("Rendered as properties"),
+    };
+
+    let prefix = "    ".repeat(level);
+    Some(format!(
+        "{p}{}\n{p}{{\n{}{p}}}\n",
+        decl,
+        CSharp::indent(lines, level + 1),
+        p = prefix
+    ))
+}
+
+/// Render the getter and the setter of a field as a property. Returns `None` if none of them
+/// is supported
+fn render_property(
+    ctx: &Context,
+    class: &Class,
+    getter: Option<&Method>,
+    setter: Option<&Method>,
+    level: usize,
+) -> Option<String> {
+    let mut ty = None;
+    let mut accessors = vec![];
+
+    if let Some(getter) = getter {
+        let output = ctx.classify(&getter.output, Some(class));
+        let body = match &output {
+            // Other opaque fields are borrowed from the struct, so they can't be owned by a
+            // handle. `shared` ones are returned with a new reference
+            Some(CsType::Class(field)) if field.shared => {
+                ctx.native(
+                    &getter.symbol,
+                    vec![format!("{} self", ctx.class_name(class))],
+                    &CsType::Primitive("IntPtr"),
+                );
+                Some(vec![format!(
+                    "return new {}(Native.{}(this));",
+                    ctx.class_name(field),
+                    getter.symbol
+                )])
+            }
+            Some(output @ CsType::Primitive(name))
+                if !matches!(*name, "bool" | "float" | "double") =>
+            {
+                ctx.native(
+                    &getter.symbol,
+                    vec![format!("{} self", ctx.class_name(class))],
+                    output,
+                );
+                Some(ctx.body(&getter.symbol, vec!["this".into()], output))
+            }
+            _ => None,
+        };
+        if let Some(body) = body {
+            ty = output.map(|o| ctx.type_name(&o));
+            accessors.push(("get", body));
+        }
+    }
+
+    if let Some(setter) = setter {
+        let params = params(ctx, Some(class), Some("this"), &setter.inputs);
+        let output = ctx.classify(&setter.output, Some(class));
+        if let (Some(params), Some(output), Some((_, value))) =
+            (params, output, setter.inputs.first())
+        {
+            ctx.native(&setter.symbol, params.native, &output);
+            let mut body = params.lines;
+            body.extend(ctx.body(&setter.symbol, params.args, &output));
+
+            let value = ctx.classify_input(value, Some(class))?;
+            ty.get_or_insert_with(|| ctx.type_name(&value));
+            accessors.push(("set", body));
+        }
+    }
+
+    let ty = ty?;
+    let name = camel_case(&getter.or(setter)?.name, true);
+    let mut lines = vec![format!("public {} {}", ty, name), "{".into()];
+    for (accessor, body) in accessors {
+        lines.push(format!("    {}", accessor));
+        lines.push("    {".into());
+        lines.extend(body.into_iter().map(|l| format!("        {}", l)));
+        lines.push("    }".into());
+    }
+    lines.push("}".into());
+
+    Some(CSharp::indent(lines, level))
+}
+
+fn is_to_string(method: &Method) -> bool {
+    matches!(method.kind, MethodKind::Instance { .. })
+        && method.name == "to_string"
+        && method.inputs.is_empty()
+        && match_fixed_type(&method.output, parse_quote!(String))
+}
+
+/// Members of the struct of a trait: the delegates called by the C API and the constructor that
+/// takes the object implementing the interface
+fn render_trait_members(ctx: &Context, class: &Class, tr: &Trait, level: usize) -> String {
+    let interface = format!("I{}", tr.ident);
+
+    let mut lines = vec![
+        "[UnmanagedFunctionPointer(CallingConvention.Cdecl)]".to_string(),
+        "internal delegate void DestroyCallback(IntPtr self);".to_string(),
+    ];
+    let mut fields = vec![
+        "private static readonly DestroyCallback destroy = self => GCHandle.FromIntPtr(self).Free();"
+            .to_string(),
+    ];
+    let mut native_params = vec![
+        "IntPtr self".to_string(),
+        format!("{}.DestroyCallback destroy", ctx.class_name(class)),
+    ];
+    let mut args = vec!["self".to_string(), "destroy".to_string()];
+    for callback in &tr.callbacks {
+        let delegate = format!("{}Callback", camel_case(&callback.name, true));
+        let field = camel_case(&callback.name, false);
+
+        let mut params = vec!["IntPtr self".to_string()];
+        let mut names = vec!["self".to_string()];
+        let mut call_args = vec![];
+        for (ident, ty) in &callback.inputs {
+            let name = param_name(ident);
+            match primitive(ty) {
+                Some(cs) => {
+                    params.push(format!("{}{} {}", marshal_as(cs), cs, name));
+                    call_args.push(name.clone());
+                }
+                // Owned by the callee
+                None => {
+                    params.push(format!("IntPtr {}", name));
+                    call_args.push(format!("Native.TakeString({})", name));
+                }
+            }
+            names.push(name);
+        }
+        let ret = primitive(&callback.output);
+
+        lines.push(String::new());
+        lines.push("[UnmanagedFunctionPointer(CallingConvention.Cdecl)]".into());
+        if ret == Some("bool") {
+            lines.push("[return: MarshalAs(UnmanagedType.U1)]".into());
+        }
+        lines.push(format!(
+            "internal delegate {} {}({});",
+            ret.unwrap_or("void"),
+            delegate,
+            params.join(", ")
+        ));
+        fields.push(format!(
+            "private static readonly {} {} = ({}) => (({})GCHandle.FromIntPtr(self).Target!).{}({});",
+            delegate,
+            field,
+            names.join(", "),
+            interface,
+            camel_case(&callback.name, true),
+            call_args.join(", ")
+        ));
+
+        native_params.push(format!("{}.{} {}", ctx.class_name(class), delegate, field));
+        args.push(field);
+    }
+    native_params.push("out IntPtr ptrOut".into());
+    args.push("out var ptrOut".into());
+    ctx.native(&tr.constructor, native_params, &CsType::Unit);
+
+    lines.push(String::new());
+    lines.push(
+        "// The C API keeps pointers to the delegates, which live as long as the class".into(),
+    );
+    lines.extend(fields);
+    lines.push(String::new());
+    lines.push(
+        "/// <summary>Wrap <paramref name=\"impl\"/>, which is called back until the struct is freed</summary>"
+            .into(),
+    );
+    lines.push(format!(
+        "public {}({} impl) : base(IntPtr.Zero, true)",
+        class.ident, interface
+    ));
+    lines.push("{".into());
+    lines.push("    var self = GCHandle.ToIntPtr(GCHandle.Alloc(impl));".into());
+    lines.push(format!(
+        "    Native.{}({});",
+        tr.constructor,
+        args.join(", ")
+    ));
+    lines.push("    SetHandle(ptrOut);".into());
+    lines.push("}".into());
+
+    CSharp::indent(lines, level)
+}
+
+fn render_class(ctx: &Context, class: &Class, level: usize) -> String {
+    let prefix = "    ".repeat(level);
+    let ownership = match (&class.destructor, class.shared) {
+        (Some(_), true) => "released when the handle is disposed or finalized",
+        (Some(_), false) => "freed when the handle is disposed or finalized",
+        (None, _) => "never freed since the struct has no `#[destructor]`",
+    };
+
+    let mut out = String::new();
+    writeln!(
+        out,
+        "{p}/// <summary>Owns a pointer to <c>{c}{ident}</c>, {ownership}</summary>\n\
+         {p}public sealed class {ident} : SafeHandle\n\
+         {p}{{\n\
+         {p}    /// <summary>Take the ownership of a pointer returned by the C API</summary>\n\
+         {p}    internal {ident}(IntPtr ptr) : base(IntPtr.Zero, {owns})\n\
+         {p}    {{\n\
+         {p}        SetHandle(ptr);\n\
+         {p}    }}\n\n\
+         {p}    public override bool IsInvalid => handle == IntPtr.Zero;\n\n\
+         {p}    protected override bool ReleaseHandle()\n\
+         {p}    {{",
+        p = prefix,
+        c = ctx.c_prefix,
+        ident = class.ident,
+        ownership = ownership,
+        owns = class.destructor.is_some(),
+    )
+    .unwrap();
+    if let Some(destructor) = &class.destructor {
+        writeln!(out, "{}        Native.{}(handle);", prefix, destructor).unwrap();
+        ctx.natives.borrow_mut().push((
+            destructor.clone(),
+            format!(
+                "[DllImport(Lib)]\ninternal static extern void {}(IntPtr ptr);",
+                destructor
+            ),
+        ));
+    }
+    writeln!(out, "{p}        return true;\n{p}    }}", p = prefix).unwrap();
+
+    if let Some(tr) = ctx.traits.iter().find(|t| t.struct_ident == class.ident) {
+        writeln!(out).unwrap();
+        write!(out, "{}", render_trait_members(ctx, class, tr, level + 1)).unwrap();
+    }
+
+    let mut rendered_properties = vec![];
+    for method in &class.methods {
+        let rendered = match method.kind {
+            MethodKind::Getter | MethodKind::Setter => {
+                if rendered_properties.contains(&&method.name) {
+                    continue;
+                }
+                rendered_properties.push(&method.name);
+
+                let find = |kind: MethodKind| {
+                    class
+                        .methods
+                        .iter()
+                        .find(|m| m.kind == kind && m.name == method.name)
+                };
+                render_property(
+                    ctx,
+                    class,
+                    find(MethodKind::Getter),
+                    find(MethodKind::Setter),
+                    level + 1,
+                )
+            }
+            _ => render_method(ctx, Some(class), method, level + 1),
+        };
+
+        writeln!(out).unwrap();
+        match rendered {
+            Some(rendered) => write!(out, "{}", rendered).unwrap(),
+            None => writeln!(
+                out,
+                "{}    // `{}` is not available: unsupported argument or return types",
+                prefix, method.name
+            )
+            .unwrap(),
+        }
+    }
+    writeln!(out, "{}}}", prefix).unwrap();
+
+    out
+}
+
+/// Transparent structs have the same layout as the C struct, with PascalCase fields
+fn render_plain(ctx: &Context, class: &Class, level: usize) -> String {
+    let mut lines = vec![
+        format!(
+            "/// <summary>Passed by value, with the layout of <c>{}{}</c></summary>",
+            ctx.c_prefix, class.ident
+        ),
+        "[StructLayout(LayoutKind.Sequential)]".into(),
+        format!("public struct {}", class.ident),
+        "{".into(),
+    ];
+    for (ident, ty) in &class.fields {
+        let cs = primitive(ty).unwrap();
+        lines.push(format!(
+            "    {}public {} {};",
+            marshal_as(cs),
+            cs,
+            camel_case(&ident.to_string(), true)
+        ));
+    }
+    lines.push("}".into());
+
+    CSharp::indent(lines, level)
+}
+
+/// The interface implemented by the objects wrapped in the struct of a trait
+fn render_interface(tr: &Trait, level: usize) -> String {
+    if !tr.supported {
+        return format!(
+            "{}// `{}` is not available: unsupported callback types\n",
+            "    ".repeat(level),
+            tr.ident
+        );
+    }
+
+    let mut lines = vec![
+        format!(
+            "/// <summary>Implemented by the objects wrapped in a <see cref=\"{}\"/></summary>",
+            tr.struct_ident
+        ),
+        format!("public interface I{}", tr.ident),
+        "{".into(),
+    ];
+    for callback in &tr.callbacks {
+        let params = callback
+            .inputs
+            .iter()
+            .map(|(ident, ty)| {
+                format!(
+                    "{} {}",
+                    primitive(ty).unwrap_or("string"),
+                    param_name(ident)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        lines.push(format!(
+            "    {} {}({});",
+            primitive(&callback.output).unwrap_or("void"),
+            camel_case(&callback.name, true),
+            params
+        ));
+    }
+    lines.push("}".into());
+
+    CSharp::indent(lines, level)
+}
+
+fn render_source(items: Recorded) -> String {
+    let modules = items.modules();
+    let Recorded {
+        classes,
+        functions,
+        traits,
+    } = &items;
+
+    let lib_name = lib_name();
+    let lib = camel_case(&lib_name, true);
+    let ctx = Context {
+        lib: lib.clone(),
+        c_prefix: std::env::var("EXPOSE_C_PREFIX").unwrap_or_default(),
+        classes,
+        traits,
+        errors: RefCell::new(BTreeSet::new()),
+        natives: RefCell::new(vec![]),
+    };
+
+    let mut body = vec![];
+    for module in &modules {
+        // Structs of the root module are declared in the namespace, its functions in a static
+        // class named after it. The other modules are nested static classes
+        let level = match module.len() {
+            1 => 0,
+            len => len,
+        };
+        let mut items = vec![];
+        for class in classes.iter().filter(|c| c.mod_path == *module) {
+            items.push(match class.opaque {
+                true => render_class(&ctx, class, level),
+                false if class.fields.iter().all(|(_, ty)| primitive(ty).is_some()) => {
+                    render_plain(&ctx, class, level)
+                }
+                false => format!(
+                    "{}// `{}` is not available: unsupported field types\n",
+                    "    ".repeat(level),
+                    class.ident
+                ),
+            });
+        }
+        for tr in traits.iter().filter(|t| t.mod_path == *module) {
+            items.push(render_interface(tr, level));
+        }
+
+        let mut functions_items = vec![];
+        for (_, function) in functions.iter().filter(|(path, _)| path == module) {
+            functions_items.push(
+                render_method(&ctx, None, function, module.len()).unwrap_or_else(|| {
+                    format!(
+                        "{}// `{}` is not available: unsupported argument or return types\n",
+                        "    ".repeat(module.len()),
+                        function.name
+                    )
+                }),
+            );
+        }
+        if module.len() == 1 {
+            body.extend(items);
+            items = vec![];
+        }
+        items.extend(functions_items);
+        if items.is_empty() {
+            continue;
+        }
+
+        let names = ctx.namespace(module);
+        let mut opening = String::new();
+        let mut closing = String::new();
+        for (i, name) in names.iter().enumerate() {
+            let p = "    ".repeat(i);
+            writeln!(
+                opening,
+                "{p}public static partial class {}\n{p}{{",
+                name,
+                p = p
+            )
+            .unwrap();
+            closing.insert_str(0, &format!("{}}}\n", p));
+        }
+        body.push(format!("{}{}{}", opening, items.join("\n"), closing));
+    }
+
+    let mut errors = String::new();
+    for error in ctx.errors.borrow().iter() {
+        writeln!(
+            errors,
+            "public sealed class {error}Exception : {lib}Exception\n\
+             {{\n    \
+             public {error}Exception(int code) : base($\"{error}: error code {{code}}\", code)\n    \
+             {{\n    \
+             }}\n\
+             }}\n",
+            error = error,
+            lib = ctx.lib
+        )
+        .unwrap();
+    }
+
+    let mut natives = ctx
+        .natives
+        .borrow()
+        .iter()
+        .map(|(_, declaration)| CSharp::indent(vec![declaration.clone()], 1))
+        .collect::<Vec<_>>();
+    natives.insert(
+        0,
+        CSharp::indent(
+            vec![
+                format!("internal const string Lib = \"{}\";", lib_name),
+                String::new(),
+                "[DllImport(Lib)]".into(),
+                format!(
+                    "internal static extern void {}string_free(IntPtr s);",
+                    ctx.c_prefix
+                ),
+                String::new(),
+                "/// <summary>Copy a string returned by the C API, and free it</summary>".into(),
+                "internal static string TakeString(IntPtr s)".into(),
+                "{".into(),
+                "    try".into(),
+                "    {".into(),
+                "        return Marshal.PtrToStringUTF8(s)!;".into(),
+                "    }".into(),
+                "    finally".into(),
+                "    {".into(),
+                format!("        {}string_free(s);", ctx.c_prefix),
+                "    }".into(),
+                "}".into(),
+                String::new(),
+                "[DllImport(Lib)]".into(),
+                format!(
+                    "internal static extern void {}bytes_free(Arr arr);",
+                    ctx.c_prefix
+                ),
+                String::new(),
+                "/// <summary>Copy a vector returned by the C API, and free it</summary>".into(),
+                "internal static byte[] TakeBytes(Arr arr)".into(),
+                "{".into(),
+                "    try".into(),
+                "    {".into(),
+                "        var bytes = new byte[(int)arr.Len];".into(),
+                "        if (bytes.Length > 0)".into(),
+                "        {".into(),
+                "            Marshal.Copy(arr.Ptr, bytes, 0, bytes.Length);".into(),
+                "        }".into(),
+                "        return bytes;".into(),
+                "    }".into(),
+                "    finally".into(),
+                "    {".into(),
+                format!("        {}bytes_free(arr);", ctx.c_prefix),
+                "    }".into(),
+                "}".into(),
+            ],
+            1,
+        ),
+    );
+
+    format!(
+        "// <auto-generated>\n\
+         // Generated by `#[expose_mod]`, do not edit\n\
+         // </auto-generated>\n\
+         #nullable enable\n\
+         using System;\n\
+         using System.Runtime.InteropServices;\n\n\
+         namespace {lib};\n\n\
+         /// <summary>Thrown when the C API returns an error code</summary>\n\
+         public class {lib}Exception : Exception\n\
+         {{\n    \
+         public int Code {{ get; }}\n\n    \
+         public {lib}Exception(string message, int code) : base(message)\n    \
+         {{\n        \
+         Code = code;\n    \
+         }}\n\
+         }}\n\n\
+         {errors}\
+         /// <summary>Array of primitives passed to or returned by the C API, with the layout of <c>{c}Arr</c></summary>\n\
+         [StructLayout(LayoutKind.Sequential)]\n\
+         internal struct Arr\n\
+         {{\n    \
+         public IntPtr Ptr;\n    \
+         public nuint Len;\n\
+         }}\n\n\
+         /// <summary>Pins an array for the duration of a call</summary>\n\
+         internal sealed class Pinned : IDisposable\n\
+         {{\n    \
+         private GCHandle handle;\n\n    \
+         internal Pinned(Array array)\n    \
+         {{\n        \
+         handle = GCHandle.Alloc(array, GCHandleType.Pinned);\n        \
+         Arr = new Arr {{ Ptr = handle.AddrOfPinnedObject(), Len = (nuint)array.Length }};\n    \
+         }}\n\n    \
+         internal Arr Arr {{ get; }}\n\n    \
+         public void Dispose() => handle.Free();\n\
+         }}\n\n\
+         internal static class Native\n\
+         {{\n\
+         {natives}\
+         }}\n\n\
+         {body}",
+        lib = lib,
+        c = ctx.c_prefix,
+        errors = errors,
+        natives = natives.join("\n"),
+        body = body.join("\n"),
+    )
+}
@@ -0,0 +1,1149 @@
+//! Dart bindings over the C API, through `dart:ffi`
+//!
+//! Like the Swift layer, the Rust side is generated by the C backend while the original
+//! signatures of the exposed items are recorded. When the root `#[expose_mod]` is expanded they
+//! are rendered to a Dart library that looks up the C functions, with a class for every opaque
+//! struct whose pointer is freed by a `NativeFinalizer` when the object is garbage collected.
+//! Failed `Result`s are thrown as exceptions, and exposed traits are implemented by Dart objects
+//! called through `NativeCallable`s. The library is written to `OUT_DIR` as
+//! `<crate>_<module>.dart`.
+
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use syn::punctuated::Punctuated;
+use syn::{
+    Ident, Item, ItemFn, ItemImpl, ItemMod, ItemStruct, ItemTrait, Path, Token, Type, TypePath,
+    TypeReference, TypeSlice,
+};
+
+use super::c::{CError, C};
+use super::c_family::{self, CFamily, Class, Method, MethodKind, Recorded, Trait};
+use super::*;
+use crate::types::*;
+
+#[derive(Debug)]
+pub struct Dart;
+
+impl CFamily for Dart {
+    const OUTPUT: &'static str = "DART";
+    const INDENT: &'static str = "  ";
+    // Dart has no overloading, but named constructors
+    const OVERLOADING: bool = false;
+    const NAMED_CONSTRUCTORS: bool = true;
+    const CALLBACKS: bool = true;
+
+    fn render(root: &Ident, items: Recorded) -> Vec<(PathBuf, String)> {
+        let file_name = format!("{}_{}.dart", lib_name(), root);
+        vec![(file_name.into(), render_source(items))]
+    }
+}
+
+impl Lang for Dart {
+    type Error = CError;
+
+    // The Rust side is the C API, so it uses the same mappings
+    const NAME: &'static str = C::NAME;
+
+    fn expose_fn(
+        function: &mut ItemFn,
+        mod_path: &Vec<Ident>,
+        extra: &mut Vec<Item>,
+    ) -> Result<Ident, Self::Error> {
+        c_family::expose_fn::<Self>(function, mod_path, extra)
+    }
+
+    fn expose_mod(
+        module: &mut ItemMod,
+        mod_path: &Vec<Ident>,
+        sub_items: Vec<ModuleItem>,
+    ) -> Result<Ident, Self::Error> {
+        c_family::expose_mod::<Self>(module, mod_path, sub_items)
+    }
+
+    fn expose_struct(
+        structure: &mut ItemStruct,
+        opts: Punctuated<ExposeStructOpts, Token![,]>,
+        mod_path: &Vec<Ident>,
+        extra: &mut Vec<Item>,
+    ) -> Result<Ident, Self::Error> {
+        c_family::expose_struct(structure, opts, mod_path, extra)
+    }
+
+    fn expose_impl(
+        implementation: &mut ItemImpl,
+        mod_path: &Vec<Ident>,
+    ) -> Result<(), Self::Error> {
+        c_family::expose_impl::<Self>(implementation, mod_path)
+    }
+
+    fn expose_trait(
+        tr: &mut ItemTrait,
+        mod_path: &Vec<Ident>,
+        extra: &mut Vec<Item>,
+    ) -> Result<Ident, Self::Error> {
+        c_family::expose_trait::<Self>(tr, mod_path, extra)
+    }
+
+    fn expose_operators(
+        self_ty: &Type,
+        operators: Vec<ExposedOperator>,
+        mod_path: &Vec<Ident>,
+        extra: &mut Vec<Item>,
+    ) -> Result<(), Self::Error> {
+        c_family::expose_operators::<Self>(self_ty, operators, mod_path, extra)
+    }
+
+    fn expose_getter(
+        field: &mut Field,
+        is_opaque: bool,
+        impl_block: &mut ItemImpl,
+    ) -> Result<(), Self::Error> {
+        C::expose_getter(field, is_opaque, impl_block)
+    }
+
+    fn expose_inline_getter(
+        field: &mut Field,
+        impl_block: &mut ItemImpl,
+    ) -> Result<(), Self::Error> {
+        C::expose_inline_getter(field, impl_block)
+    }
+
+    fn expose_inline_setter(
+        field: &mut Field,
+        validate: Option<&Path>,
+        impl_block: &mut ItemImpl,
+    ) -> Result<(), Self::Error> {
+        C::expose_inline_setter(field, validate, impl_block)
+    }
+
+    fn convert_getter_setter_ty(ty: Type) -> Result<(Type, Type), Self::Error> {
+        C::convert_getter_setter_ty(ty)
+    }
+
+    fn convert_input(ty: Type) -> Result<Input, Self::Error> {
+        C::convert_input(ty)
+    }
+
+    fn convert_output(output: Type) -> Result<Output, Self::Error> {
+        C::convert_output(output)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DartOperator {
+    Binary(&'static str),
+    Unary,
+    Indexer,
+}
+
+fn dart_operator(op: Operator) -> DartOperator {
+    match op {
+        Operator::Add => DartOperator::Binary("+"),
+        Operator::Sub => DartOperator::Binary("-"),
+        Operator::Mul => DartOperator::Binary("*"),
+        Operator::Neg => DartOperator::Unary,
+        Operator::Index => DartOperator::Indexer,
+    }
+}
+
+/// Reserved words can't be used as names in Dart
+const RESERVED: &[&str] = &[
+    "assert", "break", "case", "catch", "class", "const", "continue", "default", "do", "else",
+    "enum", "extends", "false", "final", "finally", "for", "if", "in", "is", "new", "null",
+    "rethrow", "return", "super", "switch", "this", "throw", "true", "try", "var", "void", "while",
+    "with",
+];
+
+/// `some_name` -> `someName`, suffixed with an underscore if it's a reserved word
+fn member_name(name: &str) -> String {
+    let name = camel_case(name, false);
+    if RESERVED.contains(&name.as_str()) {
+        format!("{}_", name)
+    } else {
+        name
+    }
+}
+
+/// Like `member_name`, also avoiding the names of the locals of the generated code
+fn param_name(ident: &Ident) -> String {
+    let name = member_name(&ident.to_string());
+    if ["arena", "code", "ptrOut"].contains(&name.as_str()) {
+        format!("{}_", name)
+    } else {
+        name
+    }
+}
+
+/// How a Rust type crosses the C API, from the point of view of the Dart layer
+#[derive(Clone)]
+enum DartType<'a> {
+    Unit,
+    /// Native and Dart types of the primitive
+    Primitive(&'static str, &'static str),
+    /// Copied to native memory when passed, copied and freed when returned
+    String,
+    /// Opaque struct, passed as a pointer and returned through an out pointer
+    Class(&'a Class),
+    /// Transparent struct with primitive fields, passed by value
+    Plain(&'a Class),
+    Optional(&'a Class),
+    /// List of primitives from a `Vec` or a slice, copied to native memory and passed as an `_Arr`.
+    /// Bytes from a `Vec<u8>` as a return value, copied from the returned `_Arr`
+    Array(&'static str, &'static str),
+    /// Error code, with the ok value returned through an out pointer
+    Result(Box<DartType<'a>>, String),
+}
+
+fn primitive(ty: &Type) -> Option<(&'static str, &'static str)> {
+    Some(match c_family::primitive_ident(ty)?.as_str() {
+        "bool" => ("Bool", "bool"),
+        "i8" => ("Int8", "int"),
+        "u8" => ("Uint8", "int"),
+        "i16" => ("Int16", "int"),
+        "u16" => ("Uint16", "int"),
+        "i32" => ("Int32", "int"),
+        "u32" => ("Uint32", "int"),
+        "i64" => ("Int64", "int"),
+        "u64" => ("Uint64", "int"),
+        "isize" => ("IntPtr", "int"),
+        "usize" => ("UintPtr", "int"),
+        "f32" => ("Float", "double"),
+        "f64" => ("Double", "double"),
+        _ => return None,
+    })
+}
+
+/// Returned by the callbacks when the Dart method throws
+fn exceptional_return(dart: &str) -> &'static str {
+    match dart {
+        "bool" => "false",
+        "double" => "0.0",
+        _ => "0",
+    }
+}
+
+struct Context<'a> {
+    /// Name of the base class of the exceptions
+    lib: String,
+    c_prefix: String,
+    classes: &'a [Class],
+    traits: &'a [Trait],
+    errors: RefCell<BTreeSet<String>>,
+    /// Lookups of the functions called so far
+    natives: RefCell<Vec<(Ident, String)>>,
+}
+
+impl<'a> Context<'a> {
+    fn class(&self, ty: &Type, self_class: Option<&'a Class>) -> Option<&'a Class> {
+        if match_fixed_type(ty, parse_quote!(Self)) {
+            return self_class;
+        }
+        match ty {
+            Type::Path(TypePath { path, .. }) => {
+                let ident = &path.segments.last()?.ident;
+                self.classes.iter().find(|c| &c.ident == ident)
+            }
+            _ => None,
+        }
+    }
+
+    fn error(&self, ty: &Type) -> Option<String> {
+        let ident = match ty {
+            Type::Path(TypePath { path, .. }) => path.segments.last()?.ident.to_string(),
+            _ => return None,
+        };
+        if lookup_mapping(C::NAME, ty).is_none()
+            && ident != "ValidationError"
+            && ident != "IndexError"
+        {
+            return None;
+        }
+
+        self.errors.borrow_mut().insert(ident.clone());
+        Some(format!("{}Exception", ident))
+    }
+
+    fn classify(&self, ty: &Type, self_class: Option<&'a Class>) -> Option<DartType<'a>> {
+        if let Some(target) = lookup_mapping(C::NAME, ty) {
+            return self.classify(&target, self_class);
+        }
+        if let Type::Tuple(tuple) = ty {
+            return if tuple.elems.is_empty() {
+                Some(DartType::Unit)
+            } else {
+                None
+            };
+        }
+        if let Some((native, dart)) = primitive(ty) {
+            return Some(DartType::Primitive(native, dart));
+        }
+        if match_fixed_type(ty, parse_quote!(String)) {
+            return Some(DartType::String);
+        }
+        // Returned vectors are freed with `bytes_free()`
+        if ty == &parse_quote!(Vec<u8>) {
+            return Some(DartType::Array("Uint8", "int"));
+        }
+        if let Some(inner) = match_generic_type(ty, parse_quote!(Option)) {
+            return match self.class(&inner[0], self_class) {
+                Some(class) if class.opaque => Some(DartType::Optional(class)),
+                _ => None,
+            };
+        }
+        if let Some(inner) = match_generic_type(ty, parse_quote!(Result)) {
+            if inner.len() != 2 {
+                return None;
+            }
+            let ok = match self.classify(&inner[0], self_class)? {
+                ok @ DartType::Unit
+                | ok @ DartType::Primitive(..)
+                | ok @ DartType::String
+                | ok @ DartType::Array(..)
+                | ok @ DartType::Class(_) => ok,
+                _ => return None,
+            };
+            return Some(DartType::Result(Box::new(ok), self.error(&inner[1])?));
+        }
+
+        match self.class(ty, self_class)? {
+            class if class.opaque => Some(DartType::Class(class)),
+            class if class.fields.iter().all(|(_, ty)| primitive(ty).is_some()) => {
+                Some(DartType::Plain(class))
+            }
+            _ => None,
+        }
+    }
+
+    fn classify_input(&self, ty: &Type, self_class: Option<&'a Class>) -> Option<DartType<'a>> {
+        let array = |elem: &Type| match primitive(elem)? {
+            ("Bool", _) => None,
+            (native, dart) => Some(DartType::Array(native, dart)),
+        };
+        if let Some(inner) = match_generic_type(ty, parse_quote!(Vec)) {
+            return array(&inner[0]);
+        }
+
+        match ty {
+            Type::Reference(TypeReference { elem, .. }) => match elem.as_ref() {
+                // Slices are passed like vectors
+                Type::Slice(TypeSlice { elem, .. }) => array(elem),
+                // Opaque structs are only taken by reference
+                elem => match self.classify(elem, self_class)? {
+                    class @ DartType::Class(_) => Some(class),
+                    _ => None,
+                },
+            },
+            ty => match self.classify(ty, self_class)? {
+                ty @ DartType::Primitive(..) | ty @ DartType::String | ty @ DartType::Plain(_) => {
+                    Some(ty)
+                }
+                _ => None,
+            },
+        }
+    }
+
+    /// Dart has no namespaces, so the names are prefixed with the path of the module, without the
+    /// root one
+    fn prefix(&self, mod_path: &[Ident]) -> String {
+        mod_path
+            .iter()
+            .skip(1)
+            .map(|m| camel_case(&m.to_string(), true))
+            .collect()
+    }
+
+    fn class_name(&self, class: &Class) -> String {
+        format!("{}{}", self.prefix(&class.mod_path), class.ident)
+    }
+
+    fn type_name(&self, ty: &DartType) -> String {
+        match ty {
+            DartType::Unit => "void".into(),
+            DartType::Primitive(_, dart) => dart.to_string(),
+            DartType::String => "String".into(),
+            DartType::Class(class) | DartType::Plain(class) => self.class_name(class),
+            DartType::Optional(class) => format!("{}?", self.class_name(class)),
+            DartType::Result(ok, _) => self.type_name(ok),
+            DartType::Array(_, dart) => format!("List<{}>", dart),
+        }
+    }
+
+    /// Native and Dart types of an argument of a C function
+    fn ffi_types(&self, ty: &DartType) -> (String, String) {
+        match ty {
+            DartType::Primitive(native, dart) => (native.to_string(), dart.to_string()),
+            DartType::String => ("Pointer<Utf8>".into(), "Pointer<Utf8>".into()),
+            DartType::Class(_) => ("Pointer<Void>".into(), "Pointer<Void>".into()),
+            DartType::Plain(class) => (self.class_name(class), self.class_name(class)),
+            DartType::Array(..) => ("_Arr".into(), "_Arr".into()),
+            DartType::Unit | DartType::Optional(_) | DartType::Result(..) => {
+                unreachable!("Not taken as an argument")
+            }
+        }
+    }
+
+    /// Native type pointed to by the out pointer, for a value returned by reference
+    fn out_type(&self, ty: &DartType) -> Option<&'static str> {
+        match ty {
+            DartType::Primitive(native, _) => Some(native),
+            DartType::String => Some("Pointer<Utf8>"),
+            DartType::Class(_) | DartType::Optional(_) => Some("Pointer<Void>"),
+            DartType::Array(..) => Some("_Arr"),
+            _ => None,
+        }
+    }
+
+    /// Record the lookup of `symbol`, which takes arguments of the given native and Dart types
+    fn native(&self, symbol: &Ident, mut params: Vec<(String, String)>, output: &DartType) {
+        let ret = match output {
+            DartType::Unit | DartType::Class(_) | DartType::Optional(_) => {
+                ("Void".to_string(), "void".to_string())
+            }
+            DartType::Primitive(native, dart) => (native.to_string(), dart.to_string()),
+            DartType::String => ("Pointer<Utf8>".into(), "Pointer<Utf8>".into()),
+            DartType::Plain(class) => (self.class_name(class), self.class_name(class)),
+            DartType::Result(..) => ("Int32".into(), "int".into()),
+            DartType::Array(..) => ("_Arr".into(), "_Arr".into()),
+        };
+        let out = match output {
+            DartType::Result(ok, _) => self.out_type(ok),
+            output => self.out_type(output).filter(|_| ret.0 == "Void"),
+        };
+        params.extend(out.map(|out| {
+            let ptr = format!("Pointer<{}>", out);
+            (ptr.clone(), ptr)
+        }));
+
+        let (native, dart): (Vec<_>, Vec<_>) = params.into_iter().unzip();
+        let lookup = format!(
+            "final _{symbol} = _lib.lookupFunction<{} Function({}),\n    {} Function({})>('{symbol}');",
+            ret.0,
+            native.join(", "),
+            ret.1,
+            dart.join(", "),
+            symbol = symbol,
+        );
+
+        let mut natives = self.natives.borrow_mut();
+        if !natives.iter().any(|(s, _)| s == symbol) {
+            natives.push((symbol.clone(), lookup));
+        }
+    }
+
+    /// Convert a value returned by the C API to its Dart type
+    fn convert(&self, value: &str, ty: &DartType) -> String {
+        match ty {
+            DartType::String => format!("_takeString({})", value),
+            DartType::Array(..) => format!("_takeBytes({})", value),
+            DartType::Class(class) => format!("{}._({})", self.class_name(class), value),
+            DartType::Optional(class) => format!(
+                "{value} == nullptr ? null : {}._({value})",
+                self.class_name(class),
+                value = value
+            ),
+            _ => value.to_string(),
+        }
+    }
+
+    /// Lines of the body that calls `symbol`, after the arguments have been prepared, and whether
+    /// they allocate from the arena
+    fn body(
+        &self,
+        symbol: &Ident,
+        mut args: Vec<String>,
+        output: &DartType,
+    ) -> (Vec<String>, bool) {
+        let call = |args: &[String]| format!("_{}({})", symbol, args.join(", "));
+
+        match output {
+            DartType::Unit => (vec![format!("{};", call(&args))], false),
+            DartType::Primitive(..)
+            | DartType::String
+            | DartType::Array(..)
+            | DartType::Plain(_) => (
+                vec![format!("return {};", self.convert(&call(&args), output))],
+                false,
+            ),
+            DartType::Class(_) | DartType::Optional(_) => {
+                args.push("ptrOut".into());
+                (
+                    vec![
+                        format!(
+                            "final ptrOut = arena<{}>();",
+                            self.out_type(output).unwrap()
+                        ),
+                        format!("{};", call(&args)),
+                        format!("return {};", self.convert("ptrOut.value", output)),
+                    ],
+                    true,
+                )
+            }
+            DartType::Result(ok, exception) => {
+                let out = self.out_type(ok);
+                let mut lines = vec![];
+                if let Some(out) = out {
+                    lines.push(format!("final ptrOut = arena<{}>();", out));
+                    args.push("ptrOut".into());
+                }
+                lines.extend(vec![
+                    format!("final code = {};", call(&args)),
+                    "if (code != 0) {".into(),
+                    format!("  throw {}(code);", exception),
+                    "}".into(),
+                ]);
+                if out.is_some() {
+                    // Structs are read through `ref`, not `value`
+                    let value = match ok.as_ref() {
+                        DartType::Array(..) => "ptrOut.ref",
+                        _ => "ptrOut.value",
+                    };
+                    lines.push(format!("return {};", self.convert(value, ok)));
+                }
+
+                (lines, out.is_some())
+            }
+        }
+    }
+}
+
+/// Parameters, native parameters, arguments and the lines that prepare them
+struct Params {
+    params: Vec<String>,
+    native: Vec<(String, String)>,
+    args: Vec<String>,
+    lines: Vec<String>,
+    /// Whether the lines allocate from the arena
+    arena: bool,
+}
+
+fn params<'a>(
+    ctx: &Context<'a>,
+    class: Option<&'a Class>,
+    receiver: bool,
+    inputs: &[(Ident, Type)],
+) -> Option<Params> {
+    let mut params = Params {
+        params: vec![],
+        native: vec![],
+        args: vec![],
+        lines: vec![],
+        arena: false,
+    };
+    if receiver {
+        params
+            .native
+            .push(("Pointer<Void>".into(), "Pointer<Void>".into()));
+        params.args.push("_ptr".into());
+    }
+    for (ident, ty) in inputs {
+        let ty = ctx.classify_input(ty, class)?;
+        let name = param_name(ident);
+        let local = camel_case(&ident.to_string(), false);
+        params
+            .params
+            .push(format!("{} {}", ctx.type_name(&ty), name));
+        params.native.push(ctx.ffi_types(&ty));
+
+        match ty {
+            DartType::String => {
+                params.lines.push(format!(
+                    "final {}Ptr = {}.toNativeUtf8(allocator: arena);",
+                    local, name
+                ));
+                params.args.push(format!("{}Ptr", local));
+                params.arena = true;
+            }
+            DartType::Array(native, _) => {
+                // One more element, so that nothing is allocated for empty lists
+                params.lines.extend(vec![
+                    format!(
+                        "final {}Data = arena<{}>({}.length + 1);",
+                        local, native, name
+                    ),
+                    format!(
+                        "for (var i = 0; i < {name}.length; i++) {local}Data[i] = {name}[i];",
+                        name = name,
+                        local = local
+                    ),
+                    format!("final {}Arr = arena<_Arr>();", local),
+                    format!(
+                        "{local}Arr.ref\n  ..ptr = {local}Data.cast()\n  ..len = {}.length;",
+                        name,
+                        local = local
+                    ),
+                ]);
+                params.args.push(format!("{}Arr.ref", local));
+                params.arena = true;
+            }
+            DartType::Class(_) => params.args.push(format!("{}._ptr", name)),
+            _ => params.args.push(name),
+        }
+    }
+
+    Some(params)
+}
+
+/// Wrap the body in `using()` when it allocates from the arena, which is freed when it returns
+fn with_arena(lines: Vec<String>, arena: bool, returns: bool) -> Vec<String> {
+    if !arena {
+        return lines;
+    }
+
+    let mut wrapped = vec![match returns {
+        true => "return using((arena) {".to_string(),
+        false => "using((arena) {".to_string(),
+    }];
+    wrapped.extend(
+        lines
+            .iter()
+            .flat_map(|l| l.split('\n'))
+            .map(|l| format!("  {}", l)),
+    );
+    wrapped.push("});".into());
+
+    wrapped
+}
+
+/// Render a method of a class, or a top-level function when `class` is `None`. Returns `None` if
+/// some types are not supported
+fn render_method(
+    ctx: &Context,
+    class: Option<&Class>,
+    method: &Method,
+    level: usize,
+) -> Option<String> {
+    let receiver = match method.kind {
+        MethodKind::Instance { .. } | MethodKind::Operator(_) => true,
+        MethodKind::Constructor | MethodKind::Static => false,
+        MethodKind::Getter | MethodKind::Setter => unreachable!("Rendered as accessors"),
+    };
+
+    let Params {
+        params,
+        native,
+        args,
+        mut lines,
+        arena,
+    } = params(ctx, class, receiver, &method.inputs)?;
+    let params = params.join(", ");
+
+    let output = ctx.classify(&method.output, class)?;
+    ctx.native(&method.symbol, native, &output);
+    let ret = ctx.type_name(&output);
+    let name = member_name(&method.name);
+
+    let (body, body_arena) = ctx.body(&method.symbol, args, &output);
+    lines.extend(body);
+    let lines = with_arena(lines, arena || body_arena, !matches!(ret.as_str(), "void"));
+
+    let decl = match &method.kind {
+        MethodKind::Constructor => {
+            let class = ctx.class_name(class?);
+            match name.as_str() {
+                "" => format!("factory {}({})", class, params),
+                name => format!("factory {}.{}({})", class, name, params),
+            }
+        }
+        MethodKind::Operator(op) => match dart_operator(*op) {
+            DartOperator::Binary(op) => format!("{} operator {}({})", ret, op, params),
+            DartOperator::Unary => format!("{} operator -()", ret),
+            DartOperator::Indexer => format!("{} operator []({})", ret, params),
+        },
+        // `to_string()` overrides `Object.toString()`
+        MethodKind::Instance { .. } if is_to_string(method) => {
+            "@override\nString toString()".to_string()
+        }
+        MethodKind::Instance { .. } => format!("{} {}({})", ret, name, params),
+        MethodKind::Static if class.is_some() => format!("static {} {}({})", ret, name, params),
+        MethodKind::Static => format!("{} {}({})", ret, name, params),
+        MethodKind::Getter | MethodKind::Setter => unreachable!("Rendered as accessors"),
+    };
+
+    let mut out = decl.split('\n').map(String::from).collect::<Vec<_>>();
+    let last = out.pop().unwrap();
+    out.push(format!("{} {{", last));
+    out.extend(lines.into_iter().map(|l| format!("  {}", l)));
+    out.push("}".into());
+
+    Some(Dart::indent(out, level))
+}
+
+/// Render the getter and the setter of a field. Returns `None` if none of them is supported
+fn render_accessors(
+    ctx: &Context,
+    class: &Class,
+    getter: Option<&Method>,
+    setter: Option<&Method>,
+    level: usize,
+) -> Option<String> {
+    let mut out = vec![];
+
+    if let Some(getter) = getter {
+        let name = member_name(&getter.name);
+        let output = ctx.classify(&getter.output, Some(class));
+        let receiver = vec![("Pointer<Void>".to_string(), "Pointer<Void>".to_string())];
+        let body = match &output {
+            // Other opaque fields are borrowed from the struct, so they can't be owned by an
+            // object. `shared` ones are returned with a new reference
+            Some(DartType::Class(field)) if field.shared => {
+                ctx.native(
+                    &getter.symbol,
+                    receiver,
+                    &DartType::Primitive("Pointer<Void>", "Pointer<Void>"),
+                );
+                Some(format!(
+                    "return {}._(_{}(_ptr));",
+                    ctx.class_name(field),
+                    getter.symbol
+                ))
+            }
+            Some(output @ DartType::Primitive(native, _))
+                if !matches!(*native, "Bool" | "Float" | "Double") =>
+            {
+                ctx.native(&getter.symbol, receiver, output);
+                Some(format!("return _{}(_ptr);", getter.symbol))
+            }
+            _ => None,
+        };
+        if let (Some(body), Some(output)) = (body, output) {
+            out.push(format!("{} get {} {{", ctx.type_name(&output), name));
+            out.push(format!("  {}", body));
+            out.push("}".into());
+        }
+    }
+
+    if let Some(setter) = setter {
+        let name = member_name(&setter.name);
+        let params = params(ctx, Some(class), true, &setter.inputs);
+        let output = ctx.classify(&setter.output, Some(class));
+        if let (Some(params), Some(output)) = (params, output) {
+            ctx.native(&setter.symbol, params.native, &output);
+            let mut lines = params.lines;
+            let (body, body_arena) = ctx.body(&setter.symbol, params.args, &output);
+            lines.extend(body);
+
+            if !out.is_empty() {
+                out.push(String::new());
+            }
+            out.push(format!("set {}({}) {{", name, params.params.join(", ")));
+            out.extend(
+                with_arena(lines, params.arena || body_arena, false)
+                    .into_iter()
+                    .map(|l| format!("  {}", l)),
+            );
+            out.push("}".into());
+        }
+    }
+
+    if out.is_empty() {
+        return None;
+    }
+
+    Some(Dart::indent(out, level))
+}
+
+fn is_to_string(method: &Method) -> bool {
+    matches!(method.kind, MethodKind::Instance { .. })
+        && method.name == "to_string"
+        && method.inputs.is_empty()
+        && match_fixed_type(&method.output, parse_quote!(String))
+}
+
+/// Members of the class of a trait: the callbacks called by the C API and the factory that takes
+/// the object implementing the interface
+fn render_trait_members(ctx: &Context, class: &Class, tr: &Trait) -> String {
+    let name = ctx.class_name(class);
+    let interface = format!("{}{}", ctx.prefix(&tr.mod_path), tr.ident);
+
+    let mut lines = vec![
+        "/// Objects wrapped by the structs, by the id passed to the C API as `this`".to_string(),
+        format!("static final _objects = <int, {}>{{}};", interface),
+        "static var _nextId = 1;".into(),
+        String::new(),
+        "// Called when the struct is freed, possibly by a `NativeFinalizer`, so it can't run"
+            .into(),
+        "// synchronously".into(),
+        "static final _destroy =".into(),
+        "    NativeCallable<Void Function(Pointer<Void>)>.listener(_destroyCallback)".into(),
+        "      ..keepIsolateAlive = false;".into(),
+        "static void _destroyCallback(Pointer<Void> self) => _objects.remove(self.address);".into(),
+    ];
+    let mut native_params = vec![
+        "Pointer<Void>".to_string(),
+        "Pointer<NativeFunction<Void Function(Pointer<Void>)>>".to_string(),
+    ];
+    let mut args = vec![
+        "Pointer.fromAddress(id)".to_string(),
+        "_destroy.nativeFunction".to_string(),
+    ];
+    for callback in &tr.callbacks {
+        let field = format!("_{}", member_name(&callback.name));
+
+        let mut native = vec!["Pointer<Void>".to_string()];
+        let mut params = vec!["Pointer<Void> self".to_string()];
+        let mut call_args = vec![];
+        for (ident, ty) in &callback.inputs {
+            let name = param_name(ident);
+            match primitive(ty) {
+                Some((n, dart)) => {
+                    native.push(n.to_string());
+                    params.push(format!("{} {}", dart, name));
+                    call_args.push(name);
+                }
+                // Owned by the callee
+                None => {
+                    native.push("Pointer<Utf8>".into());
+                    params.push(format!("Pointer<Utf8> {}", name));
+                    call_args.push(format!("_takeString({})", name));
+                }
+            }
+        }
+        let (ret_native, ret_dart) = primitive(&callback.output).unwrap_or(("Void", "void"));
+        let signature = format!("{} Function({})", ret_native, native.join(", "));
+        let exceptional = match ret_native {
+            "Void" => String::new(),
+            _ => format!(", exceptionalReturn: {}", exceptional_return(ret_dart)),
+        };
+
+        lines.push(String::new());
+        lines.push(format!(
+            "static final {} = NativeCallable<{}>.isolateLocal(",
+            field, signature
+        ));
+        lines.push(format!("    {}Callback{})", field, exceptional));
+        lines.push("  ..keepIsolateAlive = false;".into());
+        lines.push(format!(
+            "static {} {}Callback({}) =>",
+            ret_dart,
+            field,
+            params.join(", ")
+        ));
+        lines.push(format!(
+            "    _objects[self.address]!.{}({});",
+            member_name(&callback.name),
+            call_args.join(", ")
+        ));
+
+        native_params.push(format!("Pointer<NativeFunction<{}>>", signature));
+        args.push(format!("{}.nativeFunction", field));
+    }
+    ctx.native(
+        &tr.constructor,
+        native_params.into_iter().map(|p| (p.clone(), p)).collect(),
+        &DartType::Class(class),
+    );
+    args.push("ptrOut".into());
+
+    lines.push(String::new());
+    lines.extend(vec![
+        "/// Wrap `impl`, which is called back until the struct is freed. The callbacks must be"
+            .into(),
+        "/// called from the thread of the isolate".into(),
+        format!("factory {}({} impl) {{", name, interface),
+        "  final id = _nextId++;".into(),
+        "  _objects[id] = impl;".into(),
+        "  return using((arena) {".into(),
+        "    final ptrOut = arena<Pointer<Void>>();".into(),
+        format!("    _{}({});", tr.constructor, args.join(", ")),
+        format!("    return {}._(ptrOut.value);", name),
+        "  });".into(),
+        "}".into(),
+    ]);
+
+    Dart::indent(lines, 1)
+}
+
+fn render_class(ctx: &Context, class: &Class) -> String {
+    let name = ctx.class_name(class);
+    let ownership = match (&class.destructor, class.shared) {
+        (Some(_), true) => "released when the object is garbage collected",
+        (Some(_), false) => "freed when the object is garbage collected",
+        (None, _) => "never freed since the struct has no `#[destructor]`",
+    };
+
+    let mut lines = vec![
+        format!(
+            "/// Owns a pointer to `{}{}`, {}",
+            ctx.c_prefix, class.ident, ownership
+        ),
+        format!("final class {} implements Finalizable {{", name),
+    ];
+    match &class.destructor {
+        Some(destructor) => lines.extend(vec![
+            "  static final _finalizer = NativeFinalizer(".into(),
+            format!(
+                "      _lib.lookup<NativeFunction<Void Function(Pointer<Void>)>>('{}'));",
+                destructor
+            ),
+            String::new(),
+            "  final Pointer<Void> _ptr;".into(),
+            String::new(),
+            format!("  {}._(this._ptr) {{", name),
+            "    _finalizer.attach(this, _ptr);".into(),
+            "  }".into(),
+        ]),
+        None => lines.extend(vec![
+            "  final Pointer<Void> _ptr;".into(),
+            String::new(),
+            format!("  {}._(this._ptr);", name),
+        ]),
+    }
+    let mut out = Dart::indent(lines, 0);
+
+    if let Some(tr) = ctx.traits.iter().find(|t| t.struct_ident == class.ident) {
+        writeln!(out).unwrap();
+        write!(out, "{}", render_trait_members(ctx, class, tr)).unwrap();
+    }
+
+    let mut rendered_accessors = vec![];
+    for method in &class.methods {
+        let rendered = match method.kind {
+            MethodKind::Getter | MethodKind::Setter => {
+                if rendered_accessors.contains(&&method.name) {
+                    continue;
+                }
+                rendered_accessors.push(&method.name);
+
+                let find = |kind: MethodKind| {
+                    class
+                        .methods
+                        .iter()
+                        .find(|m| m.kind == kind && m.name == method.name)
+                };
+                render_accessors(
+                    ctx,
+                    class,
+                    find(MethodKind::Getter),
+                    find(MethodKind::Setter),
+                    1,
+                )
+            }
+            _ => render_method(ctx, Some(class), method, 1),
+        };
+
+        writeln!(out).unwrap();
+        match rendered {
+            Some(rendered) => write!(out, "{}", rendered).unwrap(),
+            None => writeln!(
+                out,
+                "  // `{}` is not available: unsupported argument or return types",
+                method.name
+            )
+            .unwrap(),
+        }
+    }
+    writeln!(out, "}}").unwrap();
+
+    out
+}
+
+/// Transparent structs have the same layout as the C struct
+fn render_plain(ctx: &Context, class: &Class) -> String {
+    let mut lines = vec![
+        format!(
+            "/// Passed by value, with the layout of `{}{}`",
+            ctx.c_prefix, class.ident
+        ),
+        format!("final class {} extends Struct {{", ctx.class_name(class)),
+    ];
+    for (i, (ident, ty)) in class.fields.iter().enumerate() {
+        let (native, dart) = primitive(ty).unwrap();
+        if i > 0 {
+            lines.push(String::new());
+        }
+        lines.push(format!("  @{}()", native));
+        lines.push(format!(
+            "  external {} {};",
+            dart,
+            member_name(&ident.to_string())
+        ));
+    }
+    lines.push("}".into());
+
+    Dart::indent(lines, 0)
+}
+
+/// The interface implemented by the objects wrapped in the struct of a trait
+fn render_interface(ctx: &Context, tr: &Trait) -> String {
+    let name = format!("{}{}", ctx.prefix(&tr.mod_path), tr.ident);
+    if !tr.supported {
+        return format!(
+            "// `{}` is not available: unsupported callback types\n",
+            name
+        );
+    }
+
+    let mut lines = vec![
+        format!(
+            "/// Implemented by the objects wrapped in a [{}{}]",
+            ctx.prefix(&tr.mod_path),
+            tr.struct_ident
+        ),
+        format!("abstract interface class {} {{", name),
+    ];
+    for callback in &tr.callbacks {
+        let params = callback
+            .inputs
+            .iter()
+            .map(|(ident, ty)| {
+                format!(
+                    "{} {}",
+                    primitive(ty).map_or("String", |(_, dart)| dart),
+                    param_name(ident)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        lines.push(format!(
+            "  {} {}({});",
+            primitive(&callback.output).map_or("void", |(_, dart)| dart),
+            member_name(&callback.name),
+            params
+        ));
+    }
+    lines.push("}".into());
+
+    Dart::indent(lines, 0)
+}
+
+fn render_source(items: Recorded) -> String {
+    let Recorded {
+        classes,
+        functions,
+        traits,
+    } = &items;
+
+    let lib_name = lib_name();
+    let ctx = Context {
+        lib: camel_case(&lib_name, true),
+        c_prefix: std::env::var("EXPOSE_C_PREFIX").unwrap_or_default(),
+        classes,
+        traits,
+        errors: RefCell::new(BTreeSet::new()),
+        natives: RefCell::new(vec![]),
+    };
+
+    let mut items = vec![];
+    for class in classes {
+        items.push(match class.opaque {
+            true => render_class(&ctx, class),
+            false if class.fields.iter().all(|(_, ty)| primitive(ty).is_some()) => {
+                render_plain(&ctx, class)
+            }
+            false => format!(
+                "// `{}` is not available: unsupported field types\n",
+                ctx.class_name(class)
+            ),
+        });
+    }
+    for tr in traits {
+        items.push(render_interface(&ctx, tr));
+    }
+    for (mod_path, function) in functions {
+        // Top-level functions are prefixed with the path of their module
+        let name = mod_path
+            .iter()
+            .skip(1)
+            .map(|m| m.to_string())
+            .chain(std::iter::once(function.name.clone()))
+            .collect::<Vec<_>>();
+        let function = Method {
+            name: name.join("_"),
+            ..function.clone()
+        };
+        items.push(render_method(&ctx, None, &function, 0).unwrap_or_else(|| {
+            format!(
+                "// `{}` is not available: unsupported argument or return types\n",
+                member_name(&function.name)
+            )
+        }));
+    }
+
+    let mut errors = String::new();
+    for error in ctx.errors.borrow().iter() {
+        writeln!(
+            errors,
+            "final class {error}Exception extends {lib}Exception {{\n  \
+             {error}Exception(int code) : super('{error}: error code $code', code);\n\
+             }}\n",
+            error = error,
+            lib = ctx.lib
+        )
+        .unwrap();
+    }
+
+    let natives = ctx
+        .natives
+        .borrow()
+        .iter()
+        .map(|(_, lookup)| format!("{}\n", lookup))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    format!(
+        "// Generated by `#[expose_mod]`. Do not edit.\n\
+         // ignore_for_file: non_constant_identifier_names\n\n\
+         import 'dart:ffi';\n\
+         import 'dart:io';\n\
+         import 'dart:typed_data';\n\n\
+         import 'package:ffi/ffi.dart';\n\n\
+         final _lib = _open();\n\n\
+         DynamicLibrary _open() {{\n  \
+         if (Platform.isMacOS || Platform.isIOS) {{\n    \
+         return DynamicLibrary.open('lib{lib_name}.dylib');\n  \
+         }}\n  \
+         if (Platform.isWindows) {{\n    \
+         return DynamicLibrary.open('{lib_name}.dll');\n  \
+         }}\n  \
+         return DynamicLibrary.open('lib{lib_name}.so');\n\
+         }}\n\n\
+         final _{c}string_free = _lib.lookupFunction<Void Function(Pointer<Utf8>),\n    \
+         void Function(Pointer<Utf8>)>('{c}string_free');\n\n\
+         /// Copy a string returned by the C API, and free it\n\
+         String _takeString(Pointer<Utf8> s) {{\n  \
+         try {{\n    \
+         return s.toDartString();\n  \
+         }} finally {{\n    \
+         _{c}string_free(s);\n  \
+         }}\n\
+         }}\n\n\
+         final _{c}bytes_free = _lib.lookupFunction<Void Function(_Arr),\n    \
+         void Function(_Arr)>('{c}bytes_free');\n\n\
+         /// Copy a vector returned by the C API, and free it\n\
+         Uint8List _takeBytes(_Arr arr) {{\n  \
+         try {{\n    \
+         return arr.len == 0\n        \
+         ? Uint8List(0)\n        \
+         : Uint8List.fromList(arr.ptr.cast<Uint8>().asTypedList(arr.len));\n  \
+         }} finally {{\n    \
+         _{c}bytes_free(arr);\n  \
+         }}\n\
+         }}\n\n\
+         /// Array of primitives passed to or returned by the C API, with the layout of `{c}Arr_*`\n\
+         final class _Arr extends Struct {{\n  \
+         external Pointer<Void> ptr;\n\n  \
+         @UintPtr()\n  \
+         external int len;\n\
+         }}\n\n\
+         /// Thrown when the C API returns an error code\n\
+         class {lib}Exception implements Exception {{\n  \
+         final String message;\n  \
+         final int code;\n\n  \
+         {lib}Exception(this.message, this.code);\n\n  \
+         @override\n  \
+         String toString() => message;\n\
+         }}\n\n\
+         {errors}\
+         {natives}\n\
+         {items}",
+        lib_name = lib_name,
+        lib = ctx.lib,
+        c = ctx.c_prefix,
+        errors = errors,
+        natives = natives,
+        items = items.join("\n"),
+    )
+}
@@ -0,0 +1,1059 @@
+//! Go package over the C API
+//!
+//! Like the Swift layer, the Rust side is generated by the C backend while the original
+//! signatures of the exposed items are recorded. When the root `#[expose_mod]` is expanded they
+//! are rendered to a cgo source that wraps every opaque struct in a Go struct with a finalizer
+//! that frees its pointer, returns error codes as typed `error`s and copies strings and slices
+//! from and to memory allocated in C. The source is written to `OUT_DIR` as
+//! `<crate>_<module>.go`, in the package named after the crate. Exposed traits are implemented by
+//! Go values, which are passed to the C API as `cgo.Handle`s and called back by the functions
+//! exported from `<crate>_<module>_callbacks.go`.
+
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use quote::ToTokens;
+use syn::punctuated::Punctuated;
+use syn::{
+    Ident, Item, ItemFn, ItemImpl, ItemMod, ItemStruct, ItemTrait, Path, Token, Type, TypePath,
+    TypeReference, TypeSlice,
+};
+
+use super::c::{CError, C};
+use super::c_family::{self, CFamily, Callback, Class, Method, MethodKind, Recorded, Trait};
+use super::*;
+use crate::types::*;
+
+#[derive(Debug)]
+pub struct Go;
+
+impl CFamily for Go {
+    const OUTPUT: &'static str = "GO";
+    const INDENT: &'static str = "\t";
+    // Go has no overloading
+    const OVERLOADING: bool = false;
+    const CALLBACKS: bool = true;
+
+    fn render(root: &Ident, items: Recorded) -> Vec<(PathBuf, String)> {
+        render_sources(root, items)
+    }
+
+    fn optional_files(root: &Ident) -> Vec<PathBuf> {
+        vec![format!("{}_{}_callbacks.go", lib_name(), root).into()]
+    }
+}
+
+impl Lang for Go {
+    type Error = CError;
+
+    // The Rust side is the C API, so it uses the same mappings
+    const NAME: &'static str = C::NAME;
+
+    fn expose_fn(
+        function: &mut ItemFn,
+        mod_path: &Vec<Ident>,
+        extra: &mut Vec<Item>,
+    ) -> Result<Ident, Self::Error> {
+        c_family::expose_fn::<Self>(function, mod_path, extra)
+    }
+
+    fn expose_mod(
+        module: &mut ItemMod,
+        mod_path: &Vec<Ident>,
+        sub_items: Vec<ModuleItem>,
+    ) -> Result<Ident, Self::Error> {
+        c_family::expose_mod::<Self>(module, mod_path, sub_items)
+    }
+
+    fn expose_struct(
+        structure: &mut ItemStruct,
+        opts: Punctuated<ExposeStructOpts, Token![,]>,
+        mod_path: &Vec<Ident>,
+        extra: &mut Vec<Item>,
+    ) -> Result<Ident, Self::Error> {
+        c_family::expose_struct(structure, opts, mod_path, extra)
+    }
+
+    fn expose_impl(
+        implementation: &mut ItemImpl,
+        mod_path: &Vec<Ident>,
+    ) -> Result<(), Self::Error> {
+        c_family::expose_impl::<Self>(implementation, mod_path)
+    }
+
+    fn expose_trait(
+        tr: &mut ItemTrait,
+        mod_path: &Vec<Ident>,
+        extra: &mut Vec<Item>,
+    ) -> Result<Ident, Self::Error> {
+        c_family::expose_trait::<Self>(tr, mod_path, extra)
+    }
+
+    fn expose_operators(
+        self_ty: &Type,
+        operators: Vec<ExposedOperator>,
+        mod_path: &Vec<Ident>,
+        extra: &mut Vec<Item>,
+    ) -> Result<(), Self::Error> {
+        // Go has no operator overloading, they are regular methods named after the operator:
+        // `Add`, `Sub`, `Mul`, `Neg` and `Index`
+        c_family::expose_operators::<Self>(self_ty, operators, mod_path, extra)
+    }
+
+    fn expose_getter(
+        field: &mut Field,
+        is_opaque: bool,
+        impl_block: &mut ItemImpl,
+    ) -> Result<(), Self::Error> {
+        C::expose_getter(field, is_opaque, impl_block)
+    }
+
+    fn expose_inline_getter(
+        field: &mut Field,
+        impl_block: &mut ItemImpl,
+    ) -> Result<(), Self::Error> {
+        C::expose_inline_getter(field, impl_block)
+    }
+
+    fn expose_inline_setter(
+        field: &mut Field,
+        validate: Option<&Path>,
+        impl_block: &mut ItemImpl,
+    ) -> Result<(), Self::Error> {
+        C::expose_inline_setter(field, validate, impl_block)
+    }
+
+    fn convert_getter_setter_ty(ty: Type) -> Result<(Type, Type), Self::Error> {
+        C::convert_getter_setter_ty(ty)
+    }
+
+    fn convert_input(ty: Type) -> Result<Input, Self::Error> {
+        C::convert_input(ty)
+    }
+
+    fn convert_output(output: Type) -> Result<Output, Self::Error> {
+        C::convert_output(output)
+    }
+}
+
+/// Go keywords, and the names used by the generated code, are suffixed with an underscore
+fn param_name(ident: &Ident) -> String {
+    const RESERVED: &[&str] = &[
+        "break",
+        "case",
+        "chan",
+        "const",
+        "continue",
+        "default",
+        "defer",
+        "else",
+        "fallthrough",
+        "for",
+        "func",
+        "go",
+        "goto",
+        "if",
+        "import",
+        "interface",
+        "map",
+        "package",
+        "range",
+        "return",
+        "select",
+        "struct",
+        "switch",
+        "type",
+        "var",
+        // Used by the generated code
+        "cgo",
+        "code",
+        "fmt",
+        "impl",
+        "out",
+        "runtime",
+        "self",
+        "unsafe",
+    ];
+
+    let name = camel_case(&ident.to_string(), false);
+    if RESERVED.contains(&name.as_str()) {
+        format!("{}_", name)
+    } else {
+        name
+    }
+}
+
+/// A primitive type, with its Go name and the name of the matching cgo type
+#[derive(Debug, Clone, Copy)]
+struct Primitive {
+    go: &'static str,
+    c: &'static str,
+}
+
+impl Primitive {
+    fn zero(&self) -> &'static str {
+        match self.go {
+            "bool" => "false",
+            _ => "0",
+        }
+    }
+}
+
+fn primitive(ty: &Type) -> Option<Primitive> {
+    let (go, c) = match c_family::primitive_ident(ty)?.as_str() {
+        "bool" => ("bool", "C.bool"),
+        "i8" => ("int8", "C.int8_t"),
+        "u8" => ("uint8", "C.uint8_t"),
+        "i16" => ("int16", "C.int16_t"),
+        "u16" => ("uint16", "C.uint16_t"),
+        "i32" => ("int32", "C.int32_t"),
+        "u32" => ("uint32", "C.uint32_t"),
+        "i64" => ("int64", "C.int64_t"),
+        "u64" => ("uint64", "C.uint64_t"),
+        "isize" => ("int", "C.intptr_t"),
+        "usize" => ("uint", "C.uintptr_t"),
+        "f32" => ("float32", "C.float"),
+        "f64" => ("float64", "C.double"),
+        _ => return None,
+    };
+
+    Some(Primitive { go, c })
+}
+
+/// How a Rust type crosses the C API, from the point of view of the Go layer
+#[derive(Clone)]
+enum GoType<'a> {
+    Unit,
+    Primitive(Primitive),
+    /// Copied to or from memory allocated in C
+    String,
+    /// Opaque struct, passed as a pointer and returned through an out pointer
+    Class(&'a Class),
+    /// Transparent struct with primitive fields, converted from and to the C struct
+    Plain(&'a Class),
+    Optional(&'a Class),
+    /// `Vec` or slice of primitives as an argument, copied to an `Arr<T>`, with the Rust name of
+    /// the primitive. `Vec<u8>` as a return value, copied from the `Arr_u8`
+    Array(Primitive, String),
+    /// Error code, with the ok value returned through an out pointer
+    Result(Box<GoType<'a>>, String),
+}
+
+struct Context<'a> {
+    c_prefix: String,
+    classes: &'a [Class],
+    errors: RefCell<BTreeSet<String>>,
+}
+
+impl<'a> Context<'a> {
+    fn class(&self, ty: &Type, self_class: Option<&'a Class>) -> Option<&'a Class> {
+        if match_fixed_type(ty, parse_quote!(Self)) {
+            return self_class;
+        }
+        match ty {
+            Type::Path(TypePath { path, .. }) => {
+                let ident = &path.segments.last()?.ident;
+                self.classes.iter().find(|c| &c.ident == ident)
+            }
+            _ => None,
+        }
+    }
+
+    fn error(&self, ty: &Type) -> Option<String> {
+        let ident = match ty {
+            Type::Path(TypePath { path, .. }) => path.segments.last()?.ident.to_string(),
+            _ => return None,
+        };
+        if lookup_mapping(C::NAME, ty).is_none()
+            && ident != "ValidationError"
+            && ident != "IndexError"
+        {
+            return None;
+        }
+
+        self.errors.borrow_mut().insert(ident.clone());
+        Some(ident)
+    }
+
+    fn classify(&self, ty: &Type, self_class: Option<&'a Class>) -> Option<GoType<'a>> {
+        if let Some(target) = lookup_mapping(C::NAME, ty) {
+            return self.classify(&target, self_class);
+        }
+        if let Type::Tuple(tuple) = ty {
+            return if tuple.elems.is_empty() {
+                Some(GoType::Unit)
+            } else {
+                None
+            };
+        }
+        if let Some(primitive) = primitive(ty) {
+            return Some(GoType::Primitive(primitive));
+        }
+        if match_fixed_type(ty, parse_quote!(String)) {
+            return Some(GoType::String);
+        }
+        // Returned vectors are freed with `bytes_free()`
+        if ty == &parse_quote!(Vec<u8>) {
+            let byte = Primitive {
+                go: "uint8",
+                c: "C.uint8_t",
+            };
+            return Some(GoType::Array(byte, "u8".into()));
+        }
+        if let Some(inner) = match_generic_type(ty, parse_quote!(Option)) {
+            return match self.class(&inner[0], self_class) {
+                Some(class) if class.opaque => Some(GoType::Optional(class)),
+                _ => None,
+            };
+        }
+        if let Some(inner) = match_generic_type(ty, parse_quote!(Result)) {
+            if inner.len() != 2 {
+                return None;
+            }
+            let ok = match self.classify(&inner[0], self_class)? {
+                ok @ GoType::Unit
+                | ok @ GoType::Primitive(_)
+                | ok @ GoType::String
+                | ok @ GoType::Array(..)
+                | ok @ GoType::Class(_) => ok,
+                _ => return None,
+            };
+            return Some(GoType::Result(Box::new(ok), self.error(&inner[1])?));
+        }
+
+        match self.class(ty, self_class)? {
+            class if class.opaque => Some(GoType::Class(class)),
+            class if class.fields.iter().all(|(_, ty)| primitive(ty).is_some()) => {
+                Some(GoType::Plain(class))
+            }
+            _ => None,
+        }
+    }
+
+    fn classify_input(&self, ty: &Type, self_class: Option<&'a Class>) -> Option<GoType<'a>> {
+        let array = |elem: &Type| {
+            Some(GoType::Array(
+                primitive(elem)?,
+                elem.to_token_stream().to_string(),
+            ))
+        };
+        if let Some(inner) = match_generic_type(ty, parse_quote!(Vec)) {
+            return array(&inner[0]);
+        }
+
+        match ty {
+            Type::Reference(TypeReference { elem, .. }) => match elem.as_ref() {
+                // Slices are passed like vectors
+                Type::Slice(TypeSlice { elem, .. }) => array(elem),
+                // Opaque structs are only taken by reference
+                elem => match self.classify(elem, self_class)? {
+                    class @ GoType::Class(_) => Some(class),
+                    _ => None,
+                },
+            },
+            ty => match self.classify(ty, self_class)? {
+                ty @ GoType::Primitive(_) | ty @ GoType::String | ty @ GoType::Plain(_) => Some(ty),
+                _ => None,
+            },
+        }
+    }
+
+    /// Modules other than the root one are prepended to the names of their items
+    fn namespace(&self, mod_path: &[Ident]) -> String {
+        mod_path
+            .iter()
+            .skip(1)
+            .map(|m| camel_case(&m.to_string(), true))
+            .collect()
+    }
+
+    fn class_name(&self, class: &Class) -> String {
+        format!("{}{}", self.namespace(&class.mod_path), class.ident)
+    }
+
+    /// Name of the cgo type of a struct
+    fn c_name(&self, ident: &Ident) -> String {
+        format!("C.{}{}", self.c_prefix, ident)
+    }
+
+    fn type_name(&self, ty: &GoType) -> String {
+        match ty {
+            GoType::Unit => String::new(),
+            GoType::Primitive(primitive) => primitive.go.into(),
+            GoType::String => "string".into(),
+            GoType::Class(class) | GoType::Optional(class) => format!("*{}", self.class_name(class)),
+            GoType::Plain(class) => self.class_name(class),
+            GoType::Array(primitive, _) if primitive.go == "uint8" => "[]byte".into(),
+            GoType::Array(primitive, _) => format!("[]{}", primitive.go),
+            GoType::Result(ok, _) if matches!(**ok, GoType::Unit) => "error".into(),
+            GoType::Result(ok, _) => format!("({}, error)", self.type_name(ok)),
+        }
+    }
+
+    /// The argument passed to the C API for a parameter, with the lines that convert it first
+    fn arg(&self, ident: &Ident, ty: &GoType) -> (String, Vec<String>) {
+        let name = param_name(ident);
+        let c_name = format!("c{}", camel_case(&ident.to_string(), true));
+        match ty {
+            GoType::Primitive(primitive) => (format!("{}({})", primitive.c, name), vec![]),
+            GoType::String => (
+                c_name.clone(),
+                vec![
+                    format!("{} := C.CString({})", c_name, name),
+                    format!("defer C.free(unsafe.Pointer({}))", c_name),
+                ],
+            ),
+            GoType::Class(_) => (
+                format!("{}.ptr", name),
+                vec![format!("defer runtime.KeepAlive({})", name)],
+            ),
+            GoType::Plain(_) => (format!("{}.toC()", name), vec![]),
+            // Never empty, so that the pointer is valid even for empty slices
+            GoType::Array(primitive, rust_name) => (
+                c_name.clone(),
+                vec![
+                    format!(
+                        "{c} := C.{}Arr_{}{{\n\
+                         \tptr: (*{})(C.calloc(C.size_t(len({name}))+1, C.size_t(unsafe.Sizeof({name}[0])))),\n\
+                         \tlen: C.uintptr_t(len({name})),\n\
+                         }}",
+                        self.c_prefix,
+                        rust_name,
+                        primitive.c,
+                        c = c_name,
+                        name = name
+                    ),
+                    format!("defer C.free(unsafe.Pointer({}.ptr))", c_name),
+                    format!(
+                        "copy(unsafe.Slice((*{})(unsafe.Pointer({}.ptr)), len({name})), {name})",
+                        primitive.go,
+                        c_name,
+                        name = name
+                    ),
+                ],
+            ),
+            GoType::Unit | GoType::Optional(_) | GoType::Result(..) => {
+                unreachable!("Not taken as an argument")
+            }
+        }
+    }
+
+    /// Convert a value returned by the C API to its Go type
+    fn convert(&self, value: &str, ty: &GoType) -> String {
+        match ty {
+            GoType::Primitive(primitive) => format!("{}({})", primitive.go, value),
+            GoType::String => format!("goString({})", value),
+            GoType::Array(..) => format!("goBytes({})", value),
+            GoType::Class(class) | GoType::Optional(class) | GoType::Plain(class) => {
+                format!("wrap{}({})", self.class_name(class), value)
+            }
+            GoType::Unit | GoType::Result(..) => value.to_string(),
+        }
+    }
+
+    /// Declaration of the out pointer for a value returned by reference
+    fn out_decl(&self, ty: &GoType) -> Option<String> {
+        match ty {
+            GoType::Primitive(primitive) => Some(format!("var out {}", primitive.c)),
+            GoType::String => Some("var out *C.char".into()),
+            GoType::Array(..) => Some(format!("var out C.{}Arr_u8", self.c_prefix)),
+            GoType::Class(class) | GoType::Optional(class) => {
+                Some(format!("var out *{}", self.c_name(&class.ident)))
+            }
+            _ => None,
+        }
+    }
+
+    /// Lines of the body that calls `symbol`, after the arguments have been converted
+    fn body(&self, symbol: &Ident, mut args: Vec<String>, output: &GoType) -> Vec<String> {
+        let call = |args: &[String]| format!("C.{}({})", symbol, args.join(", "));
+
+        match output {
+            GoType::Unit => vec![call(&args)],
+            GoType::Primitive(_) | GoType::String | GoType::Array(..) | GoType::Plain(_) => {
+                vec![format!("return {}", self.convert(&call(&args), output))]
+            }
+            GoType::Class(_) | GoType::Optional(_) => {
+                args.push("&out".into());
+
+                let mut lines = vec![self.out_decl(output).unwrap(), call(&args)];
+                if let GoType::Optional(_) = output {
+                    lines.push("if out == nil {".into());
+                    lines.push("\treturn nil".into());
+                    lines.push("}".into());
+                }
+                lines.push(format!("return {}", self.convert("out", output)));
+
+                lines
+            }
+            GoType::Result(ok, error) => {
+                let out = self.out_decl(ok);
+                let mut lines = vec![];
+                if let Some(decl) = &out {
+                    lines.push(decl.clone());
+                    args.push("&out".into());
+                }
+                let err = format!("&{}{{Code: int32(code)}}", error);
+                lines.push(format!("if code := {}; code != 0 {{", call(&args)));
+                match &**ok {
+                    GoType::Unit => lines.push(format!("\treturn {}", err)),
+                    GoType::Primitive(primitive) => {
+                        lines.push(format!("\treturn {}, {}", primitive.zero(), err))
+                    }
+                    GoType::String => lines.push(format!("\treturn \"\", {}", err)),
+                    _ => lines.push(format!("\treturn nil, {}", err)),
+                }
+                lines.push("}".into());
+                match out {
+                    Some(_) => lines.push(format!("return {}, nil", self.convert("out", ok))),
+                    None => lines.push("return nil".into()),
+                }
+
+                lines
+            }
+        }
+    }
+}
+
+/// Render a method of a class, or a function when `class` is `None`. Returns `None` if some
+/// types are not supported
+fn render_method(
+    ctx: &Context,
+    class: Option<&Class>,
+    method: &Method,
+    namespace: &str,
+) -> Option<String> {
+    let receiver = match method.kind {
+        MethodKind::Instance { .. }
+        | MethodKind::Operator(_)
+        | MethodKind::Getter
+        | MethodKind::Setter => true,
+        MethodKind::Constructor | MethodKind::Static => false,
+    };
+
+    let mut params = vec![];
+    let mut args = vec![];
+    let mut lines = vec![];
+    if receiver {
+        args.push("self.ptr".to_string());
+        lines.push("defer runtime.KeepAlive(self)".to_string());
+    }
+    for (ident, ty) in &method.inputs {
+        let ty = ctx.classify_input(ty, class)?;
+        params.push(format!("{} {}", param_name(ident), ctx.type_name(&ty)));
+
+        let (arg, conversion) = ctx.arg(ident, &ty);
+        args.push(arg);
+        lines.extend(conversion);
+    }
+    let params = params.join(", ");
+
+    let output = ctx.classify(&method.output, class)?;
+    let ret = match ctx.type_name(&output) {
+        ret if ret.is_empty() => ret,
+        ret => format!(" {}", ret),
+    };
+    let name = camel_case(&method.name, true);
+    let self_name = class.map(|class| ctx.class_name(class));
+
+    let (decl, body) = match &method.kind {
+        MethodKind::Constructor => (
+            format!("func New{}{}({}){}", self_name?, name, params, ret),
+            ctx.body(&method.symbol, args, &output),
+        ),
+        MethodKind::Static => (
+            format!(
+                "func {}{}({}){}",
+                self_name.as_deref().unwrap_or(namespace),
+                name,
+                params,
+                ret
+            ),
+            ctx.body(&method.symbol, args, &output),
+        ),
+        MethodKind::Getter => {
+            let body = match output {
+                // Other opaque fields are borrowed from the struct, so they can't be owned by a
+                // Go struct. `shared` ones are returned with a new reference
+                GoType::Class(field) if field.shared => vec![format!(
+                    "return {}",
+                    ctx.convert(&format!("C.{}(self.ptr)", method.symbol), &output)
+                )],
+                GoType::Primitive(primitive)
+                    if !matches!(primitive.go, "bool" | "float32" | "float64") =>
+                {
+                    ctx.body(&method.symbol, args, &output)
+                }
+                _ => return None,
+            };
+
+            (
+                format!("func (self *{}) {}(){}", self_name?, name, ret),
+                body,
+            )
+        }
+        MethodKind::Setter => (
+            format!("func (self *{}) Set{}({}){}", self_name?, name, params, ret),
+            ctx.body(&method.symbol, args, &output),
+        ),
+        // `to_string()` makes the struct a `fmt.Stringer`
+        MethodKind::Instance { .. } if is_stringer(method) => (
+            format!("func (self *{}) String() string", self_name?),
+            ctx.body(&method.symbol, args, &output),
+        ),
+        MethodKind::Instance { .. } | MethodKind::Operator(_) => (
+            format!("func (self *{}) {}({}){}", self_name?, name, params, ret),
+            ctx.body(&method.symbol, args, &output),
+        ),
+    };
+    lines.extend(body);
+
+    Some(format!("{} {{\n{}}}\n", decl, Go::indent(lines, 1)))
+}
+
+fn is_stringer(method: &Method) -> bool {
+    matches!(method.kind, MethodKind::Instance { .. })
+        && method.name == "to_string"
+        && method.inputs.is_empty()
+        && match_fixed_type(&method.output, parse_quote!(String))
+}
+
+fn render_class(ctx: &Context, class: &Class) -> String {
+    let name = ctx.class_name(class);
+    let c_name = ctx.c_name(&class.ident);
+    let ownership = match (&class.destructor, class.shared) {
+        (Some(_), true) => "released when the struct is garbage collected",
+        (Some(_), false) => "freed when the struct is garbage collected",
+        (None, _) => "never freed since the struct has no `#[destructor]`",
+    };
+
+    let mut out = String::new();
+    writeln!(
+        out,
+        "// {name} owns a pointer to `{c}{ident}`, {ownership}\n\
+         type {name} struct {{\n\
+         \tptr *{c_name}\n\
+         }}\n\n\
+         // wrap{name} takes the ownership of a pointer returned by the C API\n\
+         func wrap{name}(ptr *{c_name}) *{name} {{",
+        name = name,
+        c = ctx.c_prefix,
+        ident = class.ident,
+        ownership = ownership,
+        c_name = c_name,
+    )
+    .unwrap();
+    match &class.destructor {
+        Some(destructor) => writeln!(
+            out,
+            "\tobj := &{name}{{ptr: ptr}}\n\
+             \truntime.SetFinalizer(obj, func(obj *{name}) {{\n\
+             \t\tC.{destructor}(obj.ptr)\n\
+             \t}})\n\
+             \treturn obj",
+            name = name,
+            destructor = destructor,
+        )
+        .unwrap(),
+        None => writeln!(out, "\treturn &{}{{ptr: ptr}}", name).unwrap(),
+    }
+    writeln!(out, "}}").unwrap();
+
+    for method in &class.methods {
+        writeln!(out).unwrap();
+        match render_method(ctx, Some(class), method, "") {
+            Some(rendered) => write!(out, "{}", rendered).unwrap(),
+            None => writeln!(
+                out,
+                "// `{}::{}` is not available: unsupported argument or return types",
+                class.ident, method.name
+            )
+            .unwrap(),
+        }
+    }
+
+    out
+}
+
+/// Transparent structs are copied to a Go struct with exported fields
+fn render_plain(ctx: &Context, class: &Class) -> String {
+    let name = ctx.class_name(class);
+    let c_name = ctx.c_name(&class.ident);
+    let fields = class
+        .fields
+        .iter()
+        .map(|(ident, ty)| {
+            (
+                ident.to_string(),
+                camel_case(&ident.to_string(), true),
+                primitive(ty).unwrap(),
+            )
+        })
+        .collect::<Vec<_>>();
+    let width = fields.iter().map(|(_, go, _)| go.len()).max().unwrap_or(0);
+
+    let mut out = String::new();
+    writeln!(
+        out,
+        "// {} is a copy of `{}{}`\ntype {} struct {{",
+        name, ctx.c_prefix, class.ident, name
+    )
+    .unwrap();
+    for (_, go, primitive) in &fields {
+        writeln!(out, "\t{:width$} {}", go, primitive.go, width = width).unwrap();
+    }
+    writeln!(out, "}}\n").unwrap();
+
+    let to_c = fields
+        .iter()
+        .map(|(c, go, primitive)| format!("{}: {}(v.{})", c, primitive.c, go))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let from_c = fields
+        .iter()
+        .map(|(c, go, primitive)| format!("{}: {}(v.{})", go, primitive.go, c))
+        .collect::<Vec<_>>()
+        .join(", ");
+    writeln!(
+        out,
+        "func (v {name}) toC() {c_name} {{\n\
+         \treturn {c_name}{{{to_c}}}\n\
+         }}\n\n\
+         func wrap{name}(v {c_name}) {name} {{\n\
+         \treturn {name}{{{from_c}}}\n\
+         }}",
+        name = name,
+        c_name = c_name,
+        to_c = to_c,
+        from_c = from_c,
+    )
+    .unwrap();
+
+    out
+}
+
+/// Name of the function exported to the C API for a callback
+fn export_name(tr: &Trait, callback: &Callback) -> String {
+    format!("go_{}_{}", tr.symbol, callback.name)
+}
+
+fn release_handle_name(ctx: &Context, root: &Ident) -> String {
+    format!("go_{}{}_release_handle", ctx.c_prefix, root)
+}
+
+/// The Go interface of a trait and the constructor of its struct, from a value implementing it
+fn render_trait(ctx: &Context, tr: &Trait, root: &Ident) -> String {
+    let namespace = ctx.namespace(&tr.mod_path);
+    let name = format!("{}{}", namespace, tr.ident);
+    if !tr.supported {
+        return format!(
+            "// `{}` is not available: unsupported callback types\n",
+            tr.ident
+        );
+    }
+
+    let mut out = String::new();
+    writeln!(
+        out,
+        "// {name} is implemented by the Go values passed to New{name}Struct\n\
+         type {name} interface {{",
+        name = name
+    )
+    .unwrap();
+    for callback in &tr.callbacks {
+        let params = callback
+            .inputs
+            .iter()
+            .map(|(ident, ty)| {
+                let ty = match primitive(ty) {
+                    Some(primitive) => primitive.go,
+                    None => "string",
+                };
+                format!("{} {}", param_name(ident), ty)
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        let ret = match primitive(&callback.output) {
+            Some(primitive) => format!(" {}", primitive.go),
+            None => String::new(),
+        };
+        writeln!(
+            out,
+            "\t{}({}){}",
+            camel_case(&callback.name, true),
+            params,
+            ret
+        )
+        .unwrap();
+    }
+    writeln!(out, "}}\n").unwrap();
+
+    let struct_name = format!("{}{}", namespace, tr.struct_ident);
+    let callbacks = tr
+        .callbacks
+        .iter()
+        .map(|c| format!(", (*[0]byte)(C.{})", export_name(tr, c)))
+        .collect::<String>();
+    writeln!(
+        out,
+        "// New{struct_name} wraps `impl` in a `{c}{ident}`, the handle to `impl` is deleted when\n\
+         // the struct is freed\n\
+         func New{struct_name}(impl {name}) *{struct_name} {{\n\
+         \thandle := C.go_handle_ptr(C.uintptr_t(cgo.NewHandle(impl)))\n\
+         \tvar out *{c_name}\n\
+         \tC.{constructor}(handle, (*[0]byte)(C.{release}){callbacks}, &out)\n\
+         \treturn wrap{struct_name}(out)\n\
+         }}",
+        struct_name = struct_name,
+        name = name,
+        c = ctx.c_prefix,
+        ident = tr.struct_ident,
+        c_name = ctx.c_name(&tr.struct_ident),
+        constructor = tr.constructor,
+        release = release_handle_name(ctx, root),
+        callbacks = callbacks,
+    )
+    .unwrap();
+
+    out
+}
+
+/// Signature of the exported function in C, and its definition in Go
+fn render_export(ctx: &Context, tr: &Trait, callback: &Callback) -> (String, String) {
+    let export = export_name(tr, callback);
+
+    let mut c_params = vec!["void *".to_string()];
+    let mut go_params = vec!["this unsafe.Pointer".to_string()];
+    let mut args = vec![];
+    for (ident, ty) in &callback.inputs {
+        let name = param_name(ident);
+        match primitive(ty) {
+            Some(primitive) => {
+                c_params.push(primitive.c.trim_start_matches("C.").to_string());
+                go_params.push(format!("{} {}", name, primitive.c));
+                args.push(format!("{}({})", primitive.go, name));
+            }
+            // Owned by the callee
+            None => {
+                c_params.push("char *".to_string());
+                go_params.push(format!("{} *C.char", name));
+                args.push(format!("goString({})", name));
+            }
+        }
+    }
+    let output = primitive(&callback.output);
+
+    let call = format!(
+        "impl.{}({})",
+        camel_case(&callback.name, true),
+        args.join(", ")
+    );
+    let name = format!("{}{}", ctx.namespace(&tr.mod_path), tr.ident);
+    let c_decl = format!(
+        "extern {} {}({});",
+        output.map_or("void", |o| o.c.trim_start_matches("C.")),
+        export,
+        c_params.join(", ")
+    );
+    let go_def = format!(
+        "//export {export}\n\
+         func {export}({params}){ret} {{\n\
+         \timpl := cgo.Handle(uintptr(this)).Value().({name})\n\
+         \t{body}\n\
+         }}\n",
+        export = export,
+        params = go_params.join(", "),
+        ret = output.map_or(String::new(), |o| format!(" {}", o.c)),
+        name = name,
+        body = match output {
+            Some(primitive) => format!("return {}({})", primitive.c, call),
+            None => call,
+        },
+    );
+
+    (c_decl, go_def)
+}
+
+/// The packages used by a source
+fn imports(source: &str) -> String {
+    let imports = [
+        ("fmt.", "fmt"),
+        ("runtime.", "runtime"),
+        ("cgo.", "runtime/cgo"),
+        ("unsafe.", "unsafe"),
+    ]
+    .iter()
+    .filter(|(usage, _)| source.contains(usage))
+    .map(|(_, package)| format!("\t\"{}\"\n", package))
+    .collect::<String>();
+
+    match imports.is_empty() {
+        true => String::new(),
+        false => format!("\nimport (\n{})\n", imports),
+    }
+}
+
+/// Render the package source, and the one that exports the callbacks if there are traits
+fn render_sources(root: &Ident, items: Recorded) -> Vec<(PathBuf, String)> {
+    let Recorded {
+        classes,
+        functions,
+        traits,
+    } = &items;
+
+    let lib = lib_name();
+    let package = lib.replace('_', "");
+    let ctx = Context {
+        c_prefix: std::env::var("EXPOSE_C_PREFIX").unwrap_or_default(),
+        classes,
+        errors: RefCell::new(BTreeSet::new()),
+    };
+
+    let mut items = vec![];
+    for class in classes {
+        items.push(match class.opaque {
+            true => render_class(&ctx, class),
+            false if class.fields.iter().all(|(_, ty)| primitive(ty).is_some()) => {
+                render_plain(&ctx, class)
+            }
+            false => format!(
+                "// `{}` is not available: unsupported field types\n",
+                class.ident
+            ),
+        });
+    }
+    for tr in traits {
+        items.push(render_trait(&ctx, tr, root));
+    }
+    for (path, function) in functions {
+        let namespace = ctx.namespace(path);
+        items.push(
+            render_method(&ctx, None, function, &namespace).unwrap_or_else(|| {
+                format!(
+                    "// `{}` is not available: unsupported argument or return types\n",
+                    function.name
+                )
+            }),
+        );
+    }
+
+    let mut errors = String::new();
+    for error in ctx.errors.borrow().iter() {
+        writeln!(
+            errors,
+            "// {error} is returned when the C API fails with an error code\n\
+             type {error} struct {{\n\
+             \tCode int32\n\
+             }}\n\n\
+             func (e *{error}) Error() string {{\n\
+             \treturn fmt.Sprintf(\"{error}: error code %d\", e.Code)\n\
+             }}\n",
+            error = error,
+        )
+        .unwrap();
+    }
+
+    let body = format!(
+        "{errors}// goString copies a string returned by the C API, and frees it\n\
+         func goString(s *C.char) string {{\n\
+         \tdefer C.{c}string_free(s)\n\
+         \treturn C.GoString(s)\n\
+         }}\n\n\
+         // goBytes copies a vector returned by the C API, and frees it\n\
+         func goBytes(arr C.{c}Arr_u8) []byte {{\n\
+         \tdefer C.{c}bytes_free(arr)\n\
+         \treturn C.GoBytes(unsafe.Pointer(arr.ptr), C.int(arr.len))\n\
+         }}\n\n\
+         {items}",
+        errors = errors,
+        c = ctx.c_prefix,
+        items = items.join("\n"),
+    );
+
+    let exports = traits
+        .iter()
+        .filter(|t| t.supported)
+        .flat_map(|t| t.callbacks.iter().map(|c| render_export(&ctx, t, c)).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let release = release_handle_name(&ctx, root);
+
+    // The callbacks are exported from another file, whose preamble can't contain definitions
+    let mut preamble = String::new();
+    if !exports.is_empty() {
+        writeln!(
+            preamble,
+            "\n// Handles are passed as the `this` pointer of the trait structs\n\
+             static inline void *go_handle_ptr(uintptr_t handle) {{ return (void *)handle; }}\n\n\
+             extern void {}(void *);",
+            release
+        )
+        .unwrap();
+        for (c_decl, _) in &exports {
+            writeln!(preamble, "{}", c_decl).unwrap();
+        }
+    }
+
+    let header = format!(
+        "// Code generated by `#[expose_mod]`. DO NOT EDIT.\n\n\
+         package {}\n",
+        package
+    );
+    let mut sources = vec![(
+        format!("{}_{}.go", lib, root).into(),
+        format!(
+            "{header}\n\
+             /*\n\
+             #cgo LDFLAGS: -l{lib}\n\
+             #include <stdint.h>\n\
+             #include <stdlib.h>\n\
+             #include \"{lib}.h\"\n\
+             {preamble}\
+             */\n\
+             import \"C\"\n\
+             {imports}\n\
+             {body}",
+            header = header,
+            lib = lib,
+            preamble = preamble,
+            imports = imports(&body),
+            body = body,
+        ),
+    )];
+
+    if !exports.is_empty() {
+        let body = format!(
+            "//export {release}\n\
+             func {release}(this unsafe.Pointer) {{\n\
+             \tcgo.Handle(uintptr(this)).Delete()\n\
+             }}\n\n\
+             {exports}",
+            release = release,
+            exports = exports
+                .into_iter()
+                .map(|(_, go_def)| go_def)
+                .collect::<Vec<_>>()
+                .join("\n"),
+        );
+        sources.push((
+            format!("{}_{}_callbacks.go", lib, root).into(),
+            format!(
+                "{header}\n\
+                 /*\n\
+                 #include <stdint.h>\n\
+                 #include \"{lib}.h\"\n\
+                 */\n\
+                 import \"C\"\n\
+                 {imports}\n\
+                 {body}",
+                header = header,
+                lib = lib,
+                imports = imports(&body),
+                body = body,
+            ),
+        ));
+    }
+
+    sources
+}
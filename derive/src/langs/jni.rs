@@ -0,0 +1,1560 @@
+//! Kotlin bindings over JNI
+//!
+//! Every exposed function and method gets a `Java_*` entry point next to it, that converts its
+//! arguments from their JNI types and throws a Java exception for the errors returned in a
+//! `Result`. Structs are always opaque: they are handed out as `jlong` handles, owned by a Kotlin
+//! class that implements `AutoCloseable`.
+//!
+//! The signatures are recorded while expanding and, once the root `#[expose_mod]` is done,
+//! rendered to a `Native.java` with the `native` declarations and a `<Module>.kt` with the public
+//! API for every module. They are written to `OUT_DIR/jni`, in a directory per package.
+//!
+//! Signatures that can't cross JNI, like callbacks, are skipped and listed in a comment of the
+//! generated Kotlin.
+
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::fs;
+use std::path::PathBuf;
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, ToTokens};
+use syn::punctuated::Punctuated;
+use syn::{
+    FnArg, Ident, ImplItem, ImplItemMethod, Item, ItemFn, ItemImpl, ItemMod, ItemStruct,
+    ItemTrait, Pat, PatIdent, PatType, ReturnType, Signature, Token, TraitItem, Type, TypePath,
+    TypeReference,
+};
+
+use super::*;
+use crate::types::*;
+
+#[derive(Debug)]
+pub struct Jni;
+
+impl Lang for Jni {
+    type Error = JniError;
+
+    const NAME: &'static str = "jni";
+
+    fn expose_fn(
+        function: &mut ItemFn,
+        mod_path: &Vec<Ident>,
+        extra: &mut Vec<Item>,
+    ) -> Result<Ident, Self::Error> {
+        if mod_path.is_empty() {
+            return Err(JniError::NakedItem(function.sig.ident.span()));
+        }
+
+        let ident = function.sig.ident.clone();
+        let native = camel_case(&ident.to_string(), false);
+        let callee = quote!(self::#ident);
+        match expose_sig(
+            &function.sig,
+            callee,
+            &native,
+            camel_case(&ident.to_string(), false),
+            MethodKind::Static,
+            None,
+            mod_path,
+        ) {
+            Ok(shims) => {
+                for (shim, method) in shims {
+                    extra.push(syn::parse2(shim).expect("Invalid JNI entry point"));
+                    FUNCTIONS.with(|f| f.borrow_mut().push((mod_path.clone(), method)));
+                }
+            }
+            Err(JniError::Unsupported(reason)) => skip(mod_path, None, &ident, reason),
+            Err(e) => return Err(e),
+        }
+        strip_arg_attrs(&mut function.sig);
+        import_mapping(&mut function.block);
+
+        Ok(ident)
+    }
+
+    fn expose_mod(
+        module: &mut ItemMod,
+        mod_path: &Vec<Ident>,
+        _sub_items: Vec<ModuleItem>,
+    ) -> Result<Ident, Self::Error> {
+        module.vis = parse_quote!(pub);
+
+        // Nested modules are expanded first, so everything has been recorded by now
+        if mod_path.len() == 1 {
+            let sources = render_sources();
+            write_generated("JNI", Some("jni"), &sources, module.ident.span())?;
+        }
+
+        Ok(module.ident.clone())
+    }
+
+    fn expose_struct(
+        structure: &mut ItemStruct,
+        opts: Punctuated<ExposeStructOpts, Token![,]>,
+        mod_path: &Vec<Ident>,
+        extra: &mut Vec<Item>,
+    ) -> Result<Ident, Self::Error> {
+        if mod_path.is_empty() {
+            return Err(JniError::NakedItem(structure.ident.span()));
+        }
+
+        let ident = structure.ident.clone();
+        let shared = opts.iter().any(|o| *o == ExposeStructOpts::Shared);
+        structure.vis = parse_quote!(pub);
+        with_class(&ident, |class| {
+            class.mod_path = mod_path.clone();
+            class.shared = shared;
+        });
+
+        let kind = match shared {
+            true => quote!(crate::langs::Shared),
+            false => quote!(crate::langs::Boxed),
+        };
+        let impl_exposed_ptr: ItemImpl = parse_quote! {
+            impl crate::langs::ExposedPtr for #ident {
+                type Kind = #kind;
+            }
+        };
+        extra.push(impl_exposed_ptr.into());
+
+        // Called by `close()`, `#[destructor]`s are not needed
+        let destroy = jni_symbol(mod_path, &native_name(&ident, "destroy"));
+        let mut impl_block: ItemImpl = parse_quote! {
+            impl #ident {
+                #[no_mangle]
+                #[allow(non_snake_case)]
+                pub extern "system" fn #destroy<'a>(
+                    env: ::jni::JNIEnv<'a>,
+                    _class: ::jni::objects::JClass<'a>,
+                    handle: ::jni::sys::jlong,
+                ) {
+                    crate::langs::jni_call(env, move || {
+                        unsafe { crate::langs::drop_handle::<Self>(handle) };
+                        Ok(())
+                    })
+                }
+            }
+        };
+
+        // Methods implemented by the options, taking precedence over the ones with the same name
+        let mut methods: Vec<(Signature, TokenStream2, &str, MethodKind)> = vec![];
+        if opts.iter().any(|o| *o == ExposeStructOpts::ToString) {
+            methods.push((
+                parse_quote!(fn to_string(&self) -> String),
+                quote!(Self::to_string),
+                "toString",
+                MethodKind::Instance,
+            ));
+        } else if opts.iter().any(|o| *o == ExposeStructOpts::ToDebug) {
+            methods.push((
+                parse_quote!(fn to_string(&self) -> String),
+                quote!((|this: &Self| format!("{:?}", this))),
+                "toString",
+                MethodKind::Instance,
+            ));
+        }
+        if opts.iter().any(|o| *o == ExposeStructOpts::Bytes) {
+            methods.push((
+                parse_quote!(fn to_bytes(&self) -> Vec<u8>),
+                quote!((|this: &Self| crate::common::ByteContainer::as_bytes(this).to_vec())),
+                "toBytes",
+                MethodKind::Instance,
+            ));
+            methods.push((
+                parse_quote!(fn from_bytes(bytes: Vec<u8>) -> Self),
+                quote!(<Self as crate::common::ByteContainer>::from_bytes),
+                "fromBytes",
+                MethodKind::Static,
+            ));
+        }
+        for (sig, callee, name, kind) in methods {
+            let native = native_name(&ident, &sig.ident.to_string());
+            let shims = expose_sig(&sig, callee, &native, name.into(), kind, Some(&ident), mod_path)?;
+            add_shims(&mut impl_block, &ident, shims);
+        }
+        extra.push(impl_block.into());
+
+        // Transparent structs are behind a handle too, only their fields are stored inline
+        let is_opaque = opts.iter().any(|o| *o == ExposeStructOpts::Opaque);
+        let impl_block = Self::generate_getters_setters(structure, is_opaque, mod_path)?;
+        extra.push(impl_block.into());
+
+        Ok(ident)
+    }
+
+    fn expose_impl(
+        implementation: &mut ItemImpl,
+        mod_path: &Vec<Ident>,
+    ) -> Result<(), Self::Error> {
+        expose_impl_renamed(implementation, mod_path, &[])
+    }
+
+    fn expose_trait(
+        tr: &mut ItemTrait,
+        mod_path: &Vec<Ident>,
+        _extra: &mut Vec<Item>,
+    ) -> Result<Ident, Self::Error> {
+        for item in &mut tr.items {
+            if let TraitItem::Method(method) = item {
+                method.attrs.retain(|a| !a.path.is_ident("expose_trait"));
+            }
+        }
+        skip(
+            mod_path,
+            None,
+            &tr.ident,
+            "traits can't be implemented from Kotlin yet".into(),
+        );
+
+        Ok(tr.ident.clone())
+    }
+
+    fn expose_operators(
+        self_ty: &Type,
+        operators: Vec<ExposedOperator>,
+        mod_path: &Vec<Ident>,
+        extra: &mut Vec<Item>,
+    ) -> Result<(), Self::Error> {
+        let mut impl_block: ItemImpl = parse_quote! {
+            impl #self_ty {}
+        };
+        let mut names = vec![];
+        for operator in operators {
+            impl_block.items.push(operator.wrapper(false).into());
+
+            let name = match operator.op {
+                Operator::Add => "plus",
+                Operator::Sub => "minus",
+                Operator::Mul => "times",
+                Operator::Neg => "unaryMinus",
+                Operator::Index => "get",
+            };
+            names.push((operator.op.method(), name));
+        }
+
+        expose_impl_renamed(&mut impl_block, mod_path, &names)?;
+        extra.push(impl_block.into());
+
+        Ok(())
+    }
+
+    // Handles own their value, so nested structs are cloned out of their container
+    fn expose_getter(
+        field: &mut Field,
+        _is_opaque: bool,
+        impl_block: &mut ItemImpl,
+    ) -> Result<(), Self::Error> {
+        let field_ty = &field.ty;
+        let field_ident = field.ident.as_ref().expect("Missing field ident");
+        let getter_name = format_ident!("get_{}", field_ident);
+
+        let getter: ImplItemMethod = if primitive(field_ty).is_some() {
+            parse_quote! {
+                #[getter]
+                fn #getter_name(&self) -> #field_ty {
+                    self.#field_ident
+                }
+            }
+        } else {
+            parse_quote! {
+                #[getter]
+                fn #getter_name(&self) -> #field_ty {
+                    use crate::common::AccessContainer;
+                    self.#field_ident.access_container(|value| value.clone())
+                }
+            }
+        };
+        impl_block.items.push(getter.into());
+
+        Ok(())
+    }
+
+    fn expose_inline_getter(field: &mut Field, impl_block: &mut ItemImpl) -> Result<(), Self::Error> {
+        let field_ty = &field.ty;
+        let field_ident = field.ident.as_ref().expect("Missing field ident");
+        let getter_name = format_ident!("get_{}", field_ident);
+
+        let getter: ImplItemMethod = if primitive(field_ty).is_some() {
+            parse_quote! {
+                #[getter]
+                fn #getter_name(&self) -> #field_ty {
+                    self.#field_ident
+                }
+            }
+        } else {
+            parse_quote! {
+                #[getter]
+                fn #getter_name(&self) -> #field_ty {
+                    self.#field_ident.clone()
+                }
+            }
+        };
+        impl_block.items.push(getter.into());
+
+        Ok(())
+    }
+
+    fn expose_inline_setter(
+        field: &mut Field,
+        validate: Option<&Path>,
+        impl_block: &mut ItemImpl,
+    ) -> Result<(), Self::Error> {
+        let field_ty = &field.ty;
+        let field_ident = field.ident.as_ref().expect("Missing field ident");
+        let setter_name = format_ident!("set_{}", field_ident);
+
+        let setter: ImplItemMethod = match validate {
+            None => parse_quote! {
+                #[setter]
+                fn #setter_name(&mut self, #field_ident: #field_ty) {
+                    self.#field_ident = #field_ident;
+                }
+            },
+            Some(validate) => parse_quote! {
+                #[setter]
+                fn #setter_name(&mut self, #field_ident: #field_ty) -> Result<(), crate::common::ValidationError> {
+                    #validate(self, &#field_ident).map_err(crate::common::ValidationError::from_display)?;
+                    self.#field_ident = #field_ident;
+
+                    Ok(())
+                }
+            },
+        };
+        impl_block.items.push(setter.into());
+
+        Ok(())
+    }
+
+    fn convert_getter_setter_ty(ty: Type) -> Result<(Type, Type), Self::Error> {
+        Ok((ty.clone(), ty))
+    }
+
+    // The entry points convert their arguments themselves, so only the custom mappings apply here
+    fn convert_input(ty: Type) -> Result<Input, Self::Error> {
+        match Self::custom_mapping(&ty) {
+            Some(target) => Ok(Input::new_map_from(ty, vec![target])),
+            None => Ok(Input::new_unchanged(ty)),
+        }
+    }
+
+    fn convert_output(output: Type) -> Result<Output, Self::Error> {
+        match Self::custom_mapping(&output) {
+            Some(target) => Ok(Output::new_map_to_single(output, target)),
+            None => Ok(Output::new_unchanged(output)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Primitive {
+    /// Type in `jni::sys`
+    jni: &'static str,
+    java: &'static str,
+    kotlin: &'static str,
+}
+
+impl Primitive {
+    /// Kotlin's unsigned types are passed to Java as the signed type of the same size
+    fn unsigned(&self) -> bool {
+        self.kotlin.starts_with('U')
+    }
+}
+
+fn primitive(ty: &Type) -> Option<Primitive> {
+    let ident = match ty {
+        Type::Path(TypePath { path, .. }) => path.get_ident()?.to_string(),
+        _ => return None,
+    };
+
+    let (jni, java, kotlin) = match ident.as_str() {
+        "bool" => ("jboolean", "boolean", "Boolean"),
+        "i8" => ("jbyte", "byte", "Byte"),
+        "u8" => ("jbyte", "byte", "UByte"),
+        "i16" => ("jshort", "short", "Short"),
+        "u16" => ("jshort", "short", "UShort"),
+        "i32" => ("jint", "int", "Int"),
+        "u32" => ("jint", "int", "UInt"),
+        "i64" | "isize" | "usize" => ("jlong", "long", "Long"),
+        "u64" => ("jlong", "long", "ULong"),
+        "f32" => ("jfloat", "float", "Float"),
+        "f64" => ("jdouble", "double", "Double"),
+        _ => return None,
+    };
+
+    Some(Primitive { jni, java, kotlin })
+}
+
+/// How a Rust type crosses JNI
+#[derive(Debug, Clone, PartialEq)]
+enum JniType {
+    Unit,
+    Primitive(Primitive),
+    String,
+    /// Only as a return value, `null` for `None`
+    OptionalString,
+    /// `Vec<u8>` or `&[u8]`, as a `ByteArray`
+    Bytes,
+    /// Exposed struct, as a handle
+    Class(Ident),
+    /// Only as a return value, `0` for `None`
+    OptionalClass(Ident),
+}
+
+/// How an argument is passed to the Rust function
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Pass {
+    Owned,
+    Ref,
+    RefMut,
+}
+
+/// Exception thrown for the error of a `Result`
+#[derive(Debug, Clone, PartialEq)]
+enum Throws {
+    /// Generated for an error type, in the package of the root module
+    Error(String),
+    /// From `java.lang`
+    Builtin(&'static str),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum MethodKind {
+    /// Unnamed `#[constructor]`
+    Constructor,
+    Static,
+    Instance,
+    Operator,
+    /// Getter of the field with the given name
+    Getter(String),
+    /// Setter of the field with the given name
+    Setter(String),
+}
+
+#[derive(Debug, Clone)]
+struct Method {
+    /// Name of the `native` declaration
+    native: String,
+    /// Name of the Kotlin function
+    name: String,
+    kind: MethodKind,
+    /// Takes the handle of the object as first argument
+    receiver: bool,
+    inputs: Vec<(String, JniType)>,
+    output: JniType,
+    throws: Option<Throws>,
+}
+
+#[derive(Debug)]
+struct Class {
+    mod_path: Vec<Ident>,
+    ident: Ident,
+    shared: bool,
+    methods: Vec<Method>,
+}
+
+/// Item that is not exposed: its module, the struct it belongs to and the note about it
+type Skipped = (Vec<Ident>, Option<Ident>, String);
+
+thread_local! {
+    /// Structs and their methods recorded while expanding the current root module
+    static CLASSES: RefCell<Vec<Class>> = const { RefCell::new(Vec::new()) };
+    /// Free functions recorded while expanding the current root module
+    static FUNCTIONS: RefCell<Vec<(Vec<Ident>, Method)>> = const { RefCell::new(Vec::new()) };
+    /// Items that are not exposed
+    static SKIPPED: RefCell<Vec<Skipped>> = const { RefCell::new(Vec::new()) };
+    /// Error types returned in a `Result`, that get an exception class
+    static ERRORS: RefCell<BTreeSet<String>> = const { RefCell::new(BTreeSet::new()) };
+}
+
+fn with_class<F: FnOnce(&mut Class)>(ident: &Ident, f: F) {
+    CLASSES.with(|classes| {
+        let mut classes = classes.borrow_mut();
+        let pos = match classes.iter().position(|c| &c.ident == ident) {
+            Some(pos) => pos,
+            None => {
+                classes.push(Class {
+                    mod_path: vec![],
+                    ident: ident.clone(),
+                    shared: false,
+                    methods: vec![],
+                });
+                classes.len() - 1
+            }
+        };
+
+        f(&mut classes[pos]);
+    });
+}
+
+fn skip(mod_path: &[Ident], class: Option<&Ident>, item: &Ident, reason: String) {
+    let note = format!("`{}` is not exposed: {}", item, reason);
+    SKIPPED.with(|skipped| {
+        skipped
+            .borrow_mut()
+            .push((mod_path.to_vec(), class.cloned(), note))
+    });
+}
+
+/// Name of the `native` declaration of a method: `<type>_<method>` in camel case
+fn native_name(class: &Ident, method: &str) -> String {
+    camel_case(
+        &format!("{}_{}", class.to_string().to_snake_case(), method),
+        false,
+    )
+}
+
+/// Package of a module: the base package set by the build script in `EXPOSE_JNI_PACKAGE`,
+/// followed by the module path
+fn package(mod_path: &[Ident]) -> String {
+    std::env::var("EXPOSE_JNI_PACKAGE")
+        .ok()
+        .filter(|base| !base.is_empty())
+        .into_iter()
+        .chain(mod_path.iter().map(|m| m.to_string()))
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Name of the entry point of `Native.<native>` in the package of `mod_path`, following the JNI
+/// name mangling
+fn jni_symbol(mod_path: &[Ident], native: &str) -> Ident {
+    fn mangle(s: &str) -> String {
+        let mut mangled = String::with_capacity(s.len());
+        for c in s.chars() {
+            match c {
+                '.' | '/' => mangled.push('_'),
+                '_' => mangled.push_str("_1"),
+                ';' => mangled.push_str("_2"),
+                '[' => mangled.push_str("_3"),
+                c if c.is_ascii_alphanumeric() => mangled.push(c),
+                c => {
+                    let mut buf = [0; 2];
+                    for unit in c.encode_utf16(&mut buf) {
+                        write!(mangled, "_0{:04x}", unit).unwrap();
+                    }
+                }
+            }
+        }
+
+        mangled
+    }
+
+    format_ident!(
+        "Java_{}_Native_{}",
+        mangle(&package(mod_path)),
+        mangle(native)
+    )
+}
+
+fn class_of(ty: &Type, self_ident: Option<&Ident>) -> Option<Ident> {
+    if match_fixed_type(ty, parse_quote!(Self)) {
+        return self_ident.cloned();
+    }
+    match ty {
+        Type::Path(TypePath { qself: None, path }) => {
+            let segment = path.segments.last()?;
+            if segment.arguments.is_empty() && primitive(ty).is_none() {
+                Some(segment.ident.clone())
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Classify a type, after applying the custom mappings. Any other type without generics is
+/// assumed to be an exposed struct
+fn classify(ty: &Type, self_ident: Option<&Ident>) -> Option<(JniType, Option<Type>)> {
+    if let Some(target) = Jni::custom_mapping(ty) {
+        let (mapped, _) = classify(&target, self_ident)?;
+        return match mapped {
+            JniType::Primitive(_) | JniType::String | JniType::Bytes => Some((mapped, Some(target))),
+            _ => None,
+        };
+    }
+
+    let ty = match ty {
+        Type::Tuple(tuple) if tuple.elems.is_empty() => JniType::Unit,
+        ty if primitive(ty).is_some() => JniType::Primitive(primitive(ty).unwrap()),
+        ty if match_fixed_type(ty, parse_quote!(String)) => JniType::String,
+        ty if ty == &parse_quote!(Vec<u8>) => JniType::Bytes,
+        ty => {
+            if let Some(inner) = match_generic_type(ty, parse_quote!(Option)) {
+                match classify(&inner[0], self_ident)? {
+                    (JniType::String, None) => JniType::OptionalString,
+                    (JniType::Class(class), None) => JniType::OptionalClass(class),
+                    _ => return None,
+                }
+            } else {
+                JniType::Class(class_of(ty, self_ident)?)
+            }
+        }
+    };
+
+    Some((ty, None))
+}
+
+fn classify_input(ty: &Type, self_ident: Option<&Ident>) -> Option<(JniType, Pass, Option<Type>)> {
+    match ty {
+        Type::Reference(TypeReference {
+            elem, mutability, ..
+        }) => {
+            let pass = match mutability {
+                Some(_) => Pass::RefMut,
+                None => Pass::Ref,
+            };
+            let jni_ty = if elem.as_ref() == &parse_quote!(str) {
+                JniType::String
+            } else if elem.as_ref() == &parse_quote!([u8]) {
+                JniType::Bytes
+            } else {
+                match classify(elem, self_ident)? {
+                    (JniType::Unit, _) | (JniType::OptionalString, _) | (JniType::OptionalClass(_), _) => {
+                        return None
+                    }
+                    (_, Some(_)) => return None,
+                    (jni_ty, None) => jni_ty,
+                }
+            };
+
+            Some((jni_ty, pass, None))
+        }
+        ty => match classify(ty, self_ident)? {
+            (JniType::Unit, _) | (JniType::OptionalString, _) | (JniType::OptionalClass(_), _) => {
+                None
+            }
+            (jni_ty, mapping) => Some((jni_ty, Pass::Owned, mapping)),
+        },
+    }
+}
+
+/// Classify the return type, with the error type for `Result`s
+fn classify_output(
+    output: &ReturnType,
+    self_ident: Option<&Ident>,
+) -> Option<(JniType, Option<Type>, Option<Type>)> {
+    let ty = match output {
+        ReturnType::Default => return Some((JniType::Unit, None, None)),
+        ReturnType::Type(_, ty) => ty.as_ref(),
+    };
+
+    if let Some(inner) = match_generic_type(ty, parse_quote!(Result)) {
+        if inner.len() != 2 {
+            return None;
+        }
+        let (ok, mapping) = classify(&inner[0], self_ident)?;
+        return Some((ok, mapping, Some(inner[1].clone())));
+    }
+    if ty == &parse_quote!(&[u8]) {
+        return Some((JniType::Bytes, None, None));
+    }
+
+    let (ty, mapping) = classify(ty, self_ident)?;
+    Some((ty, mapping, None))
+}
+
+impl JniType {
+    fn jni_type(&self, input: bool) -> TokenStream2 {
+        match self {
+            JniType::Unit => quote!(()),
+            JniType::Primitive(p) => {
+                let ident = format_ident!("{}", p.jni);
+                quote!(::jni::sys::#ident)
+            }
+            JniType::String if input => quote!(::jni::objects::JString<'a>),
+            JniType::String | JniType::OptionalString => quote!(::jni::sys::jstring),
+            JniType::Bytes => quote!(::jni::sys::jbyteArray),
+            JniType::Class(_) | JniType::OptionalClass(_) => quote!(::jni::sys::jlong),
+        }
+    }
+
+    fn java_type(&self) -> &'static str {
+        match self {
+            JniType::Unit => "void",
+            JniType::Primitive(p) => p.java,
+            JniType::String | JniType::OptionalString => "String",
+            JniType::Bytes => "byte[]",
+            JniType::Class(_) | JniType::OptionalClass(_) => "long",
+        }
+    }
+}
+
+fn strip_arg_attrs(sig: &mut Signature) {
+    for input in &mut sig.inputs {
+        if let FnArg::Typed(PatType { attrs, .. }) = input {
+            attrs.retain(|a| !a.path.is_ident("expose"));
+        }
+    }
+}
+
+/// The other languages wrap the bodies of the exposed functions, which makes the mapping traits
+/// available to them. Keep it that way, since the bodies are called directly here
+fn import_mapping(block: &mut syn::Block) {
+    block.stmts.insert(
+        0,
+        parse_quote! {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+        },
+    );
+}
+
+/// Build the entry points of `sig`, which call `callee`: one taking all the arguments and one
+/// without the arguments that have a default value, if any. Returns `JniError::Unsupported` if
+/// some types can't cross JNI
+fn expose_sig(
+    sig: &Signature,
+    callee: TokenStream2,
+    native: &str,
+    name: String,
+    kind: MethodKind,
+    self_ident: Option<&Ident>,
+    mod_path: &[Ident],
+) -> Result<Vec<(TokenStream2, Method)>, JniError> {
+    struct Arg {
+        ident: Ident,
+        ty: Type,
+        jni_ty: JniType,
+        pass: Pass,
+        mapping: Option<Type>,
+        default: Option<syn::Expr>,
+    }
+
+    let mut receiver = None;
+    let mut args = vec![];
+    for input in &sig.inputs {
+        match input {
+            FnArg::Receiver(r) => {
+                receiver = Some(match (&r.reference, &r.mutability) {
+                    (Some(_), Some(_)) => Pass::RefMut,
+                    (Some(_), None) => Pass::Ref,
+                    (None, _) => Pass::Owned,
+                })
+            }
+            FnArg::Typed(PatType { pat, ty, attrs, .. }) => {
+                let ident = match pat.as_ref() {
+                    Pat::Ident(PatIdent { ident, .. }) => ident.clone(),
+                    _ => return Err(LangError::ComplexPatternFnArg(pat.span()).into()),
+                };
+                let (jni_ty, pass, mapping) = classify_input(ty, self_ident).ok_or_else(|| {
+                    JniError::Unsupported(format!(
+                        "the type of `{}` can't be passed over JNI",
+                        ident
+                    ))
+                })?;
+
+                args.push(Arg {
+                    ident,
+                    ty: (**ty).clone(),
+                    jni_ty,
+                    pass,
+                    mapping,
+                    default: arg_default(attrs)?,
+                });
+            }
+        }
+    }
+    let (output, output_mapping, error) = classify_output(&sig.output, self_ident)
+        .ok_or_else(|| JniError::Unsupported("the return type can't be passed over JNI".into()))?;
+
+    let (throws, map_err) = match &error {
+        None => (None, quote!()),
+        Some(error) => {
+            let error_ident = match error {
+                Type::Path(TypePath { path, .. }) => path.segments.last().map(|s| s.ident.to_string()),
+                _ => None,
+            }
+            .ok_or_else(|| JniError::Unsupported("the error type is not a path".into()))?;
+
+            match error_ident.as_str() {
+                // Converted to the matching `java.lang` exceptions by the runtime
+                "ValidationError" => (Some(Throws::Builtin("IllegalArgumentException")), quote!(?)),
+                "IndexError" => (Some(Throws::Builtin("IndexOutOfBoundsException")), quote!(?)),
+                _ => {
+                    let class = format!(
+                        "{}/{}",
+                        package(&mod_path[..1]).replace('.', "/"),
+                        error_ident
+                    );
+                    ERRORS.with(|errors| errors.borrow_mut().insert(error_ident.clone()));
+                    (
+                        Some(Throws::Error(error_ident)),
+                        quote!(.map_err(|e| JniThrow::new(#class, format!("{:?}", e)))?),
+                    )
+                }
+            }
+        }
+    };
+
+    let output_conv = match (&output, output_mapping) {
+        (_, Some(target)) => quote! {
+            let __output: #target = MapTo::map_to(__output);
+            __output.into_jni(env)
+        },
+        (JniType::Unit, None) => quote!(Ok(__output)),
+        (JniType::Class(_), None) => quote!(Ok(into_handle(__output))),
+        (JniType::OptionalClass(_), None) => quote!(Ok(__output.map(into_handle).unwrap_or(0))),
+        (_, None) => quote!(__output.into_jni(env)),
+    };
+    let ret = match output {
+        JniType::Unit => quote!(),
+        ref ty => {
+            let ty = ty.jni_type(false);
+            quote!(-> #ty)
+        }
+    };
+
+    let has_defaults = args.iter().any(|a| a.default.is_some());
+    let mut shims = vec![];
+    for with_defaults in std::iter::once(false).chain(Some(true).filter(|_| has_defaults)) {
+        let native = match with_defaults {
+            true => format!("{}WithDefaults", native),
+            false => native.to_string(),
+        };
+        let symbol = jni_symbol(mod_path, &native);
+
+        let mut params = vec![];
+        let mut conv = TokenStream2::new();
+        let mut call_args = vec![];
+        let mut inputs = vec![];
+
+        if let Some(pass) = receiver {
+            params.push(quote!(this: ::jni::sys::jlong));
+            conv.extend(match pass {
+                Pass::Owned => quote!(let this: Self = unsafe { borrow_handle::<Self>(this)? }.clone();),
+                Pass::Ref => quote!(let this = unsafe { borrow_handle::<Self>(this)? };),
+                Pass::RefMut => quote!(let this = unsafe { borrow_handle_mut::<Self>(this)? };),
+            });
+            call_args.push(quote!(this));
+        }
+
+        for arg in &args {
+            let Arg {
+                ident,
+                ty,
+                jni_ty,
+                pass,
+                mapping,
+                default,
+            } = arg;
+            call_args.push(quote!(#ident));
+
+            if let (true, Some(default)) = (with_defaults, default) {
+                let borrow = match pass {
+                    Pass::Owned => quote!(),
+                    Pass::Ref => quote!(&),
+                    Pass::RefMut => quote!(&mut),
+                };
+                conv.extend(quote! { let #ident: #ty = #borrow #default; });
+                continue;
+            }
+
+            let param_ty = jni_ty.jni_type(true);
+            params.push(quote!(#ident: #param_ty));
+            inputs.push((camel_case(&ident.to_string(), false), jni_ty.clone()));
+
+            conv.extend(match (jni_ty, pass, ty) {
+                (JniType::Class(_), Pass::Owned, ty) => {
+                    quote! { let #ident: #ty = unsafe { borrow_handle::<#ty>(#ident)? }.clone(); }
+                }
+                (JniType::Class(_), Pass::Ref, Type::Reference(TypeReference { elem, .. })) => {
+                    quote! { let #ident = unsafe { borrow_handle::<#elem>(#ident)? }; }
+                }
+                (JniType::Class(_), _, Type::Reference(TypeReference { elem, .. })) => {
+                    quote! { let #ident = unsafe { borrow_handle_mut::<#elem>(#ident)? }; }
+                }
+                (_, Pass::Owned, ty) => match mapping {
+                    Some(target) => quote! {
+                        let #ident: #target = FromJni::from_jni(env, #ident)?;
+                        let #ident: #ty = MapFrom::map_from(#ident);
+                    },
+                    None => quote! { let #ident: #ty = FromJni::from_jni(env, #ident)?; },
+                },
+                // Convert to an owned value first, which is then borrowed
+                (jni_ty, pass, _) => {
+                    let owned: Type = match jni_ty {
+                        JniType::String => parse_quote!(String),
+                        JniType::Bytes => parse_quote!(Vec<u8>),
+                        _ => match ty {
+                            Type::Reference(TypeReference { elem, .. }) => (**elem).clone(),
+                            ty => ty.clone(),
+                        },
+                    };
+                    match pass {
+                        Pass::RefMut => quote! {
+                            let mut #ident: #owned = FromJni::from_jni(env, #ident)?;
+                            let #ident = &mut #ident;
+                        },
+                        _ => quote! {
+                            let #ident: #owned = FromJni::from_jni(env, #ident)?;
+                            let #ident = &#ident;
+                        },
+                    }
+                }
+            });
+        }
+
+        let shim = quote! {
+            #[no_mangle]
+            #[allow(non_snake_case)]
+            pub extern "system" fn #symbol<'a>(
+                env: ::jni::JNIEnv<'a>,
+                _class: ::jni::objects::JClass<'a>,
+                #(#params),*
+            ) #ret {
+                #[allow(unused_imports)]
+                use crate::mapping::{MapFrom, MapTo};
+                use crate::langs::*;
+
+                jni_call(env, move || {
+                    #conv
+                    let __output = #callee(#(#call_args),*)#map_err;
+                    #output_conv
+                })
+            }
+        };
+
+        shims.push((
+            shim,
+            Method {
+                native,
+                name: name.clone(),
+                kind: kind.clone(),
+                receiver: receiver.is_some(),
+                inputs,
+                output: output.clone(),
+                throws: throws.clone(),
+            },
+        ));
+    }
+
+    Ok(shims)
+}
+
+/// Add the entry points to an `impl` block of `class`, recording their methods. Methods with
+/// the same `native` name as one that's already recorded are skipped
+fn add_shims(impl_block: &mut ItemImpl, class: &Ident, shims: Vec<(TokenStream2, Method)>) {
+    with_class(class, |class| {
+        for (shim, method) in shims {
+            if class.methods.iter().any(|m| m.native == method.native) {
+                continue;
+            }
+
+            let shim: ImplItemMethod = syn::parse2(shim).expect("Invalid JNI entry point");
+            impl_block.items.push(shim.into());
+            class.methods.push(method);
+        }
+    });
+}
+
+/// Add the entry points of the methods of an `impl` block. `operators` gives the Kotlin operator
+/// implemented by a method, if any
+fn expose_impl_renamed(
+    implementation: &mut ItemImpl,
+    mod_path: &[Ident],
+    operators: &[(Ident, &str)],
+) -> Result<(), JniError> {
+    if mod_path.is_empty() {
+        return Err(JniError::NakedItem(implementation.self_ty.span()));
+    }
+    let class_ident = match implementation.self_ty.as_ref() {
+        Type::Path(TypePath { path, .. }) => match path.segments.last() {
+            Some(segment) => segment.ident.clone(),
+            None => return Ok(()),
+        },
+        _ => return Ok(()),
+    };
+
+    // Objects are freed by `close()`
+    implementation.items.retain(|item| match item {
+        ImplItem::Method(ImplItemMethod { attrs, .. }) => {
+            !attrs.iter().any(|a| a.path.is_ident("destructor"))
+        }
+        _ => true,
+    });
+
+    let mut shims = vec![];
+    let mut has_default_constructor = false;
+    for item in &mut implementation.items {
+        let ImplItemMethod {
+            attrs, sig, block, ..
+        } = match item {
+            ImplItem::Method(method) => method,
+            _ => continue,
+        };
+        let method_ident = sig.ident.clone();
+        let method_name = method_ident.to_string();
+
+        let getter = attrs.iter().position(|a| a.path.is_ident("getter"));
+        let setter = attrs.iter().position(|a| a.path.is_ident("setter"));
+        attrs.retain(|a| !a.path.is_ident("getter") && !a.path.is_ident("setter"));
+
+        let (native, name, kind) = match take_constructor(attrs, sig)? {
+            Some(ConstructorOpts { name: Some(name) }) => (
+                native_name(&class_ident, &format!("new_{}", name)),
+                camel_case(&name.to_string(), false),
+                MethodKind::Static,
+            ),
+            Some(ConstructorOpts { name: None }) => {
+                if std::mem::replace(&mut has_default_constructor, true) {
+                    return Err(LangError::MultipleDefaultConstructors(sig.ident.span()).into());
+                }
+                (
+                    native_name(&class_ident, "new"),
+                    class_ident.to_string(),
+                    MethodKind::Constructor,
+                )
+            }
+            None => {
+                let native = native_name(&class_ident, &method_name);
+                let field = |prefix: &str| method_name.trim_start_matches(prefix).to_string();
+                let (name, kind) = match (getter, setter, sig.inputs.first()) {
+                    (Some(_), _, _) => (String::new(), MethodKind::Getter(field("get_"))),
+                    (_, Some(_), _) => (String::new(), MethodKind::Setter(field("set_"))),
+                    (_, _, Some(FnArg::Receiver(_))) => {
+                        match operators.iter().find(|(op, _)| op == &method_ident) {
+                            Some((_, name)) => (name.to_string(), MethodKind::Operator),
+                            None => (camel_case(&method_name, false), MethodKind::Instance),
+                        }
+                    }
+                    _ => (camel_case(&method_name, false), MethodKind::Static),
+                };
+                (native, name, kind)
+            }
+        };
+
+        let callee = quote!(Self::#method_ident);
+        match expose_sig(sig, callee, &native, name, kind, Some(&class_ident), mod_path) {
+            Ok(method_shims) => shims.extend(method_shims),
+            Err(JniError::Unsupported(reason)) => {
+                skip(mod_path, Some(&class_ident), &method_ident, reason)
+            }
+            Err(e) => return Err(e),
+        }
+        strip_arg_attrs(sig);
+        import_mapping(block);
+    }
+
+    add_shims(implementation, &class_ident, shims);
+    with_class(&class_ident, |class| {
+        if class.mod_path.is_empty() {
+            class.mod_path = mod_path.to_vec();
+        }
+    });
+
+    Ok(())
+}
+
+const KOTLIN_KEYWORDS: &[&str] = &[
+    "as", "break", "class", "continue", "do", "else", "false", "for", "fun", "if", "in",
+    "interface", "is", "null", "object", "package", "return", "super", "this", "throw", "true",
+    "try", "typealias", "typeof", "val", "var", "when", "while",
+];
+
+const JAVA_KEYWORDS: &[&str] = &[
+    "abstract", "assert", "boolean", "break", "byte", "case", "catch", "char", "class", "const",
+    "continue", "default", "do", "double", "else", "enum", "extends", "false", "final", "finally",
+    "float", "for", "goto", "if", "implements", "import", "instanceof", "int", "interface", "long",
+    "native", "new", "null", "package", "private", "protected", "public", "return", "short",
+    "static", "strictfp", "super", "switch", "synchronized", "this", "throw", "throws",
+    "transient", "true", "try", "void", "volatile", "while",
+];
+
+/// Kotlin keywords are escaped with backticks
+fn kotlin_ident(ident: &str) -> String {
+    match KOTLIN_KEYWORDS.contains(&ident) {
+        true => format!("`{}`", ident),
+        false => ident.to_string(),
+    }
+}
+
+/// Java keywords get a trailing underscore
+fn java_ident(ident: &str) -> String {
+    match JAVA_KEYWORDS.contains(&ident) {
+        true => format!("{}_", ident),
+        false => ident.to_string(),
+    }
+}
+
+struct Context<'a> {
+    classes: &'a [Class],
+    root_package: String,
+}
+
+impl<'a> Context<'a> {
+    /// Name of a class as seen from the package of `from`. `None` if it's not exposed
+    fn class_name(&self, ident: &Ident, from: &[Ident]) -> Option<String> {
+        let class = self.classes.iter().find(|c| &c.ident == ident)?;
+        Some(match class.mod_path == from {
+            true => class.ident.to_string(),
+            false => format!("{}.{}", package(&class.mod_path), class.ident),
+        })
+    }
+
+    fn kotlin_type(&self, ty: &JniType, from: &[Ident]) -> Option<String> {
+        Some(match ty {
+            JniType::Unit => "Unit".into(),
+            JniType::Primitive(p) => p.kotlin.into(),
+            JniType::String => "String".into(),
+            JniType::OptionalString => "String?".into(),
+            JniType::Bytes => "ByteArray".into(),
+            JniType::Class(ident) => self.class_name(ident, from)?,
+            JniType::OptionalClass(ident) => format!("{}?", self.class_name(ident, from)?),
+        })
+    }
+
+    fn throws_name(&self, throws: &Throws, from: &[Ident]) -> String {
+        match throws {
+            Throws::Builtin(name) => name.to_string(),
+            Throws::Error(name) if package(from) == self.root_package => name.clone(),
+            Throws::Error(name) => format!("{}.{}", self.root_package, name),
+        }
+    }
+
+    /// Kotlin parameters of a method, with the arguments of its `native` call
+    fn params(&self, method: &Method, from: &[Ident]) -> Option<(Vec<String>, Vec<String>)> {
+        let mut params = vec![];
+        let mut args = vec![];
+        if method.receiver {
+            args.push("handle".to_string());
+        }
+        for (name, ty) in &method.inputs {
+            let name = kotlin_ident(name);
+            params.push(format!("{}: {}", name, self.kotlin_type(ty, from)?));
+            args.push(match ty {
+                JniType::Primitive(p) if p.unsigned() => format!("{}.to{}()", name, &p.kotlin[1..]),
+                JniType::Class(_) => format!("{}.handle", name),
+                _ => name,
+            });
+        }
+
+        Some((params, args))
+    }
+
+    /// Kotlin expression converting the value returned by `call`
+    fn convert_output(&self, ty: &JniType, call: String, from: &[Ident]) -> Option<String> {
+        Some(match ty {
+            JniType::Primitive(p) if p.unsigned() => format!("{}.to{}()", call, p.kotlin),
+            JniType::Class(ident) => format!("{}({})", self.class_name(ident, from)?, call),
+            JniType::OptionalClass(ident) => format!(
+                "{}.takeIf {{ it != 0L }}?.let {{ {}(it) }}",
+                call,
+                self.class_name(ident, from)?
+            ),
+            _ => call,
+        })
+    }
+}
+
+/// Declaration of the `native` method in `Native.java`
+fn render_native(method: &Method) -> String {
+    let mut params = vec![];
+    if method.receiver {
+        params.push("long handle".to_string());
+    }
+    for (name, ty) in &method.inputs {
+        params.push(format!("{} {}", ty.java_type(), java_ident(name)));
+    }
+
+    format!(
+        "    static native {} {}({});\n",
+        method.output.java_type(),
+        method.native,
+        params.join(", ")
+    )
+}
+
+/// Render a method of a class or a top-level function, with the given indentation. `None` if it
+/// uses a struct that's not exposed
+fn render_method(ctx: &Context, method: &Method, from: &[Ident], indent: &str) -> Option<String> {
+    let (params, args) = ctx.params(method, from)?;
+    let call = format!("Native.{}({})", method.native, args.join(", "));
+    let body = ctx.convert_output(&method.output, call, from)?;
+
+    let mut out = String::new();
+    if let Some(throws) = &method.throws {
+        writeln!(out, "{}@Throws({}::class)", indent, ctx.throws_name(throws, from)).unwrap();
+    }
+
+    let modifier = match method.kind {
+        MethodKind::Operator => "operator ",
+        MethodKind::Instance
+            if method.name == "toString"
+                && method.inputs.is_empty()
+                && method.output == JniType::String =>
+        {
+            "override "
+        }
+        _ => "",
+    };
+    let name = kotlin_ident(&method.name);
+    match &method.output {
+        JniType::Unit => writeln!(
+            out,
+            "{}{}fun {}({}) {{\n{}    {}\n{}}}",
+            indent,
+            modifier,
+            name,
+            params.join(", "),
+            indent,
+            body,
+            indent
+        ),
+        ty => writeln!(
+            out,
+            "{}{}fun {}({}): {} = {}",
+            indent,
+            modifier,
+            name,
+            params.join(", "),
+            ctx.kotlin_type(ty, from)?,
+            body
+        ),
+    }
+    .unwrap();
+
+    Some(out)
+}
+
+fn render_class(ctx: &Context, class: &Class, skipped: &[String], out: &mut String) {
+    let from = &class.mod_path;
+    let ident = &class.ident;
+    let mut body = String::new();
+
+    for note in skipped {
+        writeln!(body, "    // {}", note).unwrap();
+    }
+    if !skipped.is_empty() {
+        body.push('\n');
+    }
+
+    writeln!(
+        body,
+        "    internal var handle: Long = handle\n        private set\n"
+    )
+    .unwrap();
+
+    let unsupported = |method: &Method| format!("    // `{}` uses a struct that is not exposed\n\n", method.native);
+    let mut companion = String::new();
+    for method in &class.methods {
+        match &method.kind {
+            MethodKind::Constructor => {
+                let (params, args) = match ctx.params(method, from) {
+                    Some(params) => params,
+                    None => {
+                        body.push_str(&unsupported(method));
+                        continue;
+                    }
+                };
+                let in_place = matches!(&method.output, JniType::Class(_))
+                    // It would clash with the constructor that takes the handle
+                    && params.iter().map(|p| p.rsplit(": ").next()).ne(Some(Some("Long")));
+                if !in_place {
+                    let method = Method {
+                        name: "create".into(),
+                        ..method.clone()
+                    };
+                    match render_method(ctx, &method, from, "        ") {
+                        Some(m) => writeln!(companion, "        @JvmStatic\n{}", m).unwrap(),
+                        None => body.push_str(&unsupported(&method)),
+                    }
+                    continue;
+                }
+
+                if let Some(throws) = &method.throws {
+                    writeln!(body, "    @Throws({}::class)", ctx.throws_name(throws, from)).unwrap();
+                }
+                writeln!(
+                    body,
+                    "    constructor({}) : this(Native.{}({}))\n",
+                    params.join(", "),
+                    method.native,
+                    args.join(", ")
+                )
+                .unwrap();
+            }
+            MethodKind::Static => match render_method(ctx, method, from, "        ") {
+                Some(m) => writeln!(companion, "        @JvmStatic\n{}", m).unwrap(),
+                None => body.push_str(&unsupported(method)),
+            },
+            MethodKind::Instance | MethodKind::Operator => {
+                match render_method(ctx, method, from, "    ") {
+                    Some(m) => writeln!(body, "{}", m).unwrap(),
+                    None => body.push_str(&unsupported(method)),
+                }
+            }
+            MethodKind::Getter(field) => {
+                let setter = class
+                    .methods
+                    .iter()
+                    .find(|m| m.kind == MethodKind::Setter(field.clone()));
+                let ty = ctx.kotlin_type(&method.output, from);
+                let get = ctx.convert_output(
+                    &method.output,
+                    format!("Native.{}(handle)", method.native),
+                    from,
+                );
+                let (ty, get) = match (ty, get) {
+                    (Some(ty), Some(get)) => (ty, get),
+                    _ => {
+                        body.push_str(&unsupported(method));
+                        continue;
+                    }
+                };
+
+                let name = kotlin_ident(&camel_case(field, false));
+                // The argument of the setter is always called `value` in Kotlin
+                let setter = setter.map(|s| Method {
+                    inputs: vec![("value".into(), s.inputs[0].1.clone())],
+                    ..s.clone()
+                });
+                match setter.and_then(|s| Some((ctx.params(&s, from)?, s))) {
+                    Some(((_, args), setter)) => {
+                        if let Some(throws) = &setter.throws {
+                            writeln!(body, "    @set:Throws({}::class)", ctx.throws_name(throws, from))
+                                .unwrap();
+                        }
+                        writeln!(
+                            body,
+                            "    var {}: {}\n        get() = {}\n        set(value) = Native.{}({})\n",
+                            name,
+                            ty,
+                            get,
+                            setter.native,
+                            args.join(", ")
+                        )
+                        .unwrap();
+                    }
+                    None => writeln!(body, "    val {}: {}\n        get() = {}\n", name, ty, get).unwrap(),
+                }
+            }
+            // Rendered as a function when there's no getter
+            MethodKind::Setter(field) => {
+                let has_getter = class
+                    .methods
+                    .iter()
+                    .any(|m| m.kind == MethodKind::Getter(field.clone()));
+                if has_getter {
+                    continue;
+                }
+
+                let method = Method {
+                    name: format!("set_{}", field),
+                    kind: MethodKind::Instance,
+                    ..method.clone()
+                };
+                let method = Method {
+                    name: camel_case(&method.name, false),
+                    ..method
+                };
+                match render_method(ctx, &method, from, "    ") {
+                    Some(m) => writeln!(body, "{}", m).unwrap(),
+                    None => body.push_str(&unsupported(&method)),
+                }
+            }
+        }
+    }
+
+    writeln!(
+        body,
+        "    override fun close() {{\n        if (handle != 0L) {{\n            Native.{}(handle)\n            handle = 0L\n        }}\n    }}",
+        native_name(ident, "destroy")
+    )
+    .unwrap();
+    if !companion.is_empty() {
+        write!(
+            body,
+            "\n    companion object {{\n{}    }}\n",
+            companion.trim_end_matches('\n').to_string() + "\n"
+        )
+        .unwrap();
+    }
+
+    let doc = match class.shared {
+        true => format!(
+            "/**\n * Handle to a reference-counted `{}`, released by [close]\n */",
+            ident
+        ),
+        false => format!("/**\n * Handle to an owned `{}`, freed by [close]\n */", ident),
+    };
+    writeln!(
+        out,
+        "{}\nclass {} internal constructor(handle: Long) : AutoCloseable {{\n{}}}\n",
+        doc, ident, body
+    )
+    .unwrap();
+}
+
+/// Render `Native.java` and `<Module>.kt` for every module, as paths relative to the source
+/// directory. Clears everything that was recorded
+fn render_sources() -> Vec<(PathBuf, String)> {
+    let classes = CLASSES.with(|classes| classes.replace(Vec::new()));
+    let functions = FUNCTIONS.with(|functions| functions.replace(Vec::new()));
+    let skipped = SKIPPED.with(|skipped| skipped.replace(Vec::new()));
+    let errors = ERRORS.with(|errors| errors.replace(BTreeSet::new()));
+
+    // Every module that has something to expose, in the order they are declared
+    let mut modules: Vec<Vec<Ident>> = vec![];
+    let all_paths = classes
+        .iter()
+        .map(|c| &c.mod_path)
+        .chain(functions.iter().map(|(path, _)| path))
+        .chain(skipped.iter().map(|(path, _, _)| path));
+    for path in all_paths {
+        if !modules.contains(path) {
+            modules.push(path.clone());
+        }
+    }
+    modules.sort_by_key(|path| path.iter().map(|i| i.to_string()).collect::<Vec<_>>());
+    let root = match modules.first() {
+        Some(path) => path[..1].to_vec(),
+        None => return vec![],
+    };
+    if !modules.contains(&root) && !errors.is_empty() {
+        modules.insert(0, root.clone());
+    }
+
+    let ctx = Context {
+        classes: &classes,
+        root_package: package(&root),
+    };
+    let lib = std::env::var("CARGO_PKG_NAME")
+        .unwrap_or_default()
+        .replace('-', "_");
+
+    let mut sources = vec![];
+    for module in &modules {
+        let package = package(module);
+        let dir = package.split('.').collect::<PathBuf>();
+        let module_classes = classes.iter().filter(|c| &c.mod_path == module);
+        let module_functions = functions
+            .iter()
+            .filter(|(path, _)| path == module)
+            .map(|(_, f)| f);
+
+        let mut natives = String::new();
+        for class in module_classes.clone() {
+            for method in &class.methods {
+                natives.push_str(&render_native(method));
+            }
+            writeln!(
+                natives,
+                "    static native void {}(long handle);",
+                native_name(&class.ident, "destroy")
+            )
+            .unwrap();
+        }
+        for function in module_functions.clone() {
+            natives.push_str(&render_native(function));
+        }
+        // Modules with nothing but skipped items don't have any native method
+        if !natives.is_empty() {
+            let java = format!(
+                "// Generated by `#[expose_mod]`, do not edit\n\
+                 package {package};\n\n\
+                 final class Native {{\n    \
+                 static {{\n        System.loadLibrary(\"{lib}\");\n    }}\n\n    \
+                 private Native() {{}}\n\n\
+                 {natives}}}\n",
+                package = package,
+                lib = lib,
+                natives = natives,
+            );
+            sources.push((dir.join("Native.java"), java));
+        }
+
+        let mut kotlin = format!(
+            "// Generated by `#[expose_mod]`, do not edit\npackage {}\n\n",
+            package
+        );
+        for (_, _, note) in skipped
+            .iter()
+            .filter(|(path, class, _)| path == module && class.is_none())
+        {
+            writeln!(kotlin, "// {}", note).unwrap();
+        }
+        if skipped
+            .iter()
+            .any(|(path, class, _)| path == module && class.is_none())
+        {
+            kotlin.push('\n');
+        }
+        if module == &root {
+            kotlin.push_str(
+                "/**\n * Base class of the exceptions thrown for the errors returned by Rust\n */\n\
+                 open class FfiException(message: String) : Exception(message)\n\n",
+            );
+            for error in &errors {
+                writeln!(kotlin, "class {}(message: String) : FfiException(message)\n", error).unwrap();
+            }
+        }
+        for class in module_classes {
+            let notes = skipped
+                .iter()
+                .filter(|(_, c, _)| c.as_ref() == Some(&class.ident))
+                .map(|(_, _, note)| note.clone())
+                .collect::<Vec<_>>();
+            render_class(&ctx, class, &notes, &mut kotlin);
+        }
+        for function in module_functions {
+            match render_method(&ctx, function, module, "") {
+                Some(f) => writeln!(kotlin, "{}", f).unwrap(),
+                None => writeln!(
+                    kotlin,
+                    "// `{}` uses a struct that is not exposed\n",
+                    function.native
+                )
+                .unwrap(),
+            }
+        }
+
+        let file_name = format!("{}.kt", camel_case(&module.last().unwrap().to_string(), true));
+        sources.push((dir.join(file_name), kotlin.trim_end().to_string() + "\n"));
+    }
+
+    sources
+}
+
+#[derive(Debug)]
+pub enum JniError {
+    Lang(LangError),
+
+    /// Items outside of an `#[expose_mod]` module, which don't have a package
+    NakedItem(Span),
+    /// Signature that can't cross JNI, skipped instead of failing the build
+    Unsupported(String),
+}
+
+impl fmt::Display for JniError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JniError::Lang(e) => write!(f, "{}", e),
+            JniError::NakedItem(_) => write!(
+                f,
+                "Items must be exposed as part of an `expose_mod` module with JNI"
+            ),
+            JniError::Unsupported(reason) => write!(f, "Unsupported signature: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for JniError {}
+
+impl From<JniError> for syn::Error {
+    fn from(e: JniError) -> Self {
+        match e {
+            JniError::Lang(e) => e.into(),
+            JniError::NakedItem(span) => syn::Error::new(span, e),
+            JniError::Unsupported(_) => syn::Error::new(Span::call_site(), e),
+        }
+    }
+}
+
+impl From<LangError> for JniError {
+    fn from(e: LangError) -> Self {
+        JniError::Lang(e)
+    }
+}
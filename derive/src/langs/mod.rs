@@ -1,12 +1,18 @@
+#[cfg(test)]
+use std::cell::RefCell;
 use std::collections::HashSet;
 use std::convert::TryFrom;
 use std::fmt;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
 
 use proc_macro::TokenStream;
-use proc_macro2::TokenStream as TokenStream2;
+use proc_macro2::{Span, TokenStream as TokenStream2};
 use quote::format_ident;
 use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
 use syn::token::Comma;
 use syn::{
     parse_quote, Field, Fields, FieldsNamed, FnArg, Ident, Item, ItemFn, ItemImpl, ItemMod,
@@ -21,13 +27,50 @@ pub mod common_mapping;
 
 #[cfg(feature = "c")]
 pub mod c;
+#[cfg(any(
+    feature = "cpp",
+    feature = "swift",
+    feature = "go",
+    feature = "csharp",
+    feature = "dart"
+))]
+pub mod c_family;
+#[cfg(feature = "cpp")]
+pub mod cpp;
+#[cfg(feature = "swift")]
+pub mod swift;
+#[cfg(feature = "go")]
+pub mod go;
+#[cfg(feature = "csharp")]
+pub mod csharp;
+#[cfg(feature = "dart")]
+pub mod dart;
+#[cfg(feature = "jni")]
+pub mod jni;
 #[cfg(feature = "python")]
 pub mod python;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "node")]
+pub mod napi;
+#[cfg(feature = "ruby")]
+pub mod ruby;
 
 pub trait Lang {
-    type Error: From<LangError> + std::error::Error;
+    type Error: From<LangError> + Into<syn::Error> + std::error::Error;
 
-    fn expose_fn(function: &mut ItemFn, mod_path: &Vec<Ident>) -> Result<Ident, Self::Error>;
+    /// Name of the language, used as key in `#[expose_mapping]`
+    const NAME: &'static str;
+
+    /// Whether the functions marked with `#[blocking]` are handled by the language. Otherwise the
+    /// attribute is removed and they are exposed like the others
+    const BLOCKING_CALLS: bool = false;
+
+    fn expose_fn(
+        function: &mut ItemFn,
+        mod_path: &Vec<Ident>,
+        extra: &mut Vec<Item>,
+    ) -> Result<Ident, Self::Error>;
 
     fn expose_mod(
         module: &mut ItemMod,
@@ -51,8 +94,22 @@ pub trait Lang {
         extra: &mut Vec<Item>,
     ) -> Result<Ident, Self::Error>;
 
+    /// Expose the `std::ops` implementations of `self_ty` marked with `#[expose_impl]`. All the
+    /// operators of a type are exposed together
+    fn expose_operators(
+        self_ty: &Type,
+        operators: Vec<ExposedOperator>,
+        mod_path: &Vec<Ident>,
+        extra: &mut Vec<Item>,
+    ) -> Result<(), Self::Error>;
+
     fn convert_input(ty: Type) -> Result<Input, Self::Error>;
 
+    /// Return the type declared with `#[expose_mapping]` for this language, if any
+    fn custom_mapping(ty: &Type) -> Option<Type> {
+        lookup_mapping(Self::NAME, ty)
+    }
+
     fn convert_output(output: Type) -> Result<Output, Self::Error>;
 
     // By default links directly to the `WrappedStructField` trait, but it might bave to be
@@ -64,13 +121,12 @@ pub trait Lang {
     }
 
     fn expose_getter(
-        structure: &Ident,
         field: &mut Field,
         is_opaque: bool,
         impl_block: &mut ItemImpl,
     ) -> Result<(), Self::Error> {
         if !is_opaque {
-            return Ok(());
+            return Self::expose_inline_getter(field, impl_block);
         }
 
         let field_ty = &field.ty;
@@ -90,37 +146,67 @@ pub trait Lang {
     }
 
     fn expose_setter(
-        structure: &Ident,
         field: &mut Field,
         is_opaque: bool,
+        validate: Option<&Path>,
         impl_block: &mut ItemImpl,
     ) -> Result<(), Self::Error> {
         if !is_opaque {
-            return Ok(());
+            return Self::expose_inline_setter(field, validate, impl_block);
         }
 
         let field_ty = &field.ty;
         let setter_ty = Self::convert_getter_setter_ty(field.ty.clone())?.1;
         let field_ident = field.ident.as_ref().expect("Missing field ident");
         let setter_name = format_ident!("set_{}", field_ident);
-        let setter: ImplItemMethod = parse_quote! {
-            #[setter]
-            fn #setter_name(&mut self, #field_ident: #setter_ty) {
-                use crate::common::WrappedStructField;
-                self.#field_ident = #field_ty::wrap_set(#field_ident);
-            }
+        let setter: ImplItemMethod = match validate {
+            None => parse_quote! {
+                #[setter]
+                fn #setter_name(&mut self, #field_ident: #setter_ty) {
+                    use crate::common::WrappedStructField;
+                    self.#field_ident = #field_ty::wrap_set(crate::mapping::MapFrom::map_from(#field_ident));
+                }
+            },
+            Some(validate) => parse_quote! {
+                #[setter]
+                fn #setter_name(&mut self, #field_ident: #setter_ty) -> Result<(), crate::common::ValidationError> {
+                    use crate::common::WrappedStructField;
+                    let #field_ident: #field_ty = crate::mapping::MapFrom::map_from(#field_ident);
+                    #validate(self, &#field_ident).map_err(crate::common::ValidationError::from_display)?;
+                    self.#field_ident = #field_ty::wrap_set(#field_ident);
+
+                    Ok(())
+                }
+            },
         };
         impl_block.items.push(setter.into());
 
         Ok(())
     }
 
+    /// Getter for a field stored inline in a transparent struct. Only languages that support
+    /// transparent structs have to implement this
+    fn expose_inline_getter(field: &mut Field, _impl_block: &mut ItemImpl) -> Result<(), Self::Error> {
+        Err(LangError::TransparentStructField(field.span()).into())
+    }
+
+    /// Setter for a field stored inline in a transparent struct. Only languages that support
+    /// transparent structs have to implement this
+    fn expose_inline_setter(
+        field: &mut Field,
+        _validate: Option<&Path>,
+        _impl_block: &mut ItemImpl,
+    ) -> Result<(), Self::Error> {
+        Err(LangError::TransparentStructField(field.span()).into())
+    }
+
     fn convert_fn_args<I: IntoIterator<Item = FnArg>>(
         args: I,
+        optional_defaults: bool,
     ) -> Result<(Punctuated<FnArg, Comma>, TokenStream2), Self::Error> {
         Ok(args
             .into_iter()
-            .map(|i| Argument(i).expand(Self::convert_input))
+            .map(|i| Argument(i).expand(Self::convert_input, optional_defaults))
             .collect::<Result<Vec<_>, _>>()?
             .into_iter()
             .fold(
@@ -151,6 +237,11 @@ pub trait Lang {
 
         if let Fields::Named(FieldsNamed { named, .. }) = &mut structure.fields {
             for mut field in named {
+                // The layout of transparent structs is part of the API
+                if !is_opaque {
+                    field.vis = parse_quote!(pub);
+                }
+
                 if let Some(pos) = field
                     .attrs
                     .iter()
@@ -159,37 +250,49 @@ pub trait Lang {
                     let parser = Punctuated::<ExposeStructOpts, Token![,]>::parse_terminated;
                     let parsed_attrs = field.attrs[pos]
                         .parse_args_with(parser)
-                        .map_err(LangError::ExposeTraitAttrError)?;
+                        .map_err(LangError::ExposeStructAttrError)?;
                     let parsed_attrs = parsed_attrs.into_iter().collect::<HashSet<_>>();
                     field.attrs.remove(pos);
 
+                    // Opaque structs can't be stored inline, so transparent structs hold them
+                    // like opaque structs do
+                    let wrapped = is_opaque || common_mapping::is_our_opaque_type(&field.ty);
+
                     let mut wrap_type = false;
                     if parsed_attrs.contains(&ExposeStructOpts::Get) {
                         wrap_type = true;
                         Self::expose_getter(
-                            &structure.ident,
                             &mut field,
-                            is_opaque,
+                            wrapped,
                             &mut impl_block,
                         )?;
                     }
-                    if parsed_attrs.contains(&ExposeStructOpts::Set) {
+                    let validate = parsed_attrs
+                        .iter()
+                        .find_map(|o| match o {
+                            ExposeStructOpts::ValidatedSet(path) => Some(path),
+                            _ => None,
+                        })
+                        .map(ValidateFn::path)
+                        .transpose()
+                        .map_err(LangError::ExposeStructAttrError)?;
+                    if parsed_attrs.contains(&ExposeStructOpts::Set) || validate.is_some() {
                         wrap_type = true;
                         Self::expose_setter(
-                            &structure.ident,
                             &mut field,
-                            is_opaque,
+                            wrapped,
+                            validate.as_ref(),
                             &mut impl_block,
                         )?;
                     }
 
-                    if wrap_type {
+                    if wrap_type && wrapped {
                         let field_ty = &field.ty;
                         field.ty = parse_quote!(<#field_ty as crate::common::WrappedStructField>::Store);
 
-                        field.vis = parse_quote!( pub(crate) );
-
-                        let field_ident = field.ident.as_ref().expect("Missing field ident");
+                        if is_opaque {
+                            field.vis = parse_quote!( pub(crate) );
+                        }
                     }
                 }
             }
@@ -224,27 +327,256 @@ impl<T: AsRef<str> + ?Sized> ToSnakeCase for T {
     }
 }
 
+/// `some_name` -> `someName`, or `SomeName` with `upper_first`
+#[cfg(any(
+    feature = "swift",
+    feature = "go",
+    feature = "csharp",
+    feature = "dart",
+    feature = "jni",
+    feature = "wasm",
+    feature = "node",
+    feature = "ruby"
+))]
+pub fn camel_case(s: &str, upper_first: bool) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut upper = upper_first;
+    for c in s.chars() {
+        if c == '_' {
+            upper = !result.is_empty();
+        } else if upper {
+            result.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            result.push(c);
+        }
+    }
+
+    result
+}
+
+/// Name of the crate being built, as it appears in the generated file names
+#[cfg(any(
+    feature = "cpp",
+    feature = "swift",
+    feature = "go",
+    feature = "csharp",
+    feature = "dart",
+    feature = "jni",
+    feature = "node",
+    feature = "idl"
+))]
+pub fn lib_name() -> String {
+    std::env::var("CARGO_PKG_NAME")
+        .unwrap_or_default()
+        .replace('-', "_")
+}
+
+#[cfg(all(
+    test,
+    any(
+        feature = "cpp",
+        feature = "swift",
+        feature = "go",
+        feature = "csharp",
+        feature = "dart",
+        feature = "jni",
+        feature = "node",
+        feature = "idl"
+    )
+))]
+thread_local! {
+    /// Every file written by `write_generated()`, with its `output`, compared against the
+    /// snapshots
+    pub static GENERATED: RefCell<Vec<(&'static str, PathBuf, String)>> =
+        const { RefCell::new(Vec::new()) };
+}
+
+/// Directories the generated files are written to: `OUT_DIR`, or its `out_subdir`, and the
+/// optional `<CRATE>_<output>_DIR`
+#[cfg(any(
+    feature = "cpp",
+    feature = "swift",
+    feature = "go",
+    feature = "csharp",
+    feature = "dart",
+    feature = "jni",
+    feature = "node",
+    feature = "idl"
+))]
+pub fn output_dirs(output: &str, out_subdir: Option<&str>) -> Vec<PathBuf> {
+    let out_dir = std::env::var_os("OUT_DIR").map(|dir| match out_subdir {
+        Some(subdir) => PathBuf::from(dir).join(subdir),
+        None => PathBuf::from(dir),
+    });
+    let extra_dir = std::env::var_os(format!("{}_{}_DIR", lib_name().to_uppercase(), output))
+        .map(PathBuf::from);
+
+    out_dir.into_iter().chain(extra_dir).collect()
+}
+
+/// Write the generated `files`, named relative to the `output_dirs()`. `span` is where a write
+/// error is reported
+#[cfg(any(
+    feature = "cpp",
+    feature = "swift",
+    feature = "go",
+    feature = "csharp",
+    feature = "dart",
+    feature = "jni",
+    feature = "node",
+    feature = "idl"
+))]
+pub fn write_generated(
+    output: &'static str,
+    out_subdir: Option<&str>,
+    files: &[(PathBuf, String)],
+    span: Span,
+) -> Result<(), LangError> {
+    #[cfg(test)]
+    GENERATED.with(|generated| {
+        let mut generated = generated.borrow_mut();
+        generated.extend(
+            files
+                .iter()
+                .map(|(path, content)| (output, path.clone(), content.clone())),
+        );
+    });
+
+    for dir in output_dirs(output, out_subdir) {
+        for (path, content) in files {
+            let path = dir.join(path);
+            let write = || -> io::Result<()> {
+                fs::create_dir_all(path.parent().unwrap_or(&dir))?;
+                // Avoid touching the file when nothing changed, it would trigger a rebuild of
+                // everything that depends on it
+                if fs::read_to_string(&path).ok().as_deref() != Some(content.as_str()) {
+                    fs::write(&path, content)?;
+                }
+                Ok(())
+            };
+            write().map_err(|e| LangError::WriteGenerated(span, path.clone(), e))?;
+        }
+    }
+
+    Ok(())
+}
+
 #[derive(Debug)]
 pub enum LangError {
     /// Complex pattern in function argument.
     ///
     /// Only basic patterns like `foo: u32` are supported
-    ComplexPatternFnArg,
+    ComplexPatternFnArg(Span),
 
     /// Trying to return multiple different types by reference
-    MultipleTypesByReference,
+    MultipleTypesByReference(Span),
 
     /// Invalid attributes given to `#[expose_trait]`
     ExposeTraitAttrError(syn::Error),
 
     /// Invalid attribute options in `#[expose_struct]`
     ExposeStructAttrError(syn::Error),
+
+    /// Invalid `#[expose]` attribute on a function argument
+    ExposeArgAttrError(syn::Error),
+
+    /// Default value for an argument that is converted to more than one type
+    DefaultMultipleTypes(Span),
+
+    /// Accessors on a transparent struct, which is not supported by the language
+    TransparentStructField(Span),
+
+    /// Invalid options in `#[constructor]`
+    ConstructorAttrError(syn::Error),
+
+    /// Constructor that takes a `self` receiver or doesn't return `Self`
+    InvalidConstructor(Span),
+
+    /// More than one `#[constructor]` without a `name` in the same `impl` block
+    MultipleDefaultConstructors(Span),
+
+    /// `#[expose_impl]` on a trait implementation other than the supported `std::ops` ones
+    UnsupportedOperator(Span),
+
+    /// Operator implementation without an `Output` type
+    MissingOperatorOutput(Span),
+
+    /// `#[expose_impl]` on a type that is not a plain path, like a reference or a tuple
+    InvalidImplType(Span),
+
+    /// Failure writing one of the generated files
+    WriteGenerated(Span, PathBuf, io::Error),
 }
 
 impl fmt::Display for LangError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{:?}", self)
+        match self {
+            LangError::ComplexPatternFnArg(_) => write!(
+                f,
+                "Only simple patterns like `foo: u32` are supported in exposed function arguments"
+            ),
+            LangError::MultipleTypesByReference(_) => {
+                write!(f, "Can't return multiple types by reference")
+            }
+            LangError::ExposeTraitAttrError(e) => write!(f, "Invalid `expose_trait` attribute: {}", e),
+            LangError::ExposeStructAttrError(e) => {
+                write!(f, "Invalid `expose_struct` attribute: {}", e)
+            }
+            LangError::ExposeArgAttrError(e) => write!(f, "Invalid `expose` attribute: {}", e),
+            LangError::DefaultMultipleTypes(_) => write!(
+                f,
+                "Default values are not supported for arguments converted to multiple types"
+            ),
+            LangError::TransparentStructField(_) => write!(
+                f,
+                "Accessors on transparent structs are not supported by this language"
+            ),
+            LangError::ConstructorAttrError(e) => write!(f, "Invalid `constructor` attribute: {}", e),
+            LangError::InvalidConstructor(_) => write!(
+                f,
+                "Constructors can't take a `self` receiver and must return `Self`, `Option<Self>` or `Result<Self, _>`"
+            ),
+            LangError::MultipleDefaultConstructors(_) => write!(
+                f,
+                "Only one `#[constructor]` can be unnamed, give the others a `name = \"...\"`"
+            ),
+            LangError::UnsupportedOperator(_) => write!(
+                f,
+                "Only implementations of `Add`, `Sub`, `Mul`, `Neg` and `Index<Idx>` can be exposed"
+            ),
+            LangError::MissingOperatorOutput(_) => {
+                write!(f, "Missing the `Output` type of the operator")
+            }
+            LangError::InvalidImplType(_) => {
+                write!(f, "`#[expose_impl]` is only supported on the exposed structs")
+            }
+            LangError::WriteGenerated(_, path, e) => {
+                write!(f, "Unable to write `{}`: {}", path.display(), e)
+            }
+        }
     }
 }
 
 impl std::error::Error for LangError {}
+
+impl From<LangError> for syn::Error {
+    fn from(e: LangError) -> Self {
+        match e {
+            LangError::ExposeTraitAttrError(e)
+            | LangError::ExposeStructAttrError(e)
+            | LangError::ExposeArgAttrError(e)
+            | LangError::ConstructorAttrError(e) => e,
+            LangError::ComplexPatternFnArg(span)
+            | LangError::MultipleTypesByReference(span)
+            | LangError::DefaultMultipleTypes(span)
+            | LangError::TransparentStructField(span)
+            | LangError::InvalidConstructor(span)
+            | LangError::MultipleDefaultConstructors(span)
+            | LangError::UnsupportedOperator(span)
+            | LangError::MissingOperatorOutput(span)
+            | LangError::InvalidImplType(span)
+            | LangError::WriteGenerated(span, ..) => syn::Error::new(span, e),
+        }
+    }
+}
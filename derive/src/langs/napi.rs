@@ -0,0 +1,1385 @@
+//! Node.js addon with N-API, through `napi-rs`
+//!
+//! Opaque structs are annotated with `#[napi]` and become JS classes, with their fields exposed as
+//! properties. Names are converted to camel case, and with `#[expose_mod(module_path)]` the items
+//! are exported in a namespace named after the module path.
+//!
+//! Functions marked with `#[blocking]` run on the libuv thread pool and return a `Promise`, so
+//! their arguments are copied before the call. Errors returned in a `Result` are thrown as an
+//! `Error` with the name of the error type as `code`.
+//!
+//! Exposed traits generate a `<Trait>Struct` class constructed from a JS object that implements
+//! the methods of the trait, which can be called from any thread. An exception thrown by one of
+//! them is returned if the method returns a `Result`, whose error type must implement
+//! `From<napi::Error>`. Otherwise it's thrown back to JS and the method returns the default value
+//! of its output.
+//!
+//! The TypeScript definitions are not generated by `napi-rs`, but rendered when the root
+//! `#[expose_mod]` is expanded and written to `OUT_DIR/index.d.ts`, and to `<CRATE>_NODE_DIR` if
+//! set.
+
+use std::cell::RefCell;
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::PathBuf;
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, ToTokens};
+use syn::punctuated::Punctuated;
+use syn::{
+    Attribute, Block, FnArg, GenericArgument, Ident, ImplItem, ImplItemMethod, Item, ItemFn,
+    ItemImpl, ItemMod, ItemStruct, ItemTrait, PatType, Receiver, ReturnType, Signature, Token,
+    TraitItem, TraitItemMethod, Type, TypePath, TypeReference, TypeSlice, Visibility,
+};
+
+use super::*;
+use crate::types::*;
+
+#[derive(Debug)]
+pub struct Node;
+
+impl Lang for Node {
+    type Error = NodeError;
+
+    const NAME: &'static str = "node";
+
+    const BLOCKING_CALLS: bool = true;
+
+    fn expose_fn(
+        function: &mut ItemFn,
+        mod_path: &Vec<Ident>,
+        extra: &mut Vec<Item>,
+    ) -> Result<Ident, Self::Error> {
+        let ident = function.sig.ident.clone();
+        let blocking = take_blocking(&mut function.attrs);
+        let member = Member::new(
+            MemberKind::Static,
+            camel_case(&ident.to_string(), false),
+            &function.sig,
+            blocking,
+        );
+
+        let body = match blocking {
+            true => {
+                let helper = format_ident!("__blocking_{}", ident);
+                Body::Blocking(quote!(#helper))
+            }
+            false => Body::Inline(&function.block),
+        };
+        let (args, ret, stmts) = match convert_signature(&function.sig, body) {
+            Ok(converted) => converted,
+            Err(NodeError::Unsupported(reason)) => {
+                skip(&ident.to_string(), reason);
+                strip_arg_attrs(&mut function.sig);
+                return Ok(ident);
+            }
+            Err(e) => return Err(e),
+        };
+
+        if blocking {
+            let mut helper = function.clone();
+            helper.sig.ident = format_ident!("__blocking_{}", ident);
+            helper.vis = Visibility::Inherited;
+            strip_arg_attrs(&mut helper.sig);
+            extra.push(helper.into());
+        }
+
+        let attrs = &function.attrs;
+        let js_name = &member.js_name;
+        let napi_attr = match js_namespace(mod_path) {
+            Some(namespace) => {
+                quote!( #[::napi_derive::napi(js_name = #js_name, namespace = #namespace)] )
+            }
+            None => quote!( #[::napi_derive::napi(js_name = #js_name)] ),
+        };
+        *function = parse_quote! {
+            #(#attrs)*
+            #napi_attr
+            pub fn #ident(#args) #ret {
+                #stmts
+            }
+        };
+
+        FUNCTIONS.with(|functions| {
+            functions
+                .borrow_mut()
+                .push((js_namespace(mod_path), member))
+        });
+
+        Ok(ident)
+    }
+
+    fn expose_mod(
+        module: &mut ItemMod,
+        mod_path: &Vec<Ident>,
+        _sub_items: Vec<ModuleItem>,
+    ) -> Result<Ident, Self::Error> {
+        module.vis = parse_quote!(pub);
+
+        // Nested modules are expanded first, so everything has been recorded by now
+        if mod_path.len() == 1 {
+            let definitions = vec![("index.d.ts".into(), render_definitions())];
+            write_generated("NODE", None, &definitions, module.ident.span())?;
+        }
+
+        Ok(module.ident.clone())
+    }
+
+    fn expose_struct(
+        structure: &mut ItemStruct,
+        opts: Punctuated<ExposeStructOpts, Token![,]>,
+        mod_path: &Vec<Ident>,
+        extra: &mut Vec<Item>,
+    ) -> Result<Ident, Self::Error> {
+        let ident = structure.ident.clone();
+        let is_opaque = opts.iter().any(|o| *o == ExposeStructOpts::Opaque);
+
+        structure.vis = parse_quote!(pub);
+        structure.attrs.push(napi_attr(mod_path));
+        // `napi-rs` would export the public fields of transparent structs directly. They get
+        // accessors like the fields of opaque structs instead
+        if !is_opaque {
+            for field in &mut structure.fields {
+                field.attrs.push(parse_quote!( #[napi(skip)] ));
+            }
+        }
+        with_class(&ident, |class| class.namespace = js_namespace(mod_path));
+
+        // Methods implemented by the options, the ones with the same name in the `impl` blocks
+        // are not exported
+        let mut methods: Vec<(ImplItemMethod, Member)> = vec![];
+        if opts.iter().any(|o| *o == ExposeStructOpts::ToString) {
+            methods.push((
+                parse_quote! {
+                    #[napi(js_name = "toString")]
+                    pub fn __to_string(&self) -> String {
+                        self.to_string()
+                    }
+                },
+                Member::option(MemberKind::Method, "toString", vec![], parse_quote!(String)),
+            ));
+        } else if opts.iter().any(|o| *o == ExposeStructOpts::ToDebug) {
+            methods.push((
+                parse_quote! {
+                    #[napi(js_name = "toString")]
+                    pub fn __to_string(&self) -> String {
+                        format!("{:?}", self)
+                    }
+                },
+                Member::option(MemberKind::Method, "toString", vec![], parse_quote!(String)),
+            ));
+        }
+        if opts.iter().any(|o| *o == ExposeStructOpts::Bytes) {
+            methods.push((
+                parse_quote! {
+                    #[napi(js_name = "toBytes")]
+                    pub fn __to_bytes(&self) -> ::napi::bindgen_prelude::Buffer {
+                        crate::common::ByteContainer::as_bytes(self).to_vec().into()
+                    }
+                },
+                Member::option(MemberKind::Method, "toBytes", vec![], parse_quote!(Buffer)),
+            ));
+            methods.push((
+                parse_quote! {
+                    #[napi(js_name = "fromBytes")]
+                    pub fn __from_bytes(bytes: ::napi::bindgen_prelude::Buffer) -> Self {
+                        <Self as crate::common::ByteContainer>::from_bytes(bytes.to_vec())
+                    }
+                },
+                Member::option(
+                    MemberKind::Static,
+                    "fromBytes",
+                    vec![Arg {
+                        name: "bytes".into(),
+                        ty: parse_quote!(Buffer),
+                        optional: false,
+                    }],
+                    parse_quote!(Self),
+                ),
+            ));
+        }
+        if !methods.is_empty() {
+            let (methods, members): (Vec<_>, Vec<_>) = methods.into_iter().unzip();
+            RESERVED.with(|reserved| {
+                let mut reserved = reserved.borrow_mut();
+                for method in &methods {
+                    reserved.insert((ident.to_string(), bindgen_js_name(&method.attrs)));
+                }
+            });
+            with_class(&ident, |class| class.members.extend(members));
+
+            let impl_attr = napi_attr(mod_path);
+            let impl_block: ItemImpl = parse_quote! {
+                #impl_attr
+                impl #ident {
+                    #(#methods)*
+                }
+            };
+            extra.push(impl_block.into());
+        }
+
+        let impl_block = Self::generate_getters_setters(structure, is_opaque, mod_path)?;
+        extra.push(impl_block.into());
+
+        Ok(ident)
+    }
+
+    fn expose_impl(
+        implementation: &mut ItemImpl,
+        mod_path: &Vec<Ident>,
+    ) -> Result<(), Self::Error> {
+        let self_ident = match implementation.self_ty.as_ref() {
+            Type::Path(TypePath { path, .. }) => path.segments.last().map(|s| s.ident.clone()),
+            _ => None,
+        }
+        .ok_or_else(|| LangError::InvalidImplType(implementation.self_ty.span()))?;
+        implementation.attrs.push(napi_attr(mod_path));
+
+        // The garbage collector drops the values, so destructors are not needed
+        implementation.items.retain(|item| {
+            !matches!(item, ImplItem::Method(ImplItemMethod { attrs, .. }) if attrs.iter().any(|a| a.path.is_ident("destructor")))
+        });
+
+        let mut has_default_constructor = false;
+        let mut helpers = vec![];
+        for item in &mut implementation.items {
+            if let ImplItem::Method(method) = item {
+                expose_method(
+                    method,
+                    &self_ident,
+                    &mut has_default_constructor,
+                    &mut helpers,
+                )?;
+            }
+        }
+        implementation
+            .items
+            .extend(helpers.into_iter().map(ImplItem::Method));
+
+        Ok(())
+    }
+
+    fn expose_trait(
+        tr: &mut ItemTrait,
+        mod_path: &Vec<Ident>,
+        extra: &mut Vec<Item>,
+    ) -> Result<Ident, Self::Error> {
+        let ident = tr.ident.clone();
+
+        let mut methods = vec![];
+        for item in &mut tr.items {
+            if let TraitItem::Method(TraitItemMethod { attrs, sig, .. }) = item {
+                let original_ident = match attrs
+                    .iter()
+                    .position(|a| a.path.is_ident("expose_trait"))
+                {
+                    Some(pos) => attrs
+                        .remove(pos)
+                        .parse_args_with(
+                            Punctuated::<ExposeTraitOption, Comma>::parse_separated_nonempty,
+                        )
+                        .map_err(LangError::ExposeTraitAttrError)?
+                        .into_iter()
+                        .map(|ExposeTraitOption::Original(_, i)| Ident::new(&i.value(), i.span()))
+                        .last(),
+                    None => None,
+                }
+                .unwrap_or_else(|| sig.ident.clone());
+
+                methods.push((sig.clone(), original_ident));
+            }
+        }
+
+        let trait_struct_ident = format_ident!("{}Struct", ident);
+        let supertrait = &tr.supertraits[0];
+        let mut trait_struct: ItemStruct = parse_quote! {
+            pub struct #trait_struct_ident {
+                native: Option<Box<dyn #supertrait + Send>>,
+                js: Option<crate::langs::JsImpl>,
+            }
+        };
+        // The struct has to be declared before the `impl` blocks
+        let struct_pos = extra.len();
+        Self::expose_struct(
+            &mut trait_struct,
+            vec![ExposeStructOpts::Opaque].into_iter().collect(),
+            mod_path,
+            extra,
+        )?;
+        extra.insert(struct_pos, trait_struct.into());
+
+        // Methods of the class, which call either the native implementation or the JS one
+        let wrap_fns = methods.iter().map(|(sig, original_ident)| {
+            let js_method = camel_case(&original_ident.to_string(), false);
+            let output = &sig.output;
+            let inputs = sig.inputs.iter();
+            let (arg_names, js_args): (Vec<_>, Vec<_>) = sig
+                .inputs
+                .iter()
+                .filter_map(|arg| match arg {
+                    FnArg::Receiver(_) => None,
+                    FnArg::Typed(PatType { pat, ty, .. }) => match ty.as_ref() {
+                        // Passed to JS as a copy
+                        Type::Reference(_) => Some((quote!(#pat), quote!(ToOwned::to_owned(#pat)))),
+                        _ => Some((quote!(#pat), quote!(#pat))),
+                    },
+                })
+                .unzip();
+            // An exception thrown by JS is returned through the error type of the method, if
+            // there's one. Otherwise it's thrown back to JS, and the default value is returned
+            let call = match output {
+                ReturnType::Default => quote! {
+                    if let Err(e) = js.call_void(#js_method, (#(#js_args,)*)) {
+                        js.throw(e);
+                    }
+                },
+                ReturnType::Type(_, ty) => match match_generic_type(ty, parse_quote!(Result)) {
+                    Some(inner) if inner.len() == 2 && inner[0] == parse_quote!(()) => quote! {
+                        js.call_void(#js_method, (#(#js_args,)*)).map_err(Into::into)
+                    },
+                    Some(inner) if inner.len() == 2 => quote! {
+                        js.call(#js_method, (#(#js_args,)*)).map_err(Into::into)
+                    },
+                    _ => quote! {
+                        js.call(#js_method, (#(#js_args,)*)).unwrap_or_else(|e| {
+                            js.throw(e);
+                            Default::default()
+                        })
+                    },
+                },
+            };
+
+            quote! {
+                pub fn #original_ident(#(#inputs),*) #output {
+                    if let Some(native) = &self.native {
+                        native.#original_ident(#(#arg_names),*)
+                    } else if let Some(js) = &self.js {
+                        #call
+                    } else {
+                        unreachable!()
+                    }
+                }
+            }
+        });
+        let mut impl_block: ItemImpl = parse_quote! {
+            impl #trait_struct_ident {
+                #[constructor]
+                pub fn new(implementation: crate::langs::JsImpl) -> Self {
+                    #trait_struct_ident {
+                        native: None,
+                        js: Some(implementation),
+                    }
+                }
+
+                #(#wrap_fns)*
+            }
+        };
+        Self::expose_impl(&mut impl_block, mod_path)?;
+        extra.push(impl_block.into());
+
+        // The constructor takes an object implementing the interface declared for the trait
+        with_class(&trait_struct_ident, |class| {
+            for member in &mut class.members {
+                if member.kind == MemberKind::Constructor {
+                    member.args[0].ty = parse_quote!(#ident);
+                }
+            }
+        });
+        INTERFACES.with(|interfaces| {
+            interfaces.borrow_mut().push(Interface {
+                ident: ident.to_string(),
+                namespace: js_namespace(mod_path),
+                methods: methods
+                    .iter()
+                    .map(|(sig, original_ident)| {
+                        Member::new(
+                            MemberKind::Method,
+                            camel_case(&original_ident.to_string(), false),
+                            sig,
+                            false,
+                        )
+                    })
+                    .collect(),
+            })
+        });
+
+        // Impl the trait on the trait structure
+        let impl_methods = methods.iter().map(|(sig, original_ident)| {
+            let call_args = sig.inputs.iter().filter_map(|arg| match arg {
+                FnArg::Receiver(_) => None,
+                FnArg::Typed(PatType { pat, .. }) => Some(pat.to_token_stream()),
+            });
+
+            quote! {
+                #sig {
+                    self.#original_ident(#(#call_args),*)
+                }
+            }
+        });
+        let impl_on_trait_struct: ItemImpl = parse_quote! {
+            impl #ident for #trait_struct_ident {
+                #(#impl_methods)*
+            }
+        };
+        extra.push(impl_on_trait_struct.into());
+
+        let into_trait_struct: ItemImpl = parse_quote! {
+            impl<T: 'static + #supertrait + Sized + Send> crate::langs::IntoTraitStruct for T {
+                type Target = #trait_struct_ident;
+
+                fn into_trait_struct(self) -> Self::Target {
+                    #trait_struct_ident {
+                        native: Some(Box::new(self)),
+                        js: None,
+                    }
+                }
+            }
+        };
+        extra.push(into_trait_struct.into());
+
+        Ok(trait_struct_ident)
+    }
+
+    fn expose_operators(
+        self_ty: &Type,
+        operators: Vec<ExposedOperator>,
+        mod_path: &Vec<Ident>,
+        extra: &mut Vec<Item>,
+    ) -> Result<(), Self::Error> {
+        // JavaScript has no operator overloading, so they are exported as regular methods named
+        // `add`, `sub`, `mul`, `neg` and `index`
+        let mut impl_block: ItemImpl = parse_quote! {
+            impl #self_ty {}
+        };
+        for operator in operators {
+            let rhs_by_value = operator.rhs.as_ref().is_some_and(is_primitive);
+            impl_block.items.push(operator.wrapper(rhs_by_value).into());
+        }
+
+        Self::expose_impl(&mut impl_block, mod_path)?;
+        extra.push(impl_block.into());
+
+        Ok(())
+    }
+
+    fn convert_input(ty: Type) -> Result<Input, Self::Error> {
+        if let Some(target) = Self::custom_mapping(&ty) {
+            Ok(Input::new_map_from(ty, vec![target]))
+        } else if let Type::BareFn(_) = ty {
+            Err(NodeError::Unsupported(
+                "callbacks can't be passed from JavaScript, expose a trait instead".into(),
+            ))
+        } else if ty == parse_quote!(Vec<u8>) {
+            Ok(Input::new_map_from(
+                ty,
+                vec![parse_quote!(::napi::bindgen_prelude::Buffer)],
+            ))
+        } else if ty == parse_quote!(usize) {
+            Ok(Input::new_map_from(ty, vec![parse_quote!(u32)]))
+        } else if ty == parse_quote!(u64) {
+            Ok(Input::new_map_from(ty, vec![parse_quote!(crate::langs::JsU64)]))
+        } else {
+            Ok(Input::new_unchanged(ty))
+        }
+    }
+
+    fn convert_output(output: Type) -> Result<Output, Self::Error> {
+        if let Some(target) = Self::custom_mapping(&output) {
+            Ok(Output::new_map_to_single(output, target))
+        } else if output == parse_quote!(Vec<u8>) {
+            Ok(Output::new_map_to_single(
+                output,
+                parse_quote!(::napi::bindgen_prelude::Buffer),
+            ))
+        } else if output == parse_quote!(usize) {
+            Ok(Output::new_map_to_single(
+                output,
+                parse_quote!(crate::langs::JsUsize),
+            ))
+        } else {
+            Ok(Output::new_unchanged(output))
+        }
+    }
+
+    // Setters borrow the new value, classes can't be taken by value
+    fn convert_getter_setter_ty(ty: Type) -> Result<(Type, Type), Self::Error> {
+        let getter = parse_quote!(<#ty as crate::common::WrappedStructField>::Getter);
+        let setter = parse_quote!(<#ty as crate::common::WrappedStructField>::Setter);
+        if is_primitive(&ty) {
+            Ok((getter, setter))
+        } else {
+            Ok((getter, parse_quote!(&#setter)))
+        }
+    }
+
+    fn expose_inline_getter(
+        field: &mut Field,
+        impl_block: &mut ItemImpl,
+    ) -> Result<(), Self::Error> {
+        let field_ty = &field.ty;
+        let field_ident = field.ident.as_ref().expect("Missing field ident");
+        let getter_name = format_ident!("get_{}", field_ident);
+        let getter: ImplItemMethod = parse_quote! {
+            #[getter]
+            fn #getter_name(&self) -> #field_ty {
+                self.#field_ident.clone()
+            }
+        };
+        impl_block.items.push(getter.into());
+
+        Ok(())
+    }
+
+    fn expose_inline_setter(
+        field: &mut Field,
+        validate: Option<&Path>,
+        impl_block: &mut ItemImpl,
+    ) -> Result<(), Self::Error> {
+        let field_ty = &field.ty;
+        let field_ident = field.ident.as_ref().expect("Missing field ident");
+        let setter_name = format_ident!("set_{}", field_ident);
+        let setter_ty: Type = if is_primitive(field_ty) {
+            parse_quote!(#field_ty)
+        } else {
+            parse_quote!(&#field_ty)
+        };
+        let setter: ImplItemMethod = match validate {
+            None => parse_quote! {
+                #[setter]
+                fn #setter_name(&mut self, #field_ident: #setter_ty) {
+                    self.#field_ident = crate::mapping::MapFrom::map_from(#field_ident);
+                }
+            },
+            Some(validate) => parse_quote! {
+                #[setter]
+                fn #setter_name(&mut self, #field_ident: #setter_ty) -> Result<(), crate::common::ValidationError> {
+                    let #field_ident: #field_ty = crate::mapping::MapFrom::map_from(#field_ident);
+                    #validate(self, &#field_ident).map_err(crate::common::ValidationError::from_display)?;
+                    self.#field_ident = #field_ident;
+
+                    Ok(())
+                }
+            },
+        };
+        impl_block.items.push(setter.into());
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum MemberKind {
+    Constructor,
+    Static,
+    Method,
+    Getter,
+    Setter,
+}
+
+/// Argument of an exported function, as declared in the TypeScript definitions
+struct Arg {
+    name: String,
+    ty: Type,
+    /// Has a default value, so it can be omitted
+    optional: bool,
+}
+
+/// Exported function or method, with its original Rust types
+struct Member {
+    kind: MemberKind,
+    js_name: String,
+    args: Vec<Arg>,
+    output: Type,
+    /// Returns a `Promise`
+    blocking: bool,
+}
+
+impl Member {
+    fn new(kind: MemberKind, js_name: String, sig: &Signature, blocking: bool) -> Self {
+        let args = sig
+            .inputs
+            .iter()
+            .filter_map(|input| match input {
+                FnArg::Typed(PatType { pat, ty, attrs, .. }) => match pat.as_ref() {
+                    Pat::Ident(PatIdent { ident, .. }) => Some(Arg {
+                        name: camel_case(&ident.to_string(), false),
+                        ty: (**ty).clone(),
+                        optional: attrs.iter().any(|a| a.path.is_ident("expose")),
+                    }),
+                    _ => None,
+                },
+                FnArg::Receiver(_) => None,
+            })
+            .collect();
+
+        Member {
+            kind,
+            js_name,
+            args,
+            output: sig.output.as_type(),
+            blocking,
+        }
+    }
+
+    /// Method implemented by an `#[expose_struct]` option
+    fn option(kind: MemberKind, js_name: &str, args: Vec<Arg>, output: Type) -> Self {
+        Member {
+            kind,
+            js_name: js_name.into(),
+            args,
+            output,
+            blocking: false,
+        }
+    }
+}
+
+struct Class {
+    ident: String,
+    namespace: Option<String>,
+    members: Vec<Member>,
+}
+
+/// Shape of the JS objects implementing an exposed trait
+struct Interface {
+    ident: String,
+    namespace: Option<String>,
+    methods: Vec<Member>,
+}
+
+thread_local! {
+    /// JS names of the methods implemented by the `#[expose_struct]` options, by struct
+    static RESERVED: RefCell<BTreeSet<(String, String)>> = const { RefCell::new(BTreeSet::new()) };
+    /// Exported structs, in the order they are declared
+    static CLASSES: RefCell<Vec<Class>> = const { RefCell::new(Vec::new()) };
+    /// Exported free functions, with their namespace
+    static FUNCTIONS: RefCell<Vec<(Option<String>, Member)>> = const { RefCell::new(Vec::new()) };
+    /// Exposed traits
+    static INTERFACES: RefCell<Vec<Interface>> = const { RefCell::new(Vec::new()) };
+    /// Notes about the items that are not exported
+    static SKIPPED: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+fn with_class<F: FnOnce(&mut Class)>(ident: &Ident, f: F) {
+    CLASSES.with(|classes| {
+        let mut classes = classes.borrow_mut();
+        let ident = ident.to_string();
+        match classes.iter_mut().find(|class| class.ident == ident) {
+            Some(class) => f(class),
+            None => {
+                let mut class = Class {
+                    ident,
+                    namespace: None,
+                    members: vec![],
+                };
+                f(&mut class);
+                classes.push(class);
+            }
+        }
+    });
+}
+
+fn skip(item: &str, reason: String) {
+    let note = format!("`{}` is not exported: {}", item, reason);
+    SKIPPED.with(|skipped| skipped.borrow_mut().push(note));
+}
+
+/// Namespace of the items of a module, only if `#[expose_mod(module_path)]` is set
+fn js_namespace(mod_path: &[Ident]) -> Option<String> {
+    if !mod_opts().module_path {
+        return None;
+    }
+
+    let path = mod_path
+        .iter()
+        .map(|module| module.to_string().to_snake_case())
+        .collect::<Vec<_>>()
+        .join("_");
+    Some(camel_case(&path, false))
+}
+
+/// `#[napi]` attribute of a struct and its `impl` blocks
+fn napi_attr(mod_path: &[Ident]) -> Attribute {
+    match js_namespace(mod_path) {
+        Some(namespace) => parse_quote!( #[::napi_derive::napi(namespace = #namespace)] ),
+        None => parse_quote!( #[::napi_derive::napi] ),
+    }
+}
+
+/// The `js_name` given to a method generated for the struct options
+fn bindgen_js_name(attrs: &[Attribute]) -> String {
+    attrs
+        .iter()
+        .find_map(|attr| {
+            attr.parse_args_with(|input: syn::parse::ParseStream| {
+                input.parse::<Ident>()?;
+                input.parse::<Token![=]>()?;
+                input.parse::<LitStr>()
+            })
+            .ok()
+        })
+        .map(|name| name.value())
+        .expect("Missing `js_name`")
+}
+
+/// Types that are taken by value without consuming a JS object
+fn is_primitive(ty: &Type) -> bool {
+    types_arr!(bool, i8, u8, i16, u16, i32, u32, i64, u64, isize, usize, f32, f64)
+        .iter()
+        .any(|t| t == ty)
+}
+
+fn strip_arg_attrs(sig: &mut Signature) {
+    for input in &mut sig.inputs {
+        if let FnArg::Typed(PatType { attrs, .. }) = input {
+            attrs.retain(|a| !a.path.is_ident("expose"));
+        }
+    }
+}
+
+/// The `Ok` and `Err` types of a `Result`
+fn result_types(ty: &Type) -> Option<(Type, Type)> {
+    match match_generic_type(ty, parse_quote!(Result))?.as_slice() {
+        [ok, err] => Some((ok.clone(), err.clone())),
+        _ => None,
+    }
+}
+
+/// Type of the value returned to JS and the statements converting `__output` to a
+/// `Result<_, NodeError>`, for the functions that return a `Result` and the blocking ones
+fn convert_result(output: &ReturnType) -> Result<(Type, TokenStream2), NodeError> {
+    let ty = output.as_type();
+    let (ok, unwrap) = match result_types(&ty) {
+        Some((ok, _)) => (
+            ok,
+            quote!(let __output = __output.map_err(crate::langs::NodeError::from)?;),
+        ),
+        None => (ty, quote!()),
+    };
+
+    let ExpandedOutput { ty, conv, .. } =
+        Node::convert_output(ok)?.expand(&format_ident!("__output"));
+    let ty = ty.into_iter().map(|t| *t).as_tuple();
+
+    Ok((
+        ty,
+        quote! {
+            #unwrap
+            #conv
+            Ok(__output)
+        },
+    ))
+}
+
+/// Original body of an exported function
+enum Body<'a> {
+    Inline(&'a Block),
+    /// Moved to a separate function, which is called on another thread
+    Blocking(TokenStream2),
+}
+
+/// Convert the arguments and the return value of an exported function, returning the new
+/// arguments, return type and body
+fn convert_signature(
+    sig: &Signature,
+    body: Body,
+) -> Result<(Punctuated<FnArg, Comma>, ReturnType, TokenStream2), NodeError> {
+    let (mut args, input_conversion) = Node::convert_fn_args(sig.inputs.clone(), true)?;
+
+    let (ret, stmts) = match body {
+        Body::Inline(block) => {
+            // The error is converted after the call, so `?` in the body needs the original type
+            let mut closure_ret = quote!();
+            let (ret, output_conversion) = match result_types(&sig.output.as_type()) {
+                Some(_) => {
+                    let (ok, conv) = convert_result(&sig.output)?;
+                    let output = &sig.output;
+                    closure_ret = quote!(#output);
+                    (parse_quote!(-> Result<#ok, crate::langs::NodeError>), conv)
+                }
+                None => {
+                    let ExpandedReturn {
+                        ret,
+                        extra_args,
+                        conv,
+                    } = Return(sig.output.clone()).expand(
+                        &format_ident!("__output"),
+                        &format_ident!("__ptr_out"),
+                        Node::convert_output,
+                    )?;
+                    args.extend(extra_args);
+
+                    (ret, conv.into_inner())
+                }
+            };
+
+            (
+                ret,
+                quote! {
+                    let mut block_closure = move || #closure_ret { #block };
+                    let __output = block_closure();
+
+                    #output_conversion
+                },
+            )
+        }
+        Body::Blocking(helper) => {
+            // The call outlives the JS values, so whatever is borrowed is copied
+            let mut owned = TokenStream2::default();
+            let mut call_args = vec![];
+            for input in &sig.inputs {
+                match input {
+                    FnArg::Receiver(Receiver {
+                        reference: Some(_),
+                        mutability: None,
+                        ..
+                    }) => {
+                        owned.extend(quote!(let __self = Clone::clone(self);));
+                        call_args.push(quote!(&__self));
+                    }
+                    FnArg::Receiver(receiver) => {
+                        return Err(NodeError::InvalidBlocking(receiver.self_token.span))
+                    }
+                    FnArg::Typed(PatType { pat, ty, .. }) => match ty.as_ref() {
+                        Type::Reference(TypeReference {
+                            mutability: None, ..
+                        }) => {
+                            owned.extend(quote!(let #pat = ToOwned::to_owned(#pat);));
+                            call_args.push(quote!(&#pat));
+                        }
+                        Type::Reference(_) => return Err(NodeError::InvalidBlocking(ty.span())),
+                        _ => call_args.push(quote!(#pat)),
+                    },
+                }
+            }
+            let (ok, conv) = convert_result(&sig.output)?;
+
+            (
+                parse_quote!(-> ::napi::bindgen_prelude::AsyncTask<crate::langs::Blocking<#ok>>),
+                quote! {
+                    #owned
+
+                    crate::langs::Blocking::spawn(move || {
+                        let __output = #helper(#(#call_args),*);
+
+                        #conv
+                    })
+                },
+            )
+        }
+    };
+
+    Ok((
+        args,
+        ret,
+        quote! {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+
+            #input_conversion
+
+            #stmts
+        },
+    ))
+}
+
+/// Rename a method, turn it into a constructor, getter or setter and convert its arguments. The
+/// methods that can't be exported are left as they are
+fn expose_method(
+    method: &mut ImplItemMethod,
+    self_ident: &Ident,
+    has_default_constructor: &mut bool,
+    helpers: &mut Vec<ImplItemMethod>,
+) -> Result<(), NodeError> {
+    let ImplItemMethod {
+        attrs,
+        vis,
+        sig,
+        block,
+        ..
+    } = method;
+    let name = sig.ident.to_string();
+    let blocking = take_blocking(attrs);
+    let is_static = !matches!(sig.inputs.first(), Some(FnArg::Receiver(_)));
+
+    let (napi_args, kind, js_name) = match take_constructor(attrs, sig)? {
+        Some(ConstructorOpts { name: None }) => {
+            if std::mem::replace(has_default_constructor, true) {
+                return Err(LangError::MultipleDefaultConstructors(sig.ident.span()).into());
+            }
+            if let ReturnType::Type(_, ty) = &sig.output {
+                if match_generic_type(ty, parse_quote!(Option)).is_some() {
+                    return Err(NodeError::OptionalConstructor(sig.ident.span()));
+                }
+            }
+            if blocking {
+                return Err(NodeError::InvalidBlocking(sig.ident.span()));
+            }
+
+            (
+                quote!(constructor),
+                MemberKind::Constructor,
+                "constructor".to_string(),
+            )
+        }
+        Some(ConstructorOpts { name: Some(name) }) => {
+            let js_name = camel_case(&name.to_string(), false);
+            (quote!(js_name = #js_name), MemberKind::Static, js_name)
+        }
+        None => match attrs
+            .iter()
+            .position(|a| a.path.is_ident("getter") || a.path.is_ident("setter"))
+        {
+            Some(pos) => {
+                let attr = attrs.remove(pos);
+                if blocking {
+                    return Err(NodeError::InvalidBlocking(sig.ident.span()));
+                }
+
+                if attr.path.is_ident("getter") {
+                    let property = camel_case(name.trim_start_matches("get_"), false);
+                    (
+                        quote!(getter, js_name = #property),
+                        MemberKind::Getter,
+                        property,
+                    )
+                } else {
+                    let property = camel_case(name.trim_start_matches("set_"), false);
+                    (
+                        quote!(setter, js_name = #property),
+                        MemberKind::Setter,
+                        property,
+                    )
+                }
+            }
+            None => {
+                let js_name = camel_case(&name, false);
+                let reserved = RESERVED.with(|reserved| {
+                    reserved
+                        .borrow()
+                        .contains(&(self_ident.to_string(), js_name.clone()))
+                });
+                if reserved {
+                    strip_arg_attrs(sig);
+                    return Ok(());
+                }
+
+                let kind = match is_static {
+                    true => MemberKind::Static,
+                    false => MemberKind::Method,
+                };
+                (quote!(js_name = #js_name), kind, js_name)
+            }
+        },
+    };
+    let member = Member::new(kind, js_name, sig, blocking);
+
+    let helper = format_ident!("__blocking_{}", sig.ident);
+    let body = match blocking {
+        true => Body::Blocking(quote!(Self::#helper)),
+        false => Body::Inline(block),
+    };
+    let (args, ret, stmts) = match convert_signature(sig, body) {
+        Ok(converted) => converted,
+        Err(NodeError::Unsupported(reason)) => {
+            skip(&format!("{}::{}", self_ident, name), reason);
+            strip_arg_attrs(sig);
+            return Ok(());
+        }
+        Err(e) => return Err(e),
+    };
+
+    // The original method is kept unexported, and called on the thread pool
+    if blocking {
+        let mut helper_sig = sig.clone();
+        helper_sig.ident = helper;
+        strip_arg_attrs(&mut helper_sig);
+        helpers.push(ImplItemMethod {
+            attrs: attrs.clone(),
+            vis: Visibility::Inherited,
+            defaultness: None,
+            sig: helper_sig,
+            block: block.clone(),
+        });
+    }
+
+    attrs.push(parse_quote!( #[napi(#napi_args)] ));
+    *vis = parse_quote!(pub);
+    sig.inputs = args;
+    sig.output = ret;
+    // The types are copied outside of the `impl` block by `napi-rs`
+    for input in &mut sig.inputs {
+        if let FnArg::Typed(PatType { ty, .. }) = input {
+            replace_self(ty, self_ident);
+        }
+    }
+    if let ReturnType::Type(_, ty) = &mut sig.output {
+        replace_self(ty, self_ident);
+    }
+    block.stmts = parse_quote!(#stmts);
+
+    with_class(self_ident, |class| class.members.push(member));
+
+    Ok(())
+}
+
+/// Replace `Self` with the name of the struct
+fn replace_self(ty: &mut Type, self_ident: &Ident) {
+    match ty {
+        Type::Reference(TypeReference { elem, .. }) | Type::Slice(TypeSlice { elem, .. }) => {
+            replace_self(elem, self_ident)
+        }
+        Type::Tuple(tuple) => tuple
+            .elems
+            .iter_mut()
+            .for_each(|elem| replace_self(elem, self_ident)),
+        Type::Path(TypePath { qself, path }) => {
+            if let Some(qself) = qself {
+                replace_self(&mut qself.ty, self_ident);
+            }
+            for segment in &mut path.segments {
+                if segment.ident == "Self" {
+                    segment.ident = self_ident.clone();
+                }
+                if let PathArguments::AngleBracketed(args) = &mut segment.arguments {
+                    for arg in &mut args.args {
+                        if let GenericArgument::Type(ty) = arg {
+                            replace_self(ty, self_ident);
+                        }
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Names of the classes and interfaces, qualified with their namespace
+struct Context {
+    names: HashMap<String, String>,
+    /// Class of the members being rendered, which `Self` refers to
+    self_name: Option<String>,
+}
+
+fn qualified(namespace: &Option<String>, name: &str) -> String {
+    match namespace {
+        Some(namespace) => format!("{}.{}", namespace, name),
+        None => name.to_string(),
+    }
+}
+
+fn ts_type(ty: &Type, ctx: &Context) -> String {
+    const NUMBERS: &[&str] = &[
+        "i8", "u8", "i16", "u16", "i32", "u32", "i64", "isize", "usize", "f32", "f64",
+    ];
+
+    match ty {
+        Type::Reference(TypeReference { elem, .. }) => match elem.as_ref() {
+            Type::Slice(TypeSlice { elem, .. }) if **elem == parse_quote!(u8) => {
+                "Uint8Array".into()
+            }
+            elem => ts_type(elem, ctx),
+        },
+        Type::Slice(TypeSlice { elem, .. }) => format!("Array<{}>", ts_type(elem, ctx)),
+        Type::Tuple(tuple) if tuple.elems.is_empty() => "void".into(),
+        // The getters and setters of the wrapped fields
+        Type::Path(TypePath {
+            qself: Some(qself), ..
+        }) => ts_type(&qself.ty, ctx),
+        Type::Path(TypePath { path, .. }) => {
+            let segment = path.segments.last().expect("Empty type path");
+            let args = match &segment.arguments {
+                PathArguments::AngleBracketed(args) => args
+                    .args
+                    .iter()
+                    .filter_map(|arg| match arg {
+                        GenericArgument::Type(ty) => Some(ty),
+                        _ => None,
+                    })
+                    .collect(),
+                _ => vec![],
+            };
+
+            match (segment.ident.to_string().as_str(), args.as_slice()) {
+                ("Option", [inner]) => format!("{} | null", ts_type(inner, ctx)),
+                ("Result", [ok, _]) | ("Box", [ok]) => ts_type(ok, ctx),
+                ("Vec", [inner]) if **inner == parse_quote!(u8) => "Buffer".into(),
+                ("Vec", [inner]) => format!("Array<{}>", ts_type(inner, ctx)),
+                ("bool", []) => "boolean".into(),
+                ("u64", []) => "bigint".into(),
+                (number, []) if NUMBERS.contains(&number) => "number".into(),
+                ("String", []) | ("str", []) => "string".into(),
+                ("Buffer", []) => "Buffer".into(),
+                ("Self", []) => ctx.self_name.clone().unwrap_or_else(|| "unknown".into()),
+                (name, []) => ctx
+                    .names
+                    .get(name)
+                    .cloned()
+                    .unwrap_or_else(|| "unknown".into()),
+                _ => "unknown".into(),
+            }
+        }
+        _ => "unknown".into(),
+    }
+}
+
+/// Code of the errors thrown by a function
+fn error_code(output: &Type) -> Option<String> {
+    match result_types(output)?.1 {
+        Type::Path(TypePath { path, .. }) => match path.segments.last()?.ident.to_string() {
+            // Same as the errors thrown by Node for out-of-bounds accesses
+            error if error == "IndexError" => Some("ERR_OUT_OF_RANGE".into()),
+            error => Some(error),
+        },
+        _ => None,
+    }
+}
+
+fn render_member(member: &Member, ctx: &Context, lines: &mut Vec<String>) {
+    if let Some(code) = error_code(&member.output) {
+        lines.push(format!(
+            "/** @throws {{Error}} With `code` set to `\"{}\"` */",
+            code
+        ));
+    }
+
+    let args = member
+        .args
+        .iter()
+        .map(|arg| match arg.optional {
+            true => format!(
+                "{}?: {} | undefined | null",
+                arg.name,
+                ts_type(&arg.ty, ctx)
+            ),
+            false => format!("{}: {}", arg.name, ts_type(&arg.ty, ctx)),
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let output = match member.blocking {
+        true => format!("Promise<{}>", ts_type(&member.output, ctx)),
+        false => ts_type(&member.output, ctx),
+    };
+
+    lines.push(match member.kind {
+        MemberKind::Constructor => format!("constructor({})", args),
+        MemberKind::Static => format!("static {}({}): {}", member.js_name, args, output),
+        MemberKind::Method => format!("{}({}): {}", member.js_name, args, output),
+        MemberKind::Getter => format!("get {}(): {}", member.js_name, output),
+        MemberKind::Setter => format!("set {}({})", member.js_name, args),
+    });
+}
+
+fn indent(lines: Vec<String>, level: usize) -> Vec<String> {
+    lines
+        .into_iter()
+        .map(|line| match line.is_empty() {
+            true => line,
+            false => format!("{}{}", "  ".repeat(level), line),
+        })
+        .collect()
+}
+
+/// Render the `index.d.ts` of the addon, which declares the exported items grouped by namespace,
+/// an interface for every error type and a comment with the items that are not exported
+fn render_definitions() -> String {
+    let classes = CLASSES.with(|classes| classes.replace(Vec::new()));
+    let functions = FUNCTIONS.with(|functions| functions.replace(Vec::new()));
+    let interfaces = INTERFACES.with(|interfaces| interfaces.replace(Vec::new()));
+    let skipped = SKIPPED.with(|skipped| skipped.replace(Vec::new()));
+    RESERVED.with(|reserved| reserved.replace(BTreeSet::new()));
+
+    let mut ctx = Context {
+        names: HashMap::new(),
+        self_name: None,
+    };
+    for class in &classes {
+        ctx.names.insert(
+            class.ident.clone(),
+            qualified(&class.namespace, &class.ident),
+        );
+    }
+    for interface in &interfaces {
+        ctx.names.insert(
+            interface.ident.clone(),
+            qualified(&interface.namespace, &interface.ident),
+        );
+    }
+
+    // Declarations by namespace, `None` first
+    let mut namespaces: Vec<(Option<String>, Vec<Vec<String>>)> = vec![(None, vec![])];
+    let mut declare = |namespace: &Option<String>, lines: Vec<String>| match namespaces
+        .iter_mut()
+        .find(|(n, _)| n == namespace)
+    {
+        Some((_, declarations)) => declarations.push(lines),
+        None => namespaces.push((namespace.clone(), vec![lines])),
+    };
+    let mut errors = BTreeSet::new();
+
+    for class in &classes {
+        ctx.self_name = ctx.names.get(&class.ident).cloned();
+
+        let mut members = vec![];
+        for member in &class.members {
+            errors.extend(error_code(&member.output));
+            render_member(member, &ctx, &mut members);
+        }
+
+        let mut lines = vec![format!("class {} {{", class.ident)];
+        lines.extend(indent(members, 1));
+        lines.push("}".into());
+        declare(&class.namespace, lines);
+    }
+    ctx.self_name = None;
+
+    for (namespace, function) in &functions {
+        errors.extend(error_code(&function.output));
+
+        let mut lines = vec![];
+        render_member(function, &ctx, &mut lines);
+        // Rendered as a static method
+        let signature = lines.pop().unwrap();
+        lines.push(format!(
+            "function {}",
+            signature.trim_start_matches("static ")
+        ));
+        declare(namespace, lines);
+    }
+
+    for interface in &interfaces {
+        let mut methods = vec![];
+        for method in &interface.methods {
+            render_member(method, &ctx, &mut methods);
+        }
+
+        let mut lines = vec![format!("interface {} {{", interface.ident)];
+        lines.extend(indent(methods, 1));
+        lines.push("}".into());
+        declare(&interface.namespace, lines);
+    }
+
+    let mut definitions = String::new();
+    for note in &skipped {
+        definitions.push_str(&format!("// {}\n", note));
+    }
+    for (namespace, declarations) in namespaces {
+        let mut lines = vec![];
+        match &namespace {
+            None => {
+                for declaration in declarations {
+                    if !lines.is_empty() {
+                        lines.push(String::new());
+                    }
+                    let mut declaration = declaration.into_iter();
+                    let mut first = declaration.next().unwrap();
+                    // The `@throws` of a function comes before its declaration
+                    if first.starts_with("/**") {
+                        lines.push(first);
+                        first = declaration.next().unwrap();
+                    }
+                    match first.starts_with("interface") {
+                        true => lines.push(format!("export {}", first)),
+                        false => lines.push(format!("export declare {}", first)),
+                    }
+                    lines.extend(declaration);
+                }
+            }
+            Some(namespace) => {
+                lines.push(format!("export declare namespace {} {{", namespace));
+                let mut inner = vec![];
+                for declaration in declarations {
+                    if !inner.is_empty() {
+                        inner.push(String::new());
+                    }
+                    let mut declaration = declaration.into_iter();
+                    let mut first = declaration.next().unwrap();
+                    if first.starts_with("/**") {
+                        inner.push(first);
+                        first = declaration.next().unwrap();
+                    }
+                    inner.push(format!("export {}", first));
+                    inner.extend(declaration);
+                }
+                lines.extend(indent(inner, 1));
+                lines.push("}".into());
+            }
+        }
+
+        if lines.is_empty() {
+            continue;
+        }
+        if !definitions.is_empty() {
+            definitions.push('\n');
+        }
+        for line in lines {
+            definitions.push_str(&line);
+            definitions.push('\n');
+        }
+    }
+    // A type called `Error` would shadow the builtin one
+    for error in errors
+        .iter()
+        .filter(|error| *error != "ERR_OUT_OF_RANGE" && *error != "Error")
+    {
+        if !definitions.is_empty() {
+            definitions.push('\n');
+        }
+        definitions.push_str(&format!(
+            "/** Thrown by the functions that return a `{error}` */\n\
+             export interface {error} extends Error {{\n  code: \"{error}\"\n}}\n",
+            error = error
+        ));
+    }
+
+    definitions
+}
+
+#[derive(Debug)]
+pub enum NodeError {
+    Lang(LangError),
+
+    /// Unnamed constructor returning an `Option`, which can't be a JS constructor
+    OptionalConstructor(Span),
+    /// `#[blocking]` on a constructor, an accessor or a method that takes `self` by value or
+    /// borrows mutably, which can't be moved to another thread
+    InvalidBlocking(Span),
+    /// Signature that can't cross to JavaScript, skipped instead of failing the build
+    Unsupported(String),
+}
+
+impl fmt::Display for NodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NodeError::Lang(e) => write!(f, "{}", e),
+            NodeError::OptionalConstructor(_) => write!(
+                f,
+                "Constructors without a `name` can't return an `Option` in JavaScript"
+            ),
+            NodeError::InvalidBlocking(_) => write!(
+                f,
+                "`#[blocking]` is only supported on functions, named constructors and methods that take `&self`, with arguments that are not borrowed mutably"
+            ),
+            NodeError::Unsupported(reason) => write!(f, "Unsupported signature: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for NodeError {}
+
+impl From<NodeError> for syn::Error {
+    fn from(e: NodeError) -> Self {
+        match e {
+            NodeError::Lang(e) => e.into(),
+            NodeError::OptionalConstructor(span) | NodeError::InvalidBlocking(span) => {
+                syn::Error::new(span, e)
+            }
+            NodeError::Unsupported(_) => syn::Error::new(Span::call_site(), e),
+        }
+    }
+}
+
+impl From<LangError> for NodeError {
+    fn from(e: LangError) -> Self {
+        NodeError::Lang(e)
+    }
+}
@@ -8,7 +8,7 @@ use quote::{format_ident, quote, ToTokens, TokenStreamExt};
 use syn::punctuated::Punctuated;
 use syn::{
     parse_quote, Attribute, Fields, FieldsNamed, FnArg, Ident, ImplItem, ImplItemMethod, Item,
-    ItemFn, Pat, PatIdent, PatType, Token, TraitItem, TraitItemMethod, TypeReference,
+    ItemFn, Pat, PatIdent, PatType, Receiver, Token, TraitItem, TraitItemMethod, TypeReference,
 };
 
 use super::*;
@@ -20,14 +20,21 @@ pub struct Python;
 impl Lang for Python {
     type Error = PythonError;
 
-    fn expose_fn(function: &mut ItemFn, mod_path: &Vec<Ident>) -> Result<Ident, Self::Error> {
+    const NAME: &'static str = "python";
+
+    fn expose_fn(
+        function: &mut ItemFn,
+        mod_path: &Vec<Ident>,
+        _extra: &mut Vec<Item>,
+    ) -> Result<Ident, Self::Error> {
         if mod_path.is_empty() {
-            return Err(PythonError::NakedFunction);
+            return Err(PythonError::NakedFunction(function.sig.span()));
         }
 
         let ident = &function.sig.ident;
 
-        let (mut args, input_conversion) = Self::convert_fn_args(function.sig.inputs.clone())?;
+        let args_attr = default_args_attr(&function.sig.inputs)?;
+        let (mut args, input_conversion) = Self::convert_fn_args(function.sig.inputs.clone(), true)?;
 
         let ExpandedReturn {
             ret,
@@ -42,9 +49,9 @@ impl Lang for Python {
 
         let block = &function.block;
 
-        let ident_str = ident.to_string();
         *function = parse_quote! {
             #[pyo3::prelude::pyfunction]
+            #args_attr
             fn #ident(#args) #ret {
                 use crate::mapping::{MapTo, MapFrom};
                 use crate::langs::*;
@@ -80,22 +87,25 @@ impl Lang for Python {
                         m.add_function(pyo3::wrap_pyfunction!(#ident, m)?)?;
                     }
                 }
-                ModuleItem::Structure(ident) => {
+                ModuleItem::Structure(ident) | ModuleItem::Trait(ident) => {
+                    let ident_str = ident.to_string();
                     quote! {
                         m.add_class::<#ident>()?;
+                        m.getattr(#ident_str)?.setattr("__module__", m.name()?)?;
                     }
                 }
                 ModuleItem::Module(ident) => {
                     let ident_str = ident.to_string();
                     quote! {
+                        // Register the submodule under its fully qualified name, so that it can be
+                        // imported like a package
                         let submod = pyo3::types::PyModule::new(py, #ident_str)?;
+                        let qualified_name = format!("{}.{}", m.name()?, #ident_str);
+                        submod.setattr("__name__", &qualified_name)?;
+                        py.import("sys")?.getattr("modules")?.set_item(&qualified_name, submod)?;
+
                         #ident::#ident(py, submod)?;
-                        m.add_submodule(submod)?;
-                    }
-                }
-                ModuleItem::Trait(ident) => {
-                    quote! {
-                        m.add_class::<#ident>()?;
+                        m.add(#ident_str, submod)?;
                     }
                 }
             };
@@ -107,6 +117,11 @@ impl Lang for Python {
         if mod_path.len() == 1 {
             let attr: Attribute = parse_quote!( #[pyo3::prelude::pymodule] );
             extra_attrs.append_all(&[attr]);
+
+            // `install_logger()` and `set_log_level()` are part of the runtime
+            export_tokens.extend(quote! {
+                crate::langs::add_logging(m)?;
+            });
         }
 
         *module = parse_quote! {
@@ -138,28 +153,94 @@ impl Lang for Python {
         };
         structure.attrs.push(attr);
 
+        if !opts.contains(&ExposeStructOpts::Shared) {
+            let impl_mutable: ItemImpl = parse_quote! {
+                impl crate::langs::MutablePyClass for #ident {}
+            };
+            extra.push(impl_mutable.into());
+        }
+
         let impl_block =
             Self::generate_getters_setters(structure, true, mod_path)?;
         extra.push(impl_block.into());
 
+        // A protocol can only be implemented once, so its methods are collected first
+        let mut object_methods = vec![];
         if opts.contains(&ExposeStructOpts::ToString) {
-            let impl_block: ItemImpl = parse_quote! {
+            object_methods.push(quote! {
+                fn __str__(&self) -> String {
+                    self.to_string()
+                }
+            });
+        }
+        if opts.contains(&ExposeStructOpts::ToDebug) {
+            object_methods.push(quote! {
+                fn __repr__(&self) -> String {
+                    format!("{:?}", self)
+                }
+            });
+        }
+        if opts.contains(&ExposeStructOpts::Bytes) {
+            // `shared` structs are immutable, so their views can point straight at the bytes and
+            // there's nothing to free when they are released
+            let (fill, release) = if opts.contains(&ExposeStructOpts::Shared) {
+                let release = quote! {
+                    fn bf_releasebuffer(_slf: pyo3::PyRefMut<Self>, _view: *mut pyo3::ffi::Py_buffer) {}
+                };
+                (
+                    quote! { crate::langs::fill_byte_buffer(&slf, view, flags) },
+                    release,
+                )
+            } else {
+                let release = quote! {
+                    fn bf_releasebuffer(_slf: pyo3::PyRefMut<Self>, view: *mut pyo3::ffi::Py_buffer) {
+                        unsafe { crate::langs::release_byte_buffer_copy(view) }
+                    }
+                };
+                (
+                    quote! { crate::langs::fill_byte_buffer_copy(&slf, view, flags) },
+                    release,
+                )
+            };
+            let buffer_impl: ItemImpl = parse_quote! {
                 #[pyo3::prelude::pyproto]
-                impl pyo3::class::PyObjectProtocol for #ident {
-                    fn __str__(&self) -> String {
-                        self.to_string()
+                // pyo3 dictates the signatures, `view` comes straight from the interpreter
+                #[allow(clippy::not_unsafe_ptr_arg_deref)]
+                impl pyo3::class::PyBufferProtocol for #ident {
+                    fn bf_getbuffer(
+                        slf: pyo3::PyRefMut<Self>,
+                        view: *mut pyo3::ffi::Py_buffer,
+                        flags: std::os::raw::c_int,
+                    ) -> pyo3::PyResult<()> {
+                        unsafe { #fill }
                     }
+
+                    #release
                 }
             };
-            extra.push(impl_block.into());
+            extra.push(buffer_impl.into());
+
+            // `__bytes__` is not a slot, so it's looked up like a regular method
+            let bytes_methods: ItemImpl = parse_quote! {
+                #[pyo3::prelude::pymethods]
+                impl #ident {
+                    fn __bytes__(&self) -> pyo3::PyObject {
+                        crate::langs::to_py_bytes(self)
+                    }
+
+                    #[classmethod]
+                    fn from_bytes(_cls: &pyo3::types::PyType, bytes: &pyo3::PyAny) -> pyo3::PyResult<Self> {
+                        crate::langs::from_py_bytes(bytes)
+                    }
+                }
+            };
+            extra.push(bytes_methods.into());
         }
-        if opts.contains(&ExposeStructOpts::ToDebug) {
+        if !object_methods.is_empty() {
             let impl_block: ItemImpl = parse_quote! {
                 #[pyo3::prelude::pyproto]
                 impl pyo3::class::PyObjectProtocol for #ident {
-                    fn __repr__(&self) -> String {
-                        format!("{:?}", self)
-                    }
+                    #(#object_methods)*
                 }
             };
             extra.push(impl_block.into());
@@ -170,7 +251,7 @@ impl Lang for Python {
 
     fn expose_impl(
         implementation: &mut ItemImpl,
-        mod_path: &Vec<Ident>,
+        _mod_path: &Vec<Ident>,
     ) -> Result<(), Self::Error> {
         implementation
             .attrs
@@ -178,8 +259,8 @@ impl Lang for Python {
 
         // remove items marked as "destructors" because pyo3 handles them automatically
         implementation.items.retain(|item| {
-            if let ImplItem::Method(ImplItemMethod { sig, attrs, .. }) = item {
-                if let Some(pos) = attrs.iter().position(|a| a.path.is_ident("destructor")) {
+            if let ImplItem::Method(ImplItemMethod { attrs, .. }) = item {
+                if attrs.iter().any(|a| a.path.is_ident("destructor")) {
                     return false;
                 }
             }
@@ -187,14 +268,29 @@ impl Lang for Python {
             true
         });
 
+        let mut has_default_constructor = false;
         for item in &mut implementation.items {
             if let ImplItem::Method(ImplItemMethod {
                 sig, attrs, block, ..
             }) = item
             {
-                let ident = &sig.ident;
+                let is_getter = attrs.iter().any(|a| a.path.is_ident("getter"));
+                if let Some(FnArg::Receiver(Receiver {
+                    mutability: Some(_),
+                    ..
+                })) = sig.inputs.first()
+                {
+                    // Getters only borrow mutably to clone the stored value
+                    if !is_getter {
+                        // `shared` structs can't be mutated while a view points at their bytes
+                        block
+                            .stmts
+                            .insert(0, parse_quote!(crate::langs::assert_mutable::<Self>();));
+                    }
+                }
 
-                let (mut args, input_conversion) = Self::convert_fn_args(sig.inputs.clone())?;
+                attrs.extend(default_args_attr(&sig.inputs)?);
+                let (mut args, input_conversion) = Self::convert_fn_args(sig.inputs.clone(), true)?;
                 let ExpandedReturn {
                     ret,
                     extra_args,
@@ -206,18 +302,30 @@ impl Lang for Python {
                 )?;
                 args.extend(extra_args);
 
-                if let Some(pos) = attrs.iter().position(|a| a.path.is_ident("constructor")) {
-                    attrs.remove(pos);
-                    attrs.push(parse_quote!( #[new] ));
-                    attrs.push(parse_quote!( #[allow(unused_variables)] ));
+                match take_constructor(attrs, sig)? {
+                    Some(ConstructorOpts { name: None }) => {
+                        if std::mem::replace(&mut has_default_constructor, true) {
+                            return Err(LangError::MultipleDefaultConstructors(sig.ident.span()).into());
+                        }
+
+                        attrs.push(parse_quote!( #[new] ));
+                        attrs.push(parse_quote!( #[allow(unused_variables)] ));
+
+                        args.push(parse_quote!(py: pyo3::Python<'_>));
+                    }
+                    // only one `#[new]` is allowed, the other constructors become classmethods
+                    Some(ConstructorOpts { name: Some(name) }) => {
+                        let name = name.to_string();
+                        attrs.push(parse_quote!( #[classmethod] ));
+                        attrs.push(parse_quote!( #[name = #name] ));
 
-                    args.push(parse_quote!(py: pyo3::Python<'_>));
-                } else {
-                    match args.first() {
+                        args.insert(0, parse_quote!(_cls: &pyo3::types::PyType));
+                    }
+                    None => match args.first() {
                         // the first argument is not some kind of "self", so this is a static method
                         None | Some(FnArg::Typed(_)) => attrs.push(parse_quote!( #[staticmethod] )),
                         _ => {}
-                    }
+                    },
                 }
 
                 sig.inputs = args;
@@ -383,7 +491,69 @@ impl Lang for Python {
         Ok(trait_struct_ident)
     }
 
+    fn expose_operators(
+        self_ty: &Type,
+        operators: Vec<ExposedOperator>,
+        _mod_path: &Vec<Ident>,
+        extra: &mut Vec<Item>,
+    ) -> Result<(), Self::Error> {
+        let mut number_methods = vec![];
+        let mut mapping_methods = vec![];
+        for ExposedOperator { op, rhs, output } in operators {
+            let method = format_ident!("__{}__", op.method());
+            let tr = op.trait_ident();
+            let op_method = op.method();
+
+            match op {
+                // pyo3 extracts the operands, so non-primitive types must be `Clone`
+                Operator::Add | Operator::Sub | Operator::Mul => number_methods.push(quote! {
+                    fn #method(lhs: Self, rhs: #rhs) -> #output {
+                        std::ops::#tr::#op_method(lhs, rhs)
+                    }
+                }),
+                Operator::Neg => number_methods.push(quote! {
+                    fn #method(&self) -> #output {
+                        std::ops::#tr::#op_method(self.clone())
+                    }
+                }),
+                // Raise an `IndexError` when out of bounds, which is also what stops iteration
+                Operator::Index => mapping_methods.push(quote! {
+                    fn __getitem__(&self, key: #rhs) -> Result<#output, crate::common::IndexError> {
+                        crate::common::CheckedIndex::checked_index(self, key)
+                            .cloned()
+                            .ok_or(crate::common::IndexError)
+                    }
+                }),
+            }
+        }
+
+        if !number_methods.is_empty() {
+            let impl_block: ItemImpl = parse_quote! {
+                #[pyo3::prelude::pyproto]
+                impl pyo3::class::PyNumberProtocol for #self_ty {
+                    #(#number_methods)*
+                }
+            };
+            extra.push(impl_block.into());
+        }
+        if !mapping_methods.is_empty() {
+            let impl_block: ItemImpl = parse_quote! {
+                #[pyo3::prelude::pyproto]
+                impl pyo3::class::PyMappingProtocol for #self_ty {
+                    #(#mapping_methods)*
+                }
+            };
+            extra.push(impl_block.into());
+        }
+
+        Ok(())
+    }
+
     fn convert_input(ty: Type) -> Result<Input, Self::Error> {
+        if let Some(target) = Self::custom_mapping(&ty) {
+            return Ok(Input::new_map_from(ty, vec![target]));
+        }
+
         // Take our opaque types as PyRef/PyRefMut instead of normal refs
         for t in &our_opaque_types!() {
             match ty {
@@ -416,6 +586,10 @@ impl Lang for Python {
         }
 
         if let Type::BareFn(ref bare_fn) = ty {
+            if !bare_fn.inputs.iter().all(|arg| arg.name.is_some()) {
+                return Err(PythonError::UnnamedCallbackArguments(bare_fn.span()));
+            }
+
             let inputs = bare_fn.inputs.clone();
             let output = bare_fn.output.clone();
 
@@ -463,28 +637,71 @@ impl Lang for Python {
     }
 
     fn convert_output(output: Type) -> Result<Output, Self::Error> {
-        Ok(Output::new_unchanged(output))
+        match Self::custom_mapping(&output) {
+            Some(target) => Ok(Output::new_map_to_single(output, target)),
+            None => Ok(Output::new_unchanged(output)),
+        }
     }
 }
 
 // fn generate_getter(field_name: &Ident, ) {
 // }
 
+/// Build the `#[args(...)]` attribute for the arguments with a default value, which are taken as
+/// `Option`s and can be omitted or passed by keyword from Python
+fn default_args_attr(inputs: &Punctuated<FnArg, Comma>) -> Result<Option<Attribute>, PythonError> {
+    let mut names = vec![];
+    for input in inputs {
+        if let FnArg::Typed(PatType { pat, attrs, .. }) = input {
+            if let (Pat::Ident(PatIdent { ident, .. }), Some(_)) = (pat.as_ref(), arg_default(attrs)?) {
+                names.push(ident);
+            }
+        }
+    }
+
+    if names.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(parse_quote!( #[args(#(#names = "None"),*)] )))
+    }
+}
+
 #[derive(Debug)]
 pub enum PythonError {
-    NakedFunction,
+    NakedFunction(Span),
+    UnnamedCallbackArguments(Span),
 
     Lang(LangError),
 }
 
 impl fmt::Display for PythonError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{:?}", self)
+        match self {
+            PythonError::NakedFunction(_) => write!(
+                f,
+                "Functions must be exposed as part of an `expose_mod` module in Python"
+            ),
+            PythonError::UnnamedCallbackArguments(_) => {
+                write!(f, "Callback arguments must be named in Python")
+            }
+            PythonError::Lang(e) => write!(f, "{}", e),
+        }
     }
 }
 
 impl std::error::Error for PythonError {}
 
+impl From<PythonError> for syn::Error {
+    fn from(e: PythonError) -> Self {
+        match e {
+            PythonError::Lang(e) => e.into(),
+            PythonError::NakedFunction(span) | PythonError::UnnamedCallbackArguments(span) => {
+                syn::Error::new(span, e)
+            }
+        }
+    }
+}
+
 impl From<LangError> for PythonError {
     fn from(e: LangError) -> Self {
         PythonError::Lang(e)
@@ -0,0 +1,964 @@
+//! Ruby extension through `magnus`
+//!
+//! Every `#[expose_mod]` becomes a Ruby module named after it in camel case, nested like the Rust
+//! modules, and the root one defines `Init_<crate>` so that the library can be `require`d.
+//!
+//! Exposed structs become `TypedData` classes of their module, which wrap the value in a
+//! `crate::langs::RbCell`. Ruby only hands out shared references to the wrapped data, so the
+//! methods are exported through a wrapper that borrows the cell for the duration of the call,
+//! mutably for the ones taking `&mut self`. The names are kept in snake case, constructors
+//! without a `name` become `new`, `to_string` and `to_debug` map to `to_s` and `inspect` and the
+//! operators to `+`, `-`, `*`, `-@` and `[]`. Arguments with a default value can be omitted, as
+//! long as they come last.
+//!
+//! Errors returned in a `Result` are raised as an exception class named after the error type,
+//! defined in the root module and derived from its `Error` class.
+//!
+//! Exposed traits generate a `<Trait>Struct` class constructed from a Ruby object that responds
+//! to the methods of the trait. Functions taking callbacks are not exported.
+
+use std::cell::RefCell;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote, ToTokens, TokenStreamExt};
+use syn::punctuated::Punctuated;
+use syn::{
+    Block, FnArg, Ident, ImplItem, ImplItemMethod, Item, ItemFn, PatType, Receiver, Signature,
+    Token, TraitItem, TraitItemMethod, TypePath, TypeReference, TypeSlice,
+};
+
+use super::*;
+use crate::types::*;
+
+#[derive(Debug)]
+pub struct Ruby;
+
+impl Lang for Ruby {
+    type Error = RubyError;
+
+    const NAME: &'static str = "ruby";
+
+    fn expose_fn(
+        function: &mut ItemFn,
+        mod_path: &Vec<Ident>,
+        _extra: &mut Vec<Item>,
+    ) -> Result<Ident, Self::Error> {
+        if mod_path.is_empty() {
+            return Err(RubyError::NakedFunction(function.sig.ident.span()));
+        }
+
+        let ident = function.sig.ident.clone();
+        let (args, ret, stmts, arity) =
+            match convert_signature(&function.sig, Body::Inline(&function.block)) {
+                Ok(converted) => converted,
+                Err(RubyError::Unsupported) => {
+                    strip_arg_attrs(&mut function.sig);
+                    return Ok(ident);
+                }
+                Err(e) => return Err(e),
+            };
+
+        let attrs = &function.attrs;
+        *function = parse_quote! {
+            #(#attrs)*
+            pub fn #ident(#args) #ret {
+                #stmts
+            }
+        };
+
+        FUNCTIONS.with(|functions| {
+            functions.borrow_mut().insert(
+                ident.to_string(),
+                Registration {
+                    kind: MethodKind::ModuleFunction,
+                    ruby_name: ident.to_string(),
+                    path: quote!(#ident),
+                    arity,
+                },
+            )
+        });
+
+        Ok(ident)
+    }
+
+    fn expose_mod(
+        module: &mut ItemMod,
+        mod_path: &Vec<Ident>,
+        sub_items: Vec<ModuleItem>,
+    ) -> Result<Ident, Self::Error> {
+        let ident = &module.ident;
+        let attrs = &module.attrs;
+        let content = &mut module.content.as_mut().expect("Empty module").1;
+
+        let mut content_tokens = TokenStream2::default();
+        content_tokens.append_all(content);
+
+        let mut export_tokens = TokenStream2::default();
+        for sub_item in sub_items {
+            let tokens = match sub_item {
+                ModuleItem::Function(ident) => {
+                    match FUNCTIONS
+                        .with(|functions| functions.borrow_mut().remove(&ident.to_string()))
+                    {
+                        Some(function) => function.to_token_stream(),
+                        // Not exported
+                        None => continue,
+                    }
+                }
+                ModuleItem::Structure(ident) | ModuleItem::Trait(ident) => {
+                    let name = ident.to_string();
+                    let methods = CLASSES
+                        .with(|classes| classes.borrow_mut().remove(&name))
+                        .unwrap_or_default();
+                    quote! {
+                        let class = m.define_class(#name, ruby.class_object())?;
+                        #(#methods)*
+                    }
+                }
+                ModuleItem::Module(ident) => {
+                    let name = camel_case(&ident.to_string(), true);
+                    quote! {
+                        let submod = m.define_module(#name)?;
+                        #ident::#ident(ruby, submod)?;
+                    }
+                }
+            };
+
+            export_tokens.extend(tokens);
+        }
+
+        // The library is loaded by calling `Init_<crate>`, which defines the root module and the
+        // exception classes of the errors returned by everything it contains
+        let mut init = TokenStream2::default();
+        if mod_path.len() == 1 {
+            let name = camel_case(&ident.to_string(), true);
+            let errors = ERRORS.with(|errors| std::mem::take(&mut *errors.borrow_mut()));
+            let errors = errors.iter();
+            init = quote! {
+                #[magnus::init]
+                fn __rb_init(ruby: &magnus::Ruby) -> Result<(), magnus::Error> {
+                    let m = ruby.define_module(#name)?;
+                    crate::langs::define_errors(ruby, m, &[#(#errors),*])?;
+
+                    #ident(ruby, m)
+                }
+            };
+        }
+
+        *module = parse_quote! {
+            #(#attrs)*
+            pub mod #ident {
+                #init
+                #[allow(unused_variables)]
+                pub(super) fn #ident(ruby: &magnus::Ruby, m: magnus::RModule) -> Result<(), magnus::Error> {
+                    use magnus::{Module, Object};
+
+                    #export_tokens
+                    Ok(())
+                }
+                #content_tokens
+            }
+        };
+
+        Ok(module.ident.clone())
+    }
+
+    fn expose_struct(
+        structure: &mut ItemStruct,
+        opts: Punctuated<ExposeStructOpts, Token![,]>,
+        mod_path: &Vec<Ident>,
+        extra: &mut Vec<Item>,
+    ) -> Result<Ident, Self::Error> {
+        let ident = structure.ident.clone();
+        structure.vis = parse_quote!(pub);
+        STRUCTS.with(|structs| structs.borrow_mut().insert(ident.to_string()));
+
+        let is_opaque = opts.iter().any(|o| *o == ExposeStructOpts::Opaque);
+        let impl_block = Self::generate_getters_setters(structure, is_opaque, mod_path)?;
+        extra.push(impl_block.into());
+
+        // Methods implemented by the options, the ones with the same name in the `impl` blocks
+        // are not exported
+        let mut methods: Vec<(ImplItemMethod, &str)> = vec![];
+        if opts.iter().any(|o| *o == ExposeStructOpts::ToString) {
+            methods.push((
+                parse_quote! {
+                    fn __to_s(&self) -> String {
+                        self.to_string()
+                    }
+                },
+                "to_s",
+            ));
+        }
+        if opts.iter().any(|o| *o == ExposeStructOpts::ToDebug) {
+            methods.push((
+                parse_quote! {
+                    fn __inspect(&self) -> String {
+                        format!("{:?}", self)
+                    }
+                },
+                "inspect",
+            ));
+        }
+        if opts.iter().any(|o| *o == ExposeStructOpts::Bytes) {
+            methods.push((
+                parse_quote! {
+                    fn __to_bytes(&self) -> Vec<u8> {
+                        crate::common::ByteContainer::as_bytes(self).to_vec()
+                    }
+                },
+                "to_bytes",
+            ));
+            methods.push((
+                parse_quote! {
+                    fn __from_bytes(bytes: Vec<u8>) -> Self {
+                        <Self as crate::common::ByteContainer>::from_bytes(bytes)
+                    }
+                },
+                "from_bytes",
+            ));
+        }
+        if !methods.is_empty() {
+            let mut impl_block: ItemImpl = parse_quote!(impl #ident {});
+            let mut wrappers = vec![];
+            for (mut method, ruby_name) in methods {
+                RESERVED.with(|reserved| {
+                    reserved
+                        .borrow_mut()
+                        .insert((ident.to_string(), ruby_name.to_string()))
+                });
+                expose_method(
+                    &mut method,
+                    &ident,
+                    Some(ruby_name.to_string()),
+                    &mut false,
+                    &mut wrappers,
+                )?;
+                impl_block.items.push(method.into());
+            }
+            impl_block
+                .items
+                .extend(wrappers.into_iter().map(ImplItem::Method));
+            extra.push(impl_block.into());
+        }
+
+        let class = class_path(mod_path, &ident);
+        extra.push(parse_quote!( crate::impl_ruby_class!(#ident, #class); ));
+
+        Ok(ident)
+    }
+
+    fn expose_impl(
+        implementation: &mut ItemImpl,
+        _mod_path: &Vec<Ident>,
+    ) -> Result<(), Self::Error> {
+        let self_ident = match implementation.self_ty.as_ref() {
+            Type::Path(TypePath { path, .. }) => path.segments.last().map(|s| s.ident.clone()),
+            _ => None,
+        }
+        .expect("Invalid `impl` type");
+
+        // The garbage collector drops the values, so destructors are not needed
+        implementation.items.retain(|item| {
+            !matches!(item, ImplItem::Method(ImplItemMethod { attrs, .. }) if attrs.iter().any(|a| a.path.is_ident("destructor")))
+        });
+
+        let mut has_default_constructor = false;
+        let mut wrappers = vec![];
+        for item in &mut implementation.items {
+            if let ImplItem::Method(method) = item {
+                expose_method(
+                    method,
+                    &self_ident,
+                    None,
+                    &mut has_default_constructor,
+                    &mut wrappers,
+                )?;
+            }
+        }
+        implementation
+            .items
+            .extend(wrappers.into_iter().map(ImplItem::Method));
+
+        Ok(())
+    }
+
+    fn expose_trait(
+        tr: &mut ItemTrait,
+        mod_path: &Vec<Ident>,
+        extra: &mut Vec<Item>,
+    ) -> Result<Ident, Self::Error> {
+        let ident = tr.ident.clone();
+
+        let mut methods = vec![];
+        for item in &mut tr.items {
+            if let TraitItem::Method(TraitItemMethod { attrs, sig, .. }) = item {
+                let original_ident = match attrs
+                    .iter()
+                    .position(|a| a.path.is_ident("expose_trait"))
+                {
+                    Some(pos) => attrs
+                        .remove(pos)
+                        .parse_args_with(
+                            Punctuated::<ExposeTraitOption, Comma>::parse_separated_nonempty,
+                        )
+                        .map_err(LangError::ExposeTraitAttrError)?
+                        .into_iter()
+                        .map(|ExposeTraitOption::Original(_, i)| Ident::new(&i.value(), i.span()))
+                        .last(),
+                    None => None,
+                }
+                .unwrap_or_else(|| sig.ident.clone());
+
+                methods.push((sig.clone(), original_ident));
+            }
+        }
+
+        let trait_struct_ident = format_ident!("{}Struct", ident);
+        let supertrait = &tr.supertraits[0];
+        let mut trait_struct: ItemStruct = parse_quote! {
+            pub struct #trait_struct_ident {
+                native: Option<Box<dyn #supertrait + Send>>,
+                ruby: Option<crate::langs::RbImpl>,
+            }
+        };
+        // The struct has to be declared before the `impl` blocks
+        let struct_pos = extra.len();
+        Self::expose_struct(
+            &mut trait_struct,
+            vec![ExposeStructOpts::Opaque].into_iter().collect(),
+            mod_path,
+            extra,
+        )?;
+        extra.insert(struct_pos, trait_struct.into());
+
+        // Methods of the class, which call either the native implementation or the Ruby one
+        let wrap_fns = methods.iter().map(|(sig, original_ident)| {
+            let ruby_method = original_ident.to_string();
+            let output = &sig.output;
+            let inputs = sig.inputs.iter();
+            let (arg_names, ruby_args): (Vec<_>, Vec<_>) = sig
+                .inputs
+                .iter()
+                .filter_map(|arg| match arg {
+                    FnArg::Receiver(_) => None,
+                    FnArg::Typed(PatType { pat, ty, .. }) => match ty.as_ref() {
+                        // Passed to Ruby as a copy
+                        Type::Reference(_) => Some((quote!(#pat), quote!(ToOwned::to_owned(#pat)))),
+                        _ => Some((quote!(#pat), quote!(#pat))),
+                    },
+                })
+                .unzip();
+            let call = match output {
+                ReturnType::Default => quote!(ruby.call_void(#ruby_method, (#(#ruby_args,)*))),
+                ReturnType::Type(..) => quote!(ruby.call(#ruby_method, (#(#ruby_args,)*))),
+            };
+
+            quote! {
+                pub fn #original_ident(#(#inputs),*) #output {
+                    if let Some(native) = &self.native {
+                        native.#original_ident(#(#arg_names),*)
+                    } else if let Some(ruby) = &self.ruby {
+                        #call.expect("Ruby call failed")
+                    } else {
+                        unreachable!()
+                    }
+                }
+            }
+        });
+        let mut impl_block: ItemImpl = parse_quote! {
+            impl #trait_struct_ident {
+                #[constructor]
+                pub fn new(implementation: crate::langs::RbImpl) -> Self {
+                    #trait_struct_ident {
+                        native: None,
+                        ruby: Some(implementation),
+                    }
+                }
+
+                #(#wrap_fns)*
+            }
+        };
+        Self::expose_impl(&mut impl_block, mod_path)?;
+        extra.push(impl_block.into());
+
+        // Impl the trait on the trait structure
+        let impl_methods = methods.iter().map(|(sig, original_ident)| {
+            let call_args = sig.inputs.iter().filter_map(|arg| match arg {
+                FnArg::Receiver(_) => None,
+                FnArg::Typed(PatType { pat, .. }) => Some(pat.to_token_stream()),
+            });
+
+            quote! {
+                #sig {
+                    self.#original_ident(#(#call_args),*)
+                }
+            }
+        });
+        let impl_on_trait_struct: ItemImpl = parse_quote! {
+            impl #ident for #trait_struct_ident {
+                #(#impl_methods)*
+            }
+        };
+        extra.push(impl_on_trait_struct.into());
+
+        let into_trait_struct: ItemImpl = parse_quote! {
+            impl<T: 'static + #supertrait + Sized + Send> crate::langs::IntoTraitStruct for T {
+                type Target = #trait_struct_ident;
+
+                fn into_trait_struct(self) -> Self::Target {
+                    #trait_struct_ident {
+                        native: Some(Box::new(self)),
+                        ruby: None,
+                    }
+                }
+            }
+        };
+        extra.push(into_trait_struct.into());
+
+        Ok(trait_struct_ident)
+    }
+
+    fn expose_operators(
+        self_ty: &Type,
+        operators: Vec<ExposedOperator>,
+        _mod_path: &Vec<Ident>,
+        extra: &mut Vec<Item>,
+    ) -> Result<(), Self::Error> {
+        let self_ident = match self_ty {
+            Type::Path(TypePath { path, .. }) => path.segments.last().map(|s| s.ident.clone()),
+            _ => None,
+        }
+        .expect("Invalid `impl` type");
+
+        let mut impl_block: ItemImpl = parse_quote! {
+            impl #self_ty {}
+        };
+        let mut wrappers = vec![];
+        for operator in operators {
+            let rhs_by_value = operator.rhs.as_ref().is_some_and(|rhs| !is_exposed(rhs));
+            let ruby_name = match operator.op {
+                Operator::Add => "+",
+                Operator::Sub => "-",
+                Operator::Mul => "*",
+                Operator::Neg => "-@",
+                Operator::Index => "[]",
+            };
+
+            let mut method = operator.wrapper(rhs_by_value);
+            expose_method(
+                &mut method,
+                &self_ident,
+                Some(ruby_name.to_string()),
+                &mut false,
+                &mut wrappers,
+            )?;
+            impl_block.items.push(method.into());
+        }
+        impl_block
+            .items
+            .extend(wrappers.into_iter().map(ImplItem::Method));
+        extra.push(impl_block.into());
+
+        Ok(())
+    }
+
+    fn convert_input(ty: Type) -> Result<Input, Self::Error> {
+        if let Some(target) = Self::custom_mapping(&ty) {
+            return Ok(Input::new_map_from(ty, vec![target]));
+        }
+
+        match &ty {
+            // Callbacks can't be passed from Ruby, expose a trait instead
+            Type::BareFn(_) => Err(RubyError::Unsupported),
+            // Exposed structs are borrowed from the cell of their Ruby object for the duration of
+            // the call
+            Type::Reference(TypeReference {
+                elem,
+                mutability: None,
+                ..
+            }) if is_exposed(elem) => Ok(Input::new_custom(
+                ty.clone(),
+                vec![parse_quote!(crate::langs::RbRef<#elem>)],
+                |_, ident| quote!(&*#ident).into(),
+            )),
+            // The guard can't be borrowed mutably once converted
+            Type::Reference(TypeReference {
+                elem,
+                mutability: Some(_),
+                ..
+            }) if is_exposed(elem) => Err(RubyError::Unsupported),
+            ty if is_exposed(ty) => Ok(Input::new_custom(
+                ty.clone(),
+                vec![parse_quote!(crate::langs::RbRef<#ty>)],
+                |_, ident| quote!(Clone::clone(&*#ident)).into(),
+            )),
+            // Binary data is passed as a `String`
+            Type::Reference(TypeReference { elem, .. }) if matches!(elem.as_ref(), Type::Slice(TypeSlice { elem, .. }) if **elem == parse_quote!(u8)) => {
+                Ok(Input::new_custom(
+                    ty.clone(),
+                    vec![parse_quote!(magnus::RString)],
+                    |_, ident| quote!(&<Vec<u8>>::map_from(#ident)).into(),
+                ))
+            }
+            ty if *ty == parse_quote!(Vec<u8>) => Ok(Input::new_map_from(
+                ty.clone(),
+                vec![parse_quote!(magnus::RString)],
+            )),
+            ty if *ty == parse_quote!(&str) => Ok(Input::new_custom(
+                ty.clone(),
+                vec![parse_quote!(String)],
+                |_, ident| quote!(#ident.as_str()).into(),
+            )),
+            _ => Ok(Input::new_unchanged(ty)),
+        }
+    }
+
+    fn convert_output(output: Type) -> Result<Output, Self::Error> {
+        if let Some(target) = Self::custom_mapping(&output) {
+            Ok(Output::new_map_to_single(output, target))
+        } else if output == parse_quote!(Vec<u8>) {
+            Ok(Output::new_map_to_single(
+                output,
+                parse_quote!(magnus::RString),
+            ))
+        } else {
+            Ok(Output::new_unchanged(output))
+        }
+    }
+
+    // Ruby can't hold the fields inline, but the structs store their fields as they are anyway
+    // (`WrappedStructField::Store` is the type itself), so transparent structs have the same
+    // accessors as opaque ones
+    fn expose_inline_getter(field: &mut Field, impl_block: &mut ItemImpl) -> Result<(), Self::Error> {
+        Self::expose_getter(field, true, impl_block)
+    }
+
+    fn expose_inline_setter(
+        field: &mut Field,
+        validate: Option<&Path>,
+        impl_block: &mut ItemImpl,
+    ) -> Result<(), Self::Error> {
+        Self::expose_setter(field, true, validate, impl_block)
+    }
+
+    // The fields are copied in and out of Ruby, so the getters and setters use the types
+    // directly
+    fn convert_getter_setter_ty(ty: Type) -> Result<(Type, Type), Self::Error> {
+        Ok((ty.clone(), ty))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum MethodKind {
+    /// Instance method, called with the object as first argument
+    Method,
+    /// Method of the class, like constructors and static methods
+    Singleton,
+    /// Function of a module
+    ModuleFunction,
+}
+
+/// Function or method defined on a Ruby module or class when the library is loaded
+struct Registration {
+    kind: MethodKind,
+    ruby_name: String,
+    /// Path of the Rust function
+    path: TokenStream2,
+    /// Number of arguments, `-1` when some of them can be omitted
+    arity: isize,
+}
+
+impl ToTokens for Registration {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        let Registration {
+            kind,
+            ruby_name,
+            path,
+            arity,
+        } = self;
+        let arity = proc_macro2::Literal::isize_unsuffixed(*arity);
+
+        tokens.extend(match kind {
+            MethodKind::Method => {
+                quote!( class.define_method(#ruby_name, magnus::method!(#path, #arity))?; )
+            }
+            MethodKind::Singleton => {
+                quote!( class.define_singleton_method(#ruby_name, magnus::function!(#path, #arity))?; )
+            }
+            MethodKind::ModuleFunction => {
+                quote!( m.define_module_function(#ruby_name, magnus::function!(#path, #arity))?; )
+            }
+        });
+    }
+}
+
+thread_local! {
+    /// Names of the exposed structs, which are taken through their Ruby object
+    static STRUCTS: RefCell<BTreeSet<String>> = const { RefCell::new(BTreeSet::new()) };
+    /// Ruby names of the methods implemented by the `#[expose_struct]` options, by struct
+    static RESERVED: RefCell<BTreeSet<(String, String)>> = const { RefCell::new(BTreeSet::new()) };
+    /// Methods of the exposed structs, by struct
+    static CLASSES: RefCell<HashMap<String, Vec<Registration>>> = RefCell::new(HashMap::new());
+    /// Exported functions, by name
+    static FUNCTIONS: RefCell<HashMap<String, Registration>> = RefCell::new(HashMap::new());
+    /// Names of the error types returned by the exported functions
+    static ERRORS: RefCell<BTreeSet<String>> = const { RefCell::new(BTreeSet::new()) };
+}
+
+/// Fully qualified name of the class of a struct, like `Bitcoin::Script`
+fn class_path(mod_path: &[Ident], ident: &Ident) -> String {
+    mod_path
+        .iter()
+        .map(|module| camel_case(&module.to_string(), true))
+        .chain(std::iter::once(ident.to_string()))
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+/// Whether the type is an exposed struct, which is wrapped by a Ruby object
+fn is_exposed(ty: &Type) -> bool {
+    if *ty == parse_quote!(Self) || our_opaque_types!().iter().any(|t| t == ty) {
+        return true;
+    }
+
+    match ty {
+        Type::Path(TypePath { qself: None, path }) => match path.get_ident() {
+            Some(ident) => STRUCTS.with(|structs| structs.borrow().contains(&ident.to_string())),
+            None => false,
+        },
+        _ => false,
+    }
+}
+
+fn strip_arg_attrs(sig: &mut Signature) {
+    for input in &mut sig.inputs {
+        if let FnArg::Typed(PatType { attrs, .. }) = input {
+            attrs.retain(|a| !a.path.is_ident("expose"));
+        }
+    }
+}
+
+/// The `Ok` and `Err` types of a `Result`
+fn result_types(ty: &Type) -> Option<(Type, Type)> {
+    match match_generic_type(ty, parse_quote!(Result))?.as_slice() {
+        [ok, err] => Some((ok.clone(), err.clone())),
+        _ => None,
+    }
+}
+
+/// Remember the error type, so that its exception class is defined. The errors defined in this
+/// crate are mapped to the Ruby ones instead
+fn record_error(err: &Type) {
+    if let Type::Path(TypePath { qself: None, path }) = err {
+        if path.segments.first().is_some_and(|s| s.ident == "crate") {
+            return;
+        }
+        if let Some(segment) = path.segments.last() {
+            ERRORS.with(|errors| errors.borrow_mut().insert(segment.ident.to_string()));
+        }
+    }
+}
+
+/// Original body of an exported function
+enum Body<'a> {
+    Inline(&'a Block),
+    /// Call to the original method, kept as it is
+    Call(TokenStream2),
+}
+
+/// Convert the arguments and the return value of an exported function, returning the new
+/// arguments, return type, body and arity. The receiver is left to the caller
+fn convert_signature(
+    sig: &Signature,
+    body: Body,
+) -> Result<(Punctuated<FnArg, Comma>, ReturnType, TokenStream2, isize), RubyError> {
+    let inputs = sig
+        .inputs
+        .iter()
+        .filter(|input| matches!(input, FnArg::Typed(_)))
+        .cloned()
+        .collect::<Vec<_>>();
+    let (mut args, input_conversion) = Ruby::convert_fn_args(inputs.clone(), true)?;
+
+    // Each argument with a default is converted to a single `Option`, which Ruby lets the
+    // caller omit only at the end of the call
+    let mut optional = 0;
+    for input in &inputs {
+        if let FnArg::Typed(PatType { attrs, pat, .. }) = input {
+            match arg_default(attrs)? {
+                Some(_) => optional += 1,
+                None if optional > 0 => return Err(RubyError::DefaultNotLast(pat.span())),
+                None => {}
+            }
+        }
+    }
+    let mut unpack = TokenStream2::default();
+    let arity = if optional > 0 {
+        let (names, types): (Vec<_>, Vec<_>) = args
+            .iter()
+            .filter_map(|arg| match arg {
+                FnArg::Typed(PatType { pat, ty, .. }) => Some((pat.clone(), ty.clone())),
+                FnArg::Receiver(_) => None,
+            })
+            .unzip();
+        let required = names.len() - optional;
+        let (required_names, optional_names) = names.split_at(required);
+        let (required_types, optional_types) = types.split_at(required);
+        unpack = quote! {
+            let __args = magnus::scan_args::scan_args::<(#(#required_types,)*), (#(#optional_types,)*), (), (), (), ()>(__args)?;
+            let (#(#required_names,)*) = __args.required;
+            let (#(#optional_names,)*) = __args.optional;
+        };
+        args = parse_quote!(__args: &[magnus::Value]);
+
+        -1
+    } else {
+        args.len() as isize
+    };
+
+    // The error is converted after the call, so `?` in the body needs the original type
+    let output = sig.output.as_type();
+    let (ok, unwrap) = match result_types(&output) {
+        Some((ok, err)) => {
+            record_error(&err);
+            (
+                ok,
+                quote!(let __output = __output.map_err(crate::langs::RubyError::from)?;),
+            )
+        }
+        None => (output.clone(), quote!()),
+    };
+    let fallible = optional > 0 || !unwrap.is_empty();
+    let ExpandedOutput { ty, conv, .. } =
+        Ruby::convert_output(ok)?.expand(&format_ident!("__output"));
+    let ty = ty.into_iter().map(|t| *t).as_tuple();
+    let (ret, output_conversion) = match fallible {
+        true => (
+            parse_quote!(-> Result<#ty, crate::langs::RubyError>),
+            quote! {
+                #unwrap
+                #conv
+                Ok(__output)
+            },
+        ),
+        false => (
+            parse_quote!(-> #ty),
+            quote! {
+                #conv
+                __output
+            },
+        ),
+    };
+
+    let call = match body {
+        Body::Inline(block) => {
+            let closure_ret = match unwrap.is_empty() {
+                true => quote!(),
+                false => quote!(-> #output),
+            };
+            quote! {
+                let mut block_closure = move || #closure_ret { #block };
+                let __output = block_closure();
+            }
+        }
+        Body::Call(call) => quote! {
+            let __output = #call;
+        },
+    };
+
+    Ok((
+        args,
+        ret,
+        quote! {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+
+            #unpack
+            #input_conversion
+
+            #call
+
+            #output_conversion
+        },
+        arity,
+    ))
+}
+
+/// Add a `__rb_<method>` wrapper which borrows the object and converts the arguments, and
+/// register it under its Ruby name. The original method is kept, and left unexported when it
+/// can't be
+fn expose_method(
+    method: &mut ImplItemMethod,
+    self_ident: &Ident,
+    ruby_name: Option<String>,
+    has_default_constructor: &mut bool,
+    wrappers: &mut Vec<ImplItemMethod>,
+) -> Result<(), RubyError> {
+    let ImplItemMethod { attrs, sig, .. } = method;
+    let name = sig.ident.to_string();
+
+    let (kind, ruby_name) = match take_constructor(attrs, sig)? {
+        Some(ConstructorOpts { name: None }) => {
+            if std::mem::replace(has_default_constructor, true) {
+                return Err(LangError::MultipleDefaultConstructors(sig.ident.span()).into());
+            }
+
+            (MethodKind::Singleton, "new".to_string())
+        }
+        Some(ConstructorOpts { name: Some(name) }) => (MethodKind::Singleton, name.to_string()),
+        None => match attrs
+            .iter()
+            .position(|a| a.path.is_ident("getter") || a.path.is_ident("setter"))
+        {
+            Some(pos) => match attrs.remove(pos).path.is_ident("getter") {
+                true => (
+                    MethodKind::Method,
+                    name.trim_start_matches("get_").to_string(),
+                ),
+                false => (
+                    MethodKind::Method,
+                    format!("{}=", name.trim_start_matches("set_")),
+                ),
+            },
+            None => {
+                let kind = match sig.receiver() {
+                    Some(_) => MethodKind::Method,
+                    None => MethodKind::Singleton,
+                };
+                match ruby_name {
+                    Some(ruby_name) => (kind, ruby_name),
+                    None => {
+                        let reserved = RESERVED.with(|reserved| {
+                            reserved
+                                .borrow()
+                                .contains(&(self_ident.to_string(), name.clone()))
+                        });
+                        if reserved {
+                            strip_arg_attrs(sig);
+                            return Ok(());
+                        }
+
+                        (kind, name.clone())
+                    }
+                }
+            }
+        },
+    };
+
+    // The object is taken as the first argument
+    let (receiver, self_arg) = match sig.receiver() {
+        Some(FnArg::Receiver(Receiver {
+            reference: Some(_),
+            mutability: None,
+            ..
+        })) => (
+            Some(quote!(rb_self: crate::langs::RbRef<Self>)),
+            quote!(&rb_self),
+        ),
+        Some(FnArg::Receiver(Receiver {
+            reference: Some(_),
+            mutability: Some(_),
+            ..
+        })) => (
+            Some(quote!(mut rb_self: crate::langs::RbRefMut<Self>)),
+            quote!(&mut rb_self),
+        ),
+        Some(_) => (
+            Some(quote!(rb_self: crate::langs::RbRef<Self>)),
+            quote!(Clone::clone(&*rb_self)),
+        ),
+        None => (None, quote!()),
+    };
+    let ident = sig.ident.clone();
+    let call_args = sig.inputs.iter().filter_map(|input| match input {
+        FnArg::Typed(PatType { pat, .. }) => Some(pat.to_token_stream()),
+        FnArg::Receiver(_) => None,
+    });
+    let call = match receiver {
+        Some(_) => quote!(Self::#ident(#self_arg, #(#call_args),*)),
+        None => quote!(Self::#ident(#(#call_args),*)),
+    };
+
+    let (args, ret, stmts, arity) = match convert_signature(sig, Body::Call(call)) {
+        Ok(converted) => converted,
+        Err(RubyError::Unsupported) => {
+            strip_arg_attrs(sig);
+            return Ok(());
+        }
+        Err(e) => return Err(e),
+    };
+    strip_arg_attrs(sig);
+
+    let wrapper_ident = format_ident!("__rb_{}", ident);
+    let receiver = receiver.into_iter();
+    wrappers.push(parse_quote! {
+        pub fn #wrapper_ident(#(#receiver,)* #args) #ret {
+            #stmts
+        }
+    });
+
+    CLASSES.with(|classes| {
+        classes
+            .borrow_mut()
+            .entry(self_ident.to_string())
+            .or_default()
+            .push(Registration {
+                kind,
+                ruby_name,
+                path: quote!(#self_ident::#wrapper_ident),
+                arity,
+            })
+    });
+
+    Ok(())
+}
+
+#[derive(Debug)]
+pub enum RubyError {
+    NakedFunction(Span),
+    DefaultNotLast(Span),
+    /// Item that can't be exported, which is left as it is
+    Unsupported,
+
+    Lang(LangError),
+}
+
+impl fmt::Display for RubyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RubyError::NakedFunction(_) => write!(
+                f,
+                "Functions must be exposed as part of an `expose_mod` module in Ruby"
+            ),
+            RubyError::DefaultNotLast(_) => write!(
+                f,
+                "Arguments with a default value must come after the other ones in Ruby"
+            ),
+            RubyError::Unsupported => write!(f, "Not supported in Ruby"),
+            RubyError::Lang(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for RubyError {}
+
+impl From<RubyError> for syn::Error {
+    fn from(e: RubyError) -> Self {
+        match e {
+            RubyError::Lang(e) => e.into(),
+            RubyError::NakedFunction(span) | RubyError::DefaultNotLast(span) => {
+                syn::Error::new(span, e)
+            }
+            RubyError::Unsupported => syn::Error::new(Span::call_site(), e),
+        }
+    }
+}
+
+impl From<LangError> for RubyError {
+    fn from(e: LangError) -> Self {
+        RubyError::Lang(e)
+    }
+}
@@ -0,0 +1,863 @@
+//! Swift package over the C API
+//!
+//! Like the C++ layer, the Rust side is generated by the C backend while the original signatures
+//! of the exposed items are recorded. When the root `#[expose_mod]` is expanded they are rendered
+//! to a Swift source that wraps every opaque struct in a final class that frees its pointer in
+//! `deinit`, throws error codes as Swift `enum`s and converts strings and byte vectors from
+//! `String` and `Data`. The source is written to `OUT_DIR` as `<crate>_<module>.swift`, and
+//! imports the C header as the `C<Crate>` clang module.
+
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use quote::ToTokens;
+use syn::punctuated::Punctuated;
+use syn::{
+    Ident, Item, ItemFn, ItemImpl, ItemMod, ItemStruct, ItemTrait, Path, Token, Type, TypePath,
+    TypeReference, TypeSlice,
+};
+
+use super::c::{CError, C};
+use super::c_family::{self, CFamily, Class, Method, MethodKind, Recorded};
+use super::*;
+use crate::types::*;
+
+#[derive(Debug)]
+pub struct Swift;
+
+impl CFamily for Swift {
+    const OUTPUT: &'static str = "SWIFT";
+
+    fn render(root: &Ident, items: Recorded) -> Vec<(PathBuf, String)> {
+        let file_name = format!("{}_{}.swift", lib_name(), root);
+        vec![(file_name.into(), render_source(items))]
+    }
+}
+
+impl Lang for Swift {
+    type Error = CError;
+
+    // The Rust side is the C API, so it uses the same mappings
+    const NAME: &'static str = C::NAME;
+
+    fn expose_fn(
+        function: &mut ItemFn,
+        mod_path: &Vec<Ident>,
+        extra: &mut Vec<Item>,
+    ) -> Result<Ident, Self::Error> {
+        c_family::expose_fn::<Self>(function, mod_path, extra)
+    }
+
+    fn expose_mod(
+        module: &mut ItemMod,
+        mod_path: &Vec<Ident>,
+        sub_items: Vec<ModuleItem>,
+    ) -> Result<Ident, Self::Error> {
+        c_family::expose_mod::<Self>(module, mod_path, sub_items)
+    }
+
+    fn expose_struct(
+        structure: &mut ItemStruct,
+        opts: Punctuated<ExposeStructOpts, Token![,]>,
+        mod_path: &Vec<Ident>,
+        extra: &mut Vec<Item>,
+    ) -> Result<Ident, Self::Error> {
+        c_family::expose_struct(structure, opts, mod_path, extra)
+    }
+
+    fn expose_impl(
+        implementation: &mut ItemImpl,
+        mod_path: &Vec<Ident>,
+    ) -> Result<(), Self::Error> {
+        c_family::expose_impl::<Self>(implementation, mod_path)
+    }
+
+    fn expose_trait(
+        tr: &mut ItemTrait,
+        mod_path: &Vec<Ident>,
+        extra: &mut Vec<Item>,
+    ) -> Result<Ident, Self::Error> {
+        // Callbacks are only available through the C API
+        c_family::expose_trait::<Self>(tr, mod_path, extra)
+    }
+
+    fn expose_operators(
+        self_ty: &Type,
+        operators: Vec<ExposedOperator>,
+        mod_path: &Vec<Ident>,
+        extra: &mut Vec<Item>,
+    ) -> Result<(), Self::Error> {
+        c_family::expose_operators::<Self>(self_ty, operators, mod_path, extra)
+    }
+
+    fn expose_getter(
+        field: &mut Field,
+        is_opaque: bool,
+        impl_block: &mut ItemImpl,
+    ) -> Result<(), Self::Error> {
+        C::expose_getter(field, is_opaque, impl_block)
+    }
+
+    fn expose_inline_getter(
+        field: &mut Field,
+        impl_block: &mut ItemImpl,
+    ) -> Result<(), Self::Error> {
+        C::expose_inline_getter(field, impl_block)
+    }
+
+    fn expose_inline_setter(
+        field: &mut Field,
+        validate: Option<&Path>,
+        impl_block: &mut ItemImpl,
+    ) -> Result<(), Self::Error> {
+        C::expose_inline_setter(field, validate, impl_block)
+    }
+
+    fn convert_getter_setter_ty(ty: Type) -> Result<(Type, Type), Self::Error> {
+        C::convert_getter_setter_ty(ty)
+    }
+
+    fn convert_input(ty: Type) -> Result<Input, Self::Error> {
+        C::convert_input(ty)
+    }
+
+    fn convert_output(output: Type) -> Result<Output, Self::Error> {
+        C::convert_output(output)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SwiftOperator {
+    Infix(&'static str),
+    Prefix(&'static str),
+    Subscript,
+}
+
+fn swift_operator(op: Operator) -> SwiftOperator {
+    match op {
+        Operator::Add => SwiftOperator::Infix("+"),
+        Operator::Sub => SwiftOperator::Infix("-"),
+        Operator::Mul => SwiftOperator::Infix("*"),
+        Operator::Neg => SwiftOperator::Prefix("-"),
+        Operator::Index => SwiftOperator::Subscript,
+    }
+}
+
+/// Swift keywords are escaped with backticks
+fn escape_keyword<T: AsRef<str>>(ident: T) -> String {
+    const KEYWORDS: &[&str] = &[
+        "associatedtype",
+        "case",
+        "class",
+        "default",
+        "defer",
+        "deinit",
+        "do",
+        "extension",
+        "fallthrough",
+        "func",
+        "guard",
+        "import",
+        "init",
+        "inout",
+        "internal",
+        "is",
+        "let",
+        "operator",
+        "private",
+        "protocol",
+        "public",
+        "repeat",
+        "rethrows",
+        "subscript",
+        "switch",
+        "throw",
+        "throws",
+        "try",
+        "typealias",
+        "var",
+        "where",
+        "while",
+    ];
+
+    let ident = ident.as_ref();
+    if KEYWORDS.contains(&ident) {
+        format!("`{}`", ident)
+    } else {
+        ident.to_string()
+    }
+}
+
+fn param_name(ident: &Ident) -> String {
+    escape_keyword(camel_case(&ident.to_string(), false))
+}
+
+/// How a Rust type crosses the C API, from the point of view of the Swift layer
+#[derive(Debug, Clone)]
+enum SwiftType<'a> {
+    Unit,
+    Primitive(&'static str),
+    String,
+    /// Opaque struct, passed as an `OpaquePointer` and returned through an out pointer
+    Class(&'a Class),
+    /// Transparent struct, passed by value
+    Plain(&'a Class),
+    Optional(&'a Class),
+    /// `Vec<u8>` or `&[u8]`. Crosses the C API as an `Arr_u8`
+    Data,
+    /// `Vec` or slice of other primitives, only as an argument. Passed as an `Arr<T>`, with the
+    /// Swift and the Rust names of the primitive
+    Array(&'static str, String),
+    /// Error code, with the ok value returned through an out pointer
+    Result(Box<SwiftType<'a>>, String),
+}
+
+fn primitive(ty: &Type) -> Option<&'static str> {
+    Some(match c_family::primitive_ident(ty)?.as_str() {
+        "bool" => "Bool",
+        "i8" => "Int8",
+        "u8" => "UInt8",
+        "i16" => "Int16",
+        "u16" => "UInt16",
+        "i32" => "Int32",
+        "u32" => "UInt32",
+        "i64" => "Int64",
+        "u64" => "UInt64",
+        "isize" => "Int",
+        "usize" => "UInt",
+        "f32" => "Float",
+        "f64" => "Double",
+        _ => return None,
+    })
+}
+
+struct Context<'a> {
+    /// Name of the library, in `CamelCase`
+    lib: String,
+    /// Name of the clang module of the C header
+    c_module: String,
+    c_prefix: String,
+    classes: &'a [Class],
+    errors: RefCell<BTreeSet<String>>,
+}
+
+/// A closure around the call to the C API, that gives access to the bytes of an argument
+struct Wrapper {
+    receiver: String,
+    method: &'static str,
+    binding: String,
+}
+
+impl<'a> Context<'a> {
+    fn class(&self, ty: &Type, self_class: Option<&'a Class>) -> Option<&'a Class> {
+        if match_fixed_type(ty, parse_quote!(Self)) {
+            return self_class;
+        }
+        match ty {
+            Type::Path(TypePath { path, .. }) => {
+                let ident = &path.segments.last()?.ident;
+                self.classes.iter().find(|c| &c.ident == ident)
+            }
+            _ => None,
+        }
+    }
+
+    fn error(&self, ty: &Type) -> Option<String> {
+        let ident = match ty {
+            Type::Path(TypePath { path, .. }) => path.segments.last()?.ident.to_string(),
+            _ => return None,
+        };
+        if lookup_mapping(C::NAME, ty).is_none()
+            && ident != "ValidationError"
+            && ident != "IndexError"
+        {
+            return None;
+        }
+
+        // `Error` would shadow `Swift.Error`
+        let name = match ident.as_str() {
+            "Error" => format!("{}Error", self.lib),
+            _ => ident,
+        };
+        self.errors.borrow_mut().insert(name.clone());
+        Some(name)
+    }
+
+    fn classify(&self, ty: &Type, self_class: Option<&'a Class>) -> Option<SwiftType<'a>> {
+        if let Some(target) = lookup_mapping(C::NAME, ty) {
+            return self.classify(&target, self_class);
+        }
+        if let Type::Tuple(tuple) = ty {
+            return if tuple.elems.is_empty() {
+                Some(SwiftType::Unit)
+            } else {
+                None
+            };
+        }
+        if let Some(name) = primitive(ty) {
+            return Some(SwiftType::Primitive(name));
+        }
+        if match_fixed_type(ty, parse_quote!(String)) {
+            return Some(SwiftType::String);
+        }
+        // Returned vectors are freed with `bytes_free()`
+        if ty == &parse_quote!(Vec<u8>) {
+            return Some(SwiftType::Data);
+        }
+        if let Some(inner) = match_generic_type(ty, parse_quote!(Option)) {
+            return match self.class(&inner[0], self_class) {
+                Some(class) if class.opaque => Some(SwiftType::Optional(class)),
+                _ => None,
+            };
+        }
+        if let Some(inner) = match_generic_type(ty, parse_quote!(Result)) {
+            if inner.len() != 2 {
+                return None;
+            }
+            let ok = match self.classify(&inner[0], self_class)? {
+                ok @ SwiftType::Unit
+                | ok @ SwiftType::Primitive(_)
+                | ok @ SwiftType::String
+                | ok @ SwiftType::Data
+                | ok @ SwiftType::Class(_) => ok,
+                _ => return None,
+            };
+            return Some(SwiftType::Result(Box::new(ok), self.error(&inner[1])?));
+        }
+
+        self.class(ty, self_class).map(|class| match class.opaque {
+            true => SwiftType::Class(class),
+            false => SwiftType::Plain(class),
+        })
+    }
+
+    fn classify_input(&self, ty: &Type, self_class: Option<&'a Class>) -> Option<SwiftType<'a>> {
+        let sequence = |elem: &Type| {
+            Some(match primitive(elem)? {
+                "UInt8" => SwiftType::Data,
+                name => SwiftType::Array(name, elem.to_token_stream().to_string()),
+            })
+        };
+        if let Some(inner) = match_generic_type(ty, parse_quote!(Vec)) {
+            return sequence(&inner[0]);
+        }
+
+        match ty {
+            Type::Reference(TypeReference { elem, .. }) => match elem.as_ref() {
+                // Slices are passed like vectors
+                Type::Slice(TypeSlice { elem, .. }) => sequence(elem),
+                // Opaque structs are only taken by reference
+                elem => match self.classify(elem, self_class)? {
+                    class @ SwiftType::Class(_) => Some(class),
+                    _ => None,
+                },
+            },
+            ty => match self.classify(ty, self_class)? {
+                ty @ SwiftType::Primitive(_) | ty @ SwiftType::String | ty @ SwiftType::Plain(_) => {
+                    Some(ty)
+                }
+                _ => None,
+            },
+        }
+    }
+
+    /// Modules other than the root one become caseless `enum`s, used as namespaces
+    fn namespace(&self, mod_path: &[Ident]) -> String {
+        mod_path
+            .iter()
+            .skip(1)
+            .map(|m| camel_case(&m.to_string(), true))
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+
+    /// Name of a class as seen from the namespace of `from`
+    fn class_name(&self, class: &Class, from: &[Ident]) -> String {
+        let namespace = self.namespace(&class.mod_path);
+        if class.mod_path == from || namespace.is_empty() {
+            class.ident.to_string()
+        } else {
+            format!("{}.{}", namespace, class.ident)
+        }
+    }
+
+    fn type_name(&self, ty: &SwiftType, from: &[Ident]) -> String {
+        match ty {
+            SwiftType::Unit => "Void".into(),
+            SwiftType::Primitive(name) => name.to_string(),
+            SwiftType::String => "String".into(),
+            SwiftType::Class(class) | SwiftType::Plain(class) => self.class_name(class, from),
+            SwiftType::Optional(class) => format!("{}?", self.class_name(class, from)),
+            SwiftType::Result(ok, _) => self.type_name(ok, from),
+            SwiftType::Data => "Data".into(),
+            SwiftType::Array(name, _) => format!("[{}]", name),
+        }
+    }
+
+    /// The argument passed to the C API for a parameter, with the closure it has to be wrapped
+    /// in, if any
+    fn arg(&self, ident: &Ident, ty: &SwiftType) -> (String, Option<Wrapper>) {
+        let name = param_name(ident);
+        let binding = format!("{}_", camel_case(&ident.to_string(), false));
+        match ty {
+            SwiftType::Class(_) => (format!("{}.ptr", name), None),
+            SwiftType::Data => (
+                format!(
+                    "{}.{}Arr_u8(ptr: {binding}.bindMemory(to: UInt8.self).baseAddress, len: UInt({binding}.count))",
+                    self.c_module,
+                    self.c_prefix,
+                    binding = binding
+                ),
+                Some(Wrapper {
+                    receiver: name,
+                    method: "withUnsafeBytes",
+                    binding,
+                }),
+            ),
+            SwiftType::Array(_, rust_name) => (
+                format!(
+                    "{}.{}Arr_{}(ptr: {binding}.baseAddress, len: UInt({binding}.count))",
+                    self.c_module,
+                    self.c_prefix,
+                    rust_name,
+                    binding = binding
+                ),
+                Some(Wrapper {
+                    receiver: name,
+                    method: "withUnsafeBufferPointer",
+                    binding,
+                }),
+            ),
+            _ => (name, None),
+        }
+    }
+
+    /// Declare the out pointer for a value returned by reference, and the expression that
+    /// converts it to the Swift type
+    fn out_value(&self, ty: &SwiftType, from: &[Ident]) -> Option<(String, String)> {
+        match ty {
+            SwiftType::Primitive(name) => Some((format!("var out = {}()", name), "out".into())),
+            SwiftType::String => Some((
+                "var out: UnsafeMutablePointer<CChar>? = nil".into(),
+                "takeString(out!)".into(),
+            )),
+            SwiftType::Data => Some((
+                format!("var out = {}.{}Arr_u8()", self.c_module, self.c_prefix),
+                "takeBytes(out)".into(),
+            )),
+            SwiftType::Class(class) => Some((
+                "var out: OpaquePointer? = nil".into(),
+                format!("{}(ptr: out!)", self.class_name(class, from)),
+            )),
+            _ => None,
+        }
+    }
+
+    /// Lines of the body that calls `symbol`. `ret` is how the converted value is returned:
+    /// `return` for functions, or the initializer that takes the pointer for constructors
+    fn body(
+        &self,
+        symbol: &Ident,
+        mut args: Vec<String>,
+        wrappers: &[Wrapper],
+        output: &SwiftType,
+        from: &[Ident],
+    ) -> Vec<String> {
+        let call = |args: &[String]| {
+            let call = format!("{}.{}({})", self.c_module, symbol, args.join(", "));
+            wrappers.iter().rev().fold(call, |call, w| {
+                format!("{}.{} {{ {} in {} }}", w.receiver, w.method, w.binding, call)
+            })
+        };
+
+        match output {
+            SwiftType::Unit => vec![call(&args)],
+            SwiftType::Primitive(_) | SwiftType::Plain(_) => vec![format!("return {}", call(&args))],
+            SwiftType::String => vec![format!("return takeString({})", call(&args))],
+            SwiftType::Data => vec![format!("return takeBytes({})", call(&args))],
+            SwiftType::Array(..) => unreachable!("Other vectors are only taken as arguments"),
+            SwiftType::Class(class) | SwiftType::Optional(class) => {
+                args.push("&out".into());
+
+                let mut lines = vec![
+                    "var out: OpaquePointer? = nil".to_string(),
+                    call(&args),
+                ];
+                match output {
+                    SwiftType::Optional(_) => lines.push(format!(
+                        "return out.map {{ {}(ptr: $0) }}",
+                        self.class_name(class, from)
+                    )),
+                    _ => lines.push(format!("return {}(ptr: out!)", self.class_name(class, from))),
+                }
+
+                lines
+            }
+            SwiftType::Result(ok, error) => {
+                let out = self.out_value(ok, from);
+                let mut lines = vec![];
+                if let Some((decl, _)) = &out {
+                    lines.push(decl.clone());
+                    args.push("&out".into());
+                }
+                lines.push(format!("let code = {}", call(&args)));
+                lines.push("if code != 0 {".into());
+                lines.push(format!("    throw {}.failed(code: code)", error));
+                lines.push("}".into());
+                if let Some((_, value)) = out {
+                    lines.push(format!("return {}", value));
+                }
+
+                lines
+            }
+        }
+    }
+}
+
+/// Render a method of a class, or a function when `class` is `None`. `level` is the indentation
+/// of the declaration. Returns `None` if some types are not supported
+fn render_method(
+    ctx: &Context,
+    class: Option<&Class>,
+    method: &Method,
+    from: &[Ident],
+    level: usize,
+) -> Option<String> {
+    let operator = match method.kind {
+        MethodKind::Operator(op) => Some(swift_operator(op)),
+        _ => None,
+    };
+    let receiver = match (&method.kind, operator) {
+        (_, Some(SwiftOperator::Infix(_))) => Some("lhs.ptr"),
+        (_, Some(SwiftOperator::Prefix(_))) => Some("operand.ptr"),
+        (MethodKind::Constructor, _) | (MethodKind::Static, _) => None,
+        _ => Some("ptr"),
+    };
+
+    let mut params = vec![];
+    let mut args = receiver.map(String::from).into_iter().collect::<Vec<_>>();
+    let mut wrappers = vec![];
+    for (i, (ident, ty)) in method.inputs.iter().enumerate() {
+        let ty = ctx.classify_input(ty, class)?;
+        // Only the first argument is unlabeled, operands never have a label
+        let label = match (i, &method.kind) {
+            (_, MethodKind::Operator(_)) => "",
+            (0, _) => "_ ",
+            _ => "",
+        };
+        params.push(format!(
+            "{}{}: {}",
+            label,
+            param_name(ident),
+            ctx.type_name(&ty, from)
+        ));
+
+        let (arg, wrapper) = ctx.arg(ident, &ty);
+        args.push(arg);
+        wrappers.extend(wrapper);
+    }
+    let params = params.join(", ");
+
+    let output = ctx.classify(&method.output, class)?;
+    let throws = match output {
+        SwiftType::Result(..) => " throws",
+        _ => "",
+    };
+    let ret = match &output {
+        SwiftType::Unit => String::new(),
+        SwiftType::Result(ok, _) if matches!(**ok, SwiftType::Unit) => String::new(),
+        output => format!(" -> {}", ctx.type_name(output, from)),
+    };
+    let name = escape_keyword(camel_case(&method.name, false));
+    let self_name = class.map(|class| ctx.class_name(class, from));
+
+    let (decl, body) = match (&method.kind, operator) {
+        (MethodKind::Constructor, _) => {
+            // The body returns the new object, which is replaced by the delegation to the
+            // designated initializer
+            let mut body = ctx.body(&method.symbol, args, &wrappers, &output, from);
+            body.pop();
+            body.push("self.init(ptr: out!)".into());
+
+            (format!("public convenience init({}){}", params, throws), body)
+        }
+        (MethodKind::Getter, _) => {
+            let body = match output {
+                // Other opaque fields are borrowed from the struct, so they can't be owned by a
+                // class. `shared` ones are returned with a new reference
+                SwiftType::Class(field) if field.shared => vec![format!(
+                    "return {}(ptr: {}.{}(ptr))",
+                    ctx.class_name(field, from),
+                    ctx.c_module,
+                    method.symbol
+                )],
+                SwiftType::Primitive(name) if !matches!(name, "Bool" | "Float" | "Double") => {
+                    ctx.body(&method.symbol, args, &wrappers, &output, from)
+                }
+                _ => return None,
+            };
+
+            (format!("public var {}{}", name, ret.replacen(" ->", ":", 1)), body)
+        }
+        (MethodKind::Setter, _) => (
+            format!(
+                "public func set{}({}){}",
+                camel_case(&method.name, true),
+                params,
+                throws
+            ),
+            ctx.body(&method.symbol, args, &wrappers, &output, from),
+        ),
+        (_, Some(SwiftOperator::Infix(op))) => (
+            format!(
+                "public static func {} (lhs: {}, {}){}{}",
+                op, self_name?, params, throws, ret
+            ),
+            ctx.body(&method.symbol, args, &wrappers, &output, from),
+        ),
+        (_, Some(SwiftOperator::Prefix(op))) => (
+            format!(
+                "public static prefix func {} (operand: {}){}{}",
+                op, self_name?, throws, ret
+            ),
+            ctx.body(&method.symbol, args, &wrappers, &output, from),
+        ),
+        (_, Some(SwiftOperator::Subscript)) => {
+            let body = ctx.body(&method.symbol, args, &wrappers, &output, from);
+            let body = match throws {
+                "" => body,
+                _ => std::iter::once("get throws {".to_string())
+                    .chain(body.into_iter().map(|l| format!("    {}", l)))
+                    .chain(std::iter::once("}".to_string()))
+                    .collect(),
+            };
+
+            (format!("public subscript({}){}", params, ret), body)
+        }
+        // `to_string()` makes the class `CustomStringConvertible`
+        (MethodKind::Instance { .. }, _) if is_description(method) => (
+            "public var description: String".to_string(),
+            ctx.body(&method.symbol, args, &wrappers, &output, from),
+        ),
+        _ => {
+            // Free functions of nested modules are declared in the extension of their namespace
+            let is_static = match class {
+                Some(_) => method.kind == MethodKind::Static,
+                None => from.len() > 1,
+            };
+
+            (
+                format!(
+                    "public {}func {}({}){}{}",
+                    if is_static { "static " } else { "" },
+                    name,
+                    params,
+                    throws,
+                    ret
+                ),
+                ctx.body(&method.symbol, args, &wrappers, &output, from),
+            )
+        }
+    };
+
+    let prefix = "    ".repeat(level);
+    Some(format!(
+        "{}{} {{\n{}{}}}\n",
+        prefix,
+        decl,
+        Swift::indent(body, level + 1),
+        prefix
+    ))
+}
+
+fn is_description(method: &Method) -> bool {
+    matches!(method.kind, MethodKind::Instance { .. })
+        && method.name == "to_string"
+        && method.inputs.is_empty()
+        && match_fixed_type(&method.output, parse_quote!(String))
+}
+
+fn render_class(ctx: &Context, class: &Class, level: usize) -> String {
+    let prefix = "    ".repeat(level);
+    let conformance = match class.methods.iter().any(is_description) {
+        true => ": CustomStringConvertible",
+        false => "",
+    };
+    let ownership = match (&class.destructor, class.shared) {
+        (Some(_), true) => "released when the object is deinitialized",
+        (Some(_), false) => "freed when the object is deinitialized",
+        (None, _) => "never freed since the struct has no `#[destructor]`",
+    };
+
+    let mut out = String::new();
+    writeln!(
+        out,
+        "{p}/// Owns a pointer to `{c}{ident}`, {ownership}\n\
+         {p}public final class {ident}{conformance} {{\n\
+         {p}    public let ptr: OpaquePointer\n\n\
+         {p}    /// Take the ownership of a pointer returned by the C API\n\
+         {p}    public init(ptr: OpaquePointer) {{\n\
+         {p}        self.ptr = ptr\n\
+         {p}    }}",
+        p = prefix,
+        c = ctx.c_prefix,
+        ident = class.ident,
+        ownership = ownership,
+        conformance = conformance,
+    )
+    .unwrap();
+    if let Some(destructor) = &class.destructor {
+        writeln!(
+            out,
+            "\n{p}    deinit {{\n{p}        {}.{}(ptr)\n{p}    }}",
+            ctx.c_module,
+            destructor,
+            p = prefix
+        )
+        .unwrap();
+    }
+
+    for method in &class.methods {
+        writeln!(out).unwrap();
+        match render_method(ctx, Some(class), method, &class.mod_path, level + 1) {
+            Some(rendered) => write!(out, "{}", rendered).unwrap(),
+            None => writeln!(
+                out,
+                "{}    // `{}` is not available: unsupported argument or return types",
+                prefix, method.name
+            )
+            .unwrap(),
+        }
+    }
+    writeln!(out, "{}}}", prefix).unwrap();
+
+    out
+}
+
+fn render_source(items: Recorded) -> String {
+    let namespaces = items.modules();
+    let Recorded {
+        classes, functions, ..
+    } = &items;
+
+    let lib = camel_case(&lib_name(), true);
+    let ctx = Context {
+        lib: lib.clone(),
+        c_module: format!("C{}", lib),
+        c_prefix: std::env::var("EXPOSE_C_PREFIX").unwrap_or_default(),
+        classes,
+        errors: RefCell::new(BTreeSet::new()),
+    };
+
+    let mut body = String::new();
+    for namespace in &namespaces {
+        let ns = ctx.namespace(namespace);
+        let level = match ns.is_empty() {
+            true => 0,
+            false => 1,
+        };
+
+        let mut items = vec![];
+        for class in classes.iter().filter(|c| c.mod_path == *namespace) {
+            items.push(match class.opaque {
+                true => render_class(&ctx, class, level),
+                false => format!(
+                    "{}public typealias {} = {}.{}{}\n",
+                    "    ".repeat(level),
+                    class.ident,
+                    ctx.c_module,
+                    ctx.c_prefix,
+                    class.ident
+                ),
+            });
+        }
+        for (_, function) in functions.iter().filter(|(path, _)| path == namespace) {
+            items.push(
+                render_method(&ctx, None, function, namespace, level).unwrap_or_else(|| {
+                    format!(
+                        "{}// `{}` is not available: unsupported argument or return types\n",
+                        "    ".repeat(level),
+                        function.name
+                    )
+                }),
+            );
+        }
+
+        let items = items.join("\n");
+        if ns.is_empty() {
+            writeln!(body, "{}", items).unwrap();
+        } else {
+            writeln!(body, "extension {} {{\n{}}}\n", ns, items).unwrap();
+        }
+    }
+
+    // Each nested module is a caseless `enum`, declared before it's extended
+    let mut enums = String::new();
+    for namespace in &namespaces {
+        for len in 2..=namespace.len() {
+            let (parent, ident) = (ctx.namespace(&namespace[..len - 1]), &namespace[len - 1]);
+            let declaration = format!(
+                "public enum {} {{}}\n",
+                camel_case(&ident.to_string(), true)
+            );
+            let declaration = match parent.is_empty() {
+                true => declaration,
+                false => format!("extension {} {{\n    {}}}\n", parent, declaration),
+            };
+            if !enums.contains(&declaration) {
+                enums.push_str(&declaration);
+            }
+        }
+    }
+    if !enums.is_empty() {
+        enums.push('\n');
+    }
+
+    let mut errors = String::new();
+    for error in ctx.errors.borrow().iter() {
+        writeln!(
+            errors,
+            "public enum {error}: {lib}ErrorProtocol, Equatable {{\n    \
+             case failed(code: Int32)\n\n    \
+             public var code: Int32 {{\n        \
+             switch self {{\n        \
+             case .failed(let code):\n            \
+             return code\n        \
+             }}\n    \
+             }}\n\
+             }}\n",
+            error = error,
+            lib = lib
+        )
+        .unwrap();
+    }
+
+    format!(
+        "// Generated by `#[expose_mod]`, do not edit\n\
+         import Foundation\n\
+         import {c_module}\n\n\
+         /// Error thrown when the C API returns an error code\n\
+         public protocol {lib}ErrorProtocol: Swift.Error {{\n    \
+         var code: Int32 {{ get }}\n\
+         }}\n\n\
+         /// Copy a string returned by the C API, and free it\n\
+         fileprivate func takeString(_ s: UnsafeMutablePointer<CChar>) -> String {{\n    \
+         defer {{ {c_module}.{c}string_free(s) }}\n    \
+         return String(cString: s)\n\
+         }}\n\n\
+         /// Copy a vector returned by the C API, and free it\n\
+         fileprivate func takeBytes(_ arr: {c_module}.{c}Arr_u8) -> Data {{\n    \
+         defer {{ {c_module}.{c}bytes_free(arr) }}\n    \
+         return arr.ptr.map {{ Data(bytes: $0, count: Int(arr.len)) }} ?? Data()\n\
+         }}\n\n\
+         {errors}{enums}{body}",
+        c_module = ctx.c_module,
+        c = ctx.c_prefix,
+        lib = lib,
+        errors = errors,
+        enums = enums,
+        body = body.trim_end_matches('\n').to_string() + "\n",
+    )
+}
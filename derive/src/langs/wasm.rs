@@ -0,0 +1,627 @@
+//! JavaScript bindings with `wasm-bindgen`
+//!
+//! Exposed items are annotated with `#[wasm_bindgen]`, which generates the JS glue and the
+//! TypeScript definitions when the module is processed by the `wasm-bindgen` CLI. Everything ends
+//! up in a single JS module, so names are converted to camel case and prefixed with the module
+//! path if `#[expose_mod(module_path)]` is set.
+//!
+//! Errors returned in a `Result` are thrown as instances of an `Error` subclass named after the
+//! error type, which is declared as an interface in the TypeScript definitions of the root module.
+//!
+//! Signatures that can't cross to JavaScript, like callbacks, are left unexported and listed in a
+//! comment of the TypeScript definitions.
+
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::punctuated::Punctuated;
+use syn::{
+    Attribute, FnArg, Ident, ImplItem, ImplItemMethod, Item, ItemFn, ItemImpl, ItemMod,
+    ItemStruct, ItemTrait, PatType, ReturnType, Signature, Token, TraitItem, Type, TypePath,
+};
+
+use super::*;
+use crate::types::*;
+
+#[derive(Debug)]
+pub struct Wasm;
+
+impl Lang for Wasm {
+    type Error = WasmError;
+
+    const NAME: &'static str = "wasm";
+
+    fn expose_fn(
+        function: &mut ItemFn,
+        mod_path: &Vec<Ident>,
+        _extra: &mut Vec<Item>,
+    ) -> Result<Ident, Self::Error> {
+        let ident = function.sig.ident.clone();
+
+        let (mut args, input_conversion) = match Self::convert_fn_args(function.sig.inputs.clone(), true) {
+            Ok(converted) => converted,
+            Err(WasmError::Unsupported(reason)) => {
+                skip(&ident.to_string(), reason);
+                strip_arg_attrs(&mut function.sig);
+                return Ok(ident);
+            }
+            Err(e) => return Err(e),
+        };
+        let ExpandedReturn {
+            ret,
+            extra_args,
+            conv: output_conversion,
+        } = Return(function.sig.output.clone()).expand(
+            &format_ident!("__output"),
+            &format_ident!("__ptr_out"),
+            Self::convert_output,
+        )?;
+        args.extend(extra_args);
+
+        let attrs = &function.attrs;
+        let throws = throws_doc(&function.sig.output);
+        let js_name = js_fn_name(mod_path, &ident.to_string());
+        let block = &function.block;
+        *function = parse_quote! {
+            #(#attrs)*
+            #throws
+            #[wasm_bindgen::prelude::wasm_bindgen(js_name = #js_name)]
+            pub fn #ident(#args) #ret {
+                use crate::mapping::{MapTo, MapFrom};
+                use crate::langs::*;
+
+                #input_conversion
+
+                let mut block_closure = move || { #block };
+                let __output = block_closure();
+
+                #output_conversion
+            }
+        };
+
+        Ok(ident)
+    }
+
+    fn expose_mod(
+        module: &mut ItemMod,
+        mod_path: &Vec<Ident>,
+        _sub_items: Vec<ModuleItem>,
+    ) -> Result<Ident, Self::Error> {
+        module.vis = parse_quote!(pub);
+
+        // Nested modules are expanded first, so everything has been recorded by now
+        if mod_path.len() == 1 {
+            let definitions = render_definitions();
+            if !definitions.is_empty() {
+                let content = &mut module.content.as_mut().expect("Empty module").1;
+                content.push(parse_quote! {
+                    #[wasm_bindgen::prelude::wasm_bindgen(typescript_custom_section)]
+                    const TYPESCRIPT_DEFINITIONS: &'static str = #definitions;
+                });
+            }
+        }
+
+        Ok(module.ident.clone())
+    }
+
+    fn expose_struct(
+        structure: &mut ItemStruct,
+        opts: Punctuated<ExposeStructOpts, Token![,]>,
+        mod_path: &Vec<Ident>,
+        extra: &mut Vec<Item>,
+    ) -> Result<Ident, Self::Error> {
+        let ident = structure.ident.clone();
+        let is_opaque = opts.iter().any(|o| *o == ExposeStructOpts::Opaque);
+
+        structure.vis = parse_quote!(pub);
+        structure.attrs.push(match js_class(mod_path, &ident) {
+            Some(class) => parse_quote!( #[wasm_bindgen::prelude::wasm_bindgen(js_name = #class)] ),
+            None => parse_quote!( #[wasm_bindgen::prelude::wasm_bindgen] ),
+        });
+        // wasm-bindgen would export the public fields of transparent structs directly, but only
+        // if they are `Copy`. They get accessors like the fields of opaque structs instead
+        if !is_opaque {
+            for field in &mut structure.fields {
+                field.attrs.push(parse_quote!( #[wasm_bindgen(skip)] ));
+            }
+        }
+
+        // Methods implemented by the options, the ones with the same name in the `impl` blocks
+        // are not exported
+        let mut methods: Vec<ImplItemMethod> = vec![];
+        if opts.iter().any(|o| *o == ExposeStructOpts::ToString) {
+            methods.push(parse_quote! {
+                #[wasm_bindgen::prelude::wasm_bindgen(js_name = "toString")]
+                pub fn __to_string(&self) -> String {
+                    self.to_string()
+                }
+            });
+        } else if opts.iter().any(|o| *o == ExposeStructOpts::ToDebug) {
+            methods.push(parse_quote! {
+                #[wasm_bindgen::prelude::wasm_bindgen(js_name = "toString")]
+                pub fn __to_string(&self) -> String {
+                    format!("{:?}", self)
+                }
+            });
+        }
+        if opts.iter().any(|o| *o == ExposeStructOpts::Bytes) {
+            methods.push(parse_quote! {
+                #[wasm_bindgen::prelude::wasm_bindgen(js_name = "toBytes")]
+                pub fn __to_bytes(&self) -> Vec<u8> {
+                    crate::common::ByteContainer::as_bytes(self).to_vec()
+                }
+            });
+            methods.push(parse_quote! {
+                #[wasm_bindgen::prelude::wasm_bindgen(js_name = "fromBytes")]
+                pub fn __from_bytes(bytes: &[u8]) -> Self {
+                    <Self as crate::common::ByteContainer>::from_bytes(bytes.to_vec())
+                }
+            });
+        }
+        if !methods.is_empty() {
+            RESERVED.with(|reserved| {
+                let mut reserved = reserved.borrow_mut();
+                for method in &methods {
+                    reserved.insert((ident.to_string(), bindgen_js_name(&method.attrs)));
+                }
+            });
+
+            let class_attr = impl_attr(mod_path, &ident);
+            let impl_block: ItemImpl = parse_quote! {
+                #class_attr
+                impl #ident {
+                    #(#methods)*
+                }
+            };
+            extra.push(impl_block.into());
+        }
+
+        let impl_block = Self::generate_getters_setters(structure, is_opaque, mod_path)?;
+        extra.push(impl_block.into());
+
+        Ok(ident)
+    }
+
+    fn expose_impl(
+        implementation: &mut ItemImpl,
+        mod_path: &Vec<Ident>,
+    ) -> Result<(), Self::Error> {
+        let self_ident = match implementation.self_ty.as_ref() {
+            Type::Path(TypePath { path, .. }) => path.segments.last().map(|s| s.ident.clone()),
+            _ => None,
+        }
+        .expect("Invalid `impl` type");
+        implementation.attrs.push(impl_attr(mod_path, &self_ident));
+
+        // `free()` and the garbage collector drop the values, so destructors are not needed
+        implementation.items.retain(|item| {
+            !matches!(item, ImplItem::Method(ImplItemMethod { attrs, .. }) if attrs.iter().any(|a| a.path.is_ident("destructor")))
+        });
+
+        let mut has_default_constructor = false;
+        for item in &mut implementation.items {
+            if let ImplItem::Method(method) = item {
+                expose_method(method, &self_ident, &mut has_default_constructor)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn expose_trait(
+        tr: &mut ItemTrait,
+        _mod_path: &Vec<Ident>,
+        _extra: &mut Vec<Item>,
+    ) -> Result<Ident, Self::Error> {
+        for item in &mut tr.items {
+            if let TraitItem::Method(method) = item {
+                method.attrs.retain(|a| !a.path.is_ident("expose_trait"));
+            }
+        }
+        skip(
+            &tr.ident.to_string(),
+            "traits can't be implemented from JavaScript yet".into(),
+        );
+
+        Ok(tr.ident.clone())
+    }
+
+    fn expose_operators(
+        self_ty: &Type,
+        operators: Vec<ExposedOperator>,
+        mod_path: &Vec<Ident>,
+        extra: &mut Vec<Item>,
+    ) -> Result<(), Self::Error> {
+        // JavaScript has no operator overloading, so they are exported as regular methods named
+        // `add`, `sub`, `mul`, `neg` and `index`
+        let mut impl_block: ItemImpl = parse_quote! {
+            impl #self_ty {}
+        };
+        for operator in operators {
+            let rhs_by_value = operator.rhs.as_ref().is_some_and(is_primitive);
+            impl_block.items.push(operator.wrapper(rhs_by_value).into());
+        }
+
+        Self::expose_impl(&mut impl_block, mod_path)?;
+        extra.push(impl_block.into());
+
+        Ok(())
+    }
+
+    fn convert_input(ty: Type) -> Result<Input, Self::Error> {
+        if let Some(target) = Self::custom_mapping(&ty) {
+            Ok(Input::new_map_from(ty, vec![target]))
+        } else if let Type::BareFn(_) = ty {
+            Err(WasmError::Unsupported(
+                "callbacks can't be passed from JavaScript yet".into(),
+            ))
+        } else {
+            Ok(Input::new_unchanged(ty))
+        }
+    }
+
+    fn convert_output(output: Type) -> Result<Output, Self::Error> {
+        match Self::custom_mapping(&output) {
+            Some(target) => Ok(Output::new_map_to_single(output, target)),
+            None => Ok(Output::new_unchanged(output)),
+        }
+    }
+
+    // Setters borrow the new value, taking it by value would consume the JS object
+    fn convert_getter_setter_ty(ty: Type) -> Result<(Type, Type), Self::Error> {
+        let getter = parse_quote!(<#ty as crate::common::WrappedStructField>::Getter);
+        let setter = parse_quote!(<#ty as crate::common::WrappedStructField>::Setter);
+        if is_primitive(&ty) {
+            Ok((getter, setter))
+        } else {
+            Ok((getter, parse_quote!(&#setter)))
+        }
+    }
+
+    fn expose_inline_getter(field: &mut Field, impl_block: &mut ItemImpl) -> Result<(), Self::Error> {
+        let field_ty = &field.ty;
+        let field_ident = field.ident.as_ref().expect("Missing field ident");
+        let getter_name = format_ident!("get_{}", field_ident);
+        let getter: ImplItemMethod = parse_quote! {
+            #[getter]
+            fn #getter_name(&self) -> #field_ty {
+                self.#field_ident.clone()
+            }
+        };
+        impl_block.items.push(getter.into());
+
+        Ok(())
+    }
+
+    fn expose_inline_setter(
+        field: &mut Field,
+        validate: Option<&Path>,
+        impl_block: &mut ItemImpl,
+    ) -> Result<(), Self::Error> {
+        let field_ty = &field.ty;
+        let field_ident = field.ident.as_ref().expect("Missing field ident");
+        let setter_name = format_ident!("set_{}", field_ident);
+        let setter_ty: Type = if is_primitive(field_ty) {
+            parse_quote!(#field_ty)
+        } else {
+            parse_quote!(&#field_ty)
+        };
+        let setter: ImplItemMethod = match validate {
+            None => parse_quote! {
+                #[setter]
+                fn #setter_name(&mut self, #field_ident: #setter_ty) {
+                    self.#field_ident = crate::mapping::MapFrom::map_from(#field_ident);
+                }
+            },
+            Some(validate) => parse_quote! {
+                #[setter]
+                fn #setter_name(&mut self, #field_ident: #setter_ty) -> Result<(), crate::common::ValidationError> {
+                    let #field_ident: #field_ty = crate::mapping::MapFrom::map_from(#field_ident);
+                    #validate(self, &#field_ident).map_err(crate::common::ValidationError::from_display)?;
+                    self.#field_ident = #field_ident;
+
+                    Ok(())
+                }
+            },
+        };
+        impl_block.items.push(setter.into());
+
+        Ok(())
+    }
+
+    // `Option<&T>` can't cross wasm-bindgen, so the arguments taken by reference are required even
+    // if they have a default value
+    fn convert_fn_args<I: IntoIterator<Item = FnArg>>(
+        args: I,
+        optional_defaults: bool,
+    ) -> Result<(Punctuated<FnArg, Comma>, TokenStream2), Self::Error> {
+        let mut converted = Punctuated::<FnArg, Comma>::default();
+        let mut conversion = TokenStream2::default();
+        for arg in args {
+            let by_reference = matches!(&arg, FnArg::Typed(PatType { ty, .. }) if matches!(ty.as_ref(), Type::Reference(_)));
+            let ExpandedArgument { args, conv } =
+                Argument(arg).expand(Self::convert_input, optional_defaults && !by_reference)?;
+            converted.extend(args);
+            conversion.extend(conv.into_inner());
+        }
+
+        Ok((converted, conversion))
+    }
+}
+
+thread_local! {
+    /// JS names of the methods implemented by the `#[expose_struct]` options, by struct
+    static RESERVED: RefCell<BTreeSet<(String, String)>> = const { RefCell::new(BTreeSet::new()) };
+    /// Error types returned in a `Result`, declared in the TypeScript definitions
+    static ERRORS: RefCell<BTreeSet<String>> = const { RefCell::new(BTreeSet::new()) };
+    /// Notes about the items that are not exported
+    static SKIPPED: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+fn skip(item: &str, reason: String) {
+    let note = format!("`{}` is not exported: {}", item, reason);
+    SKIPPED.with(|skipped| skipped.borrow_mut().push(note));
+}
+
+/// JS name of a free function, prefixed with the module path if `#[expose_mod(module_path)]` is
+/// set
+fn js_fn_name(mod_path: &[Ident], name: &str) -> String {
+    let mut qualified = String::new();
+    if mod_opts().module_path {
+        for module in mod_path {
+            qualified.push_str(&module.to_string().to_snake_case());
+            qualified.push('_');
+        }
+    }
+    qualified.push_str(name);
+
+    camel_case(&qualified, false)
+}
+
+/// JS name of a struct, only if it's different from the Rust one
+fn js_class(mod_path: &[Ident], ident: &Ident) -> Option<String> {
+    if !mod_opts().module_path {
+        return None;
+    }
+
+    let path = mod_path
+        .iter()
+        .map(|module| module.to_string().to_snake_case())
+        .collect::<Vec<_>>()
+        .join("_");
+    Some(format!("{}{}", camel_case(&path, true), ident))
+}
+
+/// `#[wasm_bindgen]` attribute of the `impl` blocks of a struct
+fn impl_attr(mod_path: &[Ident], ident: &Ident) -> Attribute {
+    match js_class(mod_path, ident) {
+        Some(class) => parse_quote!( #[wasm_bindgen::prelude::wasm_bindgen(js_class = #class)] ),
+        None => parse_quote!( #[wasm_bindgen::prelude::wasm_bindgen] ),
+    }
+}
+
+/// The `js_name` given to a method generated for the struct options
+fn bindgen_js_name(attrs: &[Attribute]) -> String {
+    attrs
+        .iter()
+        .find_map(|attr| {
+            attr.parse_args_with(|input: syn::parse::ParseStream| {
+                input.parse::<Ident>()?;
+                input.parse::<Token![=]>()?;
+                input.parse::<LitStr>()
+            })
+            .ok()
+        })
+        .map(|name| name.value())
+        .expect("Missing `js_name`")
+}
+
+/// Types that wasm-bindgen takes by value without consuming a JS object
+fn is_primitive(ty: &Type) -> bool {
+    types_arr!(bool, i8, u8, i16, u16, i32, u32, i64, u64, isize, usize, f32, f64)
+        .iter()
+        .any(|t| t == ty)
+}
+
+fn strip_arg_attrs(sig: &mut Signature) {
+    for input in &mut sig.inputs {
+        if let FnArg::Typed(PatType { attrs, .. }) = input {
+            attrs.retain(|a| !a.path.is_ident("expose"));
+        }
+    }
+}
+
+/// `@throws` tag for the JSDoc of the functions that return a `Result`, which also records the
+/// error type so that it's declared in the TypeScript definitions
+fn throws_doc(output: &ReturnType) -> Option<Attribute> {
+    let ty = match output {
+        ReturnType::Type(_, ty) => ty,
+        ReturnType::Default => return None,
+    };
+    let error = match match_generic_type(ty, parse_quote!(Result))?.as_slice() {
+        [_, Type::Path(TypePath { path, .. })] => path.segments.last()?.ident.to_string(),
+        _ => return None,
+    };
+
+    // Thrown as a `RangeError`, like out-of-bounds accesses to typed arrays
+    let doc = if error == "IndexError" {
+        " @throws {RangeError} If the index is out of bounds".to_string()
+    } else {
+        ERRORS.with(|errors| errors.borrow_mut().insert(error.clone()));
+        format!(" @throws {{{}}}", error)
+    };
+
+    Some(parse_quote!( #[doc = #doc] ))
+}
+
+/// Rename a method, turn it into a constructor, getter or setter and convert its arguments. The
+/// methods that can't be exported are left as they are
+fn expose_method(
+    method: &mut ImplItemMethod,
+    self_ident: &Ident,
+    has_default_constructor: &mut bool,
+) -> Result<(), WasmError> {
+    let ImplItemMethod {
+        attrs,
+        vis,
+        sig,
+        block,
+        ..
+    } = method;
+    let name = sig.ident.to_string();
+
+    let bindgen_args = match take_constructor(attrs, sig)? {
+        Some(ConstructorOpts { name: None }) => {
+            if std::mem::replace(has_default_constructor, true) {
+                return Err(LangError::MultipleDefaultConstructors(sig.ident.span()).into());
+            }
+            if let ReturnType::Type(_, ty) = &sig.output {
+                if match_generic_type(ty, parse_quote!(Option)).is_some() {
+                    return Err(WasmError::OptionalConstructor(sig.ident.span()));
+                }
+            }
+
+            quote!(constructor)
+        }
+        Some(ConstructorOpts { name: Some(name) }) => {
+            let js_name = camel_case(&name.to_string(), false);
+            quote!(js_name = #js_name)
+        }
+        None => match attrs
+            .iter()
+            .position(|a| a.path.is_ident("getter") || a.path.is_ident("setter"))
+        {
+            Some(pos) => {
+                let attr = attrs.remove(pos);
+                if attr.path.is_ident("getter") {
+                    let property = camel_case(name.trim_start_matches("get_"), false);
+                    quote!(getter = #property)
+                } else {
+                    let property = camel_case(name.trim_start_matches("set_"), false);
+                    quote!(setter = #property)
+                }
+            }
+            None => {
+                let js_name = camel_case(&name, false);
+                let reserved = RESERVED.with(|reserved| {
+                    reserved
+                        .borrow()
+                        .contains(&(self_ident.to_string(), js_name.clone()))
+                });
+                if reserved {
+                    strip_arg_attrs(sig);
+                    return Ok(());
+                }
+
+                quote!(js_name = #js_name)
+            }
+        },
+    };
+
+    let (mut args, input_conversion) = match Wasm::convert_fn_args(sig.inputs.clone(), true) {
+        Ok(converted) => converted,
+        Err(WasmError::Unsupported(reason)) => {
+            skip(&format!("{}::{}", self_ident, name), reason);
+            strip_arg_attrs(sig);
+            return Ok(());
+        }
+        Err(e) => return Err(e),
+    };
+    let ExpandedReturn {
+        ret,
+        extra_args,
+        conv: output_conversion,
+    } = Return(sig.output.clone()).expand(
+        &format_ident!("__output"),
+        &format_ident!("__ptr_out"),
+        Wasm::convert_output,
+    )?;
+    args.extend(extra_args);
+
+    attrs.extend(throws_doc(&sig.output));
+    attrs.push(parse_quote!( #[wasm_bindgen::prelude::wasm_bindgen(#bindgen_args)] ));
+    *vis = parse_quote!(pub);
+    sig.inputs = args;
+    sig.output = ret;
+    block.stmts = parse_quote! {
+        use crate::mapping::{MapTo, MapFrom};
+        use crate::langs::*;
+
+        #input_conversion
+
+        let mut block_closure = move || { #block };
+        let __output = block_closure();
+
+        #output_conversion
+    };
+
+    Ok(())
+}
+
+/// Declarations appended to the TypeScript definitions generated by wasm-bindgen: an interface
+/// for every error type and a comment with the items that are not exported
+fn render_definitions() -> String {
+    let errors = ERRORS.with(|errors| errors.replace(BTreeSet::new()));
+    let skipped = SKIPPED.with(|skipped| skipped.replace(Vec::new()));
+    RESERVED.with(|reserved| reserved.replace(BTreeSet::new()));
+
+    let mut definitions = String::new();
+    for note in &skipped {
+        definitions.push_str(&format!("// {}\n", note));
+    }
+    for error in &errors {
+        if !definitions.is_empty() {
+            definitions.push('\n');
+        }
+        definitions.push_str(&format!(
+            "/** Thrown by the functions that return a `{error}` */\n\
+             export interface {error} extends Error {{\n    name: \"{error}\";\n}}\n",
+            error = error
+        ));
+    }
+
+    definitions
+}
+
+#[derive(Debug)]
+pub enum WasmError {
+    Lang(LangError),
+
+    /// Unnamed constructor returning an `Option`, which can't be a JS constructor
+    OptionalConstructor(Span),
+    /// Signature that can't cross to JavaScript, skipped instead of failing the build
+    Unsupported(String),
+}
+
+impl fmt::Display for WasmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WasmError::Lang(e) => write!(f, "{}", e),
+            WasmError::OptionalConstructor(_) => write!(
+                f,
+                "Constructors without a `name` can't return an `Option` in JavaScript"
+            ),
+            WasmError::Unsupported(reason) => write!(f, "Unsupported signature: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for WasmError {}
+
+impl From<WasmError> for syn::Error {
+    fn from(e: WasmError) -> Self {
+        match e {
+            WasmError::Lang(e) => e.into(),
+            WasmError::OptionalConstructor(span) => syn::Error::new(span, e),
+            WasmError::Unsupported(_) => syn::Error::new(Span::call_site(), e),
+        }
+    }
+}
+
+impl From<LangError> for WasmError {
+    fn from(e: LangError) -> Self {
+        WasmError::Lang(e)
+    }
+}
@@ -7,32 +7,163 @@ use syn::parse::Parser;
 use syn::punctuated::Punctuated;
 use syn::{
     parse_macro_input, parse_quote, Attribute, Fields, Ident, ImplItem, ImplItemMethod, Item,
-    ItemFn, ItemImpl, ItemMod, ItemStruct, ItemTrait, Token, Type, TypePath,
+    ItemFn, ItemImpl, ItemMod, ItemStruct, ItemTrait, Token, Type, TypePath, UseTree,
 };
 
+#[cfg(feature = "idl")]
+mod idl;
 mod langs;
 mod types;
 
+#[cfg(test)]
+mod tests;
+
 use langs::Lang;
 use types::*;
 
-#[cfg(feature = "c")]
+#[cfg(all(
+    feature = "c",
+    not(any(
+        feature = "cpp",
+        feature = "swift",
+        feature = "go",
+        feature = "csharp",
+        feature = "dart"
+    ))
+))]
 type CurrentLang = langs::c::C;
+#[cfg(feature = "cpp")]
+type CurrentLang = langs::cpp::Cpp;
+#[cfg(feature = "swift")]
+type CurrentLang = langs::swift::Swift;
+#[cfg(feature = "go")]
+type CurrentLang = langs::go::Go;
+#[cfg(feature = "csharp")]
+type CurrentLang = langs::csharp::CSharp;
+#[cfg(feature = "dart")]
+type CurrentLang = langs::dart::Dart;
 #[cfg(feature = "python")]
 type CurrentLang = langs::python::Python;
+#[cfg(feature = "jni")]
+type CurrentLang = langs::jni::Jni;
+#[cfg(feature = "wasm")]
+type CurrentLang = langs::wasm::Wasm;
+#[cfg(feature = "node")]
+type CurrentLang = langs::napi::Node;
+#[cfg(feature = "ruby")]
+type CurrentLang = langs::ruby::Ruby;
 
-fn check_struct(s: &ItemStruct) {
+fn check_struct(s: &ItemStruct) -> syn::Result<()> {
     if !matches!(s.fields, Fields::Named(_)) {
-        panic!("Only named structs are supported");
+        return Err(syn::Error::new_spanned(
+            &s.fields,
+            "Only named structs are supported",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Merge all the errors into a single one, so that they can be reported at once
+fn combine_errors(errors: Vec<syn::Error>) -> Option<syn::Error> {
+    errors.into_iter().fold(None, |acc, e| match acc {
+        Some(mut acc) => {
+            acc.combine(e);
+            Some(acc)
+        }
+        None => Some(e),
+    })
+}
+
+fn use_tree_ident(tree: &UseTree) -> Option<&Ident> {
+    match tree {
+        UseTree::Path(path) => use_tree_ident(&path.tree),
+        UseTree::Name(name) => Some(&name.ident),
+        UseTree::Rename(rename) => Some(&rename.rename),
+        _ => None,
     }
 }
 
-fn analyze_module(module: &mut ItemMod, mut path: Vec<Ident>, extra: &mut Vec<Item>) {
+/// Register the types marked with `#[expose_mapping]` before exposing anything else, so that
+/// mappings apply regardless of the order of the items in the module
+fn collect_mappings(items: &mut Vec<Item>, errors: &mut Vec<syn::Error>) {
+    for item in items {
+        let (attrs, ident) = match item {
+            Item::Use(item) => (&mut item.attrs, use_tree_ident(&item.tree).cloned()),
+            Item::Struct(item) => (&mut item.attrs, Some(item.ident.clone())),
+            Item::Enum(item) => (&mut item.attrs, Some(item.ident.clone())),
+            Item::Type(item) => (&mut item.attrs, Some(item.ident.clone())),
+            _ => continue,
+        };
+
+        if let Some(pos) = attrs.iter().position(|a| a.path.is_ident("expose_mapping")) {
+            let attr = attrs.remove(pos);
+
+            let ident = match ident {
+                Some(ident) => ident,
+                None => {
+                    errors.push(syn::Error::new_spanned(
+                        &attr,
+                        "`expose_mapping` is only supported on single-item `use`",
+                    ));
+                    continue;
+                }
+            };
+
+            let parser = Punctuated::<ExposeMappingOpt, Token![,]>::parse_terminated;
+            match attr.parse_args_with(parser) {
+                Ok(opts) => register_mapping(parse_quote!(#ident), opts),
+                Err(e) => errors.push(e),
+            }
+        }
+    }
+}
+
+/// Remove `#[blocking]` from the functions and methods if the language doesn't handle it
+fn strip_blocking<L: Lang>(attrs: &mut Vec<Attribute>) {
+    if !L::BLOCKING_CALLS {
+        take_blocking(attrs);
+    }
+}
+
+fn strip_impl_blocking<L: Lang>(implementation: &mut ItemImpl) {
+    for item in &mut implementation.items {
+        if let ImplItem::Method(method) = item {
+            strip_blocking::<L>(&mut method.attrs);
+        }
+    }
+}
+
+fn analyze_module<L: Lang>(
+    module: &mut ItemMod,
+    mut path: Vec<Ident>,
+    opts: ExposeModOpts,
+    errors: &mut Vec<syn::Error>,
+) {
     path.push(module.ident.clone());
 
+    let items = match module.content.as_mut() {
+        Some((_, items)) => items,
+        None => {
+            errors.push(syn::Error::new_spanned(
+                &*module,
+                "Exposed modules must be declared inline",
+            ));
+            return;
+        }
+    };
+    let parent_opts = set_mod_opts(opts.clone());
+    collect_mappings(items, errors);
+    #[cfg(feature = "idl")]
+    idl::enter_module(&path, &opts);
+
     let mut sub_items = vec![];
+    // Items generated while exposing this module, which are added to it at the end
+    let mut extra = vec![];
+    // `std::ops` implementations, grouped by type
+    let mut operators = vec![];
 
-    for item in &mut module.content.as_mut().expect("Empty module").1 {
+    for item in items.iter_mut() {
         match item {
             Item::Mod(inner_module) => {
                 if let Some(pos) = inner_module
@@ -40,8 +171,24 @@ fn analyze_module(module: &mut ItemMod, mut path: Vec<Ident>, extra: &mut Vec<It
                     .iter()
                     .position(|a| a.path.is_ident("expose_mod"))
                 {
-                    inner_module.attrs.remove(pos);
-                    analyze_module(inner_module, path.clone(), extra);
+                    let attr = inner_module.attrs.remove(pos);
+                    let inner_opts = if attr.tokens.is_empty() {
+                        Ok(Default::default())
+                    } else {
+                        attr.parse_args::<ExposeModOpts>()
+                    };
+                    match inner_opts {
+                        Ok(inner_opts) => analyze_module::<L>(
+                            inner_module,
+                            path.clone(),
+                            opts.nested(inner_opts),
+                            errors,
+                        ),
+                        Err(e) => {
+                            errors.push(e);
+                            continue;
+                        }
+                    }
 
                     sub_items.push(ModuleItem::Module(inner_module.ident.clone()));
                 }
@@ -53,9 +200,14 @@ fn analyze_module(module: &mut ItemMod, mut path: Vec<Ident>, extra: &mut Vec<It
                     .position(|a| a.path.is_ident("expose_fn"))
                 {
                     function.attrs.remove(pos);
-                    sub_items.push(ModuleItem::Function(
-                        CurrentLang::expose_fn(function, &path).unwrap(),
-                    ));
+                    // Invalid attributes are reported when the item is exposed
+                    #[cfg(feature = "idl")]
+                    let _ = idl::record_fn(function);
+                    strip_blocking::<L>(&mut function.attrs);
+                    match L::expose_fn(function, &path, &mut extra) {
+                        Ok(ident) => sub_items.push(ModuleItem::Function(ident)),
+                        Err(e) => errors.push(e.into()),
+                    }
                 }
             }
             Item::Struct(structure) => {
@@ -64,17 +216,31 @@ fn analyze_module(module: &mut ItemMod, mut path: Vec<Ident>, extra: &mut Vec<It
                     .iter()
                     .position(|a| a.path.is_ident("expose_struct"))
                 {
+                    let attr = structure.attrs.remove(pos);
+
                     let parser = Punctuated::<ExposeStructOpts, Token![,]>::parse_terminated;
-                    let opts = if structure.attrs[pos].tokens.is_empty() {
-                        Default::default()
+                    let opts = if attr.tokens.is_empty() {
+                        Ok(Default::default())
                     } else {
-                        structure.attrs[pos].parse_args_with(parser).unwrap()
+                        attr.parse_args_with(parser)
+                    };
+                    let opts = match opts.and_then(|opts| check_struct(structure).map(|_| opts)) {
+                        Ok(opts) => opts,
+                        Err(e) => {
+                            errors.push(e);
+                            continue;
+                        }
                     };
 
-                    structure.attrs.remove(pos);
-                    check_struct(structure);
-
-                    let struct_ident = CurrentLang::expose_struct(structure, opts, &path, extra).unwrap();
+                    #[cfg(feature = "idl")]
+                    let _ = idl::record_struct(structure, &opts);
+                    let struct_ident = match L::expose_struct(structure, opts, &path, &mut extra) {
+                        Ok(ident) => ident,
+                        Err(e) => {
+                            errors.push(e.into());
+                            continue;
+                        }
+                    };
 
                     // Implement `ExposedStruct` automatically
                     let impl_exposed_struct: ItemImpl = parse_quote! {
@@ -92,7 +258,27 @@ fn analyze_module(module: &mut ItemMod, mut path: Vec<Ident>, extra: &mut Vec<It
                     .position(|a| a.path.is_ident("expose_impl"))
                 {
                     implementation.attrs.remove(pos);
-                    CurrentLang::expose_impl(implementation, &path).unwrap();
+                    if implementation.trait_.is_some() {
+                        match ExposedOperator::from_impl(implementation) {
+                            Ok(operator) => {
+                                #[cfg(feature = "idl")]
+                                idl::record_operator(&implementation.self_ty, &operator);
+                                add_operator(
+                                    &mut operators,
+                                    implementation.self_ty.as_ref(),
+                                    operator,
+                                )
+                            }
+                            Err(e) => errors.push(e.into()),
+                        }
+                    } else {
+                        #[cfg(feature = "idl")]
+                        let _ = idl::record_impl(implementation);
+                        strip_impl_blocking::<L>(implementation);
+                        if let Err(e) = L::expose_impl(implementation, &path) {
+                            errors.push(e.into());
+                        }
+                    }
                 }
             }
             Item::Trait(tr) => {
@@ -103,42 +289,89 @@ fn analyze_module(module: &mut ItemMod, mut path: Vec<Ident>, extra: &mut Vec<It
                 {
                     tr.attrs.remove(pos);
 
-                    sub_items.push(ModuleItem::Trait(
-                        CurrentLang::expose_trait(tr, &path, extra).unwrap(),
-                    ));
+                    #[cfg(feature = "idl")]
+                    let _ = idl::record_trait(tr);
+                    match L::expose_trait(tr, &path, &mut extra) {
+                        Ok(ident) => sub_items.push(ModuleItem::Trait(ident)),
+                        Err(e) => errors.push(e.into()),
+                    }
                 }
             }
             _ => {}
         }
     }
 
-    CurrentLang::expose_mod(module, &path, sub_items).unwrap();
+    for (self_ty, operators) in operators {
+        if let Err(e) = L::expose_operators(&self_ty, operators, &path, &mut extra) {
+            errors.push(e.into());
+        }
+    }
+
+    items.extend(extra);
+
+    if let Err(e) = L::expose_mod(module, &path, sub_items) {
+        errors.push(e.into());
+    }
+    #[cfg(feature = "idl")]
+    if let Err(e) = idl::exit_module(module.ident.span()) {
+        errors.push(e.into());
+    }
+
+    set_mod_opts(parent_opts);
 }
 
-#[proc_macro_attribute]
-pub fn expose_mod(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    let mut input = parse_macro_input!(item as ItemMod);
-    let mut extra = vec![];
-    analyze_module(&mut input, vec![], &mut extra);
+fn add_operator(
+    operators: &mut Vec<(Type, Vec<ExposedOperator>)>,
+    self_ty: &Type,
+    operator: ExposedOperator,
+) {
+    match operators.iter_mut().find(|(ty, _)| ty == self_ty) {
+        Some((_, ops)) => ops.push(operator),
+        None => operators.push((self_ty.clone(), vec![operator])),
+    }
+}
+
+/// Expand an `#[expose_mod]` module for the language `L`
+fn expand_module<L: Lang>(opts: ExposeModOpts, mut input: ItemMod) -> syn::Result<TokenStream2> {
+    let mut errors = vec![];
+    clear_mappings();
+    #[cfg(feature = "idl")]
+    idl::clear();
+    analyze_module::<L>(&mut input, vec![], opts, &mut errors);
+    clear_mappings();
 
-    match &mut input.content {
-        Some((_, items)) => items.extend(extra),
-        cont => *cont = Some((Default::default(), extra)),
+    if let Some(e) = combine_errors(errors) {
+        return Err(e);
     }
 
-    (quote! {
+    Ok(quote! {
         #input
     })
-    .into()
+}
+
+#[proc_macro_attribute]
+pub fn expose_mod(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let opts = parse_macro_input!(attr as ExposeModOpts);
+    let input = parse_macro_input!(item as ItemMod);
+
+    match expand_module::<CurrentLang>(opts, input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
 }
 
 #[proc_macro_attribute]
 pub fn expose_fn(_attr: TokenStream, item: TokenStream) -> TokenStream {
     let mut input = parse_macro_input!(item as ItemFn);
-    CurrentLang::expose_fn(&mut input, &vec![]).unwrap();
+    strip_blocking::<CurrentLang>(&mut input.attrs);
+    let mut extra = vec![];
+    if let Err(e) = CurrentLang::expose_fn(&mut input, &vec![], &mut extra) {
+        return syn::Error::from(e).to_compile_error().into();
+    }
 
     (quote! {
         #input
+        #(#extra)*
     })
     .into()
 }
@@ -146,16 +379,17 @@ pub fn expose_fn(_attr: TokenStream, item: TokenStream) -> TokenStream {
 #[proc_macro_attribute]
 pub fn expose_struct(attr: TokenStream, item: TokenStream) -> TokenStream {
     let mut input = parse_macro_input!(item as ItemStruct);
-    check_struct(&input);
-
-    let attr: TokenStream2 = attr.into();
-    let attr: Attribute = parse_quote! { #attr };
+    if let Err(e) = check_struct(&input) {
+        return e.to_compile_error().into();
+    }
 
     let parser = Punctuated::<ExposeStructOpts, Token![,]>::parse_terminated;
-    let opts = attr.parse_args_with(parser).unwrap();
+    let opts = parse_macro_input!(attr with parser);
 
     let mut extra = vec![];
-    CurrentLang::expose_struct(&mut input, opts, &vec![], &mut extra).unwrap();
+    if let Err(e) = CurrentLang::expose_struct(&mut input, opts, &vec![], &mut extra) {
+        return syn::Error::from(e).to_compile_error().into();
+    }
 
     (quote! {
         #input
@@ -167,17 +401,31 @@ pub fn expose_struct(attr: TokenStream, item: TokenStream) -> TokenStream {
 #[proc_macro_attribute]
 pub fn expose_impl(_attr: TokenStream, item: TokenStream) -> TokenStream {
     let mut input = parse_macro_input!(item as ItemImpl);
-    CurrentLang::expose_impl(&mut input, &vec![]).unwrap();
+    let mut extra = vec![];
+    let result = if input.trait_.is_some() {
+        ExposedOperator::from_impl(&input)
+            .map_err(Into::into)
+            .and_then(|operator| {
+                CurrentLang::expose_operators(&input.self_ty, vec![operator], &vec![], &mut extra)
+            })
+    } else {
+        strip_impl_blocking::<CurrentLang>(&mut input);
+        CurrentLang::expose_impl(&mut input, &vec![])
+    };
+    if let Err(e) = result {
+        return syn::Error::from(e).to_compile_error().into();
+    }
 
     (quote! {
         #input
+        #(#extra)*
     })
     .into()
 }
 
 #[proc_macro_attribute]
 pub fn expose_trait(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    let mut input = parse_macro_input!(item as ItemTrait);
+    let input = parse_macro_input!(item as ItemTrait);
     // CurrentLang::expose_trait(&mut input, &vec![]).unwrap();
 
     (quote! {
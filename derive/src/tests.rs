@@ -0,0 +1,267 @@
+//! Golden-file tests for the code generated by the `Lang` backends
+//!
+//! Every module in `tests/fixtures` is expanded with the backend enabled by the crate features and
+//! compared against the snapshot in `tests/snapshots/<lang>`, except for the ones a language only
+//! renders as skip comments. Run the tests with `BLESS=1` to update the snapshots after an
+//! intentional change.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use pretty_assertions::assert_eq;
+
+use super::*;
+
+fn fixtures() -> Vec<PathBuf> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    let mut fixtures = fs::read_dir(dir)
+        .expect("Missing fixtures directory")
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension() == Some("rs".as_ref()))
+        .collect::<Vec<_>>();
+    fixtures.sort();
+
+    fixtures
+}
+
+fn expand_fixture<L: Lang>(path: &Path) -> String {
+    let source = fs::read_to_string(path).unwrap();
+    let module: ItemMod = syn::parse_str(&source)
+        .unwrap_or_else(|e| panic!("Invalid fixture {}: {}", path.display(), e));
+
+    let tokens = expand_module::<L>(Default::default(), module)
+        .unwrap_or_else(|e| panic!("Failed to expand {}: {}", path.display(), e));
+    let file: syn::File = syn::parse2(tokens)
+        .unwrap_or_else(|e| panic!("Invalid expansion of {}: {}", path.display(), e));
+
+    prettyplease::unparse(&file)
+}
+
+fn check_snapshot(snapshot: &Path, fixture: &Path, actual: String) {
+    if std::env::var_os("BLESS").is_some() {
+        fs::create_dir_all(snapshot.parent().unwrap()).unwrap();
+        fs::write(snapshot, actual).unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(snapshot).unwrap_or_else(|_| {
+        panic!(
+            "Missing snapshot {}, run with `BLESS=1` to create it",
+            snapshot.display()
+        )
+    });
+    assert_eq!(
+        expected,
+        actual,
+        "Expansion of {} changed, run with `BLESS=1` if this is intended",
+        fixture.display()
+    );
+}
+
+fn check_snapshots<L: Lang>() {
+    let snapshots = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/snapshots")
+        .join(L::NAME);
+
+    for fixture in fixtures() {
+        let actual = expand_fixture::<L>(&fixture);
+        check_snapshot(&snapshots.join(fixture.file_name().unwrap()), &fixture, actual);
+    }
+}
+
+/// Fixtures rendered by the languages built on the C API as skip comments only, which
+/// `c_family_skips_unsupported_items` checks instead of pinning them in a snapshot per language
+#[cfg(any(feature = "swift", feature = "go", feature = "csharp", feature = "dart"))]
+const UNSUPPORTED_FIXTURES: &[&str] = &["callbacks"];
+
+/// Files generated for `output` when expanding `fixture`
+#[cfg(any(
+    feature = "cpp",
+    feature = "swift",
+    feature = "go",
+    feature = "csharp",
+    feature = "dart",
+    feature = "jni",
+    feature = "node",
+    feature = "idl"
+))]
+fn generated_files<L: Lang>(fixture: &Path, output: &str) -> Vec<(PathBuf, String)> {
+    langs::GENERATED.with(|generated| generated.borrow_mut().clear());
+    expand_fixture::<L>(fixture);
+    langs::GENERATED.with(|generated| {
+        generated
+            .borrow_mut()
+            .drain(..)
+            .filter(|(kind, _, _)| *kind == output)
+            .map(|(_, path, content)| (path, content))
+            .collect::<Vec<_>>()
+    })
+}
+
+/// Compare the files generated for `output` against `tests/snapshots/<dir>/<fixture>.<extension>`,
+/// except for the fixtures in `skip`. Several files are compared one after the other, each after a
+/// `// ---- <path> ----` line
+#[cfg(any(
+    feature = "cpp",
+    feature = "swift",
+    feature = "go",
+    feature = "csharp",
+    feature = "dart",
+    feature = "jni",
+    feature = "node",
+    feature = "idl"
+))]
+fn check_generated<L: Lang>(dir: &str, output: &str, extension: &str, skip: &[&str]) {
+    let snapshots = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/snapshots")
+        .join(dir);
+
+    for fixture in fixtures() {
+        if skip.iter().any(|s| fixture.file_stem() == Some(s.as_ref())) {
+            continue;
+        }
+        let mut files = generated_files::<L>(&fixture, output);
+
+        let actual = match files.len() {
+            0 => panic!("Nothing generated for {}", fixture.display()),
+            1 => files.remove(0).1,
+            _ => files
+                .into_iter()
+                .map(|(path, content)| format!("// ---- {} ----\n{}", path.display(), content))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        };
+        let snapshot = snapshots
+            .join(fixture.file_stem().unwrap())
+            .with_extension(extension);
+        check_snapshot(&snapshot, &fixture, actual);
+    }
+}
+
+#[test]
+#[cfg(feature = "c")]
+fn c_snapshots() {
+    check_snapshots::<langs::c::C>();
+}
+
+#[test]
+#[cfg(feature = "python")]
+fn python_snapshots() {
+    check_snapshots::<langs::python::Python>();
+}
+
+/// The Rust side is the same as the C one, so only the headers are compared
+#[test]
+#[cfg(feature = "cpp")]
+fn cpp_snapshots() {
+    check_generated::<langs::cpp::Cpp>("cpp", "HEADER", "hpp", &[]);
+}
+
+/// Like the C++ header, only the generated source is compared
+#[test]
+#[cfg(feature = "swift")]
+fn swift_snapshots() {
+    check_generated::<langs::swift::Swift>("swift", "SWIFT", "swift", UNSUPPORTED_FIXTURES);
+}
+
+/// Besides the Rust side, the generated `Native.java` and Kotlin sources are compared
+#[test]
+#[cfg(feature = "jni")]
+fn jni_snapshots() {
+    check_snapshots::<langs::jni::Jni>();
+    check_generated::<langs::jni::Jni>("jni", "JNI", "sources", &[]);
+}
+
+#[test]
+#[cfg(feature = "wasm")]
+fn wasm_snapshots() {
+    check_snapshots::<langs::wasm::Wasm>();
+}
+
+/// Besides the Rust side, the TypeScript definitions are compared
+#[test]
+#[cfg(feature = "node")]
+fn node_snapshots() {
+    check_snapshots::<langs::napi::Node>();
+    check_generated::<langs::napi::Node>("node", "NODE", "d.ts", &[]);
+}
+
+/// Like the Swift source, only the generated Go sources are compared, the exported callbacks after
+/// the bindings
+#[test]
+#[cfg(feature = "go")]
+fn go_snapshots() {
+    check_generated::<langs::go::Go>("go", "GO", "go", UNSUPPORTED_FIXTURES);
+}
+
+/// Only the generated C# source is compared
+#[test]
+#[cfg(feature = "csharp")]
+fn csharp_snapshots() {
+    check_generated::<langs::csharp::CSharp>("csharp", "CSHARP", "cs", UNSUPPORTED_FIXTURES);
+}
+
+/// Only the generated Dart library is compared
+#[test]
+#[cfg(feature = "dart")]
+fn dart_snapshots() {
+    check_generated::<langs::dart::Dart>("dart", "DART", "dart", UNSUPPORTED_FIXTURES);
+}
+
+/// Items the languages built on the C API can't render are replaced by a comment saying so
+#[test]
+#[cfg(any(feature = "swift", feature = "go", feature = "csharp", feature = "dart"))]
+fn c_family_skips_unsupported_items() {
+    let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/callbacks.rs");
+    langs::GENERATED.with(|generated| generated.borrow_mut().clear());
+    expand_fixture::<CurrentLang>(&fixture);
+    let generated = langs::GENERATED.with(|generated| {
+        generated
+            .borrow_mut()
+            .drain(..)
+            .map(|(_, _, content)| content)
+            .collect::<String>()
+    });
+
+    for item in ["apply", "describe"] {
+        let comment = format!(
+            "// `{}` is not available: unsupported argument or return types",
+            item
+        );
+        assert!(generated.contains(&comment), "Missing `{}` in:\n{}", comment, generated);
+    }
+}
+
+#[test]
+#[cfg(feature = "ruby")]
+fn ruby_snapshots() {
+    check_snapshots::<langs::ruby::Ruby>();
+}
+
+/// The description doesn't depend on the language, so the same snapshots are checked whichever
+/// backend is enabled
+#[test]
+#[cfg(feature = "idl")]
+fn idl_snapshots() {
+    check_generated::<CurrentLang>("idl", "IDL", "json", &[]);
+}
+
+/// Functions are named after the module path and the prefix of `#[expose_mod]`, while the types
+/// keep their name: cbindgen only adds the crate-wide prefix to them
+#[test]
+#[cfg(feature = "c")]
+fn c_module_options_only_name_functions() {
+    let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/module_path.rs");
+    let expanded = expand_fixture::<langs::c::C>(&fixture);
+
+    for symbol in [
+        "fn version(",
+        "fn outer_inner_thing_new(",
+        "fn outer_inner_count(",
+        "fn ext_count(",
+        "fn ext_outer_prefixed_nested_count(",
+    ] {
+        assert!(expanded.contains(symbol), "Missing `{}` in:\n{}", symbol, expanded);
+    }
+    assert!(expanded.contains("pub struct Thing {"), "Renamed type in:\n{}", expanded);
+}
@@ -5,10 +5,12 @@ use proc_macro2::TokenStream as TokenStream2;
 use quote::{format_ident, quote, ToTokens};
 
 use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
 use syn::token::{Comma, RArrow};
 use syn::{
-    parse_quote, BareFnArg, FnArg, GenericArgument, Ident, Pat, PatIdent, PatType, Path,
-    PathArguments, PathSegment, ReturnType, Type, TypePath,
+    parse_quote, Attribute, BareFnArg, Expr, FnArg, GenericArgument, Ident, LitStr, Pat,
+    PatIdent, PatType, Path, PathArguments, PathSegment, ReturnType, Signature, Token, Type,
+    TypePath, TypePtr, TypeReference,
 };
 
 use crate::langs::LangError;
@@ -195,6 +197,88 @@ impl Input {
     }
 }
 
+/// Parse the default value given with `#[expose(default = "...")]` to a function argument.
+///
+/// For reference arguments the default is an owned value, which is then borrowed
+pub fn arg_default(attrs: &[Attribute]) -> Result<Option<Expr>, LangError> {
+    let attr = match attrs.iter().find(|a| a.path.is_ident("expose")) {
+        Some(attr) => attr,
+        None => return Ok(None),
+    };
+
+    attr.parse_args_with(|input: syn::parse::ParseStream| {
+        let key = input.parse::<Ident>()?;
+        if key != "default" {
+            return Err(syn::Error::new(key.span(), "expected `default = \"...\"`"));
+        }
+        input.parse::<Token![=]>()?;
+        input.parse::<LitStr>()?.parse::<Expr>()
+    })
+    .map(Some)
+    .map_err(LangError::ExposeArgAttrError)
+}
+
+/// Options of `#[constructor]`. Additional constructors of a type are given a `name`
+#[derive(Debug, Default)]
+pub struct ConstructorOpts {
+    pub name: Option<Ident>,
+}
+
+/// Remove the `#[constructor]` attribute from a method, returning its options. Also checks that
+/// the method doesn't take `self` and returns `Self`, `Option<Self>` or `Result<Self, _>`
+pub fn take_constructor(
+    attrs: &mut Vec<Attribute>,
+    sig: &Signature,
+) -> Result<Option<ConstructorOpts>, LangError> {
+    let attr = match attrs.iter().position(|a| a.path.is_ident("constructor")) {
+        Some(pos) => attrs.remove(pos),
+        None => return Ok(None),
+    };
+
+    let opts = if attr.tokens.is_empty() {
+        ConstructorOpts::default()
+    } else {
+        attr.parse_args_with(|input: syn::parse::ParseStream| {
+            let key = input.parse::<Ident>()?;
+            if key != "name" {
+                return Err(syn::Error::new(key.span(), "expected `name = \"...\"`"));
+            }
+            input.parse::<Token![=]>()?;
+            Ok(ConstructorOpts {
+                name: Some(input.parse::<LitStr>()?.parse::<Ident>()?),
+            })
+        })
+        .map_err(LangError::ConstructorAttrError)?
+    };
+
+    let returns_self = match &sig.output {
+        ReturnType::Type(_, ty) => {
+            let is_self = |ty: &Type| match_fixed_type(ty, parse_quote!(Self));
+            is_self(ty)
+                || matches!(match_generic_type(ty, parse_quote!(Option)), Some(inner) if inner.len() == 1 && is_self(&inner[0]))
+                || matches!(match_generic_type(ty, parse_quote!(Result)), Some(inner) if inner.len() == 2 && is_self(&inner[0]))
+        }
+        ReturnType::Default => false,
+    };
+    if !returns_self || matches!(sig.inputs.first(), Some(FnArg::Receiver(_))) {
+        return Err(LangError::InvalidConstructor(sig.ident.span()));
+    }
+
+    Ok(Some(opts))
+}
+
+/// Remove the `#[blocking]` attribute from a function, returning whether it was present. Blocking
+/// functions are run on a separate thread by the languages that have asynchronous calls
+pub fn take_blocking(attrs: &mut Vec<Attribute>) -> bool {
+    match attrs.iter().position(|a| a.path.is_ident("blocking")) {
+        Some(pos) => {
+            attrs.remove(pos);
+            true
+        }
+        None => false,
+    }
+}
+
 #[derive(Debug)]
 pub struct Argument(pub FnArg);
 
@@ -214,45 +298,87 @@ pub struct ExpandedArgument {
 }
 
 impl Argument {
-    pub fn expand<F, E>(self, convert_input: F) -> Result<ExpandedArgument, E>
+    /// Expand the argument, converting its type with `convert_input`.
+    ///
+    /// If `optional_defaults` is set, arguments with a `#[expose(default = "...")]` are taken as
+    /// an `Option` which falls back to the default value when `None`
+    pub fn expand<F, E>(self, convert_input: F, optional_defaults: bool) -> Result<ExpandedArgument, E>
     where
         E: From<LangError>,
         F: Fn(Type) -> Result<Input, E>,
     {
-        let (ident, ty) = match self.0 {
+        let (ident, ty, default) = match self.0 {
             r @ FnArg::Receiver(_) => {
                 return Ok(ExpandedArgument {
                     args: vec![r].into_iter().collect(),
                     conv: ExpandedArgumentConversion::empty(),
                 });
             }
-            FnArg::Typed(PatType { pat, ty, .. }) => match *pat {
-                Pat::Ident(PatIdent { ident, .. }) => (ident, ty),
-                _ => return Err(LangError::ComplexPatternFnArg.into()),
+            FnArg::Typed(PatType { pat, ty, attrs, .. }) => match *pat {
+                Pat::Ident(PatIdent { ident, .. }) => (ident, ty, arg_default(&attrs)?),
+                pat => return Err(LangError::ComplexPatternFnArg(pat.span()).into()),
             },
         };
+        let span = ty.span();
+        let borrow = match ty.as_ref() {
+            Type::Reference(TypeReference { mutability, .. }) => quote!(& #mutability),
+            _ => quote!(),
+        };
 
         let temp_ident = format_ident!("_temp_{}", ident);
         let expanded = convert_input(*ty)?.expand(&temp_ident);
         let input_conv = &expanded.conv;
 
-        let idents = expanded
-            .types
-            .iter()
-            .enumerate()
-            .map(|(i, _)| Ident::new(&format!("__{}_{}", ident, i), ident.span()))
-            .collect::<Punctuated<_, Comma>>();
-
-        let conv = quote! {
-            let #temp_ident = (#idents);
-            let #ident = #input_conv;
+        // Keep the original name when possible, so that it can be used as a keyword argument
+        let idents = match expanded.types.len() {
+            1 => vec![ident.clone()].into_iter().collect(),
+            _ => expanded
+                .types
+                .iter()
+                .enumerate()
+                .map(|(i, _)| Ident::new(&format!("__{}_{}", ident, i), ident.span()))
+                .collect::<Punctuated<_, Comma>>(),
+        };
+
+        let default = default.filter(|_| optional_defaults);
+        let is_optional = default.is_some();
+        if is_optional && expanded.types.len() != 1 {
+            return Err(LangError::DefaultMultipleTypes(span).into());
+        }
+
+        let conv = match default {
+            Some(default) => {
+                // Both are declared here, so that the converted value can borrow from them
+                let default_ident = format_ident!("__default_{}", ident);
+                quote! {
+                    let #temp_ident;
+                    let #default_ident;
+                    let #ident = match #idents {
+                        Some(#ident) => {
+                            #temp_ident = #ident;
+                            #input_conv
+                        }
+                        None => {
+                            #default_ident = #default;
+                            #borrow #default_ident
+                        }
+                    };
+                }
+            }
+            None => quote! {
+                let #temp_ident = (#idents);
+                let #ident = #input_conv;
+            },
         };
 
         let args = expanded
             .types
             .into_iter()
             .zip(idents.into_iter())
-            .map::<FnArg, _>(|(ty, ident)| parse_quote!(#ident: #ty))
+            .map::<FnArg, _>(|(ty, arg_ident)| match is_optional {
+                true => parse_quote!(#arg_ident: Option<#ty>),
+                false => parse_quote!(#arg_ident: #ty),
+            })
             .collect();
 
         Ok(ExpandedArgument {
@@ -304,9 +430,15 @@ impl ExpandedOutputConversion {
         ts.into()
     }
 
-    pub fn by_reference(ident: &Ident) -> Self {
+    pub fn by_reference(ident: &Ident, ty: &Type) -> Self {
+        let original = match ty {
+            Type::Ptr(TypePtr { elem, .. }) => elem.as_ref(),
+            ty => ty,
+        };
+
         let ts = quote! {
-            let #ident = Box::into_raw(Box::new(#ident));
+            let #ident: #original = #ident;
+            let #ident: #ty = #ident.map_to();
         };
         ts.into()
     }
@@ -315,7 +447,7 @@ impl ExpandedOutputConversion {
         let ts = quote! {
             let #ident: Option<#original> = #ident;
             let #ident: #ty = match #ident {
-                Some(inner) => Box::into_raw(Box::new(inner)),
+                Some(inner) => inner.map_to(),
                 None => std::ptr::null_mut(),
             };
         };
@@ -413,7 +545,7 @@ impl Output {
             Output::ByReference(ty) => ExpandedOutput {
                 ty: vec![parse_quote! { *mut #ty }],
                 suffix: vec![String::new()],
-                conv: ExpandedOutputConversion::by_reference(ident),
+                conv: ExpandedOutputConversion::by_reference(ident, &ty),
             },
             Output::Result {
                 ok,
@@ -477,6 +609,7 @@ impl Return {
         E: From<LangError>,
         F: Fn(Type) -> Result<Output, E>,
     {
+        let span = self.0.span();
         let ty = self.0.as_type();
         let converted = convert_output(ty)?;
 
@@ -485,7 +618,7 @@ impl Return {
         match converted {
             Output::ByReference(_) | Output::Option { .. } => {
                 if ty.len() > 1 {
-                    return Err(LangError::MultipleTypesByReference.into());
+                    return Err(LangError::MultipleTypesByReference(span).into());
                 }
                 let ty = &ty[0];
 
@@ -499,9 +632,14 @@ impl Return {
                 })
             }
             Output::Result {
-                err, original_err, ..
+                err,
+                original_err,
+                original_ok,
+                ..
             } => {
                 let (extra_args, assign_args): (Vec<_>, Vec<_>) = match ty.as_slice() {
+                    // Nothing to return other than the error code
+                    _ if original_ok.as_ref() == &parse_quote!(()) => (vec![], vec![]),
                     [ty] => (
                         vec![parse_quote!(#arg_name: #ty)],
                         vec![quote! { unsafe { *#arg_name = #ident; } }],
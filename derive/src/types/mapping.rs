@@ -0,0 +1,88 @@
+use std::cell::RefCell;
+
+use syn::parse::{Parse, ParseStream};
+use syn::{Ident, LitStr, PathArguments, Token, Type, TypePath};
+
+/// Valid keys of `#[expose_mapping]`, the `Lang::NAME` of every backend. The languages rendered
+/// over the C API use the mappings of `c`
+const LANGS: &[&str] = &["c", "python", "jni", "wasm", "node", "ruby"];
+
+/// A `lang = "Type"` entry of `#[expose_mapping]`
+#[derive(Debug, Clone)]
+pub struct ExposeMappingOpt {
+    pub lang: Ident,
+    pub target: Type,
+}
+
+impl Parse for ExposeMappingOpt {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let lang = input.parse::<Ident>()?;
+        // Every language is checked, not only the enabled one, so that typos are always reported
+        if !LANGS.iter().any(|l| lang == l) {
+            return Err(syn::Error::new(
+                lang.span(),
+                format!(
+                    "Unknown language `{}` in `expose_mapping`, expected one of: {}",
+                    lang,
+                    LANGS.join(", ")
+                ),
+            ));
+        }
+        input.parse::<Token![=]>()?;
+        let target = input.parse::<LitStr>()?.parse::<Type>()?;
+
+        Ok(ExposeMappingOpt { lang, target })
+    }
+}
+
+thread_local! {
+    /// Mappings declared with `#[expose_mapping]` in the module being expanded, as
+    /// `(type, lang, target)`
+    static MAPPINGS: RefCell<Vec<(Type, String, Type)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Register the mappings of `ty` declared with `#[expose_mapping]`
+pub fn register_mapping<I: IntoIterator<Item = ExposeMappingOpt>>(ty: Type, opts: I) {
+    MAPPINGS.with(|mappings| {
+        let mut mappings = mappings.borrow_mut();
+        for ExposeMappingOpt { lang, target } in opts {
+            mappings.push((ty.clone(), lang.to_string(), target));
+        }
+    });
+}
+
+/// Find the type `ty` is mapped to for `lang`, if a custom mapping has been declared.
+///
+/// Mappings are registered by the name the item declares, so types are matched on the last segment
+/// of their path: `Error`, `super::Error` and `crate::errors::Error` all use the mapping of
+/// `use super::Error`, even if they name different types. Resolving the paths is out of reach of
+/// the macros, which only see the tokens of their module; registering the mappings by their full
+/// path, with a macro invoked next to the type, is out of scope for now
+pub fn lookup_mapping(lang: &str, ty: &Type) -> Option<Type> {
+    let ident = mapped_ident(ty)?;
+    MAPPINGS.with(|mappings| {
+        mappings
+            .borrow()
+            .iter()
+            .find(|(t, l, _)| mapped_ident(t) == Some(ident) && l == lang)
+            .map(|(_, _, target)| target.clone())
+    })
+}
+
+/// Last segment of the path of `ty`, if it can have a mapping: generic types can't
+fn mapped_ident(ty: &Type) -> Option<&Ident> {
+    match ty {
+        Type::Path(TypePath { qself: None, path }) => path
+            .segments
+            .last()
+            .filter(|segment| matches!(segment.arguments, PathArguments::None))
+            .map(|segment| &segment.ident),
+        Type::Group(group) => mapped_ident(&group.elem),
+        Type::Paren(paren) => mapped_ident(&paren.elem),
+        _ => None,
+    }
+}
+
+pub fn clear_mappings() {
+    MAPPINGS.with(|mappings| mappings.borrow_mut().clear());
+}
@@ -1,9 +1,13 @@
 mod function;
+mod mapping;
 mod module;
+mod operator;
 mod structure;
 mod tr;
 
 pub use function::*;
+pub use mapping::*;
 pub use module::*;
+pub use operator::*;
 pub use structure::*;
 pub use tr::*;
@@ -1,4 +1,7 @@
-use syn::Ident;
+use std::cell::RefCell;
+
+use syn::parse::{Parse, ParseStream};
+use syn::{Ident, LitStr};
 
 #[derive(Debug)]
 pub enum ModuleItem {
@@ -7,3 +10,80 @@ pub enum ModuleItem {
     Module(Ident),
     Trait(Ident),
 }
+
+/// Options given to `#[expose_mod(...)]`, inherited by the nested modules
+#[derive(Debug, Clone, Default)]
+pub struct ExposeModOpts {
+    /// Include the module path in the name of the exported symbols
+    pub module_path: bool,
+    /// Prefix of the exported C symbols, after the crate-wide one and before the module path
+    pub prefix: Option<String>,
+}
+
+impl Parse for ExposeModOpts {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut opts = ExposeModOpts::default();
+
+        while !input.is_empty() {
+            match input.parse::<Ident>()? {
+                s if s == "module_path" => opts.module_path = true,
+                s if s == "prefix" => {
+                    input.parse::<syn::Token![=]>()?;
+                    let prefix = input.parse::<LitStr>()?;
+                    let value = prefix.value();
+                    if !value.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                        return Err(syn::Error::new(
+                            prefix.span(),
+                            "the prefix can only contain ASCII letters, digits and `_`",
+                        ));
+                    }
+                    opts.prefix = Some(value);
+                }
+                s => {
+                    return Err(syn::Error::new(
+                        s.span(),
+                        "expected `module_path` or `prefix = \"...\"`",
+                    ))
+                }
+            }
+
+            if !input.is_empty() {
+                input.parse::<syn::Token![,]>()?;
+            }
+        }
+
+        Ok(opts)
+    }
+}
+
+impl ExposeModOpts {
+    /// Options of a nested module, which inherits the ones of its parent. Its own prefix replaces
+    /// the parent's one
+    pub fn nested(&self, other: ExposeModOpts) -> Self {
+        ExposeModOpts {
+            module_path: self.module_path || other.module_path,
+            prefix: other.prefix.or_else(|| self.prefix.clone()),
+        }
+    }
+}
+
+thread_local! {
+    /// Options of the module being expanded
+    static MOD_OPTS: RefCell<ExposeModOpts> = const {
+        RefCell::new(ExposeModOpts {
+            module_path: false,
+            prefix: None,
+        })
+    };
+}
+
+/// Set the options of the module being expanded, returning the previous ones
+pub fn set_mod_opts(opts: ExposeModOpts) -> ExposeModOpts {
+    MOD_OPTS.with(|cell| cell.replace(opts))
+}
+
+/// Options of the module being expanded, only used by the C and JS backends for now
+#[cfg_attr(not(any(feature = "c", feature = "wasm", feature = "node")), allow(dead_code))]
+pub fn mod_opts() -> ExposeModOpts {
+    MOD_OPTS.with(|cell| cell.borrow().clone())
+}
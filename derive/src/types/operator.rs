@@ -0,0 +1,159 @@
+use quote::{format_ident, quote};
+use syn::spanned::Spanned;
+use syn::{
+    parse_quote, GenericArgument, Ident, ImplItem, ImplItemMethod, ImplItemType, ItemImpl,
+    PathArguments, Type, TypePath,
+};
+
+use crate::langs::LangError;
+
+/// An operator from `std::ops` that can be exposed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Add,
+    Sub,
+    Mul,
+    Neg,
+    Index,
+}
+
+impl Operator {
+    fn from_trait(ident: &Ident) -> Option<Self> {
+        match ident.to_string().as_str() {
+            "Add" => Some(Operator::Add),
+            "Sub" => Some(Operator::Sub),
+            "Mul" => Some(Operator::Mul),
+            "Neg" => Some(Operator::Neg),
+            "Index" => Some(Operator::Index),
+            _ => None,
+        }
+    }
+
+    pub fn trait_ident(&self) -> Ident {
+        match self {
+            Operator::Add => format_ident!("Add"),
+            Operator::Sub => format_ident!("Sub"),
+            Operator::Mul => format_ident!("Mul"),
+            Operator::Neg => format_ident!("Neg"),
+            Operator::Index => format_ident!("Index"),
+        }
+    }
+
+    /// Name of the method in the `std::ops` trait, also used to name the exposed functions
+    pub fn method(&self) -> Ident {
+        match self {
+            Operator::Add => format_ident!("add"),
+            Operator::Sub => format_ident!("sub"),
+            Operator::Mul => format_ident!("mul"),
+            Operator::Neg => format_ident!("neg"),
+            Operator::Index => format_ident!("index"),
+        }
+    }
+}
+
+/// An `impl std::ops::<Trait><Rhs> for T` block marked with `#[expose_impl]`
+#[derive(Debug, Clone)]
+pub struct ExposedOperator {
+    pub op: Operator,
+    /// Type of the right-hand side, or the index. `None` for unary operators
+    pub rhs: Option<Type>,
+    /// The associated `Output` type
+    pub output: Type,
+}
+
+impl ExposedOperator {
+    pub fn from_impl(implementation: &ItemImpl) -> Result<Self, LangError> {
+        let (_, path, _) = implementation
+            .trait_
+            .as_ref()
+            .expect("Not a trait implementation");
+        let segment = path.segments.last().expect("Empty trait path");
+        let op = Operator::from_trait(&segment.ident)
+            .ok_or_else(|| LangError::UnsupportedOperator(path.span()))?;
+
+        let rhs = match (&segment.arguments, op) {
+            (_, Operator::Neg) => None,
+            (PathArguments::AngleBracketed(args), _) => match args.args.first() {
+                Some(GenericArgument::Type(ty)) => Some(ty.clone()),
+                _ => return Err(LangError::UnsupportedOperator(args.span())),
+            },
+            // `Index` doesn't have a default for the index type
+            (_, Operator::Index) => return Err(LangError::UnsupportedOperator(path.span())),
+            (_, _) => Some(parse_quote!(Self)),
+        };
+
+        let mut output = implementation
+            .items
+            .iter()
+            .find_map(|item| match item {
+                ImplItem::Type(ImplItemType { ident, ty, .. }) if ident == "Output" => {
+                    Some(ty.clone())
+                }
+                _ => None,
+            })
+            .ok_or_else(|| LangError::MissingOperatorOutput(implementation.self_ty.span()))?;
+
+        // Spell the type as `Self` so that it's recognized and returned by reference
+        let mut rhs = rhs;
+        if let Some(rhs) = &mut rhs {
+            replace_self_ty(rhs, &implementation.self_ty);
+        }
+        replace_self_ty(&mut output, &implementation.self_ty);
+
+        Ok(ExposedOperator { op, rhs, output })
+    }
+
+    /// Inherent method that calls the operator on clones of its operands, so that it can be
+    /// exposed like any other method. The right-hand side is taken by reference unless
+    /// `rhs_by_value` is set.
+    ///
+    /// `Index` checks the index with `CheckedIndex` and returns an `IndexError` when it's out of
+    /// bounds, without panicking
+    #[cfg_attr(not(feature = "c"), allow(dead_code))]
+    pub fn wrapper(&self, rhs_by_value: bool) -> ImplItemMethod {
+        let method = self.op.method();
+        let tr = self.op.trait_ident();
+        let output = &self.output;
+        let (rhs_arg, rhs_value) = match &self.rhs {
+            Some(ty) if rhs_by_value => (quote!(rhs: #ty), quote!(rhs)),
+            Some(ty) => (quote!(rhs: &#ty), quote!(rhs.clone())),
+            None => (quote!(), quote!()),
+        };
+
+        match self.op {
+            Operator::Index => parse_quote! {
+                fn #method(&self, #rhs_arg) -> Result<#output, crate::common::IndexError> {
+                    crate::common::CheckedIndex::checked_index(self, #rhs_value)
+                        .cloned()
+                        .ok_or(crate::common::IndexError)
+                }
+            },
+            Operator::Neg => parse_quote! {
+                fn #method(&self) -> #output {
+                    std::ops::#tr::#method(self.clone())
+                }
+            },
+            Operator::Add | Operator::Sub | Operator::Mul => parse_quote! {
+                fn #method(&self, #rhs_arg) -> #output {
+                    std::ops::#tr::#method(self.clone(), #rhs_value)
+                }
+            },
+        }
+    }
+}
+
+fn replace_self_ty(ty: &mut Type, self_ty: &Type) {
+    if ty == self_ty {
+        *ty = parse_quote!(Self);
+    } else if let Type::Path(TypePath { path, .. }) = ty {
+        for segment in &mut path.segments {
+            if let PathArguments::AngleBracketed(args) = &mut segment.arguments {
+                for arg in &mut args.args {
+                    if let GenericArgument::Type(ty) = arg {
+                        replace_self_ty(ty, self_ty);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,16 +1,26 @@
 use syn::parse::{Parse, ParseStream};
-use syn::{Lit, LitStr, Path};
+use syn::{parenthesized, token, Ident, Lit, LitStr, Path, Token};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ExposeStructOpts {
     Opaque,
+    /// Reference-counted opaque struct, that can be retained/released independently. Read-only,
+    /// since other references may be reading it: methods taking `&mut self` are rejected, and so
+    /// are the `get`/`set` fields, whose accessors hand out pointers into the struct. Fields of
+    /// other structs holding it can still be replaced
+    Shared,
     Get,
     Set,
+    /// Setter that calls the given function to validate the new value before storing it. The
+    /// function returns `Result<(), E>` with `E: Display`, which becomes the error message
+    ValidatedSet(ValidateFn),
 
     /// The struct implements `ToString`
     ToString,
     /// The struct implements `Debug`
     ToDebug,
+    /// The struct is backed by a byte container and implements `ByteContainer`
+    Bytes,
 
     #[cfg(feature = "python")]
     Subclass,
@@ -27,12 +37,28 @@ impl Parse for ExposeStructOpts {
         } else if let Ok(path) = input.parse::<Path>() {
             match path.get_ident() {
                 Some(s) if s == "get" => Ok(ExposeStructOpts::Get),
+                Some(s) if s == "set" && input.peek(token::Paren) => {
+                    let content;
+                    parenthesized!(content in input);
+
+                    match content.parse::<Ident>()? {
+                        s if s == "validate" => {
+                            content.parse::<Token![=]>()?;
+                            Ok(ExposeStructOpts::ValidatedSet(ValidateFn(
+                                content.parse::<LitStr>()?,
+                            )))
+                        }
+                        s => Err(syn::Error::new(s.span(), "expected `validate = ...`")),
+                    }
+                }
                 Some(s) if s == "set" => Ok(ExposeStructOpts::Set),
+                Some(s) if s == "shared" => Ok(ExposeStructOpts::Shared),
                 Some(s) if s == "to_string" => Ok(ExposeStructOpts::ToString),
                 Some(s) if s == "to_debug" => Ok(ExposeStructOpts::ToDebug),
-                _ => Err(syn::Error::new(
-                    input.span(),
-                    "expected one of `get` or `set`",
+                Some(s) if s == "bytes" => Ok(ExposeStructOpts::Bytes),
+                _ => Err(syn::Error::new_spanned(
+                    path,
+                    "expected one of `get`, `set`, `shared`, `to_string`, `to_debug` or `bytes`",
                 )),
             }
         } else {
@@ -40,3 +66,38 @@ impl Parse for ExposeStructOpts {
         }
     }
 }
+
+/// Path of the function given to `set(validate = "...")`, kept as the literal so that the errors
+/// about it point to the attribute. Compared by value, since the options are collected in a set
+#[derive(Clone)]
+pub struct ValidateFn(LitStr);
+
+impl ValidateFn {
+    pub fn path(&self) -> syn::Result<Path> {
+        self.0.parse()
+    }
+
+    pub fn value(&self) -> String {
+        self.0.value()
+    }
+}
+
+impl std::fmt::Debug for ValidateFn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("ValidateFn").field(&self.value()).finish()
+    }
+}
+
+impl PartialEq for ValidateFn {
+    fn eq(&self, other: &Self) -> bool {
+        self.value() == other.value()
+    }
+}
+
+impl Eq for ValidateFn {}
+
+impl std::hash::Hash for ValidateFn {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.value().hash(state)
+    }
+}
@@ -0,0 +1,19 @@
+#[test]
+fn compile_fail() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+    #[cfg(feature = "c")]
+    t.compile_fail("tests/ui/c/*.rs");
+    #[cfg(feature = "cpp")]
+    t.compile_fail("tests/ui/cpp/*.rs");
+    #[cfg(feature = "python")]
+    t.compile_fail("tests/ui/python/*.rs");
+    #[cfg(feature = "jni")]
+    t.compile_fail("tests/ui/jni/*.rs");
+    #[cfg(feature = "wasm")]
+    t.compile_fail("tests/ui/wasm/*.rs");
+    #[cfg(feature = "node")]
+    t.compile_fail("tests/ui/node/*.rs");
+    #[cfg(feature = "ruby")]
+    t.compile_fail("tests/ui/ruby/*.rs");
+}
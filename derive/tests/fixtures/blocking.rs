@@ -0,0 +1,37 @@
+mod blocking {
+    #[expose_mapping(c = "i32")]
+    use super::Error;
+
+    #[expose_struct("opaque")]
+    #[derive(Clone)]
+    pub struct Wallet {
+        inner: super::Wallet,
+    }
+
+    #[expose_impl]
+    impl Wallet {
+        #[constructor]
+        fn new(descriptor: String) -> Self {
+            super::Wallet::new(&descriptor).into()
+        }
+
+        #[blocking]
+        fn sync(&self, url: String, stop_gap: usize) -> Result<u64, Error> {
+            self.inner.sync(&url, stop_gap)
+        }
+
+        #[blocking]
+        fn sign(&self, psbt: &[u8]) -> Vec<u8> {
+            self.inner.sign(psbt)
+        }
+
+        #[destructor]
+        fn destroy(_s: Self) {}
+    }
+
+    #[expose_fn]
+    #[blocking]
+    fn broadcast(url: String, tx: Vec<u8>) -> Result<String, Error> {
+        super::broadcast(&url, &tx)
+    }
+}
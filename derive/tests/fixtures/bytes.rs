@@ -0,0 +1,24 @@
+mod bytes {
+    #[expose_struct("opaque", bytes, to_string, to_debug)]
+    pub struct Script {
+        inner: super::Script,
+    }
+
+    #[expose_impl]
+    impl Script {
+        #[destructor]
+        fn destroy(_s: Self) {}
+    }
+
+    impl crate::common::ByteContainer for Script {
+        fn as_bytes(&self) -> &[u8] {
+            self.inner.as_bytes()
+        }
+
+        fn from_bytes(bytes: Vec<u8>) -> Self {
+            Script {
+                inner: bytes.into(),
+            }
+        }
+    }
+}
@@ -0,0 +1,11 @@
+mod callbacks {
+    #[expose_fn]
+    fn apply(value: u32, cb: fn(value: u32) -> u32) -> u32 {
+        cb(value)
+    }
+
+    #[expose_fn]
+    fn describe(cb: fn(name: String, value: u32)) {
+        cb("value".to_string(), 42)
+    }
+}
@@ -0,0 +1,30 @@
+mod constructors {
+    #[expose_mapping(c = "i32")]
+    use super::Error;
+
+    #[expose_struct("opaque")]
+    pub struct Network {
+        inner: super::Network,
+    }
+
+    #[expose_impl]
+    impl Network {
+        #[constructor]
+        fn from_string(s: String) -> Result<Self, Error> {
+            Ok(s.parse::<super::Network>()?.into())
+        }
+
+        #[constructor(name = "bitcoin")]
+        fn bitcoin() -> Self {
+            super::Network::Bitcoin.into()
+        }
+
+        #[constructor(name = "from_magic")]
+        fn from_magic(magic: u32) -> Option<Self> {
+            super::Network::from_magic(magic).map(Into::into)
+        }
+
+        #[destructor]
+        fn destroy(_s: Self) {}
+    }
+}
@@ -0,0 +1,25 @@
+mod defaults {
+    #[expose_struct("opaque")]
+    pub struct FeeRate {
+        inner: super::FeeRate,
+    }
+
+    #[expose_impl]
+    impl FeeRate {
+        #[destructor]
+        fn destroy(_s: Self) {}
+
+        fn from_sat_per_vb(#[expose(default = "1.0")] sat_per_vb: f32) -> Self {
+            super::FeeRate::from_sat_per_vb(sat_per_vb).into()
+        }
+
+        fn fee_for(&self, vbytes: u64, #[expose(default = "FeeRate::default()")] min: &FeeRate) -> u64 {
+            self.inner.fee_for(vbytes).max(min.inner.fee_for(vbytes))
+        }
+    }
+
+    #[expose_fn]
+    fn greet(#[expose(default = "\"world\".to_string()")] name: String) -> String {
+        format!("Hello {}", name)
+    }
+}
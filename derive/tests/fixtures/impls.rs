@@ -0,0 +1,33 @@
+mod impls {
+    #[expose_struct("opaque")]
+    pub struct Counter {
+        inner: super::Counter,
+    }
+
+    #[expose_impl]
+    impl Counter {
+        #[constructor]
+        fn new(start: u32) -> Self {
+            super::Counter::new(start).into()
+        }
+
+        #[destructor]
+        fn destroy(_s: Self) {}
+
+        fn zero() -> Self {
+            super::Counter::new(0).into()
+        }
+
+        fn get(&self) -> u32 {
+            self.inner.get()
+        }
+
+        fn increment(&mut self, by: u32) {
+            self.inner.increment(by)
+        }
+
+        fn label(&self, prefix: String) -> String {
+            format!("{}{}", prefix, self.inner.get())
+        }
+    }
+}
@@ -0,0 +1,42 @@
+mod outer {
+    #[expose_fn]
+    fn version() -> u32 {
+        1
+    }
+
+    #[expose_mod(module_path)]
+    mod inner {
+        #[expose_struct("opaque", shared)]
+        pub struct Thing {
+            inner: super::Thing,
+        }
+
+        #[expose_impl]
+        impl Thing {
+            fn new() -> Self {
+                super::Thing::default().into()
+            }
+        }
+
+        #[expose_fn]
+        fn count() -> u32 {
+            0
+        }
+    }
+
+    #[expose_mod(prefix = "ext_")]
+    mod prefixed {
+        #[expose_fn]
+        fn count() -> u32 {
+            0
+        }
+
+        #[expose_mod(module_path)]
+        mod nested {
+            #[expose_fn]
+            fn count() -> u32 {
+                0
+            }
+        }
+    }
+}
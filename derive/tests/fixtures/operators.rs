@@ -0,0 +1,67 @@
+mod operators {
+    #[expose_mapping(c = "i32")]
+    use super::Error;
+
+    #[expose_struct("opaque")]
+    pub struct Amount {
+        inner: super::Amount,
+    }
+
+    #[expose_impl]
+    impl Amount {
+        #[destructor]
+        fn destroy(_s: Self) {}
+    }
+
+    #[expose_impl]
+    impl std::ops::Add for Amount {
+        type Output = Result<Amount, Error>;
+
+        fn add(self, rhs: Self) -> Self::Output {
+            Ok(self.inner.checked_add(rhs.inner)?.into())
+        }
+    }
+
+    #[expose_impl]
+    impl std::ops::Mul<u64> for Amount {
+        type Output = Amount;
+
+        fn mul(self, rhs: u64) -> Self::Output {
+            (self.inner * rhs).into()
+        }
+    }
+
+    #[expose_impl]
+    impl std::ops::Neg for Amount {
+        type Output = Amount;
+
+        fn neg(self) -> Self::Output {
+            (-self.inner).into()
+        }
+    }
+
+    #[expose_struct("opaque")]
+    pub struct Script {
+        inner: super::Script,
+    }
+
+    #[expose_impl]
+    impl Script {
+        #[destructor]
+        fn destroy(_s: Self) {}
+    }
+
+    #[expose_impl]
+    impl std::ops::Index<usize> for Script {
+        type Output = u8;
+
+        fn index(&self, index: usize) -> &Self::Output {
+            &self.inner.as_bytes()[index]
+        }
+    }
+    impl crate::common::CheckedIndex<usize> for Script {
+        fn checked_index(&self, index: usize) -> Option<&u8> {
+            self.inner.as_bytes().get(index)
+        }
+    }
+}
@@ -0,0 +1,41 @@
+mod returns {
+    #[expose_mapping(c = "i32")]
+    use super::Error;
+
+    #[expose_struct("opaque")]
+    pub struct Item {
+        inner: super::Item,
+    }
+
+    #[expose_impl]
+    impl Item {
+        #[destructor]
+        fn destroy(_s: Self) {}
+
+        fn parse(s: String) -> Result<Self, Error> {
+            Ok(s.parse::<super::Item>()?.into())
+        }
+
+        // Mapped like `Error`, whatever the path
+        fn check(&self) -> Result<(), super::Error> {
+            self.inner.check()
+        }
+
+        fn find(name: String) -> Option<Self> {
+            super::Item::find(&name).map(Into::into)
+        }
+
+        fn to_bytes(&self) -> Vec<u8> {
+            self.inner.to_bytes()
+        }
+
+        fn name(&self) -> String {
+            self.inner.name().to_string()
+        }
+    }
+
+    #[expose_fn]
+    fn last_error() -> Error {
+        super::last_error()
+    }
+}
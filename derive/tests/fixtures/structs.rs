@@ -0,0 +1,64 @@
+mod structs {
+    #[expose_struct("opaque", to_string)]
+    pub struct Opaque {
+        inner: super::Opaque,
+    }
+
+    #[expose_impl]
+    impl Opaque {
+        #[destructor]
+        fn destroy(_s: Self) {}
+    }
+
+    #[expose_struct("opaque", shared)]
+    pub struct Shared {
+        inner: super::Shared,
+    }
+
+    #[expose_struct("opaque")]
+    pub struct WithFields {
+        #[expose_struct(get, set)]
+        shared: Shared,
+        #[expose_struct(get, set(validate = "WithFields::validate_value"))]
+        value: u32,
+    }
+
+    #[expose_impl]
+    impl WithFields {
+        #[destructor]
+        fn destroy(_s: Self) {}
+    }
+
+    #[expose_struct]
+    pub struct Point {
+        #[expose_struct(get, set)]
+        x: i32,
+        #[expose_struct(get)]
+        y: i32,
+    }
+
+    #[expose_struct("opaque")]
+    pub struct Script {
+        inner: super::Script,
+    }
+
+    #[expose_impl]
+    impl Script {
+        #[destructor]
+        fn destroy(_s: Self) {}
+    }
+
+    // Holds the opaque struct like opaque structs do
+    #[expose_struct]
+    pub struct WithOpaque {
+        #[expose_struct(get, set)]
+        script: Script,
+        value: u32,
+    }
+
+    #[expose_impl]
+    impl WithOpaque {
+        #[destructor]
+        fn destroy(_s: Self) {}
+    }
+}
@@ -0,0 +1,7 @@
+mod traits {
+    #[expose_trait]
+    pub trait Listener: super::Listener {
+        #[expose_trait(original = "on_event")]
+        fn _wrapper_on_event(&self, event: String) -> u32;
+    }
+}
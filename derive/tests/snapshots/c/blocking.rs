@@ -0,0 +1,114 @@
+pub mod blocking {
+    use super::Error;
+    #[derive(Clone)]
+    pub struct Wallet {
+        inner: super::Wallet,
+    }
+    impl Wallet {
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        /// Ownership of the value written to `__ptr_out` is transferred to the caller
+        pub extern "C" fn wallet_new(
+            descriptor: *const libc::c_char,
+            __ptr_out: *mut *mut Self,
+        ) {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let _temp_descriptor = (descriptor);
+            let descriptor = <String>::map_from(_temp_descriptor);
+            let mut block_closure = move || {
+                { super::Wallet::new(&descriptor).into() }
+            };
+            let __output = block_closure();
+            let __output: Self = __output;
+            let __output: *mut Self = __output.map_to();
+            unsafe {
+                *__ptr_out = __output;
+            }
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "C" fn wallet_sync(
+            &self,
+            url: *const libc::c_char,
+            stop_gap: usize,
+            __ptr_out: *mut u64,
+        ) -> i32 {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let _temp_url = (url);
+            let url = <String>::map_from(_temp_url);
+            let _temp_stop_gap = (stop_gap);
+            let stop_gap = _temp_stop_gap;
+            let mut block_closure = move || { { self.inner.sync(&url, stop_gap) } };
+            let __output = block_closure();
+            let __output: Result<u64, Error> = __output;
+            let __output: u64 = match __output {
+                Ok(inner) => inner.map_to(),
+                Err(e) => return e.into_platform_error(),
+            };
+            unsafe {
+                *__ptr_out = __output;
+            }
+            Error::ok()
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "C" fn wallet_sign(
+            &self,
+            psbt: crate::langs::Arr<u8>,
+        ) -> crate::langs::Arr<u8> {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let _temp_psbt = (psbt);
+            let psbt = <&[u8]>::map_from(_temp_psbt);
+            let mut block_closure = move || { { self.inner.sign(psbt) } };
+            let __output = block_closure();
+            let __output: Vec<u8> = __output;
+            let __output = __output.map_to();
+            __output
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "C" fn wallet_destroy(_s: *mut Self) -> () {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let _temp__s = (_s);
+            let _s = <Destroy<Self>>::map_from(_temp__s);
+            let mut block_closure = move || { {} };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+    }
+    #[no_mangle]
+    #[allow(non_snake_case)]
+    pub extern "C" fn broadcast(
+        url: *const libc::c_char,
+        tx: crate::langs::Arr<u8>,
+        __ptr_out: *mut *mut libc::c_char,
+    ) -> i32 {
+        use crate::mapping::{MapFrom, MapTo};
+        use crate::langs::*;
+        let _temp_url = (url);
+        let url = <String>::map_from(_temp_url);
+        let _temp_tx = (tx);
+        let tx = <Vec<u8>>::map_from(_temp_tx);
+        let mut block_closure = move || { { super::broadcast(&url, &tx) } };
+        let __output = block_closure();
+        let __output: Result<String, Error> = __output;
+        let __output: *mut libc::c_char = match __output {
+            Ok(inner) => inner.map_to(),
+            Err(e) => return e.into_platform_error(),
+        };
+        unsafe {
+            *__ptr_out = __output;
+        }
+        Error::ok()
+    }
+    impl crate::langs::ExposedPtr for Wallet {
+        type Kind = crate::langs::Boxed;
+    }
+    impl Wallet {}
+    impl crate::common::ExposedStruct for Wallet {}
+}
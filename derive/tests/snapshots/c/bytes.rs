@@ -0,0 +1,67 @@
+pub mod bytes {
+    pub struct Script {
+        inner: super::Script,
+    }
+    impl Script {
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "C" fn script_destroy(_s: *mut Self) -> () {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let _temp__s = (_s);
+            let _s = <Destroy<Self>>::map_from(_temp__s);
+            let mut block_closure = move || { {} };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+    }
+    impl crate::common::ByteContainer for Script {
+        fn as_bytes(&self) -> &[u8] {
+            self.inner.as_bytes()
+        }
+        fn from_bytes(bytes: Vec<u8>) -> Self {
+            Script { inner: bytes.into() }
+        }
+    }
+    impl crate::langs::ExposedPtr for Script {
+        type Kind = crate::langs::Boxed;
+    }
+    impl Script {
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "C" fn script_to_bytes(&self) -> crate::langs::Arr<u8> {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let mut block_closure = move || {
+                { crate::common::ByteContainer::as_bytes(self).to_vec() }
+            };
+            let __output = block_closure();
+            let __output: Vec<u8> = __output;
+            let __output = __output.map_to();
+            __output
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "C" fn script_from_bytes(
+            bytes: crate::langs::Arr<u8>,
+            __ptr_out: *mut *mut Self,
+        ) {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let _temp_bytes = (bytes);
+            let bytes = <Vec<u8>>::map_from(_temp_bytes);
+            let mut block_closure = move || {
+                { <Self as crate::common::ByteContainer>::from_bytes(bytes) }
+            };
+            let __output = block_closure();
+            let __output: Self = __output;
+            let __output: *mut Self = __output.map_to();
+            unsafe {
+                *__ptr_out = __output;
+            }
+        }
+    }
+    impl Script {}
+    impl crate::common::ExposedStruct for Script {}
+}
@@ -0,0 +1,48 @@
+pub mod callbacks {
+    #[no_mangle]
+    #[allow(non_snake_case)]
+    pub extern "C" fn apply(
+        value: u32,
+        cb: unsafe extern "C" fn(value: u32) -> u32,
+    ) -> u32 {
+        use crate::mapping::{MapFrom, MapTo};
+        use crate::langs::*;
+        let _temp_value = (value);
+        let value = _temp_value;
+        let _temp_cb = (cb);
+        let cb = move |value: u32| {
+            let value = value;
+            let (value) = value;
+            let result = unsafe { _temp_cb(value) };
+            let result = { result };
+            result
+        };
+        let mut block_closure = move || { { cb(value) } };
+        let __output = block_closure();
+        let __output = __output;
+        __output
+    }
+    #[no_mangle]
+    #[allow(non_snake_case)]
+    pub extern "C" fn describe(
+        cb: unsafe extern "C" fn(name: *mut libc::c_char, value: u32) -> (),
+    ) -> () {
+        use crate::mapping::{MapFrom, MapTo};
+        use crate::langs::*;
+        let _temp_cb = (cb);
+        let cb = move |name: String, value: u32| {
+            let name: String = name;
+            let name = name.map_to();
+            let (name) = name;
+            let value = value;
+            let (value) = value;
+            let result = unsafe { _temp_cb(name, value) };
+            let result = { result };
+            result
+        };
+        let mut block_closure = move || { { cb("value".to_string(), 42) } };
+        let __output = block_closure();
+        let __output = __output;
+        __output
+    }
+}
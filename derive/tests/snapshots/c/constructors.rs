@@ -0,0 +1,85 @@
+pub mod constructors {
+    use super::Error;
+    pub struct Network {
+        inner: super::Network,
+    }
+    impl Network {
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        /// Ownership of the value written to `__ptr_out` is transferred to the caller
+        pub extern "C" fn network_new(
+            s: *const libc::c_char,
+            __ptr_out: *mut *mut Self,
+        ) -> i32 {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let _temp_s = (s);
+            let s = <String>::map_from(_temp_s);
+            let mut block_closure = move || {
+                { Ok(s.parse::<super::Network>()?.into()) }
+            };
+            let __output = block_closure();
+            let __output: Result<Self, Error> = __output;
+            let __output: *mut Self = match __output {
+                Ok(inner) => inner.map_to(),
+                Err(e) => return e.into_platform_error(),
+            };
+            unsafe {
+                *__ptr_out = __output;
+            }
+            Error::ok()
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        /// Ownership of the value written to `__ptr_out` is transferred to the caller
+        pub extern "C" fn network_new_bitcoin(__ptr_out: *mut *mut Self) {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let mut block_closure = move || { { super::Network::Bitcoin.into() } };
+            let __output = block_closure();
+            let __output: Self = __output;
+            let __output: *mut Self = __output.map_to();
+            unsafe {
+                *__ptr_out = __output;
+            }
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        /// Ownership of the value written to `__ptr_out` is transferred to the caller
+        pub extern "C" fn network_new_from_magic(magic: u32, __ptr_out: *mut *mut Self) {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let _temp_magic = (magic);
+            let magic = _temp_magic;
+            let mut block_closure = move || {
+                { super::Network::from_magic(magic).map(Into::into) }
+            };
+            let __output = block_closure();
+            let __output: Option<Self> = __output;
+            let __output: *mut Self = match __output {
+                Some(inner) => inner.map_to(),
+                None => std::ptr::null_mut(),
+            };
+            unsafe {
+                *__ptr_out = __output;
+            }
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "C" fn network_destroy(_s: *mut Self) -> () {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let _temp__s = (_s);
+            let _s = <Destroy<Self>>::map_from(_temp__s);
+            let mut block_closure = move || { {} };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+    }
+    impl crate::langs::ExposedPtr for Network {
+        type Kind = crate::langs::Boxed;
+    }
+    impl Network {}
+    impl crate::common::ExposedStruct for Network {}
+}
@@ -0,0 +1,129 @@
+pub mod defaults {
+    pub struct FeeRate {
+        inner: super::FeeRate,
+    }
+    impl FeeRate {
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "C" fn fee_rate_destroy(_s: *mut Self) -> () {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let _temp__s = (_s);
+            let _s = <Destroy<Self>>::map_from(_temp__s);
+            let mut block_closure = move || { {} };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "C" fn fee_rate_from_sat_per_vb(
+            sat_per_vb: f32,
+            __ptr_out: *mut *mut Self,
+        ) {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let _temp_sat_per_vb = (sat_per_vb);
+            let sat_per_vb = _temp_sat_per_vb;
+            let mut block_closure = move || {
+                { super::FeeRate::from_sat_per_vb(sat_per_vb).into() }
+            };
+            let __output = block_closure();
+            let __output: Self = __output;
+            let __output: *mut Self = __output.map_to();
+            unsafe {
+                *__ptr_out = __output;
+            }
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "C" fn fee_rate_fee_for(&self, vbytes: u64, min: &FeeRate) -> u64 {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let _temp_vbytes = (vbytes);
+            let vbytes = _temp_vbytes;
+            let _temp_min = (min);
+            let min = _temp_min;
+            let mut block_closure = move || {
+                { self.inner.fee_for(vbytes).max(min.inner.fee_for(vbytes)) }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        /// Same as `fee_rate_from_sat_per_vb`, using the default values of the omitted arguments
+        pub extern "C" fn fee_rate_from_sat_per_vb_with_defaults(
+            __ptr_out: *mut *mut Self,
+        ) {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let mut block_closure = move || {
+                {
+                    let sat_per_vb: f32 = 1.0;
+                    { super::FeeRate::from_sat_per_vb(sat_per_vb).into() }
+                }
+            };
+            let __output = block_closure();
+            let __output: Self = __output;
+            let __output: *mut Self = __output.map_to();
+            unsafe {
+                *__ptr_out = __output;
+            }
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        /// Same as `fee_rate_fee_for`, using the default values of the omitted arguments
+        pub extern "C" fn fee_rate_fee_for_with_defaults(&self, vbytes: u64) -> u64 {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let _temp_vbytes = (vbytes);
+            let vbytes = _temp_vbytes;
+            let mut block_closure = move || {
+                {
+                    let min: &FeeRate = &FeeRate::default();
+                    { self.inner.fee_for(vbytes).max(min.inner.fee_for(vbytes)) }
+                }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+    }
+    #[no_mangle]
+    #[allow(non_snake_case)]
+    pub extern "C" fn greet(name: *const libc::c_char) -> *mut libc::c_char {
+        use crate::mapping::{MapFrom, MapTo};
+        use crate::langs::*;
+        let _temp_name = (name);
+        let name = <String>::map_from(_temp_name);
+        let mut block_closure = move || { { format!("Hello {}", name) } };
+        let __output = block_closure();
+        let __output: String = __output;
+        let __output = __output.map_to();
+        __output
+    }
+    impl crate::langs::ExposedPtr for FeeRate {
+        type Kind = crate::langs::Boxed;
+    }
+    impl FeeRate {}
+    impl crate::common::ExposedStruct for FeeRate {}
+    #[no_mangle]
+    #[allow(non_snake_case)]
+    /// Same as `greet`, using the default values of the omitted arguments
+    pub extern "C" fn greet_with_defaults() -> *mut libc::c_char {
+        use crate::mapping::{MapFrom, MapTo};
+        use crate::langs::*;
+        let mut block_closure = move || {
+            {
+                let name: String = "world".to_string();
+                { format!("Hello {}", name) }
+            }
+        };
+        let __output = block_closure();
+        let __output: String = __output;
+        let __output = __output.map_to();
+        __output
+    }
+}
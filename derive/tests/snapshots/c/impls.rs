@@ -0,0 +1,98 @@
+pub mod impls {
+    pub struct Counter {
+        inner: super::Counter,
+    }
+    impl Counter {
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        /// Ownership of the value written to `__ptr_out` is transferred to the caller
+        pub extern "C" fn counter_new(start: u32, __ptr_out: *mut *mut Self) {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let _temp_start = (start);
+            let start = _temp_start;
+            let mut block_closure = move || { { super::Counter::new(start).into() } };
+            let __output = block_closure();
+            let __output: Self = __output;
+            let __output: *mut Self = __output.map_to();
+            unsafe {
+                *__ptr_out = __output;
+            }
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "C" fn counter_destroy(_s: *mut Self) -> () {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let _temp__s = (_s);
+            let _s = <Destroy<Self>>::map_from(_temp__s);
+            let mut block_closure = move || { {} };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "C" fn counter_zero(__ptr_out: *mut *mut Self) {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let mut block_closure = move || { { super::Counter::new(0).into() } };
+            let __output = block_closure();
+            let __output: Self = __output;
+            let __output: *mut Self = __output.map_to();
+            unsafe {
+                *__ptr_out = __output;
+            }
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "C" fn counter_get(&self) -> u32 {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let mut block_closure = move || { { self.inner.get() } };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "C" fn counter_increment(&mut self, by: u32) -> () {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let _temp_by = (by);
+            let by = _temp_by;
+            let mut block_closure = move || {
+                {
+                    crate::langs::assert_unique_ptr::<Self>();
+                    self.inner.increment(by)
+                }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "C" fn counter_label(
+            &self,
+            prefix: *const libc::c_char,
+        ) -> *mut libc::c_char {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let _temp_prefix = (prefix);
+            let prefix = <String>::map_from(_temp_prefix);
+            let mut block_closure = move || {
+                { format!("{}{}", prefix, self.inner.get()) }
+            };
+            let __output = block_closure();
+            let __output: String = __output;
+            let __output = __output.map_to();
+            __output
+        }
+    }
+    impl crate::langs::ExposedPtr for Counter {
+        type Kind = crate::langs::Boxed;
+    }
+    impl Counter {}
+    impl crate::common::ExposedStruct for Counter {}
+}
@@ -0,0 +1,83 @@
+pub mod outer {
+    #[no_mangle]
+    #[allow(non_snake_case)]
+    pub extern "C" fn version() -> u32 {
+        use crate::mapping::{MapFrom, MapTo};
+        use crate::langs::*;
+        let mut block_closure = move || { { 1 } };
+        let __output = block_closure();
+        let __output = __output;
+        __output
+    }
+    pub mod inner {
+        pub struct Thing {
+            inner: super::Thing,
+        }
+        impl Thing {
+            #[no_mangle]
+            #[allow(non_snake_case)]
+            pub extern "C" fn outer_inner_thing_new(__ptr_out: *mut *mut Self) {
+                use crate::mapping::{MapFrom, MapTo};
+                use crate::langs::*;
+                let mut block_closure = move || { { super::Thing::default().into() } };
+                let __output = block_closure();
+                let __output: Self = __output;
+                let __output: *mut Self = __output.map_to();
+                unsafe {
+                    *__ptr_out = __output;
+                }
+            }
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "C" fn outer_inner_count() -> u32 {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let mut block_closure = move || { { 0 } };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        impl crate::langs::ExposedPtr for Thing {
+            type Kind = crate::langs::Shared;
+        }
+        impl Thing {
+            #[no_mangle]
+            pub unsafe extern "C" fn outer_inner_thing_retain(
+                ptr: *mut Self,
+            ) -> *mut Self {
+                crate::langs::Shared::retain(ptr)
+            }
+            #[no_mangle]
+            pub unsafe extern "C" fn outer_inner_thing_release(ptr: *mut Self) {
+                crate::langs::Shared::release(ptr)
+            }
+        }
+        impl Thing {}
+        impl crate::common::ExposedStruct for Thing {}
+    }
+    pub mod prefixed {
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "C" fn ext_count() -> u32 {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let mut block_closure = move || { { 0 } };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        pub mod nested {
+            #[no_mangle]
+            #[allow(non_snake_case)]
+            pub extern "C" fn ext_outer_prefixed_nested_count() -> u32 {
+                use crate::mapping::{MapFrom, MapTo};
+                use crate::langs::*;
+                let mut block_closure = move || { { 0 } };
+                let __output = block_closure();
+                let __output = __output;
+                __output
+            }
+        }
+    }
+}
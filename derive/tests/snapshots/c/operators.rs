@@ -0,0 +1,160 @@
+pub mod operators {
+    use super::Error;
+    pub struct Amount {
+        inner: super::Amount,
+    }
+    impl Amount {
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "C" fn amount_destroy(_s: *mut Self) -> () {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let _temp__s = (_s);
+            let _s = <Destroy<Self>>::map_from(_temp__s);
+            let mut block_closure = move || { {} };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+    }
+    impl std::ops::Add for Amount {
+        type Output = Result<Amount, Error>;
+        fn add(self, rhs: Self) -> Self::Output {
+            Ok(self.inner.checked_add(rhs.inner)?.into())
+        }
+    }
+    impl std::ops::Mul<u64> for Amount {
+        type Output = Amount;
+        fn mul(self, rhs: u64) -> Self::Output {
+            (self.inner * rhs).into()
+        }
+    }
+    impl std::ops::Neg for Amount {
+        type Output = Amount;
+        fn neg(self) -> Self::Output {
+            (-self.inner).into()
+        }
+    }
+    pub struct Script {
+        inner: super::Script,
+    }
+    impl Script {
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "C" fn script_destroy(_s: *mut Self) -> () {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let _temp__s = (_s);
+            let _s = <Destroy<Self>>::map_from(_temp__s);
+            let mut block_closure = move || { {} };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+    }
+    impl std::ops::Index<usize> for Script {
+        type Output = u8;
+        fn index(&self, index: usize) -> &Self::Output {
+            &self.inner.as_bytes()[index]
+        }
+    }
+    impl crate::common::CheckedIndex<usize> for Script {
+        fn checked_index(&self, index: usize) -> Option<&u8> {
+            self.inner.as_bytes().get(index)
+        }
+    }
+    impl crate::langs::ExposedPtr for Amount {
+        type Kind = crate::langs::Boxed;
+    }
+    impl Amount {}
+    impl crate::common::ExposedStruct for Amount {}
+    impl crate::langs::ExposedPtr for Script {
+        type Kind = crate::langs::Boxed;
+    }
+    impl Script {}
+    impl crate::common::ExposedStruct for Script {}
+    impl Amount {
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "C" fn amount_add(
+            &self,
+            rhs: &Self,
+            __ptr_out: *mut *mut Self,
+        ) -> i32 {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let _temp_rhs = (rhs);
+            let rhs = _temp_rhs;
+            let mut block_closure = move || {
+                { std::ops::Add::add(self.clone(), rhs.clone()) }
+            };
+            let __output = block_closure();
+            let __output: Result<Self, Error> = __output;
+            let __output: *mut Self = match __output {
+                Ok(inner) => inner.map_to(),
+                Err(e) => return e.into_platform_error(),
+            };
+            unsafe {
+                *__ptr_out = __output;
+            }
+            Error::ok()
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "C" fn amount_mul(&self, rhs: u64, __ptr_out: *mut *mut Self) {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let _temp_rhs = (rhs);
+            let rhs = _temp_rhs;
+            let mut block_closure = move || {
+                { std::ops::Mul::mul(self.clone(), rhs) }
+            };
+            let __output = block_closure();
+            let __output: Self = __output;
+            let __output: *mut Self = __output.map_to();
+            unsafe {
+                *__ptr_out = __output;
+            }
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "C" fn amount_neg(&self, __ptr_out: *mut *mut Self) {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let mut block_closure = move || { { std::ops::Neg::neg(self.clone()) } };
+            let __output = block_closure();
+            let __output: Self = __output;
+            let __output: *mut Self = __output.map_to();
+            unsafe {
+                *__ptr_out = __output;
+            }
+        }
+    }
+    impl Script {
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "C" fn script_index(&self, rhs: usize, __ptr_out: *mut u8) -> i32 {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let _temp_rhs = (rhs);
+            let rhs = _temp_rhs;
+            let mut block_closure = move || {
+                {
+                    crate::common::CheckedIndex::checked_index(self, rhs)
+                        .cloned()
+                        .ok_or(crate::common::IndexError)
+                }
+            };
+            let __output = block_closure();
+            let __output: Result<u8, crate::common::IndexError> = __output;
+            let __output: u8 = match __output {
+                Ok(inner) => inner.map_to(),
+                Err(e) => return e.into_platform_error(),
+            };
+            unsafe {
+                *__ptr_out = __output;
+            }
+            crate::common::IndexError::ok()
+        }
+    }
+}
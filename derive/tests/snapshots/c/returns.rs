@@ -0,0 +1,117 @@
+pub mod returns {
+    use super::Error;
+    pub struct Item {
+        inner: super::Item,
+    }
+    impl Item {
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "C" fn item_destroy(_s: *mut Self) -> () {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let _temp__s = (_s);
+            let _s = <Destroy<Self>>::map_from(_temp__s);
+            let mut block_closure = move || { {} };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "C" fn item_parse(
+            s: *const libc::c_char,
+            __ptr_out: *mut *mut Self,
+        ) -> i32 {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let _temp_s = (s);
+            let s = <String>::map_from(_temp_s);
+            let mut block_closure = move || { { Ok(s.parse::<super::Item>()?.into()) } };
+            let __output = block_closure();
+            let __output: Result<Self, Error> = __output;
+            let __output: *mut Self = match __output {
+                Ok(inner) => inner.map_to(),
+                Err(e) => return e.into_platform_error(),
+            };
+            unsafe {
+                *__ptr_out = __output;
+            }
+            Error::ok()
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "C" fn item_check(&self) -> i32 {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let mut block_closure = move || { { self.inner.check() } };
+            let __output = block_closure();
+            let __output: Result<(), super::Error> = __output;
+            let __output: () = match __output {
+                Ok(inner) => inner.map_to(),
+                Err(e) => return e.into_platform_error(),
+            };
+            super::Error::ok()
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "C" fn item_find(
+            name: *const libc::c_char,
+            __ptr_out: *mut *mut Self,
+        ) {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let _temp_name = (name);
+            let name = <String>::map_from(_temp_name);
+            let mut block_closure = move || {
+                { super::Item::find(&name).map(Into::into) }
+            };
+            let __output = block_closure();
+            let __output: Option<Self> = __output;
+            let __output: *mut Self = match __output {
+                Some(inner) => inner.map_to(),
+                None => std::ptr::null_mut(),
+            };
+            unsafe {
+                *__ptr_out = __output;
+            }
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "C" fn item_to_bytes(&self) -> crate::langs::Arr<u8> {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let mut block_closure = move || { { self.inner.to_bytes() } };
+            let __output = block_closure();
+            let __output: Vec<u8> = __output;
+            let __output = __output.map_to();
+            __output
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "C" fn item_name(&self) -> *mut libc::c_char {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let mut block_closure = move || { { self.inner.name().to_string() } };
+            let __output = block_closure();
+            let __output: String = __output;
+            let __output = __output.map_to();
+            __output
+        }
+    }
+    #[no_mangle]
+    #[allow(non_snake_case)]
+    pub extern "C" fn last_error() -> i32 {
+        use crate::mapping::{MapFrom, MapTo};
+        use crate::langs::*;
+        let mut block_closure = move || { { super::last_error() } };
+        let __output = block_closure();
+        let __output: Error = __output;
+        let __output = __output.map_to();
+        __output
+    }
+    impl crate::langs::ExposedPtr for Item {
+        type Kind = crate::langs::Boxed;
+    }
+    impl Item {}
+    impl crate::common::ExposedStruct for Item {}
+}
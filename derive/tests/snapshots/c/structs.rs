@@ -0,0 +1,297 @@
+pub mod structs {
+    pub struct Opaque {
+        inner: super::Opaque,
+    }
+    impl Opaque {
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "C" fn opaque_destroy(_s: *mut Self) -> () {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let _temp__s = (_s);
+            let _s = <Destroy<Self>>::map_from(_temp__s);
+            let mut block_closure = move || { {} };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+    }
+    pub struct Shared {
+        inner: super::Shared,
+    }
+    pub struct WithFields {
+        pub(crate) shared: <Shared as crate::common::WrappedStructField>::Store,
+        pub(crate) value: <u32 as crate::common::WrappedStructField>::Store,
+    }
+    impl WithFields {
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "C" fn with_fields_destroy(_s: *mut Self) -> () {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let _temp__s = (_s);
+            let _s = <Destroy<Self>>::map_from(_temp__s);
+            let mut block_closure = move || { {} };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+    }
+    #[repr(C)]
+    pub struct Point {
+        pub x: i32,
+        pub y: i32,
+    }
+    pub struct Script {
+        inner: super::Script,
+    }
+    impl Script {
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "C" fn script_destroy(_s: *mut Self) -> () {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let _temp__s = (_s);
+            let _s = <Destroy<Self>>::map_from(_temp__s);
+            let mut block_closure = move || { {} };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+    }
+    #[repr(C)]
+    pub struct WithOpaque {
+        pub script: Box<Script>,
+        pub value: u32,
+    }
+    impl WithOpaque {
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "C" fn with_opaque_destroy(_s: *mut Self) -> () {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let _temp__s = (_s);
+            let _s = <Destroy<Self>>::map_from(_temp__s);
+            let mut block_closure = move || { {} };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+    }
+    impl crate::langs::ExposedPtr for Opaque {
+        type Kind = crate::langs::Boxed;
+    }
+    impl Opaque {}
+    impl crate::common::ExposedStruct for Opaque {}
+    impl crate::langs::ExposedPtr for Shared {
+        type Kind = crate::langs::Shared;
+    }
+    impl Shared {
+        #[no_mangle]
+        pub unsafe extern "C" fn shared_retain(ptr: *mut Self) -> *mut Self {
+            crate::langs::Shared::retain(ptr)
+        }
+        #[no_mangle]
+        pub unsafe extern "C" fn shared_release(ptr: *mut Self) {
+            crate::langs::Shared::release(ptr)
+        }
+    }
+    impl Shared {}
+    impl crate::common::ExposedStruct for Shared {}
+    impl crate::langs::ExposedPtr for WithFields {
+        type Kind = crate::langs::Boxed;
+    }
+    impl WithFields {
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        /// Borrowed from `self`, except for `shared` structs: those are returned with a new
+        /// reference, owned by the caller and given back with `<type>_release`
+        pub extern "C" fn with_fields_get_shared(&mut self) -> *mut Shared {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let mut block_closure = move || {
+                {
+                    crate::langs::assert_unique_ptr::<Self>();
+                    use crate::common::WrappedStructField;
+                    Shared::wrap_get(&mut self.shared)
+                }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "C" fn with_fields_set_shared(&mut self, shared: &Shared) -> () {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let _temp_shared = (shared);
+            let shared = _temp_shared;
+            let mut block_closure = move || {
+                {
+                    crate::langs::assert_unique_ptr::<Self>();
+                    use crate::common::WrappedStructField;
+                    self
+                        .shared = Shared::wrap_set(
+                        crate::mapping::MapFrom::map_from(shared),
+                    );
+                }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "C" fn with_fields_get_value(&mut self) -> u32 {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let mut block_closure = move || {
+                {
+                    crate::langs::assert_unique_ptr::<Self>();
+                    use crate::common::WrappedStructField;
+                    u32::wrap_get(&mut self.value)
+                }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "C" fn with_fields_set_value(&mut self, value: u32) -> i32 {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let _temp_value = (value);
+            let value = _temp_value;
+            let mut block_closure = move || {
+                {
+                    crate::langs::assert_unique_ptr::<Self>();
+                    use crate::common::WrappedStructField;
+                    let value: u32 = crate::mapping::MapFrom::map_from(value);
+                    WithFields::validate_value(self, &value)
+                        .map_err(crate::common::ValidationError::from_display)?;
+                    self.value = u32::wrap_set(value);
+                    Ok(())
+                }
+            };
+            let __output = block_closure();
+            let __output: Result<(), crate::common::ValidationError> = __output;
+            let __output: () = match __output {
+                Ok(inner) => inner.map_to(),
+                Err(e) => return e.into_platform_error(),
+            };
+            crate::common::ValidationError::ok()
+        }
+    }
+    impl crate::common::ExposedStruct for WithFields {}
+    impl crate::langs::ReprC for Point {}
+    impl crate::langs::ExposedPtr for Point {
+        type Kind = crate::langs::Boxed;
+    }
+    impl Point {
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "C" fn point_get_x(&self) -> i32 {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let mut block_closure = move || { { self.x } };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "C" fn point_set_x(&mut self, x: i32) -> () {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let _temp_x = (x);
+            let x = _temp_x;
+            let mut block_closure = move || {
+                {
+                    crate::langs::assert_unique_ptr::<Self>();
+                    self.x = crate::mapping::MapFrom::map_from(x);
+                }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "C" fn point_get_y(&self) -> i32 {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let mut block_closure = move || { { self.y } };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+    }
+    const _: () = {
+        #[allow(dead_code)]
+        fn assert_fields() {
+            crate::langs::assert_repr_c::<i32>();
+            crate::langs::assert_repr_c::<i32>();
+        }
+    };
+    impl crate::common::ExposedStruct for Point {}
+    impl crate::langs::ExposedPtr for Script {
+        type Kind = crate::langs::Boxed;
+    }
+    impl Script {}
+    impl crate::common::ExposedStruct for Script {}
+    impl crate::langs::ReprC for WithOpaque {}
+    impl crate::langs::ExposedPtr for WithOpaque {
+        type Kind = crate::langs::Boxed;
+    }
+    impl WithOpaque {
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        /// Borrowed from `self`, except for `shared` structs: those are returned with a new
+        /// reference, owned by the caller and given back with `<type>_release`
+        pub extern "C" fn with_opaque_get_script(&mut self) -> *mut Script {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let mut block_closure = move || {
+                {
+                    crate::langs::assert_unique_ptr::<Self>();
+                    use crate::common::WrappedStructField;
+                    Script::wrap_get(&mut self.script)
+                }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "C" fn with_opaque_set_script(&mut self, script: &Script) -> () {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let _temp_script = (script);
+            let script = _temp_script;
+            let mut block_closure = move || {
+                {
+                    crate::langs::assert_unique_ptr::<Self>();
+                    use crate::common::WrappedStructField;
+                    self
+                        .script = Script::wrap_set(
+                        crate::mapping::MapFrom::map_from(script),
+                    );
+                }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+    }
+    const _: () = {
+        #[allow(dead_code)]
+        fn assert_fields() {
+            crate::langs::assert_repr_c::<Box<Script>>();
+            crate::langs::assert_repr_c::<u32>();
+        }
+    };
+    impl crate::common::ExposedStruct for WithOpaque {}
+}
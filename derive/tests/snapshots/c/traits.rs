@@ -0,0 +1,88 @@
+pub mod traits {
+    pub trait Listener: super::Listener {
+        fn _wrapper_on_event(&self, event: String) -> u32;
+    }
+    pub struct ListenerStruct {
+        this: *mut libc::c_void,
+        destroy: Box<dyn Fn(*mut libc::c_void)>,
+        _wrapper_on_event: Box<dyn Fn(*mut libc::c_void, String) -> u32>,
+    }
+    impl ListenerStruct {
+        #[no_mangle]
+        pub extern "C" fn listener_struct_new(
+            this: *mut libc::c_void,
+            destroy: unsafe extern "C" fn(*mut libc::c_void),
+            _wrapper_on_event: unsafe extern "C" fn(
+                this: *mut libc::c_void,
+                event: *mut libc::c_char,
+            ) -> u32,
+            ptr_out: *mut *mut Self,
+        ) {
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            let s = ListenerStruct {
+                this,
+                destroy: Box::new(move |this: *mut libc::c_void| {
+                    if this != std::ptr::null_mut() {
+                        unsafe { destroy(this) }
+                    }
+                }),
+                _wrapper_on_event: Box::new(move |
+                    this: *mut libc::c_void,
+                    event: String|
+                {
+                    let this = this;
+                    let (this) = this;
+                    let event: String = event;
+                    let event = event.map_to();
+                    let (event) = event;
+                    let result = unsafe { _wrapper_on_event(this, event) };
+                    let result = { result };
+                    result
+                }),
+            };
+            unsafe {
+                *ptr_out = Box::into_raw(Box::new(s));
+            }
+        }
+        #[no_mangle]
+        pub unsafe extern "C" fn listener_struct_destroy(s: *mut Self) {
+            Box::from_raw(s);
+        }
+    }
+    impl Listener for ListenerStruct {
+        fn _wrapper_on_event(&self, event: String) -> u32 {
+            (self._wrapper_on_event)(self.this, event)
+        }
+    }
+    impl std::ops::Drop for ListenerStruct {
+        fn drop(&mut self) {
+            (self.destroy)(self.this)
+        }
+    }
+    impl<T: 'static + super::Listener + Sized + Send> crate::langs::IntoTraitStruct
+    for T {
+        type Target = ListenerStruct;
+        fn into_trait_struct(self) -> Self::Target {
+            use crate::langs::take_ptr;
+            let this = Box::into_raw(Box::new(self)) as *mut libc::c_void;
+            fn destroy<T>(this: *mut libc::c_void) {
+                let _this = take_ptr::<T>(this);
+            }
+            fn _wrapper_on_event<T: super::Listener>(
+                this: *mut libc::c_void,
+                event: String,
+            ) -> u32 {
+                let this = take_ptr::<T>(this);
+                let result = this.on_event(event);
+                std::mem::forget(this);
+                return result;
+            }
+            ListenerStruct {
+                this,
+                destroy: Box::new(destroy::<Self>),
+                _wrapper_on_event: Box::new(_wrapper_on_event::<Self>),
+            }
+        }
+    }
+}
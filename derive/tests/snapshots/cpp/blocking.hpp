@@ -0,0 +1,132 @@
+// Generated by `#[expose_mod]`, do not edit
+#ifndef DERIVE_BLOCKING_HPP
+#define DERIVE_BLOCKING_HPP
+
+#include <cstdint>
+#include <optional>
+#include <stdexcept>
+#include <string>
+#include <utility>
+#include <vector>
+
+#include "derive.h"
+
+namespace derive {
+
+#ifndef DERIVE_HELPERS_DEFINED
+#define DERIVE_HELPERS_DEFINED
+/// Base class of the exceptions thrown when the C API returns an error code
+class Exception : public std::runtime_error {
+public:
+    Exception(const char *name, int32_t code)
+        : std::runtime_error(std::string(name) + " (code " + std::to_string(code) + ")"), code_(code) {}
+
+    int32_t code() const noexcept {
+        return code_;
+    }
+
+private:
+    int32_t code_;
+};
+
+/// Copy a string returned by the C API, and free it
+inline std::string take_string(char *s) {
+    std::string copy(s);
+    ::string_free(s);
+    return copy;
+}
+
+/// Copy a vector returned by the C API, and free it
+inline std::vector<uint8_t> take_bytes(::Arr_u8 arr) {
+    std::vector<uint8_t> copy(arr.ptr, arr.ptr + arr.len);
+    ::bytes_free(arr);
+    return copy;
+}
+#endif
+
+#ifndef DERIVE_ERROR_DEFINED
+#define DERIVE_ERROR_DEFINED
+class Error : public Exception {
+public:
+    explicit Error(int32_t code) : Exception("Error", code) {}
+};
+#endif
+
+} // namespace derive
+
+namespace derive::blocking {
+
+class Wallet;
+
+} // namespace derive::blocking
+
+namespace derive::blocking {
+
+class Wallet {
+public:
+    /// Take the ownership of a pointer returned by the C API
+    explicit Wallet(::Wallet *ptr) noexcept : ptr_(ptr) {}
+    ~Wallet() {
+        if (ptr_) {
+            ::wallet_destroy(ptr_);
+        }
+    }
+
+    Wallet(Wallet &&other) noexcept : ptr_(std::exchange(other.ptr_, nullptr)) {}
+    Wallet &operator=(Wallet &&other) noexcept {
+        std::swap(ptr_, other.ptr_);
+        return *this;
+    }
+    Wallet(const Wallet &) = delete;
+    Wallet &operator=(const Wallet &) = delete;
+
+    /// The underlying pointer, still owned by this object
+    ::Wallet *as_raw() const noexcept {
+        return ptr_;
+    }
+    /// Give up the ownership of the underlying pointer
+    ::Wallet *into_raw() noexcept {
+        return std::exchange(ptr_, nullptr);
+    }
+
+    explicit Wallet(const std::string &descriptor);
+    uint64_t sync(const std::string &url, size_t stop_gap) const;
+    std::vector<uint8_t> sign(const std::vector<uint8_t> &psbt) const;
+
+private:
+    ::Wallet *ptr_;
+};
+
+} // namespace derive::blocking
+
+namespace derive::blocking {
+
+inline Wallet::Wallet(const std::string &descriptor) : ptr_(nullptr) {
+    ::wallet_new(descriptor.c_str(), &ptr_);
+}
+
+inline uint64_t Wallet::sync(const std::string &url, size_t stop_gap) const {
+    uint64_t out{};
+    const auto code = ::wallet_sync(ptr_, url.c_str(), stop_gap, &out);
+    if (code != 0) {
+        throw Error(code);
+    }
+    return out;
+}
+
+inline std::vector<uint8_t> Wallet::sign(const std::vector<uint8_t> &psbt) const {
+    return take_bytes(::wallet_sign(ptr_, ::Arr_u8{psbt.data(), psbt.size()}));
+}
+
+inline std::string broadcast(const std::string &url, const std::vector<uint8_t> &tx) {
+    char *out = nullptr;
+    const auto code = ::broadcast(url.c_str(), ::Arr_u8{tx.data(), tx.size()}, &out);
+    if (code != 0) {
+        throw Error(code);
+    }
+    return take_string(out);
+}
+
+} // namespace derive::blocking
+
+#endif // DERIVE_BLOCKING_HPP
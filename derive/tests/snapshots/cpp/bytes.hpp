@@ -0,0 +1,107 @@
+// Generated by `#[expose_mod]`, do not edit
+#ifndef DERIVE_BYTES_HPP
+#define DERIVE_BYTES_HPP
+
+#include <cstdint>
+#include <optional>
+#include <stdexcept>
+#include <string>
+#include <utility>
+#include <vector>
+
+#include "derive.h"
+
+namespace derive {
+
+#ifndef DERIVE_HELPERS_DEFINED
+#define DERIVE_HELPERS_DEFINED
+/// Base class of the exceptions thrown when the C API returns an error code
+class Exception : public std::runtime_error {
+public:
+    Exception(const char *name, int32_t code)
+        : std::runtime_error(std::string(name) + " (code " + std::to_string(code) + ")"), code_(code) {}
+
+    int32_t code() const noexcept {
+        return code_;
+    }
+
+private:
+    int32_t code_;
+};
+
+/// Copy a string returned by the C API, and free it
+inline std::string take_string(char *s) {
+    std::string copy(s);
+    ::string_free(s);
+    return copy;
+}
+
+/// Copy a vector returned by the C API, and free it
+inline std::vector<uint8_t> take_bytes(::Arr_u8 arr) {
+    std::vector<uint8_t> copy(arr.ptr, arr.ptr + arr.len);
+    ::bytes_free(arr);
+    return copy;
+}
+#endif
+
+} // namespace derive
+
+namespace derive::bytes {
+
+class Script;
+
+} // namespace derive::bytes
+
+namespace derive::bytes {
+
+class Script {
+public:
+    /// Take the ownership of a pointer returned by the C API
+    explicit Script(::Script *ptr) noexcept : ptr_(ptr) {}
+    ~Script() {
+        if (ptr_) {
+            ::script_destroy(ptr_);
+        }
+    }
+
+    Script(Script &&other) noexcept : ptr_(std::exchange(other.ptr_, nullptr)) {}
+    Script &operator=(Script &&other) noexcept {
+        std::swap(ptr_, other.ptr_);
+        return *this;
+    }
+    Script(const Script &) = delete;
+    Script &operator=(const Script &) = delete;
+
+    /// The underlying pointer, still owned by this object
+    ::Script *as_raw() const noexcept {
+        return ptr_;
+    }
+    /// Give up the ownership of the underlying pointer
+    ::Script *into_raw() noexcept {
+        return std::exchange(ptr_, nullptr);
+    }
+
+    std::vector<uint8_t> to_bytes() const;
+    static Script from_bytes(const std::vector<uint8_t> &bytes);
+
+private:
+    ::Script *ptr_;
+};
+
+} // namespace derive::bytes
+
+namespace derive::bytes {
+
+inline std::vector<uint8_t> Script::to_bytes() const {
+    return take_bytes(::script_to_bytes(ptr_));
+}
+
+inline Script Script::from_bytes(const std::vector<uint8_t> &bytes) {
+    ::Script *out = nullptr;
+    ::script_from_bytes(::Arr_u8{bytes.data(), bytes.size()}, &out);
+    return Script(out);
+}
+
+} // namespace derive::bytes
+
+#endif // DERIVE_BYTES_HPP
@@ -0,0 +1,57 @@
+// Generated by `#[expose_mod]`, do not edit
+#ifndef DERIVE_CALLBACKS_HPP
+#define DERIVE_CALLBACKS_HPP
+
+#include <cstdint>
+#include <optional>
+#include <stdexcept>
+#include <string>
+#include <utility>
+#include <vector>
+
+#include "derive.h"
+
+namespace derive {
+
+#ifndef DERIVE_HELPERS_DEFINED
+#define DERIVE_HELPERS_DEFINED
+/// Base class of the exceptions thrown when the C API returns an error code
+class Exception : public std::runtime_error {
+public:
+    Exception(const char *name, int32_t code)
+        : std::runtime_error(std::string(name) + " (code " + std::to_string(code) + ")"), code_(code) {}
+
+    int32_t code() const noexcept {
+        return code_;
+    }
+
+private:
+    int32_t code_;
+};
+
+/// Copy a string returned by the C API, and free it
+inline std::string take_string(char *s) {
+    std::string copy(s);
+    ::string_free(s);
+    return copy;
+}
+
+/// Copy a vector returned by the C API, and free it
+inline std::vector<uint8_t> take_bytes(::Arr_u8 arr) {
+    std::vector<uint8_t> copy(arr.ptr, arr.ptr + arr.len);
+    ::bytes_free(arr);
+    return copy;
+}
+#endif
+
+} // namespace derive
+
+namespace derive::callbacks {
+
+// `apply` is not available: unsupported argument or return types
+
+// `describe` is not available: unsupported argument or return types
+
+} // namespace derive::callbacks
+
+#endif // DERIVE_CALLBACKS_HPP
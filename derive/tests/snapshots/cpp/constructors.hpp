@@ -0,0 +1,128 @@
+// Generated by `#[expose_mod]`, do not edit
+#ifndef DERIVE_CONSTRUCTORS_HPP
+#define DERIVE_CONSTRUCTORS_HPP
+
+#include <cstdint>
+#include <optional>
+#include <stdexcept>
+#include <string>
+#include <utility>
+#include <vector>
+
+#include "derive.h"
+
+namespace derive {
+
+#ifndef DERIVE_HELPERS_DEFINED
+#define DERIVE_HELPERS_DEFINED
+/// Base class of the exceptions thrown when the C API returns an error code
+class Exception : public std::runtime_error {
+public:
+    Exception(const char *name, int32_t code)
+        : std::runtime_error(std::string(name) + " (code " + std::to_string(code) + ")"), code_(code) {}
+
+    int32_t code() const noexcept {
+        return code_;
+    }
+
+private:
+    int32_t code_;
+};
+
+/// Copy a string returned by the C API, and free it
+inline std::string take_string(char *s) {
+    std::string copy(s);
+    ::string_free(s);
+    return copy;
+}
+
+/// Copy a vector returned by the C API, and free it
+inline std::vector<uint8_t> take_bytes(::Arr_u8 arr) {
+    std::vector<uint8_t> copy(arr.ptr, arr.ptr + arr.len);
+    ::bytes_free(arr);
+    return copy;
+}
+#endif
+
+#ifndef DERIVE_ERROR_DEFINED
+#define DERIVE_ERROR_DEFINED
+class Error : public Exception {
+public:
+    explicit Error(int32_t code) : Exception("Error", code) {}
+};
+#endif
+
+} // namespace derive
+
+namespace derive::constructors {
+
+class Network;
+
+} // namespace derive::constructors
+
+namespace derive::constructors {
+
+class Network {
+public:
+    /// Take the ownership of a pointer returned by the C API
+    explicit Network(::Network *ptr) noexcept : ptr_(ptr) {}
+    ~Network() {
+        if (ptr_) {
+            ::network_destroy(ptr_);
+        }
+    }
+
+    Network(Network &&other) noexcept : ptr_(std::exchange(other.ptr_, nullptr)) {}
+    Network &operator=(Network &&other) noexcept {
+        std::swap(ptr_, other.ptr_);
+        return *this;
+    }
+    Network(const Network &) = delete;
+    Network &operator=(const Network &) = delete;
+
+    /// The underlying pointer, still owned by this object
+    ::Network *as_raw() const noexcept {
+        return ptr_;
+    }
+    /// Give up the ownership of the underlying pointer
+    ::Network *into_raw() noexcept {
+        return std::exchange(ptr_, nullptr);
+    }
+
+    explicit Network(const std::string &s);
+    static Network bitcoin();
+    static std::optional<Network> from_magic(uint32_t magic);
+
+private:
+    ::Network *ptr_;
+};
+
+} // namespace derive::constructors
+
+namespace derive::constructors {
+
+inline Network::Network(const std::string &s) : ptr_(nullptr) {
+    const auto code = ::network_new(s.c_str(), &ptr_);
+    if (code != 0) {
+        throw Error(code);
+    }
+}
+
+inline Network Network::bitcoin() {
+    ::Network *out = nullptr;
+    ::network_new_bitcoin(&out);
+    return Network(out);
+}
+
+inline std::optional<Network> Network::from_magic(uint32_t magic) {
+    ::Network *out = nullptr;
+    ::network_new_from_magic(magic, &out);
+    if (!out) {
+        return std::nullopt;
+    }
+    return Network(out);
+}
+
+} // namespace derive::constructors
+
+#endif // DERIVE_CONSTRUCTORS_HPP
@@ -0,0 +1,127 @@
+// Generated by `#[expose_mod]`, do not edit
+#ifndef DERIVE_DEFAULTS_HPP
+#define DERIVE_DEFAULTS_HPP
+
+#include <cstdint>
+#include <optional>
+#include <stdexcept>
+#include <string>
+#include <utility>
+#include <vector>
+
+#include "derive.h"
+
+namespace derive {
+
+#ifndef DERIVE_HELPERS_DEFINED
+#define DERIVE_HELPERS_DEFINED
+/// Base class of the exceptions thrown when the C API returns an error code
+class Exception : public std::runtime_error {
+public:
+    Exception(const char *name, int32_t code)
+        : std::runtime_error(std::string(name) + " (code " + std::to_string(code) + ")"), code_(code) {}
+
+    int32_t code() const noexcept {
+        return code_;
+    }
+
+private:
+    int32_t code_;
+};
+
+/// Copy a string returned by the C API, and free it
+inline std::string take_string(char *s) {
+    std::string copy(s);
+    ::string_free(s);
+    return copy;
+}
+
+/// Copy a vector returned by the C API, and free it
+inline std::vector<uint8_t> take_bytes(::Arr_u8 arr) {
+    std::vector<uint8_t> copy(arr.ptr, arr.ptr + arr.len);
+    ::bytes_free(arr);
+    return copy;
+}
+#endif
+
+} // namespace derive
+
+namespace derive::defaults {
+
+class FeeRate;
+
+} // namespace derive::defaults
+
+namespace derive::defaults {
+
+class FeeRate {
+public:
+    /// Take the ownership of a pointer returned by the C API
+    explicit FeeRate(::FeeRate *ptr) noexcept : ptr_(ptr) {}
+    ~FeeRate() {
+        if (ptr_) {
+            ::fee_rate_destroy(ptr_);
+        }
+    }
+
+    FeeRate(FeeRate &&other) noexcept : ptr_(std::exchange(other.ptr_, nullptr)) {}
+    FeeRate &operator=(FeeRate &&other) noexcept {
+        std::swap(ptr_, other.ptr_);
+        return *this;
+    }
+    FeeRate(const FeeRate &) = delete;
+    FeeRate &operator=(const FeeRate &) = delete;
+
+    /// The underlying pointer, still owned by this object
+    ::FeeRate *as_raw() const noexcept {
+        return ptr_;
+    }
+    /// Give up the ownership of the underlying pointer
+    ::FeeRate *into_raw() noexcept {
+        return std::exchange(ptr_, nullptr);
+    }
+
+    static FeeRate from_sat_per_vb(float sat_per_vb);
+    static FeeRate from_sat_per_vb();
+    uint64_t fee_for(uint64_t vbytes, const FeeRate &min) const;
+    uint64_t fee_for(uint64_t vbytes) const;
+
+private:
+    ::FeeRate *ptr_;
+};
+
+} // namespace derive::defaults
+
+namespace derive::defaults {
+
+inline FeeRate FeeRate::from_sat_per_vb(float sat_per_vb) {
+    ::FeeRate *out = nullptr;
+    ::fee_rate_from_sat_per_vb(sat_per_vb, &out);
+    return FeeRate(out);
+}
+
+inline FeeRate FeeRate::from_sat_per_vb() {
+    ::FeeRate *out = nullptr;
+    ::fee_rate_from_sat_per_vb_with_defaults(&out);
+    return FeeRate(out);
+}
+
+inline uint64_t FeeRate::fee_for(uint64_t vbytes, const FeeRate &min) const {
+    return ::fee_rate_fee_for(ptr_, vbytes, min.as_raw());
+}
+
+inline uint64_t FeeRate::fee_for(uint64_t vbytes) const {
+    return ::fee_rate_fee_for_with_defaults(ptr_, vbytes);
+}
+
+inline std::string greet(const std::string &name) {
+    return take_string(::greet(name.c_str()));
+}
+
+inline std::string greet() {
+    return take_string(::greet_with_defaults());
+}
+
+} // namespace derive::defaults
+
+#endif // DERIVE_DEFAULTS_HPP
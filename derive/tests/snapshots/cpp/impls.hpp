@@ -0,0 +1,122 @@
+// Generated by `#[expose_mod]`, do not edit
+#ifndef DERIVE_IMPLS_HPP
+#define DERIVE_IMPLS_HPP
+
+#include <cstdint>
+#include <optional>
+#include <stdexcept>
+#include <string>
+#include <utility>
+#include <vector>
+
+#include "derive.h"
+
+namespace derive {
+
+#ifndef DERIVE_HELPERS_DEFINED
+#define DERIVE_HELPERS_DEFINED
+/// Base class of the exceptions thrown when the C API returns an error code
+class Exception : public std::runtime_error {
+public:
+    Exception(const char *name, int32_t code)
+        : std::runtime_error(std::string(name) + " (code " + std::to_string(code) + ")"), code_(code) {}
+
+    int32_t code() const noexcept {
+        return code_;
+    }
+
+private:
+    int32_t code_;
+};
+
+/// Copy a string returned by the C API, and free it
+inline std::string take_string(char *s) {
+    std::string copy(s);
+    ::string_free(s);
+    return copy;
+}
+
+/// Copy a vector returned by the C API, and free it
+inline std::vector<uint8_t> take_bytes(::Arr_u8 arr) {
+    std::vector<uint8_t> copy(arr.ptr, arr.ptr + arr.len);
+    ::bytes_free(arr);
+    return copy;
+}
+#endif
+
+} // namespace derive
+
+namespace derive::impls {
+
+class Counter;
+
+} // namespace derive::impls
+
+namespace derive::impls {
+
+class Counter {
+public:
+    /// Take the ownership of a pointer returned by the C API
+    explicit Counter(::Counter *ptr) noexcept : ptr_(ptr) {}
+    ~Counter() {
+        if (ptr_) {
+            ::counter_destroy(ptr_);
+        }
+    }
+
+    Counter(Counter &&other) noexcept : ptr_(std::exchange(other.ptr_, nullptr)) {}
+    Counter &operator=(Counter &&other) noexcept {
+        std::swap(ptr_, other.ptr_);
+        return *this;
+    }
+    Counter(const Counter &) = delete;
+    Counter &operator=(const Counter &) = delete;
+
+    /// The underlying pointer, still owned by this object
+    ::Counter *as_raw() const noexcept {
+        return ptr_;
+    }
+    /// Give up the ownership of the underlying pointer
+    ::Counter *into_raw() noexcept {
+        return std::exchange(ptr_, nullptr);
+    }
+
+    explicit Counter(uint32_t start);
+    static Counter zero();
+    uint32_t get() const;
+    void increment(uint32_t by);
+    std::string label(const std::string &prefix) const;
+
+private:
+    ::Counter *ptr_;
+};
+
+} // namespace derive::impls
+
+namespace derive::impls {
+
+inline Counter::Counter(uint32_t start) : ptr_(nullptr) {
+    ::counter_new(start, &ptr_);
+}
+
+inline Counter Counter::zero() {
+    ::Counter *out = nullptr;
+    ::counter_zero(&out);
+    return Counter(out);
+}
+
+inline uint32_t Counter::get() const {
+    return ::counter_get(ptr_);
+}
+
+inline void Counter::increment(uint32_t by) {
+    ::counter_increment(ptr_, by);
+}
+
+inline std::string Counter::label(const std::string &prefix) const {
+    return take_string(::counter_label(ptr_, prefix.c_str()));
+}
+
+} // namespace derive::impls
+
+#endif // DERIVE_IMPLS_HPP
@@ -0,0 +1,134 @@
+// Generated by `#[expose_mod]`, do not edit
+#ifndef DERIVE_OUTER_HPP
+#define DERIVE_OUTER_HPP
+
+#include <cstdint>
+#include <optional>
+#include <stdexcept>
+#include <string>
+#include <utility>
+#include <vector>
+
+#include "derive.h"
+
+namespace derive {
+
+#ifndef DERIVE_HELPERS_DEFINED
+#define DERIVE_HELPERS_DEFINED
+/// Base class of the exceptions thrown when the C API returns an error code
+class Exception : public std::runtime_error {
+public:
+    Exception(const char *name, int32_t code)
+        : std::runtime_error(std::string(name) + " (code " + std::to_string(code) + ")"), code_(code) {}
+
+    int32_t code() const noexcept {
+        return code_;
+    }
+
+private:
+    int32_t code_;
+};
+
+/// Copy a string returned by the C API, and free it
+inline std::string take_string(char *s) {
+    std::string copy(s);
+    ::string_free(s);
+    return copy;
+}
+
+/// Copy a vector returned by the C API, and free it
+inline std::vector<uint8_t> take_bytes(::Arr_u8 arr) {
+    std::vector<uint8_t> copy(arr.ptr, arr.ptr + arr.len);
+    ::bytes_free(arr);
+    return copy;
+}
+#endif
+
+} // namespace derive
+
+namespace derive::outer::inner {
+
+class Thing;
+
+} // namespace derive::outer::inner
+
+namespace derive::outer::inner {
+
+class Thing {
+public:
+    /// Take the ownership of a pointer returned by the C API
+    explicit Thing(::Thing *ptr) noexcept : ptr_(ptr) {}
+    ~Thing() {
+        if (ptr_) {
+            ::outer_inner_thing_release(ptr_);
+        }
+    }
+
+    Thing(Thing &&other) noexcept : ptr_(std::exchange(other.ptr_, nullptr)) {}
+    Thing &operator=(Thing &&other) noexcept {
+        std::swap(ptr_, other.ptr_);
+        return *this;
+    }
+    Thing(const Thing &other) noexcept : ptr_(other.ptr_ ? ::outer_inner_thing_retain(other.ptr_) : nullptr) {}
+    Thing &operator=(const Thing &other) noexcept {
+        Thing copy(other);
+        std::swap(ptr_, copy.ptr_);
+        return *this;
+    }
+
+    /// The underlying pointer, still owned by this object
+    ::Thing *as_raw() const noexcept {
+        return ptr_;
+    }
+    /// Give up the ownership of the underlying pointer
+    ::Thing *into_raw() noexcept {
+        return std::exchange(ptr_, nullptr);
+    }
+
+    static Thing new_();
+
+private:
+    ::Thing *ptr_;
+};
+
+} // namespace derive::outer::inner
+
+namespace derive::outer::inner {
+
+inline Thing Thing::new_() {
+    ::Thing *out = nullptr;
+    ::outer_inner_thing_new(&out);
+    return Thing(out);
+}
+
+inline uint32_t count() {
+    return ::outer_inner_count();
+}
+
+} // namespace derive::outer::inner
+
+namespace derive::outer {
+
+inline uint32_t version() {
+    return ::version();
+}
+
+} // namespace derive::outer
+
+namespace derive::outer::prefixed {
+
+inline uint32_t count() {
+    return ::ext_count();
+}
+
+} // namespace derive::outer::prefixed
+
+namespace derive::outer::prefixed::nested {
+
+inline uint32_t count() {
+    return ::ext_outer_prefixed_nested_count();
+}
+
+} // namespace derive::outer::prefixed::nested
+
+#endif // DERIVE_OUTER_HPP
@@ -0,0 +1,178 @@
+// Generated by `#[expose_mod]`, do not edit
+#ifndef DERIVE_OPERATORS_HPP
+#define DERIVE_OPERATORS_HPP
+
+#include <cstdint>
+#include <optional>
+#include <stdexcept>
+#include <string>
+#include <utility>
+#include <vector>
+
+#include "derive.h"
+
+namespace derive {
+
+#ifndef DERIVE_HELPERS_DEFINED
+#define DERIVE_HELPERS_DEFINED
+/// Base class of the exceptions thrown when the C API returns an error code
+class Exception : public std::runtime_error {
+public:
+    Exception(const char *name, int32_t code)
+        : std::runtime_error(std::string(name) + " (code " + std::to_string(code) + ")"), code_(code) {}
+
+    int32_t code() const noexcept {
+        return code_;
+    }
+
+private:
+    int32_t code_;
+};
+
+/// Copy a string returned by the C API, and free it
+inline std::string take_string(char *s) {
+    std::string copy(s);
+    ::string_free(s);
+    return copy;
+}
+
+/// Copy a vector returned by the C API, and free it
+inline std::vector<uint8_t> take_bytes(::Arr_u8 arr) {
+    std::vector<uint8_t> copy(arr.ptr, arr.ptr + arr.len);
+    ::bytes_free(arr);
+    return copy;
+}
+#endif
+
+#ifndef DERIVE_ERROR_DEFINED
+#define DERIVE_ERROR_DEFINED
+class Error : public Exception {
+public:
+    explicit Error(int32_t code) : Exception("Error", code) {}
+};
+#endif
+
+#ifndef DERIVE_INDEXERROR_DEFINED
+#define DERIVE_INDEXERROR_DEFINED
+class IndexError : public Exception {
+public:
+    explicit IndexError(int32_t code) : Exception("IndexError", code) {}
+};
+#endif
+
+} // namespace derive
+
+namespace derive::operators {
+
+class Amount;
+class Script;
+
+} // namespace derive::operators
+
+namespace derive::operators {
+
+class Amount {
+public:
+    /// Take the ownership of a pointer returned by the C API
+    explicit Amount(::Amount *ptr) noexcept : ptr_(ptr) {}
+    ~Amount() {
+        if (ptr_) {
+            ::amount_destroy(ptr_);
+        }
+    }
+
+    Amount(Amount &&other) noexcept : ptr_(std::exchange(other.ptr_, nullptr)) {}
+    Amount &operator=(Amount &&other) noexcept {
+        std::swap(ptr_, other.ptr_);
+        return *this;
+    }
+    Amount(const Amount &) = delete;
+    Amount &operator=(const Amount &) = delete;
+
+    /// The underlying pointer, still owned by this object
+    ::Amount *as_raw() const noexcept {
+        return ptr_;
+    }
+    /// Give up the ownership of the underlying pointer
+    ::Amount *into_raw() noexcept {
+        return std::exchange(ptr_, nullptr);
+    }
+
+    Amount operator+(const Amount &rhs) const;
+    Amount operator*(uint64_t rhs) const;
+    Amount operator-() const;
+
+private:
+    ::Amount *ptr_;
+};
+
+class Script {
+public:
+    /// Take the ownership of a pointer returned by the C API
+    explicit Script(::Script *ptr) noexcept : ptr_(ptr) {}
+    ~Script() {
+        if (ptr_) {
+            ::script_destroy(ptr_);
+        }
+    }
+
+    Script(Script &&other) noexcept : ptr_(std::exchange(other.ptr_, nullptr)) {}
+    Script &operator=(Script &&other) noexcept {
+        std::swap(ptr_, other.ptr_);
+        return *this;
+    }
+    Script(const Script &) = delete;
+    Script &operator=(const Script &) = delete;
+
+    /// The underlying pointer, still owned by this object
+    ::Script *as_raw() const noexcept {
+        return ptr_;
+    }
+    /// Give up the ownership of the underlying pointer
+    ::Script *into_raw() noexcept {
+        return std::exchange(ptr_, nullptr);
+    }
+
+    uint8_t operator[](size_t rhs) const;
+
+private:
+    ::Script *ptr_;
+};
+
+} // namespace derive::operators
+
+namespace derive::operators {
+
+inline Amount Amount::operator+(const Amount &rhs) const {
+    ::Amount *out = nullptr;
+    const auto code = ::amount_add(ptr_, rhs.as_raw(), &out);
+    if (code != 0) {
+        throw Error(code);
+    }
+    return Amount(out);
+}
+
+inline Amount Amount::operator*(uint64_t rhs) const {
+    ::Amount *out = nullptr;
+    ::amount_mul(ptr_, rhs, &out);
+    return Amount(out);
+}
+
+inline Amount Amount::operator-() const {
+    ::Amount *out = nullptr;
+    ::amount_neg(ptr_, &out);
+    return Amount(out);
+}
+
+inline uint8_t Script::operator[](size_t rhs) const {
+    uint8_t out{};
+    const auto code = ::script_index(ptr_, rhs, &out);
+    if (code != 0) {
+        throw IndexError(code);
+    }
+    return out;
+}
+
+} // namespace derive::operators
+
+#endif // DERIVE_OPERATORS_HPP
@@ -0,0 +1,145 @@
+// Generated by `#[expose_mod]`, do not edit
+#ifndef DERIVE_RETURNS_HPP
+#define DERIVE_RETURNS_HPP
+
+#include <cstdint>
+#include <optional>
+#include <stdexcept>
+#include <string>
+#include <utility>
+#include <vector>
+
+#include "derive.h"
+
+namespace derive {
+
+#ifndef DERIVE_HELPERS_DEFINED
+#define DERIVE_HELPERS_DEFINED
+/// Base class of the exceptions thrown when the C API returns an error code
+class Exception : public std::runtime_error {
+public:
+    Exception(const char *name, int32_t code)
+        : std::runtime_error(std::string(name) + " (code " + std::to_string(code) + ")"), code_(code) {}
+
+    int32_t code() const noexcept {
+        return code_;
+    }
+
+private:
+    int32_t code_;
+};
+
+/// Copy a string returned by the C API, and free it
+inline std::string take_string(char *s) {
+    std::string copy(s);
+    ::string_free(s);
+    return copy;
+}
+
+/// Copy a vector returned by the C API, and free it
+inline std::vector<uint8_t> take_bytes(::Arr_u8 arr) {
+    std::vector<uint8_t> copy(arr.ptr, arr.ptr + arr.len);
+    ::bytes_free(arr);
+    return copy;
+}
+#endif
+
+#ifndef DERIVE_ERROR_DEFINED
+#define DERIVE_ERROR_DEFINED
+class Error : public Exception {
+public:
+    explicit Error(int32_t code) : Exception("Error", code) {}
+};
+#endif
+
+} // namespace derive
+
+namespace derive::returns {
+
+class Item;
+
+} // namespace derive::returns
+
+namespace derive::returns {
+
+class Item {
+public:
+    /// Take the ownership of a pointer returned by the C API
+    explicit Item(::Item *ptr) noexcept : ptr_(ptr) {}
+    ~Item() {
+        if (ptr_) {
+            ::item_destroy(ptr_);
+        }
+    }
+
+    Item(Item &&other) noexcept : ptr_(std::exchange(other.ptr_, nullptr)) {}
+    Item &operator=(Item &&other) noexcept {
+        std::swap(ptr_, other.ptr_);
+        return *this;
+    }
+    Item(const Item &) = delete;
+    Item &operator=(const Item &) = delete;
+
+    /// The underlying pointer, still owned by this object
+    ::Item *as_raw() const noexcept {
+        return ptr_;
+    }
+    /// Give up the ownership of the underlying pointer
+    ::Item *into_raw() noexcept {
+        return std::exchange(ptr_, nullptr);
+    }
+
+    static Item parse(const std::string &s);
+    void check() const;
+    static std::optional<Item> find(const std::string &name);
+    std::vector<uint8_t> to_bytes() const;
+    std::string name() const;
+
+private:
+    ::Item *ptr_;
+};
+
+} // namespace derive::returns
+
+namespace derive::returns {
+
+inline Item Item::parse(const std::string &s) {
+    ::Item *out = nullptr;
+    const auto code = ::item_parse(s.c_str(), &out);
+    if (code != 0) {
+        throw Error(code);
+    }
+    return Item(out);
+}
+
+inline void Item::check() const {
+    const auto code = ::item_check(ptr_);
+    if (code != 0) {
+        throw Error(code);
+    }
+}
+
+inline std::optional<Item> Item::find(const std::string &name) {
+    ::Item *out = nullptr;
+    ::item_find(name.c_str(), &out);
+    if (!out) {
+        return std::nullopt;
+    }
+    return Item(out);
+}
+
+inline std::vector<uint8_t> Item::to_bytes() const {
+    return take_bytes(::item_to_bytes(ptr_));
+}
+
+inline std::string Item::name() const {
+    return take_string(::item_name(ptr_));
+}
+
+inline int32_t last_error() {
+    return ::last_error();
+}
+
+} // namespace derive::returns
+
+#endif // DERIVE_RETURNS_HPP
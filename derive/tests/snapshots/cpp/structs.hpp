@@ -0,0 +1,266 @@
+// Generated by `#[expose_mod]`, do not edit
+#ifndef DERIVE_STRUCTS_HPP
+#define DERIVE_STRUCTS_HPP
+
+#include <cstdint>
+#include <optional>
+#include <stdexcept>
+#include <string>
+#include <utility>
+#include <vector>
+
+#include "derive.h"
+
+namespace derive {
+
+#ifndef DERIVE_HELPERS_DEFINED
+#define DERIVE_HELPERS_DEFINED
+/// Base class of the exceptions thrown when the C API returns an error code
+class Exception : public std::runtime_error {
+public:
+    Exception(const char *name, int32_t code)
+        : std::runtime_error(std::string(name) + " (code " + std::to_string(code) + ")"), code_(code) {}
+
+    int32_t code() const noexcept {
+        return code_;
+    }
+
+private:
+    int32_t code_;
+};
+
+/// Copy a string returned by the C API, and free it
+inline std::string take_string(char *s) {
+    std::string copy(s);
+    ::string_free(s);
+    return copy;
+}
+
+/// Copy a vector returned by the C API, and free it
+inline std::vector<uint8_t> take_bytes(::Arr_u8 arr) {
+    std::vector<uint8_t> copy(arr.ptr, arr.ptr + arr.len);
+    ::bytes_free(arr);
+    return copy;
+}
+#endif
+
+#ifndef DERIVE_VALIDATIONERROR_DEFINED
+#define DERIVE_VALIDATIONERROR_DEFINED
+class ValidationError : public Exception {
+public:
+    explicit ValidationError(int32_t code) : Exception("ValidationError", code) {}
+};
+#endif
+
+} // namespace derive
+
+namespace derive::structs {
+
+class Opaque;
+class Shared;
+class WithFields;
+using Point = ::Point;
+class Script;
+class WithOpaque;
+
+} // namespace derive::structs
+
+namespace derive::structs {
+
+class Opaque {
+public:
+    /// Take the ownership of a pointer returned by the C API
+    explicit Opaque(::Opaque *ptr) noexcept : ptr_(ptr) {}
+    ~Opaque() {
+        if (ptr_) {
+            ::opaque_destroy(ptr_);
+        }
+    }
+
+    Opaque(Opaque &&other) noexcept : ptr_(std::exchange(other.ptr_, nullptr)) {}
+    Opaque &operator=(Opaque &&other) noexcept {
+        std::swap(ptr_, other.ptr_);
+        return *this;
+    }
+    Opaque(const Opaque &) = delete;
+    Opaque &operator=(const Opaque &) = delete;
+
+    /// The underlying pointer, still owned by this object
+    ::Opaque *as_raw() const noexcept {
+        return ptr_;
+    }
+    /// Give up the ownership of the underlying pointer
+    ::Opaque *into_raw() noexcept {
+        return std::exchange(ptr_, nullptr);
+    }
+
+private:
+    ::Opaque *ptr_;
+};
+
+class Shared {
+public:
+    /// Take the ownership of a pointer returned by the C API
+    explicit Shared(::Shared *ptr) noexcept : ptr_(ptr) {}
+    ~Shared() {
+        if (ptr_) {
+            ::shared_release(ptr_);
+        }
+    }
+
+    Shared(Shared &&other) noexcept : ptr_(std::exchange(other.ptr_, nullptr)) {}
+    Shared &operator=(Shared &&other) noexcept {
+        std::swap(ptr_, other.ptr_);
+        return *this;
+    }
+    Shared(const Shared &other) noexcept : ptr_(other.ptr_ ? ::shared_retain(other.ptr_) : nullptr) {}
+    Shared &operator=(const Shared &other) noexcept {
+        Shared copy(other);
+        std::swap(ptr_, copy.ptr_);
+        return *this;
+    }
+
+    /// The underlying pointer, still owned by this object
+    ::Shared *as_raw() const noexcept {
+        return ptr_;
+    }
+    /// Give up the ownership of the underlying pointer
+    ::Shared *into_raw() noexcept {
+        return std::exchange(ptr_, nullptr);
+    }
+
+private:
+    ::Shared *ptr_;
+};
+
+class WithFields {
+public:
+    /// Take the ownership of a pointer returned by the C API
+    explicit WithFields(::WithFields *ptr) noexcept : ptr_(ptr) {}
+    ~WithFields() {
+        if (ptr_) {
+            ::with_fields_destroy(ptr_);
+        }
+    }
+
+    WithFields(WithFields &&other) noexcept : ptr_(std::exchange(other.ptr_, nullptr)) {}
+    WithFields &operator=(WithFields &&other) noexcept {
+        std::swap(ptr_, other.ptr_);
+        return *this;
+    }
+    WithFields(const WithFields &) = delete;
+    WithFields &operator=(const WithFields &) = delete;
+
+    /// The underlying pointer, still owned by this object
+    ::WithFields *as_raw() const noexcept {
+        return ptr_;
+    }
+    /// Give up the ownership of the underlying pointer
+    ::WithFields *into_raw() noexcept {
+        return std::exchange(ptr_, nullptr);
+    }
+
+    Shared get_shared() const;
+    void set_shared(const Shared &value);
+    uint32_t get_value() const;
+    void set_value(uint32_t value);
+
+private:
+    ::WithFields *ptr_;
+};
+
+class Script {
+public:
+    /// Take the ownership of a pointer returned by the C API
+    explicit Script(::Script *ptr) noexcept : ptr_(ptr) {}
+    ~Script() {
+        if (ptr_) {
+            ::script_destroy(ptr_);
+        }
+    }
+
+    Script(Script &&other) noexcept : ptr_(std::exchange(other.ptr_, nullptr)) {}
+    Script &operator=(Script &&other) noexcept {
+        std::swap(ptr_, other.ptr_);
+        return *this;
+    }
+    Script(const Script &) = delete;
+    Script &operator=(const Script &) = delete;
+
+    /// The underlying pointer, still owned by this object
+    ::Script *as_raw() const noexcept {
+        return ptr_;
+    }
+    /// Give up the ownership of the underlying pointer
+    ::Script *into_raw() noexcept {
+        return std::exchange(ptr_, nullptr);
+    }
+
+private:
+    ::Script *ptr_;
+};
+
+class WithOpaque {
+public:
+    /// Take the ownership of a pointer returned by the C API
+    explicit WithOpaque(::WithOpaque *ptr) noexcept : ptr_(ptr) {}
+    ~WithOpaque() {
+        if (ptr_) {
+            ::with_opaque_destroy(ptr_);
+        }
+    }
+
+    WithOpaque(WithOpaque &&other) noexcept : ptr_(std::exchange(other.ptr_, nullptr)) {}
+    WithOpaque &operator=(WithOpaque &&other) noexcept {
+        std::swap(ptr_, other.ptr_);
+        return *this;
+    }
+    WithOpaque(const WithOpaque &) = delete;
+    WithOpaque &operator=(const WithOpaque &) = delete;
+
+    /// The underlying pointer, still owned by this object
+    ::WithOpaque *as_raw() const noexcept {
+        return ptr_;
+    }
+    /// Give up the ownership of the underlying pointer
+    ::WithOpaque *into_raw() noexcept {
+        return std::exchange(ptr_, nullptr);
+    }
+
+    // `get_script` is not available: unsupported argument or return types
+    void set_script(const Script &value);
+
+private:
+    ::WithOpaque *ptr_;
+};
+
+} // namespace derive::structs
+
+namespace derive::structs {
+
+inline Shared WithFields::get_shared() const {
+    return Shared(::with_fields_get_shared(ptr_));
+}
+
+inline void WithFields::set_shared(const Shared &value) {
+    ::with_fields_set_shared(ptr_, value.as_raw());
+}
+
+inline uint32_t WithFields::get_value() const {
+    return ::with_fields_get_value(ptr_);
+}
+
+inline void WithFields::set_value(uint32_t value) {
+    const auto code = ::with_fields_set_value(ptr_, value);
+    if (code != 0) {
+        throw ValidationError(code);
+    }
+}
+
+inline void WithOpaque::set_script(const Script &value) {
+    ::with_opaque_set_script(ptr_, value.as_raw());
+}
+
+} // namespace derive::structs
+
+#endif // DERIVE_STRUCTS_HPP
@@ -0,0 +1,161 @@
+// <auto-generated>
+// Generated by `#[expose_mod]`, do not edit
+// </auto-generated>
+#nullable enable
+using System;
+using System.Runtime.InteropServices;
+
+namespace Derive;
+
+/// <summary>Thrown when the C API returns an error code</summary>
+public class DeriveException : Exception
+{
+    public int Code { get; }
+
+    public DeriveException(string message, int code) : base(message)
+    {
+        Code = code;
+    }
+}
+
+public sealed class ErrorException : DeriveException
+{
+    public ErrorException(int code) : base($"Error: error code {code}", code)
+    {
+    }
+}
+
+/// <summary>Array of primitives passed to or returned by the C API, with the layout of <c>Arr</c></summary>
+[StructLayout(LayoutKind.Sequential)]
+internal struct Arr
+{
+    public IntPtr Ptr;
+    public nuint Len;
+}
+
+/// <summary>Pins an array for the duration of a call</summary>
+internal sealed class Pinned : IDisposable
+{
+    private GCHandle handle;
+
+    internal Pinned(Array array)
+    {
+        handle = GCHandle.Alloc(array, GCHandleType.Pinned);
+        Arr = new Arr { Ptr = handle.AddrOfPinnedObject(), Len = (nuint)array.Length };
+    }
+
+    internal Arr Arr { get; }
+
+    public void Dispose() => handle.Free();
+}
+
+internal static class Native
+{
+    internal const string Lib = "derive";
+
+    [DllImport(Lib)]
+    internal static extern void string_free(IntPtr s);
+
+    /// <summary>Copy a string returned by the C API, and free it</summary>
+    internal static string TakeString(IntPtr s)
+    {
+        try
+        {
+            return Marshal.PtrToStringUTF8(s)!;
+        }
+        finally
+        {
+            string_free(s);
+        }
+    }
+
+    [DllImport(Lib)]
+    internal static extern void bytes_free(Arr arr);
+
+    /// <summary>Copy a vector returned by the C API, and free it</summary>
+    internal static byte[] TakeBytes(Arr arr)
+    {
+        try
+        {
+            var bytes = new byte[(int)arr.Len];
+            if (bytes.Length > 0)
+            {
+                Marshal.Copy(arr.Ptr, bytes, 0, bytes.Length);
+            }
+            return bytes;
+        }
+        finally
+        {
+            bytes_free(arr);
+        }
+    }
+
+    [DllImport(Lib)]
+    internal static extern void wallet_destroy(IntPtr ptr);
+
+    [DllImport(Lib)]
+    internal static extern void wallet_new([MarshalAs(UnmanagedType.LPUTF8Str)] string descriptor, out IntPtr ptrOut);
+
+    [DllImport(Lib)]
+    internal static extern int wallet_sync(Wallet self, [MarshalAs(UnmanagedType.LPUTF8Str)] string url, nuint stopGap, out ulong ptrOut);
+
+    [DllImport(Lib)]
+    internal static extern Arr wallet_sign(Wallet self, Arr psbt);
+
+    [DllImport(Lib)]
+    internal static extern int broadcast([MarshalAs(UnmanagedType.LPUTF8Str)] string url, Arr tx, out IntPtr ptrOut);
+}
+
+/// <summary>Owns a pointer to <c>Wallet</c>, freed when the handle is disposed or finalized</summary>
+public sealed class Wallet : SafeHandle
+{
+    /// <summary>Take the ownership of a pointer returned by the C API</summary>
+    internal Wallet(IntPtr ptr) : base(IntPtr.Zero, true)
+    {
+        SetHandle(ptr);
+    }
+
+    public override bool IsInvalid => handle == IntPtr.Zero;
+
+    protected override bool ReleaseHandle()
+    {
+        Native.wallet_destroy(handle);
+        return true;
+    }
+
+    public Wallet(string descriptor) : base(IntPtr.Zero, true)
+    {
+        Native.wallet_new(descriptor, out var ptrOut);
+        SetHandle(ptrOut);
+    }
+
+    public ulong Sync(string url, nuint stopGap)
+    {
+        var code = Native.wallet_sync(this, url, stopGap, out var ptrOut);
+        if (code != 0)
+        {
+            throw new ErrorException(code);
+        }
+        return ptrOut;
+    }
+
+    public byte[] Sign(byte[] psbt)
+    {
+        using var psbtPinned = new Pinned(psbt);
+        return Native.TakeBytes(Native.wallet_sign(this, psbtPinned.Arr));
+    }
+}
+
+public static partial class Blocking
+{
+    public static string Broadcast(string url, byte[] tx)
+    {
+        using var txPinned = new Pinned(tx);
+        var code = Native.broadcast(url, txPinned.Arr, out var ptrOut);
+        if (code != 0)
+        {
+            throw new ErrorException(code);
+        }
+        return Native.TakeString(ptrOut);
+    }
+}
@@ -0,0 +1,124 @@
+// <auto-generated>
+// Generated by `#[expose_mod]`, do not edit
+// </auto-generated>
+#nullable enable
+using System;
+using System.Runtime.InteropServices;
+
+namespace Derive;
+
+/// <summary>Thrown when the C API returns an error code</summary>
+public class DeriveException : Exception
+{
+    public int Code { get; }
+
+    public DeriveException(string message, int code) : base(message)
+    {
+        Code = code;
+    }
+}
+
+/// <summary>Array of primitives passed to or returned by the C API, with the layout of <c>Arr</c></summary>
+[StructLayout(LayoutKind.Sequential)]
+internal struct Arr
+{
+    public IntPtr Ptr;
+    public nuint Len;
+}
+
+/// <summary>Pins an array for the duration of a call</summary>
+internal sealed class Pinned : IDisposable
+{
+    private GCHandle handle;
+
+    internal Pinned(Array array)
+    {
+        handle = GCHandle.Alloc(array, GCHandleType.Pinned);
+        Arr = new Arr { Ptr = handle.AddrOfPinnedObject(), Len = (nuint)array.Length };
+    }
+
+    internal Arr Arr { get; }
+
+    public void Dispose() => handle.Free();
+}
+
+internal static class Native
+{
+    internal const string Lib = "derive";
+
+    [DllImport(Lib)]
+    internal static extern void string_free(IntPtr s);
+
+    /// <summary>Copy a string returned by the C API, and free it</summary>
+    internal static string TakeString(IntPtr s)
+    {
+        try
+        {
+            return Marshal.PtrToStringUTF8(s)!;
+        }
+        finally
+        {
+            string_free(s);
+        }
+    }
+
+    [DllImport(Lib)]
+    internal static extern void bytes_free(Arr arr);
+
+    /// <summary>Copy a vector returned by the C API, and free it</summary>
+    internal static byte[] TakeBytes(Arr arr)
+    {
+        try
+        {
+            var bytes = new byte[(int)arr.Len];
+            if (bytes.Length > 0)
+            {
+                Marshal.Copy(arr.Ptr, bytes, 0, bytes.Length);
+            }
+            return bytes;
+        }
+        finally
+        {
+            bytes_free(arr);
+        }
+    }
+
+    [DllImport(Lib)]
+    internal static extern void script_destroy(IntPtr ptr);
+
+    [DllImport(Lib)]
+    internal static extern Arr script_to_bytes(Script self);
+
+    [DllImport(Lib)]
+    internal static extern void script_from_bytes(Arr bytes, out IntPtr ptrOut);
+}
+
+/// <summary>Owns a pointer to <c>Script</c>, freed when the handle is disposed or finalized</summary>
+public sealed class Script : SafeHandle
+{
+    /// <summary>Take the ownership of a pointer returned by the C API</summary>
+    internal Script(IntPtr ptr) : base(IntPtr.Zero, true)
+    {
+        SetHandle(ptr);
+    }
+
+    public override bool IsInvalid => handle == IntPtr.Zero;
+
+    protected override bool ReleaseHandle()
+    {
+        Native.script_destroy(handle);
+        return true;
+    }
+
+    public byte[] ToBytes()
+    {
+        return Native.TakeBytes(Native.script_to_bytes(this));
+    }
+
+    public static Script FromBytes(byte[] bytes)
+    {
+        using var bytesPinned = new Pinned(bytes);
+        Native.script_from_bytes(bytesPinned.Arr, out var ptrOut);
+        return new Script(ptrOut);
+    }
+}
@@ -0,0 +1,144 @@
+// <auto-generated>
+// Generated by `#[expose_mod]`, do not edit
+// </auto-generated>
+#nullable enable
+using System;
+using System.Runtime.InteropServices;
+
+namespace Derive;
+
+/// <summary>Thrown when the C API returns an error code</summary>
+public class DeriveException : Exception
+{
+    public int Code { get; }
+
+    public DeriveException(string message, int code) : base(message)
+    {
+        Code = code;
+    }
+}
+
+public sealed class ErrorException : DeriveException
+{
+    public ErrorException(int code) : base($"Error: error code {code}", code)
+    {
+    }
+}
+
+/// <summary>Array of primitives passed to or returned by the C API, with the layout of <c>Arr</c></summary>
+[StructLayout(LayoutKind.Sequential)]
+internal struct Arr
+{
+    public IntPtr Ptr;
+    public nuint Len;
+}
+
+/// <summary>Pins an array for the duration of a call</summary>
+internal sealed class Pinned : IDisposable
+{
+    private GCHandle handle;
+
+    internal Pinned(Array array)
+    {
+        handle = GCHandle.Alloc(array, GCHandleType.Pinned);
+        Arr = new Arr { Ptr = handle.AddrOfPinnedObject(), Len = (nuint)array.Length };
+    }
+
+    internal Arr Arr { get; }
+
+    public void Dispose() => handle.Free();
+}
+
+internal static class Native
+{
+    internal const string Lib = "derive";
+
+    [DllImport(Lib)]
+    internal static extern void string_free(IntPtr s);
+
+    /// <summary>Copy a string returned by the C API, and free it</summary>
+    internal static string TakeString(IntPtr s)
+    {
+        try
+        {
+            return Marshal.PtrToStringUTF8(s)!;
+        }
+        finally
+        {
+            string_free(s);
+        }
+    }
+
+    [DllImport(Lib)]
+    internal static extern void bytes_free(Arr arr);
+
+    /// <summary>Copy a vector returned by the C API, and free it</summary>
+    internal static byte[] TakeBytes(Arr arr)
+    {
+        try
+        {
+            var bytes = new byte[(int)arr.Len];
+            if (bytes.Length > 0)
+            {
+                Marshal.Copy(arr.Ptr, bytes, 0, bytes.Length);
+            }
+            return bytes;
+        }
+        finally
+        {
+            bytes_free(arr);
+        }
+    }
+
+    [DllImport(Lib)]
+    internal static extern void network_destroy(IntPtr ptr);
+
+    [DllImport(Lib)]
+    internal static extern int network_new([MarshalAs(UnmanagedType.LPUTF8Str)] string s, out IntPtr ptrOut);
+
+    [DllImport(Lib)]
+    internal static extern void network_new_bitcoin(out IntPtr ptrOut);
+
+    [DllImport(Lib)]
+    internal static extern void network_new_from_magic(uint magic, out IntPtr ptrOut);
+}
+
+/// <summary>Owns a pointer to <c>Network</c>, freed when the handle is disposed or finalized</summary>
+public sealed class Network : SafeHandle
+{
+    /// <summary>Take the ownership of a pointer returned by the C API</summary>
+    internal Network(IntPtr ptr) : base(IntPtr.Zero, true)
+    {
+        SetHandle(ptr);
+    }
+
+    public override bool IsInvalid => handle == IntPtr.Zero;
+
+    protected override bool ReleaseHandle()
+    {
+        Native.network_destroy(handle);
+        return true;
+    }
+
+    public Network(string s) : base(IntPtr.Zero, true)
+    {
+        var code = Native.network_new(s, out var ptrOut);
+        if (code != 0)
+        {
+            throw new ErrorException(code);
+        }
+        SetHandle(ptrOut);
+    }
+
+    public static Network Bitcoin()
+    {
+        Native.network_new_bitcoin(out var ptrOut);
+        return new Network(ptrOut);
+    }
+
+    public static Network? FromMagic(uint magic)
+    {
+        Native.network_new_from_magic(magic, out var ptrOut);
+        return ptrOut == IntPtr.Zero ? null : new Network(ptrOut);
+    }
+}
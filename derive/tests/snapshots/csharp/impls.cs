@@ -0,0 +1,148 @@
+// <auto-generated>
+// Generated by `#[expose_mod]`, do not edit
+// </auto-generated>
+#nullable enable
+using System;
+using System.Runtime.InteropServices;
+
+namespace Derive;
+
+/// <summary>Thrown when the C API returns an error code</summary>
+public class DeriveException : Exception
+{
+    public int Code { get; }
+
+    public DeriveException(string message, int code) : base(message)
+    {
+        Code = code;
+    }
+}
+
+/// <summary>Array of primitives passed to or returned by the C API, with the layout of <c>Arr</c></summary>
+[StructLayout(LayoutKind.Sequential)]
+internal struct Arr
+{
+    public IntPtr Ptr;
+    public nuint Len;
+}
+
+/// <summary>Pins an array for the duration of a call</summary>
+internal sealed class Pinned : IDisposable
+{
+    private GCHandle handle;
+
+    internal Pinned(Array array)
+    {
+        handle = GCHandle.Alloc(array, GCHandleType.Pinned);
+        Arr = new Arr { Ptr = handle.AddrOfPinnedObject(), Len = (nuint)array.Length };
+    }
+
+    internal Arr Arr { get; }
+
+    public void Dispose() => handle.Free();
+}
+
+internal static class Native
+{
+    internal const string Lib = "derive";
+
+    [DllImport(Lib)]
+    internal static extern void string_free(IntPtr s);
+
+    /// <summary>Copy a string returned by the C API, and free it</summary>
+    internal static string TakeString(IntPtr s)
+    {
+        try
+        {
+            return Marshal.PtrToStringUTF8(s)!;
+        }
+        finally
+        {
+            string_free(s);
+        }
+    }
+
+    [DllImport(Lib)]
+    internal static extern void bytes_free(Arr arr);
+
+    /// <summary>Copy a vector returned by the C API, and free it</summary>
+    internal static byte[] TakeBytes(Arr arr)
+    {
+        try
+        {
+            var bytes = new byte[(int)arr.Len];
+            if (bytes.Length > 0)
+            {
+                Marshal.Copy(arr.Ptr, bytes, 0, bytes.Length);
+            }
+            return bytes;
+        }
+        finally
+        {
+            bytes_free(arr);
+        }
+    }
+
+    [DllImport(Lib)]
+    internal static extern void counter_destroy(IntPtr ptr);
+
+    [DllImport(Lib)]
+    internal static extern void counter_new(uint start, out IntPtr ptrOut);
+
+    [DllImport(Lib)]
+    internal static extern void counter_zero(out IntPtr ptrOut);
+
+    [DllImport(Lib)]
+    internal static extern uint counter_get(Counter self);
+
+    [DllImport(Lib)]
+    internal static extern void counter_increment(Counter self, uint by);
+
+    [DllImport(Lib)]
+    internal static extern IntPtr counter_label(Counter self, [MarshalAs(UnmanagedType.LPUTF8Str)] string prefix);
+}
+
+/// <summary>Owns a pointer to <c>Counter</c>, freed when the handle is disposed or finalized</summary>
+public sealed class Counter : SafeHandle
+{
+    /// <summary>Take the ownership of a pointer returned by the C API</summary>
+    internal Counter(IntPtr ptr) : base(IntPtr.Zero, true)
+    {
+        SetHandle(ptr);
+    }
+
+    public override bool IsInvalid => handle == IntPtr.Zero;
+
+    protected override bool ReleaseHandle()
+    {
+        Native.counter_destroy(handle);
+        return true;
+    }
+
+    public Counter(uint start) : base(IntPtr.Zero, true)
+    {
+        Native.counter_new(start, out var ptrOut);
+        SetHandle(ptrOut);
+    }
+
+    public static Counter Zero()
+    {
+        Native.counter_zero(out var ptrOut);
+        return new Counter(ptrOut);
+    }
+
+    public uint Get()
+    {
+        return Native.counter_get(this);
+    }
+
+    public void Increment(uint by)
+    {
+        Native.counter_increment(this, by);
+    }
+
+    public string Label(string prefix)
+    {
+        return Native.TakeString(Native.counter_label(this, prefix));
+    }
+}
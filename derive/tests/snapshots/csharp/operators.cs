@@ -0,0 +1,188 @@
+// <auto-generated>
+// Generated by `#[expose_mod]`, do not edit
+// </auto-generated>
+#nullable enable
+using System;
+using System.Runtime.InteropServices;
+
+namespace Derive;
+
+/// <summary>Thrown when the C API returns an error code</summary>
+public class DeriveException : Exception
+{
+    public int Code { get; }
+
+    public DeriveException(string message, int code) : base(message)
+    {
+        Code = code;
+    }
+}
+
+public sealed class ErrorException : DeriveException
+{
+    public ErrorException(int code) : base($"Error: error code {code}", code)
+    {
+    }
+}
+
+public sealed class IndexErrorException : DeriveException
+{
+    public IndexErrorException(int code) : base($"IndexError: error code {code}", code)
+    {
+    }
+}
+
+/// <summary>Array of primitives passed to or returned by the C API, with the layout of <c>Arr</c></summary>
+[StructLayout(LayoutKind.Sequential)]
+internal struct Arr
+{
+    public IntPtr Ptr;
+    public nuint Len;
+}
+
+/// <summary>Pins an array for the duration of a call</summary>
+internal sealed class Pinned : IDisposable
+{
+    private GCHandle handle;
+
+    internal Pinned(Array array)
+    {
+        handle = GCHandle.Alloc(array, GCHandleType.Pinned);
+        Arr = new Arr { Ptr = handle.AddrOfPinnedObject(), Len = (nuint)array.Length };
+    }
+
+    internal Arr Arr { get; }
+
+    public void Dispose() => handle.Free();
+}
+
+internal static class Native
+{
+    internal const string Lib = "derive";
+
+    [DllImport(Lib)]
+    internal static extern void string_free(IntPtr s);
+
+    /// <summary>Copy a string returned by the C API, and free it</summary>
+    internal static string TakeString(IntPtr s)
+    {
+        try
+        {
+            return Marshal.PtrToStringUTF8(s)!;
+        }
+        finally
+        {
+            string_free(s);
+        }
+    }
+
+    [DllImport(Lib)]
+    internal static extern void bytes_free(Arr arr);
+
+    /// <summary>Copy a vector returned by the C API, and free it</summary>
+    internal static byte[] TakeBytes(Arr arr)
+    {
+        try
+        {
+            var bytes = new byte[(int)arr.Len];
+            if (bytes.Length > 0)
+            {
+                Marshal.Copy(arr.Ptr, bytes, 0, bytes.Length);
+            }
+            return bytes;
+        }
+        finally
+        {
+            bytes_free(arr);
+        }
+    }
+
+    [DllImport(Lib)]
+    internal static extern void amount_destroy(IntPtr ptr);
+
+    [DllImport(Lib)]
+    internal static extern int amount_add(Amount self, Amount rhs, out IntPtr ptrOut);
+
+    [DllImport(Lib)]
+    internal static extern void amount_mul(Amount self, ulong rhs, out IntPtr ptrOut);
+
+    [DllImport(Lib)]
+    internal static extern void amount_neg(Amount self, out IntPtr ptrOut);
+
+    [DllImport(Lib)]
+    internal static extern void script_destroy(IntPtr ptr);
+
+    [DllImport(Lib)]
+    internal static extern int script_index(Script self, nuint rhs, out byte ptrOut);
+}
+
+/// <summary>Owns a pointer to <c>Amount</c>, freed when the handle is disposed or finalized</summary>
+public sealed class Amount : SafeHandle
+{
+    /// <summary>Take the ownership of a pointer returned by the C API</summary>
+    internal Amount(IntPtr ptr) : base(IntPtr.Zero, true)
+    {
+        SetHandle(ptr);
+    }
+
+    public override bool IsInvalid => handle == IntPtr.Zero;
+
+    protected override bool ReleaseHandle()
+    {
+        Native.amount_destroy(handle);
+        return true;
+    }
+
+    public static Amount operator +(Amount lhs, Amount rhs)
+    {
+        var code = Native.amount_add(lhs, rhs, out var ptrOut);
+        if (code != 0)
+        {
+            throw new ErrorException(code);
+        }
+        return new Amount(ptrOut);
+    }
+
+    public static Amount operator *(Amount lhs, ulong rhs)
+    {
+        Native.amount_mul(lhs, rhs, out var ptrOut);
+        return new Amount(ptrOut);
+    }
+
+    public static Amount operator -(Amount operand)
+    {
+        Native.amount_neg(operand, out var ptrOut);
+        return new Amount(ptrOut);
+    }
+}
+
+/// <summary>Owns a pointer to <c>Script</c>, freed when the handle is disposed or finalized</summary>
+public sealed class Script : SafeHandle
+{
+    /// <summary>Take the ownership of a pointer returned by the C API</summary>
+    internal Script(IntPtr ptr) : base(IntPtr.Zero, true)
+    {
+        SetHandle(ptr);
+    }
+
+    public override bool IsInvalid => handle == IntPtr.Zero;
+
+    protected override bool ReleaseHandle()
+    {
+        Native.script_destroy(handle);
+        return true;
+    }
+
+    public byte this[nuint rhs]
+    {
+        get
+        {
+            var code = Native.script_index(this, rhs, out var ptrOut);
+            if (code != 0)
+            {
+                throw new IndexErrorException(code);
+            }
+            return ptrOut;
+        }
+    }
+}
@@ -0,0 +1,174 @@
+// <auto-generated>
+// Generated by `#[expose_mod]`, do not edit
+// </auto-generated>
+#nullable enable
+using System;
+using System.Runtime.InteropServices;
+
+namespace Derive;
+
+/// <summary>Thrown when the C API returns an error code</summary>
+public class DeriveException : Exception
+{
+    public int Code { get; }
+
+    public DeriveException(string message, int code) : base(message)
+    {
+        Code = code;
+    }
+}
+
+public sealed class ErrorException : DeriveException
+{
+    public ErrorException(int code) : base($"Error: error code {code}", code)
+    {
+    }
+}
+
+/// <summary>Array of primitives passed to or returned by the C API, with the layout of <c>Arr</c></summary>
+[StructLayout(LayoutKind.Sequential)]
+internal struct Arr
+{
+    public IntPtr Ptr;
+    public nuint Len;
+}
+
+/// <summary>Pins an array for the duration of a call</summary>
+internal sealed class Pinned : IDisposable
+{
+    private GCHandle handle;
+
+    internal Pinned(Array array)
+    {
+        handle = GCHandle.Alloc(array, GCHandleType.Pinned);
+        Arr = new Arr { Ptr = handle.AddrOfPinnedObject(), Len = (nuint)array.Length };
+    }
+
+    internal Arr Arr { get; }
+
+    public void Dispose() => handle.Free();
+}
+
+internal static class Native
+{
+    internal const string Lib = "derive";
+
+    [DllImport(Lib)]
+    internal static extern void string_free(IntPtr s);
+
+    /// <summary>Copy a string returned by the C API, and free it</summary>
+    internal static string TakeString(IntPtr s)
+    {
+        try
+        {
+            return Marshal.PtrToStringUTF8(s)!;
+        }
+        finally
+        {
+            string_free(s);
+        }
+    }
+
+    [DllImport(Lib)]
+    internal static extern void bytes_free(Arr arr);
+
+    /// <summary>Copy a vector returned by the C API, and free it</summary>
+    internal static byte[] TakeBytes(Arr arr)
+    {
+        try
+        {
+            var bytes = new byte[(int)arr.Len];
+            if (bytes.Length > 0)
+            {
+                Marshal.Copy(arr.Ptr, bytes, 0, bytes.Length);
+            }
+            return bytes;
+        }
+        finally
+        {
+            bytes_free(arr);
+        }
+    }
+
+    [DllImport(Lib)]
+    internal static extern void item_destroy(IntPtr ptr);
+
+    [DllImport(Lib)]
+    internal static extern int item_parse([MarshalAs(UnmanagedType.LPUTF8Str)] string s, out IntPtr ptrOut);
+
+    [DllImport(Lib)]
+    internal static extern int item_check(Item self);
+
+    [DllImport(Lib)]
+    internal static extern void item_find([MarshalAs(UnmanagedType.LPUTF8Str)] string name, out IntPtr ptrOut);
+
+    [DllImport(Lib)]
+    internal static extern Arr item_to_bytes(Item self);
+
+    [DllImport(Lib)]
+    internal static extern IntPtr item_name(Item self);
+
+    [DllImport(Lib)]
+    internal static extern int last_error();
+}
+
+/// <summary>Owns a pointer to <c>Item</c>, freed when the handle is disposed or finalized</summary>
+public sealed class Item : SafeHandle
+{
+    /// <summary>Take the ownership of a pointer returned by the C API</summary>
+    internal Item(IntPtr ptr) : base(IntPtr.Zero, true)
+    {
+        SetHandle(ptr);
+    }
+
+    public override bool IsInvalid => handle == IntPtr.Zero;
+
+    protected override bool ReleaseHandle()
+    {
+        Native.item_destroy(handle);
+        return true;
+    }
+
+    public static Item Parse(string s)
+    {
+        var code = Native.item_parse(s, out var ptrOut);
+        if (code != 0)
+        {
+            throw new ErrorException(code);
+        }
+        return new Item(ptrOut);
+    }
+
+    public void Check()
+    {
+        var code = Native.item_check(this);
+        if (code != 0)
+        {
+            throw new ErrorException(code);
+        }
+    }
+
+    public static Item? Find(string name)
+    {
+        Native.item_find(name, out var ptrOut);
+        return ptrOut == IntPtr.Zero ? null : new Item(ptrOut);
+    }
+
+    public byte[] ToBytes()
+    {
+        return Native.TakeBytes(Native.item_to_bytes(this));
+    }
+
+    public string Name()
+    {
+        return Native.TakeString(Native.item_name(this));
+    }
+}
+
+public static partial class Returns
+{
+    public static int LastError()
+    {
+        return Native.last_error();
+    }
+}
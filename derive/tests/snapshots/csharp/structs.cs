@@ -0,0 +1,256 @@
+// <auto-generated>
+// Generated by `#[expose_mod]`, do not edit
+// </auto-generated>
+#nullable enable
+using System;
+using System.Runtime.InteropServices;
+
+namespace Derive;
+
+/// <summary>Thrown when the C API returns an error code</summary>
+public class DeriveException : Exception
+{
+    public int Code { get; }
+
+    public DeriveException(string message, int code) : base(message)
+    {
+        Code = code;
+    }
+}
+
+public sealed class ValidationErrorException : DeriveException
+{
+    public ValidationErrorException(int code) : base($"ValidationError: error code {code}", code)
+    {
+    }
+}
+
+/// <summary>Array of primitives passed to or returned by the C API, with the layout of <c>Arr</c></summary>
+[StructLayout(LayoutKind.Sequential)]
+internal struct Arr
+{
+    public IntPtr Ptr;
+    public nuint Len;
+}
+
+/// <summary>Pins an array for the duration of a call</summary>
+internal sealed class Pinned : IDisposable
+{
+    private GCHandle handle;
+
+    internal Pinned(Array array)
+    {
+        handle = GCHandle.Alloc(array, GCHandleType.Pinned);
+        Arr = new Arr { Ptr = handle.AddrOfPinnedObject(), Len = (nuint)array.Length };
+    }
+
+    internal Arr Arr { get; }
+
+    public void Dispose() => handle.Free();
+}
+
+internal static class Native
+{
+    internal const string Lib = "derive";
+
+    [DllImport(Lib)]
+    internal static extern void string_free(IntPtr s);
+
+    /// <summary>Copy a string returned by the C API, and free it</summary>
+    internal static string TakeString(IntPtr s)
+    {
+        try
+        {
+            return Marshal.PtrToStringUTF8(s)!;
+        }
+        finally
+        {
+            string_free(s);
+        }
+    }
+
+    [DllImport(Lib)]
+    internal static extern void bytes_free(Arr arr);
+
+    /// <summary>Copy a vector returned by the C API, and free it</summary>
+    internal static byte[] TakeBytes(Arr arr)
+    {
+        try
+        {
+            var bytes = new byte[(int)arr.Len];
+            if (bytes.Length > 0)
+            {
+                Marshal.Copy(arr.Ptr, bytes, 0, bytes.Length);
+            }
+            return bytes;
+        }
+        finally
+        {
+            bytes_free(arr);
+        }
+    }
+
+    [DllImport(Lib)]
+    internal static extern void opaque_destroy(IntPtr ptr);
+
+    [DllImport(Lib)]
+    internal static extern void shared_release(IntPtr ptr);
+
+    [DllImport(Lib)]
+    internal static extern void with_fields_destroy(IntPtr ptr);
+
+    [DllImport(Lib)]
+    internal static extern IntPtr with_fields_get_shared(WithFields self);
+
+    [DllImport(Lib)]
+    internal static extern void with_fields_set_shared(WithFields self, Shared value);
+
+    [DllImport(Lib)]
+    internal static extern uint with_fields_get_value(WithFields self);
+
+    [DllImport(Lib)]
+    internal static extern int with_fields_set_value(WithFields self, uint value);
+
+    [DllImport(Lib)]
+    internal static extern void script_destroy(IntPtr ptr);
+
+    [DllImport(Lib)]
+    internal static extern void with_opaque_destroy(IntPtr ptr);
+
+    [DllImport(Lib)]
+    internal static extern void with_opaque_set_script(WithOpaque self, Script value);
+}
+
+/// <summary>Owns a pointer to <c>Opaque</c>, freed when the handle is disposed or finalized</summary>
+public sealed class Opaque : SafeHandle
+{
+    /// <summary>Take the ownership of a pointer returned by the C API</summary>
+    internal Opaque(IntPtr ptr) : base(IntPtr.Zero, true)
+    {
+        SetHandle(ptr);
+    }
+
+    public override bool IsInvalid => handle == IntPtr.Zero;
+
+    protected override bool ReleaseHandle()
+    {
+        Native.opaque_destroy(handle);
+        return true;
+    }
+}
+
+/// <summary>Owns a pointer to <c>Shared</c>, released when the handle is disposed or finalized</summary>
+public sealed class Shared : SafeHandle
+{
+    /// <summary>Take the ownership of a pointer returned by the C API</summary>
+    internal Shared(IntPtr ptr) : base(IntPtr.Zero, true)
+    {
+        SetHandle(ptr);
+    }
+
+    public override bool IsInvalid => handle == IntPtr.Zero;
+
+    protected override bool ReleaseHandle()
+    {
+        Native.shared_release(handle);
+        return true;
+    }
+}
+
+/// <summary>Owns a pointer to <c>WithFields</c>, freed when the handle is disposed or finalized</summary>
+public sealed class WithFields : SafeHandle
+{
+    /// <summary>Take the ownership of a pointer returned by the C API</summary>
+    internal WithFields(IntPtr ptr) : base(IntPtr.Zero, true)
+    {
+        SetHandle(ptr);
+    }
+
+    public override bool IsInvalid => handle == IntPtr.Zero;
+
+    protected override bool ReleaseHandle()
+    {
+        Native.with_fields_destroy(handle);
+        return true;
+    }
+
+    public Shared Shared
+    {
+        get
+        {
+            return new Shared(Native.with_fields_get_shared(this));
+        }
+        set
+        {
+            Native.with_fields_set_shared(this, value);
+        }
+    }
+
+    public uint Value
+    {
+        get
+        {
+            return Native.with_fields_get_value(this);
+        }
+        set
+        {
+            var code = Native.with_fields_set_value(this, value);
+            if (code != 0)
+            {
+                throw new ValidationErrorException(code);
+            }
+        }
+    }
+}
+
+/// <summary>Passed by value, with the layout of <c>Point</c></summary>
+[StructLayout(LayoutKind.Sequential)]
+public struct Point
+{
+    public int X;
+    public int Y;
+}
+
+/// <summary>Owns a pointer to <c>Script</c>, freed when the handle is disposed or finalized</summary>
+public sealed class Script : SafeHandle
+{
+    /// <summary>Take the ownership of a pointer returned by the C API</summary>
+    internal Script(IntPtr ptr) : base(IntPtr.Zero, true)
+    {
+        SetHandle(ptr);
+    }
+
+    public override bool IsInvalid => handle == IntPtr.Zero;
+
+    protected override bool ReleaseHandle()
+    {
+        Native.script_destroy(handle);
+        return true;
+    }
+}
+
+/// <summary>Owns a pointer to <c>WithOpaque</c>, freed when the handle is disposed or finalized</summary>
+public sealed class WithOpaque : SafeHandle
+{
+    /// <summary>Take the ownership of a pointer returned by the C API</summary>
+    internal WithOpaque(IntPtr ptr) : base(IntPtr.Zero, true)
+    {
+        SetHandle(ptr);
+    }
+
+    public override bool IsInvalid => handle == IntPtr.Zero;
+
+    protected override bool ReleaseHandle()
+    {
+        Native.with_opaque_destroy(handle);
+        return true;
+    }
+
+    public Script Script
+    {
+        set
+        {
+            Native.with_opaque_set_script(this, value);
+        }
+    }
+}
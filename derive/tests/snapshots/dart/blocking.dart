@@ -0,0 +1,144 @@
+// Generated by `#[expose_mod]`. Do not edit.
+// ignore_for_file: non_constant_identifier_names
+
+import 'dart:ffi';
+import 'dart:io';
+import 'dart:typed_data';
+
+import 'package:ffi/ffi.dart';
+
+final _lib = _open();
+
+DynamicLibrary _open() {
+  if (Platform.isMacOS || Platform.isIOS) {
+    return DynamicLibrary.open('libderive.dylib');
+  }
+  if (Platform.isWindows) {
+    return DynamicLibrary.open('derive.dll');
+  }
+  return DynamicLibrary.open('libderive.so');
+}
+
+final _string_free = _lib.lookupFunction<Void Function(Pointer<Utf8>),
+    void Function(Pointer<Utf8>)>('string_free');
+
+/// Copy a string returned by the C API, and free it
+String _takeString(Pointer<Utf8> s) {
+  try {
+    return s.toDartString();
+  } finally {
+    _string_free(s);
+  }
+}
+
+final _bytes_free = _lib.lookupFunction<Void Function(_Arr),
+    void Function(_Arr)>('bytes_free');
+
+/// Copy a vector returned by the C API, and free it
+Uint8List _takeBytes(_Arr arr) {
+  try {
+    return arr.len == 0
+        ? Uint8List(0)
+        : Uint8List.fromList(arr.ptr.cast<Uint8>().asTypedList(arr.len));
+  } finally {
+    _bytes_free(arr);
+  }
+}
+
+/// Array of primitives passed to or returned by the C API, with the layout of `Arr_*`
+final class _Arr extends Struct {
+  external Pointer<Void> ptr;
+
+  @UintPtr()
+  external int len;
+}
+
+/// Thrown when the C API returns an error code
+class DeriveException implements Exception {
+  final String message;
+  final int code;
+
+  DeriveException(this.message, this.code);
+
+  @override
+  String toString() => message;
+}
+
+final class ErrorException extends DeriveException {
+  ErrorException(int code) : super('Error: error code $code', code);
+}
+
+final _wallet_new = _lib.lookupFunction<Void Function(Pointer<Utf8>, Pointer<Pointer<Void>>),
+    void Function(Pointer<Utf8>, Pointer<Pointer<Void>>)>('wallet_new');
+
+final _wallet_sync = _lib.lookupFunction<Int32 Function(Pointer<Void>, Pointer<Utf8>, UintPtr, Pointer<Uint64>),
+    int Function(Pointer<Void>, Pointer<Utf8>, int, Pointer<Uint64>)>('wallet_sync');
+
+final _wallet_sign = _lib.lookupFunction<_Arr Function(Pointer<Void>, _Arr),
+    _Arr Function(Pointer<Void>, _Arr)>('wallet_sign');
+
+final _broadcast = _lib.lookupFunction<Int32 Function(Pointer<Utf8>, _Arr, Pointer<Pointer<Utf8>>),
+    int Function(Pointer<Utf8>, _Arr, Pointer<Pointer<Utf8>>)>('broadcast');
+
+/// Owns a pointer to `Wallet`, freed when the object is garbage collected
+final class Wallet implements Finalizable {
+  static final _finalizer = NativeFinalizer(
+      _lib.lookup<NativeFunction<Void Function(Pointer<Void>)>>('wallet_destroy'));
+
+  final Pointer<Void> _ptr;
+
+  Wallet._(this._ptr) {
+    _finalizer.attach(this, _ptr);
+  }
+
+  factory Wallet(String descriptor) {
+    return using((arena) {
+      final descriptorPtr = descriptor.toNativeUtf8(allocator: arena);
+      final ptrOut = arena<Pointer<Void>>();
+      _wallet_new(descriptorPtr, ptrOut);
+      return Wallet._(ptrOut.value);
+    });
+  }
+
+  int sync(String url, int stopGap) {
+    return using((arena) {
+      final urlPtr = url.toNativeUtf8(allocator: arena);
+      final ptrOut = arena<Uint64>();
+      final code = _wallet_sync(_ptr, urlPtr, stopGap, ptrOut);
+      if (code != 0) {
+        throw ErrorException(code);
+      }
+      return ptrOut.value;
+    });
+  }
+
+  List<int> sign(List<int> psbt) {
+    return using((arena) {
+      final psbtData = arena<Uint8>(psbt.length + 1);
+      for (var i = 0; i < psbt.length; i++) psbtData[i] = psbt[i];
+      final psbtArr = arena<_Arr>();
+      psbtArr.ref
+        ..ptr = psbtData.cast()
+        ..len = psbt.length;
+      return _takeBytes(_wallet_sign(_ptr, psbtArr.ref));
+    });
+  }
+}
+
+String broadcast(String url, List<int> tx) {
+  return using((arena) {
+    final urlPtr = url.toNativeUtf8(allocator: arena);
+    final txData = arena<Uint8>(tx.length + 1);
+    for (var i = 0; i < tx.length; i++) txData[i] = tx[i];
+    final txArr = arena<_Arr>();
+    txArr.ref
+      ..ptr = txData.cast()
+      ..len = tx.length;
+    final ptrOut = arena<Pointer<Utf8>>();
+    final code = _broadcast(urlPtr, txArr.ref, ptrOut);
+    if (code != 0) {
+      throw ErrorException(code);
+    }
+    return _takeString(ptrOut.value);
+  });
+}
@@ -0,0 +1,101 @@
+// Generated by `#[expose_mod]`. Do not edit.
+// ignore_for_file: non_constant_identifier_names
+
+import 'dart:ffi';
+import 'dart:io';
+import 'dart:typed_data';
+
+import 'package:ffi/ffi.dart';
+
+final _lib = _open();
+
+DynamicLibrary _open() {
+  if (Platform.isMacOS || Platform.isIOS) {
+    return DynamicLibrary.open('libderive.dylib');
+  }
+  if (Platform.isWindows) {
+    return DynamicLibrary.open('derive.dll');
+  }
+  return DynamicLibrary.open('libderive.so');
+}
+
+final _string_free = _lib.lookupFunction<Void Function(Pointer<Utf8>),
+    void Function(Pointer<Utf8>)>('string_free');
+
+/// Copy a string returned by the C API, and free it
+String _takeString(Pointer<Utf8> s) {
+  try {
+    return s.toDartString();
+  } finally {
+    _string_free(s);
+  }
+}
+
+final _bytes_free = _lib.lookupFunction<Void Function(_Arr),
+    void Function(_Arr)>('bytes_free');
+
+/// Copy a vector returned by the C API, and free it
+Uint8List _takeBytes(_Arr arr) {
+  try {
+    return arr.len == 0
+        ? Uint8List(0)
+        : Uint8List.fromList(arr.ptr.cast<Uint8>().asTypedList(arr.len));
+  } finally {
+    _bytes_free(arr);
+  }
+}
+
+/// Array of primitives passed to or returned by the C API, with the layout of `Arr_*`
+final class _Arr extends Struct {
+  external Pointer<Void> ptr;
+
+  @UintPtr()
+  external int len;
+}
+
+/// Thrown when the C API returns an error code
+class DeriveException implements Exception {
+  final String message;
+  final int code;
+
+  DeriveException(this.message, this.code);
+
+  @override
+  String toString() => message;
+}
+
+final _script_to_bytes = _lib.lookupFunction<_Arr Function(Pointer<Void>),
+    _Arr Function(Pointer<Void>)>('script_to_bytes');
+
+final _script_from_bytes = _lib.lookupFunction<Void Function(_Arr, Pointer<Pointer<Void>>),
+    void Function(_Arr, Pointer<Pointer<Void>>)>('script_from_bytes');
+
+/// Owns a pointer to `Script`, freed when the object is garbage collected
+final class Script implements Finalizable {
+  static final _finalizer = NativeFinalizer(
+      _lib.lookup<NativeFunction<Void Function(Pointer<Void>)>>('script_destroy'));
+
+  final Pointer<Void> _ptr;
+
+  Script._(this._ptr) {
+    _finalizer.attach(this, _ptr);
+  }
+
+  List<int> toBytes() {
+    return _takeBytes(_script_to_bytes(_ptr));
+  }
+
+  static Script fromBytes(List<int> bytes) {
+    return using((arena) {
+      final bytesData = arena<Uint8>(bytes.length + 1);
+      for (var i = 0; i < bytes.length; i++) bytesData[i] = bytes[i];
+      final bytesArr = arena<_Arr>();
+      bytesArr.ref
+        ..ptr = bytesData.cast()
+        ..len = bytes.length;
+      final ptrOut = arena<Pointer<Void>>();
+      _script_from_bytes(bytesArr.ref, ptrOut);
+      return Script._(ptrOut.value);
+    });
+  }
+}
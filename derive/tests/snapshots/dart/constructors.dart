@@ -0,0 +1,118 @@
+// Generated by `#[expose_mod]`. Do not edit.
+// ignore_for_file: non_constant_identifier_names
+
+import 'dart:ffi';
+import 'dart:io';
+import 'dart:typed_data';
+
+import 'package:ffi/ffi.dart';
+
+final _lib = _open();
+
+DynamicLibrary _open() {
+  if (Platform.isMacOS || Platform.isIOS) {
+    return DynamicLibrary.open('libderive.dylib');
+  }
+  if (Platform.isWindows) {
+    return DynamicLibrary.open('derive.dll');
+  }
+  return DynamicLibrary.open('libderive.so');
+}
+
+final _string_free = _lib.lookupFunction<Void Function(Pointer<Utf8>),
+    void Function(Pointer<Utf8>)>('string_free');
+
+/// Copy a string returned by the C API, and free it
+String _takeString(Pointer<Utf8> s) {
+  try {
+    return s.toDartString();
+  } finally {
+    _string_free(s);
+  }
+}
+
+final _bytes_free = _lib.lookupFunction<Void Function(_Arr),
+    void Function(_Arr)>('bytes_free');
+
+/// Copy a vector returned by the C API, and free it
+Uint8List _takeBytes(_Arr arr) {
+  try {
+    return arr.len == 0
+        ? Uint8List(0)
+        : Uint8List.fromList(arr.ptr.cast<Uint8>().asTypedList(arr.len));
+  } finally {
+    _bytes_free(arr);
+  }
+}
+
+/// Array of primitives passed to or returned by the C API, with the layout of `Arr_*`
+final class _Arr extends Struct {
+  external Pointer<Void> ptr;
+
+  @UintPtr()
+  external int len;
+}
+
+/// Thrown when the C API returns an error code
+class DeriveException implements Exception {
+  final String message;
+  final int code;
+
+  DeriveException(this.message, this.code);
+
+  @override
+  String toString() => message;
+}
+
+final class ErrorException extends DeriveException {
+  ErrorException(int code) : super('Error: error code $code', code);
+}
+
+final _network_new = _lib.lookupFunction<Int32 Function(Pointer<Utf8>, Pointer<Pointer<Void>>),
+    int Function(Pointer<Utf8>, Pointer<Pointer<Void>>)>('network_new');
+
+final _network_new_bitcoin = _lib.lookupFunction<Void Function(Pointer<Pointer<Void>>),
+    void Function(Pointer<Pointer<Void>>)>('network_new_bitcoin');
+
+final _network_new_from_magic = _lib.lookupFunction<Void Function(Uint32, Pointer<Pointer<Void>>),
+    void Function(int, Pointer<Pointer<Void>>)>('network_new_from_magic');
+
+/// Owns a pointer to `Network`, freed when the object is garbage collected
+final class Network implements Finalizable {
+  static final _finalizer = NativeFinalizer(
+      _lib.lookup<NativeFunction<Void Function(Pointer<Void>)>>('network_destroy'));
+
+  final Pointer<Void> _ptr;
+
+  Network._(this._ptr) {
+    _finalizer.attach(this, _ptr);
+  }
+
+  factory Network(String s) {
+    return using((arena) {
+      final sPtr = s.toNativeUtf8(allocator: arena);
+      final ptrOut = arena<Pointer<Void>>();
+      final code = _network_new(sPtr, ptrOut);
+      if (code != 0) {
+        throw ErrorException(code);
+      }
+      return Network._(ptrOut.value);
+    });
+  }
+
+  factory Network.bitcoin() {
+    return using((arena) {
+      final ptrOut = arena<Pointer<Void>>();
+      _network_new_bitcoin(ptrOut);
+      return Network._(ptrOut.value);
+    });
+  }
+
+  static Network? fromMagic(int magic) {
+    return using((arena) {
+      final ptrOut = arena<Pointer<Void>>();
+      _network_new_from_magic(magic, ptrOut);
+      return ptrOut.value == nullptr ? null : Network._(ptrOut.value);
+    });
+  }
+}
@@ -0,0 +1,130 @@
+// Generated by `#[expose_mod]`. Do not edit.
+// ignore_for_file: non_constant_identifier_names
+
+import 'dart:ffi';
+import 'dart:io';
+import 'dart:typed_data';
+
+import 'package:ffi/ffi.dart';
+
+final _lib = _open();
+
+DynamicLibrary _open() {
+  if (Platform.isMacOS || Platform.isIOS) {
+    return DynamicLibrary.open('libderive.dylib');
+  }
+  if (Platform.isWindows) {
+    return DynamicLibrary.open('derive.dll');
+  }
+  return DynamicLibrary.open('libderive.so');
+}
+
+final _string_free = _lib.lookupFunction<Void Function(Pointer<Utf8>),
+    void Function(Pointer<Utf8>)>('string_free');
+
+/// Copy a string returned by the C API, and free it
+String _takeString(Pointer<Utf8> s) {
+  try {
+    return s.toDartString();
+  } finally {
+    _string_free(s);
+  }
+}
+
+final _bytes_free = _lib.lookupFunction<Void Function(_Arr),
+    void Function(_Arr)>('bytes_free');
+
+/// Copy a vector returned by the C API, and free it
+Uint8List _takeBytes(_Arr arr) {
+  try {
+    return arr.len == 0
+        ? Uint8List(0)
+        : Uint8List.fromList(arr.ptr.cast<Uint8>().asTypedList(arr.len));
+  } finally {
+    _bytes_free(arr);
+  }
+}
+
+/// Array of primitives passed to or returned by the C API, with the layout of `Arr_*`
+final class _Arr extends Struct {
+  external Pointer<Void> ptr;
+
+  @UintPtr()
+  external int len;
+}
+
+/// Thrown when the C API returns an error code
+class DeriveException implements Exception {
+  final String message;
+  final int code;
+
+  DeriveException(this.message, this.code);
+
+  @override
+  String toString() => message;
+}
+
+final _fee_rate_from_sat_per_vb = _lib.lookupFunction<Void Function(Float, Pointer<Pointer<Void>>),
+    void Function(double, Pointer<Pointer<Void>>)>('fee_rate_from_sat_per_vb');
+
+final _fee_rate_from_sat_per_vb_with_defaults = _lib.lookupFunction<Void Function(Pointer<Pointer<Void>>),
+    void Function(Pointer<Pointer<Void>>)>('fee_rate_from_sat_per_vb_with_defaults');
+
+final _fee_rate_fee_for = _lib.lookupFunction<Uint64 Function(Pointer<Void>, Uint64, Pointer<Void>),
+    int Function(Pointer<Void>, int, Pointer<Void>)>('fee_rate_fee_for');
+
+final _fee_rate_fee_for_with_defaults = _lib.lookupFunction<Uint64 Function(Pointer<Void>, Uint64),
+    int Function(Pointer<Void>, int)>('fee_rate_fee_for_with_defaults');
+
+final _greet = _lib.lookupFunction<Pointer<Utf8> Function(Pointer<Utf8>),
+    Pointer<Utf8> Function(Pointer<Utf8>)>('greet');
+
+final _greet_with_defaults = _lib.lookupFunction<Pointer<Utf8> Function(),
+    Pointer<Utf8> Function()>('greet_with_defaults');
+
+/// Owns a pointer to `FeeRate`, freed when the object is garbage collected
+final class FeeRate implements Finalizable {
+  static final _finalizer = NativeFinalizer(
+      _lib.lookup<NativeFunction<Void Function(Pointer<Void>)>>('fee_rate_destroy'));
+
+  final Pointer<Void> _ptr;
+
+  FeeRate._(this._ptr) {
+    _finalizer.attach(this, _ptr);
+  }
+
+  static FeeRate fromSatPerVb(double satPerVb) {
+    return using((arena) {
+      final ptrOut = arena<Pointer<Void>>();
+      _fee_rate_from_sat_per_vb(satPerVb, ptrOut);
+      return FeeRate._(ptrOut.value);
+    });
+  }
+
+  static FeeRate fromSatPerVbWithDefaults() {
+    return using((arena) {
+      final ptrOut = arena<Pointer<Void>>();
+      _fee_rate_from_sat_per_vb_with_defaults(ptrOut);
+      return FeeRate._(ptrOut.value);
+    });
+  }
+
+  int feeFor(int vbytes, FeeRate min) {
+    return _fee_rate_fee_for(_ptr, vbytes, min._ptr);
+  }
+
+  int feeForWithDefaults(int vbytes) {
+    return _fee_rate_fee_for_with_defaults(_ptr, vbytes);
+  }
+}
+
+String greet(String name) {
+  return using((arena) {
+    final namePtr = name.toNativeUtf8(allocator: arena);
+    return _takeString(_greet(namePtr));
+  });
+}
+
+String greetWithDefaults() {
+  return _takeString(_greet_with_defaults());
+}
@@ -0,0 +1,123 @@
+// Generated by `#[expose_mod]`. Do not edit.
+// ignore_for_file: non_constant_identifier_names
+
+import 'dart:ffi';
+import 'dart:io';
+import 'dart:typed_data';
+
+import 'package:ffi/ffi.dart';
+
+final _lib = _open();
+
+DynamicLibrary _open() {
+  if (Platform.isMacOS || Platform.isIOS) {
+    return DynamicLibrary.open('libderive.dylib');
+  }
+  if (Platform.isWindows) {
+    return DynamicLibrary.open('derive.dll');
+  }
+  return DynamicLibrary.open('libderive.so');
+}
+
+final _string_free = _lib.lookupFunction<Void Function(Pointer<Utf8>),
+    void Function(Pointer<Utf8>)>('string_free');
+
+/// Copy a string returned by the C API, and free it
+String _takeString(Pointer<Utf8> s) {
+  try {
+    return s.toDartString();
+  } finally {
+    _string_free(s);
+  }
+}
+
+final _bytes_free = _lib.lookupFunction<Void Function(_Arr),
+    void Function(_Arr)>('bytes_free');
+
+/// Copy a vector returned by the C API, and free it
+Uint8List _takeBytes(_Arr arr) {
+  try {
+    return arr.len == 0
+        ? Uint8List(0)
+        : Uint8List.fromList(arr.ptr.cast<Uint8>().asTypedList(arr.len));
+  } finally {
+    _bytes_free(arr);
+  }
+}
+
+/// Array of primitives passed to or returned by the C API, with the layout of `Arr_*`
+final class _Arr extends Struct {
+  external Pointer<Void> ptr;
+
+  @UintPtr()
+  external int len;
+}
+
+/// Thrown when the C API returns an error code
+class DeriveException implements Exception {
+  final String message;
+  final int code;
+
+  DeriveException(this.message, this.code);
+
+  @override
+  String toString() => message;
+}
+
+final _counter_new = _lib.lookupFunction<Void Function(Uint32, Pointer<Pointer<Void>>),
+    void Function(int, Pointer<Pointer<Void>>)>('counter_new');
+
+final _counter_zero = _lib.lookupFunction<Void Function(Pointer<Pointer<Void>>),
+    void Function(Pointer<Pointer<Void>>)>('counter_zero');
+
+final _counter_get = _lib.lookupFunction<Uint32 Function(Pointer<Void>),
+    int Function(Pointer<Void>)>('counter_get');
+
+final _counter_increment = _lib.lookupFunction<Void Function(Pointer<Void>, Uint32),
+    void Function(Pointer<Void>, int)>('counter_increment');
+
+final _counter_label = _lib.lookupFunction<Pointer<Utf8> Function(Pointer<Void>, Pointer<Utf8>),
+    Pointer<Utf8> Function(Pointer<Void>, Pointer<Utf8>)>('counter_label');
+
+/// Owns a pointer to `Counter`, freed when the object is garbage collected
+final class Counter implements Finalizable {
+  static final _finalizer = NativeFinalizer(
+      _lib.lookup<NativeFunction<Void Function(Pointer<Void>)>>('counter_destroy'));
+
+  final Pointer<Void> _ptr;
+
+  Counter._(this._ptr) {
+    _finalizer.attach(this, _ptr);
+  }
+
+  factory Counter(int start) {
+    return using((arena) {
+      final ptrOut = arena<Pointer<Void>>();
+      _counter_new(start, ptrOut);
+      return Counter._(ptrOut.value);
+    });
+  }
+
+  static Counter zero() {
+    return using((arena) {
+      final ptrOut = arena<Pointer<Void>>();
+      _counter_zero(ptrOut);
+      return Counter._(ptrOut.value);
+    });
+  }
+
+  int get() {
+    return _counter_get(_ptr);
+  }
+
+  void increment(int by) {
+    _counter_increment(_ptr, by);
+  }
+
+  String label(String prefix) {
+    return using((arena) {
+      final prefixPtr = prefix.toNativeUtf8(allocator: arena);
+      return _takeString(_counter_label(_ptr, prefixPtr));
+    });
+  }
+}
@@ -0,0 +1,116 @@
+// Generated by `#[expose_mod]`. Do not edit.
+// ignore_for_file: non_constant_identifier_names
+
+import 'dart:ffi';
+import 'dart:io';
+import 'dart:typed_data';
+
+import 'package:ffi/ffi.dart';
+
+final _lib = _open();
+
+DynamicLibrary _open() {
+  if (Platform.isMacOS || Platform.isIOS) {
+    return DynamicLibrary.open('libderive.dylib');
+  }
+  if (Platform.isWindows) {
+    return DynamicLibrary.open('derive.dll');
+  }
+  return DynamicLibrary.open('libderive.so');
+}
+
+final _string_free = _lib.lookupFunction<Void Function(Pointer<Utf8>),
+    void Function(Pointer<Utf8>)>('string_free');
+
+/// Copy a string returned by the C API, and free it
+String _takeString(Pointer<Utf8> s) {
+  try {
+    return s.toDartString();
+  } finally {
+    _string_free(s);
+  }
+}
+
+final _bytes_free = _lib.lookupFunction<Void Function(_Arr),
+    void Function(_Arr)>('bytes_free');
+
+/// Copy a vector returned by the C API, and free it
+Uint8List _takeBytes(_Arr arr) {
+  try {
+    return arr.len == 0
+        ? Uint8List(0)
+        : Uint8List.fromList(arr.ptr.cast<Uint8>().asTypedList(arr.len));
+  } finally {
+    _bytes_free(arr);
+  }
+}
+
+/// Array of primitives passed to or returned by the C API, with the layout of `Arr_*`
+final class _Arr extends Struct {
+  external Pointer<Void> ptr;
+
+  @UintPtr()
+  external int len;
+}
+
+/// Thrown when the C API returns an error code
+class DeriveException implements Exception {
+  final String message;
+  final int code;
+
+  DeriveException(this.message, this.code);
+
+  @override
+  String toString() => message;
+}
+
+final _outer_inner_thing_new = _lib.lookupFunction<Void Function(Pointer<Pointer<Void>>),
+    void Function(Pointer<Pointer<Void>>)>('outer_inner_thing_new');
+
+final _version = _lib.lookupFunction<Uint32 Function(),
+    int Function()>('version');
+
+final _outer_inner_count = _lib.lookupFunction<Uint32 Function(),
+    int Function()>('outer_inner_count');
+
+final _ext_count = _lib.lookupFunction<Uint32 Function(),
+    int Function()>('ext_count');
+
+final _ext_outer_prefixed_nested_count = _lib.lookupFunction<Uint32 Function(),
+    int Function()>('ext_outer_prefixed_nested_count');
+
+/// Owns a pointer to `Thing`, released when the object is garbage collected
+final class InnerThing implements Finalizable {
+  static final _finalizer = NativeFinalizer(
+      _lib.lookup<NativeFunction<Void Function(Pointer<Void>)>>('outer_inner_thing_release'));
+
+  final Pointer<Void> _ptr;
+
+  InnerThing._(this._ptr) {
+    _finalizer.attach(this, _ptr);
+  }
+
+  static InnerThing new_() {
+    return using((arena) {
+      final ptrOut = arena<Pointer<Void>>();
+      _outer_inner_thing_new(ptrOut);
+      return InnerThing._(ptrOut.value);
+    });
+  }
+}
+
+int version() {
+  return _version();
+}
+
+int innerCount() {
+  return _outer_inner_count();
+}
+
+int prefixedCount() {
+  return _ext_count();
+}
+
+int prefixedNestedCount() {
+  return _ext_outer_prefixed_nested_count();
+}
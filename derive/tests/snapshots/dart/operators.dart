@@ -0,0 +1,147 @@
+// Generated by `#[expose_mod]`. Do not edit.
+// ignore_for_file: non_constant_identifier_names
+
+import 'dart:ffi';
+import 'dart:io';
+import 'dart:typed_data';
+
+import 'package:ffi/ffi.dart';
+
+final _lib = _open();
+
+DynamicLibrary _open() {
+  if (Platform.isMacOS || Platform.isIOS) {
+    return DynamicLibrary.open('libderive.dylib');
+  }
+  if (Platform.isWindows) {
+    return DynamicLibrary.open('derive.dll');
+  }
+  return DynamicLibrary.open('libderive.so');
+}
+
+final _string_free = _lib.lookupFunction<Void Function(Pointer<Utf8>),
+    void Function(Pointer<Utf8>)>('string_free');
+
+/// Copy a string returned by the C API, and free it
+String _takeString(Pointer<Utf8> s) {
+  try {
+    return s.toDartString();
+  } finally {
+    _string_free(s);
+  }
+}
+
+final _bytes_free = _lib.lookupFunction<Void Function(_Arr),
+    void Function(_Arr)>('bytes_free');
+
+/// Copy a vector returned by the C API, and free it
+Uint8List _takeBytes(_Arr arr) {
+  try {
+    return arr.len == 0
+        ? Uint8List(0)
+        : Uint8List.fromList(arr.ptr.cast<Uint8>().asTypedList(arr.len));
+  } finally {
+    _bytes_free(arr);
+  }
+}
+
+/// Array of primitives passed to or returned by the C API, with the layout of `Arr_*`
+final class _Arr extends Struct {
+  external Pointer<Void> ptr;
+
+  @UintPtr()
+  external int len;
+}
+
+/// Thrown when the C API returns an error code
+class DeriveException implements Exception {
+  final String message;
+  final int code;
+
+  DeriveException(this.message, this.code);
+
+  @override
+  String toString() => message;
+}
+
+final class ErrorException extends DeriveException {
+  ErrorException(int code) : super('Error: error code $code', code);
+}
+
+final class IndexErrorException extends DeriveException {
+  IndexErrorException(int code) : super('IndexError: error code $code', code);
+}
+
+final _amount_add = _lib.lookupFunction<Int32 Function(Pointer<Void>, Pointer<Void>, Pointer<Pointer<Void>>),
+    int Function(Pointer<Void>, Pointer<Void>, Pointer<Pointer<Void>>)>('amount_add');
+
+final _amount_mul = _lib.lookupFunction<Void Function(Pointer<Void>, Uint64, Pointer<Pointer<Void>>),
+    void Function(Pointer<Void>, int, Pointer<Pointer<Void>>)>('amount_mul');
+
+final _amount_neg = _lib.lookupFunction<Void Function(Pointer<Void>, Pointer<Pointer<Void>>),
+    void Function(Pointer<Void>, Pointer<Pointer<Void>>)>('amount_neg');
+
+final _script_index = _lib.lookupFunction<Int32 Function(Pointer<Void>, UintPtr, Pointer<Uint8>),
+    int Function(Pointer<Void>, int, Pointer<Uint8>)>('script_index');
+
+/// Owns a pointer to `Amount`, freed when the object is garbage collected
+final class Amount implements Finalizable {
+  static final _finalizer = NativeFinalizer(
+      _lib.lookup<NativeFunction<Void Function(Pointer<Void>)>>('amount_destroy'));
+
+  final Pointer<Void> _ptr;
+
+  Amount._(this._ptr) {
+    _finalizer.attach(this, _ptr);
+  }
+
+  Amount operator +(Amount rhs) {
+    return using((arena) {
+      final ptrOut = arena<Pointer<Void>>();
+      final code = _amount_add(_ptr, rhs._ptr, ptrOut);
+      if (code != 0) {
+        throw ErrorException(code);
+      }
+      return Amount._(ptrOut.value);
+    });
+  }
+
+  Amount operator *(int rhs) {
+    return using((arena) {
+      final ptrOut = arena<Pointer<Void>>();
+      _amount_mul(_ptr, rhs, ptrOut);
+      return Amount._(ptrOut.value);
+    });
+  }
+
+  Amount operator -() {
+    return using((arena) {
+      final ptrOut = arena<Pointer<Void>>();
+      _amount_neg(_ptr, ptrOut);
+      return Amount._(ptrOut.value);
+    });
+  }
+}
+
+/// Owns a pointer to `Script`, freed when the object is garbage collected
+final class Script implements Finalizable {
+  static final _finalizer = NativeFinalizer(
+      _lib.lookup<NativeFunction<Void Function(Pointer<Void>)>>('script_destroy'));
+
+  final Pointer<Void> _ptr;
+
+  Script._(this._ptr) {
+    _finalizer.attach(this, _ptr);
+  }
+
+  int operator [](int rhs) {
+    return using((arena) {
+      final ptrOut = arena<Uint8>();
+      final code = _script_index(_ptr, rhs, ptrOut);
+      if (code != 0) {
+        throw IndexErrorException(code);
+      }
+      return ptrOut.value;
+    });
+  }
+}
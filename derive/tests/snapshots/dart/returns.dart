@@ -0,0 +1,139 @@
+// Generated by `#[expose_mod]`. Do not edit.
+// ignore_for_file: non_constant_identifier_names
+
+import 'dart:ffi';
+import 'dart:io';
+import 'dart:typed_data';
+
+import 'package:ffi/ffi.dart';
+
+final _lib = _open();
+
+DynamicLibrary _open() {
+  if (Platform.isMacOS || Platform.isIOS) {
+    return DynamicLibrary.open('libderive.dylib');
+  }
+  if (Platform.isWindows) {
+    return DynamicLibrary.open('derive.dll');
+  }
+  return DynamicLibrary.open('libderive.so');
+}
+
+final _string_free = _lib.lookupFunction<Void Function(Pointer<Utf8>),
+    void Function(Pointer<Utf8>)>('string_free');
+
+/// Copy a string returned by the C API, and free it
+String _takeString(Pointer<Utf8> s) {
+  try {
+    return s.toDartString();
+  } finally {
+    _string_free(s);
+  }
+}
+
+final _bytes_free = _lib.lookupFunction<Void Function(_Arr),
+    void Function(_Arr)>('bytes_free');
+
+/// Copy a vector returned by the C API, and free it
+Uint8List _takeBytes(_Arr arr) {
+  try {
+    return arr.len == 0
+        ? Uint8List(0)
+        : Uint8List.fromList(arr.ptr.cast<Uint8>().asTypedList(arr.len));
+  } finally {
+    _bytes_free(arr);
+  }
+}
+
+/// Array of primitives passed to or returned by the C API, with the layout of `Arr_*`
+final class _Arr extends Struct {
+  external Pointer<Void> ptr;
+
+  @UintPtr()
+  external int len;
+}
+
+/// Thrown when the C API returns an error code
+class DeriveException implements Exception {
+  final String message;
+  final int code;
+
+  DeriveException(this.message, this.code);
+
+  @override
+  String toString() => message;
+}
+
+final class ErrorException extends DeriveException {
+  ErrorException(int code) : super('Error: error code $code', code);
+}
+
+final _item_parse = _lib.lookupFunction<Int32 Function(Pointer<Utf8>, Pointer<Pointer<Void>>),
+    int Function(Pointer<Utf8>, Pointer<Pointer<Void>>)>('item_parse');
+
+final _item_check = _lib.lookupFunction<Int32 Function(Pointer<Void>),
+    int Function(Pointer<Void>)>('item_check');
+
+final _item_find = _lib.lookupFunction<Void Function(Pointer<Utf8>, Pointer<Pointer<Void>>),
+    void Function(Pointer<Utf8>, Pointer<Pointer<Void>>)>('item_find');
+
+final _item_to_bytes = _lib.lookupFunction<_Arr Function(Pointer<Void>),
+    _Arr Function(Pointer<Void>)>('item_to_bytes');
+
+final _item_name = _lib.lookupFunction<Pointer<Utf8> Function(Pointer<Void>),
+    Pointer<Utf8> Function(Pointer<Void>)>('item_name');
+
+final _last_error = _lib.lookupFunction<Int32 Function(),
+    int Function()>('last_error');
+
+/// Owns a pointer to `Item`, freed when the object is garbage collected
+final class Item implements Finalizable {
+  static final _finalizer = NativeFinalizer(
+      _lib.lookup<NativeFunction<Void Function(Pointer<Void>)>>('item_destroy'));
+
+  final Pointer<Void> _ptr;
+
+  Item._(this._ptr) {
+    _finalizer.attach(this, _ptr);
+  }
+
+  static Item parse(String s) {
+    return using((arena) {
+      final sPtr = s.toNativeUtf8(allocator: arena);
+      final ptrOut = arena<Pointer<Void>>();
+      final code = _item_parse(sPtr, ptrOut);
+      if (code != 0) {
+        throw ErrorException(code);
+      }
+      return Item._(ptrOut.value);
+    });
+  }
+
+  void check() {
+    final code = _item_check(_ptr);
+    if (code != 0) {
+      throw ErrorException(code);
+    }
+  }
+
+  static Item? find(String name) {
+    return using((arena) {
+      final namePtr = name.toNativeUtf8(allocator: arena);
+      final ptrOut = arena<Pointer<Void>>();
+      _item_find(namePtr, ptrOut);
+      return ptrOut.value == nullptr ? null : Item._(ptrOut.value);
+    });
+  }
+
+  List<int> toBytes() {
+    return _takeBytes(_item_to_bytes(_ptr));
+  }
+
+  String name() {
+    return _takeString(_item_name(_ptr));
+  }
+}
+
+int lastError() {
+  return _last_error();
+}
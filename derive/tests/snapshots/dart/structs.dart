@@ -0,0 +1,176 @@
+// Generated by `#[expose_mod]`. Do not edit.
+// ignore_for_file: non_constant_identifier_names
+
+import 'dart:ffi';
+import 'dart:io';
+import 'dart:typed_data';
+
+import 'package:ffi/ffi.dart';
+
+final _lib = _open();
+
+DynamicLibrary _open() {
+  if (Platform.isMacOS || Platform.isIOS) {
+    return DynamicLibrary.open('libderive.dylib');
+  }
+  if (Platform.isWindows) {
+    return DynamicLibrary.open('derive.dll');
+  }
+  return DynamicLibrary.open('libderive.so');
+}
+
+final _string_free = _lib.lookupFunction<Void Function(Pointer<Utf8>),
+    void Function(Pointer<Utf8>)>('string_free');
+
+/// Copy a string returned by the C API, and free it
+String _takeString(Pointer<Utf8> s) {
+  try {
+    return s.toDartString();
+  } finally {
+    _string_free(s);
+  }
+}
+
+final _bytes_free = _lib.lookupFunction<Void Function(_Arr),
+    void Function(_Arr)>('bytes_free');
+
+/// Copy a vector returned by the C API, and free it
+Uint8List _takeBytes(_Arr arr) {
+  try {
+    return arr.len == 0
+        ? Uint8List(0)
+        : Uint8List.fromList(arr.ptr.cast<Uint8>().asTypedList(arr.len));
+  } finally {
+    _bytes_free(arr);
+  }
+}
+
+/// Array of primitives passed to or returned by the C API, with the layout of `Arr_*`
+final class _Arr extends Struct {
+  external Pointer<Void> ptr;
+
+  @UintPtr()
+  external int len;
+}
+
+/// Thrown when the C API returns an error code
+class DeriveException implements Exception {
+  final String message;
+  final int code;
+
+  DeriveException(this.message, this.code);
+
+  @override
+  String toString() => message;
+}
+
+final class ValidationErrorException extends DeriveException {
+  ValidationErrorException(int code) : super('ValidationError: error code $code', code);
+}
+
+final _with_fields_get_shared = _lib.lookupFunction<Pointer<Void> Function(Pointer<Void>),
+    Pointer<Void> Function(Pointer<Void>)>('with_fields_get_shared');
+
+final _with_fields_set_shared = _lib.lookupFunction<Void Function(Pointer<Void>, Pointer<Void>),
+    void Function(Pointer<Void>, Pointer<Void>)>('with_fields_set_shared');
+
+final _with_fields_get_value = _lib.lookupFunction<Uint32 Function(Pointer<Void>),
+    int Function(Pointer<Void>)>('with_fields_get_value');
+
+final _with_fields_set_value = _lib.lookupFunction<Int32 Function(Pointer<Void>, Uint32),
+    int Function(Pointer<Void>, int)>('with_fields_set_value');
+
+final _with_opaque_set_script = _lib.lookupFunction<Void Function(Pointer<Void>, Pointer<Void>),
+    void Function(Pointer<Void>, Pointer<Void>)>('with_opaque_set_script');
+
+/// Owns a pointer to `Opaque`, freed when the object is garbage collected
+final class Opaque implements Finalizable {
+  static final _finalizer = NativeFinalizer(
+      _lib.lookup<NativeFunction<Void Function(Pointer<Void>)>>('opaque_destroy'));
+
+  final Pointer<Void> _ptr;
+
+  Opaque._(this._ptr) {
+    _finalizer.attach(this, _ptr);
+  }
+}
+
+/// Owns a pointer to `Shared`, released when the object is garbage collected
+final class Shared implements Finalizable {
+  static final _finalizer = NativeFinalizer(
+      _lib.lookup<NativeFunction<Void Function(Pointer<Void>)>>('shared_release'));
+
+  final Pointer<Void> _ptr;
+
+  Shared._(this._ptr) {
+    _finalizer.attach(this, _ptr);
+  }
+}
+
+/// Owns a pointer to `WithFields`, freed when the object is garbage collected
+final class WithFields implements Finalizable {
+  static final _finalizer = NativeFinalizer(
+      _lib.lookup<NativeFunction<Void Function(Pointer<Void>)>>('with_fields_destroy'));
+
+  final Pointer<Void> _ptr;
+
+  WithFields._(this._ptr) {
+    _finalizer.attach(this, _ptr);
+  }
+
+  Shared get shared {
+    return Shared._(_with_fields_get_shared(_ptr));
+  }
+
+  set shared(Shared value) {
+    _with_fields_set_shared(_ptr, value._ptr);
+  }
+
+  int get value {
+    return _with_fields_get_value(_ptr);
+  }
+
+  set value(int value) {
+    final code = _with_fields_set_value(_ptr, value);
+    if (code != 0) {
+      throw ValidationErrorException(code);
+    }
+  }
+}
+
+/// Passed by value, with the layout of `Point`
+final class Point extends Struct {
+  @Int32()
+  external int x;
+
+  @Int32()
+  external int y;
+}
+
+/// Owns a pointer to `Script`, freed when the object is garbage collected
+final class Script implements Finalizable {
+  static final _finalizer = NativeFinalizer(
+      _lib.lookup<NativeFunction<Void Function(Pointer<Void>)>>('script_destroy'));
+
+  final Pointer<Void> _ptr;
+
+  Script._(this._ptr) {
+    _finalizer.attach(this, _ptr);
+  }
+}
+
+/// Owns a pointer to `WithOpaque`, freed when the object is garbage collected
+final class WithOpaque implements Finalizable {
+  static final _finalizer = NativeFinalizer(
+      _lib.lookup<NativeFunction<Void Function(Pointer<Void>)>>('with_opaque_destroy'));
+
+  final Pointer<Void> _ptr;
+
+  WithOpaque._(this._ptr) {
+    _finalizer.attach(this, _ptr);
+  }
+
+  set script(Script value) {
+    _with_opaque_set_script(_ptr, value._ptr);
+  }
+}
@@ -0,0 +1,114 @@
+// Generated by `#[expose_mod]`. Do not edit.
+// ignore_for_file: non_constant_identifier_names
+
+import 'dart:ffi';
+import 'dart:io';
+import 'dart:typed_data';
+
+import 'package:ffi/ffi.dart';
+
+final _lib = _open();
+
+DynamicLibrary _open() {
+  if (Platform.isMacOS || Platform.isIOS) {
+    return DynamicLibrary.open('libderive.dylib');
+  }
+  if (Platform.isWindows) {
+    return DynamicLibrary.open('derive.dll');
+  }
+  return DynamicLibrary.open('libderive.so');
+}
+
+final _string_free = _lib.lookupFunction<Void Function(Pointer<Utf8>),
+    void Function(Pointer<Utf8>)>('string_free');
+
+/// Copy a string returned by the C API, and free it
+String _takeString(Pointer<Utf8> s) {
+  try {
+    return s.toDartString();
+  } finally {
+    _string_free(s);
+  }
+}
+
+final _bytes_free = _lib.lookupFunction<Void Function(_Arr),
+    void Function(_Arr)>('bytes_free');
+
+/// Copy a vector returned by the C API, and free it
+Uint8List _takeBytes(_Arr arr) {
+  try {
+    return arr.len == 0
+        ? Uint8List(0)
+        : Uint8List.fromList(arr.ptr.cast<Uint8>().asTypedList(arr.len));
+  } finally {
+    _bytes_free(arr);
+  }
+}
+
+/// Array of primitives passed to or returned by the C API, with the layout of `Arr_*`
+final class _Arr extends Struct {
+  external Pointer<Void> ptr;
+
+  @UintPtr()
+  external int len;
+}
+
+/// Thrown when the C API returns an error code
+class DeriveException implements Exception {
+  final String message;
+  final int code;
+
+  DeriveException(this.message, this.code);
+
+  @override
+  String toString() => message;
+}
+
+final _listener_struct_new = _lib.lookupFunction<Void Function(Pointer<Void>, Pointer<NativeFunction<Void Function(Pointer<Void>)>>, Pointer<NativeFunction<Uint32 Function(Pointer<Void>, Pointer<Utf8>)>>, Pointer<Pointer<Void>>),
+    void Function(Pointer<Void>, Pointer<NativeFunction<Void Function(Pointer<Void>)>>, Pointer<NativeFunction<Uint32 Function(Pointer<Void>, Pointer<Utf8>)>>, Pointer<Pointer<Void>>)>('listener_struct_new');
+
+/// Owns a pointer to `ListenerStruct`, freed when the object is garbage collected
+final class ListenerStruct implements Finalizable {
+  static final _finalizer = NativeFinalizer(
+      _lib.lookup<NativeFunction<Void Function(Pointer<Void>)>>('listener_struct_destroy'));
+
+  final Pointer<Void> _ptr;
+
+  ListenerStruct._(this._ptr) {
+    _finalizer.attach(this, _ptr);
+  }
+
+  /// Objects wrapped by the structs, by the id passed to the C API as `this`
+  static final _objects = <int, Listener>{};
+  static var _nextId = 1;
+
+  // Called when the struct is freed, possibly by a `NativeFinalizer`, so it can't run
+  // synchronously
+  static final _destroy =
+      NativeCallable<Void Function(Pointer<Void>)>.listener(_destroyCallback)
+        ..keepIsolateAlive = false;
+  static void _destroyCallback(Pointer<Void> self) => _objects.remove(self.address);
+
+  static final _onEvent = NativeCallable<Uint32 Function(Pointer<Void>, Pointer<Utf8>)>.isolateLocal(
+      _onEventCallback, exceptionalReturn: 0)
+    ..keepIsolateAlive = false;
+  static int _onEventCallback(Pointer<Void> self, Pointer<Utf8> event) =>
+      _objects[self.address]!.onEvent(_takeString(event));
+
+  /// Wrap `impl`, which is called back until the struct is freed. The callbacks must be
+  /// called from the thread of the isolate
+  factory ListenerStruct(Listener impl) {
+    final id = _nextId++;
+    _objects[id] = impl;
+    return using((arena) {
+      final ptrOut = arena<Pointer<Void>>();
+      _listener_struct_new(Pointer.fromAddress(id), _destroy.nativeFunction, _onEvent.nativeFunction, ptrOut);
+      return ListenerStruct._(ptrOut.value);
+    });
+  }
+}
+
+/// Implemented by the objects wrapped in a [ListenerStruct]
+abstract interface class Listener {
+  int onEvent(String event);
+}
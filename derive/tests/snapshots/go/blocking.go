@@ -0,0 +1,98 @@
+// Code generated by `#[expose_mod]`. DO NOT EDIT.
+
+package derive
+
+/*
+#cgo LDFLAGS: -lderive
+#include <stdint.h>
+#include <stdlib.h>
+#include "derive.h"
+*/
+import "C"
+
+import (
+	"fmt"
+	"runtime"
+	"unsafe"
+)
+
+// Error is returned when the C API fails with an error code
+type Error struct {
+	Code int32
+}
+
+func (e *Error) Error() string {
+	return fmt.Sprintf("Error: error code %d", e.Code)
+}
+
+// goString copies a string returned by the C API, and frees it
+func goString(s *C.char) string {
+	defer C.string_free(s)
+	return C.GoString(s)
+}
+
+// goBytes copies a vector returned by the C API, and frees it
+func goBytes(arr C.Arr_u8) []byte {
+	defer C.bytes_free(arr)
+	return C.GoBytes(unsafe.Pointer(arr.ptr), C.int(arr.len))
+}
+
+// Wallet owns a pointer to `Wallet`, freed when the struct is garbage collected
+type Wallet struct {
+	ptr *C.Wallet
+}
+
+// wrapWallet takes the ownership of a pointer returned by the C API
+func wrapWallet(ptr *C.Wallet) *Wallet {
+	obj := &Wallet{ptr: ptr}
+	runtime.SetFinalizer(obj, func(obj *Wallet) {
+		C.wallet_destroy(obj.ptr)
+	})
+	return obj
+}
+
+func NewWallet(descriptor string) *Wallet {
+	cDescriptor := C.CString(descriptor)
+	defer C.free(unsafe.Pointer(cDescriptor))
+	var out *C.Wallet
+	C.wallet_new(cDescriptor, &out)
+	return wrapWallet(out)
+}
+
+func (self *Wallet) Sync(url string, stopGap uint) (uint64, error) {
+	defer runtime.KeepAlive(self)
+	cUrl := C.CString(url)
+	defer C.free(unsafe.Pointer(cUrl))
+	var out C.uint64_t
+	if code := C.wallet_sync(self.ptr, cUrl, C.uintptr_t(stopGap), &out); code != 0 {
+		return 0, &Error{Code: int32(code)}
+	}
+	return uint64(out), nil
+}
+
+func (self *Wallet) Sign(psbt []byte) []byte {
+	defer runtime.KeepAlive(self)
+	cPsbt := C.Arr_u8{
+		ptr: (*C.uint8_t)(C.calloc(C.size_t(len(psbt))+1, C.size_t(unsafe.Sizeof(psbt[0])))),
+		len: C.uintptr_t(len(psbt)),
+	}
+	defer C.free(unsafe.Pointer(cPsbt.ptr))
+	copy(unsafe.Slice((*uint8)(unsafe.Pointer(cPsbt.ptr)), len(psbt)), psbt)
+	return goBytes(C.wallet_sign(self.ptr, cPsbt))
+}
+
+func Broadcast(url string, tx []byte) (string, error) {
+	cUrl := C.CString(url)
+	defer C.free(unsafe.Pointer(cUrl))
+	cTx := C.Arr_u8{
+		ptr: (*C.uint8_t)(C.calloc(C.size_t(len(tx))+1, C.size_t(unsafe.Sizeof(tx[0])))),
+		len: C.uintptr_t(len(tx)),
+	}
+	defer C.free(unsafe.Pointer(cTx.ptr))
+	copy(unsafe.Slice((*uint8)(unsafe.Pointer(cTx.ptr)), len(tx)), tx)
+	var out *C.char
+	if code := C.broadcast(cUrl, cTx, &out); code != 0 {
+		return "", &Error{Code: int32(code)}
+	}
+	return goString(out), nil
+}
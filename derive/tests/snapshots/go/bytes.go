@@ -0,0 +1,59 @@
+// Code generated by `#[expose_mod]`. DO NOT EDIT.
+
+package derive
+
+/*
+#cgo LDFLAGS: -lderive
+#include <stdint.h>
+#include <stdlib.h>
+#include "derive.h"
+*/
+import "C"
+
+import (
+	"runtime"
+	"unsafe"
+)
+
+// goString copies a string returned by the C API, and frees it
+func goString(s *C.char) string {
+	defer C.string_free(s)
+	return C.GoString(s)
+}
+
+// goBytes copies a vector returned by the C API, and frees it
+func goBytes(arr C.Arr_u8) []byte {
+	defer C.bytes_free(arr)
+	return C.GoBytes(unsafe.Pointer(arr.ptr), C.int(arr.len))
+}
+
+// Script owns a pointer to `Script`, freed when the struct is garbage collected
+type Script struct {
+	ptr *C.Script
+}
+
+// wrapScript takes the ownership of a pointer returned by the C API
+func wrapScript(ptr *C.Script) *Script {
+	obj := &Script{ptr: ptr}
+	runtime.SetFinalizer(obj, func(obj *Script) {
+		C.script_destroy(obj.ptr)
+	})
+	return obj
+}
+
+func (self *Script) ToBytes() []byte {
+	defer runtime.KeepAlive(self)
+	return goBytes(C.script_to_bytes(self.ptr))
+}
+
+func ScriptFromBytes(bytes []byte) *Script {
+	cBytes := C.Arr_u8{
+		ptr: (*C.uint8_t)(C.calloc(C.size_t(len(bytes))+1, C.size_t(unsafe.Sizeof(bytes[0])))),
+		len: C.uintptr_t(len(bytes)),
+	}
+	defer C.free(unsafe.Pointer(cBytes.ptr))
+	copy(unsafe.Slice((*uint8)(unsafe.Pointer(cBytes.ptr)), len(bytes)), bytes)
+	var out *C.Script
+	C.script_from_bytes(cBytes, &out)
+	return wrapScript(out)
+}
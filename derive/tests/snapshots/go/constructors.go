@@ -0,0 +1,77 @@
+// Code generated by `#[expose_mod]`. DO NOT EDIT.
+
+package derive
+
+/*
+#cgo LDFLAGS: -lderive
+#include <stdint.h>
+#include <stdlib.h>
+#include "derive.h"
+*/
+import "C"
+
+import (
+	"fmt"
+	"runtime"
+	"unsafe"
+)
+
+// Error is returned when the C API fails with an error code
+type Error struct {
+	Code int32
+}
+
+func (e *Error) Error() string {
+	return fmt.Sprintf("Error: error code %d", e.Code)
+}
+
+// goString copies a string returned by the C API, and frees it
+func goString(s *C.char) string {
+	defer C.string_free(s)
+	return C.GoString(s)
+}
+
+// goBytes copies a vector returned by the C API, and frees it
+func goBytes(arr C.Arr_u8) []byte {
+	defer C.bytes_free(arr)
+	return C.GoBytes(unsafe.Pointer(arr.ptr), C.int(arr.len))
+}
+
+// Network owns a pointer to `Network`, freed when the struct is garbage collected
+type Network struct {
+	ptr *C.Network
+}
+
+// wrapNetwork takes the ownership of a pointer returned by the C API
+func wrapNetwork(ptr *C.Network) *Network {
+	obj := &Network{ptr: ptr}
+	runtime.SetFinalizer(obj, func(obj *Network) {
+		C.network_destroy(obj.ptr)
+	})
+	return obj
+}
+
+func NewNetwork(s string) (*Network, error) {
+	cS := C.CString(s)
+	defer C.free(unsafe.Pointer(cS))
+	var out *C.Network
+	if code := C.network_new(cS, &out); code != 0 {
+		return nil, &Error{Code: int32(code)}
+	}
+	return wrapNetwork(out), nil
+}
+
+func NetworkBitcoin() *Network {
+	var out *C.Network
+	C.network_new_bitcoin(&out)
+	return wrapNetwork(out)
+}
+
+func NetworkFromMagic(magic uint32) *Network {
+	var out *C.Network
+	C.network_new_from_magic(C.uint32_t(magic), &out)
+	if out == nil {
+		return nil
+	}
+	return wrapNetwork(out)
+}
@@ -0,0 +1,75 @@
+// Code generated by `#[expose_mod]`. DO NOT EDIT.
+
+package derive
+
+/*
+#cgo LDFLAGS: -lderive
+#include <stdint.h>
+#include <stdlib.h>
+#include "derive.h"
+*/
+import "C"
+
+import (
+	"runtime"
+	"unsafe"
+)
+
+// goString copies a string returned by the C API, and frees it
+func goString(s *C.char) string {
+	defer C.string_free(s)
+	return C.GoString(s)
+}
+
+// goBytes copies a vector returned by the C API, and frees it
+func goBytes(arr C.Arr_u8) []byte {
+	defer C.bytes_free(arr)
+	return C.GoBytes(unsafe.Pointer(arr.ptr), C.int(arr.len))
+}
+
+// FeeRate owns a pointer to `FeeRate`, freed when the struct is garbage collected
+type FeeRate struct {
+	ptr *C.FeeRate
+}
+
+// wrapFeeRate takes the ownership of a pointer returned by the C API
+func wrapFeeRate(ptr *C.FeeRate) *FeeRate {
+	obj := &FeeRate{ptr: ptr}
+	runtime.SetFinalizer(obj, func(obj *FeeRate) {
+		C.fee_rate_destroy(obj.ptr)
+	})
+	return obj
+}
+
+func FeeRateFromSatPerVb(satPerVb float32) *FeeRate {
+	var out *C.FeeRate
+	C.fee_rate_from_sat_per_vb(C.float(satPerVb), &out)
+	return wrapFeeRate(out)
+}
+
+func FeeRateFromSatPerVbWithDefaults() *FeeRate {
+	var out *C.FeeRate
+	C.fee_rate_from_sat_per_vb_with_defaults(&out)
+	return wrapFeeRate(out)
+}
+
+func (self *FeeRate) FeeFor(vbytes uint64, min *FeeRate) uint64 {
+	defer runtime.KeepAlive(self)
+	defer runtime.KeepAlive(min)
+	return uint64(C.fee_rate_fee_for(self.ptr, C.uint64_t(vbytes), min.ptr))
+}
+
+func (self *FeeRate) FeeForWithDefaults(vbytes uint64) uint64 {
+	defer runtime.KeepAlive(self)
+	return uint64(C.fee_rate_fee_for_with_defaults(self.ptr, C.uint64_t(vbytes)))
+}
+
+func Greet(name string) string {
+	cName := C.CString(name)
+	defer C.free(unsafe.Pointer(cName))
+	return goString(C.greet(cName))
+}
+
+func GreetWithDefaults() string {
+	return goString(C.greet_with_defaults())
+}
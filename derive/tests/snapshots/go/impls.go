@@ -0,0 +1,71 @@
+// Code generated by `#[expose_mod]`. DO NOT EDIT.
+
+package derive
+
+/*
+#cgo LDFLAGS: -lderive
+#include <stdint.h>
+#include <stdlib.h>
+#include "derive.h"
+*/
+import "C"
+
+import (
+	"runtime"
+	"unsafe"
+)
+
+// goString copies a string returned by the C API, and frees it
+func goString(s *C.char) string {
+	defer C.string_free(s)
+	return C.GoString(s)
+}
+
+// goBytes copies a vector returned by the C API, and frees it
+func goBytes(arr C.Arr_u8) []byte {
+	defer C.bytes_free(arr)
+	return C.GoBytes(unsafe.Pointer(arr.ptr), C.int(arr.len))
+}
+
+// Counter owns a pointer to `Counter`, freed when the struct is garbage collected
+type Counter struct {
+	ptr *C.Counter
+}
+
+// wrapCounter takes the ownership of a pointer returned by the C API
+func wrapCounter(ptr *C.Counter) *Counter {
+	obj := &Counter{ptr: ptr}
+	runtime.SetFinalizer(obj, func(obj *Counter) {
+		C.counter_destroy(obj.ptr)
+	})
+	return obj
+}
+
+func NewCounter(start uint32) *Counter {
+	var out *C.Counter
+	C.counter_new(C.uint32_t(start), &out)
+	return wrapCounter(out)
+}
+
+func CounterZero() *Counter {
+	var out *C.Counter
+	C.counter_zero(&out)
+	return wrapCounter(out)
+}
+
+func (self *Counter) Get() uint32 {
+	defer runtime.KeepAlive(self)
+	return uint32(C.counter_get(self.ptr))
+}
+
+func (self *Counter) Increment(by uint32) {
+	defer runtime.KeepAlive(self)
+	C.counter_increment(self.ptr, C.uint32_t(by))
+}
+
+func (self *Counter) Label(prefix string) string {
+	defer runtime.KeepAlive(self)
+	cPrefix := C.CString(prefix)
+	defer C.free(unsafe.Pointer(cPrefix))
+	return goString(C.counter_label(self.ptr, cPrefix))
+}
@@ -0,0 +1,64 @@
+// Code generated by `#[expose_mod]`. DO NOT EDIT.
+
+package derive
+
+/*
+#cgo LDFLAGS: -lderive
+#include <stdint.h>
+#include <stdlib.h>
+#include "derive.h"
+*/
+import "C"
+
+import (
+	"runtime"
+	"unsafe"
+)
+
+// goString copies a string returned by the C API, and frees it
+func goString(s *C.char) string {
+	defer C.string_free(s)
+	return C.GoString(s)
+}
+
+// goBytes copies a vector returned by the C API, and frees it
+func goBytes(arr C.Arr_u8) []byte {
+	defer C.bytes_free(arr)
+	return C.GoBytes(unsafe.Pointer(arr.ptr), C.int(arr.len))
+}
+
+// InnerThing owns a pointer to `Thing`, released when the struct is garbage collected
+type InnerThing struct {
+	ptr *C.Thing
+}
+
+// wrapInnerThing takes the ownership of a pointer returned by the C API
+func wrapInnerThing(ptr *C.Thing) *InnerThing {
+	obj := &InnerThing{ptr: ptr}
+	runtime.SetFinalizer(obj, func(obj *InnerThing) {
+		C.outer_inner_thing_release(obj.ptr)
+	})
+	return obj
+}
+
+func InnerThingNew() *InnerThing {
+	var out *C.Thing
+	C.outer_inner_thing_new(&out)
+	return wrapInnerThing(out)
+}
+
+func Version() uint32 {
+	return uint32(C.version())
+}
+
+func InnerCount() uint32 {
+	return uint32(C.outer_inner_count())
+}
+
+func PrefixedCount() uint32 {
+	return uint32(C.ext_count())
+}
+
+func PrefixedNestedCount() uint32 {
+	return uint32(C.ext_outer_prefixed_nested_count())
+}
@@ -0,0 +1,108 @@
+// Code generated by `#[expose_mod]`. DO NOT EDIT.
+
+package derive
+
+/*
+#cgo LDFLAGS: -lderive
+#include <stdint.h>
+#include <stdlib.h>
+#include "derive.h"
+*/
+import "C"
+
+import (
+	"fmt"
+	"runtime"
+	"unsafe"
+)
+
+// Error is returned when the C API fails with an error code
+type Error struct {
+	Code int32
+}
+
+func (e *Error) Error() string {
+	return fmt.Sprintf("Error: error code %d", e.Code)
+}
+
+// IndexError is returned when the C API fails with an error code
+type IndexError struct {
+	Code int32
+}
+
+func (e *IndexError) Error() string {
+	return fmt.Sprintf("IndexError: error code %d", e.Code)
+}
+
+// goString copies a string returned by the C API, and frees it
+func goString(s *C.char) string {
+	defer C.string_free(s)
+	return C.GoString(s)
+}
+
+// goBytes copies a vector returned by the C API, and frees it
+func goBytes(arr C.Arr_u8) []byte {
+	defer C.bytes_free(arr)
+	return C.GoBytes(unsafe.Pointer(arr.ptr), C.int(arr.len))
+}
+
+// Amount owns a pointer to `Amount`, freed when the struct is garbage collected
+type Amount struct {
+	ptr *C.Amount
+}
+
+// wrapAmount takes the ownership of a pointer returned by the C API
+func wrapAmount(ptr *C.Amount) *Amount {
+	obj := &Amount{ptr: ptr}
+	runtime.SetFinalizer(obj, func(obj *Amount) {
+		C.amount_destroy(obj.ptr)
+	})
+	return obj
+}
+
+func (self *Amount) Add(rhs *Amount) (*Amount, error) {
+	defer runtime.KeepAlive(self)
+	defer runtime.KeepAlive(rhs)
+	var out *C.Amount
+	if code := C.amount_add(self.ptr, rhs.ptr, &out); code != 0 {
+		return nil, &Error{Code: int32(code)}
+	}
+	return wrapAmount(out), nil
+}
+
+func (self *Amount) Mul(rhs uint64) *Amount {
+	defer runtime.KeepAlive(self)
+	var out *C.Amount
+	C.amount_mul(self.ptr, C.uint64_t(rhs), &out)
+	return wrapAmount(out)
+}
+
+func (self *Amount) Neg() *Amount {
+	defer runtime.KeepAlive(self)
+	var out *C.Amount
+	C.amount_neg(self.ptr, &out)
+	return wrapAmount(out)
+}
+
+// Script owns a pointer to `Script`, freed when the struct is garbage collected
+type Script struct {
+	ptr *C.Script
+}
+
+// wrapScript takes the ownership of a pointer returned by the C API
+func wrapScript(ptr *C.Script) *Script {
+	obj := &Script{ptr: ptr}
+	runtime.SetFinalizer(obj, func(obj *Script) {
+		C.script_destroy(obj.ptr)
+	})
+	return obj
+}
+
+func (self *Script) Index(rhs uint) (uint8, error) {
+	defer runtime.KeepAlive(self)
+	var out C.uint8_t
+	if code := C.script_index(self.ptr, C.uintptr_t(rhs), &out); code != 0 {
+		return 0, &IndexError{Code: int32(code)}
+	}
+	return uint8(out), nil
+}
@@ -0,0 +1,95 @@
+// Code generated by `#[expose_mod]`. DO NOT EDIT.
+
+package derive
+
+/*
+#cgo LDFLAGS: -lderive
+#include <stdint.h>
+#include <stdlib.h>
+#include "derive.h"
+*/
+import "C"
+
+import (
+	"fmt"
+	"runtime"
+	"unsafe"
+)
+
+// Error is returned when the C API fails with an error code
+type Error struct {
+	Code int32
+}
+
+func (e *Error) Error() string {
+	return fmt.Sprintf("Error: error code %d", e.Code)
+}
+
+// goString copies a string returned by the C API, and frees it
+func goString(s *C.char) string {
+	defer C.string_free(s)
+	return C.GoString(s)
+}
+
+// goBytes copies a vector returned by the C API, and frees it
+func goBytes(arr C.Arr_u8) []byte {
+	defer C.bytes_free(arr)
+	return C.GoBytes(unsafe.Pointer(arr.ptr), C.int(arr.len))
+}
+
+// Item owns a pointer to `Item`, freed when the struct is garbage collected
+type Item struct {
+	ptr *C.Item
+}
+
+// wrapItem takes the ownership of a pointer returned by the C API
+func wrapItem(ptr *C.Item) *Item {
+	obj := &Item{ptr: ptr}
+	runtime.SetFinalizer(obj, func(obj *Item) {
+		C.item_destroy(obj.ptr)
+	})
+	return obj
+}
+
+func ItemParse(s string) (*Item, error) {
+	cS := C.CString(s)
+	defer C.free(unsafe.Pointer(cS))
+	var out *C.Item
+	if code := C.item_parse(cS, &out); code != 0 {
+		return nil, &Error{Code: int32(code)}
+	}
+	return wrapItem(out), nil
+}
+
+func (self *Item) Check() error {
+	defer runtime.KeepAlive(self)
+	if code := C.item_check(self.ptr); code != 0 {
+		return &Error{Code: int32(code)}
+	}
+	return nil
+}
+
+func ItemFind(name string) *Item {
+	cName := C.CString(name)
+	defer C.free(unsafe.Pointer(cName))
+	var out *C.Item
+	C.item_find(cName, &out)
+	if out == nil {
+		return nil
+	}
+	return wrapItem(out)
+}
+
+func (self *Item) ToBytes() []byte {
+	defer runtime.KeepAlive(self)
+	return goBytes(C.item_to_bytes(self.ptr))
+}
+
+func (self *Item) Name() string {
+	defer runtime.KeepAlive(self)
+	return goString(C.item_name(self.ptr))
+}
+
+func LastError() int32 {
+	return int32(C.last_error())
+}
@@ -0,0 +1,154 @@
+// Code generated by `#[expose_mod]`. DO NOT EDIT.
+
+package derive
+
+/*
+#cgo LDFLAGS: -lderive
+#include <stdint.h>
+#include <stdlib.h>
+#include "derive.h"
+*/
+import "C"
+
+import (
+	"fmt"
+	"runtime"
+	"unsafe"
+)
+
+// ValidationError is returned when the C API fails with an error code
+type ValidationError struct {
+	Code int32
+}
+
+func (e *ValidationError) Error() string {
+	return fmt.Sprintf("ValidationError: error code %d", e.Code)
+}
+
+// goString copies a string returned by the C API, and frees it
+func goString(s *C.char) string {
+	defer C.string_free(s)
+	return C.GoString(s)
+}
+
+// goBytes copies a vector returned by the C API, and frees it
+func goBytes(arr C.Arr_u8) []byte {
+	defer C.bytes_free(arr)
+	return C.GoBytes(unsafe.Pointer(arr.ptr), C.int(arr.len))
+}
+
+// Opaque owns a pointer to `Opaque`, freed when the struct is garbage collected
+type Opaque struct {
+	ptr *C.Opaque
+}
+
+// wrapOpaque takes the ownership of a pointer returned by the C API
+func wrapOpaque(ptr *C.Opaque) *Opaque {
+	obj := &Opaque{ptr: ptr}
+	runtime.SetFinalizer(obj, func(obj *Opaque) {
+		C.opaque_destroy(obj.ptr)
+	})
+	return obj
+}
+
+// Shared owns a pointer to `Shared`, released when the struct is garbage collected
+type Shared struct {
+	ptr *C.Shared
+}
+
+// wrapShared takes the ownership of a pointer returned by the C API
+func wrapShared(ptr *C.Shared) *Shared {
+	obj := &Shared{ptr: ptr}
+	runtime.SetFinalizer(obj, func(obj *Shared) {
+		C.shared_release(obj.ptr)
+	})
+	return obj
+}
+
+// WithFields owns a pointer to `WithFields`, freed when the struct is garbage collected
+type WithFields struct {
+	ptr *C.WithFields
+}
+
+// wrapWithFields takes the ownership of a pointer returned by the C API
+func wrapWithFields(ptr *C.WithFields) *WithFields {
+	obj := &WithFields{ptr: ptr}
+	runtime.SetFinalizer(obj, func(obj *WithFields) {
+		C.with_fields_destroy(obj.ptr)
+	})
+	return obj
+}
+
+func (self *WithFields) Shared() *Shared {
+	defer runtime.KeepAlive(self)
+	return wrapShared(C.with_fields_get_shared(self.ptr))
+}
+
+func (self *WithFields) SetShared(value *Shared) {
+	defer runtime.KeepAlive(self)
+	defer runtime.KeepAlive(value)
+	C.with_fields_set_shared(self.ptr, value.ptr)
+}
+
+func (self *WithFields) Value() uint32 {
+	defer runtime.KeepAlive(self)
+	return uint32(C.with_fields_get_value(self.ptr))
+}
+
+func (self *WithFields) SetValue(value uint32) error {
+	defer runtime.KeepAlive(self)
+	if code := C.with_fields_set_value(self.ptr, C.uint32_t(value)); code != 0 {
+		return &ValidationError{Code: int32(code)}
+	}
+	return nil
+}
+
+// Point is a copy of `Point`
+type Point struct {
+	X int32
+	Y int32
+}
+
+func (v Point) toC() C.Point {
+	return C.Point{x: C.int32_t(v.X), y: C.int32_t(v.Y)}
+}
+
+func wrapPoint(v C.Point) Point {
+	return Point{X: int32(v.x), Y: int32(v.y)}
+}
+
+// Script owns a pointer to `Script`, freed when the struct is garbage collected
+type Script struct {
+	ptr *C.Script
+}
+
+// wrapScript takes the ownership of a pointer returned by the C API
+func wrapScript(ptr *C.Script) *Script {
+	obj := &Script{ptr: ptr}
+	runtime.SetFinalizer(obj, func(obj *Script) {
+		C.script_destroy(obj.ptr)
+	})
+	return obj
+}
+
+// WithOpaque owns a pointer to `WithOpaque`, freed when the struct is garbage collected
+type WithOpaque struct {
+	ptr *C.WithOpaque
+}
+
+// wrapWithOpaque takes the ownership of a pointer returned by the C API
+func wrapWithOpaque(ptr *C.WithOpaque) *WithOpaque {
+	obj := &WithOpaque{ptr: ptr}
+	runtime.SetFinalizer(obj, func(obj *WithOpaque) {
+		C.with_opaque_destroy(obj.ptr)
+	})
+	return obj
+}
+
+// `WithOpaque::script` is not available: unsupported argument or return types
+
+func (self *WithOpaque) SetScript(value *Script) {
+	defer runtime.KeepAlive(self)
+	defer runtime.KeepAlive(value)
+	C.with_opaque_set_script(self.ptr, value.ptr)
+}
@@ -0,0 +1,91 @@
+// ---- derive_traits.go ----
+// Code generated by `#[expose_mod]`. DO NOT EDIT.
+
+package derive
+
+/*
+#cgo LDFLAGS: -lderive
+#include <stdint.h>
+#include <stdlib.h>
+#include "derive.h"
+
+// Handles are passed as the `this` pointer of the trait structs
+static inline void *go_handle_ptr(uintptr_t handle) { return (void *)handle; }
+
+extern void go_traits_release_handle(void *);
+extern uint32_t go_listener_struct_on_event(void *, char *);
+*/
+import "C"
+
+import (
+	"runtime"
+	"runtime/cgo"
+	"unsafe"
+)
+
+// goString copies a string returned by the C API, and frees it
+func goString(s *C.char) string {
+	defer C.string_free(s)
+	return C.GoString(s)
+}
+
+// goBytes copies a vector returned by the C API, and frees it
+func goBytes(arr C.Arr_u8) []byte {
+	defer C.bytes_free(arr)
+	return C.GoBytes(unsafe.Pointer(arr.ptr), C.int(arr.len))
+}
+
+// ListenerStruct owns a pointer to `ListenerStruct`, freed when the struct is garbage collected
+type ListenerStruct struct {
+	ptr *C.ListenerStruct
+}
+
+// wrapListenerStruct takes the ownership of a pointer returned by the C API
+func wrapListenerStruct(ptr *C.ListenerStruct) *ListenerStruct {
+	obj := &ListenerStruct{ptr: ptr}
+	runtime.SetFinalizer(obj, func(obj *ListenerStruct) {
+		C.listener_struct_destroy(obj.ptr)
+	})
+	return obj
+}
+
+// Listener is implemented by the Go values passed to NewListenerStruct
+type Listener interface {
+	OnEvent(event string) uint32
+}
+
+// NewListenerStruct wraps `impl` in a `ListenerStruct`, the handle to `impl` is deleted when
+// the struct is freed
+func NewListenerStruct(impl Listener) *ListenerStruct {
+	handle := C.go_handle_ptr(C.uintptr_t(cgo.NewHandle(impl)))
+	var out *C.ListenerStruct
+	C.listener_struct_new(handle, (*[0]byte)(C.go_traits_release_handle), (*[0]byte)(C.go_listener_struct_on_event), &out)
+	return wrapListenerStruct(out)
+}
+
+// ---- derive_traits_callbacks.go ----
+// Code generated by `#[expose_mod]`. DO NOT EDIT.
+
+package derive
+
+/*
+#include <stdint.h>
+#include "derive.h"
+*/
+import "C"
+
+import (
+	"runtime/cgo"
+	"unsafe"
+)
+
+//export go_traits_release_handle
+func go_traits_release_handle(this unsafe.Pointer) {
+	cgo.Handle(uintptr(this)).Delete()
+}
+
+//export go_listener_struct_on_event
+func go_listener_struct_on_event(this unsafe.Pointer, event *C.char) C.uint32_t {
+	impl := cgo.Handle(uintptr(this)).Value().(Listener)
+	return C.uint32_t(impl.OnEvent(goString(event)))
+}
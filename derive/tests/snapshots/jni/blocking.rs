@@ -0,0 +1,141 @@
+pub mod blocking {
+    use super::Error;
+    #[derive(Clone)]
+    pub struct Wallet {
+        inner: super::Wallet,
+    }
+    impl Wallet {
+        fn new(descriptor: String) -> Self {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            super::Wallet::new(&descriptor).into()
+        }
+        fn sync(&self, url: String, stop_gap: usize) -> Result<u64, Error> {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            self.inner.sync(&url, stop_gap)
+        }
+        fn sign(&self, psbt: &[u8]) -> Vec<u8> {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            self.inner.sign(psbt)
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_blocking_Native_walletNew<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+            descriptor: ::jni::objects::JString<'a>,
+        ) -> ::jni::sys::jlong {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            jni_call(
+                env,
+                move || {
+                    let descriptor: String = FromJni::from_jni(env, descriptor)?;
+                    let __output = Self::new(descriptor);
+                    Ok(into_handle(__output))
+                },
+            )
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_blocking_Native_walletSync<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+            this: ::jni::sys::jlong,
+            url: ::jni::objects::JString<'a>,
+            stop_gap: ::jni::sys::jlong,
+        ) -> ::jni::sys::jlong {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            jni_call(
+                env,
+                move || {
+                    let this = unsafe { borrow_handle::<Self>(this)? };
+                    let url: String = FromJni::from_jni(env, url)?;
+                    let stop_gap: usize = FromJni::from_jni(env, stop_gap)?;
+                    let __output = Self::sync(this, url, stop_gap)
+                        .map_err(|e| JniThrow::new(
+                            "blocking/Error",
+                            format!("{:?}", e),
+                        ))?;
+                    __output.into_jni(env)
+                },
+            )
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_blocking_Native_walletSign<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+            this: ::jni::sys::jlong,
+            psbt: ::jni::sys::jbyteArray,
+        ) -> ::jni::sys::jbyteArray {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            jni_call(
+                env,
+                move || {
+                    let this = unsafe { borrow_handle::<Self>(this)? };
+                    let psbt: Vec<u8> = FromJni::from_jni(env, psbt)?;
+                    let psbt = &psbt;
+                    let __output = Self::sign(this, psbt);
+                    __output.into_jni(env)
+                },
+            )
+        }
+    }
+    fn broadcast(url: String, tx: Vec<u8>) -> Result<String, Error> {
+        #[allow(unused_imports)]
+        use crate::mapping::{MapFrom, MapTo};
+        super::broadcast(&url, &tx)
+    }
+    impl crate::langs::ExposedPtr for Wallet {
+        type Kind = crate::langs::Boxed;
+    }
+    impl Wallet {
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_blocking_Native_walletDestroy<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+            handle: ::jni::sys::jlong,
+        ) {
+            crate::langs::jni_call(
+                env,
+                move || {
+                    unsafe { crate::langs::drop_handle::<Self>(handle) };
+                    Ok(())
+                },
+            )
+        }
+    }
+    impl Wallet {}
+    impl crate::common::ExposedStruct for Wallet {}
+    #[no_mangle]
+    #[allow(non_snake_case)]
+    pub extern "system" fn Java_blocking_Native_broadcast<'a>(
+        env: ::jni::JNIEnv<'a>,
+        _class: ::jni::objects::JClass<'a>,
+        url: ::jni::objects::JString<'a>,
+        tx: ::jni::sys::jbyteArray,
+    ) -> ::jni::sys::jstring {
+        #[allow(unused_imports)]
+        use crate::mapping::{MapFrom, MapTo};
+        use crate::langs::*;
+        jni_call(
+            env,
+            move || {
+                let url: String = FromJni::from_jni(env, url)?;
+                let tx: Vec<u8> = FromJni::from_jni(env, tx)?;
+                let __output = self::broadcast(url, tx)
+                    .map_err(|e| JniThrow::new("blocking/Error", format!("{:?}", e)))?;
+                __output.into_jni(env)
+            },
+        )
+    }
+}
@@ -0,0 +1,97 @@
+pub mod bytes {
+    pub struct Script {
+        inner: super::Script,
+    }
+    impl Script {}
+    impl crate::common::ByteContainer for Script {
+        fn as_bytes(&self) -> &[u8] {
+            self.inner.as_bytes()
+        }
+        fn from_bytes(bytes: Vec<u8>) -> Self {
+            Script { inner: bytes.into() }
+        }
+    }
+    impl crate::langs::ExposedPtr for Script {
+        type Kind = crate::langs::Boxed;
+    }
+    impl Script {
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_bytes_Native_scriptDestroy<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+            handle: ::jni::sys::jlong,
+        ) {
+            crate::langs::jni_call(
+                env,
+                move || {
+                    unsafe { crate::langs::drop_handle::<Self>(handle) };
+                    Ok(())
+                },
+            )
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_bytes_Native_scriptToString<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+            this: ::jni::sys::jlong,
+        ) -> ::jni::sys::jstring {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            jni_call(
+                env,
+                move || {
+                    let this = unsafe { borrow_handle::<Self>(this)? };
+                    let __output = Self::to_string(this);
+                    __output.into_jni(env)
+                },
+            )
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_bytes_Native_scriptToBytes<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+            this: ::jni::sys::jlong,
+        ) -> ::jni::sys::jbyteArray {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            jni_call(
+                env,
+                move || {
+                    let this = unsafe { borrow_handle::<Self>(this)? };
+                    let __output = (|this: &Self| {
+                        crate::common::ByteContainer::as_bytes(this).to_vec()
+                    })(this);
+                    __output.into_jni(env)
+                },
+            )
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_bytes_Native_scriptFromBytes<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+            bytes: ::jni::sys::jbyteArray,
+        ) -> ::jni::sys::jlong {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            jni_call(
+                env,
+                move || {
+                    let bytes: Vec<u8> = FromJni::from_jni(env, bytes)?;
+                    let __output = <Self as crate::common::ByteContainer>::from_bytes(
+                        bytes,
+                    );
+                    Ok(into_handle(__output))
+                },
+            )
+        }
+    }
+    impl Script {}
+    impl crate::common::ExposedStruct for Script {}
+}
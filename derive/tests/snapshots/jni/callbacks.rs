@@ -0,0 +1,12 @@
+pub mod callbacks {
+    fn apply(value: u32, cb: fn(value: u32) -> u32) -> u32 {
+        #[allow(unused_imports)]
+        use crate::mapping::{MapFrom, MapTo};
+        cb(value)
+    }
+    fn describe(cb: fn(name: String, value: u32)) {
+        #[allow(unused_imports)]
+        use crate::mapping::{MapFrom, MapTo};
+        cb("value".to_string(), 42)
+    }
+}
@@ -0,0 +1,104 @@
+pub mod constructors {
+    use super::Error;
+    pub struct Network {
+        inner: super::Network,
+    }
+    impl Network {
+        fn from_string(s: String) -> Result<Self, Error> {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            Ok(s.parse::<super::Network>()?.into())
+        }
+        fn bitcoin() -> Self {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            super::Network::Bitcoin.into()
+        }
+        fn from_magic(magic: u32) -> Option<Self> {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            super::Network::from_magic(magic).map(Into::into)
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_constructors_Native_networkNew<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+            s: ::jni::objects::JString<'a>,
+        ) -> ::jni::sys::jlong {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            jni_call(
+                env,
+                move || {
+                    let s: String = FromJni::from_jni(env, s)?;
+                    let __output = Self::from_string(s)
+                        .map_err(|e| JniThrow::new(
+                            "constructors/Error",
+                            format!("{:?}", e),
+                        ))?;
+                    Ok(into_handle(__output))
+                },
+            )
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_constructors_Native_networkNewBitcoin<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+        ) -> ::jni::sys::jlong {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            jni_call(
+                env,
+                move || {
+                    let __output = Self::bitcoin();
+                    Ok(into_handle(__output))
+                },
+            )
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_constructors_Native_networkNewFromMagic<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+            magic: ::jni::sys::jint,
+        ) -> ::jni::sys::jlong {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            jni_call(
+                env,
+                move || {
+                    let magic: u32 = FromJni::from_jni(env, magic)?;
+                    let __output = Self::from_magic(magic);
+                    Ok(__output.map(into_handle).unwrap_or(0))
+                },
+            )
+        }
+    }
+    impl crate::langs::ExposedPtr for Network {
+        type Kind = crate::langs::Boxed;
+    }
+    impl Network {
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_constructors_Native_networkDestroy<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+            handle: ::jni::sys::jlong,
+        ) {
+            crate::langs::jni_call(
+                env,
+                move || {
+                    unsafe { crate::langs::drop_handle::<Self>(handle) };
+                    Ok(())
+                },
+            )
+        }
+    }
+    impl Network {}
+    impl crate::common::ExposedStruct for Network {}
+}
@@ -0,0 +1,163 @@
+pub mod defaults {
+    pub struct FeeRate {
+        inner: super::FeeRate,
+    }
+    impl FeeRate {
+        fn from_sat_per_vb(sat_per_vb: f32) -> Self {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            super::FeeRate::from_sat_per_vb(sat_per_vb).into()
+        }
+        fn fee_for(&self, vbytes: u64, min: &FeeRate) -> u64 {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            self.inner.fee_for(vbytes).max(min.inner.fee_for(vbytes))
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_defaults_Native_feeRateFromSatPerVb<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+            sat_per_vb: ::jni::sys::jfloat,
+        ) -> ::jni::sys::jlong {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            jni_call(
+                env,
+                move || {
+                    let sat_per_vb: f32 = FromJni::from_jni(env, sat_per_vb)?;
+                    let __output = Self::from_sat_per_vb(sat_per_vb);
+                    Ok(into_handle(__output))
+                },
+            )
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_defaults_Native_feeRateFromSatPerVbWithDefaults<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+        ) -> ::jni::sys::jlong {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            jni_call(
+                env,
+                move || {
+                    let sat_per_vb: f32 = 1.0;
+                    let __output = Self::from_sat_per_vb(sat_per_vb);
+                    Ok(into_handle(__output))
+                },
+            )
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_defaults_Native_feeRateFeeFor<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+            this: ::jni::sys::jlong,
+            vbytes: ::jni::sys::jlong,
+            min: ::jni::sys::jlong,
+        ) -> ::jni::sys::jlong {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            jni_call(
+                env,
+                move || {
+                    let this = unsafe { borrow_handle::<Self>(this)? };
+                    let vbytes: u64 = FromJni::from_jni(env, vbytes)?;
+                    let min = unsafe { borrow_handle::<FeeRate>(min)? };
+                    let __output = Self::fee_for(this, vbytes, min);
+                    __output.into_jni(env)
+                },
+            )
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_defaults_Native_feeRateFeeForWithDefaults<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+            this: ::jni::sys::jlong,
+            vbytes: ::jni::sys::jlong,
+        ) -> ::jni::sys::jlong {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            jni_call(
+                env,
+                move || {
+                    let this = unsafe { borrow_handle::<Self>(this)? };
+                    let vbytes: u64 = FromJni::from_jni(env, vbytes)?;
+                    let min: &FeeRate = &FeeRate::default();
+                    let __output = Self::fee_for(this, vbytes, min);
+                    __output.into_jni(env)
+                },
+            )
+        }
+    }
+    fn greet(name: String) -> String {
+        #[allow(unused_imports)]
+        use crate::mapping::{MapFrom, MapTo};
+        format!("Hello {}", name)
+    }
+    impl crate::langs::ExposedPtr for FeeRate {
+        type Kind = crate::langs::Boxed;
+    }
+    impl FeeRate {
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_defaults_Native_feeRateDestroy<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+            handle: ::jni::sys::jlong,
+        ) {
+            crate::langs::jni_call(
+                env,
+                move || {
+                    unsafe { crate::langs::drop_handle::<Self>(handle) };
+                    Ok(())
+                },
+            )
+        }
+    }
+    impl FeeRate {}
+    impl crate::common::ExposedStruct for FeeRate {}
+    #[no_mangle]
+    #[allow(non_snake_case)]
+    pub extern "system" fn Java_defaults_Native_greet<'a>(
+        env: ::jni::JNIEnv<'a>,
+        _class: ::jni::objects::JClass<'a>,
+        name: ::jni::objects::JString<'a>,
+    ) -> ::jni::sys::jstring {
+        #[allow(unused_imports)]
+        use crate::mapping::{MapFrom, MapTo};
+        use crate::langs::*;
+        jni_call(
+            env,
+            move || {
+                let name: String = FromJni::from_jni(env, name)?;
+                let __output = self::greet(name);
+                __output.into_jni(env)
+            },
+        )
+    }
+    #[no_mangle]
+    #[allow(non_snake_case)]
+    pub extern "system" fn Java_defaults_Native_greetWithDefaults<'a>(
+        env: ::jni::JNIEnv<'a>,
+        _class: ::jni::objects::JClass<'a>,
+    ) -> ::jni::sys::jstring {
+        #[allow(unused_imports)]
+        use crate::mapping::{MapFrom, MapTo};
+        use crate::langs::*;
+        jni_call(
+            env,
+            move || {
+                let name: String = "world".to_string();
+                let __output = self::greet(name);
+                __output.into_jni(env)
+            },
+        )
+    }
+}
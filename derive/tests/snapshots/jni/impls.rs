@@ -0,0 +1,151 @@
+pub mod impls {
+    pub struct Counter {
+        inner: super::Counter,
+    }
+    impl Counter {
+        fn new(start: u32) -> Self {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            super::Counter::new(start).into()
+        }
+        fn zero() -> Self {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            super::Counter::new(0).into()
+        }
+        fn get(&self) -> u32 {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            self.inner.get()
+        }
+        fn increment(&mut self, by: u32) {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            self.inner.increment(by)
+        }
+        fn label(&self, prefix: String) -> String {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            format!("{}{}", prefix, self.inner.get())
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_impls_Native_counterNew<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+            start: ::jni::sys::jint,
+        ) -> ::jni::sys::jlong {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            jni_call(
+                env,
+                move || {
+                    let start: u32 = FromJni::from_jni(env, start)?;
+                    let __output = Self::new(start);
+                    Ok(into_handle(__output))
+                },
+            )
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_impls_Native_counterZero<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+        ) -> ::jni::sys::jlong {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            jni_call(
+                env,
+                move || {
+                    let __output = Self::zero();
+                    Ok(into_handle(__output))
+                },
+            )
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_impls_Native_counterGet<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+            this: ::jni::sys::jlong,
+        ) -> ::jni::sys::jint {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            jni_call(
+                env,
+                move || {
+                    let this = unsafe { borrow_handle::<Self>(this)? };
+                    let __output = Self::get(this);
+                    __output.into_jni(env)
+                },
+            )
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_impls_Native_counterIncrement<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+            this: ::jni::sys::jlong,
+            by: ::jni::sys::jint,
+        ) {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            jni_call(
+                env,
+                move || {
+                    let this = unsafe { borrow_handle_mut::<Self>(this)? };
+                    let by: u32 = FromJni::from_jni(env, by)?;
+                    let __output = Self::increment(this, by);
+                    Ok(__output)
+                },
+            )
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_impls_Native_counterLabel<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+            this: ::jni::sys::jlong,
+            prefix: ::jni::objects::JString<'a>,
+        ) -> ::jni::sys::jstring {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            jni_call(
+                env,
+                move || {
+                    let this = unsafe { borrow_handle::<Self>(this)? };
+                    let prefix: String = FromJni::from_jni(env, prefix)?;
+                    let __output = Self::label(this, prefix);
+                    __output.into_jni(env)
+                },
+            )
+        }
+    }
+    impl crate::langs::ExposedPtr for Counter {
+        type Kind = crate::langs::Boxed;
+    }
+    impl Counter {
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_impls_Native_counterDestroy<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+            handle: ::jni::sys::jlong,
+        ) {
+            crate::langs::jni_call(
+                env,
+                move || {
+                    unsafe { crate::langs::drop_handle::<Self>(handle) };
+                    Ok(())
+                },
+            )
+        }
+    }
+    impl Counter {}
+    impl crate::common::ExposedStruct for Counter {}
+}
@@ -0,0 +1,145 @@
+pub mod outer {
+    fn version() -> u32 {
+        #[allow(unused_imports)]
+        use crate::mapping::{MapFrom, MapTo};
+        1
+    }
+    pub mod inner {
+        pub struct Thing {
+            inner: super::Thing,
+        }
+        impl Thing {
+            fn new() -> Self {
+                #[allow(unused_imports)]
+                use crate::mapping::{MapFrom, MapTo};
+                super::Thing::default().into()
+            }
+            #[no_mangle]
+            #[allow(non_snake_case)]
+            pub extern "system" fn Java_outer_inner_Native_thingNew<'a>(
+                env: ::jni::JNIEnv<'a>,
+                _class: ::jni::objects::JClass<'a>,
+            ) -> ::jni::sys::jlong {
+                #[allow(unused_imports)]
+                use crate::mapping::{MapFrom, MapTo};
+                use crate::langs::*;
+                jni_call(
+                    env,
+                    move || {
+                        let __output = Self::new();
+                        Ok(into_handle(__output))
+                    },
+                )
+            }
+        }
+        fn count() -> u32 {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            0
+        }
+        impl crate::langs::ExposedPtr for Thing {
+            type Kind = crate::langs::Shared;
+        }
+        impl Thing {
+            #[no_mangle]
+            #[allow(non_snake_case)]
+            pub extern "system" fn Java_outer_inner_Native_thingDestroy<'a>(
+                env: ::jni::JNIEnv<'a>,
+                _class: ::jni::objects::JClass<'a>,
+                handle: ::jni::sys::jlong,
+            ) {
+                crate::langs::jni_call(
+                    env,
+                    move || {
+                        unsafe { crate::langs::drop_handle::<Self>(handle) };
+                        Ok(())
+                    },
+                )
+            }
+        }
+        impl Thing {}
+        impl crate::common::ExposedStruct for Thing {}
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_outer_inner_Native_count<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+        ) -> ::jni::sys::jint {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            jni_call(
+                env,
+                move || {
+                    let __output = self::count();
+                    __output.into_jni(env)
+                },
+            )
+        }
+    }
+    pub mod prefixed {
+        fn count() -> u32 {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            0
+        }
+        pub mod nested {
+            fn count() -> u32 {
+                #[allow(unused_imports)]
+                use crate::mapping::{MapFrom, MapTo};
+                0
+            }
+            #[no_mangle]
+            #[allow(non_snake_case)]
+            pub extern "system" fn Java_outer_prefixed_nested_Native_count<'a>(
+                env: ::jni::JNIEnv<'a>,
+                _class: ::jni::objects::JClass<'a>,
+            ) -> ::jni::sys::jint {
+                #[allow(unused_imports)]
+                use crate::mapping::{MapFrom, MapTo};
+                use crate::langs::*;
+                jni_call(
+                    env,
+                    move || {
+                        let __output = self::count();
+                        __output.into_jni(env)
+                    },
+                )
+            }
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_outer_prefixed_Native_count<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+        ) -> ::jni::sys::jint {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            jni_call(
+                env,
+                move || {
+                    let __output = self::count();
+                    __output.into_jni(env)
+                },
+            )
+        }
+    }
+    #[no_mangle]
+    #[allow(non_snake_case)]
+    pub extern "system" fn Java_outer_Native_version<'a>(
+        env: ::jni::JNIEnv<'a>,
+        _class: ::jni::objects::JClass<'a>,
+    ) -> ::jni::sys::jint {
+        #[allow(unused_imports)]
+        use crate::mapping::{MapFrom, MapTo};
+        use crate::langs::*;
+        jni_call(
+            env,
+            move || {
+                let __output = self::version();
+                __output.into_jni(env)
+            },
+        )
+    }
+}
@@ -0,0 +1,198 @@
+pub mod operators {
+    use super::Error;
+    pub struct Amount {
+        inner: super::Amount,
+    }
+    impl Amount {}
+    impl std::ops::Add for Amount {
+        type Output = Result<Amount, Error>;
+        fn add(self, rhs: Self) -> Self::Output {
+            Ok(self.inner.checked_add(rhs.inner)?.into())
+        }
+    }
+    impl std::ops::Mul<u64> for Amount {
+        type Output = Amount;
+        fn mul(self, rhs: u64) -> Self::Output {
+            (self.inner * rhs).into()
+        }
+    }
+    impl std::ops::Neg for Amount {
+        type Output = Amount;
+        fn neg(self) -> Self::Output {
+            (-self.inner).into()
+        }
+    }
+    pub struct Script {
+        inner: super::Script,
+    }
+    impl Script {}
+    impl std::ops::Index<usize> for Script {
+        type Output = u8;
+        fn index(&self, index: usize) -> &Self::Output {
+            &self.inner.as_bytes()[index]
+        }
+    }
+    impl crate::common::CheckedIndex<usize> for Script {
+        fn checked_index(&self, index: usize) -> Option<&u8> {
+            self.inner.as_bytes().get(index)
+        }
+    }
+    impl crate::langs::ExposedPtr for Amount {
+        type Kind = crate::langs::Boxed;
+    }
+    impl Amount {
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_operators_Native_amountDestroy<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+            handle: ::jni::sys::jlong,
+        ) {
+            crate::langs::jni_call(
+                env,
+                move || {
+                    unsafe { crate::langs::drop_handle::<Self>(handle) };
+                    Ok(())
+                },
+            )
+        }
+    }
+    impl Amount {}
+    impl crate::common::ExposedStruct for Amount {}
+    impl crate::langs::ExposedPtr for Script {
+        type Kind = crate::langs::Boxed;
+    }
+    impl Script {
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_operators_Native_scriptDestroy<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+            handle: ::jni::sys::jlong,
+        ) {
+            crate::langs::jni_call(
+                env,
+                move || {
+                    unsafe { crate::langs::drop_handle::<Self>(handle) };
+                    Ok(())
+                },
+            )
+        }
+    }
+    impl Script {}
+    impl crate::common::ExposedStruct for Script {}
+    impl Amount {
+        fn add(&self, rhs: &Self) -> Result<Self, Error> {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            std::ops::Add::add(self.clone(), rhs.clone())
+        }
+        fn mul(&self, rhs: &u64) -> Self {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            std::ops::Mul::mul(self.clone(), rhs.clone())
+        }
+        fn neg(&self) -> Self {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            std::ops::Neg::neg(self.clone())
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_operators_Native_amountAdd<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+            this: ::jni::sys::jlong,
+            rhs: ::jni::sys::jlong,
+        ) -> ::jni::sys::jlong {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            jni_call(
+                env,
+                move || {
+                    let this = unsafe { borrow_handle::<Self>(this)? };
+                    let rhs = unsafe { borrow_handle::<Self>(rhs)? };
+                    let __output = Self::add(this, rhs)
+                        .map_err(|e| JniThrow::new(
+                            "operators/Error",
+                            format!("{:?}", e),
+                        ))?;
+                    Ok(into_handle(__output))
+                },
+            )
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_operators_Native_amountMul<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+            this: ::jni::sys::jlong,
+            rhs: ::jni::sys::jlong,
+        ) -> ::jni::sys::jlong {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            jni_call(
+                env,
+                move || {
+                    let this = unsafe { borrow_handle::<Self>(this)? };
+                    let rhs: u64 = FromJni::from_jni(env, rhs)?;
+                    let rhs = &rhs;
+                    let __output = Self::mul(this, rhs);
+                    Ok(into_handle(__output))
+                },
+            )
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_operators_Native_amountNeg<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+            this: ::jni::sys::jlong,
+        ) -> ::jni::sys::jlong {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            jni_call(
+                env,
+                move || {
+                    let this = unsafe { borrow_handle::<Self>(this)? };
+                    let __output = Self::neg(this);
+                    Ok(into_handle(__output))
+                },
+            )
+        }
+    }
+    impl Script {
+        fn index(&self, rhs: &usize) -> Result<u8, crate::common::IndexError> {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            crate::common::CheckedIndex::checked_index(self, rhs.clone())
+                .cloned()
+                .ok_or(crate::common::IndexError)
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_operators_Native_scriptIndex<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+            this: ::jni::sys::jlong,
+            rhs: ::jni::sys::jlong,
+        ) -> ::jni::sys::jbyte {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            jni_call(
+                env,
+                move || {
+                    let this = unsafe { borrow_handle::<Self>(this)? };
+                    let rhs: usize = FromJni::from_jni(env, rhs)?;
+                    let rhs = &rhs;
+                    let __output = Self::index(this, rhs)?;
+                    __output.into_jni(env)
+                },
+            )
+        }
+    }
+}
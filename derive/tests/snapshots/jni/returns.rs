@@ -0,0 +1,180 @@
+pub mod returns {
+    use super::Error;
+    pub struct Item {
+        inner: super::Item,
+    }
+    impl Item {
+        fn parse(s: String) -> Result<Self, Error> {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            Ok(s.parse::<super::Item>()?.into())
+        }
+        fn check(&self) -> Result<(), super::Error> {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            self.inner.check()
+        }
+        fn find(name: String) -> Option<Self> {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            super::Item::find(&name).map(Into::into)
+        }
+        fn to_bytes(&self) -> Vec<u8> {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            self.inner.to_bytes()
+        }
+        fn name(&self) -> String {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            self.inner.name().to_string()
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_returns_Native_itemParse<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+            s: ::jni::objects::JString<'a>,
+        ) -> ::jni::sys::jlong {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            jni_call(
+                env,
+                move || {
+                    let s: String = FromJni::from_jni(env, s)?;
+                    let __output = Self::parse(s)
+                        .map_err(|e| JniThrow::new(
+                            "returns/Error",
+                            format!("{:?}", e),
+                        ))?;
+                    Ok(into_handle(__output))
+                },
+            )
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_returns_Native_itemCheck<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+            this: ::jni::sys::jlong,
+        ) {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            jni_call(
+                env,
+                move || {
+                    let this = unsafe { borrow_handle::<Self>(this)? };
+                    let __output = Self::check(this)
+                        .map_err(|e| JniThrow::new(
+                            "returns/Error",
+                            format!("{:?}", e),
+                        ))?;
+                    Ok(__output)
+                },
+            )
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_returns_Native_itemFind<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+            name: ::jni::objects::JString<'a>,
+        ) -> ::jni::sys::jlong {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            jni_call(
+                env,
+                move || {
+                    let name: String = FromJni::from_jni(env, name)?;
+                    let __output = Self::find(name);
+                    Ok(__output.map(into_handle).unwrap_or(0))
+                },
+            )
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_returns_Native_itemToBytes<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+            this: ::jni::sys::jlong,
+        ) -> ::jni::sys::jbyteArray {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            jni_call(
+                env,
+                move || {
+                    let this = unsafe { borrow_handle::<Self>(this)? };
+                    let __output = Self::to_bytes(this);
+                    __output.into_jni(env)
+                },
+            )
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_returns_Native_itemName<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+            this: ::jni::sys::jlong,
+        ) -> ::jni::sys::jstring {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            jni_call(
+                env,
+                move || {
+                    let this = unsafe { borrow_handle::<Self>(this)? };
+                    let __output = Self::name(this);
+                    __output.into_jni(env)
+                },
+            )
+        }
+    }
+    fn last_error() -> Error {
+        #[allow(unused_imports)]
+        use crate::mapping::{MapFrom, MapTo};
+        super::last_error()
+    }
+    impl crate::langs::ExposedPtr for Item {
+        type Kind = crate::langs::Boxed;
+    }
+    impl Item {
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_returns_Native_itemDestroy<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+            handle: ::jni::sys::jlong,
+        ) {
+            crate::langs::jni_call(
+                env,
+                move || {
+                    unsafe { crate::langs::drop_handle::<Self>(handle) };
+                    Ok(())
+                },
+            )
+        }
+    }
+    impl Item {}
+    impl crate::common::ExposedStruct for Item {}
+    #[no_mangle]
+    #[allow(non_snake_case)]
+    pub extern "system" fn Java_returns_Native_lastError<'a>(
+        env: ::jni::JNIEnv<'a>,
+        _class: ::jni::objects::JClass<'a>,
+    ) -> ::jni::sys::jlong {
+        #[allow(unused_imports)]
+        use crate::mapping::{MapFrom, MapTo};
+        use crate::langs::*;
+        jni_call(
+            env,
+            move || {
+                let __output = self::last_error();
+                Ok(into_handle(__output))
+            },
+        )
+    }
+}
@@ -0,0 +1,419 @@
+pub mod structs {
+    pub struct Opaque {
+        inner: super::Opaque,
+    }
+    impl Opaque {}
+    pub struct Shared {
+        inner: super::Shared,
+    }
+    pub struct WithFields {
+        pub(crate) shared: <Shared as crate::common::WrappedStructField>::Store,
+        pub(crate) value: <u32 as crate::common::WrappedStructField>::Store,
+    }
+    impl WithFields {}
+    pub struct Point {
+        pub x: i32,
+        pub y: i32,
+    }
+    pub struct Script {
+        inner: super::Script,
+    }
+    impl Script {}
+    pub struct WithOpaque {
+        pub script: <Script as crate::common::WrappedStructField>::Store,
+        pub value: u32,
+    }
+    impl WithOpaque {}
+    impl crate::langs::ExposedPtr for Opaque {
+        type Kind = crate::langs::Boxed;
+    }
+    impl Opaque {
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_structs_Native_opaqueDestroy<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+            handle: ::jni::sys::jlong,
+        ) {
+            crate::langs::jni_call(
+                env,
+                move || {
+                    unsafe { crate::langs::drop_handle::<Self>(handle) };
+                    Ok(())
+                },
+            )
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_structs_Native_opaqueToString<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+            this: ::jni::sys::jlong,
+        ) -> ::jni::sys::jstring {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            jni_call(
+                env,
+                move || {
+                    let this = unsafe { borrow_handle::<Self>(this)? };
+                    let __output = Self::to_string(this);
+                    __output.into_jni(env)
+                },
+            )
+        }
+    }
+    impl Opaque {}
+    impl crate::common::ExposedStruct for Opaque {}
+    impl crate::langs::ExposedPtr for Shared {
+        type Kind = crate::langs::Shared;
+    }
+    impl Shared {
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_structs_Native_sharedDestroy<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+            handle: ::jni::sys::jlong,
+        ) {
+            crate::langs::jni_call(
+                env,
+                move || {
+                    unsafe { crate::langs::drop_handle::<Self>(handle) };
+                    Ok(())
+                },
+            )
+        }
+    }
+    impl Shared {}
+    impl crate::common::ExposedStruct for Shared {}
+    impl crate::langs::ExposedPtr for WithFields {
+        type Kind = crate::langs::Boxed;
+    }
+    impl WithFields {
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_structs_Native_withFieldsDestroy<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+            handle: ::jni::sys::jlong,
+        ) {
+            crate::langs::jni_call(
+                env,
+                move || {
+                    unsafe { crate::langs::drop_handle::<Self>(handle) };
+                    Ok(())
+                },
+            )
+        }
+    }
+    impl WithFields {
+        fn get_shared(&self) -> Shared {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::common::AccessContainer;
+            self.shared.access_container(|value| value.clone())
+        }
+        fn set_shared(&mut self, shared: Shared) {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::common::WrappedStructField;
+            self.shared = Shared::wrap_set(crate::mapping::MapFrom::map_from(shared));
+        }
+        fn get_value(&self) -> u32 {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            self.value
+        }
+        fn set_value(
+            &mut self,
+            value: u32,
+        ) -> Result<(), crate::common::ValidationError> {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::common::WrappedStructField;
+            let value: u32 = crate::mapping::MapFrom::map_from(value);
+            WithFields::validate_value(self, &value)
+                .map_err(crate::common::ValidationError::from_display)?;
+            self.value = u32::wrap_set(value);
+            Ok(())
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_structs_Native_withFieldsGetShared<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+            this: ::jni::sys::jlong,
+        ) -> ::jni::sys::jlong {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            jni_call(
+                env,
+                move || {
+                    let this = unsafe { borrow_handle::<Self>(this)? };
+                    let __output = Self::get_shared(this);
+                    Ok(into_handle(__output))
+                },
+            )
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_structs_Native_withFieldsSetShared<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+            this: ::jni::sys::jlong,
+            shared: ::jni::sys::jlong,
+        ) {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            jni_call(
+                env,
+                move || {
+                    let this = unsafe { borrow_handle_mut::<Self>(this)? };
+                    let shared: Shared = unsafe { borrow_handle::<Shared>(shared)? }
+                        .clone();
+                    let __output = Self::set_shared(this, shared);
+                    Ok(__output)
+                },
+            )
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_structs_Native_withFieldsGetValue<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+            this: ::jni::sys::jlong,
+        ) -> ::jni::sys::jint {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            jni_call(
+                env,
+                move || {
+                    let this = unsafe { borrow_handle::<Self>(this)? };
+                    let __output = Self::get_value(this);
+                    __output.into_jni(env)
+                },
+            )
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_structs_Native_withFieldsSetValue<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+            this: ::jni::sys::jlong,
+            value: ::jni::sys::jint,
+        ) {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            jni_call(
+                env,
+                move || {
+                    let this = unsafe { borrow_handle_mut::<Self>(this)? };
+                    let value: u32 = FromJni::from_jni(env, value)?;
+                    let __output = Self::set_value(this, value)?;
+                    Ok(__output)
+                },
+            )
+        }
+    }
+    impl crate::common::ExposedStruct for WithFields {}
+    impl crate::langs::ExposedPtr for Point {
+        type Kind = crate::langs::Boxed;
+    }
+    impl Point {
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_structs_Native_pointDestroy<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+            handle: ::jni::sys::jlong,
+        ) {
+            crate::langs::jni_call(
+                env,
+                move || {
+                    unsafe { crate::langs::drop_handle::<Self>(handle) };
+                    Ok(())
+                },
+            )
+        }
+    }
+    impl Point {
+        fn get_x(&self) -> i32 {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            self.x
+        }
+        fn set_x(&mut self, x: i32) {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            self.x = x;
+        }
+        fn get_y(&self) -> i32 {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            self.y
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_structs_Native_pointGetX<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+            this: ::jni::sys::jlong,
+        ) -> ::jni::sys::jint {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            jni_call(
+                env,
+                move || {
+                    let this = unsafe { borrow_handle::<Self>(this)? };
+                    let __output = Self::get_x(this);
+                    __output.into_jni(env)
+                },
+            )
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_structs_Native_pointSetX<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+            this: ::jni::sys::jlong,
+            x: ::jni::sys::jint,
+        ) {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            jni_call(
+                env,
+                move || {
+                    let this = unsafe { borrow_handle_mut::<Self>(this)? };
+                    let x: i32 = FromJni::from_jni(env, x)?;
+                    let __output = Self::set_x(this, x);
+                    Ok(__output)
+                },
+            )
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_structs_Native_pointGetY<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+            this: ::jni::sys::jlong,
+        ) -> ::jni::sys::jint {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            jni_call(
+                env,
+                move || {
+                    let this = unsafe { borrow_handle::<Self>(this)? };
+                    let __output = Self::get_y(this);
+                    __output.into_jni(env)
+                },
+            )
+        }
+    }
+    impl crate::common::ExposedStruct for Point {}
+    impl crate::langs::ExposedPtr for Script {
+        type Kind = crate::langs::Boxed;
+    }
+    impl Script {
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_structs_Native_scriptDestroy<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+            handle: ::jni::sys::jlong,
+        ) {
+            crate::langs::jni_call(
+                env,
+                move || {
+                    unsafe { crate::langs::drop_handle::<Self>(handle) };
+                    Ok(())
+                },
+            )
+        }
+    }
+    impl Script {}
+    impl crate::common::ExposedStruct for Script {}
+    impl crate::langs::ExposedPtr for WithOpaque {
+        type Kind = crate::langs::Boxed;
+    }
+    impl WithOpaque {
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_structs_Native_withOpaqueDestroy<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+            handle: ::jni::sys::jlong,
+        ) {
+            crate::langs::jni_call(
+                env,
+                move || {
+                    unsafe { crate::langs::drop_handle::<Self>(handle) };
+                    Ok(())
+                },
+            )
+        }
+    }
+    impl WithOpaque {
+        fn get_script(&self) -> Script {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::common::AccessContainer;
+            self.script.access_container(|value| value.clone())
+        }
+        fn set_script(&mut self, script: Script) {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::common::WrappedStructField;
+            self.script = Script::wrap_set(crate::mapping::MapFrom::map_from(script));
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_structs_Native_withOpaqueGetScript<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+            this: ::jni::sys::jlong,
+        ) -> ::jni::sys::jlong {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            jni_call(
+                env,
+                move || {
+                    let this = unsafe { borrow_handle::<Self>(this)? };
+                    let __output = Self::get_script(this);
+                    Ok(into_handle(__output))
+                },
+            )
+        }
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn Java_structs_Native_withOpaqueSetScript<'a>(
+            env: ::jni::JNIEnv<'a>,
+            _class: ::jni::objects::JClass<'a>,
+            this: ::jni::sys::jlong,
+            script: ::jni::sys::jlong,
+        ) {
+            #[allow(unused_imports)]
+            use crate::mapping::{MapFrom, MapTo};
+            use crate::langs::*;
+            jni_call(
+                env,
+                move || {
+                    let this = unsafe { borrow_handle_mut::<Self>(this)? };
+                    let script: Script = unsafe { borrow_handle::<Script>(script)? }
+                        .clone();
+                    let __output = Self::set_script(this, script);
+                    Ok(__output)
+                },
+            )
+        }
+    }
+    impl crate::common::ExposedStruct for WithOpaque {}
+}
@@ -0,0 +1,5 @@
+pub mod traits {
+    pub trait Listener: super::Listener {
+        fn _wrapper_on_event(&self, event: String) -> u32;
+    }
+}
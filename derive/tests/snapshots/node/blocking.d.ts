@@ -0,0 +1,9 @@
+export declare class Wallet {
+  constructor(descriptor: string)
+  /** @throws {Error} With `code` set to `"Error"` */
+  sync(url: string, stopGap: number): Promise<bigint>
+  sign(psbt: Uint8Array): Promise<Buffer>
+}
+
+/** @throws {Error} With `code` set to `"Error"` */
+export declare function broadcast(url: string, tx: Buffer): Promise<string>
@@ -0,0 +1,94 @@
+pub mod blocking {
+    use super::Error;
+    #[derive(Clone)]
+    #[::napi_derive::napi]
+    pub struct Wallet {
+        inner: super::Wallet,
+    }
+    #[::napi_derive::napi]
+    impl Wallet {
+        #[napi(constructor)]
+        pub fn new(descriptor: String) -> Wallet {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_descriptor = (descriptor);
+            let descriptor = _temp_descriptor;
+            let mut block_closure = move || {
+                { super::Wallet::new(&descriptor).into() }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[napi(js_name = "sync")]
+        pub fn sync(
+            &self,
+            url: String,
+            stop_gap: u32,
+        ) -> ::napi::bindgen_prelude::AsyncTask<crate::langs::Blocking<u64>> {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_url = (url);
+            let url = _temp_url;
+            let _temp_stop_gap = (stop_gap);
+            let stop_gap = <usize>::map_from(_temp_stop_gap);
+            let __self = Clone::clone(self);
+            crate::langs::Blocking::spawn(move || {
+                let __output = Self::__blocking_sync(&__self, url, stop_gap);
+                let __output = __output.map_err(crate::langs::NodeError::from)?;
+                let __output = __output;
+                Ok(__output)
+            })
+        }
+        #[napi(js_name = "sign")]
+        pub fn sign(
+            &self,
+            psbt: &[u8],
+        ) -> ::napi::bindgen_prelude::AsyncTask<
+            crate::langs::Blocking<::napi::bindgen_prelude::Buffer>,
+        > {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_psbt = (psbt);
+            let psbt = _temp_psbt;
+            let __self = Clone::clone(self);
+            let psbt = ToOwned::to_owned(psbt);
+            crate::langs::Blocking::spawn(move || {
+                let __output = Self::__blocking_sign(&__self, &psbt);
+                let __output: Vec<u8> = __output;
+                let __output = __output.map_to();
+                Ok(__output)
+            })
+        }
+        fn __blocking_sync(&self, url: String, stop_gap: usize) -> Result<u64, Error> {
+            self.inner.sync(&url, stop_gap)
+        }
+        fn __blocking_sign(&self, psbt: &[u8]) -> Vec<u8> {
+            self.inner.sign(psbt)
+        }
+    }
+    #[::napi_derive::napi(js_name = "broadcast")]
+    pub fn broadcast(
+        url: String,
+        tx: ::napi::bindgen_prelude::Buffer,
+    ) -> ::napi::bindgen_prelude::AsyncTask<crate::langs::Blocking<String>> {
+        use crate::mapping::{MapTo, MapFrom};
+        use crate::langs::*;
+        let _temp_url = (url);
+        let url = _temp_url;
+        let _temp_tx = (tx);
+        let tx = <Vec<u8>>::map_from(_temp_tx);
+        crate::langs::Blocking::spawn(move || {
+            let __output = __blocking_broadcast(url, tx);
+            let __output = __output.map_err(crate::langs::NodeError::from)?;
+            let __output = __output;
+            Ok(__output)
+        })
+    }
+    #[::napi_derive::napi]
+    impl Wallet {}
+    impl crate::common::ExposedStruct for Wallet {}
+    fn __blocking_broadcast(url: String, tx: Vec<u8>) -> Result<String, Error> {
+        super::broadcast(&url, &tx)
+    }
+}
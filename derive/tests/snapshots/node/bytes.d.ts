@@ -0,0 +1,5 @@
+export declare class Script {
+  toString(): string
+  toBytes(): Buffer
+  static fromBytes(bytes: Buffer): Script
+}
@@ -0,0 +1,34 @@
+pub mod bytes {
+    #[::napi_derive::napi]
+    pub struct Script {
+        inner: super::Script,
+    }
+    #[::napi_derive::napi]
+    impl Script {}
+    impl crate::common::ByteContainer for Script {
+        fn as_bytes(&self) -> &[u8] {
+            self.inner.as_bytes()
+        }
+        fn from_bytes(bytes: Vec<u8>) -> Self {
+            Script { inner: bytes.into() }
+        }
+    }
+    #[::napi_derive::napi]
+    impl Script {
+        #[napi(js_name = "toString")]
+        pub fn __to_string(&self) -> String {
+            self.to_string()
+        }
+        #[napi(js_name = "toBytes")]
+        pub fn __to_bytes(&self) -> ::napi::bindgen_prelude::Buffer {
+            crate::common::ByteContainer::as_bytes(self).to_vec().into()
+        }
+        #[napi(js_name = "fromBytes")]
+        pub fn __from_bytes(bytes: ::napi::bindgen_prelude::Buffer) -> Self {
+            <Self as crate::common::ByteContainer>::from_bytes(bytes.to_vec())
+        }
+    }
+    #[::napi_derive::napi]
+    impl Script {}
+    impl crate::common::ExposedStruct for Script {}
+}
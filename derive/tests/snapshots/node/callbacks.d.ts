@@ -0,0 +1,2 @@
+// `apply` is not exported: callbacks can't be passed from JavaScript, expose a trait instead
+// `describe` is not exported: callbacks can't be passed from JavaScript, expose a trait instead
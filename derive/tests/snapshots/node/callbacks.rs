@@ -0,0 +1,8 @@
+pub mod callbacks {
+    fn apply(value: u32, cb: fn(value: u32) -> u32) -> u32 {
+        cb(value)
+    }
+    fn describe(cb: fn(name: String, value: u32)) {
+        cb("value".to_string(), 42)
+    }
+}
@@ -0,0 +1,6 @@
+export declare class Network {
+  /** @throws {Error} With `code` set to `"Error"` */
+  constructor(s: string)
+  static bitcoin(): Network
+  static fromMagic(magic: number): Network | null
+}
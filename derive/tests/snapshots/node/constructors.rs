@@ -0,0 +1,49 @@
+pub mod constructors {
+    use super::Error;
+    #[::napi_derive::napi]
+    pub struct Network {
+        inner: super::Network,
+    }
+    #[::napi_derive::napi]
+    impl Network {
+        #[napi(constructor)]
+        pub fn from_string(s: String) -> Result<Network, crate::langs::NodeError> {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_s = (s);
+            let s = _temp_s;
+            let mut block_closure = move || -> Result<Self, Error> {
+                { Ok(s.parse::<super::Network>()?.into()) }
+            };
+            let __output = block_closure();
+            let __output = __output.map_err(crate::langs::NodeError::from)?;
+            let __output = __output;
+            Ok(__output)
+        }
+        #[napi(js_name = "bitcoin")]
+        pub fn bitcoin() -> Network {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let mut block_closure = move || { { super::Network::Bitcoin.into() } };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[napi(js_name = "fromMagic")]
+        pub fn from_magic(magic: u32) -> Option<Network> {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_magic = (magic);
+            let magic = _temp_magic;
+            let mut block_closure = move || {
+                { super::Network::from_magic(magic).map(Into::into) }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+    }
+    #[::napi_derive::napi]
+    impl Network {}
+    impl crate::common::ExposedStruct for Network {}
+}
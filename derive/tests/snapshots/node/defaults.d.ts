@@ -0,0 +1,6 @@
+export declare class FeeRate {
+  static fromSatPerVb(satPerVb?: number | undefined | null): FeeRate
+  feeFor(vbytes: bigint, min?: FeeRate | undefined | null): bigint
+}
+
+export declare function greet(name?: string | undefined | null): string
@@ -0,0 +1,7 @@
+export declare class Counter {
+  constructor(start: number)
+  static zero(): Counter
+  get(): number
+  increment(by: number): void
+  label(prefix: string): string
+}
@@ -0,0 +1,65 @@
+pub mod impls {
+    #[::napi_derive::napi]
+    pub struct Counter {
+        inner: super::Counter,
+    }
+    #[::napi_derive::napi]
+    impl Counter {
+        #[napi(constructor)]
+        pub fn new(start: u32) -> Counter {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_start = (start);
+            let start = _temp_start;
+            let mut block_closure = move || { { super::Counter::new(start).into() } };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[napi(js_name = "zero")]
+        pub fn zero() -> Counter {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let mut block_closure = move || { { super::Counter::new(0).into() } };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[napi(js_name = "get")]
+        pub fn get(&self) -> u32 {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let mut block_closure = move || { { self.inner.get() } };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[napi(js_name = "increment")]
+        pub fn increment(&mut self, by: u32) -> () {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_by = (by);
+            let by = _temp_by;
+            let mut block_closure = move || { { self.inner.increment(by) } };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[napi(js_name = "label")]
+        pub fn label(&self, prefix: String) -> String {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_prefix = (prefix);
+            let prefix = _temp_prefix;
+            let mut block_closure = move || {
+                { format!("{}{}", prefix, self.inner.get()) }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+    }
+    #[::napi_derive::napi]
+    impl Counter {}
+    impl crate::common::ExposedStruct for Counter {}
+}
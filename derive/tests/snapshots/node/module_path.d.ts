@@ -0,0 +1,15 @@
+export declare function version(): number
+
+export declare function count(): number
+
+export declare namespace outerInner {
+  export class Thing {
+    static new(): outerInner.Thing
+  }
+
+  export function count(): number
+}
+
+export declare namespace outerPrefixedNested {
+  export function count(): number
+}
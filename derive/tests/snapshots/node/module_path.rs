@@ -0,0 +1,63 @@
+pub mod outer {
+    #[::napi_derive::napi(js_name = "version")]
+    pub fn version() -> u32 {
+        use crate::mapping::{MapTo, MapFrom};
+        use crate::langs::*;
+        let mut block_closure = move || { { 1 } };
+        let __output = block_closure();
+        let __output = __output;
+        __output
+    }
+    pub mod inner {
+        #[::napi_derive::napi(namespace = "outerInner")]
+        pub struct Thing {
+            inner: super::Thing,
+        }
+        #[::napi_derive::napi(namespace = "outerInner")]
+        impl Thing {
+            #[napi(js_name = "new")]
+            pub fn new() -> Thing {
+                use crate::mapping::{MapTo, MapFrom};
+                use crate::langs::*;
+                let mut block_closure = move || { { super::Thing::default().into() } };
+                let __output = block_closure();
+                let __output = __output;
+                __output
+            }
+        }
+        #[::napi_derive::napi(js_name = "count", namespace = "outerInner")]
+        pub fn count() -> u32 {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let mut block_closure = move || { { 0 } };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[::napi_derive::napi(namespace = "outerInner")]
+        impl Thing {}
+        impl crate::common::ExposedStruct for Thing {}
+    }
+    pub mod prefixed {
+        #[::napi_derive::napi(js_name = "count")]
+        pub fn count() -> u32 {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let mut block_closure = move || { { 0 } };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        pub mod nested {
+            #[::napi_derive::napi(js_name = "count", namespace = "outerPrefixedNested")]
+            pub fn count() -> u32 {
+                use crate::mapping::{MapTo, MapFrom};
+                use crate::langs::*;
+                let mut block_closure = move || { { 0 } };
+                let __output = block_closure();
+                let __output = __output;
+                __output
+            }
+        }
+    }
+}
@@ -0,0 +1,11 @@
+export declare class Amount {
+  /** @throws {Error} With `code` set to `"Error"` */
+  add(rhs: Amount): Amount
+  mul(rhs: bigint): Amount
+  neg(): Amount
+}
+
+export declare class Script {
+  /** @throws {Error} With `code` set to `"ERR_OUT_OF_RANGE"` */
+  index(rhs: number): number
+}
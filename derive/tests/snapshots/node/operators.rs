@@ -0,0 +1,110 @@
+pub mod operators {
+    use super::Error;
+    #[::napi_derive::napi]
+    pub struct Amount {
+        inner: super::Amount,
+    }
+    #[::napi_derive::napi]
+    impl Amount {}
+    impl std::ops::Add for Amount {
+        type Output = Result<Amount, Error>;
+        fn add(self, rhs: Self) -> Self::Output {
+            Ok(self.inner.checked_add(rhs.inner)?.into())
+        }
+    }
+    impl std::ops::Mul<u64> for Amount {
+        type Output = Amount;
+        fn mul(self, rhs: u64) -> Self::Output {
+            (self.inner * rhs).into()
+        }
+    }
+    impl std::ops::Neg for Amount {
+        type Output = Amount;
+        fn neg(self) -> Self::Output {
+            (-self.inner).into()
+        }
+    }
+    #[::napi_derive::napi]
+    pub struct Script {
+        inner: super::Script,
+    }
+    #[::napi_derive::napi]
+    impl Script {}
+    impl std::ops::Index<usize> for Script {
+        type Output = u8;
+        fn index(&self, index: usize) -> &Self::Output {
+            &self.inner.as_bytes()[index]
+        }
+    }
+    impl crate::common::CheckedIndex<usize> for Script {
+        fn checked_index(&self, index: usize) -> Option<&u8> {
+            self.inner.as_bytes().get(index)
+        }
+    }
+    #[::napi_derive::napi]
+    impl Amount {}
+    impl crate::common::ExposedStruct for Amount {}
+    #[::napi_derive::napi]
+    impl Script {}
+    impl crate::common::ExposedStruct for Script {}
+    #[::napi_derive::napi]
+    impl Amount {
+        #[napi(js_name = "add")]
+        pub fn add(&self, rhs: &Amount) -> Result<Amount, crate::langs::NodeError> {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_rhs = (rhs);
+            let rhs = _temp_rhs;
+            let mut block_closure = move || -> Result<Self, Error> {
+                { std::ops::Add::add(self.clone(), rhs.clone()) }
+            };
+            let __output = block_closure();
+            let __output = __output.map_err(crate::langs::NodeError::from)?;
+            let __output = __output;
+            Ok(__output)
+        }
+        #[napi(js_name = "mul")]
+        pub fn mul(&self, rhs: crate::langs::JsU64) -> Amount {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_rhs = (rhs);
+            let rhs = <u64>::map_from(_temp_rhs);
+            let mut block_closure = move || {
+                { std::ops::Mul::mul(self.clone(), rhs) }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[napi(js_name = "neg")]
+        pub fn neg(&self) -> Amount {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let mut block_closure = move || { { std::ops::Neg::neg(self.clone()) } };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+    }
+    #[::napi_derive::napi]
+    impl Script {
+        #[napi(js_name = "index")]
+        pub fn index(&self, rhs: u32) -> Result<u8, crate::langs::NodeError> {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_rhs = (rhs);
+            let rhs = <usize>::map_from(_temp_rhs);
+            let mut block_closure = move || -> Result<u8, crate::common::IndexError> {
+                {
+                    crate::common::CheckedIndex::checked_index(self, rhs)
+                        .cloned()
+                        .ok_or(crate::common::IndexError)
+                }
+            };
+            let __output = block_closure();
+            let __output = __output.map_err(crate::langs::NodeError::from)?;
+            let __output = __output;
+            Ok(__output)
+        }
+    }
+}
@@ -0,0 +1,11 @@
+export declare class Item {
+  /** @throws {Error} With `code` set to `"Error"` */
+  static parse(s: string): Item
+  /** @throws {Error} With `code` set to `"Error"` */
+  check(): void
+  static find(name: string): Item | null
+  toBytes(): Buffer
+  name(): string
+}
+
+export declare function lastError(): unknown
@@ -0,0 +1,80 @@
+pub mod returns {
+    use super::Error;
+    #[::napi_derive::napi]
+    pub struct Item {
+        inner: super::Item,
+    }
+    #[::napi_derive::napi]
+    impl Item {
+        #[napi(js_name = "parse")]
+        pub fn parse(s: String) -> Result<Item, crate::langs::NodeError> {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_s = (s);
+            let s = _temp_s;
+            let mut block_closure = move || -> Result<Self, Error> {
+                { Ok(s.parse::<super::Item>()?.into()) }
+            };
+            let __output = block_closure();
+            let __output = __output.map_err(crate::langs::NodeError::from)?;
+            let __output = __output;
+            Ok(__output)
+        }
+        #[napi(js_name = "check")]
+        pub fn check(&self) -> Result<(), crate::langs::NodeError> {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let mut block_closure = move || -> Result<(), super::Error> {
+                { self.inner.check() }
+            };
+            let __output = block_closure();
+            let __output = __output.map_err(crate::langs::NodeError::from)?;
+            let __output = __output;
+            Ok(__output)
+        }
+        #[napi(js_name = "find")]
+        pub fn find(name: String) -> Option<Item> {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_name = (name);
+            let name = _temp_name;
+            let mut block_closure = move || {
+                { super::Item::find(&name).map(Into::into) }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[napi(js_name = "toBytes")]
+        pub fn to_bytes(&self) -> ::napi::bindgen_prelude::Buffer {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let mut block_closure = move || { { self.inner.to_bytes() } };
+            let __output = block_closure();
+            let __output: Vec<u8> = __output;
+            let __output = __output.map_to();
+            __output
+        }
+        #[napi(js_name = "name")]
+        pub fn name(&self) -> String {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let mut block_closure = move || { { self.inner.name().to_string() } };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+    }
+    #[::napi_derive::napi(js_name = "lastError")]
+    pub fn last_error() -> Error {
+        use crate::mapping::{MapTo, MapFrom};
+        use crate::langs::*;
+        let mut block_closure = move || { { super::last_error() } };
+        let __output = block_closure();
+        let __output = __output;
+        __output
+    }
+    #[::napi_derive::napi]
+    impl Item {}
+    impl crate::common::ExposedStruct for Item {}
+}
@@ -0,0 +1,33 @@
+export declare class Opaque {
+  toString(): string
+}
+
+export declare class Shared {
+}
+
+export declare class WithFields {
+  get shared(): Shared
+  set shared(shared: Shared)
+  get value(): number
+  /** @throws {Error} With `code` set to `"ValidationError"` */
+  set value(value: number)
+}
+
+export declare class Point {
+  get x(): number
+  set x(x: number)
+  get y(): number
+}
+
+export declare class Script {
+}
+
+export declare class WithOpaque {
+  get script(): Script
+  set script(script: Script)
+}
+
+/** Thrown by the functions that return a `ValidationError` */
+export interface ValidationError extends Error {
+  code: "ValidationError"
+}
@@ -0,0 +1,221 @@
+pub mod structs {
+    #[::napi_derive::napi]
+    pub struct Opaque {
+        inner: super::Opaque,
+    }
+    #[::napi_derive::napi]
+    impl Opaque {}
+    #[::napi_derive::napi]
+    pub struct Shared {
+        inner: super::Shared,
+    }
+    #[::napi_derive::napi]
+    pub struct WithFields {
+        pub(crate) shared: <Shared as crate::common::WrappedStructField>::Store,
+        pub(crate) value: <u32 as crate::common::WrappedStructField>::Store,
+    }
+    #[::napi_derive::napi]
+    impl WithFields {}
+    #[::napi_derive::napi]
+    pub struct Point {
+        #[napi(skip)]
+        pub x: i32,
+        #[napi(skip)]
+        pub y: i32,
+    }
+    #[::napi_derive::napi]
+    pub struct Script {
+        inner: super::Script,
+    }
+    #[::napi_derive::napi]
+    impl Script {}
+    #[::napi_derive::napi]
+    pub struct WithOpaque {
+        #[napi(skip)]
+        pub script: <Script as crate::common::WrappedStructField>::Store,
+        #[napi(skip)]
+        pub value: u32,
+    }
+    #[::napi_derive::napi]
+    impl WithOpaque {}
+    #[::napi_derive::napi]
+    impl Opaque {
+        #[napi(js_name = "toString")]
+        pub fn __to_string(&self) -> String {
+            self.to_string()
+        }
+    }
+    #[::napi_derive::napi]
+    impl Opaque {}
+    impl crate::common::ExposedStruct for Opaque {}
+    #[::napi_derive::napi]
+    impl Shared {}
+    impl crate::common::ExposedStruct for Shared {}
+    #[::napi_derive::napi]
+    impl WithFields {
+        #[napi(getter, js_name = "shared")]
+        pub fn get_shared(
+            &mut self,
+        ) -> <Shared as crate::common::WrappedStructField>::Getter {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let mut block_closure = move || {
+                {
+                    use crate::common::WrappedStructField;
+                    Shared::wrap_get(&mut self.shared)
+                }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[napi(setter, js_name = "shared")]
+        pub fn set_shared(
+            &mut self,
+            shared: &<Shared as crate::common::WrappedStructField>::Setter,
+        ) -> () {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_shared = (shared);
+            let shared = _temp_shared;
+            let mut block_closure = move || {
+                {
+                    use crate::common::WrappedStructField;
+                    self
+                        .shared = Shared::wrap_set(
+                        crate::mapping::MapFrom::map_from(shared),
+                    );
+                }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[napi(getter, js_name = "value")]
+        pub fn get_value(
+            &mut self,
+        ) -> <u32 as crate::common::WrappedStructField>::Getter {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let mut block_closure = move || {
+                {
+                    use crate::common::WrappedStructField;
+                    u32::wrap_get(&mut self.value)
+                }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[napi(setter, js_name = "value")]
+        pub fn set_value(
+            &mut self,
+            value: <u32 as crate::common::WrappedStructField>::Setter,
+        ) -> Result<(), crate::langs::NodeError> {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_value = (value);
+            let value = _temp_value;
+            let mut block_closure = move || -> Result<
+                (),
+                crate::common::ValidationError,
+            > {
+                {
+                    use crate::common::WrappedStructField;
+                    let value: u32 = crate::mapping::MapFrom::map_from(value);
+                    WithFields::validate_value(self, &value)
+                        .map_err(crate::common::ValidationError::from_display)?;
+                    self.value = u32::wrap_set(value);
+                    Ok(())
+                }
+            };
+            let __output = block_closure();
+            let __output = __output.map_err(crate::langs::NodeError::from)?;
+            let __output = __output;
+            Ok(__output)
+        }
+    }
+    impl crate::common::ExposedStruct for WithFields {}
+    #[::napi_derive::napi]
+    impl Point {
+        #[napi(getter, js_name = "x")]
+        pub fn get_x(&self) -> i32 {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let mut block_closure = move || { { self.x.clone() } };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[napi(setter, js_name = "x")]
+        pub fn set_x(&mut self, x: i32) -> () {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_x = (x);
+            let x = _temp_x;
+            let mut block_closure = move || {
+                {
+                    self.x = crate::mapping::MapFrom::map_from(x);
+                }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[napi(getter, js_name = "y")]
+        pub fn get_y(&self) -> i32 {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let mut block_closure = move || { { self.y.clone() } };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+    }
+    impl crate::common::ExposedStruct for Point {}
+    #[::napi_derive::napi]
+    impl Script {}
+    impl crate::common::ExposedStruct for Script {}
+    #[::napi_derive::napi]
+    impl WithOpaque {
+        #[napi(getter, js_name = "script")]
+        pub fn get_script(
+            &mut self,
+        ) -> <Script as crate::common::WrappedStructField>::Getter {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let mut block_closure = move || {
+                {
+                    use crate::common::WrappedStructField;
+                    Script::wrap_get(&mut self.script)
+                }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[napi(setter, js_name = "script")]
+        pub fn set_script(
+            &mut self,
+            script: &<Script as crate::common::WrappedStructField>::Setter,
+        ) -> () {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_script = (script);
+            let script = _temp_script;
+            let mut block_closure = move || {
+                {
+                    use crate::common::WrappedStructField;
+                    self
+                        .script = Script::wrap_set(
+                        crate::mapping::MapFrom::map_from(script),
+                    );
+                }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+    }
+    impl crate::common::ExposedStruct for WithOpaque {}
+}
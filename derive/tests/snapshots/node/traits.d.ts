@@ -0,0 +1,8 @@
+export declare class ListenerStruct {
+  constructor(implementation: Listener)
+  onEvent(event: string): number
+}
+
+export interface Listener {
+  onEvent(event: string): number
+}
@@ -0,0 +1,73 @@
+pub mod traits {
+    pub trait Listener: super::Listener {
+        fn _wrapper_on_event(&self, event: String) -> u32;
+    }
+    #[::napi_derive::napi]
+    pub struct ListenerStruct {
+        native: Option<Box<dyn super::Listener + Send>>,
+        js: Option<crate::langs::JsImpl>,
+    }
+    #[::napi_derive::napi]
+    impl ListenerStruct {}
+    #[::napi_derive::napi]
+    impl ListenerStruct {
+        #[napi(constructor)]
+        pub fn new(implementation: crate::langs::JsImpl) -> ListenerStruct {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_implementation = (implementation);
+            let implementation = _temp_implementation;
+            let mut block_closure = move || {
+                {
+                    ListenerStruct {
+                        native: None,
+                        js: Some(implementation),
+                    }
+                }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[napi(js_name = "onEvent")]
+        pub fn on_event(&self, event: String) -> u32 {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_event = (event);
+            let event = _temp_event;
+            let mut block_closure = move || {
+                {
+                    if let Some(native) = &self.native {
+                        native.on_event(event)
+                    } else if let Some(js) = &self.js {
+                        js.call("onEvent", (event,))
+                            .unwrap_or_else(|e| {
+                                js.throw(e);
+                                Default::default()
+                            })
+                    } else {
+                        unreachable!()
+                    }
+                }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+    }
+    impl Listener for ListenerStruct {
+        fn _wrapper_on_event(&self, event: String) -> u32 {
+            self.on_event(event)
+        }
+    }
+    impl<T: 'static + super::Listener + Sized + Send> crate::langs::IntoTraitStruct
+    for T {
+        type Target = ListenerStruct;
+        fn into_trait_struct(self) -> Self::Target {
+            ListenerStruct {
+                native: Some(Box::new(self)),
+                js: None,
+            }
+        }
+    }
+}
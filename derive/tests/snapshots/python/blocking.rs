@@ -0,0 +1,75 @@
+pub mod blocking {
+    #[pyo3::prelude::pymodule]
+    pub(super) fn blocking(
+        py: pyo3::Python,
+        m: &pyo3::types::PyModule,
+    ) -> pyo3::PyResult<()> {
+        m.add_class::<Wallet>()?;
+        m.getattr("Wallet")?.setattr("__module__", m.name()?)?;
+        m.add_function(pyo3::wrap_pyfunction!(broadcast, m)?)?;
+        crate::langs::add_logging(m)?;
+        Ok(())
+    }
+    use super::Error;
+    #[derive(Clone)]
+    #[pyo3::prelude::pyclass]
+    pub struct Wallet {
+        inner: super::Wallet,
+    }
+    #[pyo3::prelude::pymethods]
+    impl Wallet {
+        #[new]
+        #[allow(unused_variables)]
+        fn new(descriptor: String, py: pyo3::Python<'_>) -> Self {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_descriptor = (descriptor);
+            let descriptor = _temp_descriptor;
+            let mut block_closure = move || {
+                { super::Wallet::new(&descriptor).into() }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        fn sync(&self, url: String, stop_gap: usize) -> Result<u64, Error> {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_url = (url);
+            let url = _temp_url;
+            let _temp_stop_gap = (stop_gap);
+            let stop_gap = _temp_stop_gap;
+            let mut block_closure = move || { { self.inner.sync(&url, stop_gap) } };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        fn sign(&self, psbt: &[u8]) -> Vec<u8> {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_psbt = (psbt);
+            let psbt = _temp_psbt;
+            let mut block_closure = move || { { self.inner.sign(psbt) } };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+    }
+    #[pyo3::prelude::pyfunction]
+    fn broadcast(url: String, tx: Vec<u8>) -> Result<String, Error> {
+        use crate::mapping::{MapTo, MapFrom};
+        use crate::langs::*;
+        let _temp_url = (url);
+        let url = _temp_url;
+        let _temp_tx = (tx);
+        let tx = _temp_tx;
+        let mut block_closure = move || { { super::broadcast(&url, &tx) } };
+        let __output = block_closure();
+        let __output = __output;
+        __output
+    }
+    impl crate::langs::MutablePyClass for Wallet {}
+    #[pyo3::prelude::pymethods]
+    impl Wallet {}
+    impl crate::common::ExposedStruct for Wallet {}
+}
@@ -0,0 +1,69 @@
+pub mod bytes {
+    #[pyo3::prelude::pymodule]
+    pub(super) fn bytes(
+        py: pyo3::Python,
+        m: &pyo3::types::PyModule,
+    ) -> pyo3::PyResult<()> {
+        m.add_class::<Script>()?;
+        m.getattr("Script")?.setattr("__module__", m.name()?)?;
+        crate::langs::add_logging(m)?;
+        Ok(())
+    }
+    #[pyo3::prelude::pyclass]
+    pub struct Script {
+        inner: super::Script,
+    }
+    #[pyo3::prelude::pymethods]
+    impl Script {}
+    impl crate::common::ByteContainer for Script {
+        fn as_bytes(&self) -> &[u8] {
+            self.inner.as_bytes()
+        }
+        fn from_bytes(bytes: Vec<u8>) -> Self {
+            Script { inner: bytes.into() }
+        }
+    }
+    impl crate::langs::MutablePyClass for Script {}
+    #[pyo3::prelude::pymethods]
+    impl Script {}
+    #[pyo3::prelude::pyproto]
+    #[allow(clippy::not_unsafe_ptr_arg_deref)]
+    impl pyo3::class::PyBufferProtocol for Script {
+        fn bf_getbuffer(
+            slf: pyo3::PyRefMut<Self>,
+            view: *mut pyo3::ffi::Py_buffer,
+            flags: std::os::raw::c_int,
+        ) -> pyo3::PyResult<()> {
+            unsafe { crate::langs::fill_byte_buffer_copy(&slf, view, flags) }
+        }
+        fn bf_releasebuffer(
+            _slf: pyo3::PyRefMut<Self>,
+            view: *mut pyo3::ffi::Py_buffer,
+        ) {
+            unsafe { crate::langs::release_byte_buffer_copy(view) }
+        }
+    }
+    #[pyo3::prelude::pymethods]
+    impl Script {
+        fn __bytes__(&self) -> pyo3::PyObject {
+            crate::langs::to_py_bytes(self)
+        }
+        #[classmethod]
+        fn from_bytes(
+            _cls: &pyo3::types::PyType,
+            bytes: &pyo3::PyAny,
+        ) -> pyo3::PyResult<Self> {
+            crate::langs::from_py_bytes(bytes)
+        }
+    }
+    #[pyo3::prelude::pyproto]
+    impl pyo3::class::PyObjectProtocol for Script {
+        fn __str__(&self) -> String {
+            self.to_string()
+        }
+        fn __repr__(&self) -> String {
+            format!("{:?}", self)
+        }
+    }
+    impl crate::common::ExposedStruct for Script {}
+}
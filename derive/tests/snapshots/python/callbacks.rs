@@ -0,0 +1,40 @@
+pub mod callbacks {
+    #[pyo3::prelude::pymodule]
+    pub(super) fn callbacks(
+        py: pyo3::Python,
+        m: &pyo3::types::PyModule,
+    ) -> pyo3::PyResult<()> {
+        m.add_function(pyo3::wrap_pyfunction!(apply, m)?)?;
+        m.add_function(pyo3::wrap_pyfunction!(describe, m)?)?;
+        crate::langs::add_logging(m)?;
+        Ok(())
+    }
+    #[pyo3::prelude::pyfunction]
+    fn apply(value: u32, cb: crate::python_callback::PyCb<'_>) -> u32 {
+        use crate::mapping::{MapTo, MapFrom};
+        use crate::langs::*;
+        let _temp_value = (value);
+        let value = _temp_value;
+        let _temp_cb = (cb);
+        let cb = |value: u32| -> u32 {
+            _temp_cb.call1((value)).unwrap().extract().unwrap()
+        };
+        let mut block_closure = move || { { cb(value) } };
+        let __output = block_closure();
+        let __output = __output;
+        __output
+    }
+    #[pyo3::prelude::pyfunction]
+    fn describe(cb: crate::python_callback::PyCb<'_>) -> () {
+        use crate::mapping::{MapTo, MapFrom};
+        use crate::langs::*;
+        let _temp_cb = (cb);
+        let cb = |name: String, value: u32| {
+            _temp_cb.call1((name, value)).unwrap().extract().unwrap()
+        };
+        let mut block_closure = move || { { cb("value".to_string(), 42) } };
+        let __output = block_closure();
+        let __output = __output;
+        __output
+    }
+}
@@ -0,0 +1,62 @@
+pub mod constructors {
+    #[pyo3::prelude::pymodule]
+    pub(super) fn constructors(
+        py: pyo3::Python,
+        m: &pyo3::types::PyModule,
+    ) -> pyo3::PyResult<()> {
+        m.add_class::<Network>()?;
+        m.getattr("Network")?.setattr("__module__", m.name()?)?;
+        crate::langs::add_logging(m)?;
+        Ok(())
+    }
+    use super::Error;
+    #[pyo3::prelude::pyclass]
+    pub struct Network {
+        inner: super::Network,
+    }
+    #[pyo3::prelude::pymethods]
+    impl Network {
+        #[new]
+        #[allow(unused_variables)]
+        fn from_string(s: String, py: pyo3::Python<'_>) -> Result<Self, Error> {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_s = (s);
+            let s = _temp_s;
+            let mut block_closure = move || {
+                { Ok(s.parse::<super::Network>()?.into()) }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[classmethod]
+        #[name = "bitcoin"]
+        fn bitcoin(_cls: &pyo3::types::PyType) -> Self {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let mut block_closure = move || { { super::Network::Bitcoin.into() } };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[classmethod]
+        #[name = "from_magic"]
+        fn from_magic(_cls: &pyo3::types::PyType, magic: u32) -> Option<Self> {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_magic = (magic);
+            let magic = _temp_magic;
+            let mut block_closure = move || {
+                { super::Network::from_magic(magic).map(Into::into) }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+    }
+    impl crate::langs::MutablePyClass for Network {}
+    #[pyo3::prelude::pymethods]
+    impl Network {}
+    impl crate::common::ExposedStruct for Network {}
+}
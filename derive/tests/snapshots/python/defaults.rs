@@ -0,0 +1,95 @@
+pub mod defaults {
+    #[pyo3::prelude::pymodule]
+    pub(super) fn defaults(
+        py: pyo3::Python,
+        m: &pyo3::types::PyModule,
+    ) -> pyo3::PyResult<()> {
+        m.add_class::<FeeRate>()?;
+        m.getattr("FeeRate")?.setattr("__module__", m.name()?)?;
+        m.add_function(pyo3::wrap_pyfunction!(greet, m)?)?;
+        crate::langs::add_logging(m)?;
+        Ok(())
+    }
+    #[pyo3::prelude::pyclass]
+    pub struct FeeRate {
+        inner: super::FeeRate,
+    }
+    #[pyo3::prelude::pymethods]
+    impl FeeRate {
+        #[args(sat_per_vb = "None")]
+        #[staticmethod]
+        fn from_sat_per_vb(sat_per_vb: Option<f32>) -> Self {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_sat_per_vb;
+            let __default_sat_per_vb;
+            let sat_per_vb = match sat_per_vb {
+                Some(sat_per_vb) => {
+                    _temp_sat_per_vb = sat_per_vb;
+                    _temp_sat_per_vb
+                }
+                None => {
+                    __default_sat_per_vb = 1.0;
+                    __default_sat_per_vb
+                }
+            };
+            let mut block_closure = move || {
+                { super::FeeRate::from_sat_per_vb(sat_per_vb).into() }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[args(min = "None")]
+        fn fee_for(&self, vbytes: u64, min: Option<&FeeRate>) -> u64 {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_vbytes = (vbytes);
+            let vbytes = _temp_vbytes;
+            let _temp_min;
+            let __default_min;
+            let min = match min {
+                Some(min) => {
+                    _temp_min = min;
+                    _temp_min
+                }
+                None => {
+                    __default_min = FeeRate::default();
+                    &__default_min
+                }
+            };
+            let mut block_closure = move || {
+                { self.inner.fee_for(vbytes).max(min.inner.fee_for(vbytes)) }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+    }
+    #[pyo3::prelude::pyfunction]
+    #[args(name = "None")]
+    fn greet(name: Option<String>) -> String {
+        use crate::mapping::{MapTo, MapFrom};
+        use crate::langs::*;
+        let _temp_name;
+        let __default_name;
+        let name = match name {
+            Some(name) => {
+                _temp_name = name;
+                _temp_name
+            }
+            None => {
+                __default_name = "world".to_string();
+                __default_name
+            }
+        };
+        let mut block_closure = move || { { format!("Hello {}", name) } };
+        let __output = block_closure();
+        let __output = __output;
+        __output
+    }
+    impl crate::langs::MutablePyClass for FeeRate {}
+    #[pyo3::prelude::pymethods]
+    impl FeeRate {}
+    impl crate::common::ExposedStruct for FeeRate {}
+}
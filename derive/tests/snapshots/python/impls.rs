@@ -0,0 +1,79 @@
+pub mod impls {
+    #[pyo3::prelude::pymodule]
+    pub(super) fn impls(
+        py: pyo3::Python,
+        m: &pyo3::types::PyModule,
+    ) -> pyo3::PyResult<()> {
+        m.add_class::<Counter>()?;
+        m.getattr("Counter")?.setattr("__module__", m.name()?)?;
+        crate::langs::add_logging(m)?;
+        Ok(())
+    }
+    #[pyo3::prelude::pyclass]
+    pub struct Counter {
+        inner: super::Counter,
+    }
+    #[pyo3::prelude::pymethods]
+    impl Counter {
+        #[new]
+        #[allow(unused_variables)]
+        fn new(start: u32, py: pyo3::Python<'_>) -> Self {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_start = (start);
+            let start = _temp_start;
+            let mut block_closure = move || { { super::Counter::new(start).into() } };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[staticmethod]
+        fn zero() -> Self {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let mut block_closure = move || { { super::Counter::new(0).into() } };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        fn get(&self) -> u32 {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let mut block_closure = move || { { self.inner.get() } };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        fn increment(&mut self, by: u32) -> () {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_by = (by);
+            let by = _temp_by;
+            let mut block_closure = move || {
+                {
+                    crate::langs::assert_mutable::<Self>();
+                    self.inner.increment(by)
+                }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        fn label(&self, prefix: String) -> String {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_prefix = (prefix);
+            let prefix = _temp_prefix;
+            let mut block_closure = move || {
+                { format!("{}{}", prefix, self.inner.get()) }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+    }
+    impl crate::langs::MutablePyClass for Counter {}
+    #[pyo3::prelude::pymethods]
+    impl Counter {}
+    impl crate::common::ExposedStruct for Counter {}
+}
@@ -0,0 +1,113 @@
+pub mod outer {
+    #[pyo3::prelude::pymodule]
+    pub(super) fn outer(
+        py: pyo3::Python,
+        m: &pyo3::types::PyModule,
+    ) -> pyo3::PyResult<()> {
+        m.add_function(pyo3::wrap_pyfunction!(version, m)?)?;
+        let submod = pyo3::types::PyModule::new(py, "inner")?;
+        let qualified_name = format!("{}.{}", m.name() ?, "inner");
+        submod.setattr("__name__", &qualified_name)?;
+        py.import("sys")?.getattr("modules")?.set_item(&qualified_name, submod)?;
+        inner::inner(py, submod)?;
+        m.add("inner", submod)?;
+        let submod = pyo3::types::PyModule::new(py, "prefixed")?;
+        let qualified_name = format!("{}.{}", m.name() ?, "prefixed");
+        submod.setattr("__name__", &qualified_name)?;
+        py.import("sys")?.getattr("modules")?.set_item(&qualified_name, submod)?;
+        prefixed::prefixed(py, submod)?;
+        m.add("prefixed", submod)?;
+        crate::langs::add_logging(m)?;
+        Ok(())
+    }
+    #[pyo3::prelude::pyfunction]
+    fn version() -> u32 {
+        use crate::mapping::{MapTo, MapFrom};
+        use crate::langs::*;
+        let mut block_closure = move || { { 1 } };
+        let __output = block_closure();
+        let __output = __output;
+        __output
+    }
+    pub mod inner {
+        pub(super) fn inner(
+            py: pyo3::Python,
+            m: &pyo3::types::PyModule,
+        ) -> pyo3::PyResult<()> {
+            m.add_class::<Thing>()?;
+            m.getattr("Thing")?.setattr("__module__", m.name()?)?;
+            m.add_function(pyo3::wrap_pyfunction!(count, m)?)?;
+            Ok(())
+        }
+        #[pyo3::prelude::pyclass]
+        pub struct Thing {
+            inner: super::Thing,
+        }
+        #[pyo3::prelude::pymethods]
+        impl Thing {
+            #[staticmethod]
+            fn new() -> Self {
+                use crate::mapping::{MapTo, MapFrom};
+                use crate::langs::*;
+                let mut block_closure = move || { { super::Thing::default().into() } };
+                let __output = block_closure();
+                let __output = __output;
+                __output
+            }
+        }
+        #[pyo3::prelude::pyfunction]
+        fn count() -> u32 {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let mut block_closure = move || { { 0 } };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[pyo3::prelude::pymethods]
+        impl Thing {}
+        impl crate::common::ExposedStruct for Thing {}
+    }
+    pub mod prefixed {
+        pub(super) fn prefixed(
+            py: pyo3::Python,
+            m: &pyo3::types::PyModule,
+        ) -> pyo3::PyResult<()> {
+            m.add_function(pyo3::wrap_pyfunction!(count, m)?)?;
+            let submod = pyo3::types::PyModule::new(py, "nested")?;
+            let qualified_name = format!("{}.{}", m.name() ?, "nested");
+            submod.setattr("__name__", &qualified_name)?;
+            py.import("sys")?.getattr("modules")?.set_item(&qualified_name, submod)?;
+            nested::nested(py, submod)?;
+            m.add("nested", submod)?;
+            Ok(())
+        }
+        #[pyo3::prelude::pyfunction]
+        fn count() -> u32 {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let mut block_closure = move || { { 0 } };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        pub mod nested {
+            pub(super) fn nested(
+                py: pyo3::Python,
+                m: &pyo3::types::PyModule,
+            ) -> pyo3::PyResult<()> {
+                m.add_function(pyo3::wrap_pyfunction!(count, m)?)?;
+                Ok(())
+            }
+            #[pyo3::prelude::pyfunction]
+            fn count() -> u32 {
+                use crate::mapping::{MapTo, MapFrom};
+                use crate::langs::*;
+                let mut block_closure = move || { { 0 } };
+                let __output = block_closure();
+                let __output = __output;
+                __output
+            }
+        }
+    }
+}
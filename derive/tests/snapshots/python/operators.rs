@@ -0,0 +1,84 @@
+pub mod operators {
+    #[pyo3::prelude::pymodule]
+    pub(super) fn operators(
+        py: pyo3::Python,
+        m: &pyo3::types::PyModule,
+    ) -> pyo3::PyResult<()> {
+        m.add_class::<Amount>()?;
+        m.getattr("Amount")?.setattr("__module__", m.name()?)?;
+        m.add_class::<Script>()?;
+        m.getattr("Script")?.setattr("__module__", m.name()?)?;
+        crate::langs::add_logging(m)?;
+        Ok(())
+    }
+    use super::Error;
+    #[pyo3::prelude::pyclass]
+    pub struct Amount {
+        inner: super::Amount,
+    }
+    #[pyo3::prelude::pymethods]
+    impl Amount {}
+    impl std::ops::Add for Amount {
+        type Output = Result<Amount, Error>;
+        fn add(self, rhs: Self) -> Self::Output {
+            Ok(self.inner.checked_add(rhs.inner)?.into())
+        }
+    }
+    impl std::ops::Mul<u64> for Amount {
+        type Output = Amount;
+        fn mul(self, rhs: u64) -> Self::Output {
+            (self.inner * rhs).into()
+        }
+    }
+    impl std::ops::Neg for Amount {
+        type Output = Amount;
+        fn neg(self) -> Self::Output {
+            (-self.inner).into()
+        }
+    }
+    #[pyo3::prelude::pyclass]
+    pub struct Script {
+        inner: super::Script,
+    }
+    #[pyo3::prelude::pymethods]
+    impl Script {}
+    impl std::ops::Index<usize> for Script {
+        type Output = u8;
+        fn index(&self, index: usize) -> &Self::Output {
+            &self.inner.as_bytes()[index]
+        }
+    }
+    impl crate::common::CheckedIndex<usize> for Script {
+        fn checked_index(&self, index: usize) -> Option<&u8> {
+            self.inner.as_bytes().get(index)
+        }
+    }
+    impl crate::langs::MutablePyClass for Amount {}
+    #[pyo3::prelude::pymethods]
+    impl Amount {}
+    impl crate::common::ExposedStruct for Amount {}
+    impl crate::langs::MutablePyClass for Script {}
+    #[pyo3::prelude::pymethods]
+    impl Script {}
+    impl crate::common::ExposedStruct for Script {}
+    #[pyo3::prelude::pyproto]
+    impl pyo3::class::PyNumberProtocol for Amount {
+        fn __add__(lhs: Self, rhs: Self) -> Result<Self, Error> {
+            std::ops::Add::add(lhs, rhs)
+        }
+        fn __mul__(lhs: Self, rhs: u64) -> Self {
+            std::ops::Mul::mul(lhs, rhs)
+        }
+        fn __neg__(&self) -> Self {
+            std::ops::Neg::neg(self.clone())
+        }
+    }
+    #[pyo3::prelude::pyproto]
+    impl pyo3::class::PyMappingProtocol for Script {
+        fn __getitem__(&self, key: usize) -> Result<u8, crate::common::IndexError> {
+            crate::common::CheckedIndex::checked_index(self, key)
+                .cloned()
+                .ok_or(crate::common::IndexError)
+        }
+    }
+}
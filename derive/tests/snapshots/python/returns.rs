@@ -0,0 +1,82 @@
+pub mod returns {
+    #[pyo3::prelude::pymodule]
+    pub(super) fn returns(
+        py: pyo3::Python,
+        m: &pyo3::types::PyModule,
+    ) -> pyo3::PyResult<()> {
+        m.add_class::<Item>()?;
+        m.getattr("Item")?.setattr("__module__", m.name()?)?;
+        m.add_function(pyo3::wrap_pyfunction!(last_error, m)?)?;
+        crate::langs::add_logging(m)?;
+        Ok(())
+    }
+    use super::Error;
+    #[pyo3::prelude::pyclass]
+    pub struct Item {
+        inner: super::Item,
+    }
+    #[pyo3::prelude::pymethods]
+    impl Item {
+        #[staticmethod]
+        fn parse(s: String) -> Result<Self, Error> {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_s = (s);
+            let s = _temp_s;
+            let mut block_closure = move || { { Ok(s.parse::<super::Item>()?.into()) } };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        fn check(&self) -> Result<(), super::Error> {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let mut block_closure = move || { { self.inner.check() } };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[staticmethod]
+        fn find(name: String) -> Option<Self> {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_name = (name);
+            let name = _temp_name;
+            let mut block_closure = move || {
+                { super::Item::find(&name).map(Into::into) }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        fn to_bytes(&self) -> Vec<u8> {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let mut block_closure = move || { { self.inner.to_bytes() } };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        fn name(&self) -> String {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let mut block_closure = move || { { self.inner.name().to_string() } };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+    }
+    #[pyo3::prelude::pyfunction]
+    fn last_error() -> Error {
+        use crate::mapping::{MapTo, MapFrom};
+        use crate::langs::*;
+        let mut block_closure = move || { { super::last_error() } };
+        let __output = block_closure();
+        let __output = __output;
+        __output
+    }
+    impl crate::langs::MutablePyClass for Item {}
+    #[pyo3::prelude::pymethods]
+    impl Item {}
+    impl crate::common::ExposedStruct for Item {}
+}
@@ -0,0 +1,253 @@
+pub mod structs {
+    #[pyo3::prelude::pymodule]
+    pub(super) fn structs(
+        py: pyo3::Python,
+        m: &pyo3::types::PyModule,
+    ) -> pyo3::PyResult<()> {
+        m.add_class::<Opaque>()?;
+        m.getattr("Opaque")?.setattr("__module__", m.name()?)?;
+        m.add_class::<Shared>()?;
+        m.getattr("Shared")?.setattr("__module__", m.name()?)?;
+        m.add_class::<WithFields>()?;
+        m.getattr("WithFields")?.setattr("__module__", m.name()?)?;
+        m.add_class::<Point>()?;
+        m.getattr("Point")?.setattr("__module__", m.name()?)?;
+        m.add_class::<Script>()?;
+        m.getattr("Script")?.setattr("__module__", m.name()?)?;
+        m.add_class::<WithOpaque>()?;
+        m.getattr("WithOpaque")?.setattr("__module__", m.name()?)?;
+        crate::langs::add_logging(m)?;
+        Ok(())
+    }
+    #[pyo3::prelude::pyclass]
+    pub struct Opaque {
+        inner: super::Opaque,
+    }
+    #[pyo3::prelude::pymethods]
+    impl Opaque {}
+    #[pyo3::prelude::pyclass]
+    pub struct Shared {
+        inner: super::Shared,
+    }
+    #[pyo3::prelude::pyclass]
+    pub struct WithFields {
+        pub(crate) shared: <Shared as crate::common::WrappedStructField>::Store,
+        pub(crate) value: <u32 as crate::common::WrappedStructField>::Store,
+    }
+    #[pyo3::prelude::pymethods]
+    impl WithFields {}
+    #[pyo3::prelude::pyclass]
+    pub struct Point {
+        pub(crate) x: <i32 as crate::common::WrappedStructField>::Store,
+        pub(crate) y: <i32 as crate::common::WrappedStructField>::Store,
+    }
+    #[pyo3::prelude::pyclass]
+    pub struct Script {
+        inner: super::Script,
+    }
+    #[pyo3::prelude::pymethods]
+    impl Script {}
+    #[pyo3::prelude::pyclass]
+    pub struct WithOpaque {
+        pub(crate) script: <Script as crate::common::WrappedStructField>::Store,
+        value: u32,
+    }
+    #[pyo3::prelude::pymethods]
+    impl WithOpaque {}
+    impl crate::langs::MutablePyClass for Opaque {}
+    #[pyo3::prelude::pymethods]
+    impl Opaque {}
+    #[pyo3::prelude::pyproto]
+    impl pyo3::class::PyObjectProtocol for Opaque {
+        fn __str__(&self) -> String {
+            self.to_string()
+        }
+    }
+    impl crate::common::ExposedStruct for Opaque {}
+    #[pyo3::prelude::pymethods]
+    impl Shared {}
+    impl crate::common::ExposedStruct for Shared {}
+    impl crate::langs::MutablePyClass for WithFields {}
+    #[pyo3::prelude::pymethods]
+    impl WithFields {
+        #[getter]
+        fn get_shared(
+            &mut self,
+        ) -> <Shared as crate::common::WrappedStructField>::Getter {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let mut block_closure = move || {
+                {
+                    use crate::common::WrappedStructField;
+                    Shared::wrap_get(&mut self.shared)
+                }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[setter]
+        fn set_shared(
+            &mut self,
+            shared: <Shared as crate::common::WrappedStructField>::Setter,
+        ) -> () {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_shared = (shared);
+            let shared = _temp_shared;
+            let mut block_closure = move || {
+                {
+                    crate::langs::assert_mutable::<Self>();
+                    use crate::common::WrappedStructField;
+                    self
+                        .shared = Shared::wrap_set(
+                        crate::mapping::MapFrom::map_from(shared),
+                    );
+                }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[getter]
+        fn get_value(&mut self) -> <u32 as crate::common::WrappedStructField>::Getter {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let mut block_closure = move || {
+                {
+                    use crate::common::WrappedStructField;
+                    u32::wrap_get(&mut self.value)
+                }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[setter]
+        fn set_value(
+            &mut self,
+            value: <u32 as crate::common::WrappedStructField>::Setter,
+        ) -> Result<(), crate::common::ValidationError> {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_value = (value);
+            let value = _temp_value;
+            let mut block_closure = move || {
+                {
+                    crate::langs::assert_mutable::<Self>();
+                    use crate::common::WrappedStructField;
+                    let value: u32 = crate::mapping::MapFrom::map_from(value);
+                    WithFields::validate_value(self, &value)
+                        .map_err(crate::common::ValidationError::from_display)?;
+                    self.value = u32::wrap_set(value);
+                    Ok(())
+                }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+    }
+    impl crate::common::ExposedStruct for WithFields {}
+    impl crate::langs::MutablePyClass for Point {}
+    #[pyo3::prelude::pymethods]
+    impl Point {
+        #[getter]
+        fn get_x(&mut self) -> <i32 as crate::common::WrappedStructField>::Getter {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let mut block_closure = move || {
+                {
+                    use crate::common::WrappedStructField;
+                    i32::wrap_get(&mut self.x)
+                }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[setter]
+        fn set_x(
+            &mut self,
+            x: <i32 as crate::common::WrappedStructField>::Setter,
+        ) -> () {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_x = (x);
+            let x = _temp_x;
+            let mut block_closure = move || {
+                {
+                    crate::langs::assert_mutable::<Self>();
+                    use crate::common::WrappedStructField;
+                    self.x = i32::wrap_set(crate::mapping::MapFrom::map_from(x));
+                }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[getter]
+        fn get_y(&mut self) -> <i32 as crate::common::WrappedStructField>::Getter {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let mut block_closure = move || {
+                {
+                    use crate::common::WrappedStructField;
+                    i32::wrap_get(&mut self.y)
+                }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+    }
+    impl crate::common::ExposedStruct for Point {}
+    impl crate::langs::MutablePyClass for Script {}
+    #[pyo3::prelude::pymethods]
+    impl Script {}
+    impl crate::common::ExposedStruct for Script {}
+    impl crate::langs::MutablePyClass for WithOpaque {}
+    #[pyo3::prelude::pymethods]
+    impl WithOpaque {
+        #[getter]
+        fn get_script(
+            &mut self,
+        ) -> <Script as crate::common::WrappedStructField>::Getter {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let mut block_closure = move || {
+                {
+                    use crate::common::WrappedStructField;
+                    Script::wrap_get(&mut self.script)
+                }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[setter]
+        fn set_script(
+            &mut self,
+            script: <Script as crate::common::WrappedStructField>::Setter,
+        ) -> () {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_script = (script);
+            let script = _temp_script;
+            let mut block_closure = move || {
+                {
+                    crate::langs::assert_mutable::<Self>();
+                    use crate::common::WrappedStructField;
+                    self
+                        .script = Script::wrap_set(
+                        crate::mapping::MapFrom::map_from(script),
+                    );
+                }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+    }
+    impl crate::common::ExposedStruct for WithOpaque {}
+}
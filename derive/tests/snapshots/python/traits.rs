@@ -0,0 +1,88 @@
+pub mod traits {
+    #[pyo3::prelude::pymodule]
+    pub(super) fn traits(
+        py: pyo3::Python,
+        m: &pyo3::types::PyModule,
+    ) -> pyo3::PyResult<()> {
+        m.add_class::<ListenerStruct>()?;
+        m.getattr("ListenerStruct")?.setattr("__module__", m.name()?)?;
+        crate::langs::add_logging(m)?;
+        Ok(())
+    }
+    pub trait Listener: super::Listener {
+        fn _wrapper_on_event(&self, event: String) -> u32;
+    }
+    impl crate::langs::MutablePyClass for ListenerStruct {}
+    #[pyo3::prelude::pymethods]
+    impl ListenerStruct {}
+    #[pyo3::prelude::pyclass(subclass)]
+    pub struct ListenerStruct {
+        native: Option<Box<dyn super::Listener + Send>>,
+        #[pyo3(set)]
+        python: Option<pyo3::PyObject>,
+    }
+    #[pyo3::prelude::pymethods]
+    impl ListenerStruct {
+        #[new]
+        #[allow(unused_variables)]
+        pub fn new(py: pyo3::Python<'_>) -> Self {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let mut block_closure = move || {
+                {
+                    ListenerStruct {
+                        native: None,
+                        python: None,
+                    }
+                }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        pub fn rust_on_event(&self, event: String) -> u32 {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_event = (event);
+            let event = _temp_event;
+            let mut block_closure = move || {
+                {
+                    if let Some(native) = &self.native {
+                        native.on_event(event)
+                    } else if let Some(python) = &self.python {
+                        pyo3::prelude::Python::with_gil(|py| -> pyo3::PyResult<u32> {
+                                Ok(
+                                    python
+                                        .call_method1(py, "rust_on_event", (event,))?
+                                        .extract(py)?,
+                                )
+                            })
+                            .expect("Python call failed")
+                    } else {
+                        panic!(
+                            "`self` reference not found. In your subclass constructor add: `self.python = self`"
+                        )
+                    }
+                }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+    }
+    impl Listener for ListenerStruct {
+        fn _wrapper_on_event(&self, event: String) -> u32 {
+            self.rust_on_event(event)
+        }
+    }
+    impl<T: 'static + super::Listener + Sized + Send> crate::langs::IntoTraitStruct
+    for T {
+        type Target = ListenerStruct;
+        fn into_trait_struct(self) -> Self::Target {
+            ListenerStruct {
+                native: Some(Box::new(self)),
+                python: None,
+            }
+        }
+    }
+}
@@ -0,0 +1,96 @@
+pub mod blocking {
+    #[magnus::init]
+    fn __rb_init(ruby: &magnus::Ruby) -> Result<(), magnus::Error> {
+        let m = ruby.define_module("Blocking")?;
+        crate::langs::define_errors(ruby, m, &["Error"])?;
+        blocking(ruby, m)
+    }
+    #[allow(unused_variables)]
+    pub(super) fn blocking(
+        ruby: &magnus::Ruby,
+        m: magnus::RModule,
+    ) -> Result<(), magnus::Error> {
+        use magnus::{Module, Object};
+        let class = m.define_class("Wallet", ruby.class_object())?;
+        class.define_singleton_method("new", magnus::function!(Wallet::__rb_new, 1))?;
+        class.define_method("sync", magnus::method!(Wallet::__rb_sync, 2))?;
+        class.define_method("sign", magnus::method!(Wallet::__rb_sign, 1))?;
+        m.define_module_function("broadcast", magnus::function!(broadcast, 2))?;
+        Ok(())
+    }
+    use super::Error;
+    #[derive(Clone)]
+    pub struct Wallet {
+        inner: super::Wallet,
+    }
+    impl Wallet {
+        fn new(descriptor: String) -> Self {
+            super::Wallet::new(&descriptor).into()
+        }
+        fn sync(&self, url: String, stop_gap: usize) -> Result<u64, Error> {
+            self.inner.sync(&url, stop_gap)
+        }
+        fn sign(&self, psbt: &[u8]) -> Vec<u8> {
+            self.inner.sign(psbt)
+        }
+        pub fn __rb_new(descriptor: String) -> Self {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_descriptor = (descriptor);
+            let descriptor = _temp_descriptor;
+            let __output = Self::new(descriptor);
+            let __output = __output;
+            __output
+        }
+        pub fn __rb_sync(
+            rb_self: crate::langs::RbRef<Self>,
+            url: String,
+            stop_gap: usize,
+        ) -> Result<u64, crate::langs::RubyError> {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_url = (url);
+            let url = _temp_url;
+            let _temp_stop_gap = (stop_gap);
+            let stop_gap = _temp_stop_gap;
+            let __output = Self::sync(&rb_self, url, stop_gap);
+            let __output = __output.map_err(crate::langs::RubyError::from)?;
+            let __output = __output;
+            Ok(__output)
+        }
+        pub fn __rb_sign(
+            rb_self: crate::langs::RbRef<Self>,
+            psbt: magnus::RString,
+        ) -> magnus::RString {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_psbt = (psbt);
+            let psbt = &<Vec<u8>>::map_from(_temp_psbt);
+            let __output = Self::sign(&rb_self, psbt);
+            let __output: Vec<u8> = __output;
+            let __output = __output.map_to();
+            __output
+        }
+    }
+    pub fn broadcast(
+        url: String,
+        tx: magnus::RString,
+    ) -> Result<String, crate::langs::RubyError> {
+        use crate::mapping::{MapTo, MapFrom};
+        use crate::langs::*;
+        let _temp_url = (url);
+        let url = _temp_url;
+        let _temp_tx = (tx);
+        let tx = <Vec<u8>>::map_from(_temp_tx);
+        let mut block_closure = move || -> Result<String, Error> {
+            { super::broadcast(&url, &tx) }
+        };
+        let __output = block_closure();
+        let __output = __output.map_err(crate::langs::RubyError::from)?;
+        let __output = __output;
+        Ok(__output)
+    }
+    impl Wallet {}
+    crate::impl_ruby_class!(Wallet, "Blocking::Wallet");
+    impl crate::common::ExposedStruct for Wallet {}
+}
@@ -0,0 +1,85 @@
+pub mod bytes {
+    #[magnus::init]
+    fn __rb_init(ruby: &magnus::Ruby) -> Result<(), magnus::Error> {
+        let m = ruby.define_module("Bytes")?;
+        crate::langs::define_errors(ruby, m, &[])?;
+        bytes(ruby, m)
+    }
+    #[allow(unused_variables)]
+    pub(super) fn bytes(
+        ruby: &magnus::Ruby,
+        m: magnus::RModule,
+    ) -> Result<(), magnus::Error> {
+        use magnus::{Module, Object};
+        let class = m.define_class("Script", ruby.class_object())?;
+        class.define_method("to_s", magnus::method!(Script::__rb___to_s, 0))?;
+        class.define_method("inspect", magnus::method!(Script::__rb___inspect, 0))?;
+        class.define_method("to_bytes", magnus::method!(Script::__rb___to_bytes, 0))?;
+        class
+            .define_singleton_method(
+                "from_bytes",
+                magnus::function!(Script::__rb___from_bytes, 1),
+            )?;
+        Ok(())
+    }
+    pub struct Script {
+        inner: super::Script,
+    }
+    impl Script {}
+    impl crate::common::ByteContainer for Script {
+        fn as_bytes(&self) -> &[u8] {
+            self.inner.as_bytes()
+        }
+        fn from_bytes(bytes: Vec<u8>) -> Self {
+            Script { inner: bytes.into() }
+        }
+    }
+    impl Script {}
+    impl Script {
+        fn __to_s(&self) -> String {
+            self.to_string()
+        }
+        fn __inspect(&self) -> String {
+            format!("{:?}", self)
+        }
+        fn __to_bytes(&self) -> Vec<u8> {
+            crate::common::ByteContainer::as_bytes(self).to_vec()
+        }
+        fn __from_bytes(bytes: Vec<u8>) -> Self {
+            <Self as crate::common::ByteContainer>::from_bytes(bytes)
+        }
+        pub fn __rb___to_s(rb_self: crate::langs::RbRef<Self>) -> String {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let __output = Self::__to_s(&rb_self);
+            let __output = __output;
+            __output
+        }
+        pub fn __rb___inspect(rb_self: crate::langs::RbRef<Self>) -> String {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let __output = Self::__inspect(&rb_self);
+            let __output = __output;
+            __output
+        }
+        pub fn __rb___to_bytes(rb_self: crate::langs::RbRef<Self>) -> magnus::RString {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let __output = Self::__to_bytes(&rb_self);
+            let __output: Vec<u8> = __output;
+            let __output = __output.map_to();
+            __output
+        }
+        pub fn __rb___from_bytes(bytes: magnus::RString) -> Self {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_bytes = (bytes);
+            let bytes = <Vec<u8>>::map_from(_temp_bytes);
+            let __output = Self::__from_bytes(bytes);
+            let __output = __output;
+            __output
+        }
+    }
+    crate::impl_ruby_class!(Script, "Bytes::Script");
+    impl crate::common::ExposedStruct for Script {}
+}
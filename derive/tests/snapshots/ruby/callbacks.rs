@@ -0,0 +1,22 @@
+pub mod callbacks {
+    #[magnus::init]
+    fn __rb_init(ruby: &magnus::Ruby) -> Result<(), magnus::Error> {
+        let m = ruby.define_module("Callbacks")?;
+        crate::langs::define_errors(ruby, m, &[])?;
+        callbacks(ruby, m)
+    }
+    #[allow(unused_variables)]
+    pub(super) fn callbacks(
+        ruby: &magnus::Ruby,
+        m: magnus::RModule,
+    ) -> Result<(), magnus::Error> {
+        use magnus::{Module, Object};
+        Ok(())
+    }
+    fn apply(value: u32, cb: fn(value: u32) -> u32) -> u32 {
+        cb(value)
+    }
+    fn describe(cb: fn(name: String, value: u32)) {
+        cb("value".to_string(), 42)
+    }
+}
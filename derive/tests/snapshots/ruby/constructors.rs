@@ -0,0 +1,76 @@
+pub mod constructors {
+    #[magnus::init]
+    fn __rb_init(ruby: &magnus::Ruby) -> Result<(), magnus::Error> {
+        let m = ruby.define_module("Constructors")?;
+        crate::langs::define_errors(ruby, m, &["Error"])?;
+        constructors(ruby, m)
+    }
+    #[allow(unused_variables)]
+    pub(super) fn constructors(
+        ruby: &magnus::Ruby,
+        m: magnus::RModule,
+    ) -> Result<(), magnus::Error> {
+        use magnus::{Module, Object};
+        let class = m.define_class("Network", ruby.class_object())?;
+        class
+            .define_singleton_method(
+                "new",
+                magnus::function!(Network::__rb_from_string, 1),
+            )?;
+        class
+            .define_singleton_method(
+                "bitcoin",
+                magnus::function!(Network::__rb_bitcoin, 0),
+            )?;
+        class
+            .define_singleton_method(
+                "from_magic",
+                magnus::function!(Network::__rb_from_magic, 1),
+            )?;
+        Ok(())
+    }
+    use super::Error;
+    pub struct Network {
+        inner: super::Network,
+    }
+    impl Network {
+        fn from_string(s: String) -> Result<Self, Error> {
+            Ok(s.parse::<super::Network>()?.into())
+        }
+        fn bitcoin() -> Self {
+            super::Network::Bitcoin.into()
+        }
+        fn from_magic(magic: u32) -> Option<Self> {
+            super::Network::from_magic(magic).map(Into::into)
+        }
+        pub fn __rb_from_string(s: String) -> Result<Self, crate::langs::RubyError> {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_s = (s);
+            let s = _temp_s;
+            let __output = Self::from_string(s);
+            let __output = __output.map_err(crate::langs::RubyError::from)?;
+            let __output = __output;
+            Ok(__output)
+        }
+        pub fn __rb_bitcoin() -> Self {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let __output = Self::bitcoin();
+            let __output = __output;
+            __output
+        }
+        pub fn __rb_from_magic(magic: u32) -> Option<Self> {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_magic = (magic);
+            let magic = _temp_magic;
+            let __output = Self::from_magic(magic);
+            let __output = __output;
+            __output
+        }
+    }
+    impl Network {}
+    crate::impl_ruby_class!(Network, "Constructors::Network");
+    impl crate::common::ExposedStruct for Network {}
+}
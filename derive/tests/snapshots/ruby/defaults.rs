@@ -0,0 +1,133 @@
+pub mod defaults {
+    #[magnus::init]
+    fn __rb_init(ruby: &magnus::Ruby) -> Result<(), magnus::Error> {
+        let m = ruby.define_module("Defaults")?;
+        crate::langs::define_errors(ruby, m, &[])?;
+        defaults(ruby, m)
+    }
+    #[allow(unused_variables)]
+    pub(super) fn defaults(
+        ruby: &magnus::Ruby,
+        m: magnus::RModule,
+    ) -> Result<(), magnus::Error> {
+        use magnus::{Module, Object};
+        let class = m.define_class("FeeRate", ruby.class_object())?;
+        class
+            .define_singleton_method(
+                "from_sat_per_vb",
+                magnus::function!(FeeRate::__rb_from_sat_per_vb, - 1),
+            )?;
+        class.define_method("fee_for", magnus::method!(FeeRate::__rb_fee_for, - 1))?;
+        m.define_module_function("greet", magnus::function!(greet, - 1))?;
+        Ok(())
+    }
+    pub struct FeeRate {
+        inner: super::FeeRate,
+    }
+    impl FeeRate {
+        fn from_sat_per_vb(sat_per_vb: f32) -> Self {
+            super::FeeRate::from_sat_per_vb(sat_per_vb).into()
+        }
+        fn fee_for(&self, vbytes: u64, min: &FeeRate) -> u64 {
+            self.inner.fee_for(vbytes).max(min.inner.fee_for(vbytes))
+        }
+        pub fn __rb_from_sat_per_vb(
+            __args: &[magnus::Value],
+        ) -> Result<Self, crate::langs::RubyError> {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let __args = magnus::scan_args::scan_args::<
+                (),
+                (Option<f32>,),
+                (),
+                (),
+                (),
+                (),
+            >(__args)?;
+            let () = __args.required;
+            let (sat_per_vb,) = __args.optional;
+            let _temp_sat_per_vb;
+            let __default_sat_per_vb;
+            let sat_per_vb = match sat_per_vb {
+                Some(sat_per_vb) => {
+                    _temp_sat_per_vb = sat_per_vb;
+                    _temp_sat_per_vb
+                }
+                None => {
+                    __default_sat_per_vb = 1.0;
+                    __default_sat_per_vb
+                }
+            };
+            let __output = Self::from_sat_per_vb(sat_per_vb);
+            let __output = __output;
+            Ok(__output)
+        }
+        pub fn __rb_fee_for(
+            rb_self: crate::langs::RbRef<Self>,
+            __args: &[magnus::Value],
+        ) -> Result<u64, crate::langs::RubyError> {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let __args = magnus::scan_args::scan_args::<
+                (u64,),
+                (Option<crate::langs::RbRef<FeeRate>>,),
+                (),
+                (),
+                (),
+                (),
+            >(__args)?;
+            let (vbytes,) = __args.required;
+            let (min,) = __args.optional;
+            let _temp_vbytes = (vbytes);
+            let vbytes = _temp_vbytes;
+            let _temp_min;
+            let __default_min;
+            let min = match min {
+                Some(min) => {
+                    _temp_min = min;
+                    &*_temp_min
+                }
+                None => {
+                    __default_min = FeeRate::default();
+                    &__default_min
+                }
+            };
+            let __output = Self::fee_for(&rb_self, vbytes, min);
+            let __output = __output;
+            Ok(__output)
+        }
+    }
+    pub fn greet(__args: &[magnus::Value]) -> Result<String, crate::langs::RubyError> {
+        use crate::mapping::{MapTo, MapFrom};
+        use crate::langs::*;
+        let __args = magnus::scan_args::scan_args::<
+            (),
+            (Option<String>,),
+            (),
+            (),
+            (),
+            (),
+        >(__args)?;
+        let () = __args.required;
+        let (name,) = __args.optional;
+        let _temp_name;
+        let __default_name;
+        let name = match name {
+            Some(name) => {
+                _temp_name = name;
+                _temp_name
+            }
+            None => {
+                __default_name = "world".to_string();
+                __default_name
+            }
+        };
+        let mut block_closure = move || { { format!("Hello {}", name) } };
+        let __output = block_closure();
+        let __output = __output;
+        Ok(__output)
+    }
+    impl FeeRate {}
+    crate::impl_ruby_class!(FeeRate, "Defaults::FeeRate");
+    impl crate::common::ExposedStruct for FeeRate {}
+}
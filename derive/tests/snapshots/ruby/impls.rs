@@ -0,0 +1,86 @@
+pub mod impls {
+    #[magnus::init]
+    fn __rb_init(ruby: &magnus::Ruby) -> Result<(), magnus::Error> {
+        let m = ruby.define_module("Impls")?;
+        crate::langs::define_errors(ruby, m, &[])?;
+        impls(ruby, m)
+    }
+    #[allow(unused_variables)]
+    pub(super) fn impls(
+        ruby: &magnus::Ruby,
+        m: magnus::RModule,
+    ) -> Result<(), magnus::Error> {
+        use magnus::{Module, Object};
+        let class = m.define_class("Counter", ruby.class_object())?;
+        class.define_singleton_method("new", magnus::function!(Counter::__rb_new, 1))?;
+        class.define_singleton_method("zero", magnus::function!(Counter::__rb_zero, 0))?;
+        class.define_method("get", magnus::method!(Counter::__rb_get, 0))?;
+        class.define_method("increment", magnus::method!(Counter::__rb_increment, 1))?;
+        class.define_method("label", magnus::method!(Counter::__rb_label, 1))?;
+        Ok(())
+    }
+    pub struct Counter {
+        inner: super::Counter,
+    }
+    impl Counter {
+        fn new(start: u32) -> Self {
+            super::Counter::new(start).into()
+        }
+        fn zero() -> Self {
+            super::Counter::new(0).into()
+        }
+        fn get(&self) -> u32 {
+            self.inner.get()
+        }
+        fn increment(&mut self, by: u32) {
+            self.inner.increment(by)
+        }
+        fn label(&self, prefix: String) -> String {
+            format!("{}{}", prefix, self.inner.get())
+        }
+        pub fn __rb_new(start: u32) -> Self {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_start = (start);
+            let start = _temp_start;
+            let __output = Self::new(start);
+            let __output = __output;
+            __output
+        }
+        pub fn __rb_zero() -> Self {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let __output = Self::zero();
+            let __output = __output;
+            __output
+        }
+        pub fn __rb_get(rb_self: crate::langs::RbRef<Self>) -> u32 {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let __output = Self::get(&rb_self);
+            let __output = __output;
+            __output
+        }
+        pub fn __rb_increment(mut rb_self: crate::langs::RbRefMut<Self>, by: u32) -> () {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_by = (by);
+            let by = _temp_by;
+            let __output = Self::increment(&mut rb_self, by);
+            let __output = __output;
+            __output
+        }
+        pub fn __rb_label(rb_self: crate::langs::RbRef<Self>, prefix: String) -> String {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_prefix = (prefix);
+            let prefix = _temp_prefix;
+            let __output = Self::label(&rb_self, prefix);
+            let __output = __output;
+            __output
+        }
+    }
+    impl Counter {}
+    crate::impl_ruby_class!(Counter, "Impls::Counter");
+    impl crate::common::ExposedStruct for Counter {}
+}
@@ -0,0 +1,107 @@
+pub mod outer {
+    #[magnus::init]
+    fn __rb_init(ruby: &magnus::Ruby) -> Result<(), magnus::Error> {
+        let m = ruby.define_module("Outer")?;
+        crate::langs::define_errors(ruby, m, &[])?;
+        outer(ruby, m)
+    }
+    #[allow(unused_variables)]
+    pub(super) fn outer(
+        ruby: &magnus::Ruby,
+        m: magnus::RModule,
+    ) -> Result<(), magnus::Error> {
+        use magnus::{Module, Object};
+        m.define_module_function("version", magnus::function!(version, 0))?;
+        let submod = m.define_module("Inner")?;
+        inner::inner(ruby, submod)?;
+        let submod = m.define_module("Prefixed")?;
+        prefixed::prefixed(ruby, submod)?;
+        Ok(())
+    }
+    pub fn version() -> u32 {
+        use crate::mapping::{MapTo, MapFrom};
+        use crate::langs::*;
+        let mut block_closure = move || { { 1 } };
+        let __output = block_closure();
+        let __output = __output;
+        __output
+    }
+    pub mod inner {
+        #[allow(unused_variables)]
+        pub(super) fn inner(
+            ruby: &magnus::Ruby,
+            m: magnus::RModule,
+        ) -> Result<(), magnus::Error> {
+            use magnus::{Module, Object};
+            let class = m.define_class("Thing", ruby.class_object())?;
+            class.define_singleton_method("new", magnus::function!(Thing::__rb_new, 0))?;
+            m.define_module_function("count", magnus::function!(count, 0))?;
+            Ok(())
+        }
+        pub struct Thing {
+            inner: super::Thing,
+        }
+        impl Thing {
+            fn new() -> Self {
+                super::Thing::default().into()
+            }
+            pub fn __rb_new() -> Self {
+                use crate::mapping::{MapTo, MapFrom};
+                use crate::langs::*;
+                let __output = Self::new();
+                let __output = __output;
+                __output
+            }
+        }
+        pub fn count() -> u32 {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let mut block_closure = move || { { 0 } };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        impl Thing {}
+        crate::impl_ruby_class!(Thing, "Outer::Inner::Thing");
+        impl crate::common::ExposedStruct for Thing {}
+    }
+    pub mod prefixed {
+        #[allow(unused_variables)]
+        pub(super) fn prefixed(
+            ruby: &magnus::Ruby,
+            m: magnus::RModule,
+        ) -> Result<(), magnus::Error> {
+            use magnus::{Module, Object};
+            let submod = m.define_module("Nested")?;
+            nested::nested(ruby, submod)?;
+            Ok(())
+        }
+        pub fn count() -> u32 {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let mut block_closure = move || { { 0 } };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        pub mod nested {
+            #[allow(unused_variables)]
+            pub(super) fn nested(
+                ruby: &magnus::Ruby,
+                m: magnus::RModule,
+            ) -> Result<(), magnus::Error> {
+                use magnus::{Module, Object};
+                m.define_module_function("count", magnus::function!(count, 0))?;
+                Ok(())
+            }
+            pub fn count() -> u32 {
+                use crate::mapping::{MapTo, MapFrom};
+                use crate::langs::*;
+                let mut block_closure = move || { { 0 } };
+                let __output = block_closure();
+                let __output = __output;
+                __output
+            }
+        }
+    }
+}
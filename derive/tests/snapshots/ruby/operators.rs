@@ -0,0 +1,126 @@
+pub mod operators {
+    #[magnus::init]
+    fn __rb_init(ruby: &magnus::Ruby) -> Result<(), magnus::Error> {
+        let m = ruby.define_module("Operators")?;
+        crate::langs::define_errors(ruby, m, &["Error"])?;
+        operators(ruby, m)
+    }
+    #[allow(unused_variables)]
+    pub(super) fn operators(
+        ruby: &magnus::Ruby,
+        m: magnus::RModule,
+    ) -> Result<(), magnus::Error> {
+        use magnus::{Module, Object};
+        let class = m.define_class("Amount", ruby.class_object())?;
+        class.define_method("+", magnus::method!(Amount::__rb_add, 1))?;
+        class.define_method("*", magnus::method!(Amount::__rb_mul, 1))?;
+        class.define_method("-@", magnus::method!(Amount::__rb_neg, 0))?;
+        let class = m.define_class("Script", ruby.class_object())?;
+        class.define_method("[]", magnus::method!(Script::__rb_index, 1))?;
+        Ok(())
+    }
+    use super::Error;
+    pub struct Amount {
+        inner: super::Amount,
+    }
+    impl Amount {}
+    impl std::ops::Add for Amount {
+        type Output = Result<Amount, Error>;
+        fn add(self, rhs: Self) -> Self::Output {
+            Ok(self.inner.checked_add(rhs.inner)?.into())
+        }
+    }
+    impl std::ops::Mul<u64> for Amount {
+        type Output = Amount;
+        fn mul(self, rhs: u64) -> Self::Output {
+            (self.inner * rhs).into()
+        }
+    }
+    impl std::ops::Neg for Amount {
+        type Output = Amount;
+        fn neg(self) -> Self::Output {
+            (-self.inner).into()
+        }
+    }
+    pub struct Script {
+        inner: super::Script,
+    }
+    impl Script {}
+    impl std::ops::Index<usize> for Script {
+        type Output = u8;
+        fn index(&self, index: usize) -> &Self::Output {
+            &self.inner.as_bytes()[index]
+        }
+    }
+    impl crate::common::CheckedIndex<usize> for Script {
+        fn checked_index(&self, index: usize) -> Option<&u8> {
+            self.inner.as_bytes().get(index)
+        }
+    }
+    impl Amount {}
+    crate::impl_ruby_class!(Amount, "Operators::Amount");
+    impl crate::common::ExposedStruct for Amount {}
+    impl Script {}
+    crate::impl_ruby_class!(Script, "Operators::Script");
+    impl crate::common::ExposedStruct for Script {}
+    impl Amount {
+        fn add(&self, rhs: &Self) -> Result<Self, Error> {
+            std::ops::Add::add(self.clone(), rhs.clone())
+        }
+        fn mul(&self, rhs: u64) -> Self {
+            std::ops::Mul::mul(self.clone(), rhs)
+        }
+        fn neg(&self) -> Self {
+            std::ops::Neg::neg(self.clone())
+        }
+        pub fn __rb_add(
+            rb_self: crate::langs::RbRef<Self>,
+            rhs: crate::langs::RbRef<Self>,
+        ) -> Result<Self, crate::langs::RubyError> {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_rhs = (rhs);
+            let rhs = &*_temp_rhs;
+            let __output = Self::add(&rb_self, rhs);
+            let __output = __output.map_err(crate::langs::RubyError::from)?;
+            let __output = __output;
+            Ok(__output)
+        }
+        pub fn __rb_mul(rb_self: crate::langs::RbRef<Self>, rhs: u64) -> Self {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_rhs = (rhs);
+            let rhs = _temp_rhs;
+            let __output = Self::mul(&rb_self, rhs);
+            let __output = __output;
+            __output
+        }
+        pub fn __rb_neg(rb_self: crate::langs::RbRef<Self>) -> Self {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let __output = Self::neg(&rb_self);
+            let __output = __output;
+            __output
+        }
+    }
+    impl Script {
+        fn index(&self, rhs: usize) -> Result<u8, crate::common::IndexError> {
+            crate::common::CheckedIndex::checked_index(self, rhs)
+                .cloned()
+                .ok_or(crate::common::IndexError)
+        }
+        pub fn __rb_index(
+            rb_self: crate::langs::RbRef<Self>,
+            rhs: usize,
+        ) -> Result<u8, crate::langs::RubyError> {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_rhs = (rhs);
+            let rhs = _temp_rhs;
+            let __output = Self::index(&rb_self, rhs);
+            let __output = __output.map_err(crate::langs::RubyError::from)?;
+            let __output = __output;
+            Ok(__output)
+        }
+    }
+}
@@ -0,0 +1,99 @@
+pub mod returns {
+    #[magnus::init]
+    fn __rb_init(ruby: &magnus::Ruby) -> Result<(), magnus::Error> {
+        let m = ruby.define_module("Returns")?;
+        crate::langs::define_errors(ruby, m, &["Error"])?;
+        returns(ruby, m)
+    }
+    #[allow(unused_variables)]
+    pub(super) fn returns(
+        ruby: &magnus::Ruby,
+        m: magnus::RModule,
+    ) -> Result<(), magnus::Error> {
+        use magnus::{Module, Object};
+        let class = m.define_class("Item", ruby.class_object())?;
+        class.define_singleton_method("parse", magnus::function!(Item::__rb_parse, 1))?;
+        class.define_method("check", magnus::method!(Item::__rb_check, 0))?;
+        class.define_singleton_method("find", magnus::function!(Item::__rb_find, 1))?;
+        class.define_method("to_bytes", magnus::method!(Item::__rb_to_bytes, 0))?;
+        class.define_method("name", magnus::method!(Item::__rb_name, 0))?;
+        m.define_module_function("last_error", magnus::function!(last_error, 0))?;
+        Ok(())
+    }
+    use super::Error;
+    pub struct Item {
+        inner: super::Item,
+    }
+    impl Item {
+        fn parse(s: String) -> Result<Self, Error> {
+            Ok(s.parse::<super::Item>()?.into())
+        }
+        fn check(&self) -> Result<(), super::Error> {
+            self.inner.check()
+        }
+        fn find(name: String) -> Option<Self> {
+            super::Item::find(&name).map(Into::into)
+        }
+        fn to_bytes(&self) -> Vec<u8> {
+            self.inner.to_bytes()
+        }
+        fn name(&self) -> String {
+            self.inner.name().to_string()
+        }
+        pub fn __rb_parse(s: String) -> Result<Self, crate::langs::RubyError> {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_s = (s);
+            let s = _temp_s;
+            let __output = Self::parse(s);
+            let __output = __output.map_err(crate::langs::RubyError::from)?;
+            let __output = __output;
+            Ok(__output)
+        }
+        pub fn __rb_check(
+            rb_self: crate::langs::RbRef<Self>,
+        ) -> Result<(), crate::langs::RubyError> {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let __output = Self::check(&rb_self);
+            let __output = __output.map_err(crate::langs::RubyError::from)?;
+            let __output = __output;
+            Ok(__output)
+        }
+        pub fn __rb_find(name: String) -> Option<Self> {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_name = (name);
+            let name = _temp_name;
+            let __output = Self::find(name);
+            let __output = __output;
+            __output
+        }
+        pub fn __rb_to_bytes(rb_self: crate::langs::RbRef<Self>) -> magnus::RString {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let __output = Self::to_bytes(&rb_self);
+            let __output: Vec<u8> = __output;
+            let __output = __output.map_to();
+            __output
+        }
+        pub fn __rb_name(rb_self: crate::langs::RbRef<Self>) -> String {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let __output = Self::name(&rb_self);
+            let __output = __output;
+            __output
+        }
+    }
+    pub fn last_error() -> Error {
+        use crate::mapping::{MapTo, MapFrom};
+        use crate::langs::*;
+        let mut block_closure = move || { { super::last_error() } };
+        let __output = block_closure();
+        let __output = __output;
+        __output
+    }
+    impl Item {}
+    crate::impl_ruby_class!(Item, "Returns::Item");
+    impl crate::common::ExposedStruct for Item {}
+}
@@ -0,0 +1,214 @@
+pub mod structs {
+    #[magnus::init]
+    fn __rb_init(ruby: &magnus::Ruby) -> Result<(), magnus::Error> {
+        let m = ruby.define_module("Structs")?;
+        crate::langs::define_errors(ruby, m, &[])?;
+        structs(ruby, m)
+    }
+    #[allow(unused_variables)]
+    pub(super) fn structs(
+        ruby: &magnus::Ruby,
+        m: magnus::RModule,
+    ) -> Result<(), magnus::Error> {
+        use magnus::{Module, Object};
+        let class = m.define_class("Opaque", ruby.class_object())?;
+        class.define_method("to_s", magnus::method!(Opaque::__rb___to_s, 0))?;
+        let class = m.define_class("Shared", ruby.class_object())?;
+        let class = m.define_class("WithFields", ruby.class_object())?;
+        class.define_method("shared", magnus::method!(WithFields::__rb_get_shared, 0))?;
+        class.define_method("shared=", magnus::method!(WithFields::__rb_set_shared, 1))?;
+        class.define_method("value", magnus::method!(WithFields::__rb_get_value, 0))?;
+        class.define_method("value=", magnus::method!(WithFields::__rb_set_value, 1))?;
+        let class = m.define_class("Point", ruby.class_object())?;
+        class.define_method("x", magnus::method!(Point::__rb_get_x, 0))?;
+        class.define_method("x=", magnus::method!(Point::__rb_set_x, 1))?;
+        class.define_method("y", magnus::method!(Point::__rb_get_y, 0))?;
+        let class = m.define_class("Script", ruby.class_object())?;
+        let class = m.define_class("WithOpaque", ruby.class_object())?;
+        class.define_method("script", magnus::method!(WithOpaque::__rb_get_script, 0))?;
+        class.define_method("script=", magnus::method!(WithOpaque::__rb_set_script, 1))?;
+        Ok(())
+    }
+    pub struct Opaque {
+        inner: super::Opaque,
+    }
+    impl Opaque {}
+    pub struct Shared {
+        inner: super::Shared,
+    }
+    pub struct WithFields {
+        pub(crate) shared: <Shared as crate::common::WrappedStructField>::Store,
+        pub(crate) value: <u32 as crate::common::WrappedStructField>::Store,
+    }
+    impl WithFields {}
+    pub struct Point {
+        pub x: i32,
+        pub y: i32,
+    }
+    pub struct Script {
+        inner: super::Script,
+    }
+    impl Script {}
+    pub struct WithOpaque {
+        pub script: <Script as crate::common::WrappedStructField>::Store,
+        pub value: u32,
+    }
+    impl WithOpaque {}
+    impl Opaque {}
+    impl Opaque {
+        fn __to_s(&self) -> String {
+            self.to_string()
+        }
+        pub fn __rb___to_s(rb_self: crate::langs::RbRef<Self>) -> String {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let __output = Self::__to_s(&rb_self);
+            let __output = __output;
+            __output
+        }
+    }
+    crate::impl_ruby_class!(Opaque, "Structs::Opaque");
+    impl crate::common::ExposedStruct for Opaque {}
+    impl Shared {}
+    crate::impl_ruby_class!(Shared, "Structs::Shared");
+    impl crate::common::ExposedStruct for Shared {}
+    impl WithFields {
+        fn get_shared(&mut self) -> Shared {
+            use crate::common::WrappedStructField;
+            Shared::wrap_get(&mut self.shared)
+        }
+        fn set_shared(&mut self, shared: Shared) {
+            use crate::common::WrappedStructField;
+            self.shared = Shared::wrap_set(crate::mapping::MapFrom::map_from(shared));
+        }
+        fn get_value(&mut self) -> u32 {
+            use crate::common::WrappedStructField;
+            u32::wrap_get(&mut self.value)
+        }
+        fn set_value(
+            &mut self,
+            value: u32,
+        ) -> Result<(), crate::common::ValidationError> {
+            use crate::common::WrappedStructField;
+            let value: u32 = crate::mapping::MapFrom::map_from(value);
+            WithFields::validate_value(self, &value)
+                .map_err(crate::common::ValidationError::from_display)?;
+            self.value = u32::wrap_set(value);
+            Ok(())
+        }
+        pub fn __rb_get_shared(mut rb_self: crate::langs::RbRefMut<Self>) -> Shared {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let __output = Self::get_shared(&mut rb_self);
+            let __output = __output;
+            __output
+        }
+        pub fn __rb_set_shared(
+            mut rb_self: crate::langs::RbRefMut<Self>,
+            shared: crate::langs::RbRef<Shared>,
+        ) -> () {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_shared = (shared);
+            let shared = Clone::clone(&*_temp_shared);
+            let __output = Self::set_shared(&mut rb_self, shared);
+            let __output = __output;
+            __output
+        }
+        pub fn __rb_get_value(mut rb_self: crate::langs::RbRefMut<Self>) -> u32 {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let __output = Self::get_value(&mut rb_self);
+            let __output = __output;
+            __output
+        }
+        pub fn __rb_set_value(
+            mut rb_self: crate::langs::RbRefMut<Self>,
+            value: u32,
+        ) -> Result<(), crate::langs::RubyError> {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_value = (value);
+            let value = _temp_value;
+            let __output = Self::set_value(&mut rb_self, value);
+            let __output = __output.map_err(crate::langs::RubyError::from)?;
+            let __output = __output;
+            Ok(__output)
+        }
+    }
+    crate::impl_ruby_class!(WithFields, "Structs::WithFields");
+    impl crate::common::ExposedStruct for WithFields {}
+    impl Point {
+        fn get_x(&mut self) -> i32 {
+            use crate::common::WrappedStructField;
+            i32::wrap_get(&mut self.x)
+        }
+        fn set_x(&mut self, x: i32) {
+            use crate::common::WrappedStructField;
+            self.x = i32::wrap_set(crate::mapping::MapFrom::map_from(x));
+        }
+        fn get_y(&mut self) -> i32 {
+            use crate::common::WrappedStructField;
+            i32::wrap_get(&mut self.y)
+        }
+        pub fn __rb_get_x(mut rb_self: crate::langs::RbRefMut<Self>) -> i32 {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let __output = Self::get_x(&mut rb_self);
+            let __output = __output;
+            __output
+        }
+        pub fn __rb_set_x(mut rb_self: crate::langs::RbRefMut<Self>, x: i32) -> () {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_x = (x);
+            let x = _temp_x;
+            let __output = Self::set_x(&mut rb_self, x);
+            let __output = __output;
+            __output
+        }
+        pub fn __rb_get_y(mut rb_self: crate::langs::RbRefMut<Self>) -> i32 {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let __output = Self::get_y(&mut rb_self);
+            let __output = __output;
+            __output
+        }
+    }
+    crate::impl_ruby_class!(Point, "Structs::Point");
+    impl crate::common::ExposedStruct for Point {}
+    impl Script {}
+    crate::impl_ruby_class!(Script, "Structs::Script");
+    impl crate::common::ExposedStruct for Script {}
+    impl WithOpaque {
+        fn get_script(&mut self) -> Script {
+            use crate::common::WrappedStructField;
+            Script::wrap_get(&mut self.script)
+        }
+        fn set_script(&mut self, script: Script) {
+            use crate::common::WrappedStructField;
+            self.script = Script::wrap_set(crate::mapping::MapFrom::map_from(script));
+        }
+        pub fn __rb_get_script(mut rb_self: crate::langs::RbRefMut<Self>) -> Script {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let __output = Self::get_script(&mut rb_self);
+            let __output = __output;
+            __output
+        }
+        pub fn __rb_set_script(
+            mut rb_self: crate::langs::RbRefMut<Self>,
+            script: crate::langs::RbRef<Script>,
+        ) -> () {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_script = (script);
+            let script = Clone::clone(&*_temp_script);
+            let __output = Self::set_script(&mut rb_self, script);
+            let __output = __output;
+            __output
+        }
+    }
+    crate::impl_ruby_class!(WithOpaque, "Structs::WithOpaque");
+    impl crate::common::ExposedStruct for WithOpaque {}
+}
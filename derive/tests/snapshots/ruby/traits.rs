@@ -0,0 +1,86 @@
+pub mod traits {
+    #[magnus::init]
+    fn __rb_init(ruby: &magnus::Ruby) -> Result<(), magnus::Error> {
+        let m = ruby.define_module("Traits")?;
+        crate::langs::define_errors(ruby, m, &[])?;
+        traits(ruby, m)
+    }
+    #[allow(unused_variables)]
+    pub(super) fn traits(
+        ruby: &magnus::Ruby,
+        m: magnus::RModule,
+    ) -> Result<(), magnus::Error> {
+        use magnus::{Module, Object};
+        let class = m.define_class("ListenerStruct", ruby.class_object())?;
+        class
+            .define_singleton_method(
+                "new",
+                magnus::function!(ListenerStruct::__rb_new, 1),
+            )?;
+        class
+            .define_method(
+                "on_event",
+                magnus::method!(ListenerStruct::__rb_on_event, 1),
+            )?;
+        Ok(())
+    }
+    pub trait Listener: super::Listener {
+        fn _wrapper_on_event(&self, event: String) -> u32;
+    }
+    pub struct ListenerStruct {
+        native: Option<Box<dyn super::Listener + Send>>,
+        ruby: Option<crate::langs::RbImpl>,
+    }
+    impl ListenerStruct {}
+    crate::impl_ruby_class!(ListenerStruct, "Traits::ListenerStruct");
+    impl ListenerStruct {
+        pub fn new(implementation: crate::langs::RbImpl) -> Self {
+            ListenerStruct {
+                native: None,
+                ruby: Some(implementation),
+            }
+        }
+        pub fn on_event(&self, event: String) -> u32 {
+            if let Some(native) = &self.native {
+                native.on_event(event)
+            } else if let Some(ruby) = &self.ruby {
+                ruby.call("on_event", (event,)).expect("Ruby call failed")
+            } else {
+                unreachable!()
+            }
+        }
+        pub fn __rb_new(implementation: crate::langs::RbImpl) -> Self {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_implementation = (implementation);
+            let implementation = _temp_implementation;
+            let __output = Self::new(implementation);
+            let __output = __output;
+            __output
+        }
+        pub fn __rb_on_event(rb_self: crate::langs::RbRef<Self>, event: String) -> u32 {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_event = (event);
+            let event = _temp_event;
+            let __output = Self::on_event(&rb_self, event);
+            let __output = __output;
+            __output
+        }
+    }
+    impl Listener for ListenerStruct {
+        fn _wrapper_on_event(&self, event: String) -> u32 {
+            self.on_event(event)
+        }
+    }
+    impl<T: 'static + super::Listener + Sized + Send> crate::langs::IntoTraitStruct
+    for T {
+        type Target = ListenerStruct;
+        fn into_trait_struct(self) -> Self::Target {
+            ListenerStruct {
+                native: Some(Box::new(self)),
+                ruby: None,
+            }
+        }
+    }
+}
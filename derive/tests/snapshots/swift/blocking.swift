@@ -0,0 +1,73 @@
+// Generated by `#[expose_mod]`, do not edit
+import Foundation
+import CDerive
+
+/// Error thrown when the C API returns an error code
+public protocol DeriveErrorProtocol: Swift.Error {
+    var code: Int32 { get }
+}
+
+/// Copy a string returned by the C API, and free it
+fileprivate func takeString(_ s: UnsafeMutablePointer<CChar>) -> String {
+    defer { CDerive.string_free(s) }
+    return String(cString: s)
+}
+
+/// Copy a vector returned by the C API, and free it
+fileprivate func takeBytes(_ arr: CDerive.Arr_u8) -> Data {
+    defer { CDerive.bytes_free(arr) }
+    return arr.ptr.map { Data(bytes: $0, count: Int(arr.len)) } ?? Data()
+}
+
+public enum DeriveError: DeriveErrorProtocol, Equatable {
+    case failed(code: Int32)
+
+    public var code: Int32 {
+        switch self {
+        case .failed(let code):
+            return code
+        }
+    }
+}
+
+/// Owns a pointer to `Wallet`, freed when the object is deinitialized
+public final class Wallet {
+    public let ptr: OpaquePointer
+
+    /// Take the ownership of a pointer returned by the C API
+    public init(ptr: OpaquePointer) {
+        self.ptr = ptr
+    }
+
+    deinit {
+        CDerive.wallet_destroy(ptr)
+    }
+
+    public convenience init(_ descriptor: String) {
+        var out: OpaquePointer? = nil
+        CDerive.wallet_new(descriptor, &out)
+        self.init(ptr: out!)
+    }
+
+    public func sync(_ url: String, stopGap: UInt) throws -> UInt64 {
+        var out = UInt64()
+        let code = CDerive.wallet_sync(ptr, url, stopGap, &out)
+        if code != 0 {
+            throw DeriveError.failed(code: code)
+        }
+        return out
+    }
+
+    public func sign(_ psbt: Data) -> Data {
+        return takeBytes(psbt.withUnsafeBytes { psbt_ in CDerive.wallet_sign(ptr, CDerive.Arr_u8(ptr: psbt_.bindMemory(to: UInt8.self).baseAddress, len: UInt(psbt_.count))) })
+    }
+}
+
+public func broadcast(_ url: String, tx: Data) throws -> String {
+    var out: UnsafeMutablePointer<CChar>? = nil
+    let code = tx.withUnsafeBytes { tx_ in CDerive.broadcast(url, CDerive.Arr_u8(ptr: tx_.bindMemory(to: UInt8.self).baseAddress, len: UInt(tx_.count)), &out) }
+    if code != 0 {
+        throw DeriveError.failed(code: code)
+    }
+    return takeString(out!)
+}
@@ -0,0 +1,44 @@
+// Generated by `#[expose_mod]`, do not edit
+import Foundation
+import CDerive
+
+/// Error thrown when the C API returns an error code
+public protocol DeriveErrorProtocol: Swift.Error {
+    var code: Int32 { get }
+}
+
+/// Copy a string returned by the C API, and free it
+fileprivate func takeString(_ s: UnsafeMutablePointer<CChar>) -> String {
+    defer { CDerive.string_free(s) }
+    return String(cString: s)
+}
+
+/// Copy a vector returned by the C API, and free it
+fileprivate func takeBytes(_ arr: CDerive.Arr_u8) -> Data {
+    defer { CDerive.bytes_free(arr) }
+    return arr.ptr.map { Data(bytes: $0, count: Int(arr.len)) } ?? Data()
+}
+
+/// Owns a pointer to `Script`, freed when the object is deinitialized
+public final class Script {
+    public let ptr: OpaquePointer
+
+    /// Take the ownership of a pointer returned by the C API
+    public init(ptr: OpaquePointer) {
+        self.ptr = ptr
+    }
+
+    deinit {
+        CDerive.script_destroy(ptr)
+    }
+
+    public func toBytes() -> Data {
+        return takeBytes(CDerive.script_to_bytes(ptr))
+    }
+
+    public static func fromBytes(_ bytes: Data) -> Script {
+        var out: OpaquePointer? = nil
+        bytes.withUnsafeBytes { bytes_ in CDerive.script_from_bytes(CDerive.Arr_u8(ptr: bytes_.bindMemory(to: UInt8.self).baseAddress, len: UInt(bytes_.count)), &out) }
+        return Script(ptr: out!)
+    }
+}
@@ -0,0 +1,66 @@
+// Generated by `#[expose_mod]`, do not edit
+import Foundation
+import CDerive
+
+/// Error thrown when the C API returns an error code
+public protocol DeriveErrorProtocol: Swift.Error {
+    var code: Int32 { get }
+}
+
+/// Copy a string returned by the C API, and free it
+fileprivate func takeString(_ s: UnsafeMutablePointer<CChar>) -> String {
+    defer { CDerive.string_free(s) }
+    return String(cString: s)
+}
+
+/// Copy a vector returned by the C API, and free it
+fileprivate func takeBytes(_ arr: CDerive.Arr_u8) -> Data {
+    defer { CDerive.bytes_free(arr) }
+    return arr.ptr.map { Data(bytes: $0, count: Int(arr.len)) } ?? Data()
+}
+
+public enum DeriveError: DeriveErrorProtocol, Equatable {
+    case failed(code: Int32)
+
+    public var code: Int32 {
+        switch self {
+        case .failed(let code):
+            return code
+        }
+    }
+}
+
+/// Owns a pointer to `Network`, freed when the object is deinitialized
+public final class Network {
+    public let ptr: OpaquePointer
+
+    /// Take the ownership of a pointer returned by the C API
+    public init(ptr: OpaquePointer) {
+        self.ptr = ptr
+    }
+
+    deinit {
+        CDerive.network_destroy(ptr)
+    }
+
+    public convenience init(_ s: String) throws {
+        var out: OpaquePointer? = nil
+        let code = CDerive.network_new(s, &out)
+        if code != 0 {
+            throw DeriveError.failed(code: code)
+        }
+        self.init(ptr: out!)
+    }
+
+    public static func bitcoin() -> Network {
+        var out: OpaquePointer? = nil
+        CDerive.network_new_bitcoin(&out)
+        return Network(ptr: out!)
+    }
+
+    public static func fromMagic(_ magic: UInt32) -> Network? {
+        var out: OpaquePointer? = nil
+        CDerive.network_new_from_magic(magic, &out)
+        return out.map { Network(ptr: $0) }
+    }
+}
@@ -0,0 +1,62 @@
+// Generated by `#[expose_mod]`, do not edit
+import Foundation
+import CDerive
+
+/// Error thrown when the C API returns an error code
+public protocol DeriveErrorProtocol: Swift.Error {
+    var code: Int32 { get }
+}
+
+/// Copy a string returned by the C API, and free it
+fileprivate func takeString(_ s: UnsafeMutablePointer<CChar>) -> String {
+    defer { CDerive.string_free(s) }
+    return String(cString: s)
+}
+
+/// Copy a vector returned by the C API, and free it
+fileprivate func takeBytes(_ arr: CDerive.Arr_u8) -> Data {
+    defer { CDerive.bytes_free(arr) }
+    return arr.ptr.map { Data(bytes: $0, count: Int(arr.len)) } ?? Data()
+}
+
+/// Owns a pointer to `FeeRate`, freed when the object is deinitialized
+public final class FeeRate {
+    public let ptr: OpaquePointer
+
+    /// Take the ownership of a pointer returned by the C API
+    public init(ptr: OpaquePointer) {
+        self.ptr = ptr
+    }
+
+    deinit {
+        CDerive.fee_rate_destroy(ptr)
+    }
+
+    public static func fromSatPerVb(_ satPerVb: Float) -> FeeRate {
+        var out: OpaquePointer? = nil
+        CDerive.fee_rate_from_sat_per_vb(satPerVb, &out)
+        return FeeRate(ptr: out!)
+    }
+
+    public static func fromSatPerVb() -> FeeRate {
+        var out: OpaquePointer? = nil
+        CDerive.fee_rate_from_sat_per_vb_with_defaults(&out)
+        return FeeRate(ptr: out!)
+    }
+
+    public func feeFor(_ vbytes: UInt64, min: FeeRate) -> UInt64 {
+        return CDerive.fee_rate_fee_for(ptr, vbytes, min.ptr)
+    }
+
+    public func feeFor(_ vbytes: UInt64) -> UInt64 {
+        return CDerive.fee_rate_fee_for_with_defaults(ptr, vbytes)
+    }
+}
+
+public func greet(_ name: String) -> String {
+    return takeString(CDerive.greet(name))
+}
+
+public func greet() -> String {
+    return takeString(CDerive.greet_with_defaults())
+}
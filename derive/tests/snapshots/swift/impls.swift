@@ -0,0 +1,58 @@
+// Generated by `#[expose_mod]`, do not edit
+import Foundation
+import CDerive
+
+/// Error thrown when the C API returns an error code
+public protocol DeriveErrorProtocol: Swift.Error {
+    var code: Int32 { get }
+}
+
+/// Copy a string returned by the C API, and free it
+fileprivate func takeString(_ s: UnsafeMutablePointer<CChar>) -> String {
+    defer { CDerive.string_free(s) }
+    return String(cString: s)
+}
+
+/// Copy a vector returned by the C API, and free it
+fileprivate func takeBytes(_ arr: CDerive.Arr_u8) -> Data {
+    defer { CDerive.bytes_free(arr) }
+    return arr.ptr.map { Data(bytes: $0, count: Int(arr.len)) } ?? Data()
+}
+
+/// Owns a pointer to `Counter`, freed when the object is deinitialized
+public final class Counter {
+    public let ptr: OpaquePointer
+
+    /// Take the ownership of a pointer returned by the C API
+    public init(ptr: OpaquePointer) {
+        self.ptr = ptr
+    }
+
+    deinit {
+        CDerive.counter_destroy(ptr)
+    }
+
+    public convenience init(_ start: UInt32) {
+        var out: OpaquePointer? = nil
+        CDerive.counter_new(start, &out)
+        self.init(ptr: out!)
+    }
+
+    public static func zero() -> Counter {
+        var out: OpaquePointer? = nil
+        CDerive.counter_zero(&out)
+        return Counter(ptr: out!)
+    }
+
+    public func get() -> UInt32 {
+        return CDerive.counter_get(ptr)
+    }
+
+    public func increment(_ by: UInt32) {
+        CDerive.counter_increment(ptr, by)
+    }
+
+    public func label(_ prefix: String) -> String {
+        return takeString(CDerive.counter_label(ptr, prefix))
+    }
+}
@@ -0,0 +1,68 @@
+// Generated by `#[expose_mod]`, do not edit
+import Foundation
+import CDerive
+
+/// Error thrown when the C API returns an error code
+public protocol DeriveErrorProtocol: Swift.Error {
+    var code: Int32 { get }
+}
+
+/// Copy a string returned by the C API, and free it
+fileprivate func takeString(_ s: UnsafeMutablePointer<CChar>) -> String {
+    defer { CDerive.string_free(s) }
+    return String(cString: s)
+}
+
+/// Copy a vector returned by the C API, and free it
+fileprivate func takeBytes(_ arr: CDerive.Arr_u8) -> Data {
+    defer { CDerive.bytes_free(arr) }
+    return arr.ptr.map { Data(bytes: $0, count: Int(arr.len)) } ?? Data()
+}
+
+public enum Inner {}
+public enum Prefixed {}
+extension Prefixed {
+    public enum Nested {}
+}
+
+extension Inner {
+    /// Owns a pointer to `Thing`, released when the object is deinitialized
+    public final class Thing {
+        public let ptr: OpaquePointer
+
+        /// Take the ownership of a pointer returned by the C API
+        public init(ptr: OpaquePointer) {
+            self.ptr = ptr
+        }
+
+        deinit {
+            CDerive.outer_inner_thing_release(ptr)
+        }
+
+        public static func new() -> Thing {
+            var out: OpaquePointer? = nil
+            CDerive.outer_inner_thing_new(&out)
+            return Thing(ptr: out!)
+        }
+    }
+
+    public static func count() -> UInt32 {
+        return CDerive.outer_inner_count()
+    }
+}
+
+public func version() -> UInt32 {
+    return CDerive.version()
+}
+
+extension Prefixed {
+    public static func count() -> UInt32 {
+        return CDerive.ext_count()
+    }
+}
+
+extension Prefixed.Nested {
+    public static func count() -> UInt32 {
+        return CDerive.ext_outer_prefixed_nested_count()
+    }
+}
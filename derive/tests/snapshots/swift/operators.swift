@@ -0,0 +1,102 @@
+// Generated by `#[expose_mod]`, do not edit
+import Foundation
+import CDerive
+
+/// Error thrown when the C API returns an error code
+public protocol DeriveErrorProtocol: Swift.Error {
+    var code: Int32 { get }
+}
+
+/// Copy a string returned by the C API, and free it
+fileprivate func takeString(_ s: UnsafeMutablePointer<CChar>) -> String {
+    defer { CDerive.string_free(s) }
+    return String(cString: s)
+}
+
+/// Copy a vector returned by the C API, and free it
+fileprivate func takeBytes(_ arr: CDerive.Arr_u8) -> Data {
+    defer { CDerive.bytes_free(arr) }
+    return arr.ptr.map { Data(bytes: $0, count: Int(arr.len)) } ?? Data()
+}
+
+public enum DeriveError: DeriveErrorProtocol, Equatable {
+    case failed(code: Int32)
+
+    public var code: Int32 {
+        switch self {
+        case .failed(let code):
+            return code
+        }
+    }
+}
+
+public enum IndexError: DeriveErrorProtocol, Equatable {
+    case failed(code: Int32)
+
+    public var code: Int32 {
+        switch self {
+        case .failed(let code):
+            return code
+        }
+    }
+}
+
+/// Owns a pointer to `Amount`, freed when the object is deinitialized
+public final class Amount {
+    public let ptr: OpaquePointer
+
+    /// Take the ownership of a pointer returned by the C API
+    public init(ptr: OpaquePointer) {
+        self.ptr = ptr
+    }
+
+    deinit {
+        CDerive.amount_destroy(ptr)
+    }
+
+    public static func + (lhs: Amount, rhs: Amount) throws -> Amount {
+        var out: OpaquePointer? = nil
+        let code = CDerive.amount_add(lhs.ptr, rhs.ptr, &out)
+        if code != 0 {
+            throw DeriveError.failed(code: code)
+        }
+        return Amount(ptr: out!)
+    }
+
+    public static func * (lhs: Amount, rhs: UInt64) -> Amount {
+        var out: OpaquePointer? = nil
+        CDerive.amount_mul(lhs.ptr, rhs, &out)
+        return Amount(ptr: out!)
+    }
+
+    public static prefix func - (operand: Amount) -> Amount {
+        var out: OpaquePointer? = nil
+        CDerive.amount_neg(operand.ptr, &out)
+        return Amount(ptr: out!)
+    }
+}
+
+/// Owns a pointer to `Script`, freed when the object is deinitialized
+public final class Script {
+    public let ptr: OpaquePointer
+
+    /// Take the ownership of a pointer returned by the C API
+    public init(ptr: OpaquePointer) {
+        self.ptr = ptr
+    }
+
+    deinit {
+        CDerive.script_destroy(ptr)
+    }
+
+    public subscript(rhs: UInt) -> UInt8 {
+        get throws {
+            var out = UInt8()
+            let code = CDerive.script_index(ptr, rhs, &out)
+            if code != 0 {
+                throw IndexError.failed(code: code)
+            }
+            return out
+        }
+    }
+}
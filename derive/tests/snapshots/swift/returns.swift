@@ -0,0 +1,79 @@
+// Generated by `#[expose_mod]`, do not edit
+import Foundation
+import CDerive
+
+/// Error thrown when the C API returns an error code
+public protocol DeriveErrorProtocol: Swift.Error {
+    var code: Int32 { get }
+}
+
+/// Copy a string returned by the C API, and free it
+fileprivate func takeString(_ s: UnsafeMutablePointer<CChar>) -> String {
+    defer { CDerive.string_free(s) }
+    return String(cString: s)
+}
+
+/// Copy a vector returned by the C API, and free it
+fileprivate func takeBytes(_ arr: CDerive.Arr_u8) -> Data {
+    defer { CDerive.bytes_free(arr) }
+    return arr.ptr.map { Data(bytes: $0, count: Int(arr.len)) } ?? Data()
+}
+
+public enum DeriveError: DeriveErrorProtocol, Equatable {
+    case failed(code: Int32)
+
+    public var code: Int32 {
+        switch self {
+        case .failed(let code):
+            return code
+        }
+    }
+}
+
+/// Owns a pointer to `Item`, freed when the object is deinitialized
+public final class Item {
+    public let ptr: OpaquePointer
+
+    /// Take the ownership of a pointer returned by the C API
+    public init(ptr: OpaquePointer) {
+        self.ptr = ptr
+    }
+
+    deinit {
+        CDerive.item_destroy(ptr)
+    }
+
+    public static func parse(_ s: String) throws -> Item {
+        var out: OpaquePointer? = nil
+        let code = CDerive.item_parse(s, &out)
+        if code != 0 {
+            throw DeriveError.failed(code: code)
+        }
+        return Item(ptr: out!)
+    }
+
+    public func check() throws {
+        let code = CDerive.item_check(ptr)
+        if code != 0 {
+            throw DeriveError.failed(code: code)
+        }
+    }
+
+    public static func find(_ name: String) -> Item? {
+        var out: OpaquePointer? = nil
+        CDerive.item_find(name, &out)
+        return out.map { Item(ptr: $0) }
+    }
+
+    public func toBytes() -> Data {
+        return takeBytes(CDerive.item_to_bytes(ptr))
+    }
+
+    public func name() -> String {
+        return takeString(CDerive.item_name(ptr))
+    }
+}
+
+public func lastError() -> Int32 {
+    return CDerive.last_error()
+}
@@ -0,0 +1,128 @@
+// Generated by `#[expose_mod]`, do not edit
+import Foundation
+import CDerive
+
+/// Error thrown when the C API returns an error code
+public protocol DeriveErrorProtocol: Swift.Error {
+    var code: Int32 { get }
+}
+
+/// Copy a string returned by the C API, and free it
+fileprivate func takeString(_ s: UnsafeMutablePointer<CChar>) -> String {
+    defer { CDerive.string_free(s) }
+    return String(cString: s)
+}
+
+/// Copy a vector returned by the C API, and free it
+fileprivate func takeBytes(_ arr: CDerive.Arr_u8) -> Data {
+    defer { CDerive.bytes_free(arr) }
+    return arr.ptr.map { Data(bytes: $0, count: Int(arr.len)) } ?? Data()
+}
+
+public enum ValidationError: DeriveErrorProtocol, Equatable {
+    case failed(code: Int32)
+
+    public var code: Int32 {
+        switch self {
+        case .failed(let code):
+            return code
+        }
+    }
+}
+
+/// Owns a pointer to `Opaque`, freed when the object is deinitialized
+public final class Opaque {
+    public let ptr: OpaquePointer
+
+    /// Take the ownership of a pointer returned by the C API
+    public init(ptr: OpaquePointer) {
+        self.ptr = ptr
+    }
+
+    deinit {
+        CDerive.opaque_destroy(ptr)
+    }
+}
+
+/// Owns a pointer to `Shared`, released when the object is deinitialized
+public final class Shared {
+    public let ptr: OpaquePointer
+
+    /// Take the ownership of a pointer returned by the C API
+    public init(ptr: OpaquePointer) {
+        self.ptr = ptr
+    }
+
+    deinit {
+        CDerive.shared_release(ptr)
+    }
+}
+
+/// Owns a pointer to `WithFields`, freed when the object is deinitialized
+public final class WithFields {
+    public let ptr: OpaquePointer
+
+    /// Take the ownership of a pointer returned by the C API
+    public init(ptr: OpaquePointer) {
+        self.ptr = ptr
+    }
+
+    deinit {
+        CDerive.with_fields_destroy(ptr)
+    }
+
+    public var shared: Shared {
+        return Shared(ptr: CDerive.with_fields_get_shared(ptr))
+    }
+
+    public func setShared(_ value: Shared) {
+        CDerive.with_fields_set_shared(ptr, value.ptr)
+    }
+
+    public var value: UInt32 {
+        return CDerive.with_fields_get_value(ptr)
+    }
+
+    public func setValue(_ value: UInt32) throws {
+        let code = CDerive.with_fields_set_value(ptr, value)
+        if code != 0 {
+            throw ValidationError.failed(code: code)
+        }
+    }
+}
+
+public typealias Point = CDerive.Point
+
+/// Owns a pointer to `Script`, freed when the object is deinitialized
+public final class Script {
+    public let ptr: OpaquePointer
+
+    /// Take the ownership of a pointer returned by the C API
+    public init(ptr: OpaquePointer) {
+        self.ptr = ptr
+    }
+
+    deinit {
+        CDerive.script_destroy(ptr)
+    }
+}
+
+/// Owns a pointer to `WithOpaque`, freed when the object is deinitialized
+public final class WithOpaque {
+    public let ptr: OpaquePointer
+
+    /// Take the ownership of a pointer returned by the C API
+    public init(ptr: OpaquePointer) {
+        self.ptr = ptr
+    }
+
+    deinit {
+        CDerive.with_opaque_destroy(ptr)
+    }
+
+    // `script` is not available: unsupported argument or return types
+
+    public func setScript(_ value: Script) {
+        CDerive.with_opaque_set_script(ptr, value.ptr)
+    }
+}
@@ -0,0 +1,68 @@
+pub mod blocking {
+    use super::Error;
+    #[derive(Clone)]
+    #[wasm_bindgen::prelude::wasm_bindgen]
+    pub struct Wallet {
+        inner: super::Wallet,
+    }
+    #[wasm_bindgen::prelude::wasm_bindgen]
+    impl Wallet {
+        #[wasm_bindgen::prelude::wasm_bindgen(constructor)]
+        pub fn new(descriptor: String) -> Self {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_descriptor = (descriptor);
+            let descriptor = _temp_descriptor;
+            let mut block_closure = move || {
+                { super::Wallet::new(&descriptor).into() }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        /// @throws {Error}
+        #[wasm_bindgen::prelude::wasm_bindgen(js_name = "sync")]
+        pub fn sync(&self, url: String, stop_gap: usize) -> Result<u64, Error> {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_url = (url);
+            let url = _temp_url;
+            let _temp_stop_gap = (stop_gap);
+            let stop_gap = _temp_stop_gap;
+            let mut block_closure = move || { { self.inner.sync(&url, stop_gap) } };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[wasm_bindgen::prelude::wasm_bindgen(js_name = "sign")]
+        pub fn sign(&self, psbt: &[u8]) -> Vec<u8> {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_psbt = (psbt);
+            let psbt = _temp_psbt;
+            let mut block_closure = move || { { self.inner.sign(psbt) } };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+    }
+    /// @throws {Error}
+    #[wasm_bindgen::prelude::wasm_bindgen(js_name = "broadcast")]
+    pub fn broadcast(url: String, tx: Vec<u8>) -> Result<String, Error> {
+        use crate::mapping::{MapTo, MapFrom};
+        use crate::langs::*;
+        let _temp_url = (url);
+        let url = _temp_url;
+        let _temp_tx = (tx);
+        let tx = _temp_tx;
+        let mut block_closure = move || { { super::broadcast(&url, &tx) } };
+        let __output = block_closure();
+        let __output = __output;
+        __output
+    }
+    #[wasm_bindgen::prelude::wasm_bindgen]
+    impl Wallet {}
+    impl crate::common::ExposedStruct for Wallet {}
+    #[wasm_bindgen::prelude::wasm_bindgen(typescript_custom_section)]
+    const TYPESCRIPT_DEFINITIONS: &'static str = "/** Thrown by the functions that return a `Error` */\nexport interface Error extends Error {\n    name: \"Error\";\n}\n";
+}
@@ -0,0 +1,34 @@
+pub mod bytes {
+    #[wasm_bindgen::prelude::wasm_bindgen]
+    pub struct Script {
+        inner: super::Script,
+    }
+    #[wasm_bindgen::prelude::wasm_bindgen]
+    impl Script {}
+    impl crate::common::ByteContainer for Script {
+        fn as_bytes(&self) -> &[u8] {
+            self.inner.as_bytes()
+        }
+        fn from_bytes(bytes: Vec<u8>) -> Self {
+            Script { inner: bytes.into() }
+        }
+    }
+    #[wasm_bindgen::prelude::wasm_bindgen]
+    impl Script {
+        #[wasm_bindgen::prelude::wasm_bindgen(js_name = "toString")]
+        pub fn __to_string(&self) -> String {
+            self.to_string()
+        }
+        #[wasm_bindgen::prelude::wasm_bindgen(js_name = "toBytes")]
+        pub fn __to_bytes(&self) -> Vec<u8> {
+            crate::common::ByteContainer::as_bytes(self).to_vec()
+        }
+        #[wasm_bindgen::prelude::wasm_bindgen(js_name = "fromBytes")]
+        pub fn __from_bytes(bytes: &[u8]) -> Self {
+            <Self as crate::common::ByteContainer>::from_bytes(bytes.to_vec())
+        }
+    }
+    #[wasm_bindgen::prelude::wasm_bindgen]
+    impl Script {}
+    impl crate::common::ExposedStruct for Script {}
+}
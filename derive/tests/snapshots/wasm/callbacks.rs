@@ -0,0 +1,10 @@
+pub mod callbacks {
+    fn apply(value: u32, cb: fn(value: u32) -> u32) -> u32 {
+        cb(value)
+    }
+    fn describe(cb: fn(name: String, value: u32)) {
+        cb("value".to_string(), 42)
+    }
+    #[wasm_bindgen::prelude::wasm_bindgen(typescript_custom_section)]
+    const TYPESCRIPT_DEFINITIONS: &'static str = "// `apply` is not exported: callbacks can't be passed from JavaScript yet\n// `describe` is not exported: callbacks can't be passed from JavaScript yet\n";
+}
@@ -0,0 +1,51 @@
+pub mod constructors {
+    use super::Error;
+    #[wasm_bindgen::prelude::wasm_bindgen]
+    pub struct Network {
+        inner: super::Network,
+    }
+    #[wasm_bindgen::prelude::wasm_bindgen]
+    impl Network {
+        /// @throws {Error}
+        #[wasm_bindgen::prelude::wasm_bindgen(constructor)]
+        pub fn from_string(s: String) -> Result<Self, Error> {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_s = (s);
+            let s = _temp_s;
+            let mut block_closure = move || {
+                { Ok(s.parse::<super::Network>()?.into()) }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[wasm_bindgen::prelude::wasm_bindgen(js_name = "bitcoin")]
+        pub fn bitcoin() -> Self {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let mut block_closure = move || { { super::Network::Bitcoin.into() } };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[wasm_bindgen::prelude::wasm_bindgen(js_name = "fromMagic")]
+        pub fn from_magic(magic: u32) -> Option<Self> {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_magic = (magic);
+            let magic = _temp_magic;
+            let mut block_closure = move || {
+                { super::Network::from_magic(magic).map(Into::into) }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+    }
+    #[wasm_bindgen::prelude::wasm_bindgen]
+    impl Network {}
+    impl crate::common::ExposedStruct for Network {}
+    #[wasm_bindgen::prelude::wasm_bindgen(typescript_custom_section)]
+    const TYPESCRIPT_DEFINITIONS: &'static str = "/** Thrown by the functions that return a `Error` */\nexport interface Error extends Error {\n    name: \"Error\";\n}\n";
+}
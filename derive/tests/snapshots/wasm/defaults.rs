@@ -0,0 +1,71 @@
+pub mod defaults {
+    #[wasm_bindgen::prelude::wasm_bindgen]
+    pub struct FeeRate {
+        inner: super::FeeRate,
+    }
+    #[wasm_bindgen::prelude::wasm_bindgen]
+    impl FeeRate {
+        #[wasm_bindgen::prelude::wasm_bindgen(js_name = "fromSatPerVb")]
+        pub fn from_sat_per_vb(sat_per_vb: Option<f32>) -> Self {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_sat_per_vb;
+            let __default_sat_per_vb;
+            let sat_per_vb = match sat_per_vb {
+                Some(sat_per_vb) => {
+                    _temp_sat_per_vb = sat_per_vb;
+                    _temp_sat_per_vb
+                }
+                None => {
+                    __default_sat_per_vb = 1.0;
+                    __default_sat_per_vb
+                }
+            };
+            let mut block_closure = move || {
+                { super::FeeRate::from_sat_per_vb(sat_per_vb).into() }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[wasm_bindgen::prelude::wasm_bindgen(js_name = "feeFor")]
+        pub fn fee_for(&self, vbytes: u64, min: &FeeRate) -> u64 {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_vbytes = (vbytes);
+            let vbytes = _temp_vbytes;
+            let _temp_min = (min);
+            let min = _temp_min;
+            let mut block_closure = move || {
+                { self.inner.fee_for(vbytes).max(min.inner.fee_for(vbytes)) }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+    }
+    #[wasm_bindgen::prelude::wasm_bindgen(js_name = "greet")]
+    pub fn greet(name: Option<String>) -> String {
+        use crate::mapping::{MapTo, MapFrom};
+        use crate::langs::*;
+        let _temp_name;
+        let __default_name;
+        let name = match name {
+            Some(name) => {
+                _temp_name = name;
+                _temp_name
+            }
+            None => {
+                __default_name = "world".to_string();
+                __default_name
+            }
+        };
+        let mut block_closure = move || { { format!("Hello {}", name) } };
+        let __output = block_closure();
+        let __output = __output;
+        __output
+    }
+    #[wasm_bindgen::prelude::wasm_bindgen]
+    impl FeeRate {}
+    impl crate::common::ExposedStruct for FeeRate {}
+}
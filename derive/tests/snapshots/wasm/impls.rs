@@ -0,0 +1,65 @@
+pub mod impls {
+    #[wasm_bindgen::prelude::wasm_bindgen]
+    pub struct Counter {
+        inner: super::Counter,
+    }
+    #[wasm_bindgen::prelude::wasm_bindgen]
+    impl Counter {
+        #[wasm_bindgen::prelude::wasm_bindgen(constructor)]
+        pub fn new(start: u32) -> Self {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_start = (start);
+            let start = _temp_start;
+            let mut block_closure = move || { { super::Counter::new(start).into() } };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[wasm_bindgen::prelude::wasm_bindgen(js_name = "zero")]
+        pub fn zero() -> Self {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let mut block_closure = move || { { super::Counter::new(0).into() } };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[wasm_bindgen::prelude::wasm_bindgen(js_name = "get")]
+        pub fn get(&self) -> u32 {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let mut block_closure = move || { { self.inner.get() } };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[wasm_bindgen::prelude::wasm_bindgen(js_name = "increment")]
+        pub fn increment(&mut self, by: u32) -> () {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_by = (by);
+            let by = _temp_by;
+            let mut block_closure = move || { { self.inner.increment(by) } };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[wasm_bindgen::prelude::wasm_bindgen(js_name = "label")]
+        pub fn label(&self, prefix: String) -> String {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_prefix = (prefix);
+            let prefix = _temp_prefix;
+            let mut block_closure = move || {
+                { format!("{}{}", prefix, self.inner.get()) }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+    }
+    #[wasm_bindgen::prelude::wasm_bindgen]
+    impl Counter {}
+    impl crate::common::ExposedStruct for Counter {}
+}
@@ -0,0 +1,63 @@
+pub mod outer {
+    #[wasm_bindgen::prelude::wasm_bindgen(js_name = "version")]
+    pub fn version() -> u32 {
+        use crate::mapping::{MapTo, MapFrom};
+        use crate::langs::*;
+        let mut block_closure = move || { { 1 } };
+        let __output = block_closure();
+        let __output = __output;
+        __output
+    }
+    pub mod inner {
+        #[wasm_bindgen::prelude::wasm_bindgen(js_name = "OuterInnerThing")]
+        pub struct Thing {
+            inner: super::Thing,
+        }
+        #[wasm_bindgen::prelude::wasm_bindgen(js_class = "OuterInnerThing")]
+        impl Thing {
+            #[wasm_bindgen::prelude::wasm_bindgen(js_name = "new")]
+            pub fn new() -> Self {
+                use crate::mapping::{MapTo, MapFrom};
+                use crate::langs::*;
+                let mut block_closure = move || { { super::Thing::default().into() } };
+                let __output = block_closure();
+                let __output = __output;
+                __output
+            }
+        }
+        #[wasm_bindgen::prelude::wasm_bindgen(js_name = "outerInnerCount")]
+        pub fn count() -> u32 {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let mut block_closure = move || { { 0 } };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[wasm_bindgen::prelude::wasm_bindgen(js_class = "OuterInnerThing")]
+        impl Thing {}
+        impl crate::common::ExposedStruct for Thing {}
+    }
+    pub mod prefixed {
+        #[wasm_bindgen::prelude::wasm_bindgen(js_name = "count")]
+        pub fn count() -> u32 {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let mut block_closure = move || { { 0 } };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        pub mod nested {
+            #[wasm_bindgen::prelude::wasm_bindgen(js_name = "outerPrefixedNestedCount")]
+            pub fn count() -> u32 {
+                use crate::mapping::{MapTo, MapFrom};
+                use crate::langs::*;
+                let mut block_closure = move || { { 0 } };
+                let __output = block_closure();
+                let __output = __output;
+                __output
+            }
+        }
+    }
+}
@@ -0,0 +1,112 @@
+pub mod operators {
+    use super::Error;
+    #[wasm_bindgen::prelude::wasm_bindgen]
+    pub struct Amount {
+        inner: super::Amount,
+    }
+    #[wasm_bindgen::prelude::wasm_bindgen]
+    impl Amount {}
+    impl std::ops::Add for Amount {
+        type Output = Result<Amount, Error>;
+        fn add(self, rhs: Self) -> Self::Output {
+            Ok(self.inner.checked_add(rhs.inner)?.into())
+        }
+    }
+    impl std::ops::Mul<u64> for Amount {
+        type Output = Amount;
+        fn mul(self, rhs: u64) -> Self::Output {
+            (self.inner * rhs).into()
+        }
+    }
+    impl std::ops::Neg for Amount {
+        type Output = Amount;
+        fn neg(self) -> Self::Output {
+            (-self.inner).into()
+        }
+    }
+    #[wasm_bindgen::prelude::wasm_bindgen]
+    pub struct Script {
+        inner: super::Script,
+    }
+    #[wasm_bindgen::prelude::wasm_bindgen]
+    impl Script {}
+    impl std::ops::Index<usize> for Script {
+        type Output = u8;
+        fn index(&self, index: usize) -> &Self::Output {
+            &self.inner.as_bytes()[index]
+        }
+    }
+    impl crate::common::CheckedIndex<usize> for Script {
+        fn checked_index(&self, index: usize) -> Option<&u8> {
+            self.inner.as_bytes().get(index)
+        }
+    }
+    #[wasm_bindgen::prelude::wasm_bindgen]
+    impl Amount {}
+    impl crate::common::ExposedStruct for Amount {}
+    #[wasm_bindgen::prelude::wasm_bindgen]
+    impl Script {}
+    impl crate::common::ExposedStruct for Script {}
+    #[wasm_bindgen::prelude::wasm_bindgen]
+    impl Amount {
+        /// @throws {Error}
+        #[wasm_bindgen::prelude::wasm_bindgen(js_name = "add")]
+        pub fn add(&self, rhs: &Self) -> Result<Self, Error> {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_rhs = (rhs);
+            let rhs = _temp_rhs;
+            let mut block_closure = move || {
+                { std::ops::Add::add(self.clone(), rhs.clone()) }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[wasm_bindgen::prelude::wasm_bindgen(js_name = "mul")]
+        pub fn mul(&self, rhs: u64) -> Self {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_rhs = (rhs);
+            let rhs = _temp_rhs;
+            let mut block_closure = move || {
+                { std::ops::Mul::mul(self.clone(), rhs) }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[wasm_bindgen::prelude::wasm_bindgen(js_name = "neg")]
+        pub fn neg(&self) -> Self {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let mut block_closure = move || { { std::ops::Neg::neg(self.clone()) } };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+    }
+    #[wasm_bindgen::prelude::wasm_bindgen]
+    impl Script {
+        /// @throws {RangeError} If the index is out of bounds
+        #[wasm_bindgen::prelude::wasm_bindgen(js_name = "index")]
+        pub fn index(&self, rhs: usize) -> Result<u8, crate::common::IndexError> {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_rhs = (rhs);
+            let rhs = _temp_rhs;
+            let mut block_closure = move || {
+                {
+                    crate::common::CheckedIndex::checked_index(self, rhs)
+                        .cloned()
+                        .ok_or(crate::common::IndexError)
+                }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+    }
+    #[wasm_bindgen::prelude::wasm_bindgen(typescript_custom_section)]
+    const TYPESCRIPT_DEFINITIONS: &'static str = "/** Thrown by the functions that return a `Error` */\nexport interface Error extends Error {\n    name: \"Error\";\n}\n";
+}
@@ -0,0 +1,77 @@
+pub mod returns {
+    use super::Error;
+    #[wasm_bindgen::prelude::wasm_bindgen]
+    pub struct Item {
+        inner: super::Item,
+    }
+    #[wasm_bindgen::prelude::wasm_bindgen]
+    impl Item {
+        /// @throws {Error}
+        #[wasm_bindgen::prelude::wasm_bindgen(js_name = "parse")]
+        pub fn parse(s: String) -> Result<Self, Error> {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_s = (s);
+            let s = _temp_s;
+            let mut block_closure = move || { { Ok(s.parse::<super::Item>()?.into()) } };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        /// @throws {Error}
+        #[wasm_bindgen::prelude::wasm_bindgen(js_name = "check")]
+        pub fn check(&self) -> Result<(), super::Error> {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let mut block_closure = move || { { self.inner.check() } };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[wasm_bindgen::prelude::wasm_bindgen(js_name = "find")]
+        pub fn find(name: String) -> Option<Self> {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_name = (name);
+            let name = _temp_name;
+            let mut block_closure = move || {
+                { super::Item::find(&name).map(Into::into) }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[wasm_bindgen::prelude::wasm_bindgen(js_name = "toBytes")]
+        pub fn to_bytes(&self) -> Vec<u8> {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let mut block_closure = move || { { self.inner.to_bytes() } };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[wasm_bindgen::prelude::wasm_bindgen(js_name = "name")]
+        pub fn name(&self) -> String {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let mut block_closure = move || { { self.inner.name().to_string() } };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+    }
+    #[wasm_bindgen::prelude::wasm_bindgen(js_name = "lastError")]
+    pub fn last_error() -> Error {
+        use crate::mapping::{MapTo, MapFrom};
+        use crate::langs::*;
+        let mut block_closure = move || { { super::last_error() } };
+        let __output = block_closure();
+        let __output = __output;
+        __output
+    }
+    #[wasm_bindgen::prelude::wasm_bindgen]
+    impl Item {}
+    impl crate::common::ExposedStruct for Item {}
+    #[wasm_bindgen::prelude::wasm_bindgen(typescript_custom_section)]
+    const TYPESCRIPT_DEFINITIONS: &'static str = "/** Thrown by the functions that return a `Error` */\nexport interface Error extends Error {\n    name: \"Error\";\n}\n";
+}
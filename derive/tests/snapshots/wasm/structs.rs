@@ -0,0 +1,220 @@
+pub mod structs {
+    #[wasm_bindgen::prelude::wasm_bindgen]
+    pub struct Opaque {
+        inner: super::Opaque,
+    }
+    #[wasm_bindgen::prelude::wasm_bindgen]
+    impl Opaque {}
+    #[wasm_bindgen::prelude::wasm_bindgen]
+    pub struct Shared {
+        inner: super::Shared,
+    }
+    #[wasm_bindgen::prelude::wasm_bindgen]
+    pub struct WithFields {
+        pub(crate) shared: <Shared as crate::common::WrappedStructField>::Store,
+        pub(crate) value: <u32 as crate::common::WrappedStructField>::Store,
+    }
+    #[wasm_bindgen::prelude::wasm_bindgen]
+    impl WithFields {}
+    #[wasm_bindgen::prelude::wasm_bindgen]
+    pub struct Point {
+        #[wasm_bindgen(skip)]
+        pub x: i32,
+        #[wasm_bindgen(skip)]
+        pub y: i32,
+    }
+    #[wasm_bindgen::prelude::wasm_bindgen]
+    pub struct Script {
+        inner: super::Script,
+    }
+    #[wasm_bindgen::prelude::wasm_bindgen]
+    impl Script {}
+    #[wasm_bindgen::prelude::wasm_bindgen]
+    pub struct WithOpaque {
+        #[wasm_bindgen(skip)]
+        pub script: <Script as crate::common::WrappedStructField>::Store,
+        #[wasm_bindgen(skip)]
+        pub value: u32,
+    }
+    #[wasm_bindgen::prelude::wasm_bindgen]
+    impl WithOpaque {}
+    #[wasm_bindgen::prelude::wasm_bindgen]
+    impl Opaque {
+        #[wasm_bindgen::prelude::wasm_bindgen(js_name = "toString")]
+        pub fn __to_string(&self) -> String {
+            self.to_string()
+        }
+    }
+    #[wasm_bindgen::prelude::wasm_bindgen]
+    impl Opaque {}
+    impl crate::common::ExposedStruct for Opaque {}
+    #[wasm_bindgen::prelude::wasm_bindgen]
+    impl Shared {}
+    impl crate::common::ExposedStruct for Shared {}
+    #[wasm_bindgen::prelude::wasm_bindgen]
+    impl WithFields {
+        #[wasm_bindgen::prelude::wasm_bindgen(getter = "shared")]
+        pub fn get_shared(
+            &mut self,
+        ) -> <Shared as crate::common::WrappedStructField>::Getter {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let mut block_closure = move || {
+                {
+                    use crate::common::WrappedStructField;
+                    Shared::wrap_get(&mut self.shared)
+                }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[wasm_bindgen::prelude::wasm_bindgen(setter = "shared")]
+        pub fn set_shared(
+            &mut self,
+            shared: &<Shared as crate::common::WrappedStructField>::Setter,
+        ) -> () {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_shared = (shared);
+            let shared = _temp_shared;
+            let mut block_closure = move || {
+                {
+                    use crate::common::WrappedStructField;
+                    self
+                        .shared = Shared::wrap_set(
+                        crate::mapping::MapFrom::map_from(shared),
+                    );
+                }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[wasm_bindgen::prelude::wasm_bindgen(getter = "value")]
+        pub fn get_value(
+            &mut self,
+        ) -> <u32 as crate::common::WrappedStructField>::Getter {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let mut block_closure = move || {
+                {
+                    use crate::common::WrappedStructField;
+                    u32::wrap_get(&mut self.value)
+                }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        /// @throws {ValidationError}
+        #[wasm_bindgen::prelude::wasm_bindgen(setter = "value")]
+        pub fn set_value(
+            &mut self,
+            value: <u32 as crate::common::WrappedStructField>::Setter,
+        ) -> Result<(), crate::common::ValidationError> {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_value = (value);
+            let value = _temp_value;
+            let mut block_closure = move || {
+                {
+                    use crate::common::WrappedStructField;
+                    let value: u32 = crate::mapping::MapFrom::map_from(value);
+                    WithFields::validate_value(self, &value)
+                        .map_err(crate::common::ValidationError::from_display)?;
+                    self.value = u32::wrap_set(value);
+                    Ok(())
+                }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+    }
+    impl crate::common::ExposedStruct for WithFields {}
+    #[wasm_bindgen::prelude::wasm_bindgen]
+    impl Point {
+        #[wasm_bindgen::prelude::wasm_bindgen(getter = "x")]
+        pub fn get_x(&self) -> i32 {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let mut block_closure = move || { { self.x.clone() } };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[wasm_bindgen::prelude::wasm_bindgen(setter = "x")]
+        pub fn set_x(&mut self, x: i32) -> () {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_x = (x);
+            let x = _temp_x;
+            let mut block_closure = move || {
+                {
+                    self.x = crate::mapping::MapFrom::map_from(x);
+                }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[wasm_bindgen::prelude::wasm_bindgen(getter = "y")]
+        pub fn get_y(&self) -> i32 {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let mut block_closure = move || { { self.y.clone() } };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+    }
+    impl crate::common::ExposedStruct for Point {}
+    #[wasm_bindgen::prelude::wasm_bindgen]
+    impl Script {}
+    impl crate::common::ExposedStruct for Script {}
+    #[wasm_bindgen::prelude::wasm_bindgen]
+    impl WithOpaque {
+        #[wasm_bindgen::prelude::wasm_bindgen(getter = "script")]
+        pub fn get_script(
+            &mut self,
+        ) -> <Script as crate::common::WrappedStructField>::Getter {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let mut block_closure = move || {
+                {
+                    use crate::common::WrappedStructField;
+                    Script::wrap_get(&mut self.script)
+                }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+        #[wasm_bindgen::prelude::wasm_bindgen(setter = "script")]
+        pub fn set_script(
+            &mut self,
+            script: &<Script as crate::common::WrappedStructField>::Setter,
+        ) -> () {
+            use crate::mapping::{MapTo, MapFrom};
+            use crate::langs::*;
+            let _temp_script = (script);
+            let script = _temp_script;
+            let mut block_closure = move || {
+                {
+                    use crate::common::WrappedStructField;
+                    self
+                        .script = Script::wrap_set(
+                        crate::mapping::MapFrom::map_from(script),
+                    );
+                }
+            };
+            let __output = block_closure();
+            let __output = __output;
+            __output
+        }
+    }
+    impl crate::common::ExposedStruct for WithOpaque {}
+    #[wasm_bindgen::prelude::wasm_bindgen(typescript_custom_section)]
+    const TYPESCRIPT_DEFINITIONS: &'static str = "/** Thrown by the functions that return a `ValidationError` */\nexport interface ValidationError extends Error {\n    name: \"ValidationError\";\n}\n";
+}
@@ -0,0 +1,7 @@
+pub mod traits {
+    pub trait Listener: super::Listener {
+        fn _wrapper_on_event(&self, event: String) -> u32;
+    }
+    #[wasm_bindgen::prelude::wasm_bindgen(typescript_custom_section)]
+    const TYPESCRIPT_DEFINITIONS: &'static str = "// `Listener` is not exported: traits can't be implemented from JavaScript yet\n";
+}
@@ -0,0 +1,12 @@
+use derive::expose_mod;
+
+#[expose_mod]
+mod shared_transparent {
+    #[expose_struct(shared)]
+    pub struct Point {
+        x: i32,
+        y: i32,
+    }
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use derive::expose_mod;
+
+#[expose_mod]
+mod unnamed_callback_arguments {
+    #[expose_fn]
+    fn apply(cb: fn(u32) -> u32) -> u32 {
+        cb(0)
+    }
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use derive::expose_mod;
+
+#[expose_mod]
+mod complex_pattern {
+    #[expose_fn]
+    fn sum((a, b): (u32, u32)) -> u32 {
+        a + b
+    }
+}
+
+fn main() {}
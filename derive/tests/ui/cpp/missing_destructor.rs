@@ -0,0 +1,11 @@
+use derive::expose_mod;
+
+#[expose_mod]
+mod missing_destructor {
+    #[expose_struct("opaque")]
+    pub struct Wallet {
+        inner: u32,
+    }
+}
+
+fn main() {}
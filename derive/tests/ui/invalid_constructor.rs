@@ -0,0 +1,25 @@
+use derive::expose_mod;
+
+#[expose_mod]
+mod invalid_constructor {
+    #[expose_struct("opaque")]
+    pub struct Network {
+        inner: u32,
+    }
+
+    #[expose_impl]
+    impl Network {
+        #[constructor(name = "magic")]
+        fn magic(&self) -> u32 {
+            self.inner
+        }
+    }
+
+    #[expose_impl]
+    impl Network {
+        #[destructor]
+        fn destroy(_s: Self) {}
+    }
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use derive::expose_mod;
+
+#[expose_mod]
+mod invalid_default {
+    #[expose_fn]
+    fn double(#[expose(defaults = "1")] value: u32) -> u32 {
+        value * 2
+    }
+}
+
+fn main() {}
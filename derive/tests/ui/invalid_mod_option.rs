@@ -0,0 +1,6 @@
+use derive::expose_mod;
+
+#[expose_mod(module_paths)]
+mod invalid_mod_option {}
+
+fn main() {}
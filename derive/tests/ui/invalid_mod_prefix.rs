@@ -0,0 +1,6 @@
+use derive::expose_mod;
+
+#[expose_mod(prefix = "ext-")]
+mod invalid_mod_prefix {}
+
+fn main() {}
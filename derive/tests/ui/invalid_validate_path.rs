@@ -0,0 +1,12 @@
+use derive::expose_mod;
+
+#[expose_mod]
+mod invalid_validate_path {
+    #[expose_struct("opaque")]
+    struct Opaque {
+        #[expose_struct(set(validate = "not a path"))]
+        value: u32,
+    }
+}
+
+fn main() {}
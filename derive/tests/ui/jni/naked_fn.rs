@@ -0,0 +1,8 @@
+use derive::expose_fn;
+
+#[expose_fn]
+fn double(value: u32) -> u32 {
+    value * 2
+}
+
+fn main() {}
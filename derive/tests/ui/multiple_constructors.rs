@@ -0,0 +1,30 @@
+use derive::expose_mod;
+
+#[expose_mod]
+mod multiple_constructors {
+    #[expose_struct("opaque")]
+    pub struct Network {
+        inner: u32,
+    }
+
+    #[expose_impl]
+    impl Network {
+        #[constructor]
+        fn new(inner: u32) -> Self {
+            Network { inner }
+        }
+
+        #[constructor]
+        fn bitcoin() -> Self {
+            Network { inner: 0 }
+        }
+    }
+
+    #[expose_impl]
+    impl Network {
+        #[destructor]
+        fn destroy(_s: Self) {}
+    }
+}
+
+fn main() {}
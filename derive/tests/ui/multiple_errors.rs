@@ -0,0 +1,17 @@
+use derive::expose_mod;
+
+#[expose_mod]
+mod multiple_errors {
+    #[expose_struct("opaque", unknown)]
+    pub struct First {
+        inner: u32,
+    }
+
+    #[expose_struct("opaque")]
+    pub struct Second(u32);
+
+    #[expose_mapping(c)]
+    use std::fmt::Error;
+}
+
+fn main() {}
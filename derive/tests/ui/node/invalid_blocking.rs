@@ -0,0 +1,19 @@
+use derive::expose_mod;
+
+#[expose_mod]
+mod invalid_blocking {
+    #[expose_struct("opaque")]
+    pub struct Wallet {
+        inner: u32,
+    }
+
+    #[expose_impl]
+    impl Wallet {
+        #[blocking]
+        fn sync(&mut self) {
+            self.inner += 1;
+        }
+    }
+}
+
+fn main() {}
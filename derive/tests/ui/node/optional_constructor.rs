@@ -0,0 +1,19 @@
+use derive::expose_mod;
+
+#[expose_mod]
+mod optional_constructor {
+    #[expose_struct("opaque")]
+    pub struct Network {
+        inner: u32,
+    }
+
+    #[expose_impl]
+    impl Network {
+        #[constructor]
+        fn from_magic(magic: u32) -> Option<Self> {
+            Some(Network { inner: magic })
+        }
+    }
+}
+
+fn main() {}
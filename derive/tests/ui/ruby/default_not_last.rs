@@ -0,0 +1,11 @@
+use derive::expose_mod;
+
+#[expose_mod]
+mod default_not_last {
+    #[expose_fn]
+    fn pay(#[expose(default = "1")] fee_rate: u64, amount: u64) -> u64 {
+        amount * fee_rate
+    }
+}
+
+fn main() {}
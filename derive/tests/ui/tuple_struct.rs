@@ -0,0 +1,9 @@
+use derive::expose_mod;
+
+#[expose_mod]
+mod tuple_struct {
+    #[expose_struct("opaque")]
+    pub struct Wrapper(u32);
+}
+
+fn main() {}
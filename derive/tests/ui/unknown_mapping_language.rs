@@ -0,0 +1,9 @@
+use derive::expose_mod;
+
+#[expose_mod]
+mod unknown_mapping_language {
+    #[expose_mapping(c = "i32", pyhton = "pyo3::PyObject")]
+    use std::fmt::Error;
+}
+
+fn main() {}
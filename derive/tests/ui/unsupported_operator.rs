@@ -0,0 +1,26 @@
+use derive::expose_mod;
+
+#[expose_mod]
+mod unsupported_operator {
+    #[expose_struct("opaque")]
+    pub struct Amount {
+        inner: u64,
+    }
+
+    #[expose_impl]
+    impl std::ops::Div for Amount {
+        type Output = Amount;
+
+        fn div(self, rhs: Self) -> Self::Output {
+            Amount { inner: self.inner / rhs.inner }
+        }
+    }
+
+    #[expose_impl]
+    impl Amount {
+        #[destructor]
+        fn destroy(_s: Self) {}
+    }
+}
+
+fn main() {}
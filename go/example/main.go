@@ -0,0 +1,57 @@
+package main
+
+import (
+	"errors"
+	"fmt"
+	"runtime"
+
+	"bdkffi"
+)
+
+func main() {
+	address, err := bdkffi.NewAddress("32qQDeCM67ZyLT1EYvv9HHTG9bXHLVK1gf")
+	if err != nil {
+		panic(err)
+	}
+	fmt.Println(address)
+
+	// `Txid` is shared: the getter takes a new reference, released by its own finalizer
+	outPoint, err := bdkffi.OutPointFromString("e567952fb6cc33857f392efa3a46c995a28f69cca4bb1b37e0204dab1ec7a389:1")
+	if err != nil {
+		panic(err)
+	}
+	fmt.Println(outPoint.Txid())
+
+	script, err := bdkffi.ScriptFromHex("a9140c8e1071fea96ca41f2b6735ef63a882187d39e087")
+	if err != nil {
+		panic(err)
+	}
+	fmt.Println(script.ToHex())
+
+	joined := script.Add(bdkffi.NewScript([]byte{0x51}))
+	fmt.Println(joined.Asm())
+
+	var bitcoinErr *bdkffi.BitcoinError
+	if _, err := bdkffi.NewNetwork("not a network"); !errors.As(err, &bitcoinErr) {
+		panic("expected a BitcoinError")
+	}
+	fmt.Println("caught", bitcoinErr)
+
+	var validationErr *bdkffi.ValidationError
+	if err := address.SetScript(bdkffi.ScriptEmpty()); !errors.As(err, &validationErr) {
+		panic("expected a ValidationError")
+	}
+	fmt.Println("caught", validationErr)
+
+	var indexErr *bdkffi.IndexError
+	if _, err := joined.Index(100); !errors.As(err, &indexErr) {
+		panic("expected an IndexError")
+	}
+	fmt.Println("caught", indexErr)
+
+	fmt.Println(bdkffi.AddressFromScript(script, bdkffi.NetworkTestnet()))
+	fmt.Println(bdkffi.AddressFromScriptWithDefaults(script))
+
+	// The finalizers free the objects
+	runtime.GC()
+}
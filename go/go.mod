@@ -0,0 +1,3 @@
+module bdkffi
+
+go 1.17
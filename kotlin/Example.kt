@@ -0,0 +1,41 @@
+import org.bitcoindevkit.bitcoin.Address
+import org.bitcoindevkit.bitcoin.BitcoinError
+import org.bitcoindevkit.bitcoin.Network
+import org.bitcoindevkit.bitcoin.Script
+
+fun main() {
+    Address("32qQDeCM67ZyLT1EYvv9HHTG9bXHLVK1gf").use { address ->
+        println(address)
+
+        // Fields are copied out of the address, so the copy outlives it
+        val script = address.script
+        address.close()
+        println(script.toHex())
+
+        script.use {
+            Script.fromHex("51").use { other ->
+                (script + other).use { joined ->
+                    println(joined.asm())
+
+                    try {
+                        joined[1000]
+                        check(false)
+                    } catch (e: IndexOutOfBoundsException) {
+                        println("caught $e")
+                    }
+                }
+            }
+
+            try {
+                Network("not a network")
+                check(false)
+            } catch (e: BitcoinError) {
+                println("caught ${e.message}")
+            }
+
+            Network.testnet().use { network ->
+                Address.fromScript(script, network)!!.use { println(it) }
+            }
+        }
+    }
+}
@@ -0,0 +1,43 @@
+const assert = require("assert");
+
+const { Address, Network, Script } = require(".");
+
+const address = new Address("32qQDeCM67ZyLT1EYvv9HHTG9bXHLVK1gf");
+console.log(address.toString());
+
+// Fields are copied out of the address
+const script = address.script;
+console.log(script.toHex());
+console.log(script.toBytes());
+
+const joined = script.add(Script.fromBytes(Buffer.of(0x51)));
+console.log(joined.asm());
+
+try {
+    new Network("not a network");
+    assert.fail();
+} catch (e) {
+    assert(e instanceof Error);
+    assert.strictEqual(e.code, "BitcoinError");
+    console.log(`caught ${e.code}: ${e.message}`);
+}
+
+try {
+    address.script = Script.empty();
+    assert.fail();
+} catch (e) {
+    assert.strictEqual(e.code, "ValidationError");
+    console.log(`caught ${e.code}: ${e.message}`);
+}
+
+try {
+    joined.index(100);
+    assert.fail();
+} catch (e) {
+    assert.strictEqual(e.code, "ERR_OUT_OF_RANGE");
+    console.log(`caught ${e.code}: ${e.message}`);
+}
+
+const fromScript = Address.fromScript(script, Network.testnet());
+console.log(fromScript.toString());
+console.log(Address.fromScript(script).toString());
@@ -0,0 +1 @@
+module.exports = require("./bdk_ffi.node");
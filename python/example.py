@@ -1,5 +1,11 @@
+import logging
+
 from bdk.bitcoin import *
 
+# Forward the records logged by the library to `logging`
+logging.basicConfig(level=logging.INFO)
+install_logger(logging.INFO)
+
 a = Address('32qQDeCM67ZyLT1EYvv9HHTG9bXHLVK1gf')
 print(a.script)
 
@@ -0,0 +1,38 @@
+require_relative "bdk_ffi"
+
+address = Bitcoin::Address.new("32qQDeCM67ZyLT1EYvv9HHTG9bXHLVK1gf")
+puts address
+
+# Fields are copied out of the address
+script = address.script
+puts script.to_hex
+p script.to_bytes
+
+joined = script + Bitcoin::Script.from_bytes("\x51".b)
+puts joined.asm
+p joined
+
+begin
+  Bitcoin::Network.new("not a network")
+  raise "expected an error"
+rescue Bitcoin::BitcoinError => e
+  raise "not a Bitcoin::Error" unless e.is_a?(Bitcoin::Error)
+  puts "caught #{e.class}: #{e.message}"
+end
+
+begin
+  address.script = Bitcoin::Script.empty
+  raise "expected an error"
+rescue ArgumentError => e
+  puts "caught #{e.class}: #{e.message}"
+end
+
+begin
+  joined[100]
+  raise "expected an error"
+rescue IndexError => e
+  puts "caught #{e.class}: #{e.message}"
+end
+
+puts Bitcoin::Address.from_script(script, Bitcoin::Network.testnet)
+puts Bitcoin::Address.from_script(script)
@@ -4,9 +4,10 @@ use super::*;
 mod bitcoin {
     use std::ops::Deref;
 
+    #[expose_mapping(c = "i32")]
     use super::BitcoinError;
 
-    #[expose_struct("opaque", to_string)]
+    #[expose_struct("opaque", to_string, bytes)]
     pub struct Script {
         inner: bdk::bitcoin::Script,
     }
@@ -20,9 +21,11 @@ mod bitcoin {
         #[destructor]
         fn destroy(_s: Self) {}
 
+        #[constructor(name = "empty")]
         fn empty() -> Self {
             bdk::bitcoin::Script::new().into()
         }
+        #[constructor(name = "from_hex")]
         fn from_hex(hex: String) -> Result<Self, BitcoinError> {
             use bdk::bitcoin::hashes::hex::FromHex;
             Ok(bdk::bitcoin::Script::from_hex(&hex)?.into())
@@ -40,6 +43,40 @@ mod bitcoin {
         }
     }
 
+    impl crate::common::ByteContainer for Script {
+        fn as_bytes(&self) -> &[u8] {
+            self.deref().as_bytes()
+        }
+
+        fn from_bytes(bytes: Vec<u8>) -> Self {
+            bdk::bitcoin::Script::from(bytes).into()
+        }
+    }
+
+    #[expose_impl]
+    impl std::ops::Add for Script {
+        type Output = Script;
+
+        fn add(self, rhs: Self) -> Self::Output {
+            let mut bytes = self.deref().to_bytes();
+            bytes.extend(rhs.as_bytes());
+            bdk::bitcoin::Script::from(bytes).into()
+        }
+    }
+    #[expose_impl]
+    impl std::ops::Index<usize> for Script {
+        type Output = u8;
+
+        fn index(&self, index: usize) -> &Self::Output {
+            &self.as_bytes()[index]
+        }
+    }
+    impl crate::common::CheckedIndex<usize> for Script {
+        fn checked_index(&self, index: usize) -> Option<&u8> {
+            self.as_bytes().get(index)
+        }
+    }
+
     #[expose_struct("opaque", to_string)]
     pub struct Network {
         inner: bdk::bitcoin::Network,
@@ -56,15 +93,19 @@ mod bitcoin {
         #[destructor]
         fn destroy(_s: Self) {}
 
+        #[constructor(name = "bitcoin")]
         fn bitcoin() -> Self {
             bdk::bitcoin::Network::Bitcoin.into()
         }
+        #[constructor(name = "testnet")]
         fn testnet() -> Self {
             bdk::bitcoin::Network::Testnet.into()
         }
+        #[constructor(name = "regtest")]
         fn regtest() -> Self {
             bdk::bitcoin::Network::Regtest.into()
         }
+        #[constructor(name = "signet")]
         fn signet() -> Self {
             bdk::bitcoin::Network::Signet.into()
         }
@@ -88,14 +129,18 @@ mod bitcoin {
 
     #[expose_struct(to_string)]
     pub struct Address {
-        #[expose_struct(get)] // TODO: add the setter manually to check if the script is valid
+        #[expose_struct(get, set(validate = "Address::validate_script"))]
         script: Script,
         #[expose_struct(get, set)]
         network: Network,
     }
     #[expose_impl]
     impl Address {
-        fn from_script(script: &Script, network: &Network) -> Option<Self> {
+        #[constructor(name = "from_script")]
+        fn from_script(
+            script: &Script,
+            #[expose(default = "bdk::bitcoin::Network::Bitcoin.into()")] network: &Network,
+        ) -> Option<Self> {
             // check if the script can be turned into an address
             bdk::bitcoin::Address::from_script(script.deref(), network.deref().clone())
                 .map(|_| Address {
@@ -122,6 +167,15 @@ mod bitcoin {
         }
     }
     impl Address {
+        fn validate_script(&self, script: &Script) -> Result<(), &'static str> {
+            use crate::common::AccessContainer;
+
+            let network = self.network.access_container(|n| n.clone());
+            bdk::bitcoin::Address::from_script(script, *network)
+                .map(|_| ())
+                .ok_or("The script can't be converted to an address")
+        }
+
         fn as_native(&self) -> bdk::bitcoin::Address {
             use crate::common::AccessContainer;
 
@@ -131,6 +185,65 @@ mod bitcoin {
         }
     }
 
+    /// Shared, so that it can be kept after the `OutPoint` it was read from is destroyed
+    #[expose_struct("opaque", shared, to_string)]
+    pub struct Txid {
+        inner: bdk::bitcoin::Txid,
+    }
+    wrap_struct!(Txid, bdk::bitcoin::Txid);
+    #[expose_impl]
+    impl Txid {
+        #[constructor]
+        fn from_hex(hex: String) -> Result<Self, BitcoinError> {
+            use bdk::bitcoin::hashes::hex::FromHex;
+            Ok(bdk::bitcoin::Txid::from_hex(&hex)?.into())
+        }
+        #[destructor]
+        fn destroy(_s: Self) {}
+
+        fn to_string(&self) -> String {
+            self.deref().to_string()
+        }
+    }
+
+    #[expose_struct("opaque", to_string)]
+    pub struct OutPoint {
+        #[expose_struct(get, set)]
+        txid: Txid,
+        #[expose_struct(get, set)]
+        vout: u32,
+    }
+    #[expose_impl]
+    impl OutPoint {
+        #[constructor]
+        fn new(txid: &Txid, vout: u32) -> Self {
+            OutPoint {
+                txid: MapFrom::map_from(txid),
+                vout,
+            }
+        }
+        #[constructor(name = "from_string")]
+        fn from_string(s: String) -> Result<Self, BitcoinError> {
+            use std::str::FromStr;
+            use crate::common::IntoWrapped;
+
+            let outpoint = bdk::bitcoin::OutPoint::from_str(&s)?;
+            Ok(OutPoint {
+                txid: MapFrom::map_from(outpoint.txid.into_wrapped()),
+                vout: outpoint.vout,
+            })
+        }
+        #[destructor]
+        fn destroy(_s: Self) {}
+
+        fn to_string(&self) -> String {
+            use crate::common::AccessContainer;
+
+            let txid = self.txid.access_container(|t| *t.deref());
+            bdk::bitcoin::OutPoint::new(txid, self.vout).to_string()
+        }
+    }
+
     // pub use transaction::*;
     // #[expose_mod]
     // mod transaction {
@@ -347,13 +460,18 @@ impl From<std::io::Error> for BitcoinError {
 }
 #[cfg(feature = "python")]
 impl_py_error!(BitcoinError);
+#[cfg(feature = "wasm")]
+impl_wasm_error!(BitcoinError);
+#[cfg(feature = "node")]
+impl_node_error!(BitcoinError);
+#[cfg(feature = "ruby")]
+impl_ruby_error!(BitcoinError);
 #[cfg(feature = "c")]
 impl langs::IntoPlatformError for BitcoinError {
     type TargetType = i32;
 
     fn into_platform_error(self) -> Self::TargetType {
-        // match self {
-        // }
+        langs::set_last_error(format!("{:?}", self));
         -1
     }
 
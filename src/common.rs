@@ -11,17 +11,57 @@ pub trait WrappedStructField {
     type Getter;
     type Setter;
 
+    // The JNI getters clone the fields instead
+    #[cfg_attr(feature = "jni", allow(dead_code))]
     fn wrap_get(s: &mut Self::Store) -> Self::Getter;
 
     fn wrap_set(s: Self::Setter) -> Self::Store;
 }
 
+/// Error returned by setters when the new value is rejected by their `validate` function
+#[derive(Debug)]
+pub struct ValidationError(pub String);
+
+impl ValidationError {
+    pub fn from_display<E: std::fmt::Display>(e: E) -> Self {
+        ValidationError(e.to_string())
+    }
+}
+
+/// Opaque structs backed by a byte container, exposed with the `bytes` option of
+/// `#[expose_struct]`
+#[cfg_attr(not(feature = "python"), allow(dead_code))]
+pub trait ByteContainer: Sized {
+    fn as_bytes(&self) -> &[u8];
+
+    fn from_bytes(bytes: Vec<u8>) -> Self;
+}
+
+/// Error returned by exposed `Index` implementations when the index is out of bounds
+#[derive(Debug)]
+pub struct IndexError;
+
+/// Required by the `Index` implementations marked with `#[expose_impl]`, so that out-of-bounds
+/// indexes are reported without unwinding, which isn't possible with `panic = "abort"`
+pub trait CheckedIndex<Idx>: std::ops::Index<Idx> {
+    /// Like `Index::index`, but returns `None` instead of panicking when out of bounds
+    fn checked_index(&self, index: Idx) -> Option<&Self::Output>;
+}
+
 pub trait AccessContainer {
     type Content;
 
     /// Access a container and pass a reference to its content to the closure
     fn access_container<R, F: Fn(&Self::Content) -> R>(&self, f: F) -> R;
+}
 
+/// Containers that also hand out mutable access to their content. `shared` structs don't
+/// implement it: they are immutable once exposed, since any other holder may read them concurrently
+///
+/// Only for the hand-written code of the exposed modules, the generated code never mutates fields
+/// through it
+#[allow(dead_code)]
+pub trait AccessContainerMut: AccessContainer {
     /// Same as `access_container()` but mutably
     fn access_container_mut<R, F: Fn(&mut Self::Content) -> R>(&mut self, f: F) -> R;
 }
@@ -1,15 +1,17 @@
 use crate::mapping::*;
 use crate::common::*;
 
-pub struct Destroy<T>(*mut T);
+use super::ptr::*;
 
-impl<T> std::ops::Drop for Destroy<T> {
+pub struct Destroy<T: ExposedPtr>(*mut T);
+
+impl<T: ExposedPtr> std::ops::Drop for Destroy<T> {
     fn drop(&mut self) {
-        let _inner = unsafe { Box::from_raw(self.0) };
+        unsafe { <T::Kind as PtrKind<T>>::drop_raw(self.0) }
     }
 }
 
-impl<T> MapFrom<*mut T> for Destroy<T> {
+impl<T: ExposedPtr> MapFrom<*mut T> for Destroy<T> {
     #[inline]
     fn map_from(ptr: *mut T) -> Self {
         Destroy(ptr)
@@ -24,6 +26,32 @@ pub trait IntoPlatformError {
     fn ok() -> Self::TargetType;
 }
 
+impl IntoPlatformError for ValidationError {
+    type TargetType = i32;
+
+    fn into_platform_error(self) -> Self::TargetType {
+        set_last_error(self.0);
+        -1
+    }
+
+    fn ok() -> Self::TargetType {
+        0
+    }
+}
+
+impl IntoPlatformError for IndexError {
+    type TargetType = i32;
+
+    fn into_platform_error(self) -> Self::TargetType {
+        set_last_error("index out of range".into());
+        -1
+    }
+
+    fn ok() -> Self::TargetType {
+        0
+    }
+}
+
 #[derive(Debug)]
 pub struct PlatformOption;
 
@@ -41,41 +69,233 @@ pub trait IntoTraitStruct: Sized {
     fn into_trait_struct(self) -> Self::Target;
 }
 
-#[inline]
-pub fn take_ptr<I>(this: *mut libc::c_void) -> Box<I> {
-    unsafe { Box::from_raw(this as *mut I) }
-}
+/// Types with a C layout, that can be stored inline in a transparent struct
+///
+/// Automatically implemented by `#[expose_struct]` on transparent structs. Opaque structs,
+/// `String`s and other Rust-only types don't implement it
+pub trait ReprC {}
 
-#[repr(C)]
-#[derive(Clone, Debug)]
-pub struct Arr<T> {
-    pub ptr: *const T,
-    pub len: usize,
+macro_rules! impl_repr_c {
+    ($ty:ty) => {
+        impl ReprC for $ty {}
+    };
 }
+impl_repr_c!(bool);
+impl_repr_c!(i8);
+impl_repr_c!(u8);
+impl_repr_c!(i16);
+impl_repr_c!(u16);
+impl_repr_c!(i32);
+impl_repr_c!(u32);
+impl_repr_c!(i64);
+impl_repr_c!(u64);
+impl_repr_c!(isize);
+impl_repr_c!(usize);
+impl_repr_c!(f32);
+impl_repr_c!(f64);
 
-impl<T> AccessContainer for Box<T> {
-    type Content = T;
+impl<T> ReprC for *mut T {}
+// Opaque fields of transparent structs, which C sees as `T*`
+impl<T> ReprC for Box<T> {}
+impl<T> ReprC for *const T {}
+impl<T: ReprC, const N: usize> ReprC for [T; N] {}
+
+/// Fails to compile if `T` can't be stored inline in a transparent struct
+#[inline]
+pub fn assert_repr_c<T: ReprC>() {}
 
-    fn access_container<R, F: Fn(&Self::Content) -> R>(&self, f: F) -> R {
-        f(self)
+/// Free a string returned by the C API, which are allocated by Rust. Ignores null pointers
+///
+/// # Safety
+///
+/// `s` must be null or a string returned by the C API that hasn't been freed yet
+#[export_name = concat!(env!("EXPOSE_C_PREFIX"), "string_free")]
+pub unsafe extern "C" fn string_free(s: *mut libc::c_char) {
+    if !s.is_null() {
+        drop(std::ffi::CString::from_raw(s));
     }
+}
 
-    fn access_container_mut<R, F: Fn(&mut Self::Content) -> R>(&mut self, f: F) -> R {
-        f(self)
+/// Free a vector returned by the C API, which are allocated by Rust. Ignores null pointers
+///
+/// # Safety
+///
+/// `arr` must be null or a vector of bytes returned by the C API that hasn't been freed yet
+#[export_name = concat!(env!("EXPOSE_C_PREFIX"), "bytes_free")]
+pub unsafe extern "C" fn bytes_free(arr: Arr<u8>) {
+    if !arr.ptr.is_null() {
+        drop(Box::from_raw(std::ptr::slice_from_raw_parts_mut(
+            arr.ptr as *mut u8,
+            arr.len,
+        )));
     }
 }
 
-impl<T: ExposedStruct> WrappedStructField for T {
-    type Store = Box<T>;
+/// Version of the library, compare it with the version from the header to detect a mismatch.
+/// The string is static and must not be freed
+#[export_name = concat!(env!("EXPOSE_C_PREFIX"), "ffi_version")]
+pub extern "C" fn ffi_version() -> *const libc::c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr() as *const libc::c_char
+}
+
+/// Hash of the declarations of the header generated with this library, which
+/// `BDK_FFI_ABI_COMPATIBLE()` compares to the `FFI_ABI_HASH` of the header the caller was built
+/// against
+#[export_name = concat!(env!("EXPOSE_C_PREFIX"), "ffi_abi_hash")]
+pub extern "C" fn ffi_abi_hash() -> u64 {
+    include!(concat!(env!("OUT_DIR"), "/abi_hash.rs"))
+}
+
+thread_local! {
+    /// Message of the last error returned on this thread, see `last_error_message()`
+    static LAST_ERROR: std::cell::RefCell<Option<std::ffi::CString>> = const {
+        std::cell::RefCell::new(None)
+    };
+}
+
+/// Record the message of an error returned to C, for `last_error_message()`
+pub fn set_last_error(message: String) {
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(lossy_c_string(message)));
+}
+
+/// Message of the last error returned by a function of the library on the calling thread, or null
+/// if none failed yet. Only meaningful right after a function returned an error code, since
+/// successful calls don't reset it. The string is owned by the caller and freed with `string_free()`
+#[export_name = concat!(env!("EXPOSE_C_PREFIX"), "last_error_message")]
+pub extern "C" fn last_error_message() -> *mut libc::c_char {
+    LAST_ERROR.with(|last| match &*last.borrow() {
+        Some(message) => message.clone().into_raw(),
+        None => std::ptr::null_mut(),
+    })
+}
+
+// Levels passed to `install_logger()` and `set_log_level()`, and to the callback
+pub const LOG_LEVEL_OFF: i32 = 0;
+pub const LOG_LEVEL_ERROR: i32 = 1;
+pub const LOG_LEVEL_WARN: i32 = 2;
+pub const LOG_LEVEL_INFO: i32 = 3;
+pub const LOG_LEVEL_DEBUG: i32 = 4;
+pub const LOG_LEVEL_TRACE: i32 = 5;
+
+/// Receives the records logged by the library, with one of the `LOG_LEVEL_*` levels. `target` and
+/// `message` are only valid during the call. Called from whichever thread logged the record.
+/// Nullable, so that cbindgen declares it as a function pointer
+pub type LogCallback = Option<
+    unsafe extern "C" fn(
+        user_data: *mut libc::c_void,
+        level: i32,
+        target: *const libc::c_char,
+        message: *const libc::c_char,
+    ),
+>;
+
+#[derive(Clone, Copy)]
+struct CLogger {
+    callback: LogCallback,
+    user_data: *mut libc::c_void,
+}
+
+// The caller is responsible for `user_data` being usable from any thread
+unsafe impl Send for CLogger {}
+unsafe impl Sync for CLogger {}
+
+static C_LOGGER: std::sync::RwLock<CLogger> = std::sync::RwLock::new(CLogger {
+    callback: None,
+    user_data: std::ptr::null_mut(),
+});
 
-    type Getter = *mut T;
-    type Setter = T;
+struct CLogBridge;
 
-    fn wrap_get(s: &mut Self::Store) -> Self::Getter {
-        &mut **s as *mut T
+impl log::Log for CLogBridge {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= log::max_level()
     }
 
-    fn wrap_set(s: Self::Setter) -> Self::Store {
-        Box::new(s)
+    fn log(&self, record: &log::Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let target = lossy_c_string(record.target().to_string());
+        let message = lossy_c_string(record.args().to_string());
+
+        // Copied out of the lock, so that the callback can log or replace itself
+        let logger = *C_LOGGER.read().unwrap_or_else(|e| e.into_inner());
+        let callback = match logger.callback {
+            Some(callback) => callback,
+            None => return,
+        };
+        unsafe {
+            callback(
+                logger.user_data,
+                record.level() as i32,
+                target.as_ptr(),
+                message.as_ptr(),
+            )
+        };
+    }
+
+    fn flush(&self) {}
+}
+
+/// Drops the nul bytes, which can't be represented in a C string
+fn lossy_c_string(s: String) -> std::ffi::CString {
+    std::ffi::CString::new(s).unwrap_or_else(|e| {
+        let mut bytes = e.into_vec();
+        bytes.retain(|b| *b != 0);
+        std::ffi::CString::new(bytes).unwrap()
+    })
+}
+
+fn level_filter(level: i32) -> log::LevelFilter {
+    match level {
+        i32::MIN..=LOG_LEVEL_OFF => log::LevelFilter::Off,
+        LOG_LEVEL_ERROR => log::LevelFilter::Error,
+        LOG_LEVEL_WARN => log::LevelFilter::Warn,
+        LOG_LEVEL_INFO => log::LevelFilter::Info,
+        LOG_LEVEL_DEBUG => log::LevelFilter::Debug,
+        _ => log::LevelFilter::Trace,
     }
 }
+
+/// Forward the records logged by the library up to `level` to `callback`, replacing the previous
+/// one. Pass a null `callback` to stop logging. Once this returns the previous callback won't be
+/// called anymore, but calls already in progress on other threads may still be running
+///
+/// # Safety
+///
+/// `callback` must be safe to call with `user_data` from any thread until it's replaced, and until
+/// the calls in progress at that time return
+#[export_name = concat!(env!("EXPOSE_C_PREFIX"), "install_logger")]
+pub unsafe extern "C" fn install_logger(
+    callback: LogCallback,
+    user_data: *mut libc::c_void,
+    level: i32,
+) {
+    static BRIDGE: CLogBridge = CLogBridge;
+    // Fails if it's already installed, which is fine
+    let _ = log::set_logger(&BRIDGE);
+
+    *C_LOGGER.write().unwrap_or_else(|e| e.into_inner()) = CLogger { callback, user_data };
+    log::set_max_level(match callback {
+        Some(_) => level_filter(level),
+        None => log::LevelFilter::Off,
+    });
+}
+
+/// Change the level of the records passed to the callback set with `install_logger()`
+#[export_name = concat!(env!("EXPOSE_C_PREFIX"), "set_log_level")]
+pub extern "C" fn set_log_level(level: i32) {
+    log::set_max_level(level_filter(level));
+}
+
+#[inline]
+pub fn take_ptr<I>(this: *mut libc::c_void) -> Box<I> {
+    unsafe { Box::from_raw(this as *mut I) }
+}
+
+#[repr(C)]
+#[derive(Clone, Debug)]
+pub struct Arr<T> {
+    pub ptr: *const T,
+    pub len: usize,
+}
@@ -0,0 +1,233 @@
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use ::jni::objects::JString;
+use ::jni::sys::{
+    jboolean, jbyte, jbyteArray, jdouble, jfloat, jint, jlong, jobject, jshort, jstring,
+};
+use ::jni::JNIEnv;
+
+use super::ptr::*;
+use crate::common::*;
+
+/// Exception to throw when returning to the JVM
+#[derive(Debug)]
+pub struct JniThrow {
+    /// Binary name of the exception class, like `java/lang/IllegalStateException`
+    pub class: String,
+    pub message: String,
+}
+
+impl JniThrow {
+    pub fn new<C: Into<String>, M: Into<String>>(class: C, message: M) -> Self {
+        JniThrow {
+            class: class.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl From<::jni::errors::Error> for JniThrow {
+    fn from(e: ::jni::errors::Error) -> Self {
+        JniThrow::new("java/lang/RuntimeException", e.to_string())
+    }
+}
+
+/// Values returned to the JVM when an exception is thrown, which are ignored by the caller
+pub trait JniReturn {
+    fn null() -> Self;
+}
+
+macro_rules! impl_jni_return {
+    ($ty:ty, $null:expr) => {
+        impl JniReturn for $ty {
+            #[inline]
+            fn null() -> Self {
+                $null
+            }
+        }
+    };
+}
+impl_jni_return!((), ());
+impl_jni_return!(jboolean, 0);
+impl_jni_return!(jbyte, 0);
+impl_jni_return!(jshort, 0);
+impl_jni_return!(jint, 0);
+impl_jni_return!(jlong, 0);
+impl_jni_return!(jfloat, 0.0);
+impl_jni_return!(jdouble, 0.0);
+impl_jni_return!(jobject, std::ptr::null_mut());
+
+/// Run the body of a `Java_*` entry point, turning errors and panics into Java exceptions
+pub fn jni_call<R: JniReturn, F: FnOnce() -> Result<R, JniThrow>>(env: JNIEnv, f: F) -> R {
+    let throw = match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(value)) => return value,
+        Ok(Err(throw)) => throw,
+        Err(panic) => {
+            let message = panic
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| panic.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "Rust panic".to_string());
+            JniThrow::new("java/lang/RuntimeException", message)
+        }
+    };
+
+    // Don't replace an exception that is already pending, like one thrown by a JNI call
+    if !env.exception_check().unwrap_or(false) {
+        let _ = env.throw_new(throw.class, throw.message);
+    }
+    R::null()
+}
+
+/// Move a value to the heap, returning the handle stored by its Kotlin class
+#[inline]
+pub fn into_handle<T: ExposedPtr>(t: T) -> jlong {
+    <T::Kind as PtrKind<T>>::into_raw(t) as jlong
+}
+
+/// Drop a value previously returned by `into_handle`
+///
+/// # Safety
+///
+/// The handle must be valid and not used anymore after this call
+pub unsafe fn drop_handle<T: ExposedPtr>(handle: jlong) {
+    if handle != 0 {
+        <T::Kind as PtrKind<T>>::drop_raw(handle as *mut T)
+    }
+}
+
+/// # Safety
+///
+/// The handle must be either `0` or valid for the returned lifetime
+pub unsafe fn borrow_handle<'a, T>(handle: jlong) -> Result<&'a T, JniThrow> {
+    (handle as *const T).as_ref().ok_or_else(closed_error)
+}
+
+/// # Safety
+///
+/// The handle must be either `0` or valid and not aliased for the returned lifetime
+pub unsafe fn borrow_handle_mut<'a, T: UniquePtr>(handle: jlong) -> Result<&'a mut T, JniThrow> {
+    (handle as *mut T).as_mut().ok_or_else(closed_error)
+}
+
+fn closed_error() -> JniThrow {
+    JniThrow::new(
+        "java/lang/IllegalStateException",
+        "The object has already been closed",
+    )
+}
+
+/// Conversion of an argument received from the JVM
+pub trait FromJni<'a, Source>: Sized {
+    fn from_jni(env: JNIEnv<'a>, s: Source) -> Result<Self, JniThrow>;
+}
+
+/// Conversion of a value returned to the JVM
+pub trait IntoJni<'a, Target> {
+    fn into_jni(self, env: JNIEnv<'a>) -> Result<Target, JniThrow>;
+}
+
+// Java doesn't have unsigned types, so those are reinterpreted as the signed type of the same size
+macro_rules! impl_jni_primitive {
+    ($ty:ty, $jty:ty) => {
+        impl<'a> FromJni<'a, $jty> for $ty {
+            #[inline]
+            fn from_jni(_env: JNIEnv<'a>, s: $jty) -> Result<Self, JniThrow> {
+                Ok(s as $ty)
+            }
+        }
+
+        impl<'a> IntoJni<'a, $jty> for $ty {
+            #[inline]
+            fn into_jni(self, _env: JNIEnv<'a>) -> Result<$jty, JniThrow> {
+                Ok(self as $jty)
+            }
+        }
+    };
+}
+impl_jni_primitive!(i8, jbyte);
+impl_jni_primitive!(u8, jbyte);
+impl_jni_primitive!(i16, jshort);
+impl_jni_primitive!(u16, jshort);
+impl_jni_primitive!(i32, jint);
+impl_jni_primitive!(u32, jint);
+impl_jni_primitive!(i64, jlong);
+impl_jni_primitive!(u64, jlong);
+impl_jni_primitive!(isize, jlong);
+impl_jni_primitive!(usize, jlong);
+impl_jni_primitive!(f32, jfloat);
+impl_jni_primitive!(f64, jdouble);
+
+impl<'a> FromJni<'a, jboolean> for bool {
+    #[inline]
+    fn from_jni(_env: JNIEnv<'a>, s: jboolean) -> Result<Self, JniThrow> {
+        Ok(s != 0)
+    }
+}
+
+impl<'a> IntoJni<'a, jboolean> for bool {
+    #[inline]
+    fn into_jni(self, _env: JNIEnv<'a>) -> Result<jboolean, JniThrow> {
+        Ok(self as jboolean)
+    }
+}
+
+impl<'a> IntoJni<'a, ()> for () {
+    #[inline]
+    fn into_jni(self, _env: JNIEnv<'a>) -> Result<(), JniThrow> {
+        Ok(())
+    }
+}
+
+impl<'a> FromJni<'a, JString<'a>> for String {
+    fn from_jni(env: JNIEnv<'a>, s: JString<'a>) -> Result<Self, JniThrow> {
+        Ok(env.get_string(s)?.into())
+    }
+}
+
+impl<'a> IntoJni<'a, jstring> for String {
+    fn into_jni(self, env: JNIEnv<'a>) -> Result<jstring, JniThrow> {
+        Ok(env.new_string(self)?.into_inner())
+    }
+}
+
+impl<'a> IntoJni<'a, jstring> for Option<String> {
+    fn into_jni(self, env: JNIEnv<'a>) -> Result<jstring, JniThrow> {
+        match self {
+            Some(s) => s.into_jni(env),
+            None => Ok(std::ptr::null_mut()),
+        }
+    }
+}
+
+impl<'a> FromJni<'a, jbyteArray> for Vec<u8> {
+    fn from_jni(env: JNIEnv<'a>, s: jbyteArray) -> Result<Self, JniThrow> {
+        Ok(env.convert_byte_array(s)?)
+    }
+}
+
+impl<'a> IntoJni<'a, jbyteArray> for Vec<u8> {
+    fn into_jni(self, env: JNIEnv<'a>) -> Result<jbyteArray, JniThrow> {
+        self.as_slice().into_jni(env)
+    }
+}
+
+impl<'a> IntoJni<'a, jbyteArray> for &[u8] {
+    fn into_jni(self, env: JNIEnv<'a>) -> Result<jbyteArray, JniThrow> {
+        Ok(env.byte_array_from_slice(self)?)
+    }
+}
+
+// Errors returned by setters and indexing are thrown as the matching Java exceptions, instead of
+// the exceptions generated for the other error types
+impl From<ValidationError> for JniThrow {
+    fn from(e: ValidationError) -> Self {
+        JniThrow::new("java/lang/IllegalArgumentException", e.0)
+    }
+}
+
+impl From<IndexError> for JniThrow {
+    fn from(_: IndexError) -> Self {
+        JniThrow::new("java/lang/IndexOutOfBoundsException", "index out of range")
+    }
+}
@@ -0,0 +1,33 @@
+//! Storage of the exposed structs that are owned by a JavaScript object, shared by wasm and Node
+
+use crate::common::*;
+
+// Values are copied in and out of JS, so the fields are stored inline
+impl<T: ExposedStruct + Clone> WrappedStructField for T {
+    type Store = T;
+
+    type Getter = T;
+    type Setter = T;
+
+    fn wrap_get(s: &mut Self::Store) -> Self::Getter {
+        s.clone()
+    }
+
+    fn wrap_set(s: Self::Setter) -> Self::Store {
+        s
+    }
+}
+
+impl<T: ExposedStruct> AccessContainer for T {
+    type Content = T;
+
+    fn access_container<R, F: Fn(&Self::Content) -> R>(&self, f: F) -> R {
+        f(self)
+    }
+}
+
+impl<T: ExposedStruct> AccessContainerMut for T {
+    fn access_container_mut<R, F: Fn(&mut Self::Content) -> R>(&mut self, f: F) -> R {
+        f(self)
+    }
+}
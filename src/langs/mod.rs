@@ -1,3 +1,8 @@
+#[cfg(any(feature = "c", feature = "jni"))]
+pub mod ptr;
+#[cfg(any(feature = "c", feature = "jni"))]
+pub use ptr::*;
+
 #[cfg(feature = "c")]
 pub mod c;
 #[cfg(feature = "c")]
@@ -8,3 +13,29 @@ pub use c::*;
 pub mod python;
 #[cfg(feature = "python")]
 pub use python::*;
+
+#[cfg(feature = "jni")]
+pub mod jni;
+#[cfg(feature = "jni")]
+pub use self::jni::*;
+
+#[cfg(any(feature = "wasm", feature = "node"))]
+pub mod js;
+
+#[cfg(feature = "wasm")]
+#[macro_use]
+pub mod wasm;
+#[cfg(feature = "wasm")]
+pub use wasm::*;
+
+#[cfg(feature = "node")]
+#[macro_use]
+pub mod napi;
+#[cfg(feature = "node")]
+pub use self::napi::*;
+
+#[cfg(feature = "ruby")]
+#[macro_use]
+pub mod ruby;
+#[cfg(feature = "ruby")]
+pub use self::ruby::*;
@@ -0,0 +1,363 @@
+use std::convert::TryFrom;
+use std::ptr;
+use std::sync::mpsc;
+use std::thread::{self, ThreadId};
+
+use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{
+    ErrorStrategy, ThreadSafeCallContext, ThreadsafeFunction, ThreadsafeFunctionCallMode,
+};
+use napi::{JsFunction, JsObject, JsUnknown, NapiValue};
+
+use crate::common::*;
+
+/// Error thrown to JavaScript: an `Error` with the name of the Rust error type as `code`
+pub type NodeError = napi::Error<String>;
+
+pub fn node_error(code: &str, message: String) -> NodeError {
+    napi::Error::new(code.to_string(), message)
+}
+
+#[macro_export]
+macro_rules! impl_node_error {
+    ($type:ident) => {
+        impl From<$type> for ::napi::Error<String> {
+            fn from(e: $type) -> Self {
+                $crate::langs::node_error(stringify!($type), format!("{:?}", e))
+            }
+        }
+    };
+}
+
+impl From<ValidationError> for NodeError {
+    fn from(e: ValidationError) -> Self {
+        node_error("ValidationError", e.0)
+    }
+}
+
+// Same code as the errors thrown by Node for out-of-bounds accesses
+impl From<IndexError> for NodeError {
+    fn from(_: IndexError) -> Self {
+        node_error("ERR_OUT_OF_RANGE", "index out of range".to_string())
+    }
+}
+
+/// Throw a `TypeError` instead of the plain `Error` napi-rs throws, and return the error telling
+/// napi-rs that it's pending
+///
+/// # Safety
+///
+/// `env` must be the environment of the current call
+unsafe fn throw_type_error(env: sys::napi_env, message: String) -> napi::Error {
+    napi::JsTypeError::from(napi::Error::from_reason(message)).throw_into(env);
+    napi::Error::new(napi::Status::PendingException, "TypeError".to_string())
+}
+
+/// Same as `throw_type_error`, with a `RangeError`
+///
+/// # Safety
+///
+/// `env` must be the environment of the current call
+unsafe fn throw_range_error(env: sys::napi_env, message: String) -> napi::Error {
+    napi::JsRangeError::from(napi::Error::from_reason(message)).throw_into(env);
+    napi::Error::new(napi::Status::PendingException, "RangeError".to_string())
+}
+
+/// `u64` taken from a `BigInt`, which must be positive and fit in 64 bits. Otherwise a
+/// `TypeError` is thrown
+pub struct JsU64(pub u64);
+
+impl FromNapiValue for JsU64 {
+    unsafe fn from_napi_value(env: sys::napi_env, value: sys::napi_value) -> napi::Result<Self> {
+        match BigInt::from_napi_value(env, value)?.get_u64() {
+            (false, n, true) => Ok(JsU64(n)),
+            _ => Err(throw_type_error(
+                env,
+                "The BigInt must be positive and fit in 64 bits".to_string(),
+            )),
+        }
+    }
+}
+
+impl TypeName for JsU64 {
+    fn type_name() -> &'static str {
+        "BigInt"
+    }
+
+    fn value_type() -> ValueType {
+        ValueType::BigInt
+    }
+}
+
+impl ValidateNapiValue for JsU64 {}
+
+/// `usize` returned as a number. A `RangeError` is thrown if it's too large for a `u32`
+pub struct JsUsize(pub usize);
+
+impl ToNapiValue for JsUsize {
+    unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> napi::Result<sys::napi_value> {
+        match u32::try_from(val.0) {
+            Ok(n) => u32::to_napi_value(env, n),
+            Err(_) => Err(throw_range_error(
+                env,
+                format!("{} is out of the range of a JS number", val.0),
+            )),
+        }
+    }
+}
+
+impl TypeName for JsUsize {
+    fn type_name() -> &'static str {
+        "number"
+    }
+
+    fn value_type() -> ValueType {
+        ValueType::Number
+    }
+}
+
+pub trait IntoTraitStruct: Sized {
+    type Target;
+
+    fn into_trait_struct(self) -> Self::Target;
+}
+
+/// Call marked with `#[blocking]`, run on the libuv thread pool. The Promise returned to JS is
+/// resolved with its output
+pub struct Blocking<T>(Option<Box<dyn FnOnce() -> std::result::Result<T, NodeError> + Send>>);
+
+impl<T: ToNapiValue + TypeName + Send + 'static> Blocking<T> {
+    pub fn spawn<F: FnOnce() -> std::result::Result<T, NodeError> + Send + 'static>(
+        f: F,
+    ) -> AsyncTask<Self> {
+        AsyncTask::new(Blocking(Some(Box::new(f))))
+    }
+}
+
+impl<T: ToNapiValue + TypeName + Send + 'static> Task for Blocking<T> {
+    type Output = std::result::Result<T, NodeError>;
+    type JsValue = T;
+
+    fn compute(&mut self) -> napi::Result<Self::Output> {
+        let f = self.0.take().expect("Blocking call already run");
+
+        // Reject the Promise instead of bringing down the whole process
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(f))
+            .map_err(|_| napi::Error::from_reason("Blocking call panicked"))
+    }
+
+    fn resolve(&mut self, env: Env, output: Self::Output) -> napi::Result<T> {
+        // The error has to be created on the JS thread to keep its `code`
+        output.map_err(|e| napi::JsError::from(e).into_unknown(env).into())
+    }
+}
+
+type Callback = Box<dyn FnOnce(Env) + Send>;
+
+/// Arguments of a call to JavaScript, as a tuple
+pub trait JsArgs: Send + 'static {
+    /// # Safety
+    ///
+    /// `env` must be the environment of the current call, on the JS thread
+    unsafe fn into_js_args(self, env: sys::napi_env) -> napi::Result<Vec<sys::napi_value>>;
+}
+
+macro_rules! impl_js_args {
+    ($($arg:ident),*) => {
+        impl<$($arg: ToNapiValue + Send + 'static),*> JsArgs for ($($arg,)*) {
+            #[allow(non_snake_case, unused_variables)]
+            unsafe fn into_js_args(self, env: sys::napi_env) -> napi::Result<Vec<sys::napi_value>> {
+                let ($($arg,)*) = self;
+                Ok(vec![$($arg::to_napi_value(env, $arg)?),*])
+            }
+        }
+    };
+}
+
+impl_js_args!();
+impl_js_args!(A);
+impl_js_args!(A, B);
+impl_js_args!(A, B, C);
+impl_js_args!(A, B, C, D);
+impl_js_args!(A, B, C, D, E);
+impl_js_args!(A, B, C, D, E, F);
+
+/// JavaScript object implementing an exposed trait, taken by the constructor of the
+/// `<Trait>Struct`.
+///
+/// Its methods are called directly from the JS thread. The other threads queue the call on the
+/// JS thread and wait for it to return, so the JS thread must not be waiting for them. An
+/// exception thrown by a method is cleared and returned as an error
+pub struct JsImpl {
+    env: sys::napi_env,
+    object: sys::napi_ref,
+    thread: ThreadId,
+    queue: ThreadsafeFunction<Callback, ErrorStrategy::Fatal>,
+}
+
+// The raw pointers are only used from the JS thread
+unsafe impl Send for JsImpl {}
+unsafe impl Sync for JsImpl {}
+
+struct SendPtr<T>(*mut T);
+unsafe impl<T> Send for SendPtr<T> {}
+
+impl JsImpl {
+    /// Call `method` on the object, converting its return value to `R`
+    pub fn call<A, R>(&self, method: &'static str, args: A) -> napi::Result<R>
+    where
+        A: JsArgs,
+        R: FromNapiValue + Send + 'static,
+    {
+        self.call_with(method, args, |value| R::from_unknown(value))
+    }
+
+    /// Call `method` on the object, ignoring its return value
+    pub fn call_void<A>(&self, method: &'static str, args: A) -> napi::Result<()>
+    where
+        A: JsArgs,
+    {
+        self.call_with(method, args, |_| Ok(()))
+    }
+
+    /// Throw `error` to JS, for the methods that can't return it. On the JS thread it's raised
+    /// when the current call returns to JS, otherwise as an uncaught exception
+    pub fn throw(&self, error: napi::Error) {
+        if thread::current().id() == self.thread {
+            unsafe { napi::JsError::from(error).throw_into(self.env) };
+            return;
+        }
+
+        self.queue.call(
+            Box::new(move |env| unsafe { napi::JsError::from(error).throw_into(env.raw()) }),
+            ThreadsafeFunctionCallMode::NonBlocking,
+        );
+    }
+
+    fn call_with<A, R>(
+        &self,
+        method: &'static str,
+        args: A,
+        convert: fn(JsUnknown) -> napi::Result<R>,
+    ) -> napi::Result<R>
+    where
+        A: JsArgs,
+        R: Send + 'static,
+    {
+        if thread::current().id() == self.thread {
+            let env = unsafe { Env::from_raw(self.env) };
+            return call_method(env, self.object, method, args).and_then(convert);
+        }
+
+        let (sender, receiver) = mpsc::channel();
+        let object = SendPtr(self.object);
+        let callback: Callback = Box::new(move |env| {
+            let object = object;
+            let result = call_method(env, object.0, method, args).and_then(convert);
+            let _ = sender.send(result);
+        });
+        self.queue
+            .call(callback, ThreadsafeFunctionCallMode::Blocking);
+
+        receiver
+            .recv()
+            .map_err(|_| napi::Error::from_reason("The JavaScript thread is gone"))?
+    }
+}
+
+fn call_method<A: JsArgs>(
+    env: Env,
+    object: sys::napi_ref,
+    method: &str,
+    args: A,
+) -> napi::Result<JsUnknown> {
+    let mut this = ptr::null_mut();
+    napi::check_status!(unsafe { sys::napi_get_reference_value(env.raw(), object, &mut this) })?;
+    let this = unsafe { JsObject::from_raw_unchecked(env.raw(), this) };
+
+    let function: JsFunction = this.get_named_property(method)?;
+    let args = unsafe { args.into_js_args(env.raw())? }
+        .into_iter()
+        .map(|value| unsafe { JsUnknown::from_raw_unchecked(env.raw(), value) })
+        .collect::<Vec<_>>();
+
+    function
+        .call(Some(&this), &args)
+        .map_err(|e| take_exception(env).unwrap_or(e))
+}
+
+/// Clear the pending exception, if any, and turn it into an error with the same message
+fn take_exception(env: Env) -> Option<napi::Error> {
+    let mut pending = false;
+    unsafe { sys::napi_is_exception_pending(env.raw(), &mut pending) };
+    if !pending {
+        return None;
+    }
+
+    let mut exception = ptr::null_mut();
+    unsafe { sys::napi_get_and_clear_last_exception(env.raw(), &mut exception) };
+    let message = unsafe { JsUnknown::from_raw_unchecked(env.raw(), exception) }
+        .coerce_to_string()
+        .and_then(|s| s.into_utf8())
+        .and_then(|s| s.into_owned())
+        .unwrap_or_else(|_| "JavaScript exception".to_string());
+
+    Some(napi::Error::from_reason(message))
+}
+
+impl FromNapiValue for JsImpl {
+    unsafe fn from_napi_value(env: sys::napi_env, value: sys::napi_value) -> napi::Result<Self> {
+        let mut object = ptr::null_mut();
+        napi::check_status!(sys::napi_create_reference(env, value, 1, &mut object))?;
+
+        // The calls from the other threads are queued as closures, which are run by the callback
+        // of a function that does nothing
+        let js_env = Env::from_raw(env);
+        let function =
+            js_env.create_function_from_closure("callback", |ctx| ctx.env.get_undefined())?;
+        let mut queue =
+            function.create_threadsafe_function(0, |ctx: ThreadSafeCallContext<Callback>| {
+                (ctx.value)(ctx.env);
+                Ok(Vec::<JsUnknown>::new())
+            })?;
+        // Don't keep the process alive
+        queue.unref(&js_env)?;
+
+        Ok(JsImpl {
+            env,
+            object,
+            thread: thread::current().id(),
+            queue,
+        })
+    }
+}
+
+impl TypeName for JsImpl {
+    fn type_name() -> &'static str {
+        "Object"
+    }
+
+    fn value_type() -> ValueType {
+        ValueType::Object
+    }
+}
+
+impl ValidateNapiValue for JsImpl {}
+
+impl Drop for JsImpl {
+    fn drop(&mut self) {
+        if thread::current().id() == self.thread {
+            unsafe { sys::napi_delete_reference(self.env, self.object) };
+            return;
+        }
+
+        let object = SendPtr(self.object);
+        self.queue.call(
+            Box::new(move |env| {
+                let object = object;
+                unsafe { sys::napi_delete_reference(env.raw(), object.0) };
+            }),
+            ThreadsafeFunctionCallMode::NonBlocking,
+        );
+    }
+}
@@ -0,0 +1,174 @@
+//! Heap allocated values handed out to the foreign side as raw pointers, shared by the
+//! languages that don't have their own object model
+
+use crate::common::*;
+
+/// Strategy used to move a value to the heap and hand out pointers to it
+pub trait PtrKind<T> {
+    /// How the value is stored when it's a field of another exposed struct
+    type Store;
+
+    fn into_raw(t: T) -> *mut T;
+    /// Give back the pointer and drop the value once nobody else owns it
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have been returned by `into_raw` or `get_stored` of the same kind and be owned
+    /// by the caller, which can't use it anymore
+    unsafe fn drop_raw(ptr: *mut T);
+
+    fn store(t: T) -> Self::Store;
+    /// Return a pointer to a stored value. The pointer is borrowed from the store for `Boxed`
+    /// types, and a new reference owned by the caller for `Shared` types, so that it outlives
+    /// its parent. The generated C getters document it, and the wrappers rendered over the C API
+    /// take the ownership of `shared` values only
+    fn get_stored(s: &mut Self::Store) -> *mut T;
+}
+
+/// Values with a single owner, freed when their pointer is destroyed
+#[derive(Debug)]
+pub struct Boxed;
+
+impl<T> PtrKind<T> for Boxed {
+    type Store = Box<T>;
+
+    #[inline]
+    fn into_raw(t: T) -> *mut T {
+        Box::into_raw(Box::new(t))
+    }
+
+    #[inline]
+    unsafe fn drop_raw(ptr: *mut T) {
+        let _inner = Box::from_raw(ptr);
+    }
+
+    fn store(t: T) -> Self::Store {
+        Box::new(t)
+    }
+
+    fn get_stored(s: &mut Self::Store) -> *mut T {
+        &mut **s as *mut T
+    }
+}
+
+/// Reference-counted values, freed when the last pointer is released. They are immutable: any
+/// holder may read them concurrently, so `&mut self` methods are rejected at compile time
+#[derive(Debug)]
+pub struct Shared;
+
+impl Shared {
+    /// Return a new owned pointer to the same value
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have been returned by `Shared::into_raw`, `get_stored` or `retain` and must not
+    /// have been released yet
+    pub unsafe fn retain<T>(ptr: *mut T) -> *mut T {
+        std::sync::Arc::increment_strong_count(ptr as *const T);
+        ptr
+    }
+
+    /// Give back an owned pointer, dropping the value if it was the last one
+    ///
+    /// # Safety
+    ///
+    /// Same as `retain`. The caller gives up its ownership of `ptr` and can't use it anymore
+    pub unsafe fn release<T>(ptr: *mut T) {
+        std::sync::Arc::decrement_strong_count(ptr as *const T);
+    }
+}
+
+impl<T> PtrKind<T> for Shared {
+    type Store = std::sync::Arc<T>;
+
+    #[inline]
+    fn into_raw(t: T) -> *mut T {
+        std::sync::Arc::into_raw(std::sync::Arc::new(t)) as *mut T
+    }
+
+    #[inline]
+    unsafe fn drop_raw(ptr: *mut T) {
+        Shared::release(ptr)
+    }
+
+    fn store(t: T) -> Self::Store {
+        std::sync::Arc::new(t)
+    }
+
+    fn get_stored(s: &mut Self::Store) -> *mut T {
+        std::sync::Arc::into_raw(std::sync::Arc::clone(s)) as *mut T
+    }
+}
+
+/// Types that can be handed out by pointer
+///
+/// Automatically implemented by `#[expose_struct]`
+pub trait ExposedPtr: Sized {
+    type Kind: PtrKind<Self>;
+}
+
+macro_rules! impl_boxed_exposed_ptr {
+    ($ty:ty) => {
+        impl ExposedPtr for $ty {
+            type Kind = Boxed;
+        }
+    };
+}
+impl_boxed_exposed_ptr!(bool);
+impl_boxed_exposed_ptr!(i8);
+impl_boxed_exposed_ptr!(u8);
+impl_boxed_exposed_ptr!(i16);
+impl_boxed_exposed_ptr!(u16);
+impl_boxed_exposed_ptr!(i32);
+impl_boxed_exposed_ptr!(u32);
+impl_boxed_exposed_ptr!(i64);
+impl_boxed_exposed_ptr!(u64);
+impl_boxed_exposed_ptr!(f32);
+impl_boxed_exposed_ptr!(f64);
+
+/// Types that can be mutated through their pointer, because nobody else holds it
+pub trait UniquePtr {}
+
+impl<T: ExposedPtr<Kind = Boxed>> UniquePtr for T {}
+
+/// Fails to compile if `T` is `shared`. Used to reject `&mut self` methods on those types
+#[inline]
+pub fn assert_unique_ptr<T: UniquePtr>() {}
+
+impl<T> AccessContainer for Box<T> {
+    type Content = T;
+
+    fn access_container<R, F: Fn(&Self::Content) -> R>(&self, f: F) -> R {
+        f(self)
+    }
+}
+
+impl<T> AccessContainerMut for Box<T> {
+    fn access_container_mut<R, F: Fn(&mut Self::Content) -> R>(&mut self, f: F) -> R {
+        f(self)
+    }
+}
+
+/// Read-only: `shared` structs are immutable, so there's no `AccessContainerMut` for `Arc`
+impl<T> AccessContainer for std::sync::Arc<T> {
+    type Content = T;
+
+    fn access_container<R, F: Fn(&Self::Content) -> R>(&self, f: F) -> R {
+        f(self)
+    }
+}
+
+impl<T: ExposedStruct + ExposedPtr> WrappedStructField for T {
+    type Store = <T::Kind as PtrKind<T>>::Store;
+
+    type Getter = *mut T;
+    type Setter = T;
+
+    fn wrap_get(s: &mut Self::Store) -> Self::Getter {
+        <T::Kind as PtrKind<T>>::get_stored(s)
+    }
+
+    fn wrap_set(s: Self::Setter) -> Self::Store {
+        <T::Kind as PtrKind<T>>::store(s)
+    }
+}
@@ -1,6 +1,8 @@
 use std::ops::{Deref, DerefMut};
+use std::os::raw::{c_int, c_void};
 
 use pyo3::prelude::*;
+use pyo3::PyNativeType;
 use pyo3::pyclass::PyClass;
 use pyo3::pyclass_init::PyClassInitializer;
 use pyo3::type_object::{PyBorrowFlagLayout, PyTypeInfo};
@@ -52,7 +54,9 @@ impl<T: PyClass> AccessContainer for pyo3::Py<T> {
     fn access_container<R, F: Fn(&Self::Content) -> R>(&self, f: F) -> R {
         Python::with_gil(|py| { f(self.as_ref(py).borrow().deref()) })
     }
+}
 
+impl<T: PyClass> AccessContainerMut for pyo3::Py<T> {
     fn access_container_mut<R, F: Fn(&mut Self::Content) -> R>(&mut self, f: F) -> R {
         Python::with_gil(|py| { f(self.as_ref(py).borrow_mut().deref_mut()) })
     }
@@ -70,6 +74,151 @@ macro_rules! impl_py_error {
     };
 }
 
+impl Into<pyo3::PyErr> for ValidationError {
+    fn into(self) -> pyo3::PyErr {
+        pyo3::exceptions::PyValueError::new_err(self.0)
+    }
+}
+
+impl Into<pyo3::PyErr> for IndexError {
+    fn into(self) -> pyo3::PyErr {
+        pyo3::exceptions::PyIndexError::new_err("index out of range")
+    }
+}
+
+/// Fill a read-only view over the bytes of a `ByteContainer`, without copying them. The view
+/// holds a strong reference to the object in `obj`, so the memory stays valid until the view is
+/// released. Only for `shared` structs, which are immutable: use `fill_byte_buffer_copy` for
+/// the others
+///
+/// # Safety
+///
+/// `view` must be null or point to a `Py_buffer` that can be written, as passed to `bf_getbuffer`
+pub unsafe fn fill_byte_buffer<T: PyClass + ByteContainer>(
+    slf: &PyRefMut<T>,
+    view: *mut pyo3::ffi::Py_buffer,
+    flags: c_int,
+) -> PyResult<()> {
+    let bytes = slf.as_bytes();
+    fill_buffer_view(slf, view, flags, bytes.as_ptr(), bytes.len())
+}
+
+/// Fill a read-only view over a copy of the bytes of a `ByteContainer`. The copy is owned by the
+/// view and freed by `release_byte_buffer_copy`, so it stays valid even if the object is
+/// modified while the view is alive
+///
+/// # Safety
+///
+/// `view` must be null or point to a `Py_buffer` that can be written, as passed to `bf_getbuffer`
+pub unsafe fn fill_byte_buffer_copy<T: PyClass + ByteContainer>(
+    slf: &PyRefMut<T>,
+    view: *mut pyo3::ffi::Py_buffer,
+    flags: c_int,
+) -> PyResult<()> {
+    let bytes: Box<[u8]> = slf.as_bytes().into();
+    let len = bytes.len();
+    let buf = Box::into_raw(bytes) as *const u8;
+
+    let result = fill_buffer_view(slf, view, flags, buf, len);
+    if result.is_err() {
+        drop(Box::from_raw(std::ptr::slice_from_raw_parts_mut(
+            buf as *mut u8,
+            len,
+        )));
+    }
+    result
+}
+
+unsafe fn fill_buffer_view<T: PyClass>(
+    slf: &PyRefMut<T>,
+    view: *mut pyo3::ffi::Py_buffer,
+    flags: c_int,
+    buf: *const u8,
+    len: usize,
+) -> PyResult<()> {
+    use pyo3::AsPyPointer;
+
+    if view.is_null() {
+        return Err(pyo3::exceptions::PyBufferError::new_err("View is null"));
+    }
+    if (flags & pyo3::ffi::PyBUF_WRITABLE) == pyo3::ffi::PyBUF_WRITABLE {
+        return Err(pyo3::exceptions::PyBufferError::new_err(
+            "Object is not writable",
+        ));
+    }
+
+    (*view).obj = slf.as_ptr();
+    pyo3::ffi::Py_INCREF((*view).obj);
+
+    (*view).buf = buf as *mut c_void;
+    (*view).len = len as isize;
+    (*view).readonly = 1;
+    (*view).itemsize = 1;
+
+    (*view).format = std::ptr::null_mut();
+    if (flags & pyo3::ffi::PyBUF_FORMAT) == pyo3::ffi::PyBUF_FORMAT {
+        (*view).format = b"B\0".as_ptr() as *mut _;
+    }
+
+    (*view).ndim = 1;
+    (*view).shape = std::ptr::null_mut();
+    if (flags & pyo3::ffi::PyBUF_ND) == pyo3::ffi::PyBUF_ND {
+        (*view).shape = &(*view).len as *const _ as *mut _;
+    }
+    (*view).strides = std::ptr::null_mut();
+    if (flags & pyo3::ffi::PyBUF_STRIDES) == pyo3::ffi::PyBUF_STRIDES {
+        (*view).strides = &(*view).itemsize as *const _ as *mut _;
+    }
+
+    (*view).suboffsets = std::ptr::null_mut();
+    (*view).internal = std::ptr::null_mut();
+
+    Ok(())
+}
+
+/// Free the copy of the bytes made by `fill_byte_buffer_copy`
+///
+/// # Safety
+///
+/// `view` must have been filled by `fill_byte_buffer_copy` and not released yet, as passed to
+/// `bf_releasebuffer`
+pub unsafe fn release_byte_buffer_copy(view: *mut pyo3::ffi::Py_buffer) {
+    if view.is_null() || (*view).buf.is_null() {
+        return;
+    }
+
+    let len = (*view).len as usize;
+    drop(Box::from_raw(std::ptr::slice_from_raw_parts_mut(
+        (*view).buf as *mut u8,
+        len,
+    )));
+    (*view).buf = std::ptr::null_mut();
+}
+
+/// Copy the bytes of a `ByteContainer` into a Python `bytes` object
+pub fn to_py_bytes<T: ByteContainer>(s: &T) -> PyObject {
+    Python::with_gil(|py| pyo3::types::PyBytes::new(py, s.as_bytes()).into())
+}
+
+/// Build a `ByteContainer` from `bytes` or any other object that supports the buffer protocol
+pub fn from_py_bytes<T: ByteContainer>(obj: &PyAny) -> PyResult<T> {
+    if let Ok(bytes) = obj.downcast::<pyo3::types::PyBytes>() {
+        return Ok(T::from_bytes(bytes.as_bytes().to_vec()));
+    }
+
+    let buffer = pyo3::buffer::PyBuffer::<u8>::get(obj)?;
+    Ok(T::from_bytes(buffer.to_vec(obj.py())?))
+}
+
+/// Classes that can be mutated from Python. Not implemented by `shared` structs, whose buffer
+/// views point straight at their bytes
+pub trait MutablePyClass {}
+
+/// Fails to compile if `T` is `shared`. Used to reject setters and `&mut self` methods on those
+/// types
+#[inline]
+pub fn assert_mutable<T: MutablePyClass>() {}
+
 pub trait IntoTraitStruct: Sized {
     type Target;
 
@@ -94,3 +243,85 @@ where
         MapFrom::map_from(s)
     }
 }
+
+/// Forwards the records to the `logging` module, under a logger named after the Rust target with
+/// `::` replaced by `.`
+struct PyLogBridge;
+
+impl log::Log for PyLogBridge {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &log::Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        Python::with_gil(|py| {
+            let level = match record.level() {
+                log::Level::Error => 40,
+                log::Level::Warn => 30,
+                log::Level::Info => 20,
+                log::Level::Debug => 10,
+                // There's no `TRACE` in `logging`
+                log::Level::Trace => 5,
+            };
+            let result = py
+                .import("logging")
+                .and_then(|logging| {
+                    logging
+                        .getattr("getLogger")?
+                        .call1((record.target().replace("::", "."),))
+                })
+                .and_then(|logger| {
+                    logger.call_method1("log", (level, record.args().to_string()))
+                });
+            // Raising isn't possible from here
+            if let Err(e) = result {
+                e.print(py);
+            }
+        });
+    }
+
+    fn flush(&self) {}
+}
+
+/// `logging` level -> the most verbose level passed to Python, `CRITICAL` and above disable it
+fn level_filter(level: i32) -> log::LevelFilter {
+    match level {
+        l if l > 40 => log::LevelFilter::Off,
+        l if l > 30 => log::LevelFilter::Error,
+        l if l > 20 => log::LevelFilter::Warn,
+        l if l > 10 => log::LevelFilter::Info,
+        l if l > 5 => log::LevelFilter::Debug,
+        _ => log::LevelFilter::Trace,
+    }
+}
+
+/// Forward the records logged by the library to the `logging` module, discarding those below
+/// `level` (`logging.WARNING` by default) before they reach Python
+#[pyfunction]
+#[text_signature = "(level=logging.WARNING)"]
+fn install_logger(level: Option<i32>) {
+    static BRIDGE: PyLogBridge = PyLogBridge;
+    // Fails if it's already installed, which is fine
+    let _ = log::set_logger(&BRIDGE);
+
+    log::set_max_level(level_filter(level.unwrap_or(30)));
+}
+
+/// Change the level of the records forwarded by `install_logger()`
+#[pyfunction]
+#[text_signature = "(level)"]
+fn set_log_level(level: i32) {
+    log::set_max_level(level_filter(level));
+}
+
+/// Add `install_logger()` and `set_log_level()` to the root module
+pub fn add_logging(m: &pyo3::types::PyModule) -> PyResult<()> {
+    m.add_function(pyo3::wrap_pyfunction!(install_logger, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(set_log_level, m)?)?;
+
+    Ok(())
+}
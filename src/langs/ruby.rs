@@ -0,0 +1,255 @@
+use std::cell::{Ref, RefCell, RefMut};
+use std::mem::ManuallyDrop;
+use std::ops::{Deref, DerefMut};
+use std::sync::OnceLock;
+
+use magnus::value::{BoxValue, Opaque, ReprValue};
+use magnus::{
+    ArgList, DataTypeFunctions, Error, ExceptionClass, Module, RModule, Ruby, TryConvert, Value,
+};
+
+use crate::common::*;
+
+/// Exception raised to Ruby, from the class named after the Rust error type
+pub type RubyError = magnus::Error;
+
+/// Root module of the extension, where the exception classes are defined
+static ERROR_MODULE: OnceLock<Opaque<RModule>> = OnceLock::new();
+
+/// Define `Error`, derived from `StandardError`, and a subclass of it for each error type in
+/// `names`. Called when the extension is loaded
+pub fn define_errors(ruby: &Ruby, module: RModule, names: &[&str]) -> Result<(), Error> {
+    let base = module.define_error("Error", ruby.exception_standard_error())?;
+    for name in names.iter().filter(|name| **name != "Error") {
+        module.define_error(*name, base)?;
+    }
+
+    let _ = ERROR_MODULE.set(module.into());
+    Ok(())
+}
+
+/// Exception of the class `name`, or the base `Error` class when it was not defined
+pub fn ruby_error(name: &str, message: String) -> RubyError {
+    let ruby = Ruby::get().expect("Ruby exception created outside of a Ruby thread");
+    let module = ruby.get_inner(*ERROR_MODULE.get().expect("Extension not initialized"));
+    let class = module
+        .const_get::<_, ExceptionClass>(name)
+        .or_else(|_| module.const_get::<_, ExceptionClass>("Error"))
+        .unwrap_or_else(|_| ruby.exception_standard_error());
+
+    Error::new(class, message)
+}
+
+#[macro_export]
+macro_rules! impl_ruby_error {
+    ($type:ident) => {
+        impl From<$type> for ::magnus::Error {
+            fn from(e: $type) -> Self {
+                $crate::langs::ruby_error(stringify!($type), format!("{:?}", e))
+            }
+        }
+    };
+}
+
+impl From<ValidationError> for RubyError {
+    fn from(e: ValidationError) -> Self {
+        let ruby = Ruby::get().expect("Ruby exception created outside of a Ruby thread");
+        Error::new(ruby.exception_arg_error(), e.0)
+    }
+}
+
+impl From<IndexError> for RubyError {
+    fn from(_: IndexError) -> Self {
+        let ruby = Ruby::get().expect("Ruby exception created outside of a Ruby thread");
+        Error::new(ruby.exception_index_error(), "index out of range")
+    }
+}
+
+pub trait IntoTraitStruct: Sized {
+    type Target;
+
+    fn into_trait_struct(self) -> Self::Target;
+}
+
+/// Data of the Ruby object wrapping an exposed struct.
+///
+/// Ruby only gives out shared references to it, so the value is borrowed dynamically by the
+/// methods that take `&mut self`
+pub struct RbCell<T>(RefCell<T>);
+
+impl<T> RbCell<T> {
+    pub fn new(value: T) -> Self {
+        RbCell(RefCell::new(value))
+    }
+}
+
+impl<T: Send> DataTypeFunctions for RbCell<T> {}
+
+fn already_borrowed() -> Error {
+    let ruby = Ruby::get().expect("Ruby exception created outside of a Ruby thread");
+    Error::new(ruby.exception_runtime_error(), "object already borrowed")
+}
+
+/// Exposed struct borrowed from its Ruby object for the duration of a call.
+///
+/// The object is an argument of the call, so it is kept alive by Ruby for longer than the guard
+pub struct RbRef<T: 'static>(Ref<'static, T>);
+
+impl<T: 'static> TryConvert for RbRef<T>
+where
+    RbCell<T>: magnus::TypedData,
+{
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        let cell = <&'static RbCell<T>>::try_convert(val)?;
+        cell.0
+            .try_borrow()
+            .map(RbRef)
+            .map_err(|_| already_borrowed())
+    }
+}
+
+impl<T> Deref for RbRef<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// Same as `RbRef` but mutably
+pub struct RbRefMut<T: 'static>(RefMut<'static, T>);
+
+impl<T: 'static> TryConvert for RbRefMut<T>
+where
+    RbCell<T>: magnus::TypedData,
+{
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        let cell = <&'static RbCell<T>>::try_convert(val)?;
+        cell.0
+            .try_borrow_mut()
+            .map(RbRefMut)
+            .map_err(|_| already_borrowed())
+    }
+}
+
+impl<T> Deref for RbRefMut<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for RbRefMut<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+/// Bind an exposed struct to its Ruby class, `$class` being its full name. The class is defined
+/// when the extension is loaded
+#[macro_export]
+macro_rules! impl_ruby_class {
+    ($ty:ty, $class:literal) => {
+        unsafe impl ::magnus::TypedData for $crate::langs::RbCell<$ty> {
+            fn class(ruby: &::magnus::Ruby) -> ::magnus::RClass {
+                static CLASS: ::magnus::value::Lazy<::magnus::RClass> =
+                    ::magnus::value::Lazy::new(|ruby| {
+                        use ::magnus::value::ReprValue;
+                        use ::magnus::Class;
+
+                        let class: ::magnus::RClass = ruby
+                            .class_object()
+                            .funcall("const_get", ($class,))
+                            .expect(concat!("Class ", $class, " not defined"));
+                        class.undef_default_alloc_func();
+                        class
+                    });
+                ruby.get_inner(&CLASS)
+            }
+
+            fn data_type() -> &'static ::magnus::DataType {
+                static DATA_TYPE: ::magnus::DataType =
+                    ::magnus::data_type_builder!($crate::langs::RbCell<$ty>, $class)
+                        .free_immediately()
+                        .size()
+                        .build();
+                &DATA_TYPE
+            }
+        }
+
+        impl ::magnus::IntoValue for $ty {
+            fn into_value_with(self, ruby: &::magnus::Ruby) -> ::magnus::Value {
+                ::magnus::IntoValue::into_value_with($crate::langs::RbCell::new(self), ruby)
+            }
+        }
+    };
+}
+
+/// Ruby object implementing an exposed trait, taken by the constructor of the `<Trait>Struct`.
+///
+/// Its methods can only be called from a Ruby thread
+pub struct RbImpl(ManuallyDrop<BoxValue<Value>>);
+
+// The value is only accessed from Ruby threads, which hold the GVL
+unsafe impl Send for RbImpl {}
+unsafe impl Sync for RbImpl {}
+
+impl RbImpl {
+    /// Call `method` on the object, converting its return value to `R`
+    pub fn call<A: ArgList, R: TryConvert>(&self, method: &str, args: A) -> Result<R, Error> {
+        Ruby::get().expect("Ruby object called outside of a Ruby thread");
+        self.0.funcall(method, args)
+    }
+
+    /// Call `method` on the object, ignoring its return value
+    pub fn call_void<A: ArgList>(&self, method: &str, args: A) -> Result<(), Error> {
+        self.call::<A, Value>(method, args).map(|_| ())
+    }
+}
+
+impl TryConvert for RbImpl {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        Ok(RbImpl(ManuallyDrop::new(BoxValue::new(val))))
+    }
+}
+
+impl Drop for RbImpl {
+    fn drop(&mut self) {
+        // Unregistering the object from the garbage collector needs the GVL, so it is leaked
+        // when dropped from another thread
+        if Ruby::get().is_ok() {
+            unsafe { ManuallyDrop::drop(&mut self.0) };
+        }
+    }
+}
+
+// Values are copied in and out of Ruby, so the fields are stored inline
+impl<T: ExposedStruct + Clone> WrappedStructField for T {
+    type Store = T;
+
+    type Getter = T;
+    type Setter = T;
+
+    fn wrap_get(s: &mut Self::Store) -> Self::Getter {
+        s.clone()
+    }
+
+    fn wrap_set(s: Self::Setter) -> Self::Store {
+        s
+    }
+}
+
+impl<T: ExposedStruct> AccessContainer for T {
+    type Content = T;
+
+    fn access_container<R, F: Fn(&Self::Content) -> R>(&self, f: F) -> R {
+        f(self)
+    }
+}
+
+impl<T: ExposedStruct> AccessContainerMut for T {
+    fn access_container_mut<R, F: Fn(&mut Self::Content) -> R>(&mut self, f: F) -> R {
+        f(self)
+    }
+}
@@ -0,0 +1,115 @@
+use wasm_bindgen::prelude::*;
+
+use crate::common::*;
+
+#[wasm_bindgen(inline_js = r#"
+const classes = new Map();
+
+export function new_error(name, message) {
+    let cls = classes.get(name);
+    if (cls === undefined) {
+        cls = class extends Error {};
+        Object.defineProperty(cls, "name", { value: name });
+        Object.defineProperty(cls.prototype, "name", { value: name, writable: true, configurable: true });
+        classes.set(name, cls);
+    }
+
+    return new cls(message);
+}
+"#)]
+extern "C" {
+    fn new_error(name: &str, message: &str) -> JsValue;
+}
+
+/// Build an instance of the `Error` subclass called `name`, which is created the first time it's
+/// used. The class is the same for all the errors with the same name
+pub fn js_error(name: &str, message: &str) -> JsValue {
+    new_error(name, message)
+}
+
+#[macro_export]
+macro_rules! impl_wasm_error {
+    ($type:ident) => {
+        impl From<$type> for wasm_bindgen::JsValue {
+            fn from(e: $type) -> Self {
+                $crate::langs::js_error(stringify!($type), &format!("{:?}", e))
+            }
+        }
+    };
+}
+
+impl From<ValidationError> for JsValue {
+    fn from(e: ValidationError) -> Self {
+        js_error("ValidationError", &e.0)
+    }
+}
+
+impl From<IndexError> for JsValue {
+    fn from(_: IndexError) -> Self {
+        js_sys::RangeError::new("index out of range").into()
+    }
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use wasm_bindgen::JsCast;
+    use wasm_bindgen_test::*;
+
+    use super::*;
+    use crate::bitcoin_mod::bitcoin::*;
+
+    const ADDRESS: &str = "32qQDeCM67ZyLT1EYvv9HHTG9bXHLVK1gf";
+
+    #[wasm_bindgen_test]
+    fn parse_address() {
+        let mut address = Address::from_string(ADDRESS.to_string()).unwrap();
+        assert_eq!(address.__to_string(), ADDRESS);
+        assert_eq!(
+            address.get_script().to_hex(),
+            "a9140c8e1071fea96ca41f2b6735ef63a882187d39e087"
+        );
+        assert!(address.get_network().is_bitcoin());
+    }
+
+    #[wasm_bindgen_test]
+    fn decode_script() {
+        let script = Script::__from_bytes(&[0x51, 0x52]);
+        assert_eq!(script.asm(), "OP_PUSHNUM_1 OP_PUSHNUM_2");
+        assert_eq!(script.__to_bytes(), vec![0x51, 0x52]);
+        assert_eq!(script.index(1).unwrap(), 0x52);
+
+        let joined = script.add(&Script::from_hex("53".to_string()).unwrap());
+        assert_eq!(joined.to_hex(), "515253");
+    }
+
+    #[wasm_bindgen_test]
+    fn errors_are_error_subclasses() {
+        let error = match Network::from_string("not a network".to_string()) {
+            Ok(_) => panic!("Invalid network parsed"),
+            Err(e) => JsValue::from(e),
+        };
+        let error = error.dyn_into::<js_sys::Error>().unwrap();
+        assert_eq!(error.name(), "BitcoinError");
+
+        // The same class is reused
+        let other = JsValue::from(crate::bitcoin_mod::BitcoinError::IO(std::io::ErrorKind::Other.into()));
+        assert!(other.is_instance_of::<js_sys::Error>());
+        assert_eq!(
+            js_sys::Object::get_prototype_of(&error),
+            js_sys::Object::get_prototype_of(&other)
+        );
+
+        let index = JsValue::from(IndexError);
+        assert!(index.is_instance_of::<js_sys::RangeError>());
+    }
+
+    #[wasm_bindgen_test]
+    fn validated_setter() {
+        let mut address = Address::from_string(ADDRESS.to_string()).unwrap();
+        let error = match address.set_script(&Script::empty()) {
+            Ok(_) => panic!("Invalid script accepted"),
+            Err(e) => JsValue::from(e),
+        };
+        assert_eq!(error.dyn_into::<js_sys::Error>().unwrap().name(), "ValidationError");
+    }
+}
@@ -4,12 +4,20 @@ pub mod mapping;
 #[macro_use]
 pub mod langs;
 
-#[cfg(not(any(feature = "c", feature = "python")))]
+#[cfg(not(any(feature = "c", feature = "python", feature = "jni", feature = "wasm", feature = "node", feature = "ruby")))]
 compile_error!("No language enabled");
 
-#[cfg(all(feature = "c", any(feature = "python")))]
+#[cfg(all(feature = "c", any(feature = "python", feature = "jni", feature = "wasm", feature = "node", feature = "ruby")))]
 compile_error!("Enable at most one language");
-#[cfg(all(feature = "python", any(feature = "c")))]
+#[cfg(all(feature = "python", any(feature = "c", feature = "jni", feature = "wasm", feature = "node", feature = "ruby")))]
+compile_error!("Enable at most one language");
+#[cfg(all(feature = "jni", any(feature = "c", feature = "python", feature = "wasm", feature = "node", feature = "ruby")))]
+compile_error!("Enable at most one language");
+#[cfg(all(feature = "wasm", any(feature = "c", feature = "python", feature = "jni", feature = "node", feature = "ruby")))]
+compile_error!("Enable at most one language");
+#[cfg(all(feature = "node", any(feature = "c", feature = "python", feature = "jni", feature = "wasm", feature = "ruby")))]
+compile_error!("Enable at most one language");
+#[cfg(all(feature = "ruby", any(feature = "c", feature = "python", feature = "jni", feature = "wasm", feature = "node")))]
 compile_error!("Enable at most one language");
 
 #[macro_use]
@@ -33,84 +41,91 @@ pub mod bitcoin_mod;
 //     }
 // }
 
-// #[expose_mod]
-// mod test_mod {
-//     #[expose_struct]
-//     struct Inner {
-//         val: u32,
-//     }
-//     impl Clone for Inner {
-//         fn clone(&self) -> Self {
-//             Inner { val: self.val }
-//         }
-//     }
-// 
-//     #[expose_impl]
-//     impl Inner {
-//         #[constructor]
-//         fn new(val: u32) -> Self {
-//             Inner { val }
-//         }
-// 
-//         #[cfg(feature = "python")]
-//         #[getter]
-//         fn get_val(&self) -> u32 {
-//             self.val
-//         }
-//         #[cfg(feature = "python")]
-//         #[setter]
-//         fn set_val(&mut self, val: u32) {
-//             self.val = val;
-//         }
-//     }
-// 
-//     #[expose_struct("opaque")]
-//     struct Outer {
-//         #[expose_struct(get, set)]
-//         inner: Inner,
-//         #[expose_struct(get, set)]
-//         value: u32,
-//     }
-// 
-//     #[expose_impl]
-//     impl Outer {
-//         #[constructor]
-//         fn new(inner: &Inner, value: u32) -> Self {
-//             Outer { inner, value }
-//         }
-//         #[destructor]
-//         fn destroy(_s: Self) {}
-//     }
-//     //     #[expose_struct("opaque")]
-//     //     struct ImplMyTrait {
-//     //         inner: super::ImplMyTrait,
-//     //     }
-//     //     impl ImplMyTrait {
-//     //         fn into_inner(self) -> super::ImplMyTrait {
-//     //             self.inner
-//     //         }
-//     //     }
-//     //     #[expose_fn]
-//     //     fn impl_my_trait_new(val: u32) -> MyTraitStruct {
-//     //         super::ImplMyTrait(val).into_trait_struct()
-//     //     }
-//     //
-//     //     #[expose_trait]
-//     //     pub trait MyTrait: super::MyTrait {
-//     //         #[expose_trait(original = "method")]
-//     //         fn _wrapper_method(&self, s: String) -> String;
-//     //     }
-//     //     impl super::MyTrait for MyTraitStruct {
-//     //         fn method(&self, s: String) -> String {
-//     //             self._wrapper_method(s)
-//     //         }
-//     //     }
-//     //
-//     //     #[expose_fn]
-//     //     fn use_trait(t: &MyTraitStruct) {
-//     //         use super::MyTrait;
-//     //
-//     //         let ret = t.method("Hello from Rust".to_string());
-//     //         println!("Returned: {}", ret);
-//     //     }
-// }
+/// Transparent struct stored in an opaque one, for the C example. Not rendered by the languages
+/// built on the C API
+#[cfg(all(
+    feature = "c",
+    not(any(
+        feature = "cpp",
+        feature = "swift",
+        feature = "go",
+        feature = "csharp",
+        feature = "dart"
+    ))
+))]
+#[expose_mod]
+mod test_mod {
+    #[expose_struct]
+    struct Inner {
+        val: u32,
+    }
+    impl Clone for Inner {
+        fn clone(&self) -> Self {
+            Inner { val: self.val }
+        }
+    }
+
+    #[expose_impl]
+    impl Inner {
+        #[constructor]
+        fn new(val: u32) -> Self {
+            Inner { val }
+        }
+        #[destructor]
+        fn destroy(_s: Self) {}
+    }
+
+    #[expose_struct("opaque")]
+    struct Outer {
+        #[expose_struct(get, set)]
+        inner: Inner,
+        #[expose_struct(get, set)]
+        value: u32,
+    }
+
+    #[expose_impl]
+    impl Outer {
+        #[constructor]
+        fn new(inner: &Inner, value: u32) -> Self {
+            Outer {
+                inner: crate::mapping::MapFrom::map_from(inner),
+                value,
+            }
+        }
+        #[destructor]
+        fn destroy(_s: Self) {}
+    }
+
+    // #[expose_struct("opaque")]
+    // struct ImplMyTrait {
+    //     inner: super::ImplMyTrait,
+    // }
+    // impl ImplMyTrait {
+    //     fn into_inner(self) -> super::ImplMyTrait {
+    //         self.inner
+    //     }
+    // }
+    // #[expose_fn]
+    // fn impl_my_trait_new(val: u32) -> MyTraitStruct {
+    //     super::ImplMyTrait(val).into_trait_struct()
+    // }
+    //
+    // #[expose_trait]
+    // pub trait MyTrait: super::MyTrait {
+    //     #[expose_trait(original = "method")]
+    //     fn _wrapper_method(&self, s: String) -> String;
+    // }
+    // impl super::MyTrait for MyTraitStruct {
+    //     fn method(&self, s: String) -> String {
+    //         self._wrapper_method(s)
+    //     }
+    // }
+    //
+    // #[expose_fn]
+    // fn use_trait(t: &MyTraitStruct) {
+    //     use super::MyTrait;
+    //
+    //     let ret = t.method("Hello from Rust".to_string());
+    //     println!("Returned: {}", ret);
+    // }
+}
@@ -20,6 +20,42 @@ impl<T> MapTo<T> for T {
     }
 }
 
+// Storage of the exposed structs handed out as pointers, shared by C and JNI
+#[cfg(any(feature = "c", feature = "jni"))]
+mod ptr_mapping {
+    use super::MapFrom;
+
+    impl<T: Clone> MapFrom<&T> for T {
+        fn map_from(t: &T) -> Self {
+            t.clone()
+        }
+    }
+
+    impl<T> MapFrom<T> for Box<T> {
+        fn map_from(t: T) -> Self {
+            Box::new(t)
+        }
+    }
+
+    impl<T: Clone> MapFrom<&T> for Box<T> {
+        fn map_from(t: &T) -> Self {
+            Box::new(t.clone())
+        }
+    }
+
+    impl<T> MapFrom<T> for std::sync::Arc<T> {
+        fn map_from(t: T) -> Self {
+            std::sync::Arc::new(t)
+        }
+    }
+
+    impl<T: Clone> MapFrom<&T> for std::sync::Arc<T> {
+        fn map_from(t: &T) -> Self {
+            std::sync::Arc::new(t.clone())
+        }
+    }
+}
+
 #[cfg(feature = "c")]
 mod c_mapping {
     use super::{MapFrom, MapTo};
@@ -50,9 +86,12 @@ mod c_mapping {
         }
     }
 
-    impl<T: Clone> MapFrom<&T> for T {
-        fn map_from(t: &T) -> Self {
-            t.clone()
+    impl<T> MapFrom<Arr<T>> for &[T] {
+        fn map_from(arr: Arr<T>) -> Self {
+            match arr.ptr.is_null() {
+                true => &[],
+                false => unsafe { std::slice::from_raw_parts(arr.ptr, arr.len) },
+            }
         }
     }
 
@@ -71,18 +110,6 @@ mod c_mapping {
         }
     }
 
-    impl<T> MapFrom<T> for Box<T> {
-        fn map_from(t: T) -> Self {
-            Box::new(t)
-        }
-    }
-
-    impl<T: Clone> MapFrom<&T> for Box<T> {
-        fn map_from(t: &T) -> Self {
-            Box::new(t.clone())
-        }
-    }
-
     impl MapTo<*mut libc::c_char> for String {
         fn map_to(self) -> *mut libc::c_char {
             let cstring = std::ffi::CString::new(self).expect("Invalid outgoing string");
@@ -105,14 +132,26 @@ mod c_mapping {
         }
     }
 
-    impl<T> MapTo<*mut T> for T {
+    impl<F, T: MapTo<F>> MapTo<Arr<F>> for Vec<T> {
+        fn map_to(self) -> Arr<F> {
+            let mapped: Box<[F]> = self.into_iter().map(T::map_to).collect();
+            let len = mapped.len();
+
+            Arr {
+                ptr: Box::into_raw(mapped) as *const F,
+                len,
+            }
+        }
+    }
+
+    impl<T: ExposedPtr> MapTo<*mut T> for T {
         #[inline]
         fn map_to(self) -> *mut T {
-            Box::into_raw(Box::new(self))
+            <T::Kind as PtrKind<T>>::into_raw(self)
         }
     }
 
-    impl<T> MapTo<*mut T> for Option<T> {
+    impl<T: ExposedPtr> MapTo<*mut T> for Option<T> {
         #[inline]
         fn map_to(self) -> *mut T {
             self.map(MapTo::map_to)
@@ -166,3 +205,86 @@ mod python_mapping {
 }
 #[cfg(feature = "python")]
 pub use python_mapping::*;
+
+// Exposed structs are copied in and out of JS, like their fields. Shared by wasm and Node
+#[cfg(any(feature = "wasm", feature = "node"))]
+mod js_mapping {
+    use super::MapFrom;
+
+    impl<T: Clone> MapFrom<&T> for T {
+        fn map_from(t: &T) -> Self {
+            t.clone()
+        }
+    }
+}
+
+#[cfg(feature = "node")]
+mod node_mapping {
+    use super::{MapFrom, MapTo};
+
+    use napi::bindgen_prelude::Buffer;
+
+    use crate::langs::{JsU64, JsUsize};
+
+    // Indexes and lengths are plain numbers in JS
+    impl MapFrom<u32> for usize {
+        fn map_from(n: u32) -> Self {
+            n as usize
+        }
+    }
+
+    // The range is checked when the value is passed to JS
+    impl MapTo<JsUsize> for usize {
+        fn map_to(self) -> JsUsize {
+            JsUsize(self)
+        }
+    }
+
+    // Numbers can't hold all the `u64`s, the `BigInt` is checked when it's taken from JS
+    impl MapFrom<JsU64> for u64 {
+        fn map_from(n: JsU64) -> Self {
+            n.0
+        }
+    }
+
+    impl MapFrom<Buffer> for Vec<u8> {
+        fn map_from(buffer: Buffer) -> Self {
+            buffer.into()
+        }
+    }
+
+    impl MapTo<Buffer> for Vec<u8> {
+        fn map_to(self) -> Buffer {
+            self.into()
+        }
+    }
+}
+
+#[cfg(feature = "ruby")]
+mod ruby_mapping {
+    use super::{MapFrom, MapTo};
+
+    use magnus::{RString, Ruby};
+
+    // Exposed structs are copied out of their Ruby object
+    impl<T: Clone> MapFrom<&T> for T {
+        fn map_from(t: &T) -> Self {
+            t.clone()
+        }
+    }
+
+    // Binary data is held in `String`s
+    impl MapFrom<RString> for Vec<u8> {
+        fn map_from(s: RString) -> Self {
+            // The slice is copied before Ruby can run again
+            unsafe { s.as_slice() }.to_vec()
+        }
+    }
+
+    impl MapTo<RString> for Vec<u8> {
+        fn map_to(self) -> RString {
+            let ruby = Ruby::get().expect("Ruby string created outside of a Ruby thread");
+            ruby.str_from_slice(&self)
+        }
+    }
+}
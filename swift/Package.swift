@@ -0,0 +1,16 @@
+// swift-tools-version:5.5
+import PackageDescription
+
+let package = Package(
+    name: "BdkFfi",
+    products: [
+        .library(name: "BdkFfi", targets: ["BdkFfi"]),
+    ],
+    targets: [
+        // The C header, copied here by `make`, and the library built by cargo
+        .systemLibrary(name: "CBdkFfi", path: "Sources/CBdkFfi"),
+        // The Swift sources generated by `#[expose_mod]`
+        .target(name: "BdkFfi", dependencies: ["CBdkFfi"]),
+        .testTarget(name: "BdkFfiTests", dependencies: ["BdkFfi"]),
+    ]
+)
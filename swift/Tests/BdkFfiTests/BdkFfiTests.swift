@@ -0,0 +1,47 @@
+import Foundation
+import XCTest
+
+import BdkFfi
+
+final class BdkFfiTests: XCTestCase {
+    func testAddress() throws {
+        let address = try Address("32qQDeCM67ZyLT1EYvv9HHTG9bXHLVK1gf")
+        XCTAssertEqual(address.description, "32qQDeCM67ZyLT1EYvv9HHTG9bXHLVK1gf")
+    }
+
+    func testSharedTxidOutlivesOutPoint() throws {
+        var outPoint: OutPoint? = try OutPoint.fromString("e567952fb6cc33857f392efa3a46c995a28f69cca4bb1b37e0204dab1ec7a389:1")
+        // `Txid` is shared: the getter takes a new reference
+        let txid = outPoint!.txid
+        outPoint = nil
+        XCTAssertEqual(txid.description, "e567952fb6cc33857f392efa3a46c995a28f69cca4bb1b37e0204dab1ec7a389")
+    }
+
+    func testScriptFromData() throws {
+        let script = Script(Data([0x51]))
+        XCTAssertEqual(script.toHex(), "51")
+        XCTAssertEqual(try Script.fromHex("51").toHex(), script.toHex())
+    }
+
+    func testOperators() throws {
+        let joined = try Script.fromHex("51") + Script.fromHex("52")
+        XCTAssertEqual(joined.toHex(), "5152")
+        XCTAssertEqual(try joined[1], 0x52)
+        XCTAssertThrowsError(try joined[1000]) { error in
+            XCTAssertEqual(error as? IndexError, .failed(code: -1))
+        }
+    }
+
+    func testErrors() {
+        XCTAssertThrowsError(try Network("not a network")) { error in
+            XCTAssertTrue(error is BitcoinError)
+        }
+    }
+
+    func testFromScript() throws {
+        let script = try Script.fromHex("a9140c8e1071fea96ca41f2b6735ef63a882187d39e087")
+        let testnet = Address.fromScript(script, network: Network.testnet())
+        XCTAssertNotNil(testnet)
+        XCTAssertTrue(Network.testnet().isTestnet())
+    }
+}
@@ -0,0 +1,42 @@
+//! Report the messages of the errors returned to the languages that only receive an error code
+
+#[test]
+#[cfg(feature = "c")]
+fn c_last_error_message() {
+    use std::ffi::{c_void, CStr};
+
+    use bdk_ffi::langs::c::*;
+
+    extern "C" {
+        #[link_name = concat!(env!("EXPOSE_C_PREFIX"), "network_new")]
+        fn network_new(s: *const libc::c_char, out: *mut *mut c_void) -> i32;
+        #[link_name = concat!(env!("EXPOSE_C_PREFIX"), "network_destroy")]
+        fn network_destroy(network: *mut c_void);
+    }
+
+    let last_error = || {
+        let message = last_error_message();
+        assert!(!message.is_null());
+        let owned = unsafe { CStr::from_ptr(message) }.to_string_lossy().into_owned();
+        unsafe { string_free(message) };
+        owned
+    };
+
+    assert!(last_error_message().is_null());
+
+    let mut network = std::ptr::null_mut();
+    let code = unsafe { network_new(b"not a network\0".as_ptr() as *const libc::c_char, &mut network) };
+    assert_eq!(code, -1);
+    assert!(last_error().contains("Unknown network"));
+
+    // Not reset by successful calls
+    let code = unsafe { network_new(b"testnet\0".as_ptr() as *const libc::c_char, &mut network) };
+    assert_eq!(code, 0);
+    unsafe { network_destroy(network) };
+    assert!(last_error().contains("Unknown network"));
+
+    // Kept per thread
+    std::thread::spawn(|| assert!(last_error_message().is_null()))
+        .join()
+        .unwrap();
+}
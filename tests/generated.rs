@@ -0,0 +1,196 @@
+//! Compile the examples and the sources generated in `OUT_DIR`. The checks need the toolchain of
+//! their language, so they are ignored by default: run them with `make check` in the directory of
+//! the language, or `cargo test --features <lang> -- --ignored`, which fail if the toolchain is
+//! missing. The `toolchains` CI workflow runs all of them
+#![cfg(any(
+    feature = "cpp",
+    feature = "swift",
+    feature = "go",
+    feature = "csharp",
+    feature = "dart",
+    feature = "jni"
+))]
+
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Where the build script writes the C header, and the `expose_mod` macro the other sources
+fn out_dir() -> PathBuf {
+    PathBuf::from(env!("OUT_DIR"))
+}
+
+/// Files with `extension` in `dir` and its subdirectories
+#[cfg(feature = "jni")]
+fn find_sources(dir: &std::path::Path, extension: &str) -> Vec<PathBuf> {
+    let mut sources = vec![];
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            sources.extend(find_sources(&path, extension));
+        } else if path.extension() == Some(extension.as_ref()) {
+            sources.push(path);
+        }
+    }
+
+    sources
+}
+
+#[cfg(any(feature = "cpp", feature = "dart"))]
+fn crate_dir() -> &'static std::path::Path {
+    std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+}
+
+/// Fail if `program` can't be run, instead of reporting the check as passed
+fn require(program: &str) {
+    // Running it without arguments fails, but only spawning it matters
+    if let Err(e) = Command::new(program).output() {
+        panic!("`{}` is needed by this check but can't be run: {}", program, e);
+    }
+}
+
+fn run(command: &mut Command) {
+    let output = command
+        .output()
+        .unwrap_or_else(|e| panic!("Unable to run {:?}: {}", command, e));
+    // Some tools, like `dotnet`, report the errors on stdout
+    assert!(
+        output.status.success(),
+        "{:?} failed:\n{}{}",
+        command,
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+#[ignore = "needs a C++ compiler"]
+#[cfg(feature = "cpp")]
+fn cpp_example_compiles() {
+    require("c++");
+
+    run(Command::new("c++")
+        .args(&["-std=c++17", "-fsyntax-only", "-Wall", "-Wextra", "-I"])
+        .arg(out_dir())
+        .arg(crate_dir().join("cpp/example.cpp")));
+}
+
+#[test]
+#[ignore = "needs the Swift toolchain"]
+#[cfg(feature = "swift")]
+fn swift_source_typechecks() {
+    require("swiftc");
+
+    // The module map of the Swift package expects the header next to it
+    let module_dir = out_dir().join("swift-check");
+    std::fs::create_dir_all(&module_dir).unwrap();
+    std::fs::write(
+        module_dir.join("module.modulemap"),
+        format!(
+            "module CBdkFfi {{\n    header \"{}\"\n    export *\n}}\n",
+            out_dir().join("bdk_ffi.h").display()
+        ),
+    )
+    .unwrap();
+
+    run(Command::new("swiftc")
+        .arg("-typecheck")
+        .arg("-I")
+        .arg(&module_dir)
+        .arg(out_dir().join("bdk_ffi_bitcoin.swift")));
+}
+
+#[test]
+#[ignore = "needs the Go toolchain"]
+#[cfg(feature = "go")]
+fn go_sources_vet() {
+    require("go");
+
+    // `go vet` runs cgo, which finds the C header next to the sources
+    let sources = std::fs::read_dir(out_dir())
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension() == Some("go".as_ref()))
+        .collect::<Vec<_>>();
+    run(Command::new("go")
+        .arg("vet")
+        .args(&sources)
+        .current_dir(out_dir()));
+}
+
+#[test]
+#[ignore = "needs the .NET SDK"]
+#[cfg(feature = "csharp")]
+fn csharp_source_builds() {
+    require("dotnet");
+
+    // Same settings as the `BdkFfi` project, which the source is usually copied to
+    let project_dir = out_dir().join("csharp-check");
+    std::fs::create_dir_all(&project_dir).unwrap();
+    std::fs::write(
+        project_dir.join("BdkFfiCheck.csproj"),
+        format!(
+            "<Project Sdk=\"Microsoft.NET.Sdk\">\n\
+             \x20 <PropertyGroup>\n\
+             \x20   <TargetFramework>net6.0</TargetFramework>\n\
+             \x20   <Nullable>enable</Nullable>\n\
+             \x20   <EnableDefaultCompileItems>false</EnableDefaultCompileItems>\n\
+             \x20 </PropertyGroup>\n\
+             \x20 <ItemGroup>\n\
+             \x20   <Compile Include=\"{}\" />\n\
+             \x20 </ItemGroup>\n\
+             </Project>\n",
+            out_dir().join("bdk_ffi_bitcoin.cs").display()
+        ),
+    )
+    .unwrap();
+
+    run(Command::new("dotnet").arg("build").current_dir(&project_dir));
+}
+
+#[test]
+#[ignore = "needs the Dart SDK"]
+#[cfg(feature = "dart")]
+fn dart_library_analyzes() {
+    require("dart");
+
+    // A copy of the `dart` package, for the dependencies of the library
+    let package_dir = out_dir().join("dart-check");
+    std::fs::create_dir_all(package_dir.join("lib/src")).unwrap();
+    std::fs::copy(
+        crate_dir().join("dart/pubspec.yaml"),
+        package_dir.join("pubspec.yaml"),
+    )
+    .unwrap();
+    std::fs::copy(
+        out_dir().join("bdk_ffi_bitcoin.dart"),
+        package_dir.join("lib/src/bdk_ffi_bitcoin.dart"),
+    )
+    .unwrap();
+
+    run(Command::new("dart").args(["pub", "get"]).current_dir(&package_dir));
+    run(Command::new("dart").arg("analyze").current_dir(&package_dir));
+}
+
+#[test]
+#[ignore = "needs kotlinc and a JDK"]
+#[cfg(feature = "jni")]
+fn kotlin_sources_compile() {
+    require("javac");
+    require("kotlinc");
+
+    let sources_dir = out_dir().join("jni");
+    let classes_dir = out_dir().join("kotlin-check");
+    std::fs::create_dir_all(&classes_dir).unwrap();
+
+    // `Native.java` doesn't depend on the Kotlin side, so it's compiled first
+    run(Command::new("javac")
+        .arg("-d")
+        .arg(&classes_dir)
+        .args(find_sources(&sources_dir, "java")));
+    run(Command::new("kotlinc")
+        .arg("-cp")
+        .arg(&classes_dir)
+        .arg("-d")
+        .arg(&classes_dir)
+        .arg(&sources_dir));
+}
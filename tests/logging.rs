@@ -0,0 +1,97 @@
+//! Forward the records logged by the library to the logger of the language. The logger is global,
+//! so each language has a single test that goes through the whole lifecycle
+
+#[test]
+#[cfg(feature = "c")]
+fn c_logger_receives_records() {
+    use std::ffi::CStr;
+    use std::sync::Mutex;
+
+    use bdk_ffi::langs::c::*;
+
+    static RECORDS: Mutex<Vec<(i32, String, String)>> = Mutex::new(Vec::new());
+
+    unsafe extern "C" fn record(
+        user_data: *mut libc::c_void,
+        level: i32,
+        target: *const libc::c_char,
+        message: *const libc::c_char,
+    ) {
+        let records = &*(user_data as *const Mutex<Vec<(i32, String, String)>>);
+        records.lock().unwrap().push((
+            level,
+            CStr::from_ptr(target).to_string_lossy().into_owned(),
+            CStr::from_ptr(message).to_string_lossy().into_owned(),
+        ));
+    }
+
+    let user_data = &RECORDS as *const _ as *mut libc::c_void;
+    unsafe { install_logger(Some(record), user_data, LOG_LEVEL_INFO) };
+    log::info!(target: "bdk_ffi::tests", "kept");
+    log::debug!(target: "bdk_ffi::tests", "below the level");
+
+    set_log_level(LOG_LEVEL_DEBUG);
+    log::debug!(target: "bdk_ffi::tests", "now kept");
+
+    unsafe { install_logger(None, std::ptr::null_mut(), LOG_LEVEL_TRACE) };
+    log::error!(target: "bdk_ffi::tests", "after the removal");
+
+    let expected = vec![
+        (LOG_LEVEL_INFO, "bdk_ffi::tests".to_string(), "kept".to_string()),
+        (LOG_LEVEL_DEBUG, "bdk_ffi::tests".to_string(), "now kept".to_string()),
+    ];
+    assert_eq!(*RECORDS.lock().unwrap(), expected);
+}
+
+#[test]
+#[cfg(feature = "python")]
+fn python_logger_receives_records() {
+    use pyo3::prelude::*;
+    use pyo3::types::PyModule;
+
+    // The tests embed the interpreter, while the extension module is loaded by it
+    pyo3::prepare_freethreaded_python();
+
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let module = PyModule::new(py, "bitcoin").unwrap();
+    bdk_ffi::langs::python::add_logging(module).unwrap();
+
+    // Keep the records of `bdk_ffi.tests` in a list
+    let records = PyModule::from_code(
+        py,
+        "import logging\n\
+         \n\
+         records = []\n\
+         \n\
+         class Handler(logging.Handler):\n\
+         \x20   def emit(self, record):\n\
+         \x20       records.append((record.name, record.levelno, record.getMessage()))\n\
+         \n\
+         logger = logging.getLogger('bdk_ffi.tests')\n\
+         logger.setLevel(logging.DEBUG)\n\
+         logger.addHandler(Handler())\n",
+        "records.py",
+        "records",
+    )
+    .unwrap();
+
+    module.getattr("install_logger").unwrap().call1((20,)).unwrap();
+    log::info!(target: "bdk_ffi::tests", "kept");
+    log::debug!(target: "bdk_ffi::tests", "below the level");
+
+    module.getattr("set_log_level").unwrap().call1((10,)).unwrap();
+    log::debug!(target: "bdk_ffi::tests", "now kept");
+
+    module.getattr("set_log_level").unwrap().call1((50,)).unwrap();
+    log::error!(target: "bdk_ffi::tests", "disabled");
+
+    let records: Vec<(String, i32, String)> =
+        records.getattr("records").unwrap().extract().unwrap();
+    let expected = vec![
+        ("bdk_ffi.tests".to_string(), 20, "kept".to_string()),
+        ("bdk_ffi.tests".to_string(), 10, "now kept".to_string()),
+    ];
+    assert_eq!(records, expected);
+}
@@ -0,0 +1,34 @@
+const assert = require("assert");
+
+const { Address, Network, Script } = require("./pkg/bdk_ffi");
+
+const address = new Address("32qQDeCM67ZyLT1EYvv9HHTG9bXHLVK1gf");
+console.log(address.toString());
+
+// Fields are copied out of the address
+const script = address.script;
+console.log(script.toHex());
+console.log(script.toBytes());
+
+const joined = script.add(Script.fromBytes(Uint8Array.of(0x51)));
+console.log(joined.asm());
+
+try {
+    new Network("not a network");
+    assert.fail();
+} catch (e) {
+    assert(e instanceof Error);
+    assert.strictEqual(e.name, "BitcoinError");
+    console.log(`caught ${e.name}: ${e.message}`);
+}
+
+try {
+    address.script = Script.empty();
+    assert.fail();
+} catch (e) {
+    assert.strictEqual(e.name, "ValidationError");
+    console.log(`caught ${e.name}: ${e.message}`);
+}
+
+const fromScript = Address.fromScript(script, Network.testnet());
+console.log(fromScript.toString());